@@ -0,0 +1,473 @@
+//! Post-game blunder analysis: which placements lost the game.
+//!
+//! A [`RecordedGame`] is everything needed to replay one seat bit-for-bit —
+//! the [`EngineConfig`], the engine seed, the per-slice [`InputFrame`]s, and
+//! the attack that was queued against the seat (versus) — because the engine is
+//! a pure function of those. The [`Analyst`] re-simulates it through a fresh
+//! [`Engine`] and, at every lock, searches the **pre-placement** decision state
+//! (the state at the piece's spawn, before any hold) with a full-strength
+//! [`BeamPlanner`]. The planner's per-root back-ups
+//! ([`BeamPlanner::root_scores`]) price every reachable placement on one scale,
+//! so the placement the player actually locked is looked up among them and the
+//! gap to the best is the cost of the move, in evaluator units.
+//!
+//! Identifying the played placement needs no engine instrumentation: each
+//! candidate is locked onto the decision board and compared with the board the
+//! engine actually produced (un-shifting any garbage that rose on the same
+//! lock). Equal boards from different paths (a spin vs a plain drop into the
+//! same cells) take the best-scoring match — the player gets the benefit of the
+//! doubt. A lock movegen cannot reproduce is reported [`Verdict::Unrated`]
+//! rather than guessed at.
+//!
+//! The pass is incremental ([`Analyst::advance`] takes a lock budget) so a host
+//! can spread the search across frames behind a result screen;
+//! [`analyze_game`] is the one-shot form for headless callers.
+
+use crate::ai::eval::Evaluator;
+use crate::ai::search::{BeamPlanner, SearchBudget, think_to_completion};
+use crate::ai::state::{SearchState, board_from_snapshot};
+use crate::engine::{
+    ActivePiece, BitBoard, Engine, EngineConfig, EngineEvent, EngineSnapshot, InputFrame, PieceType,
+};
+
+/// One seat's game as a deterministic replay: rules, deal, and every input.
+#[derive(Clone, Debug, Default)]
+pub struct RecordedGame {
+    /// The engine rules the seat ran under.
+    pub config: EngineConfig,
+    /// The engine seed (the seven-bag deal and the garbage hole stream).
+    pub seed: u64,
+    /// The frame fed to [`Engine::step`], one per simulation slice.
+    pub inputs: Vec<InputFrame>,
+    /// Attack queued against the seat, in arrival order.
+    pub garbage: Vec<IncomingGarbage>,
+}
+
+/// An attack of `lines` queued via [`Engine::queue_garbage`] right after the
+/// step of frame `after_frame` (the session routes attack between slices).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct IncomingGarbage {
+    pub after_frame: usize,
+    pub lines: u32,
+}
+
+impl RecordedGame {
+    /// An empty recording for a seat about to start under `config` / `seed`.
+    pub fn new(config: EngineConfig, seed: u64) -> Self {
+        Self {
+            config,
+            seed,
+            inputs: Vec::new(),
+            garbage: Vec::new(),
+        }
+    }
+
+    /// Append the frame the seat's engine was just stepped with.
+    pub fn push_frame(&mut self, input: InputFrame) {
+        self.inputs.push(input);
+    }
+
+    /// Record `lines` of attack queued after the most recent frame. A zero
+    /// attack is not recorded (queueing it is a no-op).
+    pub fn push_garbage(&mut self, lines: u32) {
+        if lines == 0 {
+            return;
+        }
+        self.garbage.push(IncomingGarbage {
+            after_frame: self.inputs.len().saturating_sub(1),
+            lines,
+        });
+    }
+}
+
+/// The analysis operating point. The defaults are the "full-strength" review
+/// search — wider and deeper than any in-game bot, since the pass runs once,
+/// after the game, with no frame budget to honour.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AnalysisConfig {
+    /// Beam width of the review search.
+    pub beam_width: usize,
+    /// Ply depth of the review search.
+    pub depth: u8,
+    /// A gap of at least this many evaluator units is an [`Verdict::Inaccuracy`].
+    pub inaccuracy_gap: i32,
+    /// A gap of at least this many evaluator units is a [`Verdict::Blunder`].
+    pub blunder_gap: i32,
+}
+
+impl Default for AnalysisConfig {
+    fn default() -> Self {
+        // Thresholds sit on the CC2 board scale, read off this search reviewing
+        // the greedy DT-20 bot (median gap ~100, 90th percentile ~700-800): an
+        // inaccuracy is worse than a typical greedy slip, a blunder is that
+        // bot's worst tenth. The search reviewing its own play scores 0 gaps.
+        Self {
+            beam_width: 32,
+            depth: 3,
+            inaccuracy_gap: 200,
+            blunder_gap: 800,
+        }
+    }
+}
+
+/// How costly a played placement was, judged by its gap to the best.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    /// The played placement is (tied for) the search's best.
+    Best,
+    /// Worse than the best, but under the inaccuracy threshold.
+    Good,
+    Inaccuracy,
+    Blunder,
+    /// The played lock is not among the search's reachable placements (a
+    /// finesse the movegen does not model), so it cannot be priced.
+    Unrated,
+}
+
+/// The review of one lock.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlacementReview {
+    /// 0-based index of the lock in the game.
+    pub piece_index: usize,
+    /// Index of the input frame that locked the piece.
+    pub frame: usize,
+    /// Game time at the lock (the sum of the frames' `dt_seconds`).
+    pub elapsed_seconds: f32,
+    /// The piece that locked (after any hold swap).
+    pub piece_type: PieceType,
+    /// Backed-up score of the best placement.
+    pub best_score: i32,
+    /// Backed-up score of the played placement, `None` when unrated.
+    pub played_score: Option<i32>,
+    pub verdict: Verdict,
+}
+
+impl PlacementReview {
+    /// What the played placement cost against the best (`0` = best), or `None`
+    /// when unrated.
+    pub fn gap(&self) -> Option<i32> {
+        self.played_score
+            .map(|played| self.best_score.saturating_sub(played).max(0))
+    }
+}
+
+/// The finished timeline: one review per lock, in play order.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct GameAnalysis {
+    pub reviews: Vec<PlacementReview>,
+}
+
+impl GameAnalysis {
+    /// The locks judged [`Verdict::Inaccuracy`].
+    pub fn inaccuracies(&self) -> impl Iterator<Item = &PlacementReview> {
+        self.with_verdict(Verdict::Inaccuracy)
+    }
+
+    /// The locks judged [`Verdict::Blunder`].
+    pub fn blunders(&self) -> impl Iterator<Item = &PlacementReview> {
+        self.with_verdict(Verdict::Blunder)
+    }
+
+    /// The single most expensive rated lock (the first on ties), if any.
+    pub fn worst(&self) -> Option<&PlacementReview> {
+        self.reviews.iter().filter(|r| r.gap().is_some()).fold(
+            None,
+            |worst: Option<&PlacementReview>, r| match worst {
+                Some(w) if w.gap() >= r.gap() => Some(w),
+                _ => Some(r),
+            },
+        )
+    }
+
+    fn with_verdict(&self, verdict: Verdict) -> impl Iterator<Item = &PlacementReview> {
+        self.reviews.iter().filter(move |r| r.verdict == verdict)
+    }
+}
+
+/// The incremental re-simulation + review pass over one [`RecordedGame`].
+pub struct Analyst {
+    engine: Engine,
+    game: RecordedGame,
+    /// Next input frame to replay.
+    cursor: usize,
+    /// Next entry of `game.garbage` to queue.
+    garbage_cursor: usize,
+    elapsed_seconds: f32,
+    /// The current piece's decision state (captured at its spawn), `None` once
+    /// the game is over.
+    decision: Option<SearchState>,
+    /// Whether the current piece was swapped through hold.
+    held: bool,
+    planner: BeamPlanner,
+    eval: Box<dyn Evaluator>,
+    config: AnalysisConfig,
+    analysis: GameAnalysis,
+}
+
+impl Analyst {
+    /// Start a pass over `game`, pricing placements with `eval`.
+    pub fn new(game: RecordedGame, eval: Box<dyn Evaluator>, config: AnalysisConfig) -> Self {
+        let engine = Engine::new(game.config.clone(), game.seed);
+        let decision = SearchState::at_next_spawn(&engine.snapshot());
+        Self {
+            engine,
+            game,
+            cursor: 0,
+            garbage_cursor: 0,
+            elapsed_seconds: 0.0,
+            decision,
+            held: false,
+            planner: BeamPlanner::new(config.beam_width),
+            eval,
+            config,
+            analysis: GameAnalysis::default(),
+        }
+    }
+
+    /// Replay frames until `max_locks` more locks have been reviewed or the
+    /// recording ends. Returns `true` once the whole game has been analysed.
+    pub fn advance(&mut self, max_locks: usize) -> bool {
+        let mut reviewed = 0;
+        while reviewed < max_locks && !self.is_finished() {
+            let frame = self.cursor;
+            let input = self.game.inputs[frame].clone();
+            self.elapsed_seconds += input.dt_seconds.max(0.0);
+            let events = self.engine.step(input);
+            self.cursor += 1;
+            while let Some(incoming) = self.game.garbage.get(self.garbage_cursor)
+                && incoming.after_frame <= frame
+            {
+                self.engine.queue_garbage(incoming.lines);
+                self.garbage_cursor += 1;
+            }
+
+            self.held |= events.iter().any(|e| matches!(e, EngineEvent::Held { .. }));
+            let locked = events.iter().find_map(|e| match e {
+                EngineEvent::Locked { piece_type, .. } => Some(*piece_type),
+                _ => None,
+            });
+            let Some(piece_type) = locked else {
+                continue;
+            };
+            let risen = events
+                .iter()
+                .map(|e| match e {
+                    EngineEvent::GarbageInserted { lines } => *lines,
+                    _ => 0,
+                })
+                .sum();
+            let post = self.engine.snapshot();
+            if let Some(state) = self.decision.take() {
+                let review = self.review(&state, piece_type, &post, risen, frame);
+                self.analysis.reviews.push(review);
+                reviewed += 1;
+            }
+            // The engine deals the next piece inside the locking step, so the
+            // post-lock snapshot is already the next decision state.
+            self.decision = SearchState::from_snapshot(&post);
+            self.held = false;
+        }
+        self.is_finished()
+    }
+
+    /// Whether every recorded frame has been replayed.
+    pub fn is_finished(&self) -> bool {
+        self.cursor >= self.game.inputs.len()
+    }
+
+    /// Replay progress in `0.0..=1.0` (by frame), for a progress readout.
+    pub fn progress(&self) -> f32 {
+        if self.game.inputs.is_empty() {
+            1.0
+        } else {
+            self.cursor as f32 / self.game.inputs.len() as f32
+        }
+    }
+
+    /// The reviews so far (the whole timeline once finished).
+    pub fn analysis(&self) -> &GameAnalysis {
+        &self.analysis
+    }
+
+    /// Consume the pass, yielding the timeline reviewed so far.
+    pub fn into_analysis(self) -> GameAnalysis {
+        self.analysis
+    }
+
+    /// Search `state` and price the placement the engine actually locked.
+    fn review(
+        &mut self,
+        state: &SearchState,
+        piece_type: PieceType,
+        post: &EngineSnapshot,
+        risen: u32,
+        frame: usize,
+    ) -> PlacementReview {
+        let budget = SearchBudget::beam(self.config.depth);
+        think_to_completion(&mut self.planner, state, self.eval.as_ref(), budget);
+        let post_board = board_from_snapshot(post);
+        let mut best_score = i32::MIN;
+        let mut played_score = None;
+        for (placement, score) in self.planner.root_scores() {
+            best_score = best_score.max(score);
+            let played = placement.used_hold == self.held
+                && placement.piece.piece_type() == piece_type
+                && lock_matches(state.board, &placement.piece, &post_board, risen);
+            if played {
+                played_score = Some(played_score.map_or(score, |s: i32| s.max(score)));
+            }
+        }
+        let gap = played_score.map(|played| best_score.saturating_sub(played));
+        let verdict = match gap {
+            None => Verdict::Unrated,
+            Some(gap) if gap <= 0 => Verdict::Best,
+            Some(gap) if gap >= self.config.blunder_gap => Verdict::Blunder,
+            Some(gap) if gap >= self.config.inaccuracy_gap => Verdict::Inaccuracy,
+            Some(_) => Verdict::Good,
+        };
+        PlacementReview {
+            piece_index: self.analysis.reviews.len(),
+            frame,
+            elapsed_seconds: self.elapsed_seconds,
+            piece_type,
+            best_score,
+            played_score,
+            verdict,
+        }
+    }
+}
+
+/// Whether locking `piece` on `board` yields `post` once the `risen` garbage
+/// rows the same lock pushed in underneath are shifted back out.
+fn lock_matches(mut board: BitBoard, piece: &ActivePiece, post: &BitBoard, risen: u32) -> bool {
+    board.lock_piece(piece);
+    board
+        .columns()
+        .iter()
+        .zip(post.columns())
+        .all(|(&child, &actual)| actual.checked_shr(risen).unwrap_or(0) == child)
+}
+
+/// Analyse a whole recorded game in one blocking call (the headless form of
+/// [`Analyst`]).
+pub fn analyze_game(
+    game: &RecordedGame,
+    eval: Box<dyn Evaluator>,
+    config: AnalysisConfig,
+) -> GameAnalysis {
+    let mut analyst = Analyst::new(game.clone(), eval, config);
+    analyst.advance(usize::MAX);
+    analyst.into_analysis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{AiController, Cc2Evaluator, Handicap};
+    use crate::player::PlayerController;
+
+    /// A short game played by a bot, recorded the way a host records a seat.
+    fn recorded_bot_game(handicap: Handicap, frames: usize) -> RecordedGame {
+        let config = EngineConfig::default();
+        let mut game = RecordedGame::new(config.clone(), 7);
+        let mut engine = Engine::new(config, 7);
+        let mut bot = AiController::new(handicap, 7);
+        for _ in 0..frames {
+            let frame = bot.poll(&engine.snapshot());
+            engine.step(frame.clone());
+            game.push_frame(frame);
+            if engine.snapshot().game_over.is_some() {
+                break;
+            }
+        }
+        game
+    }
+
+    fn quick() -> AnalysisConfig {
+        AnalysisConfig {
+            beam_width: 8,
+            depth: 2,
+            ..AnalysisConfig::default()
+        }
+    }
+
+    #[test]
+    fn every_lock_gets_one_review_in_play_order() {
+        let game = recorded_bot_game(Handicap::perfect(), 600);
+        let analysis = analyze_game(&game, Box::new(Cc2Evaluator::default()), quick());
+        assert!(analysis.reviews.len() >= 5, "the bot locks pieces");
+        for (i, review) in analysis.reviews.iter().enumerate() {
+            assert_eq!(review.piece_index, i);
+        }
+        assert!(
+            analysis.reviews.windows(2).all(|w| w[0].frame < w[1].frame),
+            "reviews follow the replay"
+        );
+    }
+
+    #[test]
+    fn played_placements_are_found_among_the_search_roots() {
+        // The bot only plays movegen placements, so every lock is priceable.
+        let game = recorded_bot_game(Handicap::perfect(), 600);
+        let analysis = analyze_game(&game, Box::new(Cc2Evaluator::default()), quick());
+        assert!(
+            analysis
+                .reviews
+                .iter()
+                .all(|r| r.verdict != Verdict::Unrated),
+            "{:?}",
+            analysis.reviews
+        );
+    }
+
+    #[test]
+    fn idling_into_the_stack_is_judged_worse_than_best() {
+        // Hard-dropping every piece at spawn builds a tower in the middle —
+        // the analysis must flag it as costly.
+        let config = EngineConfig::default();
+        let mut game = RecordedGame::new(config, 3);
+        for i in 0..200 {
+            game.push_frame(InputFrame {
+                dt_seconds: 1.0 / 60.0,
+                hard_drop: i % 2 == 1,
+                ..InputFrame::default()
+            });
+        }
+        let analysis = analyze_game(&game, Box::new(Cc2Evaluator::default()), quick());
+        assert!(!analysis.reviews.is_empty());
+        assert!(analysis.blunders().count() + analysis.inaccuracies().count() > 0);
+        assert!(analysis.worst().and_then(PlacementReview::gap).unwrap() > 0);
+    }
+
+    #[test]
+    fn incremental_advance_matches_the_one_shot_pass() {
+        let game = recorded_bot_game(Handicap::default(), 400);
+        let whole = analyze_game(&game, Box::new(Cc2Evaluator::default()), quick());
+        let mut analyst = Analyst::new(game, Box::new(Cc2Evaluator::default()), quick());
+        while !analyst.advance(1) {}
+        assert_eq!(analyst.into_analysis(), whole);
+    }
+
+    #[test]
+    fn recorded_garbage_is_replayed_after_its_frame() {
+        let mut game = RecordedGame::new(EngineConfig::default(), 9);
+        game.push_garbage(4); // nothing recorded yet: lands after frame 0
+        game.push_frame(InputFrame::default());
+        game.push_frame(InputFrame::default());
+        game.push_garbage(0); // no-op
+        game.push_garbage(2);
+        assert_eq!(
+            game.garbage,
+            vec![
+                IncomingGarbage {
+                    after_frame: 0,
+                    lines: 4
+                },
+                IncomingGarbage {
+                    after_frame: 1,
+                    lines: 2
+                },
+            ]
+        );
+    }
+}
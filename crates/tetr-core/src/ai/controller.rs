@@ -10,9 +10,13 @@
 //! shell drives a greedy search, a future beam, or a neural policy unchanged. It
 //! owns the two concerns a brain has no opinion on:
 //!
-//! - **When** to act — a [`Handicap::reaction`] delay before playing a new piece.
+//! - **When** to act — a [`Handicap::reaction`] delay before playing a new piece,
+//!   and a [`Handicap::max_pps`] ceiling on how often it may commit to one.
 //! - **How** to act — rendering the policy's chosen placement to engine input, one
-//!   pulse per poll.
+//!   pulse per poll, occasionally slipping onto a neighbouring placement
+//!   ([`Handicap::misdrop`]).
+//! - **What it sees** — with [`Handicap::garbage_blind`] the snapshot's pending
+//!   queue is stripped before anything reads it.
 //!
 //! The *other* half of the handicap — how *perfectly* to play — lives in the policy,
 //! not here (a search softmax-samples a near-best, a net raises its temperature).
@@ -27,6 +31,11 @@
 //! emitting a neutral frame (`dt` only) when the queue is empty or it is still
 //! "reacting". This matches how the keyboard controller feeds the engine.
 //!
+//! [`with_human_inputs`](AiController::with_human_inputs) swaps that exact
+//! renderer for [`placement_to_human_inputs`]: the same path keyed through DAS
+//! with jittered key intervals and gravity running, so the bot moves at a
+//! person's pace rather than in zero game time.
+//!
 //! # The poll state machine
 //!
 //! Each [`poll`](AiController::poll):
@@ -43,17 +52,20 @@
 //!    bot pays anyway.
 //! 3. **React.** Accumulate the poll's `dt` into a reaction timer; while it is
 //!    below [`Handicap::reaction`], emit neutral frames. The buffered decision is
-//!    not *applied* until the delay elapses.
-//! 4. **Apply.** Once the reaction elapses and a decision is buffered, render it
-//!    to frames and enqueue them.
+//!    not *applied* until the delay elapses — nor until the pace cap allows
+//!    another commitment.
+//! 4. **Apply.** Once the reaction elapses and a decision is buffered, roll the
+//!    misdrop, render the placement to frames and enqueue them.
 //! 5. **Emit.** Pop the next queued frame, or a neutral frame if none.
 //!
 //! # Determinism
 //!
-//! The shell adds no randomness and no clock — it only integrates the poll `dt`
-//! (deterministic given the poll cadence). All AI randomness lives in the policy's
-//! own seeded RNG. So a fixed `(engine seed, ai seed, handicap)` reproduces an
-//! identical game every run — the determinism the headless benchmarks rely on.
+//! The shell adds no clock — it only integrates the poll `dt` (deterministic
+//! given the poll cadence). Placement randomness lives in the policy's own seeded
+//! RNG; the only randomness the shell owns is the misdrop roll and the human-like
+//! renderer's key jitter, both drawn from the shell's RNG seeded at construction. So a
+//! fixed `(engine seed, ai seed, handicap)` reproduces an identical game every run —
+//! the determinism the headless benchmarks rely on.
 //!
 //! # No Bevy here
 //!
@@ -64,13 +76,18 @@
 use core::time::Duration;
 use std::collections::VecDeque;
 
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+
 use crate::ai::eval::{Cc2Evaluator, Cc2Weights};
 use crate::ai::handicap::Handicap;
+use crate::ai::humanize::{HumanInputConfig, placement_to_human_inputs};
+use crate::ai::movegen::Placement;
 use crate::ai::plan::placement_to_inputs;
 use crate::ai::policy::{Decision, Policy, SearchPolicy};
 use crate::ai::runner::budgeted::DEFAULT_BUDGET;
 use crate::ai::runner::{BudgetedRunner, DecisionRunner, MonotonicClock, SlicedRunner, SyncRunner};
-use crate::ai::search::{BestFirstPlanner, SearchBudget};
+use crate::ai::search::{BestFirstPlanner, SearchBudget, hold_placements};
 use crate::ai::state::SearchState;
 use crate::engine::{EngineSnapshot, InputFrame, PieceType};
 use crate::player::PlayerController;
@@ -95,6 +112,11 @@ const ATTACK_NODE_BUDGET: u32 = 192;
 /// visible queue, not width.
 const ATTACK_DEPTH: u8 = 6;
 
+/// Salt folded into the seed of the shell's misdrop RNG, so a controller built
+/// with one seed for both layers never draws its misdrops in lockstep with the
+/// policy's imperfection rolls.
+const MISDROP_SALT: u64 = 0x0051_1D09;
+
 /// An AI [`PlayerController`]: a model-agnostic shell that drives a
 /// [`Policy`] (via a [`DecisionRunner`]) and feeds its chosen
 /// placement to the engine one pulse per poll, with a reaction-delay handicap.
@@ -117,6 +139,19 @@ pub struct AiController {
     /// A decision the runner finished while the reaction window was still
     /// running, held until the controller may act on it.
     ready: Option<Decision>,
+    /// The human-like renderer's config, when the bot keys its placements at
+    /// a person's pace; `None` renders exactly.
+    human: Option<HumanInputConfig>,
+    /// Minimum seconds between two committed placements ([`Handicap::max_pps`]).
+    min_piece_seconds: f32,
+    /// Time since the last placement was committed (integrates every poll).
+    since_commit: f32,
+    /// Chance of executing a neighbouring placement ([`Handicap::misdrop`]).
+    misdrop: f32,
+    /// Strip the pending-garbage queue before reading a snapshot.
+    garbage_blind: bool,
+    /// The shell's RNG: misdrop rolls and human key jitter.
+    rng: StdRng,
 }
 
 /// A cheap fingerprint of "which piece, on which board" so the controller can tell
@@ -174,7 +209,7 @@ impl AiController {
     /// a synchronous runner, with the given `handicap` and RNG `seed`.
     pub fn new(handicap: Handicap, seed: u64) -> Self {
         let policy = SearchPolicy::greedy(handicap.imperfection, seed);
-        Self::with_policy(Box::new(policy), handicap.reaction)
+        Self::with_policy(Box::new(policy), handicap.reaction).with_handicap(handicap, seed)
     }
 
     /// A controller with the **default** handicap (a beatable opponent) and the
@@ -205,10 +240,11 @@ impl AiController {
             Box::new(SlicedRunner::new(Box::new(policy))),
             handicap.reaction,
         )
+        .with_handicap(handicap, seed)
     }
 
     /// The interactive-catalog construction: a (mind, evaluator, budget) triple
-    /// under the **default** (beatable) handicap and AI seed, in the
+    /// under the seat's `handicap` and the default AI seed, in the
     /// **time-budgeted** cooperative venue ([`BudgetedRunner`]). One home for the
     /// menu-bot convention so no game surface can fork the operating conventions from
     /// the core's.
@@ -226,14 +262,15 @@ impl AiController {
         mind: Box<dyn crate::ai::Mind>,
         eval: Box<dyn crate::ai::Evaluator>,
         budget: SearchBudget,
+        handicap: Handicap,
         clock: Box<dyn MonotonicClock>,
     ) -> Self {
-        let handicap = Handicap::default();
         let policy = SearchPolicy::new(mind, eval, budget, handicap.imperfection, DEFAULT_AI_SEED);
         Self::with_runner(
             Box::new(BudgetedRunner::new(Box::new(policy), DEFAULT_BUDGET, clock)),
             handicap.reaction,
         )
+        .with_handicap(handicap, DEFAULT_AI_SEED)
     }
 
     /// [`interactive`](Self::interactive) with an explicit handicap and venue
@@ -258,6 +295,7 @@ impl AiController {
             Box::new(SlicedRunner::with_quantum(Box::new(policy), quantum)),
             handicap.reaction,
         )
+        .with_handicap(handicap, DEFAULT_AI_SEED)
     }
 
     /// A controller around an explicit [`Policy`], wrapped in the **blocking**
@@ -266,8 +304,9 @@ impl AiController {
     /// no frame exists to hitch and exact full-budget decisions per poll are the
     /// contract. Interactive surfaces wrap their policy in a [`SlicedRunner`]
     /// via [`with_runner`](Self::with_runner) instead (as [`attack`](Self::attack)
-    /// and the game's model registry do). `reaction` is the shell-level handicap;
-    /// the policy carries its own imperfection + RNG.
+    /// and the game's model registry do). `reaction` is the only shell dial set
+    /// here (the rest via [`with_handicap`](Self::with_handicap)); the policy
+    /// carries its own imperfection + RNG.
     pub fn with_policy(policy: Box<dyn Policy>, reaction: Duration) -> Self {
         Self::with_runner(Box::new(SyncRunner::new(policy)), reaction)
     }
@@ -284,7 +323,38 @@ impl AiController {
             think_elapsed: 0.0,
             submitted: false,
             ready: None,
+            human: None,
+            min_piece_seconds: 0.0,
+            since_commit: f32::INFINITY,
+            misdrop: 0.0,
+            garbage_blind: false,
+            rng: StdRng::seed_from_u64(DEFAULT_AI_SEED ^ MISDROP_SALT),
+        }
+    }
+
+    /// Apply the shell half of `handicap` — reaction, pace cap, misdrop rate and
+    /// garbage blindness — seeding the shell's RNG from `seed`. The policy half
+    /// ([`Handicap::imperfection`]) is the policy's, fixed when it was built.
+    pub fn with_handicap(mut self, handicap: Handicap, seed: u64) -> Self {
+        self.reaction = handicap.reaction;
+        self.min_piece_seconds = handicap.min_piece_seconds();
+        self.misdrop = handicap.misdrop.clamp(0.0, 1.0);
+        if self.misdrop.is_nan() {
+            self.misdrop = 0.0;
         }
+        self.garbage_blind = handicap.garbage_blind;
+        self.rng = StdRng::seed_from_u64(seed ^ MISDROP_SALT);
+        self
+    }
+
+    /// Key every placement the way a person would — DAS under `config`, key
+    /// intervals jittered from the shell's seeded RNG, gravity running through
+    /// the maneuver — instead of the exact zero-time pulses. Bounds the bot's
+    /// pieces-per-second by human mechanics for exhibition play and fair
+    /// handicaps.
+    pub fn with_human_inputs(mut self, config: HumanInputConfig) -> Self {
+        self.human = Some(config);
+        self
     }
 
     /// Begin planning for a freshly seen piece: cancel the in-flight decision, reset
@@ -306,6 +376,7 @@ impl AiController {
             // leave the plan empty so we emit neutral frames.
             Decision::None => {}
             Decision::Place(placement) => {
+                let placement = self.maybe_misdrop(placement, obs);
                 // A plan that begins with a hold swaps the active piece one frame
                 // from now. That swap is the controller's own doing, not a new
                 // planning situation: retarget the expected signature to the
@@ -321,15 +392,76 @@ impl AiController {
                 }
                 // Render against the board the maneuver happens on, from the active
                 // piece's current pose — the inputs `placement_to_inputs` round-trips.
-                let frames = placement_to_inputs(&obs.board.to_array2d(), &obs.active, &placement);
+                let board = obs.board.to_array2d();
+                let frames = match self.human {
+                    Some(config) => placement_to_human_inputs(
+                        &board,
+                        &obs.active,
+                        &placement,
+                        &config,
+                        &mut self.rng,
+                    ),
+                    None => placement_to_inputs(&board, &obs.active, &placement),
+                };
                 self.plan = frames.into();
             }
         }
     }
 }
 
+impl AiController {
+    /// Roll the misdrop: with probability [`Handicap::misdrop`], swap the chosen
+    /// placement for a legal neighbour — same piece, same hold choice, and either
+    /// the same rotation one column over or the same column in another rotation.
+    /// A placement with no legal neighbour is kept.
+    fn maybe_misdrop(&mut self, chosen: Placement, obs: &SearchState) -> Placement {
+        if self.misdrop <= 0.0 || !self.rng.random_bool(f64::from(self.misdrop)) {
+            return chosen;
+        }
+        // A symmetric piece turned in place can land on the very same cells;
+        // that is no slip at all, so neighbours must differ in footprint.
+        let footprint = |p: &Placement| {
+            let (ox, oy) = p.origin();
+            let mut cells = p.piece.piece().cells().map(|(x, y)| (x + ox, y + oy));
+            cells.sort();
+            cells
+        };
+        let intended = footprint(&chosen);
+        let (column, _) = chosen.origin();
+        let mut neighbours: Vec<Placement> = hold_placements(obs)
+            .into_iter()
+            .filter(|p| {
+                if p.used_hold != chosen.used_hold || p.piece_type() != chosen.piece_type() {
+                    return false;
+                }
+                let shift = (p.origin().0 - column).abs();
+                let turned = p.rotation() != chosen.rotation();
+                ((shift == 1 && !turned) || (shift == 0 && turned)) && footprint(p) != intended
+            })
+            .collect();
+        if neighbours.is_empty() {
+            return chosen;
+        }
+        let pick = self.rng.random_range(0..neighbours.len());
+        neighbours.swap_remove(pick)
+    }
+}
+
 impl PlayerController for AiController {
     fn poll(&mut self, snapshot: &EngineSnapshot) -> InputFrame {
+        // (0) Pace and sight: every poll is one slice of wall time toward the
+        // PPS cap, and a garbage-blind bot never sees its pending queue.
+        self.since_commit += NOMINAL_DT;
+        let blinded;
+        let snapshot = if self.garbage_blind && !snapshot.pending_garbage.is_empty() {
+            let mut stripped = snapshot.clone();
+            stripped.pending_garbage.clear();
+            blinded = stripped;
+            &blinded
+        } else {
+            snapshot
+        };
+
         // (1) No active piece (pre-spawn / game over): nothing to decide; idle.
         let Some(signature) = PieceSignature::of(snapshot) else {
             return neutral();
@@ -342,7 +474,8 @@ impl PlayerController for AiController {
 
         // If we still have queued frames for the current piece, keep draining them
         // (don't re-decide mid-maneuver). One pulse per poll. Maneuver frames carry
-        // their own `dt == 0` so positioning advances no gravity — emit as-is.
+        // their own `dt` (zero for the exact renderer, so positioning advances no
+        // gravity; the nominal slice for the human one) — emit as-is.
         if let Some(frame) = self.plan.pop_front() {
             return frame;
         }
@@ -371,9 +504,16 @@ impl PlayerController for AiController {
             return neutral(); // still "reacting"
         }
 
+        // (3b) The pace cap: no new commitment until `1 / max_pps` has passed
+        // since the last one.
+        if self.since_commit < self.min_piece_seconds {
+            return neutral();
+        }
+
         // (4) Reaction elapsed: apply the buffered decision and emit its first
         // maneuver frame.
         if let Some(decision) = self.ready.take() {
+            self.since_commit = 0.0;
             self.apply_decision(decision, &obs);
             if let Some(frame) = self.plan.pop_front() {
                 return frame;
@@ -388,9 +528,10 @@ impl PlayerController for AiController {
 /// Nominal per-poll `dt` for the controller's reaction-timer integration: the fixed
 /// sim slice at `SIM_HZ` (60 Hz). The driver steps the engine at that rate, so
 /// integrating the reaction delay in these units paces it in real seconds. Maneuver
-/// frames carry their *own* `dt == 0` (gravity-free positioning) and are emitted
-/// unchanged; only neutral "reacting"/idle frames advance time.
-const NOMINAL_DT: f32 = 1.0 / 60.0;
+/// frames carry their *own* `dt` and are emitted unchanged: `0` from the exact
+/// renderer (gravity-free positioning, so only neutral "reacting"/idle frames
+/// advance time), this slice from the human-like one.
+pub(crate) const NOMINAL_DT: f32 = 1.0 / 60.0;
 
 /// A neutral frame: advance one sim slice of time, press nothing. Emitted while
 /// reacting or idle so gravity and the lock timer keep ticking between maneuvers.
@@ -568,6 +709,150 @@ mod tests {
         );
     }
 
+    #[test]
+    fn human_inputs_cap_the_pace_and_still_play_well() {
+        // Same brain, same reaction: keyed like a person the bot places markedly
+        // fewer pieces in a minute, stays under a human ceiling, and still
+        // survives and clears — the gravity running through each maneuver must
+        // not knock its placements off.
+        const MINUTE: usize = 3_600;
+        let mut exact = AiController::new(Handicap::perfect(), DEFAULT_AI_SEED);
+        let mut human = AiController::new(Handicap::perfect(), DEFAULT_AI_SEED)
+            .with_human_inputs(HumanInputConfig::default());
+        let (exact_locks, _, _) = play(&mut exact, 7, MINUTE);
+        let (human_locks, lines, topped_out) = play(&mut human, 7, MINUTE);
+
+        assert!(!topped_out, "the human-paced bot must survive a minute");
+        assert!(lines > 0, "and clear lines ({human_locks} placed)");
+        assert!(
+            human_locks * 2 < exact_locks,
+            "human keying must slow the bot (exact {exact_locks}, human {human_locks})"
+        );
+        let pps = human_locks as f32 / 60.0;
+        assert!(
+            (0.5..4.0).contains(&pps),
+            "a plausible human pace, got {pps:.2} PPS"
+        );
+    }
+
+    #[test]
+    fn human_key_jitter_comes_from_the_shell_rng() {
+        // Same flawless brain; only the seed `with_handicap` gives the shell's
+        // RNG differs. The key timing follows it, and replays from it.
+        let frames = |seed: u64| {
+            let mut bot = AiController::new(Handicap::perfect(), DEFAULT_AI_SEED)
+                .with_handicap(Handicap::perfect(), seed)
+                .with_human_inputs(HumanInputConfig::default());
+            let mut engine = Engine::new(EngineConfig::default(), 7);
+            (0..600)
+                .map(|_| {
+                    let frame = bot.poll(&engine.snapshot());
+                    engine.step(frame.clone());
+                    frame
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(frames(1), frames(1), "a fixed seed replays its keying");
+        assert_ne!(frames(1), frames(2), "the jitter is the shell's to draw");
+    }
+
+    #[test]
+    fn the_pps_cap_bounds_the_pace() {
+        const FRAMES: usize = 3_600;
+        let capped = Handicap {
+            max_pps: Some(1.0),
+            ..Handicap::perfect()
+        };
+        let (free_locks, _, _) = play(
+            &mut AiController::new(Handicap::perfect(), DEFAULT_AI_SEED),
+            7,
+            FRAMES,
+        );
+        let (capped_locks, _, topped_out) =
+            play(&mut AiController::new(capped, DEFAULT_AI_SEED), 7, FRAMES);
+        assert!(!topped_out);
+        assert!(
+            capped_locks <= 61,
+            "1 PPS over a minute allows at most 61 pieces, placed {capped_locks}"
+        );
+        assert!(
+            capped_locks >= 50 && free_locks > capped_locks * 2,
+            "the cap binds without stalling (free {free_locks}, capped {capped_locks})"
+        );
+    }
+
+    /// The cells the first placement locks, on a fresh engine of `seed`.
+    fn first_lock(controller: &mut AiController, seed: u64) -> Vec<(isize, isize)> {
+        let mut engine = Engine::new(EngineConfig::default(), seed);
+        for _ in 0..600 {
+            let snapshot = engine.snapshot();
+            let before = snapshot.board_cells.len();
+            engine.step(controller.poll(&snapshot));
+            let after = engine.snapshot();
+            if after.board_cells.len() != before {
+                let mut cells: Vec<_> = after.board_cells.iter().map(|c| (c.x, c.y)).collect();
+                cells.sort();
+                return cells;
+            }
+        }
+        panic!("no lock within 600 frames");
+    }
+
+    #[test]
+    fn a_certain_misdrop_lands_a_neighbour_of_the_chosen_placement() {
+        let slipping = Handicap {
+            misdrop: 1.0,
+            ..Handicap::perfect()
+        };
+        for seed in 0..6 {
+            let intended = first_lock(&mut AiController::new(Handicap::perfect(), 9), seed);
+            let slipped = first_lock(&mut AiController::new(slipping, 9), seed);
+            assert_ne!(
+                intended, slipped,
+                "seed {seed}: the slip must move the piece"
+            );
+            let columns = |cells: &[(isize, isize)]| {
+                let xs = cells.iter().map(|c| c.0);
+                (xs.clone().min().unwrap(), xs.max().unwrap())
+            };
+            let (a, b) = (columns(&intended), columns(&slipped));
+            assert!(
+                (a.0 - b.0).abs() <= 2 && (a.1 - b.1).abs() <= 2,
+                "seed {seed}: a misdrop stays next to the intent ({intended:?} vs {slipped:?})"
+            );
+        }
+    }
+
+    #[test]
+    fn a_garbage_blind_bot_ignores_an_arrival_mid_think() {
+        let blind = Handicap {
+            reaction: core::time::Duration::from_millis(500),
+            garbage_blind: true,
+            ..Handicap::perfect()
+        };
+        let polls_until_first_action = |interrupt: bool| {
+            let mut controller = AiController::new(blind, DEFAULT_AI_SEED);
+            let mut engine = Engine::new(EngineConfig::default(), 1);
+            engine.step(InputFrame::default());
+            for poll in 0..120 {
+                if interrupt && poll == 10 {
+                    engine.queue_garbage(2);
+                }
+                let frame = controller.poll(&engine.snapshot());
+                if pressed_anything(&frame) {
+                    return poll;
+                }
+                engine.step(frame);
+            }
+            panic!("controller never acted within 120 polls");
+        };
+        assert_eq!(
+            polls_until_first_action(true),
+            polls_until_first_action(false),
+            "a blind bot cannot notice the meter jump"
+        );
+    }
+
     #[test]
     fn determinism_same_seed_same_game() {
         // Two controllers with the same AI seed, driving two engines with the same
@@ -137,6 +137,73 @@ impl Cc2Weights {
         Self::DEFAULT.with_board_params(&ATTACK_BOARD_PARAMS)
     }
 
+    /// Personality: the **T-spin specialist**. The attack profile with the
+    /// T-slot Value and T-spin rewards roughly doubled, a wasted T priced
+    /// twice as dear, and plain small clears discouraged — it builds slots and
+    /// cashes them. Checked by the research `personality-style` eval.
+    pub fn t_spin_specialist() -> Self {
+        Self {
+            tslot: [0.5, 4.0, 9.0, 10.0],
+            spin_clears: [0.0, 3.0, 14.0, 18.0],
+            mini_spin_clears: [0.0, -1.0, 0.0],
+            wasted_t: -4.0,
+            normal_clears: [0.0, -3.0, -2.5, -2.0, 0.5],
+            tetris_well_depth: 0.0,
+            ..Self::attack_tuned()
+        }
+    }
+
+    /// Personality: the **4-wide combo** bot. Clears are cheap and combos
+    /// dear: every line-count pays, the combo reward is tripled, and the
+    /// height tiers relax so it can stack the walls a long chain needs.
+    pub fn four_wide() -> Self {
+        Self {
+            combo_attack: 10.0,
+            normal_clears: [0.0, -1.0, -0.5, 0.0, 2.0],
+            height: 0.0,
+            height_upper_half: -0.4,
+            height_upper_quarter: -3.0,
+            tetris_well_depth: 0.0,
+            tslot: [0.0; 4],
+            ..Self::attack_tuned()
+        }
+    }
+
+    /// Personality: the **downstacker**. Holes and covered cells weigh far
+    /// more than anything it could send, every clear is welcome, and the
+    /// stack is kept low — it digs out and stays clean rather than attacking.
+    pub fn downstacker() -> Self {
+        Self {
+            holes: -4.0,
+            cell_coveredness: -0.6,
+            height: -1.0,
+            height_upper_half: -3.0,
+            height_upper_quarter: -8.0,
+            tetris_well_depth: 0.0,
+            tslot: [0.0; 4],
+            normal_clears: [0.0, 1.5, 2.0, 2.5, 3.0],
+            combo_attack: 0.5,
+            ..Self::attack_tuned()
+        }
+    }
+
+    /// Personality: the **tetris-only classic** bot. Keeps one deep well and
+    /// clears four at a time: anything smaller is punished, T-slots and spins
+    /// are worth nothing, and back-to-back tetrises are the prize.
+    pub fn tetris_only() -> Self {
+        Self {
+            normal_clears: [0.0, -6.0, -5.0, -4.0, 8.0],
+            tetris_well_depth: 1.0,
+            tslot: [0.0; 4],
+            spin_clears: [0.0; 4],
+            mini_spin_clears: [0.0; 3],
+            wasted_t: 0.0,
+            back_to_back_clear: 2.0,
+            combo_attack: 0.0,
+            ..Self::attack_tuned()
+        }
+    }
+
     /// Return a copy with the board-Value weights replaced by `p` (see
     /// [`board_params`](Self::board_params) for the order).
     pub fn with_board_params(mut self, p: &[f32; Self::BOARD_PARAM_COUNT]) -> Self {
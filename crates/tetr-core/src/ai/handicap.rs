@@ -4,33 +4,42 @@
 //! beatable opponent. It is split across the AI player's two layers, and consumed
 //! at construction:
 //!
-//! - [`reaction`](Handicap::reaction) is consumed by the **controller shell** — a
-//!   delay before the bot acts on a new piece (human "look, then move" latency).
+//! - The **controller shell** consumes the execution dials:
+//!   [`reaction`](Handicap::reaction) — a delay before the bot acts on a new piece
+//!   (human "look, then move" latency); [`max_pps`](Handicap::max_pps) — a
+//!   pieces-per-second ceiling; [`misdrop`](Handicap::misdrop) — the chance the
+//!   bot's fingers commit to a neighbouring placement (one column or one
+//!   rotation off) instead of the one it chose; and
+//!   [`garbage_blind`](Handicap::garbage_blind) — whether it plans without
+//!   seeing its pending-garbage queue.
 //! - [`imperfection`](Handicap::imperfection) is consumed by the **policy** (the
 //!   brain) — how sub-optimally it plays. Each model interprets it: the search
 //!   policy softmax-samples a near-best placement; a neural policy would raise its
 //!   sampling temperature.
 //!
 //! The name is deliberately *not* `Difficulty`: this is the mechanism, not the
-//! player-facing Easy/Medium/Hard label a menu shows (a UI level maps *onto* a
-//! `Handicap`). Search *capability* (lookahead depth, node budget) is **not** here
-//! — that belongs to the search policy, not the handicap.
+//! player-facing label a menu shows. That label is a [`SkillPreset`] (Novice
+//! through Master), which maps *onto* a `Handicap`. Search *capability*
+//! (lookahead depth, node budget) is **not** here — that belongs to the search
+//! policy, not the handicap.
 //!
 //! The default is **beatable / even** rather than peak strength: a perfect bot
 //! is no fun. No published source on human-like degradation survived
-//! verification, so the defaults are pragmatic, meant to be tuned empirically
-//! against real play.
+//! verification, so the defaults are pragmatic; the presets are calibrated by
+//! the research crate's `handicap-ladder` eval (measured PPS/APM against fixed
+//! human anchors — see [`SkillPreset`]).
 //!
 //! # Determinism
 //!
 //! Pure data — no Bevy, no RNG, no clock. The randomness `imperfection` drives
-//! lives in the policy's own seeded RNG, never here.
+//! lives in the policy's own seeded RNG, and the misdrop draws in the
+//! controller's, never here.
 
 use core::time::Duration;
 
 /// The AI's handicap: a deliberate, model-agnostic weakening of an otherwise-strong
-/// bot into a beatable opponent. Cloneable plain data; `reaction` is consumed by the
-/// controller shell, `imperfection` by the policy.
+/// bot into a beatable opponent. Cloneable plain data; `imperfection` is consumed by
+/// the policy, everything else by the controller shell.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Handicap {
     /// Reaction delay before the bot acts on a new piece. The controller integrates
@@ -42,15 +51,41 @@ pub struct Handicap {
     /// near-best instead. The policy interprets it (a search: a softmax over
     /// candidates; a net: sampling temperature). Clamped on use.
     pub imperfection: f32,
+    /// Pieces-per-second ceiling: the controller waits until `1 / max_pps`
+    /// seconds have passed since it last committed to a placement before
+    /// committing to the next. `None` is uncapped.
+    pub max_pps: Option<f32>,
+    /// Probability, in `0.0..=1.0`, that the bot executes a neighbouring
+    /// placement — the chosen one shifted a column or turned a rotation —
+    /// instead of the one it decided on. Unlike `imperfection` this is a
+    /// finger slip, not a judgement error: the brain picked right. Clamped on
+    /// use.
+    pub misdrop: f32,
+    /// Plan without seeing the pending-garbage queue. The engine still cancels
+    /// and rises by rule; only the bot's view is stripped.
+    pub garbage_blind: bool,
 }
 
 impl Handicap {
-    /// A flawless, instant bot: no reaction delay, no imperfection. The strongest
-    /// setting and a deterministic baseline for tests.
+    /// A flawless, instant bot: no reaction delay, no imperfection, no pace cap,
+    /// no misdrops, full sight of its garbage queue. The strongest setting and a
+    /// deterministic baseline for tests.
     pub fn perfect() -> Self {
         Self {
             reaction: Duration::ZERO,
             imperfection: 0.0,
+            max_pps: None,
+            misdrop: 0.0,
+            garbage_blind: false,
+        }
+    }
+
+    /// The minimum time between two committed placements implied by
+    /// [`max_pps`](Self::max_pps), in seconds (`0` when uncapped).
+    pub fn min_piece_seconds(&self) -> f32 {
+        match self.max_pps {
+            Some(pps) if pps > 0.0 && pps.is_finite() => 1.0 / pps,
+            _ => 0.0,
         }
     }
 }
@@ -65,8 +100,146 @@ impl Default for Handicap {
             reaction: Duration::from_millis(200),
             // Misplaces roughly one piece in eight.
             imperfection: 0.12,
+            max_pps: None,
+            misdrop: 0.0,
+            garbage_blind: false,
+        }
+    }
+}
+
+/// A named, player-facing skill level that maps onto a [`Handicap`].
+///
+/// Calibrated with the research `handicap-ladder` eval: each preset plays a
+/// capped marathon on the reference brain and its pieces-per-second and
+/// attack-per-minute are read against the fixed human anchors in
+/// [`anchor`](Self::anchor). The three weaker presets also plan blind to
+/// their pending garbage; Expert and Master read it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum SkillPreset {
+    Novice,
+    Casual,
+    #[default]
+    Skilled,
+    Expert,
+    Master,
+}
+
+/// The human reference point a [`SkillPreset`] is calibrated against.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SkillAnchor {
+    /// Pieces per second a player at this level sustains.
+    pub pps: f32,
+    /// Attack (garbage lines sent) per minute.
+    pub apm: f32,
+}
+
+impl SkillPreset {
+    /// Every preset, weakest first (the setup screen's cycle order).
+    pub const ALL: [SkillPreset; 5] = [
+        SkillPreset::Novice,
+        SkillPreset::Casual,
+        SkillPreset::Skilled,
+        SkillPreset::Expert,
+        SkillPreset::Master,
+    ];
+
+    /// Display name.
+    pub fn label(self) -> &'static str {
+        match self {
+            SkillPreset::Novice => "Novice",
+            SkillPreset::Casual => "Casual",
+            SkillPreset::Skilled => "Skilled",
+            SkillPreset::Expert => "Expert",
+            SkillPreset::Master => "Master",
+        }
+    }
+
+    /// The human pace this preset is tuned to play at.
+    pub fn anchor(self) -> SkillAnchor {
+        let (pps, apm) = match self {
+            SkillPreset::Novice => (0.5, 8.0),
+            SkillPreset::Casual => (0.9, 18.0),
+            SkillPreset::Skilled => (1.4, 32.0),
+            SkillPreset::Expert => (2.0, 50.0),
+            SkillPreset::Master => (3.0, 80.0),
+        };
+        SkillAnchor { pps, apm }
+    }
+
+    /// The handicap this preset plays with.
+    pub fn handicap(self) -> Handicap {
+        let (reaction_ms, imperfection, max_pps, misdrop, garbage_blind) = match self {
+            SkillPreset::Novice => (600, 0.35, 0.5, 0.08, true),
+            SkillPreset::Casual => (400, 0.22, 0.9, 0.04, true),
+            SkillPreset::Skilled => (250, 0.12, 1.4, 0.015, true),
+            SkillPreset::Expert => (150, 0.06, 2.0, 0.005, false),
+            SkillPreset::Master => (16, 0.0, 3.0, 0.0, false),
+        };
+        Handicap {
+            reaction: Duration::from_millis(reaction_ms),
+            imperfection,
+            max_pps: Some(max_pps),
+            misdrop,
+            garbage_blind,
+        }
+    }
+
+    /// The top of the continuous skill scale [`handicap_at`](Self::handicap_at)
+    /// reads: `0.0` is Novice, this is Master.
+    pub const MAX_LEVEL: f32 = (Self::ALL.len() - 1) as f32;
+
+    /// A point on the preset ladder as a continuous level: whole numbers are
+    /// the presets (`0.0` Novice … [`MAX_LEVEL`](Self::MAX_LEVEL) Master), and
+    /// a fractional level blends its two neighbours' dials linearly. Clamped
+    /// to the scale; NaN reads as Novice. What an adaptive opponent steers.
+    pub fn handicap_at(level: f32) -> Handicap {
+        let level = if level.is_nan() {
+            0.0
+        } else {
+            level.clamp(0.0, Self::MAX_LEVEL)
+        };
+        let low = level.floor() as usize;
+        let high = (low + 1).min(Self::ALL.len() - 1);
+        let t = level - low as f32;
+        let (a, b) = (Self::ALL[low].handicap(), Self::ALL[high].handicap());
+        if t == 0.0 {
+            return a;
+        }
+        let mix = |x: f32, y: f32| x + (y - x) * t;
+        Handicap {
+            reaction: Duration::from_secs_f32(mix(
+                a.reaction.as_secs_f32(),
+                b.reaction.as_secs_f32(),
+            )),
+            imperfection: mix(a.imperfection, b.imperfection),
+            max_pps: match (a.max_pps, b.max_pps) {
+                (Some(x), Some(y)) => Some(mix(x, y)),
+                (x, y) => {
+                    if t < 0.5 {
+                        x
+                    } else {
+                        y
+                    }
+                }
+            },
+            misdrop: mix(a.misdrop, b.misdrop),
+            garbage_blind: if t < 0.5 {
+                a.garbage_blind
+            } else {
+                b.garbage_blind
+            },
         }
     }
+
+    /// The preset nearest a continuous level (for labelling one).
+    pub fn nearest(level: f32) -> SkillPreset {
+        let index = if level.is_nan() {
+            0.0
+        } else {
+            level.clamp(0.0, Self::MAX_LEVEL).round()
+        };
+        Self::ALL[index as usize]
+    }
 }
 
 #[cfg(test)]
@@ -83,6 +256,21 @@ mod tests {
             h.imperfection < 0.5,
             "but still play well most of the time (imperfection < 50%)"
         );
+        assert!(
+            !h.garbage_blind,
+            "blindness is a preset's choice, not the default's"
+        );
+    }
+
+    #[test]
+    fn only_the_weaker_presets_play_blind() {
+        let blind: Vec<bool> = SkillPreset::ALL
+            .map(|preset| preset.handicap().garbage_blind)
+            .into();
+        assert_eq!(blind, [true, true, true, false, false]);
+        // The continuous scale switches at the midpoint between neighbours.
+        assert!(SkillPreset::handicap_at(2.4).garbage_blind);
+        assert!(!SkillPreset::handicap_at(2.6).garbage_blind);
     }
 
     #[test]
@@ -90,5 +278,55 @@ mod tests {
         let h = Handicap::perfect();
         assert_eq!(h.reaction, Duration::ZERO);
         assert_eq!(h.imperfection, 0.0);
+        assert_eq!(h.max_pps, None);
+        assert_eq!(h.misdrop, 0.0);
+        assert!(!h.garbage_blind);
+        assert_eq!(h.min_piece_seconds(), 0.0);
+    }
+
+    #[test]
+    fn presets_strengthen_monotonically() {
+        for pair in SkillPreset::ALL.windows(2) {
+            let (weaker, stronger) = (pair[0].handicap(), pair[1].handicap());
+            assert!(weaker.reaction > stronger.reaction, "{pair:?}");
+            assert!(weaker.imperfection >= stronger.imperfection, "{pair:?}");
+            assert!(weaker.misdrop >= stronger.misdrop, "{pair:?}");
+            assert!(
+                weaker.min_piece_seconds() > stronger.min_piece_seconds(),
+                "{pair:?}"
+            );
+            assert!(pair[0].anchor().pps < pair[1].anchor().pps, "{pair:?}");
+        }
+    }
+
+    #[test]
+    fn the_continuous_scale_hits_the_presets_and_blends_between() {
+        for (i, preset) in SkillPreset::ALL.into_iter().enumerate() {
+            let at = SkillPreset::handicap_at(i as f32);
+            let exact = preset.handicap();
+            assert!((at.imperfection - exact.imperfection).abs() < 1e-6);
+            assert!((at.reaction.as_secs_f32() - exact.reaction.as_secs_f32()).abs() < 1e-4);
+            assert_eq!(SkillPreset::nearest(i as f32), preset);
+        }
+        let (low, mid, high) = (
+            SkillPreset::handicap_at(1.0),
+            SkillPreset::handicap_at(1.5),
+            SkillPreset::handicap_at(2.0),
+        );
+        assert!(low.imperfection > mid.imperfection && mid.imperfection > high.imperfection);
+        assert!(low.reaction > mid.reaction && mid.reaction > high.reaction);
+        assert!(low.min_piece_seconds() > mid.min_piece_seconds());
+        assert_eq!(
+            SkillPreset::handicap_at(-3.0),
+            SkillPreset::Novice.handicap()
+        );
+        assert_eq!(
+            SkillPreset::handicap_at(f32::NAN),
+            SkillPreset::Novice.handicap()
+        );
+        assert_eq!(
+            SkillPreset::handicap_at(99.0).imperfection,
+            SkillPreset::Master.handicap().imperfection
+        );
     }
 }
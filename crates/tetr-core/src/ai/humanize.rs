@@ -0,0 +1,476 @@
+//! Human-like plan-to-input: render a placement the way a person would key it.
+//!
+//! [`placement_to_inputs`](crate::ai::plan::placement_to_inputs) is the exact
+//! renderer: one-cell pulses at `dt == 0`, so a bot positions a piece in zero
+//! game time and with key patterns no hand can produce. That is right for
+//! benchmarks and research, and wrong for an exhibition match or a fair
+//! handicap. This module is the alternate renderer for those: it turns the same
+//! [`Move`] path into a **held-key timeline** and runs it through the player's
+//! own DAS machine, so the bot's pieces-per-second is bounded by the mechanics
+//! a human is bounded by.
+//!
+//! # What makes it human
+//!
+//! - **Real time.** Every frame carries the controller's nominal slice, so
+//!   gravity and the lock timer act *during* the maneuver, exactly as they do
+//!   while a person is still moving a piece.
+//! - **DAS, not pulses.** Lateral keys are pressed and held as
+//!   [`RawKeyboardFrame`]s and resolved by the keyboard controller's
+//!   [`DasState`](crate::player::DasState) under a configurable
+//!   [`DasConfig`]: a tap moves one cell, a hold waits out the delay and then
+//!   repeats.
+//! - **Finesse key choices.** A run that ends against the wall is a DAS to the
+//!   wall (held a little past it, as people do); a run that stops one short of
+//!   the wall is a DAS to the wall and a tap back; anything else is taps.
+//! - **Key intervals with jitter.** Consecutive presses are separated by
+//!   [`key_interval_seconds`](HumanInputConfig::key_interval_seconds) plus a
+//!   uniform jitter drawn from the caller's seeded RNG — the controller shell's,
+//!   the one its misdrop rolls use — so a fixed seed replays an identical game.
+//!
+//! # Fidelity
+//!
+//! Because time passes, the maneuver is no longer guaranteed to land on the
+//! planned pose: a piece spawned onto a tall stack can land (and lock) before
+//! the hard drop, and a kick can resolve differently a row lower. That is the
+//! point — a person misexecutes under the same pressure — and the controller
+//! already re-plans whenever the board changes under it. On open boards the
+//! rendering is faithful (see the round-trip test).
+
+use rand::RngExt;
+use rand::rngs::StdRng;
+
+use crate::ai::controller::NOMINAL_DT;
+use crate::ai::movegen::{Move, Placement, spawn_piece};
+use crate::ai::plan::{RotationDir, drop_to_floor, rotate, step_lateral};
+use crate::engine::{ActivePiece, Board, InputFrame, MoveDirection};
+use crate::player::{DasConfig, DasState, KeyboardController, RawKeyboardFrame};
+
+/// How a human-like bot keys its placements.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HumanInputConfig {
+    /// The DAS the bot's lateral keys run through.
+    pub das: DasConfig,
+    /// Time from one key press to the next, in seconds (release included).
+    pub key_interval_seconds: f32,
+    /// Upper bound of the uniform extra delay added to each interval, in
+    /// seconds. `0.0` keys with metronome regularity.
+    pub jitter_seconds: f32,
+}
+
+impl Default for HumanInputConfig {
+    /// A steady club player: default DAS, ~4 frames between presses, up to 3
+    /// frames of jitter. Lands a little over one piece per second even with no
+    /// reaction delay.
+    fn default() -> Self {
+        Self {
+            das: DasConfig::default(),
+            key_interval_seconds: 0.066,
+            jitter_seconds: 0.05,
+        }
+    }
+}
+
+/// Render `placement`'s movement path as a human would key it, from the pose
+/// `start` on `board` (the same inputs as
+/// [`placement_to_inputs`](crate::ai::plan::placement_to_inputs)).
+///
+/// Every returned frame carries `dt == NOMINAL_DT`; the last one is the hard
+/// drop. `rng` draws the per-key jitter and nothing else.
+pub fn placement_to_human_inputs(
+    board: &Board,
+    start: &ActivePiece,
+    placement: &Placement,
+    config: &HumanInputConfig,
+    rng: &mut StdRng,
+) -> Vec<InputFrame> {
+    let timeline = key_timeline(board, start, placement, config, rng);
+    let mut keyboard = KeyboardController::new(config.das);
+    timeline
+        .iter()
+        .map(|raw| keyboard.resolve_frame(raw))
+        .collect()
+}
+
+/// The held-key timeline behind [`placement_to_human_inputs`], one
+/// [`RawKeyboardFrame`] per frame.
+fn key_timeline(
+    board: &Board,
+    start: &ActivePiece,
+    placement: &Placement,
+    config: &HumanInputConfig,
+    rng: &mut StdRng,
+) -> Vec<RawKeyboardFrame> {
+    let mut keys = Timeline {
+        frames: Vec::new(),
+        config,
+        rng,
+    };
+    // The same shadow walk as the exact renderer, so runs and drops are
+    // measured against the pose movegen searched from.
+    let mut piece = ActivePiece::at_pose(start.piece_type(), start.origin(), start.rotation());
+    let path = &placement.path;
+
+    let mut i = 0;
+    while i < path.len() {
+        match path[i] {
+            Move::Hold => {
+                piece = spawn_piece(placement.piece_type(), board.width(), board.height());
+                keys.tap(|f| f.hold_just_pressed = true);
+            }
+            Move::Cw => {
+                rotate(&mut piece, board, RotationDir::Cw);
+                keys.tap(|f| f.rotate_cw_just_pressed = true);
+            }
+            Move::Ccw => {
+                rotate(&mut piece, board, RotationDir::Ccw);
+                keys.tap(|f| f.rotate_ccw_just_pressed = true);
+            }
+            Move::SoftDrop => {
+                let cells = drop_to_floor(board, &mut piece);
+                keys.hold_soft_drop(cells);
+            }
+            Move::Left | Move::Right => {
+                let mv = path[i];
+                let run = path[i..].iter().take_while(|m| **m == mv).count();
+                let dir = if mv == Move::Left {
+                    MoveDirection::Left
+                } else {
+                    MoveDirection::Right
+                };
+                keys.lateral_run(board, &mut piece, dir, run);
+                i += run;
+                continue;
+            }
+        }
+        i += 1;
+    }
+
+    keys.tap(|f| f.hard_drop_just_pressed = true);
+    keys.frames
+}
+
+/// Builds a [`key_timeline`]: presses separated by jittered intervals.
+struct Timeline<'a> {
+    frames: Vec<RawKeyboardFrame>,
+    config: &'a HumanInputConfig,
+    rng: &'a mut StdRng,
+}
+
+impl Timeline<'_> {
+    /// Idle frames between two presses; none before the first press (the
+    /// controller's reaction delay already covers "look, then move").
+    fn interval(&mut self) {
+        if self.frames.is_empty() {
+            return;
+        }
+        let jitter = self.jitter();
+        let seconds = self.config.key_interval_seconds.max(0.0) + jitter;
+        // At least one release frame, so two taps of one key are two edges.
+        for _ in 0..frames_for(seconds).max(1) {
+            self.frames.push(idle());
+        }
+    }
+
+    fn jitter(&mut self) -> f32 {
+        let bound = self.config.jitter_seconds;
+        if bound > 0.0 {
+            self.rng.random_range(0.0..bound)
+        } else {
+            0.0
+        }
+    }
+
+    /// One press of an edge-triggered key (or a one-frame lateral tap).
+    fn tap(&mut self, set: impl FnOnce(&mut RawKeyboardFrame)) {
+        self.interval();
+        let mut frame = idle();
+        set(&mut frame);
+        self.frames.push(frame);
+    }
+
+    /// Hold soft drop for as many frames as the piece has cells to fall;
+    /// gravity only shortens the trip, and a grounded soft drop is a no-op.
+    fn hold_soft_drop(&mut self, cells: usize) {
+        self.interval();
+        for _ in 0..cells {
+            self.frames.push(RawKeyboardFrame {
+                soft_drop: true,
+                ..idle()
+            });
+        }
+    }
+
+    /// Key a run of `cells` one-cell shifts in `dir`, choosing the finesse a
+    /// person would: DAS to the wall, DAS to the wall and tap back, or taps.
+    fn lateral_run(
+        &mut self,
+        board: &Board,
+        piece: &mut ActivePiece,
+        dir: MoveDirection,
+        cells: usize,
+    ) {
+        for _ in 0..cells {
+            step_lateral(board, piece, dir);
+        }
+        let width = board.width();
+        if cells >= 2 && touches_wall(piece, dir, width) {
+            self.das_to_wall(dir, cells);
+            return;
+        }
+        if cells >= 3 {
+            let mut beyond = piece.clone();
+            step_lateral(board, &mut beyond, dir);
+            if beyond.origin() != piece.origin() && touches_wall(&beyond, dir, width) {
+                self.das_to_wall(dir, cells + 1);
+                self.lateral_tap(opposite(dir));
+                return;
+            }
+        }
+        for _ in 0..cells {
+            self.lateral_tap(dir);
+        }
+    }
+
+    fn lateral_tap(&mut self, dir: MoveDirection) {
+        self.tap(|f| press(f, dir, true));
+    }
+
+    /// Press and hold `dir` until the DAS has fired `pulses` shifts (enough to
+    /// reach the wall), then keep holding a jittered moment longer — those
+    /// extra pulses are blocked by the wall.
+    fn das_to_wall(&mut self, dir: MoveDirection, pulses: usize) {
+        self.interval();
+        let config = self.config.das;
+        let mut das = DasState::default();
+        let mut fired = 0;
+        let mut just_pressed = true;
+        while fired < pulses {
+            let mut frame = idle();
+            press(&mut frame, dir, just_pressed);
+            if das
+                .next_pulse(Some(dir), just_pressed, frame.dt_seconds, &config)
+                .is_some()
+            {
+                fired += 1;
+            }
+            just_pressed = false;
+            self.frames.push(frame);
+        }
+        let jitter = self.jitter();
+        for _ in 0..frames_for(jitter) {
+            let mut frame = idle();
+            press(&mut frame, dir, false);
+            self.frames.push(frame);
+        }
+    }
+}
+
+/// Whether `piece` sits flush against the wall on its `dir` side.
+fn touches_wall(piece: &ActivePiece, dir: MoveDirection, width: usize) -> bool {
+    let (ox, _) = piece.origin();
+    let xs = piece.piece().cells().map(|(x, _)| x + ox);
+    match dir {
+        MoveDirection::Left => xs.iter().min() == Some(&0),
+        MoveDirection::Right => xs.iter().max() == Some(&(width as isize - 1)),
+        MoveDirection::Down => false,
+    }
+}
+
+fn opposite(dir: MoveDirection) -> MoveDirection {
+    match dir {
+        MoveDirection::Left => MoveDirection::Right,
+        MoveDirection::Right => MoveDirection::Left,
+        MoveDirection::Down => MoveDirection::Down,
+    }
+}
+
+fn press(frame: &mut RawKeyboardFrame, dir: MoveDirection, just_pressed: bool) {
+    match dir {
+        MoveDirection::Left => {
+            frame.left_pressed = true;
+            frame.left_just_pressed = just_pressed;
+        }
+        MoveDirection::Right => {
+            frame.right_pressed = true;
+            frame.right_just_pressed = just_pressed;
+        }
+        MoveDirection::Down => frame.soft_drop = true,
+    }
+}
+
+/// A frame with no key down that still advances one nominal slice.
+fn idle() -> RawKeyboardFrame {
+    RawKeyboardFrame {
+        dt_seconds: NOMINAL_DT,
+        ..RawKeyboardFrame::default()
+    }
+}
+
+/// Whole frames covering `seconds` at the nominal slice.
+fn frames_for(seconds: f32) -> usize {
+    (seconds / NOMINAL_DT).round().max(0.0) as usize
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::movegen::generate;
+    use crate::engine::{Engine, EngineConfig, PieceType};
+    use rand::SeedableRng;
+
+    fn rng() -> StdRng {
+        StdRng::seed_from_u64(11)
+    }
+
+    fn placement(piece: ActivePiece, path: Vec<Move>) -> Placement {
+        Placement {
+            piece,
+            path: path.into(),
+            used_hold: false,
+        }
+    }
+
+    fn presses(timeline: &[RawKeyboardFrame], dir: MoveDirection) -> usize {
+        timeline
+            .iter()
+            .filter(|f| match dir {
+                MoveDirection::Left => f.left_just_pressed,
+                _ => f.right_just_pressed,
+            })
+            .count()
+    }
+
+    #[test]
+    fn a_run_to_the_wall_is_one_held_press() {
+        let board = Board::new(10, 20);
+        let start = spawn_piece(PieceType::T, 10, 20);
+        let mut wall = start.clone();
+        let mut cells = 0;
+        while !touches_wall(&wall, MoveDirection::Left, 10) {
+            step_lateral(&board, &mut wall, MoveDirection::Left);
+            cells += 1;
+        }
+        assert!(cells >= 2, "premise: spawn is several cells off the wall");
+
+        let p = placement(start.clone(), vec![Move::Left; cells]);
+        let config = HumanInputConfig::default();
+        let timeline = key_timeline(&board, &start, &p, &config, &mut rng());
+        assert_eq!(presses(&timeline, MoveDirection::Left), 1, "DAS, not taps");
+
+        let frames = placement_to_human_inputs(&board, &start, &p, &config, &mut rng());
+        let pulses = frames.iter().filter(|f| f.left).count();
+        assert!(pulses >= cells, "the hold carries the piece to the wall");
+        assert!(frames.iter().all(|f| f.dt_seconds == NOMINAL_DT));
+        assert!(frames.last().unwrap().hard_drop);
+    }
+
+    #[test]
+    fn a_short_run_is_taps() {
+        let board = Board::new(10, 20);
+        let start = spawn_piece(PieceType::T, 10, 20);
+        let p = placement(start.clone(), vec![Move::Right; 2]);
+        let timeline = key_timeline(&board, &start, &p, &HumanInputConfig::default(), &mut rng());
+        assert_eq!(presses(&timeline, MoveDirection::Right), 2);
+        assert_eq!(presses(&timeline, MoveDirection::Left), 0);
+    }
+
+    #[test]
+    fn one_short_of_the_wall_is_das_and_tap_back() {
+        let board = Board::new(10, 20);
+        let start = spawn_piece(PieceType::T, 10, 20);
+        let mut shadow = start.clone();
+        let mut cells = 0;
+        while !touches_wall(&shadow, MoveDirection::Right, 10) {
+            step_lateral(&board, &mut shadow, MoveDirection::Right);
+            cells += 1;
+        }
+        let cells = cells - 1;
+        assert!(cells >= 3, "premise: a long run that stops short");
+
+        let p = placement(start.clone(), vec![Move::Right; cells]);
+        let timeline = key_timeline(&board, &start, &p, &HumanInputConfig::default(), &mut rng());
+        assert_eq!(presses(&timeline, MoveDirection::Right), 1);
+        assert_eq!(presses(&timeline, MoveDirection::Left), 1);
+    }
+
+    #[test]
+    fn jitter_is_seeded() {
+        let board = Board::new(10, 20);
+        let start = spawn_piece(PieceType::T, 10, 20);
+        let p = placement(start.clone(), vec![Move::Cw, Move::Left, Move::Left]);
+        let config = HumanInputConfig::default();
+        let render = |seed| {
+            placement_to_human_inputs(
+                &board,
+                &start,
+                &p,
+                &config,
+                &mut StdRng::seed_from_u64(seed),
+            )
+        };
+        assert_eq!(render(3), render(3), "same seed, same keying");
+        let lengths: std::collections::HashSet<usize> = (0..16).map(|s| render(s).len()).collect();
+        assert!(lengths.len() > 1, "the seed varies the timing");
+
+        let steady = HumanInputConfig {
+            jitter_seconds: 0.0,
+            ..config
+        };
+        let a = placement_to_human_inputs(&board, &start, &p, &steady, &mut rng());
+        let b =
+            placement_to_human_inputs(&board, &start, &p, &steady, &mut StdRng::seed_from_u64(99));
+        assert_eq!(a, b, "no jitter, no dependence on the seed");
+    }
+
+    #[test]
+    fn round_trip_lands_every_placement_with_gravity_running() {
+        // The human rendering spends real time, so gravity pulls the piece while
+        // it is keyed. On an open board every placement must still lock exactly
+        // the cells the planner chose.
+        let config = EngineConfig::default();
+        let w = config.board_width;
+        let h = config.visible_height;
+        let board = Board::with_top_margin(w, h, crate::engine::BUFFER_HEIGHT);
+        let seed = 7;
+        let spawned = Engine::new(config.clone(), seed).snapshot().next_queue[0];
+        let start = spawn_piece(spawned, w, h);
+        let placements = generate(&board, &start);
+        assert!(!placements.is_empty());
+
+        let mut rng = rng();
+        for placement in &placements {
+            let frames = placement_to_human_inputs(
+                &board,
+                &start,
+                placement,
+                &HumanInputConfig::default(),
+                &mut rng,
+            );
+            let mut engine = Engine::new(config.clone(), seed);
+            for frame in &frames {
+                engine.step(frame.clone());
+            }
+            let mut locked: Vec<(isize, isize)> = engine
+                .snapshot()
+                .board_cells
+                .iter()
+                .map(|c| (c.x, c.y))
+                .collect();
+            locked.sort();
+            let (ox, oy) = placement.origin();
+            let mut expected: Vec<(isize, isize)> = placement
+                .piece
+                .piece()
+                .cells()
+                .iter()
+                .map(|(x, y)| (x + ox, y + oy))
+                .collect();
+            expected.sort();
+            assert_eq!(
+                locked, expected,
+                "{spawned:?} landed elsewhere; path = {:?}",
+                placement.path
+            );
+        }
+    }
+}
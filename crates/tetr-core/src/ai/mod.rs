@@ -23,18 +23,26 @@
 //! - [`eval`] — the `(Value, Reward)` evaluator seam.
 //! - [`movegen`] + [`search`] — reachable placements + the anytime
 //!   search session ([`Mind`]).
-//! - [`plan`] — placement → [`InputFrame`](crate::engine::InputFrame)s.
+//! - [`plan`] — placement → [`InputFrame`](crate::engine::InputFrame)s;
+//!   [`humanize`] renders the same path at a person's pace instead.
 //! - [`runner`] + [`controller`] — the compute seam and the
 //!   [`AiController`] (a [`PlayerController`](crate::player::PlayerController)).
+//! - [`analysis`] — the post-game review: replay a recorded game and price
+//!   every lock against the search's best.
+//! - [`pc_solver`] — every perfect clear of a fixed board and queue, and its
+//!   odds over the bag.
 //!
 //! The Bevy-aware surface that seats the bot in a game session lives in the game
 //! crate (`tetr_online::session`), outside this engine-agnostic core — keeping
 //! `tetr-core` free of Bevy.
 
+pub mod analysis;
 pub mod controller;
 pub mod eval;
 pub mod handicap;
+pub mod humanize;
 pub mod movegen;
+pub mod pc_solver;
 pub mod plan;
 pub mod policy;
 pub mod runner;
@@ -47,17 +55,21 @@ pub mod state;
 /// verb drain (`SearchPolicy::decide`) — type-different loops, same contract.
 pub(crate) const MAX_THINK_CALLS: u32 = 100_000;
 
+pub use analysis::{
+    AnalysisConfig, Analyst, GameAnalysis, PlacementReview, RecordedGame, Verdict, analyze_game,
+};
 pub use controller::{AiController, DEFAULT_AI_SEED};
 pub use eval::{
     Cc2Evaluator, Cc2Weights, EvalContext, Evaluator, LinearEvaluator, Reward, Value, Weights,
 };
-pub use handicap::Handicap;
+pub use handicap::{Handicap, SkillAnchor, SkillPreset};
+pub use humanize::{HumanInputConfig, placement_to_human_inputs};
 pub use movegen::{Move, Placement, generate, generate_with_hold};
 pub use plan::placement_to_inputs;
 pub use policy::{Decision, Observation, Policy, SearchPolicy};
 pub use runner::{BudgetedRunner, DecisionRunner, MonotonicClock, SlicedRunner, SyncRunner};
 pub use search::{
-    BeamPlanner, BestFirstPlanner, Mind, PcCoverageConfig, PcCoveragePlanner, PcCoverageUnit,
-    PlacementPlan, SearchBudget, ThinkProgress, think_to_completion,
+    BeamPlanner, BestFirstPlanner, Mind, OpenerBookPlanner, PcCoverageConfig, PcCoveragePlanner,
+    PcCoverageUnit, PlacementPlan, SearchBudget, ThinkProgress, think_to_completion,
 };
 pub use state::{BagState, SearchState};
@@ -0,0 +1,479 @@
+//! Exhaustive perfect-clear solutions for a fixed board.
+//!
+//! Where [`PcCoveragePlanner`](crate::ai::search::PcCoveragePlanner) asks which
+//! root keeps the most futures PC-alive, this module answers the
+//! solution-finder questions directly: given a board, a piece sequence, the
+//! hold slot and a line height, what are **all** the distinct perfect clears
+//! ([`solve`]), and over every legal bag continuation, how often does one
+//! exist ([`survey`])?
+//!
+//! The search is a depth-first walk over [`hold_placements`] — the same
+//! reachable placements the planners use, so every listed order is playable
+//! under the engine's SRS — pruned three ways, none of which can drop a real
+//! solution:
+//!
+//! - no cell may land above the rows still to clear (the stack's rows are
+//!   contiguous from the floor, so such a cell would need an extra row);
+//! - `pc_feasible`, the coverage planner's cell-count and height gate;
+//! - a transposition set of positions already shown to have no solution.
+//!
+//! A solution's identity is its **layout** — which cells each piece fills,
+//! counted in the starting board's rows, before any clear moves them — so two
+//! orders that build the same picture are one solution with two
+//! [`orders`](PcSolution::orders). The walk is exponential in the piece count:
+//! it is meant for the 2-, 4- and 6-line endings of real setups (up to about
+//! seven pieces), not for a 10-piece first-bag PC from an empty board.
+
+use rustc_hash::{FxHashMap, FxHashSet};
+
+use crate::ai::movegen::Placement;
+use crate::ai::search::hold_placements;
+use crate::ai::search::pc_coverage::{enumerate_continuations, pc_feasible};
+use crate::ai::state::{BagState, SearchState};
+use crate::engine::{ActivePiece, BitBoard, PieceType};
+
+/// Whether a solution may use the hold slot.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HoldPolicy {
+    Allowed,
+    Forbidden,
+}
+
+/// One perfect-clear question.
+#[derive(Clone)]
+pub struct PcProblem {
+    /// The starting board. Its visible height is the spawn height.
+    pub board: BitBoard,
+    /// The piece sequence, the piece in play first.
+    pub queue: Vec<PieceType>,
+    pub hold: Option<PieceType>,
+    /// The perfect clear's height: every occupied row plus the rows the
+    /// pieces fill, all cleared.
+    pub lines: usize,
+    pub hold_policy: HoldPolicy,
+}
+
+impl PcProblem {
+    /// How many pieces the clear takes, or `None` when no number of pieces
+    /// fills exactly `lines` rows (the stack is taller, or the empty cells are
+    /// not a whole number of pieces).
+    pub fn pieces_needed(&self) -> Option<usize> {
+        let height = self.board.cell_coords().iter().map(|&(_, y)| y + 1).max();
+        if height.is_some_and(|height| height as usize > self.lines) {
+            return None;
+        }
+        let cells = self.board.cell_coords().len();
+        let empty = (self.lines * self.board.width()).checked_sub(cells)?;
+        empty.is_multiple_of(4).then_some(empty / 4)
+    }
+
+    /// How long a piece sequence has to be to decide the problem: the pieces
+    /// needed, plus one when an empty hold may be used (the swap funds itself
+    /// from the queue, so the last piece may stay unplaced).
+    pub fn sequence_len(&self) -> Option<usize> {
+        let spare = self.hold_policy == HoldPolicy::Allowed && self.hold.is_none();
+        self.pieces_needed()
+            .map(|needed| needed + usize::from(spare))
+    }
+}
+
+/// One piece of a layout: its type and the four cells it fills, sorted, in
+/// the starting board's rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct PcPiece {
+    pub piece_type: PieceType,
+    pub cells: [(isize, isize); 4],
+}
+
+/// A distinct perfect clear.
+#[derive(Clone, Debug)]
+pub struct PcSolution {
+    /// The pieces, sorted by their lowest cell — the solution's identity.
+    pub layout: Vec<PcPiece>,
+    /// Every placement order found that builds the layout, each playable from
+    /// the problem's start with [`SearchState::commit_placement`].
+    pub orders: Vec<Vec<Placement>>,
+}
+
+/// Every distinct perfect clear of `problem`, in the order the walk first
+/// finds them. Empty when there is none, the queue is empty, or the problem
+/// has no whole number of pieces ([`PcProblem::pieces_needed`]).
+pub fn solve(problem: &PcProblem) -> Vec<PcSolution> {
+    let mut walk = Walk::new(problem, false);
+    walk.run(problem);
+    walk.solutions
+}
+
+/// Whether `problem` has at least one perfect clear — [`solve`] stopping at
+/// the first.
+pub fn solvable(problem: &PcProblem) -> bool {
+    Walk::new(problem, true).run(problem)
+}
+
+/// How a layout fared over a [`survey`].
+#[derive(Clone, Debug)]
+pub struct LayoutShare {
+    pub layout: Vec<PcPiece>,
+    /// Sequences on which this layout can be built.
+    pub sequences: usize,
+}
+
+/// A problem's perfect-clear odds over the bag.
+#[derive(Clone, Debug)]
+pub struct PcSurvey {
+    /// Distinct legal sequences judged — all equally likely under a seven-bag.
+    pub sequences: usize,
+    /// Sequences with at least one perfect clear.
+    pub solved: usize,
+    /// Every layout seen, most widely buildable first.
+    pub layouts: Vec<LayoutShare>,
+}
+
+impl PcSurvey {
+    /// The probability of a perfect clear, in `[0, 1]` (0 with no sequences).
+    pub fn success_rate(&self) -> f64 {
+        if self.sequences == 0 {
+            0.0
+        } else {
+            self.solved as f64 / self.sequences as f64
+        }
+    }
+}
+
+/// Judge `problem` over every legal completion of its queue. `problem.queue`
+/// is the known prefix; when it is shorter than [`PcProblem::sequence_len`],
+/// each legal continuation drawn from `bag` (the remainder after the known
+/// pieces) is appended and solved in turn. Every continuation of the same
+/// length is equally likely under a seven-bag, so `solved / sequences` is the
+/// exact success probability.
+pub fn survey(problem: &PcProblem, bag: BagState) -> PcSurvey {
+    let Some(len) = problem.sequence_len() else {
+        return PcSurvey {
+            sequences: 0,
+            solved: 0,
+            layouts: Vec::new(),
+        };
+    };
+    let draws = len.saturating_sub(problem.queue.len());
+    let mut solved = 0;
+    let mut sequences = 0;
+    let mut layouts: Vec<LayoutShare> = Vec::new();
+    let mut index: FxHashMap<Vec<PcPiece>, usize> = FxHashMap::default();
+    for continuation in enumerate_continuations(bag, draws) {
+        let mut full = problem.clone();
+        full.queue.extend(continuation);
+        sequences += 1;
+        let solutions = solve(&full);
+        solved += usize::from(!solutions.is_empty());
+        for solution in solutions {
+            let slot = *index.entry(solution.layout.clone()).or_insert_with(|| {
+                layouts.push(LayoutShare {
+                    layout: solution.layout,
+                    sequences: 0,
+                });
+                layouts.len() - 1
+            });
+            layouts[slot].sequences += 1;
+        }
+    }
+    // Stable: equally common layouts keep the order they were first found.
+    layouts.sort_by_key(|share| std::cmp::Reverse(share.sequences));
+    PcSurvey {
+        sequences,
+        solved,
+        layouts,
+    }
+}
+
+/// A position's identity within one walk: the queue is fixed, so its length
+/// names the suffix still to come.
+type PositionKey = (BitBoard, PieceType, Option<PieceType>, usize, usize);
+
+/// One depth-first enumeration.
+struct Walk {
+    lines: usize,
+    hold_policy: HoldPolicy,
+    /// Stop at the first solution ([`solvable`]).
+    first_only: bool,
+    /// Positions with no solution below them.
+    dead: FxHashSet<PositionKey>,
+    /// The placements and layout pieces of the line being walked.
+    path: Vec<Placement>,
+    pieces: Vec<PcPiece>,
+    solutions: Vec<PcSolution>,
+    index: FxHashMap<Vec<PcPiece>, usize>,
+}
+
+impl Walk {
+    fn new(problem: &PcProblem, first_only: bool) -> Self {
+        Self {
+            lines: problem.lines,
+            hold_policy: problem.hold_policy,
+            first_only,
+            dead: FxHashSet::default(),
+            path: Vec::new(),
+            pieces: Vec::new(),
+            solutions: Vec::new(),
+            index: FxHashMap::default(),
+        }
+    }
+
+    fn run(&mut self, problem: &PcProblem) -> bool {
+        let (Some(needed), Some((&active, queue))) =
+            (problem.pieces_needed(), problem.queue.split_first())
+        else {
+            return false;
+        };
+        let state = SearchState::from_position(
+            problem.board,
+            active,
+            problem.hold,
+            queue.iter().copied(),
+            BagState::full(), // never read: the walk deals only from the queue
+        );
+        if state.dead || !pc_feasible(&state, remaining(needed)) {
+            return false;
+        }
+        let rows: Vec<isize> = (0..self.lines as isize).collect();
+        self.descend(&state, &rows, needed)
+    }
+
+    /// Walk every line from `state`, which has `left` pieces to place and
+    /// whose current rows are `rows` of the starting board. Returns whether
+    /// any line perfect-clears.
+    fn descend(&mut self, state: &SearchState, rows: &[isize], left: usize) -> bool {
+        if left == 0 {
+            if state.board.is_empty() {
+                self.record();
+                return true;
+            }
+            return false;
+        }
+        let key = (
+            state.board,
+            state.active.piece_type(),
+            state.hold,
+            state.queue.len(),
+            left,
+        );
+        if self.dead.contains(&key) {
+            return false;
+        }
+        let mut found = false;
+        let mut tried = FxHashSet::default();
+        for placement in hold_placements(state) {
+            if placement.used_hold && self.hold_policy == HoldPolicy::Forbidden {
+                continue;
+            }
+            let cells = piece_cells(&placement.piece);
+            if cells
+                .iter()
+                .any(|&(_, y)| y < 0 || y as usize >= rows.len())
+                || !tried.insert((placement.piece_type(), cells))
+            {
+                continue;
+            }
+            // An empty-hold swap spends a queue piece on top of the next spawn.
+            let spent = usize::from(placement.used_hold && state.hold.is_none());
+            if left > 1 && state.queue.len() <= spent {
+                continue; // no piece would be in play for the next placement
+            }
+            let mut child = state.clone();
+            let outcome = child.commit_placement(&placement);
+            if child.dead || !pc_feasible(&child, remaining(left - 1)) {
+                continue;
+            }
+            let mut child_rows = rows.to_vec();
+            for &y in outcome.cleared_rows.iter().rev() {
+                child_rows.remove(y as usize);
+            }
+            self.pieces.push(PcPiece {
+                piece_type: placement.piece_type(),
+                cells: cells.map(|(x, y)| (x, rows[y as usize])),
+            });
+            self.path.push(placement);
+            let hit = self.descend(&child, &child_rows, left - 1);
+            self.path.pop();
+            self.pieces.pop();
+            if hit {
+                found = true;
+                if self.first_only {
+                    return true;
+                }
+            }
+        }
+        if !found {
+            self.dead.insert(key);
+        }
+        found
+    }
+
+    /// File the current line under its layout.
+    fn record(&mut self) {
+        let mut layout = self.pieces.clone();
+        layout.sort_by_key(|piece| piece.cells[0]);
+        let slot = *self.index.entry(layout.clone()).or_insert_with(|| {
+            self.solutions.push(PcSolution {
+                layout,
+                orders: Vec::new(),
+            });
+            self.solutions.len() - 1
+        });
+        self.solutions[slot].orders.push(self.path.clone());
+    }
+}
+
+/// `pc_feasible`'s piece budget, saturated to its `u8`.
+fn remaining(pieces: usize) -> u8 {
+    u8::try_from(pieces).unwrap_or(u8::MAX)
+}
+
+/// The board cells `piece` covers, sorted by row, then column.
+fn piece_cells(piece: &ActivePiece) -> [(isize, isize); 4] {
+    let (ox, oy) = piece.origin();
+    let mut cells = piece.piece().cells().map(|(x, y)| (x + ox, y + oy));
+    cells.sort_by_key(|&(x, y)| (y, x));
+    cells
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{Board, CellKind};
+
+    /// A realistic board whose bottom rows read `rows` (top to bottom, `#`
+    /// filled).
+    fn board(rows: &[&str]) -> BitBoard {
+        let mut board = Board::with_top_margin(10, 20, 20);
+        for (depth, row) in rows.iter().enumerate() {
+            let y = (rows.len() - 1 - depth) as isize;
+            for (x, cell) in row.chars().enumerate() {
+                if cell == '#' {
+                    board.set(x as isize, y, CellKind::Garbage);
+                }
+            }
+        }
+        BitBoard::from_board(&board)
+    }
+
+    fn problem(board: BitBoard, queue: &[PieceType], lines: usize) -> PcProblem {
+        PcProblem {
+            board,
+            queue: queue.to_vec(),
+            hold: None,
+            lines,
+            hold_policy: HoldPolicy::Allowed,
+        }
+    }
+
+    /// Playing `order` from the problem's start empties the board.
+    fn clears(problem: &PcProblem, order: &[Placement]) -> bool {
+        let mut state = SearchState::from_position(
+            problem.board,
+            problem.queue[0],
+            problem.hold,
+            problem.queue[1..].iter().copied(),
+            BagState::full(),
+        );
+        for placement in order {
+            state.commit_placement(placement);
+        }
+        state.board.is_empty()
+    }
+
+    #[test]
+    fn pieces_needed_checks_height_and_cell_count() {
+        let well = board(&["#########.", "#########.", "#########.", "#########."]);
+        assert_eq!(problem(well, &[], 4).pieces_needed(), Some(1));
+        assert_eq!(problem(well, &[], 3).pieces_needed(), None, "too tall");
+        assert_eq!(problem(well, &[], 6).pieces_needed(), Some(6));
+        let odd = board(&["########.."]);
+        assert_eq!(problem(odd, &[], 1).pieces_needed(), None, "two cells");
+        assert_eq!(problem(odd, &[], 2).pieces_needed(), Some(3));
+        assert_eq!(
+            problem(BitBoard::empty(10, 20, 40), &[], 4).pieces_needed(),
+            Some(10)
+        );
+    }
+
+    /// Two O's in a 4×2 hole: one picture, built left-first or right-first.
+    #[test]
+    fn orders_of_one_picture_are_one_solution() {
+        let hole = board(&["....######", "....######"]);
+        let p = problem(hole, &[PieceType::O, PieceType::O], 2);
+        let solutions = solve(&p);
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].orders.len(), 2);
+        assert!(solutions[0].orders.iter().all(|order| clears(&p, order)));
+        assert!(solvable(&p));
+    }
+
+    /// A horizontal I on the top row and a vertical I down the well: placed
+    /// vertical-first, the first clear drops the top row to the floor, yet the
+    /// layout still reads it in its starting row.
+    #[test]
+    fn layouts_read_cells_in_the_starting_rows() {
+        let top = board(&[".#####....", ".#########", ".#########", ".#########"]);
+        let p = PcProblem {
+            hold_policy: HoldPolicy::Forbidden,
+            ..problem(top, &[PieceType::I, PieceType::I], 4)
+        };
+        let solutions = solve(&p);
+        assert_eq!(solutions.len(), 1);
+        assert_eq!(solutions[0].orders.len(), 2, "either I first");
+        let rows: Vec<isize> = solutions[0]
+            .layout
+            .iter()
+            .flat_map(|piece| piece.cells.map(|(_, y)| y))
+            .collect();
+        assert_eq!(rows.iter().filter(|&&y| y == 3).count(), 5);
+        assert!(solutions[0].orders.iter().all(|order| clears(&p, order)));
+    }
+
+    #[test]
+    fn the_hold_policy_decides_a_held_solution() {
+        let hole = board(&["....######", "....######"]);
+        let queue = [PieceType::S, PieceType::O, PieceType::O];
+        let with_hold = problem(hole, &queue, 2);
+        let solutions = solve(&with_hold);
+        assert_eq!(solutions.len(), 1);
+        assert!(solutions[0].orders.iter().all(|order| order[0].used_hold));
+        assert!(
+            solutions[0]
+                .orders
+                .iter()
+                .all(|order| clears(&with_hold, order))
+        );
+        let without = PcProblem {
+            hold_policy: HoldPolicy::Forbidden,
+            ..with_hold
+        };
+        assert!(solve(&without).is_empty());
+        assert!(!solvable(&without));
+    }
+
+    /// A four-deep I well: with hold, an I in either of the first two pieces
+    /// clears (12 of the 7·6 sequences); without, only an I first (1 in 7).
+    #[test]
+    fn survey_counts_sequences_exactly() {
+        let well = board(&["#########.", "#########.", "#########.", "#########."]);
+        let held = survey(&problem(well, &[], 4), BagState::full());
+        assert_eq!((held.solved, held.sequences), (12, 42));
+        assert_eq!(held.layouts.len(), 1);
+        assert_eq!(held.layouts[0].sequences, 12);
+
+        let bare = PcProblem {
+            hold_policy: HoldPolicy::Forbidden,
+            ..problem(well, &[], 4)
+        };
+        let first_only = survey(&bare, BagState::full());
+        assert_eq!((first_only.solved, first_only.sequences), (1, 7));
+
+        // A known T leads: the one unknown draw comes from the bag T left.
+        let mut bag = BagState::full();
+        bag.deal(PieceType::T);
+        let led = survey(&problem(well, &[PieceType::T], 4), bag);
+        assert_eq!((led.solved, led.sequences), (1, 6));
+        assert!((led.success_rate() - 1.0 / 6.0).abs() < 1e-12);
+    }
+}
@@ -136,7 +136,7 @@ fn hold_frame() -> InputFrame {
 /// Advance the shadow `piece` one lateral cell if the engine would allow it —
 /// **movegen's own `shift`**, so the renderer's pose tracking can never drift
 /// from what the search enumerated. A blocked shift is a no-op, like the engine.
-fn step_lateral(board: &Board, piece: &mut ActivePiece, dir: MoveDirection) {
+pub(super) fn step_lateral(board: &Board, piece: &mut ActivePiece, dir: MoveDirection) {
     if let Some(moved) = crate::ai::movegen::shift(board, piece, dir) {
         *piece = moved;
     }
@@ -144,7 +144,7 @@ fn step_lateral(board: &Board, piece: &mut ActivePiece, dir: MoveDirection) {
 
 /// Direction of an SRS rotation, mirroring [`Move::Cw`] / [`Move::Ccw`].
 #[derive(Clone, Copy)]
-enum RotationDir {
+pub(super) enum RotationDir {
     Cw,
     Ccw,
 }
@@ -152,7 +152,7 @@ enum RotationDir {
 /// Advance the shadow `piece` by one SRS rotation — **movegen's own `rotate`**
 /// (kicks, no-op suppression and all), so the pose the renderer replays is by
 /// construction the pose the search enumerated. A no-op stays a no-op.
-fn rotate(piece: &mut ActivePiece, board: &Board, dir: RotationDir) {
+pub(super) fn rotate(piece: &mut ActivePiece, board: &Board, dir: RotationDir) {
     use crate::engine::RotationDirection;
     let engine_dir = match dir {
         RotationDir::Cw => RotationDirection::Clockwise,
@@ -166,7 +166,7 @@ fn rotate(piece: &mut ActivePiece, board: &Board, dir: RotationDir) {
 /// Drop the shadow `piece` straight to the floor, returning how many cells it fell
 /// (the number of soft-drop pulses a sonic drop expands to). Uses the engine's
 /// `try_move(.., Down)`, the same primitive movegen's `soft_drop` uses.
-fn drop_to_floor(board: &Board, piece: &mut ActivePiece) -> usize {
+pub(super) fn drop_to_floor(board: &Board, piece: &mut ActivePiece) -> usize {
     let mut cells = 0;
     while let Some(origin) = piece
         .piece()
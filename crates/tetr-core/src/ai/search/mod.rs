@@ -40,10 +40,12 @@
 
 pub mod beam;
 pub mod best_first;
+pub mod opener_book;
 pub mod pc_coverage;
 
 pub use beam::BeamPlanner;
 pub use best_first::BestFirstPlanner;
+pub use opener_book::OpenerBookPlanner;
 pub use pc_coverage::{PcCoverageConfig, PcCoveragePlanner, PcCoverageUnit};
 
 use smallvec::SmallVec;
@@ -0,0 +1,127 @@
+//! The opener book as a subgoal layer over another mind.
+//!
+//! [`OpenerBookPlanner`] answers a decision from the [`OpenerBook`] while the
+//! board is on one of its setups and that setup can still be finished with
+//! the known pieces, and hands every other decision to the wrapped mind. A
+//! book decision costs no search: [`Mind::think`] reports
+//! [`ThinkProgress::Exhausted`] at once and [`Mind::best`] returns the book's
+//! placement at [`BOOK_PLAN_SCORE`]. Pending garbage always goes to the
+//! search — a rising row ends any setup, and the book does not know how to
+//! defend.
+
+use super::{Mind, PlacementPlan, RootKey, ThinkProgress};
+use crate::ai::eval::Evaluator;
+use crate::ai::state::SearchState;
+use crate::opener::OpenerBook;
+
+/// Synthetic score for a plan served from the book — the decision is the
+/// book's, not an evaluation.
+const BOOK_PLAN_SCORE: i32 = 0;
+
+/// A [`Mind`] that plays the opener book first and `inner` after it.
+pub struct OpenerBookPlanner {
+    book: OpenerBook,
+    inner: Box<dyn Mind>,
+    /// The root the book was last asked about, and its answer.
+    root: Option<RootKey>,
+    served: Option<PlacementPlan>,
+}
+
+impl OpenerBookPlanner {
+    pub fn new(book: OpenerBook, inner: Box<dyn Mind>) -> Self {
+        Self {
+            book,
+            inner,
+            root: None,
+            served: None,
+        }
+    }
+}
+
+impl Mind for OpenerBookPlanner {
+    fn reroot(&mut self, state: &SearchState, eval: &dyn Evaluator, max_depth: u8) {
+        let root = RootKey::of(state);
+        if self.root.as_ref() != Some(&root) {
+            self.served = if state.pending.is_empty() {
+                self.book
+                    .next_placement(state)
+                    .map(|placement| PlacementPlan {
+                        placement,
+                        score: BOOK_PLAN_SCORE,
+                    })
+            } else {
+                None
+            };
+            self.root = Some(root);
+        }
+        if self.served.is_none() {
+            self.inner.reroot(state, eval, max_depth);
+        }
+    }
+
+    fn think(&mut self, quantum: u32, eval: &dyn Evaluator) -> ThinkProgress {
+        if self.served.is_some() {
+            return ThinkProgress::Exhausted; // the book answered: nothing to search
+        }
+        self.inner.think(quantum, eval)
+    }
+
+    fn best(&self) -> Option<PlacementPlan> {
+        self.served.clone().or_else(|| self.inner.best())
+    }
+
+    fn nodes_expanded(&self) -> u32 {
+        if self.served.is_some() {
+            0
+        } else {
+            self.inner.nodes_expanded()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::eval::LinearEvaluator;
+    use crate::ai::movegen::spawn_piece;
+    use crate::ai::search::{BeamPlanner, SearchBudget, think_to_completion};
+    use crate::engine::{Board, CellKind, PieceType};
+
+    fn state(board: Board, active: PieceType, queue: &[PieceType]) -> SearchState {
+        SearchState::for_test(
+            board,
+            spawn_piece(active, 10, 20),
+            None,
+            queue.iter().copied(),
+        )
+    }
+
+    #[test]
+    fn serves_the_book_on_track_and_searches_off_it() {
+        use PieceType::*;
+        let eval = LinearEvaluator::default();
+        let book = OpenerBook::standard();
+        let mut planner = OpenerBookPlanner::new(book.clone(), Box::new(BeamPlanner::new(16)));
+
+        let opening = state(Board::new(10, 20), I, &[O, L, S, Z, J]);
+        let plan = think_to_completion(&mut planner, &opening, &eval, SearchBudget::beam(2))
+            .expect("the book has an opening move");
+        assert_eq!(plan.score, BOOK_PLAN_SCORE);
+        assert_eq!(planner.nodes_expanded(), 0, "a book move costs no search");
+        let tki = &book.openers()[0].setups()[0];
+        assert!(
+            tki.targets()
+                .iter()
+                .any(|target| target.matches(&plan.placement))
+        );
+
+        // A cell no setup accounts for hands the decision to the search.
+        let mut board = Board::new(10, 20);
+        board.set(4, 0, CellKind::Some(T));
+        let off_book = state(board, I, &[O, L, S]);
+        let plan = think_to_completion(&mut planner, &off_book, &eval, SearchBudget::beam(2))
+            .expect("the search plans");
+        assert!(planner.nodes_expanded() > 0);
+        assert_ne!(plan.score, BOOK_PLAN_SCORE);
+    }
+}
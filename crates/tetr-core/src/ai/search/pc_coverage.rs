@@ -1217,7 +1217,7 @@ fn board_cells_and_height(state: &SearchState) -> (usize, usize) {
 /// count makes the total cells a whole number of rows, and the current stack
 /// is no taller than the rows that would all clear. Never prunes a reachable
 /// PC (both conditions hold on every true PC line).
-fn pc_feasible(state: &SearchState, remaining: u8) -> bool {
+pub(crate) fn pc_feasible(state: &SearchState, remaining: u8) -> bool {
     if state.board.is_empty() {
         return true;
     }
@@ -1307,7 +1307,7 @@ fn continuation_sample_stratified(bag: BagState, draws: usize, cap: usize) -> Ve
 /// materialized before sampling truncates it; with the engine's 5-piece
 /// preview and the registered horizon-10 arms this is ≤ 2,520 sequences —
 /// check this bound before raising the horizon or shrinking the preview.
-fn enumerate_continuations(bag: BagState, draws: usize) -> Vec<Vec<PieceType>> {
+pub(crate) fn enumerate_continuations(bag: BagState, draws: usize) -> Vec<Vec<PieceType>> {
     fn recurse(
         bag: BagState,
         draws: usize,
@@ -197,20 +197,86 @@ impl SearchState {
     /// over), since a search has nothing to plan from in that case.
     pub fn from_snapshot(snapshot: &EngineSnapshot) -> Option<Self> {
         let active_snapshot = snapshot.active.as_ref()?;
+        let queue = snapshot.next_queue.iter().copied().collect();
+        Some(Self::from_parts(
+            snapshot,
+            rebuild_active(active_snapshot),
+            queue,
+        ))
+    }
 
-        let config = &snapshot.config;
-        let board = BitBoard::from_board(&rebuild_board(snapshot));
-        let active = rebuild_active(active_snapshot);
+    /// Build the decision state **before the first spawn**: a fresh engine's
+    /// snapshot has no active piece yet (the engine deals it at the top of its
+    /// first [`step`](crate::engine::Engine::step); every later piece is dealt
+    /// inside the locking step). The queue front is spawned at its guideline
+    /// origin, exactly the piece that step deals;
+    /// the exported bag remainder is already net of it, so the bag is untouched
+    /// (the module's queue-spawn convention).
+    ///
+    /// Returns `None` on a finished game, a snapshot that already has an active
+    /// piece (use [`from_snapshot`](Self::from_snapshot)), or an empty queue.
+    /// A spawn that would block out yields a `dead` state, as in the search.
+    pub fn at_next_spawn(snapshot: &EngineSnapshot) -> Option<Self> {
+        if snapshot.game_over.is_some() || snapshot.active.is_some() {
+            return None;
+        }
+        let (&front, rest) = snapshot.next_queue.split_first()?;
+        let mut state = Self::from_parts(
+            snapshot,
+            ActivePiece::new(front, (0, 0)),
+            rest.iter().copied().collect(),
+        );
+        state.spawn(front);
+        Some(state)
+    }
+
+    /// Build the decision state for a bare position with no engine behind it:
+    /// `board` with `active` spawned at its guideline origin, the `hold` slot,
+    /// the revealed `queue`, and the `bag` remainder past it. No B2B or combo
+    /// chain and no pending garbage; the garbage cap is the engine default.
+    /// The entry point for position solvers ([`crate::ai::pc_solver`]). A spawn
+    /// that would block out yields a `dead` state, as in the search.
+    pub fn from_position(
+        board: BitBoard,
+        active: crate::engine::PieceType,
+        hold: Option<crate::engine::PieceType>,
+        queue: impl IntoIterator<Item = crate::engine::PieceType>,
+        bag: BagState,
+    ) -> Self {
+        let mut state = Self {
+            board,
+            active: ActivePiece::new(active, (0, 0)),
+            hold,
+            queue: queue.into_iter().collect(),
+            bag,
+            b2b: false,
+            combo: 0,
+            dead: false,
+            pending: BatchQueue::new(),
+            garbage_cap: crate::engine::EngineConfig::default().garbage_cap,
+            board_width: board.width(),
+            visible_height: board.height(),
+        };
+        state.spawn(active);
+        state
+    }
 
-        let queue: SmallVec<[crate::engine::PieceType; 16]> =
-            snapshot.next_queue.iter().copied().collect();
+    /// The shared body of the two snapshot constructors: everything but the
+    /// active piece and the revealed queue comes straight off the snapshot.
+    fn from_parts(
+        snapshot: &EngineSnapshot,
+        active: ActivePiece,
+        queue: SmallVec<[crate::engine::PieceType; 16]>,
+    ) -> Self {
+        let config = &snapshot.config;
+        let board = board_from_snapshot(snapshot);
 
         // The engine exports its generator's current-bag remainder directly —
         // already net of every dealt piece (active, queue, and any held piece), so
         // no reconstruction or hold special-casing is needed here.
         let bag = BagState::from_pieces(snapshot.bag_remainder.iter().copied());
 
-        Some(Self {
+        Self {
             board,
             active,
             hold: snapshot.hold,
@@ -218,12 +284,12 @@ impl SearchState {
             bag,
             b2b: snapshot.back_to_back_active,
             combo: snapshot.combo, // resume the real in-game combo, so the search can value continuing it
-            dead: false,           // a snapshot with an active piece is a live game
+            dead: false,           // a live game (`at_next_spawn` re-checks its spawn)
             pending: snapshot.pending_garbage.iter().copied().collect(),
             garbage_cap: config.garbage_cap,
             board_width: config.board_width,
             visible_height: config.visible_height,
-        })
+        }
     }
 
     /// Lock the active piece at its current pose and advance to the next piece.
@@ -525,6 +591,12 @@ impl SearchState {
     }
 }
 
+/// The snapshot's playfield as a search board — the occupancy the analysis pass
+/// compares a played lock against.
+pub(crate) fn board_from_snapshot(snapshot: &EngineSnapshot) -> BitBoard {
+    BitBoard::from_board(&rebuild_board(snapshot))
+}
+
 /// Rebuild the playfield (margin included) from the snapshot's occupied cells.
 fn rebuild_board(snapshot: &EngineSnapshot) -> Board {
     let config = &snapshot.config;
@@ -631,6 +703,21 @@ mod tests {
         assert!(SearchState::from_snapshot(&snapshot).is_none());
     }
 
+    #[test]
+    fn at_next_spawn_deals_the_piece_the_first_step_spawns() {
+        let mut engine = Engine::new(EngineConfig::default(), 7);
+        let before = engine.snapshot();
+        let state = SearchState::at_next_spawn(&before).expect("a fresh engine has a queue");
+        engine.step(InputFrame::default());
+        let after = engine.snapshot();
+        let spawned = SearchState::from_snapshot(&after).unwrap();
+        assert_eq!(state.active.piece_type(), spawned.active.piece_type());
+        assert_eq!(state.queue.as_slice(), &before.next_queue[1..]);
+        assert!(!state.dead);
+        // Once a piece is active the spawn-point constructor declines.
+        assert!(SearchState::at_next_spawn(&after).is_none());
+    }
+
     #[test]
     fn from_snapshot_preserves_rotation() {
         let mut engine = Engine::new(EngineConfig::default(), 1);
@@ -13,17 +13,24 @@ use crate::engine::RotationDirection;
 use crate::engine::active_piece::ActivePiece;
 use crate::engine::attack::attack_lines;
 use crate::engine::board::{Board, CellKind};
+use crate::engine::dig::DigState;
 use crate::engine::game_over::{is_block_out, is_lock_out};
 use crate::engine::garbage::PendingGarbage;
 use crate::engine::generator::PieceGenerator;
-use crate::engine::gravity::fall_speed_seconds;
+use crate::engine::gravity::{Gravity, fall_speed_seconds};
+use crate::engine::lock_age::LockAges;
 use crate::engine::lock_clear::lock_and_clear;
-use crate::engine::lock_down::apply_grounded_move_or_rotation;
+use crate::engine::lock_down::{apply_grounded_move_or_rotation, lock_down_seconds_for_level};
+use crate::engine::master::MasterState;
 use crate::engine::pieces::{MoveDirection, Piece, PieceRotation, PieceType};
 use crate::engine::scoring::{EngineScoreAction, ScoreAward, ScoreState, score_action};
 use crate::engine::t_spin::{TSpinKind, classify_t_spin, is_t_slot};
 use crate::engine::types::*;
 
+/// `Clone` is a complete save state: the clone carries the bag and hole-stream
+/// RNGs mid-sequence, so stepping it with the same frames reproduces the
+/// original exactly (rollback netcode restores engines this way).
+#[derive(Clone)]
 pub struct Engine {
     config: EngineConfig,
     board: Board,
@@ -36,6 +43,12 @@ pub struct Engine {
     gravity_accumulator_seconds: f32,
     /// Versus: incoming garbage queued against this player (see `garbage.rs`).
     garbage: PendingGarbage,
+    /// Dig: the cheese still to clear (see `dig.rs`).
+    dig: Option<DigState>,
+    /// Master: the level counter, sections and grade (see `master.rs`).
+    master: Option<MasterState>,
+    /// When each settled cell arrived (see `lock_age.rs`).
+    lock_ages: LockAges,
 }
 
 impl Engine {
@@ -53,19 +66,46 @@ impl Engine {
         let board =
             Board::with_top_margin(config.board_width, config.visible_height, BUFFER_HEIGHT);
         let score_state = ScoreState::new(config.goal_system, config.starting_level);
+        let master = config.master.then(MasterState::new);
+        let lock_ages = LockAges::new(config.board_width, board.backing_rows());
+        let generator = match &config.scenario {
+            Some(scenario) => PieceGenerator::with_sequence(seed, &scenario.sequence),
+            None => PieceGenerator::with_seed(seed),
+        };
         let mut engine = Self {
             config,
             board,
             active: None,
-            generator: PieceGenerator::with_seed(seed),
+            generator,
             next_queue: Vec::new(),
             hold: None,
             score_state,
             game_over: None,
             gravity_accumulator_seconds: 0.0,
             garbage: PendingGarbage::new(seed),
+            dig: None,
+            master,
+            lock_ages,
         };
         engine.fill_next_queue();
+        if let Some(scenario) = engine.config.scenario.clone() {
+            for (x, y, cell) in scenario.cells {
+                engine.set_cell(x, y, cell);
+            }
+            engine.hold = scenario.hold;
+            if scenario.garbage > 0 {
+                engine.queue_garbage(scenario.garbage);
+            }
+        }
+        if let Some(dig) = engine.config.dig {
+            // The opening cheese goes in through the garbage seam, a row at a
+            // time (nothing is active yet, so nothing can be buried).
+            let mut state = DigState::new(dig, seed);
+            for hole in state.refill(engine.config.board_width) {
+                engine.insert_garbage(1, hole);
+            }
+            engine.dig = Some(state);
+        }
         engine
     }
 
@@ -74,6 +114,10 @@ impl Engine {
         if self.game_over.is_some() {
             return events;
         }
+        self.lock_ages.advance(input.dt_seconds.max(0.0));
+        if let Some(master) = &mut self.master {
+            master.advance(input.dt_seconds.max(0.0));
+        }
 
         if self.active.is_none() {
             self.spawn_next_piece(&mut events);
@@ -100,10 +144,14 @@ impl Engine {
             self.rotate_active_piece(RotationDirection::Counterclockwise, &mut events);
         }
 
-        match (input.left, input.right) {
-            (true, false) => self.move_active_piece(MoveDirection::Left, &mut events),
-            (false, true) => self.move_active_piece(MoveDirection::Right, &mut events),
-            _ => {}
+        let shift = match (input.left, input.right) {
+            (true, false) => Some(MoveDirection::Left),
+            (false, true) => Some(MoveDirection::Right),
+            _ => None,
+        };
+        if let Some(direction) = shift {
+            // An instant shift keeps going until the piece is stopped.
+            while self.move_active_piece(direction, &mut events) && input.instant_shift {}
         }
 
         if input.soft_drop {
@@ -119,10 +167,11 @@ impl Engine {
         EngineSnapshot {
             config: self.config.clone(),
             board_cells: self.board_snapshot_cells(),
+            board_cell_ages: self.board_cell_ages(),
             active: self
                 .active
                 .as_ref()
-                .map(|active| active_piece_snapshot(active, &self.config)),
+                .map(|active| active_piece_snapshot(active, self.lock_seconds())),
             ghost_cells: self.ghost_snapshot_cells(),
             hold: self.hold,
             next_queue: self.next_queue.clone(),
@@ -134,10 +183,20 @@ impl Engine {
             combo: self.score_state.combo(),
             bag_remainder: self.generator.bag_remainder().to_vec(),
             pending_garbage: self.garbage.batches().collect(),
+            dig_remaining: self.dig.as_ref().map(DigState::remaining),
+            master: self.master.as_ref().map(MasterState::snapshot),
             game_over: self.game_over,
         }
     }
 
+    /// The `count` pieces the engine will deal after its [`next
+    /// queue`](EngineSnapshot::next_queue), in order, without dealing them.
+    /// Hidden information in a real game; Practice mode reveals the next bag
+    /// with it.
+    pub fn upcoming_pieces(&self, count: usize) -> Vec<PieceType> {
+        self.generator.upcoming(count)
+    }
+
     /// True iff the playfield is empty — the perfect-clear test. Cheap: delegates to
     /// [`Board::is_empty`], which short-circuits and allocates nothing, so sim loops
     /// can check it per line clear without building a full [`snapshot`](Self::snapshot).
@@ -156,6 +215,7 @@ impl Engine {
     /// because it is a harness seam, not part of the game-facing API.
     #[doc(hidden)]
     pub fn set_cell(&mut self, x: isize, y: isize, cell: CellKind) -> bool {
+        self.lock_ages.stamp([(x, y)]);
         self.board.set(x, y, cell)
     }
 
@@ -182,6 +242,7 @@ impl Engine {
             return true;
         }
         let overflow = self.board.insert_garbage_lines(count, hole_col);
+        self.lock_ages.raised(count);
         let buried = self
             .active
             .as_ref()
@@ -260,6 +321,11 @@ impl Engine {
     }
 
     fn spawn_next_piece(&mut self, events: &mut Vec<EngineEvent>) {
+        // A piece from the queue climbs the Master counter before it spawns,
+        // so it falls at the new level's speed (a hold swap does not count).
+        if let Some(master) = &mut self.master {
+            master.piece_spawned();
+        }
         let piece_type = self.pop_next_piece_type();
         self.spawn_piece_type(piece_type, false, events);
     }
@@ -288,9 +354,18 @@ impl Engine {
         if hold_used {
             active.mark_hold_used();
         }
-        update_landing_state(&self.board, &self.config, &mut active, false, false);
+        update_landing_state(
+            &self.board,
+            &self.config,
+            self.lock_seconds(),
+            &mut active,
+            false,
+            false,
+        );
         self.active = Some(active);
         self.gravity_accumulator_seconds = 0.0;
+        // Under 20G the piece spawns already on the floor.
+        self.sink_under_twenty_g();
     }
 
     fn hold_active_piece(&mut self, events: &mut Vec<EngineEvent>) {
@@ -313,16 +388,22 @@ impl Engine {
         }
     }
 
-    fn move_active_piece(&mut self, direction: MoveDirection, events: &mut Vec<EngineEvent>) {
+    /// Move the active piece one cell; `false` if it could not move.
+    fn move_active_piece(
+        &mut self,
+        direction: MoveDirection,
+        events: &mut Vec<EngineEvent>,
+    ) -> bool {
+        let lock_seconds = self.lock_seconds();
         let Some(active) = self.active.as_mut() else {
-            return;
+            return false;
         };
         let was_landed = active.landed();
         let Some(origin) = active
             .piece()
             .try_move(&self.board, active.origin(), direction)
         else {
-            return;
+            return false;
         };
 
         let action = match direction {
@@ -333,6 +414,7 @@ impl Engine {
         update_landing_state(
             &self.board,
             &self.config,
+            lock_seconds,
             active,
             was_landed,
             matches!(direction, MoveDirection::Left | MoveDirection::Right),
@@ -340,10 +422,15 @@ impl Engine {
         if direction == MoveDirection::Down {
             self.gravity_accumulator_seconds = 0.0;
             self.score(EngineScoreAction::SoftDrop, events);
+        } else {
+            // Shifted off a ledge under 20G: straight down.
+            self.sink_under_twenty_g();
         }
+        true
     }
 
     fn rotate_active_piece(&mut self, direction: RotationDirection, events: &mut Vec<EngineEvent>) {
+        let lock_seconds = self.lock_seconds();
         let Some(active) = self.active.as_mut() else {
             return;
         };
@@ -382,13 +469,21 @@ impl Engine {
             kick_number,
             entered_t_slot_with_kick_5,
         );
-        update_landing_state(&self.board, &self.config, active, was_landed, true);
+        update_landing_state(
+            &self.board,
+            &self.config,
+            lock_seconds,
+            active,
+            was_landed,
+            true,
+        );
         events.push(EngineEvent::Rotated {
             piece_type: active.piece_type(),
             rotation,
             origin,
             kick_number,
         });
+        self.sink_under_twenty_g();
     }
 
     fn hard_drop_active_piece(&mut self, events: &mut Vec<EngineEvent>) {
@@ -427,6 +522,15 @@ impl Engine {
 
         let outcome = lock_and_clear(&active, &mut self.board);
         let lines_cleared = outcome.cleared_rows.len();
+        self.lock_ages
+            .stamp(outcome.cells_locked.iter().map(|&(x, y, _)| (x, y)));
+        self.lock_ages.cleared(&outcome.cleared_rows);
+        if let Some(dig) = &mut self.dig {
+            dig.cleared(&outcome.cleared_rows);
+        }
+        if let Some(master) = &mut self.master {
+            master.lines_cleared(lines_cleared);
+        }
 
         events.push(EngineEvent::Locked {
             piece_type,
@@ -436,7 +540,16 @@ impl Engine {
         // (the same pre-increment convention the research harness pinned), so
         // capture it before scoring mutates the chain state.
         let combo_before = self.score_state.combo();
+        let level_before = self.score_state.level();
         let award = self.score_lock_result(t_spin, lines_cleared, events);
+        if self.config.perfect_clear_bonus && lines_cleared > 0 && self.board.is_empty() {
+            // Scored at the clear's own level, like the clear itself.
+            let back_to_back = award.is_some_and(|award| award.back_to_back_bonus);
+            let (score, total_score) =
+                self.score_state
+                    .perfect_clear(lines_cleared, back_to_back, level_before);
+            events.push(EngineEvent::PerfectClearBonus { score, total_score });
+        }
 
         if lock_out {
             self.game_over = Some(GameOverStatus::LockOut);
@@ -469,11 +582,15 @@ impl Engine {
         // Versus: pending garbage rises after a lock that cleared nothing —
         // between lock and spawn, so a fatal rise is an ordinary block-out for
         // the next spawn (or an outright overflow here).
+        // Dig: the cheese reserve rises to replace the garbage rows this
+        // lock cleared, at the same point in the lock.
         if lines_cleared == 0 {
             self.rise_pending_garbage(events);
-            if self.game_over.is_some() {
-                return;
-            }
+        } else {
+            self.refill_cheese(events);
+        }
+        if self.game_over.is_some() {
+            return;
         }
 
         self.spawn_next_piece(events);
@@ -492,6 +609,7 @@ impl Engine {
             overflow |= self
                 .board
                 .insert_garbage_lines(batch.lines as usize, batch.hole_col);
+            self.lock_ages.raised(batch.lines as usize);
             inserted += batch.lines;
         }
         if inserted > 0 {
@@ -505,6 +623,35 @@ impl Engine {
         }
     }
 
+    /// Dig: raise the cheese back up to its visible height (see `dig.rs`)
+    /// through the same board primitive, emitting
+    /// [`EngineEvent::GarbageInserted`]. The rows replace cleared ones, so
+    /// the stack is never higher than before the lock — the overflow check
+    /// is belt and braces.
+    fn refill_cheese(&mut self, events: &mut Vec<EngineEvent>) {
+        let Some(dig) = &mut self.dig else {
+            return;
+        };
+        let holes = dig.refill(self.config.board_width);
+        if holes.is_empty() {
+            return;
+        }
+        let mut overflow = false;
+        for &hole in &holes {
+            overflow |= self.board.insert_garbage_lines(1, hole);
+            self.lock_ages.raised(1);
+        }
+        events.push(EngineEvent::GarbageInserted {
+            lines: holes.len() as u32,
+        });
+        if overflow {
+            self.game_over = Some(GameOverStatus::BlockOut);
+            events.push(EngineEvent::GameOver {
+                reason: GameOverStatus::BlockOut,
+            });
+        }
+    }
+
     fn score_lock_result(
         &mut self,
         t_spin: Option<TSpinKind>,
@@ -556,8 +703,15 @@ impl Engine {
     }
 
     fn advance_gravity(&mut self, dt_seconds: f32) {
+        let fall_seconds = match self.gravity() {
+            Gravity::SecondsPerRow(seconds) => seconds,
+            Gravity::TwentyG => {
+                self.sink_under_twenty_g();
+                return;
+            }
+        };
+        let lock_seconds = self.lock_seconds();
         self.gravity_accumulator_seconds += dt_seconds;
-        let fall_seconds = fall_speed_seconds(self.score_state.level());
 
         while self.gravity_accumulator_seconds >= fall_seconds {
             self.gravity_accumulator_seconds -= fall_seconds;
@@ -570,13 +724,27 @@ impl Engine {
                     .piece()
                     .try_move(&self.board, active.origin(), MoveDirection::Down)
             else {
-                update_landing_state(&self.board, &self.config, active, false, false);
+                update_landing_state(
+                    &self.board,
+                    &self.config,
+                    lock_seconds,
+                    active,
+                    false,
+                    false,
+                );
                 self.gravity_accumulator_seconds = 0.0;
                 return;
             };
 
             active.move_to(origin, crate::engine::PieceAction::Fall);
-            update_landing_state(&self.board, &self.config, active, false, false);
+            update_landing_state(
+                &self.board,
+                &self.config,
+                lock_seconds,
+                active,
+                false,
+                false,
+            );
             if active.landed() {
                 self.gravity_accumulator_seconds = 0.0;
                 return;
@@ -584,6 +752,60 @@ impl Engine {
         }
     }
 
+    /// The fall speed in force: a Master run's, otherwise the guideline
+    /// curve at the current level.
+    fn gravity(&self) -> Gravity {
+        match &self.master {
+            Some(master) => master.gravity(),
+            None => Gravity::SecondsPerRow(fall_speed_seconds(self.score_state.level())),
+        }
+    }
+
+    /// The lock delay in force: a Master run's section delay, otherwise the
+    /// config's curve at the current level.
+    fn lock_seconds(&self) -> f32 {
+        match &self.master {
+            Some(master) => master.lock_seconds(),
+            None => lock_down_seconds_for_level(
+                self.config.lock_delay_curve,
+                self.config.lock_down_seconds,
+                self.score_state.level(),
+            ),
+        }
+    }
+
+    /// 20G: drop the active piece to the floor. Nothing hangs in the air —
+    /// the piece lands the moment it spawns, or shifts or rotates off a
+    /// ledge (a fresh landing, so the lock timer starts over).
+    fn sink_under_twenty_g(&mut self) {
+        if self.gravity() != Gravity::TwentyG {
+            return;
+        }
+        let lock_seconds = self.lock_seconds();
+        let Some(active) = self.active.as_mut() else {
+            return;
+        };
+        let mut fell = false;
+        while let Some(origin) =
+            active
+                .piece()
+                .try_move(&self.board, active.origin(), MoveDirection::Down)
+        {
+            active.move_to(origin, crate::engine::PieceAction::Fall);
+            fell = true;
+        }
+        if fell {
+            update_landing_state(
+                &self.board,
+                &self.config,
+                lock_seconds,
+                active,
+                false,
+                false,
+            );
+        }
+    }
+
     fn board_snapshot_cells(&self) -> Vec<SnapshotCell> {
         self.board
             .cells()
@@ -606,6 +828,16 @@ impl Engine {
             .collect()
     }
 
+    /// Parallel to [`board_snapshot_cells`](Self::board_snapshot_cells): the
+    /// same cells in the same order.
+    fn board_cell_ages(&self) -> Vec<f32> {
+        self.board
+            .cells()
+            .into_iter()
+            .map(|(x, y, _)| self.lock_ages.age(x, y))
+            .collect()
+    }
+
     fn ghost_snapshot_cells(&self) -> Vec<SnapshotCell> {
         let Some(active) = self.active.as_ref() else {
             return Vec::new();
@@ -623,9 +855,9 @@ impl Engine {
     }
 }
 
-fn active_piece_snapshot(active: &ActivePiece, config: &EngineConfig) -> ActivePieceSnapshot {
+fn active_piece_snapshot(active: &ActivePiece, lock_seconds: f32) -> ActivePieceSnapshot {
     let lock_timer_fraction = if active.lock_timer_active() {
-        (active.lock_timer_seconds() / config.lock_down_seconds).clamp(0.0, 1.0)
+        (active.lock_timer_seconds() / lock_seconds).clamp(0.0, 1.0)
     } else {
         0.0
     };
@@ -665,6 +897,7 @@ fn active_is_grounded(board: &Board, active: &ActivePiece) -> bool {
 fn update_landing_state(
     board: &Board,
     config: &EngineConfig,
+    lock_seconds: f32,
     active: &mut ActivePiece,
     was_landed: bool,
     grounded_move_or_rotation: bool,
@@ -676,9 +909,9 @@ fn update_landing_state(
 
     if !was_landed {
         active.mark_landed();
-        active.reset_lock_timer(config.lock_down_seconds);
+        active.reset_lock_timer(lock_seconds);
     } else if grounded_move_or_rotation {
-        apply_grounded_move_or_rotation(active, config.lock_down_mode, config.lock_down_seconds);
+        apply_grounded_move_or_rotation(active, config.lock_down_mode, lock_seconds);
     }
 }
 
@@ -1709,4 +1942,159 @@ mod tests {
         };
         assert_eq!(board_after(42), board_after(42), "same seed, same holes");
     }
+
+    /// A clone is a save state: restored mid-game (pieces dealt, garbage
+    /// queued), it replays the same frames into the same game — bag, hole
+    /// stream and all.
+    #[test]
+    fn a_cloned_engine_replays_identically() {
+        let tick = InputFrame {
+            dt_seconds: 1.0 / 60.0,
+            ..InputFrame::default()
+        };
+        let drop = InputFrame {
+            hard_drop: true,
+            ..tick.clone()
+        };
+        let mut engine = Engine::new(EngineConfig::default(), 9);
+        for _ in 0..3 {
+            engine.step(tick.clone());
+            engine.step(drop.clone());
+        }
+        let saved = engine.clone();
+        let script = |engine: &mut Engine| {
+            engine.queue_garbage(2);
+            for _ in 0..8 {
+                engine.step(tick.clone());
+                engine.step(drop.clone());
+            }
+            engine.snapshot()
+        };
+        let played = script(&mut engine);
+        assert_eq!(script(&mut saved.clone()), played);
+        assert_ne!(saved.snapshot(), played, "the save is not aliased");
+    }
+
+    #[test]
+    fn dig_starts_on_cheese_and_refills_what_a_clear_digs_out() {
+        let config = EngineConfig {
+            dig: Some(crate::engine::DigConfig {
+                lines: 12,
+                messy: true,
+            }),
+            ..EngineConfig::default()
+        };
+        let mut engine = Engine::new(config, 7);
+        let holes_per_row = |engine: &Engine| {
+            (0..12)
+                .map(|y| {
+                    (0..10)
+                        .filter(|&x| engine.board.get_cell_kind(x, y) == CellKind::None)
+                        .count()
+                })
+                .collect::<Vec<_>>()
+        };
+        let cheese = [vec![1; 10], vec![10; 2]].concat();
+        assert_eq!(holes_per_row(&engine), cheese);
+        assert_eq!(engine.snapshot().dig_remaining, Some(12));
+
+        // Plug the bottom row's hole; the next lock clears it.
+        let hole = (0..10)
+            .find(|&x| engine.board.get_cell_kind(x, 0) == CellKind::None)
+            .unwrap();
+        engine.set_cell(hole, 0, CellKind::Some(PieceType::J));
+        let events = lock_piece(&mut engine, ActivePiece::new(PieceType::O, (4, 10)));
+        assert!(events.contains(&EngineEvent::GarbageInserted { lines: 1 }));
+        assert_eq!(engine.snapshot().dig_remaining, Some(11));
+        // One row of the reserve rose beneath the stack to replace it.
+        let holes = holes_per_row(&engine);
+        assert_eq!(holes[..10], [1; 10]);
+    }
+
+    #[test]
+    fn master_counts_pieces_and_spawns_on_the_floor_at_twenty_g() {
+        let config = EngineConfig {
+            master: true,
+            goal_system: crate::engine::GoalSystem::None,
+            ..EngineConfig::default()
+        };
+        let mut engine = Engine::new(config, 7);
+        let frame = InputFrame {
+            dt_seconds: 1.0 / 60.0,
+            ..InputFrame::default()
+        };
+        engine.step(frame.clone());
+        let snapshot = engine.snapshot();
+        assert_eq!(snapshot.master.as_ref().unwrap().level, 1);
+        assert!(!snapshot.active.unwrap().landed);
+
+        engine.master = Some(MasterState::at_level(crate::engine::TWENTY_G_FROM_LEVEL));
+        engine.step(InputFrame {
+            hard_drop: true,
+            ..frame.clone()
+        });
+        engine.step(frame.clone());
+        let active = engine.snapshot().active.unwrap();
+        assert!(active.landed, "a 20G piece spawns on the floor");
+        // One frame into the first 20G section's shorter lock delay.
+        assert_eq!(active.lock_timer_seconds, 0.45 - 1.0 / 60.0);
+        assert_eq!(engine.snapshot().master.unwrap().level, 501);
+    }
+
+    #[test]
+    fn board_cell_ages_count_from_each_lock() {
+        let mut engine = Engine::new(EngineConfig::default(), 3);
+        let drop = InputFrame {
+            hard_drop: true,
+            ..InputFrame::default()
+        };
+        engine.step(drop.clone());
+        engine.step(InputFrame {
+            dt_seconds: 2.0,
+            ..drop
+        });
+        let snapshot = engine.snapshot();
+        assert_eq!(snapshot.board_cell_ages.len(), snapshot.board_cells.len());
+        let mut ages = snapshot.board_cell_ages.clone();
+        ages.sort_by(f32::total_cmp);
+        ages.dedup();
+        // The first piece has sat for the two seconds; the second just locked.
+        assert_eq!(ages, [0.0, 2.0]);
+    }
+
+    #[test]
+    fn upcoming_pieces_are_the_deal_behind_the_queue() {
+        let mut engine = Engine::new(EngineConfig::default(), 5);
+        let upcoming = engine.upcoming_pieces(7);
+        engine.step(InputFrame::default());
+        assert_eq!(engine.snapshot().next_queue.last(), upcoming.first());
+        assert_eq!(engine.upcoming_pieces(6), upcoming[1..]);
+    }
+
+    #[test]
+    fn a_scenario_sets_the_board_hold_and_script_before_the_first_piece() {
+        use PieceType::{I, L, T};
+        let config = EngineConfig {
+            scenario: Some(crate::engine::Scenario {
+                cells: vec![(0, 0, CellKind::Garbage), (9, 0, CellKind::Some(L))],
+                hold: Some(I),
+                sequence: vec![T, L],
+                garbage: 3,
+            }),
+            ..EngineConfig::default()
+        };
+        let mut engine = Engine::new(config.clone(), 5);
+        let snapshot = engine.snapshot();
+        assert_eq!(snapshot.board_cells.len(), 2);
+        assert_eq!(snapshot.hold, Some(I));
+        assert_eq!(snapshot.next_queue[..2], [T, L]);
+        assert_eq!(snapshot.pending_garbage_total(), 3);
+
+        // The same config rebuilds the same position, script and garbage holes.
+        engine.step(InputFrame::default());
+        let mut again = Engine::new(config, 5);
+        again.step(InputFrame::default());
+        assert_eq!(engine.snapshot(), again.snapshot());
+        assert_eq!(engine.snapshot().active.unwrap().piece_type, T);
+    }
 }
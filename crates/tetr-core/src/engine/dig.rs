@@ -0,0 +1,174 @@
+//! The dig (cheese race) rules: a seeded garbage stack the player clears.
+//!
+//! A dig game starts with [`DigConfig::lines`] garbage rows to clear, but at
+//! most [`DIG_VISIBLE_ROWS`] of them stand on the board at once; the rest wait
+//! below and rise to replace cleared garbage rows after the lock that cleared
+//! them, so the visible cheese stays topped up until the reserve runs dry.
+//! Only clearing a *garbage* row counts — rows the player builds above the
+//! cheese are ordinary clears.
+//!
+//! Garbage rows always sit at the bottom of the stack (they enter beneath it
+//! and line clears preserve row order), so the engine tracks them as a single
+//! count rather than tagging cells: a cleared row is garbage iff its pre-clear
+//! index is below that count.
+//!
+//! Like versus holes, every hole column comes from a stream seeded by the
+//! engine seed and salted apart from the bag and the versus hole stream, so a
+//! `(seed, inputs)` pair replays a dig game exactly.
+
+use rand::RngExt;
+
+use crate::engine::rng::EngineRng;
+
+/// Garbage rows standing on the board at once (fewer once the reserve is
+/// spent).
+pub const DIG_VISIBLE_ROWS: u32 = 10;
+
+/// Keeps the dig stream apart from the bag and versus hole streams seeded from
+/// the same engine seed.
+const DIG_SALT: u64 = 0x6469_675F_6368_6565; // "dig_chee"
+
+/// Clean cheese keeps its hole for a run of rows; it moves on one row in this
+/// many (drawn per row).
+const CLEAN_HOLE_RUN: u32 = 4;
+
+/// A dig game's setup, carried on
+/// [`EngineConfig::dig`](super::EngineConfig::dig).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DigConfig {
+    /// Garbage rows to clear in total.
+    pub lines: u32,
+    /// Messy cheese moves the hole on every row (each row needs its own
+    /// clear); clean cheese keeps it for runs of rows, so one well digs
+    /// several at once.
+    pub messy: bool,
+}
+
+/// The live cheese: how much is on the board, how much is still to rise, and
+/// the hole stream.
+#[derive(Clone)]
+pub(crate) struct DigState {
+    config: DigConfig,
+    /// Garbage rows on the board — always the bottom `on_board` rows.
+    on_board: u32,
+    /// Garbage rows not yet risen.
+    reserve: u32,
+    last_hole: Option<usize>,
+    rng: EngineRng,
+}
+
+impl DigState {
+    pub(crate) fn new(config: DigConfig, engine_seed: u64) -> Self {
+        Self {
+            config,
+            on_board: 0,
+            reserve: config.lines,
+            last_hole: None,
+            rng: EngineRng::seed_from_u64(engine_seed ^ DIG_SALT),
+        }
+    }
+
+    /// Garbage rows left to clear, on the board or still to rise.
+    pub(crate) fn remaining(&self) -> u32 {
+        self.on_board + self.reserve
+    }
+
+    /// Count the garbage among a lock's cleared rows (pre-clear indices).
+    pub(crate) fn cleared(&mut self, cleared_rows: &[isize]) {
+        let garbage = cleared_rows
+            .iter()
+            .filter(|&&y| y >= 0 && (y as u32) < self.on_board)
+            .count() as u32;
+        self.on_board -= garbage;
+    }
+
+    /// The hole columns of the rows due to rise now, bottom-most last (the
+    /// order they are inserted in): enough to top the board back up to
+    /// [`DIG_VISIBLE_ROWS`].
+    pub(crate) fn refill(&mut self, board_width: usize) -> Vec<usize> {
+        let due = DIG_VISIBLE_ROWS
+            .saturating_sub(self.on_board)
+            .min(self.reserve);
+        self.reserve -= due;
+        self.on_board += due;
+        (0..due).map(|_| self.next_hole(board_width)).collect()
+    }
+
+    /// The next row's hole. Messy cheese never repeats the row above's hole;
+    /// clean cheese repeats it except one row in [`CLEAN_HOLE_RUN`].
+    fn next_hole(&mut self, board_width: usize) -> usize {
+        let width = board_width.max(2);
+        let hole = match self.last_hole {
+            None => self.rng.random_range(0..width),
+            Some(last) if !self.config.messy && self.rng.random_range(0..CLEAN_HOLE_RUN) != 0 => {
+                last
+            }
+            // A uniform draw over the other columns.
+            Some(last) => (last + 1 + self.rng.random_range(0..width - 1)) % width,
+        };
+        self.last_hole = Some(hole);
+        hole
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn state(lines: u32, messy: bool) -> DigState {
+        DigState::new(DigConfig { lines, messy }, 7)
+    }
+
+    #[test]
+    fn the_board_holds_at_most_the_visible_rows_and_refills_as_garbage_clears() {
+        let mut dig = state(18, true);
+        assert_eq!(dig.refill(10).len(), DIG_VISIBLE_ROWS as usize);
+        assert_eq!(dig.remaining(), 18);
+        // Two garbage rows and one row above the cheese clear together.
+        dig.cleared(&[0, 3, 12]);
+        assert_eq!(dig.remaining(), 16);
+        assert_eq!(dig.refill(10).len(), 2);
+        // The reserve runs dry: 10 cleared, only 6 left to rise.
+        dig.cleared(&(0..10).collect::<Vec<_>>());
+        assert_eq!(dig.refill(10).len(), 6);
+        assert_eq!(dig.remaining(), 6);
+        assert!(dig.refill(10).is_empty());
+    }
+
+    #[test]
+    fn messy_holes_never_repeat_and_clean_holes_run() {
+        let mut messy = state(100, true);
+        let mut holes = Vec::new();
+        while messy.remaining() > 0 {
+            holes.extend(messy.refill(10));
+            messy.cleared(&(0..10).collect::<Vec<_>>());
+        }
+        assert_eq!(holes.len(), 100);
+        assert!(holes.windows(2).all(|pair| pair[0] != pair[1]));
+        assert!(holes.iter().all(|&hole| hole < 10));
+
+        let mut clean = state(100, false);
+        let holes = clean.refill(10);
+        let repeats = holes.windows(2).filter(|pair| pair[0] == pair[1]).count();
+        assert!(
+            repeats >= 4,
+            "clean cheese should mostly share holes: {holes:?}"
+        );
+    }
+
+    #[test]
+    fn the_hole_stream_is_seeded() {
+        assert_eq!(state(10, true).refill(10), state(10, true).refill(10));
+        assert_ne!(
+            state(10, true).refill(10),
+            DigState::new(
+                DigConfig {
+                    lines: 10,
+                    messy: true
+                },
+                8
+            )
+            .refill(10)
+        );
+    }
+}
@@ -26,10 +26,11 @@
 //! rising batches through the same `Board::insert_garbage_lines` primitive the
 //! out-of-band harness seam uses.
 
-use rand::rngs::StdRng;
-use rand::{RngExt, SeedableRng};
+use rand::RngExt;
 use smallvec::SmallVec;
 
+use crate::engine::rng::EngineRng;
+
 /// Decorrelates the hole stream from the piece generator: both are seeded from
 /// the engine seed, and identical streams would let a player predict holes from
 /// the bag (or vice versa).
@@ -102,19 +103,20 @@ pub(crate) fn rise(batches: &mut BatchQueue, cap: u32) -> BatchQueue {
 }
 
 /// The pending-garbage queue plus the receiver-owned hole stream.
+#[derive(Clone)]
 pub(crate) struct PendingGarbage {
     /// FIFO of queued batches, oldest at the front — cancellation and rising
     /// both consume from the front (oldest attack lands or is offset first).
     batches: BatchQueue,
     /// Seeded hole stream; advanced once per queued batch.
-    rng: StdRng,
+    rng: EngineRng,
 }
 
 impl PendingGarbage {
     pub(crate) fn new(engine_seed: u64) -> Self {
         Self {
             batches: BatchQueue::new(),
-            rng: StdRng::seed_from_u64(engine_seed ^ HOLE_SALT),
+            rng: EngineRng::seed_from_u64(engine_seed ^ HOLE_SALT),
         }
     }
 
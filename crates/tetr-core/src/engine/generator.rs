@@ -4,12 +4,16 @@
 //! guideline-standard randomizer. Implemented as an [`Iterator`] so callers can
 //! pull pieces lazily; the bag is refilled transparently and kept topped up so a
 //! short preview window is always available.
+//!
+//! A puzzle can put a fixed script in front of the bag
+//! ([`PieceGenerator::with_sequence`]): the scripted pieces are dealt first,
+//! in order, and the seeded bags take over once they run out.
 
 use crate::engine::pieces::PieceType;
-use rand::SeedableRng;
-use rand::rngs::StdRng;
+use crate::engine::rng::EngineRng;
 use rand::seq::SliceRandom;
 
+#[derive(Clone)]
 pub struct PieceGenerator {
     /// Deal stack: pieces pop from the END. Layout invariant (kept by `with_seed`
     /// pre-filling one bag ahead and `next` refilling whenever `len` drops below
@@ -18,16 +22,23 @@ pub struct PieceGenerator {
     /// `bag[7..]` IS [`bag_remainder`](Self::bag_remainder), and an exactly-empty
     /// remainder (`len == 7`) is a bag boundary.
     bag: Vec<PieceType>,
-    rng: StdRng,
+    /// Scripted pieces still to deal ahead of the bag, popped from the END
+    /// (stored reversed). Empty for an ordinary seeded game.
+    script: Vec<PieceType>,
+    rng: EngineRng,
 }
 
 impl PieceGenerator {
     pub fn with_seed(seed: u64) -> Self {
-        let mut rng = StdRng::seed_from_u64(seed);
+        let mut rng = EngineRng::seed_from_u64(seed);
         let mut bag = Vec::from(PieceType::all());
         bag.shuffle(&mut rng);
 
-        let mut generator = Self { bag, rng };
+        let mut generator = Self {
+            bag,
+            script: Vec::new(),
+            rng,
+        };
         // Pre-fill one bag ahead so the layout invariant above holds from the
         // start (the fresh, untouched current bag sits at index 7..). This draws
         // the second bag's shuffle at construction instead of on the first
@@ -37,6 +48,15 @@ impl PieceGenerator {
         generator
     }
 
+    /// Deal `sequence` first, in order, then the bags [`with_seed`](Self::with_seed)
+    /// would have dealt. The script draws nothing from the rng, so the bags
+    /// that follow it are the seed's own.
+    pub fn with_sequence(seed: u64, sequence: &[PieceType]) -> Self {
+        let mut generator = Self::with_seed(seed);
+        generator.script = sequence.iter().rev().copied().collect();
+        generator
+    }
+
     fn refill_bag(&mut self) {
         let mut next_bag = Vec::from(PieceType::all());
         next_bag.shuffle(&mut self.rng);
@@ -51,10 +71,20 @@ impl PieceGenerator {
     /// engine-side ground truth a search needs to speculate past the revealed
     /// queue; reconstructing it from the queue alone is impossible (the queue
     /// window straddles bag boundaries).
+    ///
+    /// While a script is still being dealt this is the bag waiting behind it:
+    /// the set the first piece *after* the script draws from.
     pub fn bag_remainder(&self) -> &[PieceType] {
         &self.bag[7..]
     }
 
+    /// The next `count` pieces, in deal order, without consuming anything:
+    /// the deal of a clone. Practice mode's bag reveal reads it; a game never
+    /// needs it (its preview is the engine's own look-ahead queue).
+    pub fn upcoming(&self, count: usize) -> Vec<PieceType> {
+        self.clone().take(count).collect()
+    }
+
     /// The next `PieceType::LEN` pieces, in deal order, without consuming the
     /// bag. Used to assert preview invariants; production preview is served by
     /// the engine's own look-ahead queue, so this is test-only.
@@ -72,6 +102,9 @@ impl Iterator for PieceGenerator {
     type Item = PieceType;
 
     fn next(&mut self) -> Option<Self::Item> {
+        if let Some(scripted) = self.script.pop() {
+            return Some(scripted);
+        }
         if self.bag.is_empty() {
             self.refill_bag();
         }
@@ -119,6 +152,20 @@ mod tests {
         assert_eq!(left_sequence, right_sequence);
     }
 
+    #[test]
+    fn a_script_is_dealt_before_the_seeded_bags() {
+        use PieceType::{I, O, T};
+        let mut scripted = PieceGenerator::with_sequence(9, &[T, T, I, O]);
+        let mut plain = PieceGenerator::with_seed(9);
+
+        let dealt = (0..4).map(|_| scripted.next().unwrap()).collect::<Vec<_>>();
+        assert_eq!(dealt, [T, T, I, O]);
+        assert_eq!(sorted(scripted.bag_remainder()), PieceType::all());
+        for _ in 0..14 {
+            assert_eq!(scripted.next(), plain.next());
+        }
+    }
+
     #[test]
     fn every_dealt_bag_contains_all_piece_types() {
         let mut generator = PieceGenerator::with_seed(42);
@@ -174,6 +221,17 @@ mod tests {
         }
     }
 
+    #[test]
+    fn upcoming_peeks_the_deal_without_consuming_it() {
+        let mut generator = PieceGenerator::with_seed(11);
+        let peeked = generator.upcoming(16);
+        let dealt = (0..16)
+            .map(|_| generator.next().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(peeked, dealt);
+        assert_eq!(generator.upcoming(3).len(), 3);
+    }
+
     #[test]
     fn bag_remainder_is_empty_exactly_at_bag_boundaries() {
         let mut generator = PieceGenerator::with_seed(0);
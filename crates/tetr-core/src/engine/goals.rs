@@ -1,7 +1,8 @@
 //! Level-goal progression and the Back-to-Back qualification rules.
 //!
-//! Two goal systems are supported: Fixed (prorated start level, then ten lines
-//! per level) and Variable (per-clear "goal units" weighted by clear type, §25.9).
+//! Three goal systems are supported: Fixed (prorated start level, then ten lines
+//! per level), Variable (per-clear "goal units" weighted by clear type, §25.9),
+//! and Blitz (a short line goal that grows by one line per level).
 //! [`GoalProgress`] tracks the current level and lines remaining and advances on
 //! [`GoalProgress::award`]. The free functions also expose which clears qualify
 //! for or break a Back-to-Back chain, shared with [`scoring`](crate::engine::scoring).
@@ -19,6 +20,10 @@ pub enum GoalSystem {
     /// is permanently `0` lines remaining, which [`GoalProgress::award`]
     /// already treats as "nothing to advance".
     None,
+    /// Fast leveling for a timed sprint: level `n` takes `n + 2` lines
+    /// ([`blitz_goal_for_level`]), so a two-minute run climbs most of the
+    /// gravity curve.
+    Blitz,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -93,9 +98,14 @@ pub fn goal_for_level(system: GoalSystem, start_level: u8, level: u8) -> usize {
         GoalSystem::Fixed => fixed_goal_for_level(start_level, level),
         GoalSystem::Variable => variable_goal_for_level(level),
         GoalSystem::None => 0,
+        GoalSystem::Blitz => blitz_goal_for_level(level),
     }
 }
 
+pub fn blitz_goal_for_level(level: u8) -> usize {
+    clamp_level(level) as usize + 2
+}
+
 pub fn fixed_goal_for_level(start_level: u8, level: u8) -> usize {
     let start_level = clamp_level(start_level);
     let level = clamp_level(level);
@@ -169,6 +179,19 @@ mod tests {
         assert_eq!((1..=15).map(variable_goal_for_level).sum::<usize>(), 600);
     }
 
+    #[test]
+    fn blitz_levels_come_fast_and_slow_down_gently() {
+        assert_eq!(blitz_goal_for_level(1), 3);
+        assert_eq!(blitz_goal_for_level(15), 17);
+        let mut progress = GoalProgress::new(GoalSystem::Blitz, 1);
+        // Three singles and a Tetris: level 1 (3 lines), then 4 of level 2's 4.
+        for lines in [1, 1, 1, 4] {
+            progress.award(lines);
+        }
+        assert_eq!(progress.level(), 3);
+        assert_eq!(progress.remaining(), 5);
+    }
+
     #[test]
     fn variable_goal_units_match_guideline_table() {
         assert_eq!(variable_goal_units(None, 1, false), 1);
@@ -1,12 +1,68 @@
 //! Gravity timing: how long a piece takes to fall one row at each level.
 //!
-//! Implements the guideline fall-speed curve as seconds-per-row, with soft drop
-//! fixed at 20x normal speed. Levels outside [`MIN_LEVEL`]..=[`MAX_LEVEL`] are
-//! clamped.
+//! Implements the guideline fall-speed curve as seconds-per-row, with the
+//! guideline soft drop at 20x normal speed. Levels outside
+//! [`MIN_LEVEL`]..=[`MAX_LEVEL`] are clamped. A player's own soft-drop factor
+//! paces the keyboard's soft drop off the same curve
+//! ([`DasConfig::soft_drop_factor`](crate::player::DasConfig::soft_drop_factor)).
+//!
+//! Master play runs on its own curve ([`master_gravity`]), indexed by its
+//! 0–999 level counter, which tops out at [`Gravity::TwentyG`]: the piece
+//! never hangs in the air at all, and spawns already on the floor.
 
 pub const MIN_LEVEL: u8 = 1;
 pub const MAX_LEVEL: u8 = 15;
 
+/// The Master level from which pieces fall at [`Gravity::TwentyG`].
+pub const TWENTY_G_FROM_LEVEL: u16 = 500;
+
+/// How fast the active piece falls.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Gravity {
+    /// One row per this many seconds (a fraction of a frame falls several
+    /// rows per step).
+    SecondsPerRow(f32),
+    /// Infinite gravity: the piece drops to the floor the moment it spawns,
+    /// moves or rotates, so it is always resting on the stack.
+    TwentyG,
+}
+
+/// TGM's internal gravity table: from each Master level, the fall speed in
+/// 1/256ths of a row per 60 Hz frame (256 = 1G). Levels from
+/// [`TWENTY_G_FROM_LEVEL`] are 20G.
+const MASTER_GRAVITY_TABLE: [(u16, u32); 30] = [
+    (0, 4),
+    (30, 6),
+    (35, 8),
+    (40, 10),
+    (50, 12),
+    (60, 16),
+    (70, 32),
+    (80, 48),
+    (90, 64),
+    (100, 80),
+    (120, 96),
+    (140, 112),
+    (160, 128),
+    (170, 144),
+    (200, 4),
+    (220, 32),
+    (230, 64),
+    (233, 96),
+    (236, 128),
+    (239, 160),
+    (243, 192),
+    (247, 224),
+    (251, 256),
+    (300, 512),
+    (330, 768),
+    (360, 1024),
+    (400, 1280),
+    (420, 1024),
+    (450, 768),
+    (TWENTY_G_FROM_LEVEL, 5120),
+];
+
 pub fn fall_speed_seconds(level: u8) -> f32 {
     let level = f32::from(level.clamp(MIN_LEVEL, MAX_LEVEL));
     (0.8 - ((level - 1.0) * 0.007)).powf(level - 1.0)
@@ -16,6 +72,21 @@ pub fn soft_drop_speed_seconds(level: u8) -> f32 {
     fall_speed_seconds(level) / 20.0
 }
 
+/// The fall speed at Master `level`: TGM's table, including its famous dip
+/// back to a crawl at level 200, then [`Gravity::TwentyG`] from
+/// [`TWENTY_G_FROM_LEVEL`].
+pub fn master_gravity(level: u16) -> Gravity {
+    if level >= TWENTY_G_FROM_LEVEL {
+        return Gravity::TwentyG;
+    }
+    let per_frame = MASTER_GRAVITY_TABLE
+        .iter()
+        .rev()
+        .find(|&&(from, _)| level >= from)
+        .map_or(MASTER_GRAVITY_TABLE[0].1, |&(_, speed)| speed);
+    Gravity::SecondsPerRow(256.0 / (per_frame as f32 * 60.0))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -40,6 +111,22 @@ mod tests {
         assert_near(soft_drop_speed_seconds(15), fall_speed_seconds(15) / 20.0);
     }
 
+    #[test]
+    fn master_gravity_crawls_dips_at_200_and_reaches_twenty_g() {
+        let seconds = |level| match master_gravity(level) {
+            Gravity::SecondsPerRow(seconds) => seconds,
+            Gravity::TwentyG => 0.0,
+        };
+        assert_near(seconds(0), 256.0 / 240.0);
+        assert!(seconds(199) < seconds(0));
+        assert_eq!(seconds(200), seconds(0));
+        // 1G is a row a frame.
+        assert_near(seconds(251), 1.0 / 60.0);
+        assert_eq!(master_gravity(TWENTY_G_FROM_LEVEL - 1), master_gravity(450));
+        assert_eq!(master_gravity(TWENTY_G_FROM_LEVEL), Gravity::TwentyG);
+        assert_eq!(master_gravity(999), Gravity::TwentyG);
+    }
+
     #[test]
     fn level_is_clamped_to_guideline_range() {
         assert_eq!(fall_speed_seconds(0), fall_speed_seconds(MIN_LEVEL));
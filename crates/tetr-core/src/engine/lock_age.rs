@@ -0,0 +1,119 @@
+//! When each settled cell arrived: the plane behind fading and invisible
+//! stacks.
+//!
+//! The board knows *what* sits in each cell, not *when* it got there, and a
+//! renderer that fades locked minos needs the second. [`LockAges`] keeps a
+//! per-cell timestamp beside the board and mirrors every way the stack moves
+//! — a lock stamps its cells, a clear compacts by the same full-row list, a
+//! garbage or cheese rise shifts everything up — so the ages stay in lockstep
+//! with the cells they describe. Rising rows are stamped when they rise.
+//!
+//! The clock is the engine's own (the sum of stepped `dt`), so ages replay
+//! exactly from `(seed, inputs)`. Nothing in the rules reads them.
+
+/// The engine clock and a timestamp for every backing cell.
+#[derive(Clone)]
+pub(crate) struct LockAges {
+    width: usize,
+    /// Engine time played, in seconds.
+    clock: f32,
+    /// `stamps[y * width + x]`: the clock when `(x, y)` was filled.
+    stamps: Vec<f32>,
+}
+
+impl LockAges {
+    pub(crate) fn new(width: usize, backing_rows: usize) -> Self {
+        Self {
+            width,
+            clock: 0.0,
+            stamps: vec![0.0; width * backing_rows],
+        }
+    }
+
+    /// Run the clock for one step.
+    pub(crate) fn advance(&mut self, dt_seconds: f32) {
+        self.clock += dt_seconds;
+    }
+
+    /// Cells were filled now (a lock, or a board-setup write). Off-grid
+    /// cells are ignored, as the board drops them.
+    pub(crate) fn stamp(&mut self, cells: impl IntoIterator<Item = (isize, isize)>) {
+        for (x, y) in cells {
+            if let Some(index) = self.index(x, y) {
+                self.stamps[index] = self.clock;
+            }
+        }
+    }
+
+    /// `rows` (pre-clear coordinates) cleared: drop them and compact the
+    /// rest downward, as [`Board::clear_lines`](crate::engine::Board::clear_lines) does.
+    pub(crate) fn cleared(&mut self, rows: &[isize]) {
+        if rows.is_empty() {
+            return;
+        }
+        let mut compacted = Vec::with_capacity(self.stamps.len());
+        for (y, row) in self.stamps.chunks(self.width).enumerate() {
+            if !rows.contains(&(y as isize)) {
+                compacted.extend_from_slice(row);
+            }
+        }
+        compacted.resize(self.stamps.len(), self.clock);
+        self.stamps = compacted;
+    }
+
+    /// `count` rows rose from the bottom now, pushing the stack up, as
+    /// [`Board::insert_garbage_lines`](crate::engine::Board::insert_garbage_lines) does.
+    pub(crate) fn raised(&mut self, count: usize) {
+        let shift = (count * self.width).min(self.stamps.len());
+        self.stamps.rotate_right(shift);
+        self.stamps[..shift].fill(self.clock);
+    }
+
+    /// Seconds since `(x, y)` was filled.
+    pub(crate) fn age(&self, x: isize, y: isize) -> f32 {
+        self.index(x, y)
+            .map_or(0.0, |index| self.clock - self.stamps[index])
+    }
+
+    fn index(&self, x: isize, y: isize) -> Option<usize> {
+        if x < 0 || y < 0 || x as usize >= self.width {
+            return None;
+        }
+        let index = y as usize * self.width + x as usize;
+        (index < self.stamps.len()).then_some(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ages_follow_their_cells_through_clears_and_rises() {
+        let mut ages = LockAges::new(2, 4);
+        ages.stamp([(0, 0), (0, 1), (0, 2)]);
+        ages.advance(1.0);
+        ages.stamp([(1, 1)]);
+        ages.advance(2.0);
+        assert_eq!(ages.age(0, 2), 3.0);
+        assert_eq!(ages.age(1, 1), 2.0);
+
+        // Row 1 clears: row 2 drops into it and keeps its age.
+        ages.cleared(&[1]);
+        assert_eq!(ages.age(0, 1), 3.0);
+
+        // A row rises: everything shifts up, the new row is fresh.
+        ages.raised(1);
+        assert_eq!(ages.age(0, 0), 0.0);
+        assert_eq!(ages.age(0, 2), 3.0);
+    }
+
+    #[test]
+    fn off_grid_cells_have_no_age() {
+        let mut ages = LockAges::new(2, 2);
+        ages.stamp([(-1, 0), (2, 0), (0, 5)]);
+        ages.advance(1.0);
+        assert_eq!(ages.age(0, 5), 0.0);
+        assert_eq!(ages.age(0, 0), 1.0);
+    }
+}
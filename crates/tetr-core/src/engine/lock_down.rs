@@ -1,15 +1,56 @@
-//! Lock-down timer reset policy.
+//! Lock-down timer reset policy and the per-level lock delay.
 //!
 //! When a grounded piece is moved or rotated, whether its lock timer resets
 //! depends on [`LockDownMode`]: Extended Placement allows a bounded number of
 //! resets ([`EXTENDED_LOCK_RESET_BUDGET`]) per new lowest row, Infinite always
 //! resets, and Classic never does. [`apply_grounded_move_or_rotation`] applies
 //! the policy and reports whether the timer was reset.
+//!
+//! How long the timer runs is [`lock_down_seconds_for_level`]: the config's
+//! lock delay at every level under [`LockDelayCurve::Flat`], or shrinking with
+//! the level under [`LockDelayCurve::Tightening`] — the lock-delay partner of
+//! [`fall_speed_seconds`](crate::engine::fall_speed_seconds).
 
 use crate::engine::active_piece::ActivePiece;
+use crate::engine::gravity::{MAX_LEVEL, MIN_LEVEL};
 
 pub const LOCK_DOWN_SECONDS: f32 = 0.5;
 pub const EXTENDED_LOCK_RESET_BUDGET: u8 = 15;
+/// The shortest lock delay [`LockDelayCurve::Tightening`] reaches (at
+/// [`MAX_LEVEL`]).
+pub const MIN_LOCK_DOWN_SECONDS: f32 = 0.2;
+/// [`LockDelayCurve::Tightening`] holds the configured delay through this
+/// level, then shrinks it.
+pub const TIGHTENING_FROM_LEVEL: u8 = 5;
+
+/// How the lock delay changes as the level rises.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum LockDelayCurve {
+    /// The configured delay at every level (the guideline).
+    #[default]
+    Flat,
+    /// The configured delay through [`TIGHTENING_FROM_LEVEL`], then linearly
+    /// down to [`MIN_LOCK_DOWN_SECONDS`] at [`MAX_LEVEL`] (Blitz).
+    Tightening,
+}
+
+/// The lock delay at `level` for a `base` delay under `curve`. Levels outside
+/// [`MIN_LEVEL`]..=[`MAX_LEVEL`] are clamped, as gravity's are; a base already
+/// under the floor is never raised.
+pub fn lock_down_seconds_for_level(curve: LockDelayCurve, base: f32, level: u8) -> f32 {
+    let level = level.clamp(MIN_LEVEL, MAX_LEVEL);
+    match curve {
+        LockDelayCurve::Flat => base,
+        LockDelayCurve::Tightening if level <= TIGHTENING_FROM_LEVEL => base,
+        LockDelayCurve::Tightening => {
+            let floor = MIN_LOCK_DOWN_SECONDS.min(base);
+            // The share of the shrink still to come, so MAX_LEVEL lands on
+            // the floor exactly.
+            let left = f32::from(MAX_LEVEL - level) / f32::from(MAX_LEVEL - TIGHTENING_FROM_LEVEL);
+            floor + (base - floor) * left
+        }
+    }
+}
 
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum LockDownMode {
@@ -48,6 +89,28 @@ mod tests {
     use super::*;
     use crate::engine::PieceType;
 
+    #[test]
+    fn the_tightening_curve_holds_then_shrinks_to_its_floor() {
+        let at = |level| lock_down_seconds_for_level(LockDelayCurve::Tightening, 0.5, level);
+        assert_eq!(at(1), 0.5);
+        assert_eq!(at(TIGHTENING_FROM_LEVEL), 0.5);
+        assert!((at(10) - 0.35).abs() < 1e-6);
+        assert_eq!(at(MAX_LEVEL), MIN_LOCK_DOWN_SECONDS);
+        assert_eq!(at(u8::MAX), MIN_LOCK_DOWN_SECONDS);
+        for level in MIN_LEVEL..MAX_LEVEL {
+            assert!(at(level + 1) <= at(level));
+        }
+        assert_eq!(
+            lock_down_seconds_for_level(LockDelayCurve::Flat, 0.5, MAX_LEVEL),
+            0.5
+        );
+        // A base under the floor stays put.
+        assert_eq!(
+            lock_down_seconds_for_level(LockDelayCurve::Tightening, 0.1, MAX_LEVEL),
+            0.1
+        );
+    }
+
     #[test]
     fn extended_resets_until_budget_is_exhausted() {
         let mut active_piece = ActivePiece::new(PieceType::T, (3, 19));
@@ -0,0 +1,307 @@
+//! The Master rules: TGM's 0–999 level counter, its sections, and its grade.
+//!
+//! The counter climbs by one for every piece that enters from the queue and by
+//! one per line cleared, but a piece alone can never carry it past a section
+//! stop — the last level of each 100-level section (998 in the final one) —
+//! so every section ends on a clear. It drives everything else: gravity
+//! ([`master_gravity`], 20G from
+//! [`TWENTY_G_FROM_LEVEL`](crate::engine::TWENTY_G_FROM_LEVEL)), a lock delay
+//! that shortens section by section, and the section clock.
+//!
+//! The grade weighs clears against speed: every clear earns points (more for
+//! more lines at once), every section finished within
+//! [`MASTER_SECTION_PAR_SECONDS`] earns a bonus, and the total maps onto TGM's
+//! 9 → 1 → S1 → S9 ladder. Grand Master is reserved for a run that reaches
+//! [`MASTER_END_LEVEL`] at the top grade with every section under par.
+//!
+//! The clock is the engine's own (the sum of stepped `dt`), so section times
+//! and the grade replay exactly from `(seed, inputs)`.
+
+use crate::engine::gravity::{Gravity, master_gravity};
+
+/// The level that completes a Master run.
+pub const MASTER_END_LEVEL: u16 = 999;
+/// Levels per section.
+pub const MASTER_SECTION_LEVELS: u16 = 100;
+/// Sections in a full run (the last one ends at [`MASTER_END_LEVEL`]).
+pub const MASTER_SECTIONS: usize = 10;
+/// A section finished within this many seconds earns the speed bonus.
+pub const MASTER_SECTION_PAR_SECONDS: f32 = 60.0;
+
+/// The lock delay in each section: the guideline half second, shortening
+/// from the first 20G section on.
+const SECTION_LOCK_SECONDS: [f32; MASTER_SECTIONS] =
+    [0.5, 0.5, 0.5, 0.5, 0.5, 0.45, 0.4, 0.35, 0.3, 0.25];
+
+/// Grade points per clear, by lines cleared at once.
+const CLEAR_POINTS: [u32; 5] = [0, 1, 3, 6, 12];
+
+/// Grade points per section finished under par.
+const PAR_SECTION_POINTS: u32 = 30;
+
+/// The points each grade above 9 needs: 8, 7, …, 1, then S1 … S9.
+const GRADE_THRESHOLDS: [u32; 17] = [
+    10, 25, 45, 70, 100, 140, 185, 235, 290, 350, 415, 485, 560, 640, 725, 815, 910,
+];
+
+/// A Master grade, from 9 (the lowest) up through 1 and S1–S9 to Grand
+/// Master. Orders lowest first.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MasterGrade(u8);
+
+impl MasterGrade {
+    /// Where every run starts.
+    pub const NINE: Self = Self(0);
+    /// The top grade.
+    pub const GRAND_MASTER: Self = Self(GRADE_THRESHOLDS.len() as u8 + 1);
+
+    /// The grade at `index` (0 = 9, 8 = 1, 9 = S1, 17 = S9, 18 = GM).
+    pub fn from_index(index: u8) -> Option<Self> {
+        (index <= Self::GRAND_MASTER.0).then_some(Self(index))
+    }
+
+    /// Inverse of [`from_index`](Self::from_index).
+    pub fn index(self) -> u8 {
+        self.0
+    }
+
+    /// How the grade is written: `9` … `1`, `S1` … `S9`, `GM`.
+    pub fn label(self) -> String {
+        match self.0 {
+            index @ 0..=8 => (9 - index).to_string(),
+            index if self < Self::GRAND_MASTER => format!("S{}", index - 8),
+            _ => "GM".into(),
+        }
+    }
+}
+
+/// A Master run's progress, carried on
+/// [`EngineSnapshot::master`](super::EngineSnapshot::master).
+#[derive(Clone, Debug, PartialEq)]
+pub struct MasterSnapshot {
+    /// The 0–999 level counter.
+    pub level: u16,
+    /// Where the counter waits for a clear: this section's last level.
+    pub level_stop: u16,
+    /// The seconds each finished section took, in order.
+    pub section_times: Vec<f32>,
+    /// Seconds spent in the current section so far.
+    pub section_seconds: f32,
+    /// The grade the run holds now.
+    pub grade: MasterGrade,
+    /// The fall speed in force.
+    pub gravity: Gravity,
+}
+
+/// The live Master run.
+#[derive(Clone)]
+pub(crate) struct MasterState {
+    level: u16,
+    /// Engine time played, in seconds.
+    clock: f32,
+    section_started: f32,
+    section_times: Vec<f32>,
+    clear_points: u32,
+}
+
+impl MasterState {
+    pub(crate) fn new() -> Self {
+        Self {
+            level: 0,
+            clock: 0.0,
+            section_started: 0.0,
+            section_times: Vec::new(),
+            clear_points: 0,
+        }
+    }
+
+    /// A run already at `level` (tests start at 20G without playing there).
+    #[cfg(test)]
+    pub(crate) fn at_level(level: u16) -> Self {
+        Self {
+            level,
+            ..Self::new()
+        }
+    }
+
+    /// Run the clock for one step.
+    pub(crate) fn advance(&mut self, dt_seconds: f32) {
+        self.clock += dt_seconds;
+    }
+
+    /// A piece entered from the queue: one level, unless at the stop.
+    pub(crate) fn piece_spawned(&mut self) {
+        if self.level < self.level_stop() {
+            self.level += 1;
+        }
+    }
+
+    /// A lock cleared `lines`: a level each (through the stop), grade points,
+    /// and the section clock if that finished a section.
+    pub(crate) fn lines_cleared(&mut self, lines: usize) {
+        if lines == 0 {
+            return;
+        }
+        self.clear_points += CLEAR_POINTS[lines.min(CLEAR_POINTS.len() - 1)];
+        self.level = (self.level + lines as u16).min(MASTER_END_LEVEL);
+        let finished = if self.complete() {
+            MASTER_SECTIONS
+        } else {
+            usize::from(self.level / MASTER_SECTION_LEVELS)
+        };
+        while self.section_times.len() < finished {
+            self.section_times.push(self.clock - self.section_started);
+            self.section_started = self.clock;
+        }
+    }
+
+    /// Whether the counter has reached [`MASTER_END_LEVEL`].
+    pub(crate) fn complete(&self) -> bool {
+        self.level >= MASTER_END_LEVEL
+    }
+
+    pub(crate) fn gravity(&self) -> Gravity {
+        master_gravity(self.level)
+    }
+
+    /// The current section's lock delay.
+    pub(crate) fn lock_seconds(&self) -> f32 {
+        SECTION_LOCK_SECONDS[self.section()]
+    }
+
+    pub(crate) fn snapshot(&self) -> MasterSnapshot {
+        MasterSnapshot {
+            level: self.level,
+            level_stop: self.level_stop(),
+            section_times: self.section_times.clone(),
+            section_seconds: self.clock - self.section_started,
+            grade: self.grade(),
+            gravity: self.gravity(),
+        }
+    }
+
+    fn section(&self) -> usize {
+        usize::from(self.level / MASTER_SECTION_LEVELS).min(MASTER_SECTIONS - 1)
+    }
+
+    fn level_stop(&self) -> u16 {
+        if self.section() == MASTER_SECTIONS - 1 {
+            MASTER_END_LEVEL - 1
+        } else {
+            self.level / MASTER_SECTION_LEVELS * MASTER_SECTION_LEVELS + MASTER_SECTION_LEVELS - 1
+        }
+    }
+
+    fn grade(&self) -> MasterGrade {
+        let under_par = self
+            .section_times
+            .iter()
+            .filter(|&&seconds| seconds <= MASTER_SECTION_PAR_SECONDS)
+            .count();
+        let points = self.clear_points + under_par as u32 * PAR_SECTION_POINTS;
+        let grade = GRADE_THRESHOLDS
+            .iter()
+            .filter(|&&needed| points >= needed)
+            .count() as u8;
+        if grade == GRADE_THRESHOLDS.len() as u8 && self.complete() && under_par == MASTER_SECTIONS
+        {
+            MasterGrade::GRAND_MASTER
+        } else {
+            MasterGrade(grade)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pieces_stop_at_the_section_end_and_a_clear_carries_through() {
+        let mut master = MasterState::new();
+        for _ in 0..150 {
+            master.piece_spawned();
+        }
+        assert_eq!(master.level, 99);
+        master.advance(42.0);
+        master.lines_cleared(2);
+        assert_eq!(master.level, 101);
+        assert_eq!(master.section_times, [42.0]);
+        assert_eq!(master.snapshot().level_stop, 199);
+
+        // The last section stops a level short of the end.
+        master.level = 950;
+        for _ in 0..100 {
+            master.piece_spawned();
+        }
+        assert_eq!(master.level, 998);
+        master.lines_cleared(4);
+        assert!(master.complete());
+        assert_eq!(master.level, MASTER_END_LEVEL);
+        assert_eq!(master.section_times.len(), MASTER_SECTIONS);
+    }
+
+    #[test]
+    fn the_lock_delay_shortens_once_gravity_is_twenty_g() {
+        let mut master = MasterState::new();
+        assert_eq!(master.lock_seconds(), 0.5);
+        master.level = 500;
+        assert_eq!(master.gravity(), Gravity::TwentyG);
+        assert!(master.lock_seconds() < 0.5);
+        master.level = MASTER_END_LEVEL;
+        assert_eq!(master.lock_seconds(), SECTION_LOCK_SECONDS[9]);
+    }
+
+    #[test]
+    fn grades_climb_with_clears_and_speed() {
+        let mut master = MasterState::new();
+        assert_eq!(master.grade(), MasterGrade::NINE);
+        for _ in 0..10 {
+            master.lines_cleared(1);
+        }
+        assert_eq!(master.grade().label(), "8");
+
+        // The same clears, but finishing the section under par.
+        let mut fast = MasterState::new();
+        fast.level = 90;
+        fast.advance(MASTER_SECTION_PAR_SECONDS);
+        for _ in 0..10 {
+            fast.lines_cleared(1);
+        }
+        assert!(fast.grade() > master.grade());
+    }
+
+    #[test]
+    fn grand_master_needs_the_top_grade_every_section_under_par_and_the_end() {
+        let mut master = MasterState::new();
+        for _ in 0..(MASTER_END_LEVEL / 4) {
+            master.advance(2.0);
+            master.lines_cleared(4);
+        }
+        master.lines_cleared(3);
+        assert!(master.complete());
+        assert_eq!(master.grade(), MasterGrade::GRAND_MASTER);
+        assert_eq!(master.grade().label(), "GM");
+
+        let mut slow = MasterState::new();
+        for _ in 0..(MASTER_END_LEVEL / 4) {
+            slow.advance(100.0);
+            slow.lines_cleared(4);
+        }
+        slow.lines_cleared(3);
+        assert_eq!(slow.grade().label(), "S9");
+    }
+
+    #[test]
+    fn grade_indices_round_trip_and_label_the_ladder() {
+        let labels: Vec<String> = (0..=MasterGrade::GRAND_MASTER.index())
+            .map(|index| MasterGrade::from_index(index).unwrap().label())
+            .collect();
+        assert_eq!(labels[0], "9");
+        assert_eq!(labels[8], "1");
+        assert_eq!(labels[9], "S1");
+        assert_eq!(labels[17], "S9");
+        assert_eq!(labels[18], "GM");
+        assert_eq!(MasterGrade::from_index(19), None);
+    }
+}
@@ -5,7 +5,9 @@
 //! [`Engine`] facade using plain data ([`InputFrame`] in, [`EngineEvent`]s and
 //! [`EngineSnapshot`] out). Submodules are split by concern: board/piece
 //! geometry, the seven-bag generator, gravity and lock-down timing, line
-//! clearing, scoring, level goals, T-spin detection, and game-over conditions.
+//! clearing, scoring, level goals, T-spin detection, game-over conditions, and
+//! the versus and dig garbage rules, Master's level counter and grade, and the
+//! scripted starts puzzles begin from.
 //! Most of those concerns are exposed as pure free functions so they can be
 //! reused outside the per-frame loop (search bots, replay validators).
 
@@ -15,14 +17,19 @@ mod attack;
 mod bit_board;
 mod board;
 mod constants;
+mod dig;
 mod game_over;
 pub(crate) mod garbage; // crate-visible: the search mirrors its rules (one home)
 mod generator;
 mod goals;
 mod gravity;
+mod lock_age;
 mod lock_clear;
 mod lock_down;
+mod master;
 mod pieces;
+mod rng;
+mod scenario;
 mod scoring;
 mod t_spin;
 mod types;
@@ -32,20 +39,31 @@ pub use api::Engine;
 pub use attack::{COMBO_TABLE, PERFECT_CLEAR_ATTACK, attack_lines};
 pub use bit_board::{BitBoard, ColumnView, Occupancy};
 pub use board::{Board, CellKind};
+pub use dig::{DIG_VISIBLE_ROWS, DigConfig};
 pub use game_over::{is_block_out, is_lock_out, is_top_out};
 pub use garbage::GarbageBatch;
 pub use generator::PieceGenerator;
 pub use goals::{
-    GoalProgress, GoalSystem, breaks_back_to_back, fixed_goal_for_level, goal_for_level,
-    qualifies_for_back_to_back, variable_goal_for_level, variable_goal_units,
+    GoalProgress, GoalSystem, blitz_goal_for_level, breaks_back_to_back, fixed_goal_for_level,
+    goal_for_level, qualifies_for_back_to_back, variable_goal_for_level, variable_goal_units,
+};
+pub use gravity::{
+    Gravity, MAX_LEVEL, MIN_LEVEL, TWENTY_G_FROM_LEVEL, fall_speed_seconds, master_gravity,
+    soft_drop_speed_seconds,
 };
-pub use gravity::{MAX_LEVEL, MIN_LEVEL, fall_speed_seconds, soft_drop_speed_seconds};
 pub use lock_clear::{LockOutcome, lock_and_clear};
 pub use lock_down::{
-    EXTENDED_LOCK_RESET_BUDGET, LOCK_DOWN_SECONDS, LockDownMode, apply_grounded_move_or_rotation,
+    EXTENDED_LOCK_RESET_BUDGET, LOCK_DOWN_SECONDS, LockDelayCurve, LockDownMode,
+    MIN_LOCK_DOWN_SECONDS, TIGHTENING_FROM_LEVEL, apply_grounded_move_or_rotation,
+    lock_down_seconds_for_level,
+};
+pub use master::{
+    MASTER_END_LEVEL, MASTER_SECTION_LEVELS, MASTER_SECTION_PAR_SECONDS, MASTER_SECTIONS,
+    MasterGrade, MasterSnapshot,
 };
 pub use pieces::{MoveDirection, Piece, PieceRotation, PieceType};
-pub use scoring::EngineScoreAction;
+pub use scenario::Scenario;
+pub use scoring::{EngineScoreAction, perfect_clear_bonus};
 pub use t_spin::{TSpinCorners, TSpinKind, classify_t_spin, is_t_slot, t_spin_corners};
 pub use types::{
     ActivePieceSnapshot, BUFFER_HEIGHT, EngineConfig, EngineEvent, EngineSnapshot, GameOverStatus,
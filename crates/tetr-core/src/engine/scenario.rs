@@ -0,0 +1,25 @@
+//! A scripted start: the board, hold slot and opening pieces a puzzle begins
+//! from.
+//!
+//! A [`Scenario`] rides on [`EngineConfig::scenario`](super::EngineConfig::scenario)
+//! rather than being poked into a running engine, so [`Engine::new`](super::Engine::new)
+//! builds the same position every time and a `(config, seed, inputs)` triple
+//! re-simulates a puzzle attempt exactly, like any other game.
+
+use crate::engine::board::CellKind;
+use crate::engine::pieces::PieceType;
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Scenario {
+    /// Cells filled before the first piece spawns, as `(x, y, kind)` with `y`
+    /// counted up from the floor. Off-grid cells are dropped.
+    pub cells: Vec<(isize, isize, CellKind)>,
+    /// The hold slot's starting contents.
+    pub hold: Option<PieceType>,
+    /// Dealt first, in order; the seeded 7-bag takes over once it runs out
+    /// (see [`PieceGenerator::with_sequence`](super::PieceGenerator::with_sequence)).
+    pub sequence: Vec<PieceType>,
+    /// Garbage lines queued against the player at the start, as one batch —
+    /// they rise under the usual cap after clear-less locks.
+    pub garbage: u32,
+}
@@ -226,7 +226,7 @@ impl ScoreState {
         self.combo = if lines_cleared > 0 { self.combo + 1 } else { 0 };
 
         let goal_units = match goal_system {
-            GoalSystem::Fixed => lines_cleared,
+            GoalSystem::Fixed | GoalSystem::Blitz => lines_cleared,
             GoalSystem::Variable => variable_goal_units(t_spin, lines_cleared, back_to_back_bonus),
             GoalSystem::None => 0,
         };
@@ -241,6 +241,20 @@ impl ScoreState {
         })
     }
 
+    /// Add the perfect-clear bonus for a `lines`-line clear scored at
+    /// `level`; `back_to_back` is whether that clear earned the B2B bonus.
+    /// Returns the bonus and the new total.
+    pub(crate) fn perfect_clear(
+        &mut self,
+        lines: usize,
+        back_to_back: bool,
+        level: u8,
+    ) -> (usize, usize) {
+        let bonus = perfect_clear_bonus(lines, back_to_back) * level as usize;
+        self.score += bonus;
+        (bonus, self.score)
+    }
+
     pub(crate) fn manual_drop(
         &mut self,
         action: EngineScoreAction,
@@ -262,6 +276,19 @@ impl ScoreState {
     }
 }
 
+/// The guideline perfect-clear bonus at level 1: 800 / 1200 / 1800 / 2000 for
+/// a Single through a Tetris, 3200 for a Back-to-Back Tetris.
+pub fn perfect_clear_bonus(lines: usize, back_to_back: bool) -> usize {
+    match (lines, back_to_back) {
+        (1, _) => 800,
+        (2, _) => 1200,
+        (3, _) => 1800,
+        (4, false) => 2000,
+        (4, true) => 3200,
+        _ => 0,
+    }
+}
+
 impl Default for ScoreState {
     fn default() -> Self {
         Self::new(GoalSystem::Fixed, MIN_LEVEL)
@@ -303,6 +330,15 @@ mod tests {
         assert!(second.back_to_back_bonus);
     }
 
+    #[test]
+    fn perfect_clear_bonus_scales_with_level_and_back_to_back() {
+        let mut s = ScoreState::new(GoalSystem::Fixed, 1);
+        assert_eq!(s.perfect_clear(1, false, 1), (800, 800));
+        assert_eq!(s.perfect_clear(4, false, 2), (4000, 4800));
+        assert_eq!(s.perfect_clear(4, true, 1), (3200, 8000));
+        assert_eq!(perfect_clear_bonus(0, false), 0);
+    }
+
     #[test]
     fn combo_advances_on_clears_and_resets_on_clearless_lock() {
         let mut s = ScoreState::new(GoalSystem::Fixed, 0);
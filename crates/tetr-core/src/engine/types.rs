@@ -2,11 +2,15 @@
 //! out. Everything here is behavior-free data every host (game, embed,
 //! research) imports; the machine that produces it lives in [`api`](super::api).
 
+use crate::engine::dig::DigConfig;
 use crate::engine::garbage::GarbageBatch;
 use crate::engine::goals::GoalSystem;
+use crate::engine::gravity::{Gravity, fall_speed_seconds};
+use crate::engine::master::MasterSnapshot;
 use crate::engine::pieces::{PieceRotation, PieceType};
+use crate::engine::scenario::Scenario;
 use crate::engine::scoring::EngineScoreAction;
-use crate::engine::{LOCK_DOWN_SECONDS, LockDownMode, MIN_LEVEL};
+use crate::engine::{LOCK_DOWN_SECONDS, LockDelayCurve, LockDownMode, MIN_LEVEL};
 
 /// Hidden rows above the visible field — the guideline buffer zone where
 /// pieces spawn and can lock (§16.4). A constant, not a config knob: nothing
@@ -20,6 +24,9 @@ pub struct EngineConfig {
     pub preview_count: usize,
     pub lock_down_mode: LockDownMode,
     pub lock_down_seconds: f32,
+    /// How the lock delay shrinks as the level rises, from
+    /// `lock_down_seconds` (see [`lock_down_seconds_for_level`](crate::engine::lock_down_seconds_for_level)).
+    pub lock_delay_curve: LockDelayCurve,
     pub starting_level: u8,
     pub goal_system: GoalSystem,
     /// Versus: the maximum pending-garbage lines that rise onto the board after
@@ -29,6 +36,20 @@ pub struct EngineConfig {
     /// "uncapped" convention some games use). Irrelevant outside versus — the
     /// queue is only fed by [`Engine::queue_garbage`](crate::engine::Engine::queue_garbage).
     pub garbage_cap: u32,
+    /// Award the guideline perfect-clear ("all clear") bonus on top of the
+    /// clear's own score (see [`EngineEvent::PerfectClearBonus`]). Off by
+    /// default: Marathon, Sprint and Ultra score as they always have.
+    pub perfect_clear_bonus: bool,
+    /// Dig: start on a seeded cheese stack that refills as its rows clear
+    /// (see `dig.rs`). `None` — every other game — starts on an empty board.
+    pub dig: Option<DigConfig>,
+    /// Master: TGM's 0–999 level counter drives gravity (up to 20G) and a
+    /// per-section lock delay in place of `lock_down_seconds` and the
+    /// guideline curve, and the run is graded (see `master.rs`).
+    pub master: bool,
+    /// Puzzle: start from a set board, hold slot and piece script instead of
+    /// an empty field and a plain bag (see `scenario.rs`).
+    pub scenario: Option<Scenario>,
 }
 
 impl Default for EngineConfig {
@@ -39,9 +60,14 @@ impl Default for EngineConfig {
             preview_count: 5,
             lock_down_mode: LockDownMode::Extended,
             lock_down_seconds: LOCK_DOWN_SECONDS,
+            lock_delay_curve: LockDelayCurve::Flat,
             starting_level: MIN_LEVEL,
             goal_system: GoalSystem::Fixed,
             garbage_cap: 8,
+            perfect_clear_bonus: false,
+            dig: None,
+            master: false,
+            scenario: None,
         }
     }
 }
@@ -51,6 +77,9 @@ pub struct InputFrame {
     pub dt_seconds: f32,
     pub left: bool,
     pub right: bool,
+    /// With `left`/`right`: slide as far as the piece goes this frame rather
+    /// than one cell (a player's ARR 0 auto-shift). Inert on its own.
+    pub instant_shift: bool,
     pub soft_drop: bool,
     pub hard_drop: bool,
     pub rotate_clockwise: bool,
@@ -59,6 +88,52 @@ pub struct InputFrame {
     pub pause: bool,
 }
 
+impl InputFrame {
+    /// The bits [`buttons`](Self::buttons) can set: one per button, nine in
+    /// all.
+    pub const BUTTON_MASK: u16 = 0x1FF;
+
+    /// The buttons packed one bit each — the one layout every wire and file
+    /// format shares: left, right, soft drop, hard drop, clockwise,
+    /// counter-clockwise, hold, pause, then instant shift at bit 8 (added
+    /// last, so older streams read unchanged).
+    pub fn buttons(&self) -> u16 {
+        [
+            self.left,
+            self.right,
+            self.soft_drop,
+            self.hard_drop,
+            self.rotate_clockwise,
+            self.rotate_counterclockwise,
+            self.hold,
+            self.pause,
+            self.instant_shift,
+        ]
+        .iter()
+        .enumerate()
+        .fold(0u16, |acc, (i, &down)| acc | (u16::from(down) << i))
+    }
+
+    /// Inverse of [`buttons`](Self::buttons), stamped with `dt_seconds`. Bits
+    /// outside [`BUTTON_MASK`](Self::BUTTON_MASK) are ignored; a format that
+    /// must refuse them checks first.
+    pub fn from_buttons(bits: u16, dt_seconds: f32) -> Self {
+        let bit = |i: u16| bits & (1 << i) != 0;
+        Self {
+            dt_seconds,
+            left: bit(0),
+            right: bit(1),
+            soft_drop: bit(2),
+            hard_drop: bit(3),
+            rotate_clockwise: bit(4),
+            rotate_counterclockwise: bit(5),
+            hold: bit(6),
+            pause: bit(7),
+            instant_shift: bit(8),
+        }
+    }
+}
+
 /// Game-facing happenings of one [`Engine::step`](crate::engine::Engine::step). Deliberately NOT a movement
 /// trace: spawning and per-cell movement (lateral, soft-drop, gravity) are
 /// snapshot state — observe them by diffing [`EngineSnapshot::active`] across
@@ -86,6 +161,13 @@ pub enum EngineEvent {
         total_score: usize,
         back_to_back_bonus: bool,
     },
+    /// With [`EngineConfig::perfect_clear_bonus`]: the lock just before this
+    /// event emptied the board, scoring `score` on top of its clear's
+    /// [`ScoreAwarded`](Self::ScoreAwarded).
+    PerfectClearBonus {
+        score: usize,
+        total_score: usize,
+    },
     Held {
         held: PieceType,
         active: PieceType,
@@ -98,7 +180,8 @@ pub enum EngineEvent {
         lines: u32,
     },
     /// Versus: pending garbage rose onto the board after a clear-less lock
-    /// (`lines` rows, capped per lock by [`EngineConfig::garbage_cap`]).
+    /// (`lines` rows, capped per lock by [`EngineConfig::garbage_cap`]), or —
+    /// in dig — cheese rose to replace the garbage rows a clear removed.
     GarbageInserted {
         lines: u32,
     },
@@ -143,6 +226,10 @@ pub struct ActivePieceSnapshot {
 pub struct EngineSnapshot {
     pub config: EngineConfig,
     pub board_cells: Vec<SnapshotCell>,
+    /// Seconds since each board cell settled (locked, or rose as garbage),
+    /// parallel to [`board_cells`](Self::board_cells) — what a fading or
+    /// invisible stack is drawn from. Engine time, so it replays exactly.
+    pub board_cell_ages: Vec<f32>,
     pub active: Option<ActivePieceSnapshot>,
     pub ghost_cells: Vec<SnapshotCell>,
     pub hold: Option<PieceType>,
@@ -169,10 +256,34 @@ pub struct EngineSnapshot {
     /// rising exactly; [`pending_garbage_total`](Self::pending_garbage_total)
     /// is the incoming-meter sum a UI shows.
     pub pending_garbage: Vec<GarbageBatch>,
+    /// Dig: cheese rows left to clear, on the board or still to rise (`None`
+    /// outside dig).
+    pub dig_remaining: Option<u32>,
+    /// Master: the level counter, section times and grade (`None` outside
+    /// Master).
+    pub master: Option<MasterSnapshot>,
     pub game_over: Option<GameOverStatus>,
 }
 
 impl EngineSnapshot {
+    /// The level a result files under: the guideline level, or a Master
+    /// run's grade ([`MasterGrade::index`](crate::engine::MasterGrade::index)),
+    /// since its guideline level never moves.
+    pub fn result_level(&self) -> u8 {
+        self.master
+            .as_ref()
+            .map_or(self.level, |master| master.grade.index())
+    }
+
+    /// The fall speed in force: a Master run's, otherwise the guideline
+    /// curve at [`level`](Self::level) (which a Master run never moves).
+    pub fn gravity(&self) -> Gravity {
+        self.master.as_ref().map_or_else(
+            || Gravity::SecondsPerRow(fall_speed_seconds(self.level)),
+            |master| master.gravity,
+        )
+    }
+
     /// Total pending-garbage lines (the incoming meter). Saturating, like the
     /// engine's own meter.
     pub fn pending_garbage_total(&self) -> u32 {
@@ -182,3 +293,37 @@ impl EngineSnapshot {
             .fold(0u32, u32::saturating_add)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Each button owns its bit, and the packing round-trips.
+    #[test]
+    fn buttons_pack_one_bit_each() {
+        let pressed = |f: fn(&mut InputFrame)| {
+            let mut frame = InputFrame::default();
+            f(&mut frame);
+            frame.buttons()
+        };
+        let bits = [
+            pressed(|f| f.left = true),
+            pressed(|f| f.right = true),
+            pressed(|f| f.soft_drop = true),
+            pressed(|f| f.hard_drop = true),
+            pressed(|f| f.rotate_clockwise = true),
+            pressed(|f| f.rotate_counterclockwise = true),
+            pressed(|f| f.hold = true),
+            pressed(|f| f.pause = true),
+            pressed(|f| f.instant_shift = true),
+        ];
+        for (i, bits) in bits.into_iter().enumerate() {
+            assert_eq!(bits, 1 << i);
+            assert_eq!(InputFrame::from_buttons(bits, 0.5).buttons(), bits);
+        }
+        assert_eq!(
+            InputFrame::from_buttons(u16::MAX, 0.0).buttons(),
+            InputFrame::BUTTON_MASK
+        );
+    }
+}
@@ -1,8 +1,9 @@
 //! `tetr-core` — the engine-agnostic core of tetr_online.
 //!
 //! This crate is the engine boundary made into a crate boundary: a pure,
-//! deterministic Tetris rule [`engine`], a [`player`] controller abstraction, and
-//! an [`ai`] bot — all with **no Bevy types**. The rule "the engine carries no
+//! deterministic Tetris rule [`engine`], a [`player`] controller abstraction, an
+//! [`ai`] bot, the [`opener`] book its opening and the game's trainer share,
+//! and the solo [`variant`] rules that end and rank a run — all with **no Bevy types**. The rule "the engine carries no
 //! rendering or Bevy types" is enforced by the compiler, not by convention:
 //! this crate does not depend on Bevy (except an optional, off-by-
 //! default `bevy` feature that only adds a keyboard-input *adapter*).
@@ -19,4 +20,6 @@
 
 pub mod ai;
 pub mod engine;
+pub mod opener;
 pub mod player;
+pub mod variant;
@@ -0,0 +1,821 @@
+//! The opener book: named first-bag setups as target placements.
+//!
+//! An opener is a shape to build on an empty board — TKI, DT Cannon, MKO,
+//! PCO — drawn as a diagram in a small text file ([`OpenerBook::parse`]; the
+//! standard book is compiled in from `book.txt`). Each piece of the diagram
+//! is one **target**: a piece type and the four cells it must land on. An
+//! optional lower-case `t` group is the **finisher**, the T-spin the setup
+//! exists for, eligible only once every setup target is down. Every setup
+//! is also played mirrored (`x → 9 − x`, S↔Z, J↔L), so an opener carries
+//! twice the setups its file draws.
+//!
+//! Whether a setup fits the game is a question about the **bag order**, so
+//! the book never stores orders: [`Setup::next_placement`] searches the
+//! known pieces (active, hold, revealed queue) for an order of target
+//! placements — each reachable by [`movegen`](crate::ai::movegen) from spawn,
+//! hold swaps included — that finishes the setup, and hands back the first
+//! step. Pieces beyond the revealed queue are unknown; a line that runs out
+//! of known pieces with targets left is taken on trust.
+//!
+//! Two consumers share the book: the game's opener trainer ([`OpenerRun`]
+//! grades each placement against it) and the AI's opening subgoal layer
+//! ([`OpenerBookPlanner`](crate::ai::search::OpenerBookPlanner) serves the
+//! book's placement before handing the game to its search).
+
+use crate::ai::movegen::Placement;
+use crate::ai::search::{hold_placements, hold_placements_pathless};
+use crate::ai::state::SearchState;
+use crate::engine::{ActivePiece, BitBoard, PieceRotation, PieceType};
+
+/// Columns in an opener diagram (the standard field).
+pub const BOOK_WIDTH: usize = 10;
+
+/// The compiled-in standard book.
+const STANDARD: &str = include_str!("book.txt");
+
+/// One piece of a setup: its type and the four board cells it lands on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Target {
+    pub piece_type: PieceType,
+    /// Absolute `(x, y)` cells, y up from the floor, sorted.
+    pub cells: [(isize, isize); 4],
+}
+
+impl Target {
+    /// Whether `placement` lands exactly this target.
+    pub fn matches(&self, placement: &Placement) -> bool {
+        placement.piece_type() == self.piece_type && piece_cells(&placement.piece) == self.cells
+    }
+
+    /// How many of the target's cells `board` fills.
+    fn filled(&self, board: &BitBoard) -> usize {
+        self.cells
+            .iter()
+            .filter(|&&(x, y)| board.occupied(x, y))
+            .count()
+    }
+
+    fn mirrored(&self) -> Self {
+        let mut cells = self.cells.map(|(x, y)| (BOOK_WIDTH as isize - 1 - x, y));
+        cells.sort();
+        Self {
+            piece_type: mirror_type(self.piece_type),
+            cells,
+        }
+    }
+}
+
+/// One buildable form of an opener: its setup targets and the optional
+/// finisher that cashes them in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Setup {
+    targets: Vec<Target>,
+    finisher: Option<Target>,
+    mirrored: bool,
+}
+
+impl Setup {
+    pub fn targets(&self) -> &[Target] {
+        &self.targets
+    }
+
+    pub fn finisher(&self) -> Option<&Target> {
+        self.finisher.as_ref()
+    }
+
+    /// Whether this is the generated mirror image of a drawn setup.
+    pub fn mirrored(&self) -> bool {
+        self.mirrored
+    }
+
+    /// How many setup targets `board` holds, or `None` when the board is off
+    /// this setup's track — a target half-filled, or any cell no target
+    /// accounts for.
+    pub fn progress(&self, board: &BitBoard) -> Option<usize> {
+        self.placed_mask(board)
+            .map(|mask| mask.count_ones() as usize)
+    }
+
+    /// Whether every setup target is down (the finisher aside).
+    pub fn is_built(&self, board: &BitBoard) -> bool {
+        self.progress(board) == Some(self.targets.len())
+    }
+
+    /// The book's next placement from `state` — one of
+    /// [`hold_placements`]`(state)`, so its path is ready to execute — or
+    /// `None` when the board is off track, the setup is finished, or no order
+    /// of the known pieces completes it. Placements without a hold swap are
+    /// preferred.
+    pub fn next_placement(&self, state: &SearchState) -> Option<Placement> {
+        let mask = self.placed_mask(&state.board)?;
+        let mut candidates: Vec<(Placement, u32)> = hold_placements(state)
+            .into_iter()
+            .filter_map(|placement| {
+                let bit = self.target_bit(&placement, mask)?;
+                Some((placement, bit))
+            })
+            .collect();
+        candidates.sort_by_key(|(placement, _)| placement.used_hold);
+        candidates
+            .into_iter()
+            .find(|(placement, bit)| self.completes(state, placement, mask | bit))
+            .map(|(placement, _)| placement)
+    }
+
+    /// The bit of the target `placement` lands, if it is still open and
+    /// eligible under `mask` (the finisher only once the setup is built).
+    fn target_bit(&self, placement: &Placement, mask: u32) -> Option<u32> {
+        let built = self.all_targets();
+        if let Some(i) = self
+            .targets
+            .iter()
+            .position(|target| target.matches(placement))
+        {
+            let bit = 1 << i;
+            return (mask & bit == 0).then_some(bit);
+        }
+        let finisher = self.finisher.as_ref()?;
+        (mask == built && finisher.matches(placement)).then_some(1 << self.targets.len())
+    }
+
+    /// Whether committing `placement` from `state` (reaching `mask`) leaves a
+    /// line that finishes the setup with the known pieces.
+    fn completes(&self, state: &SearchState, placement: &Placement, mask: u32) -> bool {
+        if mask == self.goal() || state.queue.is_empty() {
+            // Done — or out of known pieces, where the rest is taken on trust.
+            return true;
+        }
+        let mut child = state.clone();
+        child.commit_placement(placement);
+        if child.dead {
+            return false;
+        }
+        hold_placements_pathless(&child)
+            .iter()
+            .filter_map(|next| Some((next, self.target_bit(next, mask)?)))
+            .any(|(next, bit)| self.completes(&child, next, mask | bit))
+    }
+
+    fn all_targets(&self) -> u32 {
+        (1 << self.targets.len()) - 1
+    }
+
+    /// The mask of a finished setup: every target, plus the finisher's bit.
+    fn goal(&self) -> u32 {
+        match self.finisher {
+            Some(_) => (1 << (self.targets.len() + 1)) - 1,
+            None => self.all_targets(),
+        }
+    }
+
+    fn placed_mask(&self, board: &BitBoard) -> Option<u32> {
+        let mut mask = 0u32;
+        for (i, target) in self.targets.iter().enumerate() {
+            match target.filled(board) {
+                4 => mask |= 1 << i,
+                0 => {}
+                _ => return None,
+            }
+        }
+        (board.cell_coords().len() == 4 * mask.count_ones() as usize).then_some(mask)
+    }
+
+    fn mirror(&self) -> Self {
+        Self {
+            targets: self.targets.iter().map(Target::mirrored).collect(),
+            finisher: self.finisher.as_ref().map(Target::mirrored),
+            mirrored: !self.mirrored,
+        }
+    }
+}
+
+/// A named opener: its drawn setups followed by their mirrors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Opener {
+    pub id: String,
+    pub name: String,
+    setups: Vec<Setup>,
+}
+
+impl Opener {
+    pub fn setups(&self) -> &[Setup] {
+        &self.setups
+    }
+
+    /// The first setup (in book order, mirrors after the drawn forms) that is
+    /// on track and buildable from `state`, with its next placement.
+    pub fn next_placement(&self, state: &SearchState) -> Option<(usize, Placement)> {
+        self.setups
+            .iter()
+            .enumerate()
+            .find_map(|(i, setup)| Some((i, setup.next_placement(state)?)))
+    }
+
+    /// Grade the lock that took `before` to the board `after`, given the
+    /// placement the book `planned`: the planned placement is
+    /// [`Grade::Book`]; another placement that leaves the board on one of
+    /// this opener's setups is [`Grade::Alternate`]; anything else is
+    /// [`Grade::OffBook`].
+    pub fn grade(&self, before: &SearchState, planned: &Placement, after: &BitBoard) -> Grade {
+        let mut expected = before.clone();
+        expected.commit_placement(planned);
+        if expected.board == *after {
+            return Grade::Book;
+        }
+        let advanced = self.setups.iter().any(|setup| {
+            matches!(
+                (setup.progress(&before.board), setup.progress(after)),
+                (Some(was), Some(now)) if now == was + 1
+            )
+        });
+        if advanced {
+            Grade::Alternate
+        } else {
+            Grade::OffBook
+        }
+    }
+}
+
+/// How one placement measured up against the book.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Grade {
+    /// Exactly the placement the book asked for.
+    Book,
+    /// A different placement that still builds one of the opener's setups
+    /// (another order, or a switch to the mirror).
+    Alternate,
+    /// The board left every setup of the opener.
+    OffBook,
+}
+
+/// A collection of openers, in display order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpenerBook {
+    openers: Vec<Opener>,
+}
+
+impl OpenerBook {
+    /// The compiled-in standard book (TKI, DT Cannon, MKO, PCO).
+    pub fn standard() -> Self {
+        Self::parse(STANDARD).expect("the bundled opener book parses")
+    }
+
+    /// Parse a book file (format in `book.txt`'s header). Errors name the
+    /// offending line.
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut openers: Vec<Opener> = Vec::new();
+        let mut rows: Option<Vec<&str>> = None;
+        for (number, raw) in text.lines().enumerate() {
+            let line = raw.trim();
+            let at = |message: String| format!("line {}: {message}", number + 1);
+            if line.starts_with('#') {
+                continue;
+            }
+            let header = line.strip_prefix("opener ");
+            // A blank line or the next header closes the setup being drawn.
+            if (line.is_empty() || line == "setup" || header.is_some())
+                && let Some(rows) = rows.take()
+            {
+                let opener = openers.last_mut().expect("a setup follows its opener");
+                opener.setups.push(parse_setup(&rows).map_err(at)?);
+            }
+            if let Some(header) = header {
+                let (id, name) = header
+                    .trim()
+                    .split_once(' ')
+                    .ok_or_else(|| at("expected `opener <id> <name>`".to_string()))?;
+                if openers.iter().any(|opener| opener.id == id) {
+                    return Err(at(format!("duplicate opener `{id}`")));
+                }
+                openers.push(Opener {
+                    id: id.to_string(),
+                    name: name.trim().to_string(),
+                    setups: Vec::new(),
+                });
+            } else if line == "setup" {
+                if openers.is_empty() {
+                    return Err(at("`setup` before any `opener`".to_string()));
+                }
+                rows = Some(Vec::new());
+            } else if !line.is_empty() {
+                rows.as_mut()
+                    .ok_or_else(|| at(format!("unexpected `{line}`")))?
+                    .push(line);
+            }
+        }
+        if let Some(rows) = rows {
+            let opener = openers.last_mut().expect("a setup follows its opener");
+            opener.setups.push(parse_setup(&rows)?);
+        }
+        for opener in &mut openers {
+            if opener.setups.is_empty() {
+                return Err(format!("opener `{}` has no setup", opener.id));
+            }
+            let mirrors: Vec<Setup> = opener.setups.iter().map(Setup::mirror).collect();
+            opener.setups.extend(mirrors);
+        }
+        Ok(Self { openers })
+    }
+
+    pub fn openers(&self) -> &[Opener] {
+        &self.openers
+    }
+
+    pub fn get(&self, id: &str) -> Option<&Opener> {
+        self.openers.iter().find(|opener| opener.id == id)
+    }
+
+    /// The book's move from `state`, if any opener's setup is on track and
+    /// buildable: the furthest-along setup wins, book order breaking ties —
+    /// so an empty board starts the first opener the bag allows.
+    pub fn next_placement(&self, state: &SearchState) -> Option<Placement> {
+        let mut on_track: Vec<(usize, &Setup)> = self
+            .openers
+            .iter()
+            .flat_map(|opener| &opener.setups)
+            .filter_map(|setup| Some((setup.progress(&state.board)?, setup)))
+            .collect();
+        on_track.sort_by_key(|&(progress, _)| std::cmp::Reverse(progress));
+        on_track
+            .into_iter()
+            .find_map(|(_, setup)| setup.next_placement(state))
+    }
+}
+
+/// How an [`OpenerRun`] ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RunEnd {
+    /// The setup is built and its finisher (if it has one) landed.
+    Complete,
+    /// A placement left the book, or no order of the known pieces can
+    /// finish the setup any more.
+    OffBook,
+}
+
+/// One attempt at an opener: the book's current target and a grade per
+/// placement.
+///
+/// Feed it every decision point with [`observe`](Self::observe) — the first
+/// spawn, and after each lock or hold — so the target always answers the
+/// piece in play.
+#[derive(Clone)]
+pub struct OpenerRun {
+    opener: Opener,
+    setup: Option<usize>,
+    /// The state the target was planned from, and the target.
+    plan: Option<(SearchState, Placement)>,
+    grades: Vec<Grade>,
+}
+
+impl OpenerRun {
+    pub fn new(opener: Opener) -> Self {
+        Self {
+            opener,
+            setup: None,
+            plan: None,
+            grades: Vec::new(),
+        }
+    }
+
+    pub fn opener(&self) -> &Opener {
+        &self.opener
+    }
+
+    pub fn grades(&self) -> &[Grade] {
+        &self.grades
+    }
+
+    /// The placement the book wants next, once the run has a decision.
+    pub fn target(&self) -> Option<&Placement> {
+        self.plan.as_ref().map(|(_, placement)| placement)
+    }
+
+    /// Fold in one decision point: grade the lock that led here when
+    /// `locked`, then re-plan from `state` (`None` when no piece is in play
+    /// — a finished game). Returns how the run ended, if it just did.
+    pub fn observe(&mut self, locked: bool, state: Option<&SearchState>) -> Option<RunEnd> {
+        if let Some((before, planned)) = self.plan.take().filter(|_| locked) {
+            let Some(after) = state else {
+                self.grades.push(Grade::OffBook);
+                return Some(RunEnd::OffBook);
+            };
+            let finishing = self.setup.is_some_and(|i| {
+                self.opener.setups[i]
+                    .finisher()
+                    .is_some_and(|finisher| finisher.matches(&planned))
+            });
+            let grade = self.opener.grade(&before, &planned, &after.board);
+            self.grades.push(grade);
+            match grade {
+                Grade::OffBook => return Some(RunEnd::OffBook),
+                Grade::Book if finishing => return Some(RunEnd::Complete),
+                _ => {}
+            }
+        }
+        let state = state?;
+        // Stay on the setup being built while it is on track; otherwise take
+        // the first one that is.
+        let current = self
+            .setup
+            .filter(|&i| self.opener.setups[i].progress(&state.board).is_some());
+        for i in current.into_iter().chain(0..self.opener.setups.len()) {
+            let setup = &self.opener.setups[i];
+            if setup.finisher().is_none() && setup.is_built(&state.board) {
+                self.setup = Some(i);
+                return Some(RunEnd::Complete);
+            }
+            if let Some(placement) = setup.next_placement(state) {
+                self.setup = Some(i);
+                self.plan = Some((state.clone(), placement));
+                return None;
+            }
+        }
+        Some(RunEnd::OffBook)
+    }
+}
+
+/// Parse one setup diagram (rows top to bottom) into its targets.
+fn parse_setup(rows: &[&str]) -> Result<Setup, String> {
+    let mut groups: Vec<(char, Vec<(isize, isize)>)> = Vec::new();
+    for (i, row) in rows.iter().enumerate() {
+        if row.chars().count() != BOOK_WIDTH {
+            return Err(format!("setup row `{row}` is not {BOOK_WIDTH} cells wide"));
+        }
+        let y = (rows.len() - 1 - i) as isize;
+        for (x, c) in row.chars().enumerate() {
+            if c == '.' {
+                continue;
+            }
+            match groups.iter_mut().find(|(letter, _)| *letter == c) {
+                Some((_, cells)) => cells.push((x as isize, y)),
+                None => groups.push((c, vec![(x as isize, y)])),
+            }
+        }
+    }
+    let mut targets = Vec::new();
+    let mut finisher = None;
+    for (letter, cells) in groups {
+        let piece_type = piece_for(letter.to_ascii_uppercase())
+            .ok_or_else(|| format!("unknown cell `{letter}`"))?;
+        let target = target_from(piece_type, &cells)
+            .ok_or_else(|| format!("the `{letter}` cells are not one {piece_type:?} piece"))?;
+        match letter {
+            't' => finisher = Some(target),
+            _ if letter.is_ascii_uppercase() => targets.push(target),
+            _ => return Err(format!("only the T finisher is lower-case, not `{letter}`")),
+        }
+    }
+    if targets.is_empty() {
+        return Err("a setup needs at least one piece".to_string());
+    }
+    let mut row_counts = vec![0; rows.len()];
+    for target in &targets {
+        for &(_, y) in &target.cells {
+            row_counts[y as usize] += 1;
+        }
+    }
+    if let Some(y) = row_counts.iter().position(|&count| count == BOOK_WIDTH) {
+        return Err(format!("row {y} is full before the finisher"));
+    }
+    Ok(Setup {
+        targets,
+        finisher,
+        mirrored: false,
+    })
+}
+
+/// The target `cells` make for `piece_type`, if they are that piece in one
+/// of its four rotations.
+fn target_from(piece_type: PieceType, cells: &[(isize, isize)]) -> Option<Target> {
+    let cells: [(isize, isize); 4] = cells.try_into().ok()?;
+    let mut cells = cells;
+    cells.sort();
+    let shape = normalized(&cells);
+    [
+        PieceRotation::R0,
+        PieceRotation::R90,
+        PieceRotation::R180,
+        PieceRotation::R270,
+    ]
+    .into_iter()
+    .any(|rotation| {
+        normalized(&piece_cells(&ActivePiece::at_pose(
+            piece_type,
+            (0, 0),
+            rotation,
+        ))) == shape
+    })
+    .then_some(Target { piece_type, cells })
+}
+
+/// A piece's absolute cells at its pose, sorted.
+fn piece_cells(piece: &ActivePiece) -> [(isize, isize); 4] {
+    let (ox, oy) = piece.origin();
+    let mut cells = piece.piece().cells().map(|(x, y)| (x + ox, y + oy));
+    cells.sort();
+    cells
+}
+
+/// `cells` shifted so their lowest x and y are 0 (sorted input stays sorted).
+fn normalized(cells: &[(isize, isize); 4]) -> [(isize, isize); 4] {
+    let min_x = cells.iter().map(|&(x, _)| x).min().unwrap_or(0);
+    let min_y = cells.iter().map(|&(_, y)| y).min().unwrap_or(0);
+    cells.map(|(x, y)| (x - min_x, y - min_y))
+}
+
+fn piece_for(letter: char) -> Option<PieceType> {
+    Some(match letter {
+        'I' => PieceType::I,
+        'O' => PieceType::O,
+        'T' => PieceType::T,
+        'S' => PieceType::S,
+        'Z' => PieceType::Z,
+        'J' => PieceType::J,
+        'L' => PieceType::L,
+        _ => return None,
+    })
+}
+
+/// The piece a mirror image turns `piece_type` into.
+fn mirror_type(piece_type: PieceType) -> PieceType {
+    match piece_type {
+        PieceType::S => PieceType::Z,
+        PieceType::Z => PieceType::S,
+        PieceType::J => PieceType::L,
+        PieceType::L => PieceType::J,
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::movegen::spawn_piece;
+    use crate::engine::{Board, CellKind, TSpinKind, classify_t_spin};
+
+    const BAG: [PieceType; 7] = [
+        PieceType::I,
+        PieceType::J,
+        PieceType::L,
+        PieceType::O,
+        PieceType::S,
+        PieceType::T,
+        PieceType::Z,
+    ];
+
+    /// A fresh game dealing `order` then `next`, `revealed` pieces in the
+    /// queue; the rest come back undealt.
+    fn opening(
+        order: &[PieceType],
+        next: &[PieceType],
+        revealed: usize,
+    ) -> (SearchState, Vec<PieceType>) {
+        let mut pieces: Vec<PieceType> = order.iter().chain(next).copied().collect();
+        let rest = pieces.split_off(1 + revealed);
+        let state = SearchState::for_test(
+            Board::with_top_margin(10, 20, 20),
+            spawn_piece(pieces[0], 10, 20),
+            None,
+            pieces[1..].iter().copied(),
+        );
+        (state, rest)
+    }
+
+    /// Commit `placement` and reveal the next undealt piece, as the engine does.
+    fn play(state: &mut SearchState, placement: &Placement, undealt: &mut Vec<PieceType>) {
+        state.commit_placement(placement);
+        if !undealt.is_empty() {
+            state.queue.push(undealt.remove(0));
+        }
+    }
+
+    /// A deterministic sample of bag orders (all 5040 are slow in debug
+    /// builds): one LCG shuffle per seed.
+    fn orders(count: u64) -> Vec<Vec<PieceType>> {
+        (0..count)
+            .map(|seed| {
+                let mut order = BAG.to_vec();
+                let mut x = seed
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                for i in (1..order.len()).rev() {
+                    x = x
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    order.swap(i, (x >> 33) as usize % (i + 1));
+                }
+                order
+            })
+            .collect()
+    }
+
+    #[test]
+    fn standard_book_parses_with_mirrors() {
+        let book = OpenerBook::standard();
+        let ids: Vec<&str> = book.openers().iter().map(|o| o.id.as_str()).collect();
+        assert_eq!(ids, ["tki", "dt", "mko", "pco"]);
+        for opener in book.openers() {
+            assert_eq!(opener.setups().len(), 2, "{}: drawn + mirror", opener.id);
+            let (drawn, mirror) = (&opener.setups()[0], &opener.setups()[1]);
+            assert!(!drawn.mirrored() && mirror.mirrored());
+            assert_eq!(drawn.targets().len(), 6, "{}: a bag minus the T", opener.id);
+            assert_eq!(mirror.mirror(), *drawn);
+        }
+        let pco = book.get("pco").unwrap();
+        assert!(pco.setups()[0].finisher().is_none());
+        assert!(book.get("tki").unwrap().setups()[0].finisher().is_some());
+    }
+
+    #[test]
+    fn parse_rejects_malformed_setups() {
+        let book = |rows: &str| OpenerBook::parse(&format!("opener x X\nsetup\n{rows}\n"));
+        assert!(book("IIII......").is_ok());
+        assert!(book("IIII.....").unwrap_err().contains("10 cells"));
+        assert!(book("III.I.....").unwrap_err().contains("not one I"));
+        assert!(book("IIIIJJJ.OO\n......JOO.").is_err());
+        assert!(book("IIIIIIIIOO\nLLL.....OO\nL.........").is_err());
+        assert!(book("QQQQ......").unwrap_err().contains("unknown"));
+        assert!(OpenerBook::parse("setup\nIIII......").is_err());
+        assert!(
+            OpenerBook::parse("opener x X\n")
+                .unwrap_err()
+                .contains("no setup")
+        );
+    }
+
+    #[test]
+    fn parse_full_row_is_an_error() {
+        let rows = "........LL\n....J..OOL\nIIIIJJJOOL";
+        let err = OpenerBook::parse(&format!("opener x X\nsetup\n{rows}\n")).unwrap_err();
+        assert!(err.contains("full before the finisher"), "{err}");
+    }
+
+    /// With the whole bag in view, following the book builds each setup for
+    /// every order it accepts — each TSD finisher a real T-spin double — and
+    /// every setup is accepted by some order.
+    #[test]
+    fn every_setup_builds_from_some_bag_order() {
+        let book = OpenerBook::standard();
+        for opener in book.openers() {
+            for (index, setup) in opener.setups().iter().enumerate() {
+                let mut built = 0;
+                for order in orders(30) {
+                    let (mut state, mut undealt) = opening(&order, &BAG, 12);
+                    if setup.next_placement(&state).is_none() {
+                        continue;
+                    }
+                    let mut finished = false;
+                    for _ in 0..8 {
+                        let Some(placement) = setup.next_placement(&state) else {
+                            break;
+                        };
+                        if setup.is_built(&state.board) {
+                            let finisher = setup.finisher().expect("only a finisher remains");
+                            assert!(finisher.matches(&placement));
+                            let before = state.board;
+                            assert_eq!(
+                                classify_t_spin(&placement.piece, &before),
+                                Some(TSpinKind::Full),
+                                "{} setup {index}",
+                                opener.id
+                            );
+                            let outcome = state.clone().commit_placement(&placement);
+                            assert_eq!(outcome.cleared_rows.len(), 2, "{}", opener.id);
+                            finished = true;
+                            break;
+                        }
+                        play(&mut state, &placement, &mut undealt);
+                    }
+                    finished |= setup.finisher().is_none() && setup.is_built(&state.board);
+                    assert!(finished, "{} setup {index} stalled on {order:?}", opener.id);
+                    built += 1;
+                }
+                assert!(built > 0, "{} setup {index}: no order builds it", opener.id);
+            }
+        }
+    }
+
+    #[test]
+    fn progress_tracks_targets_and_rejects_strays() {
+        let book = OpenerBook::standard();
+        let setup = &book.get("tki").unwrap().setups()[0];
+        let mut board = BitBoard::empty(10, 20, 40);
+        assert_eq!(setup.progress(&board), Some(0));
+        let i = setup
+            .targets()
+            .iter()
+            .find(|t| t.piece_type == PieceType::I)
+            .unwrap();
+        let mut half = board;
+        for &(x, y) in &i.cells {
+            board.set(x, y);
+        }
+        assert_eq!(setup.progress(&board), Some(1));
+        half.set(i.cells[0].0, i.cells[0].1);
+        assert_eq!(setup.progress(&half), None, "half a target is off track");
+        board.set(0, 10);
+        assert_eq!(setup.progress(&board), None, "a stray cell is off track");
+    }
+
+    #[test]
+    fn grades_book_alternate_and_off_book_placements() {
+        use PieceType::*;
+        let book = OpenerBook::standard();
+        let tki = book.get("tki").unwrap();
+        let (state, _) = opening(&[I, O, L, S, Z, J, T], &BAG, 5);
+        let (_, planned) = tki.next_placement(&state).unwrap();
+
+        let board_after = |placement: &Placement| {
+            let mut after = state.clone();
+            after.commit_placement(placement);
+            after.board
+        };
+        assert_eq!(
+            tki.grade(&state, &planned, &board_after(&planned)),
+            Grade::Book
+        );
+
+        // The I landing on the mirror's target instead still builds a setup.
+        let placements = hold_placements(&state);
+        let mirror_i = placements
+            .iter()
+            .find(|p| tki.setups()[1].targets().iter().any(|t| t.matches(p)))
+            .filter(|p| !tki.setups()[0].targets().iter().any(|t| t.matches(p)))
+            .expect("the mirror's I target is reachable");
+        assert_eq!(
+            tki.grade(&state, &planned, &board_after(mirror_i)),
+            Grade::Alternate
+        );
+
+        let stray = placements
+            .iter()
+            .find(|p| {
+                !tki.setups()
+                    .iter()
+                    .any(|s| s.targets().iter().any(|t| t.matches(p)))
+            })
+            .unwrap();
+        assert_eq!(
+            tki.grade(&state, &planned, &board_after(stray)),
+            Grade::OffBook
+        );
+    }
+
+    #[test]
+    fn run_follows_the_book_to_completion() {
+        use PieceType::*;
+        let book = OpenerBook::standard();
+        let mut run = OpenerRun::new(book.get("tki").unwrap().clone());
+        let (mut state, mut undealt) = opening(&[I, O, L, S, Z, J, T], &BAG, 5);
+        assert_eq!(run.observe(false, Some(&state)), None);
+        let mut end = None;
+        for _ in 0..8 {
+            let target = run
+                .target()
+                .expect("a target while the run goes on")
+                .clone();
+            play(&mut state, &target, &mut undealt);
+            end = run.observe(true, Some(&state));
+            if end.is_some() {
+                break;
+            }
+        }
+        assert_eq!(end, Some(RunEnd::Complete));
+        assert_eq!(
+            run.grades(),
+            [Grade::Book; 7],
+            "six setup pieces and the TSD"
+        );
+    }
+
+    #[test]
+    fn run_ends_off_book_on_a_stray_placement() {
+        use PieceType::*;
+        let book = OpenerBook::standard();
+        let mut run = OpenerRun::new(book.get("mko").unwrap().clone());
+        let (mut state, mut undealt) = opening(&[I, O, L, S, Z, J, T], &BAG, 5);
+        assert_eq!(run.observe(false, Some(&state)), None);
+        let stray = hold_placements(&state)
+            .into_iter()
+            .find(|p| {
+                !run.opener()
+                    .setups()
+                    .iter()
+                    .any(|s| s.targets().iter().any(|t| t.matches(p)))
+            })
+            .unwrap();
+        play(&mut state, &stray, &mut undealt);
+        assert_eq!(run.observe(true, Some(&state)), Some(RunEnd::OffBook));
+        assert_eq!(run.grades(), [Grade::OffBook]);
+    }
+
+    #[test]
+    fn book_serves_nothing_off_track() {
+        use PieceType::*;
+        let book = OpenerBook::standard();
+        let mut board = Board::with_top_margin(10, 20, 20);
+        board.set(0, 0, CellKind::Garbage);
+        let state = SearchState::for_test(board, spawn_piece(I, 10, 20), None, [O, L, S]);
+        assert!(book.next_placement(&state).is_none());
+    }
+}
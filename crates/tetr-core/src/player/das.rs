@@ -12,17 +12,31 @@
 //!   * Pressing the opposite direction restarts the full initial delay.
 //!   * Charge persists as long as the direction stays held; nothing external
 //!     resets it across piece locks/spawns, so auto-repeat carries over.
+//!   * A DAS cut ([`DasState::cut`], on a rotation or a spawn) holds a charged
+//!     auto-shift off for `cut_delay_seconds`, then it resumes where it was.
+//!   * `repeat_seconds == 0` is "ARR 0": every auto-shift is a slide to the
+//!     wall, which the controller asks of the engine with
+//!     [`InputFrame::instant_shift`](crate::engine::InputFrame::instant_shift).
 
 use crate::engine::MoveDirection;
 
-/// Player-side DAS timings. These live with the player, not the engine — the
-/// engine applies one cell per `left`/`right` pulse and never consults them.
+/// Player-side handling: the DAS timings plus the soft-drop speed. These live
+/// with the player, not the engine — the engine applies one cell per
+/// `left`/`right`/`soft_drop` pulse and never consults them.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct DasConfig {
     /// Initial delay before auto-shift begins, in seconds (~0.3s per spec).
     pub delay_seconds: f32,
     /// Auto-shift repeat interval after the initial delay, in seconds (~0.05s).
+    /// `0.0` is ARR 0: a charged shift slides the piece to the wall at once.
     pub repeat_seconds: f32,
+    /// DAS cut delay: how long a charged auto-shift waits after a rotation or
+    /// a spawn before it shifts again, in seconds. `0.0` turns the cut off.
+    pub cut_delay_seconds: f32,
+    /// Soft-drop factor: a held soft drop falls this many times faster than
+    /// the gravity in force (the guideline is 20). `None` drops one cell
+    /// every frame the key is held, whatever the gravity.
+    pub soft_drop_factor: Option<f32>,
 }
 
 impl Default for DasConfig {
@@ -30,6 +44,8 @@ impl Default for DasConfig {
         Self {
             delay_seconds: 0.3,
             repeat_seconds: 0.05,
+            cut_delay_seconds: 0.0,
+            soft_drop_factor: None,
         }
     }
 }
@@ -41,6 +57,8 @@ pub struct DasState {
     active_direction: Option<MoveDirection>,
     held_seconds: f32,
     repeat_elapsed_seconds: f32,
+    /// What is left of a DAS cut; auto-shift is held off while positive.
+    cut_seconds: f32,
 }
 
 impl DasState {
@@ -50,6 +68,21 @@ impl DasState {
         self.active_direction
     }
 
+    /// Whether DAS is past its initial delay: the next pulses are
+    /// auto-shifts, not taps.
+    pub fn charged(&self, config: &DasConfig) -> bool {
+        self.active_direction.is_some() && self.held_seconds >= config.delay_seconds
+    }
+
+    /// Cut a charged auto-shift (a rotation or a spawn just happened): it
+    /// pauses for `cut_delay_seconds`, then carries on. An uncharged DAS is
+    /// untouched — its initial delay is already a wait.
+    pub fn cut(&mut self, config: &DasConfig) {
+        if self.charged(config) {
+            self.cut_seconds = config.cut_delay_seconds.max(0.0);
+        }
+    }
+
     /// Advance the DAS machine one frame and return the move pulse (if any).
     ///
     /// * `held_direction`: the resolved horizontal direction held this frame, or
@@ -78,12 +111,14 @@ impl DasState {
             self.active_direction = Some(direction);
             self.held_seconds = 0.0;
             self.repeat_elapsed_seconds = 0.0;
+            self.cut_seconds = 0.0;
             return just_pressed.then_some(direction);
         }
 
         // Re-press of the same direction: fire immediately, restart repeat phase.
         if just_pressed {
             self.repeat_elapsed_seconds = 0.0;
+            self.cut_seconds = 0.0;
             return Some(direction);
         }
 
@@ -99,9 +134,21 @@ impl DasState {
             return None;
         }
 
+        if self.cut_seconds > 0.0 {
+            self.cut_seconds -= dt_seconds;
+            if self.cut_seconds > 0.0 {
+                return None;
+            }
+            // The cut is over: resume with a shift, as if freshly charged.
+            self.cut_seconds = 0.0;
+            self.repeat_elapsed_seconds = 0.0;
+            return Some(direction);
+        }
+
+        let repeat_seconds = config.repeat_seconds.max(0.0);
         self.repeat_elapsed_seconds += dt_seconds;
-        if self.repeat_elapsed_seconds >= config.repeat_seconds {
-            self.repeat_elapsed_seconds -= config.repeat_seconds;
+        if self.repeat_elapsed_seconds >= repeat_seconds {
+            self.repeat_elapsed_seconds -= repeat_seconds;
             Some(direction)
         } else {
             None
@@ -112,6 +159,7 @@ impl DasState {
         self.active_direction = None;
         self.held_seconds = 0.0;
         self.repeat_elapsed_seconds = 0.0;
+        self.cut_seconds = 0.0;
     }
 }
 
@@ -122,6 +170,8 @@ mod tests {
     const CONFIG: DasConfig = DasConfig {
         delay_seconds: 0.3,
         repeat_seconds: 0.05,
+        cut_delay_seconds: 0.0,
+        soft_drop_factor: None,
     };
 
     #[test]
@@ -235,4 +285,76 @@ mod tests {
             "carry-over must not re-arm the initial delay",
         );
     }
+
+    #[test]
+    fn a_cut_pauses_a_charged_shift_then_resumes() {
+        let config = DasConfig {
+            cut_delay_seconds: 0.1,
+            ..CONFIG
+        };
+        let mut das = DasState::default();
+        das.next_pulse(Some(MoveDirection::Left), true, 0.0, &config);
+        das.next_pulse(Some(MoveDirection::Left), false, 0.3, &config);
+        assert!(das.charged(&config));
+
+        das.cut(&config);
+        assert_eq!(
+            das.next_pulse(Some(MoveDirection::Left), false, 0.05, &config),
+            None,
+            "the cut holds the repeat off"
+        );
+        assert_eq!(
+            das.next_pulse(Some(MoveDirection::Left), false, 0.04, &config),
+            None
+        );
+        assert_eq!(
+            das.next_pulse(Some(MoveDirection::Left), false, 0.02, &config),
+            Some(MoveDirection::Left),
+            "and the shift resumes once it runs out"
+        );
+        assert_eq!(
+            das.next_pulse(Some(MoveDirection::Left), false, 0.05, &config),
+            Some(MoveDirection::Left),
+            "back on the repeat cadence"
+        );
+    }
+
+    #[test]
+    fn a_cut_leaves_an_uncharged_das_alone() {
+        let config = DasConfig {
+            cut_delay_seconds: 0.1,
+            ..CONFIG
+        };
+        let mut das = DasState::default();
+        das.next_pulse(Some(MoveDirection::Left), true, 0.0, &config);
+        das.cut(&config);
+        // The initial delay runs as if nothing happened.
+        das.next_pulse(Some(MoveDirection::Left), false, 0.29, &config);
+        assert_eq!(
+            das.next_pulse(Some(MoveDirection::Left), false, 0.02, &config),
+            Some(MoveDirection::Left)
+        );
+    }
+
+    #[test]
+    fn arr_zero_pulses_every_charged_frame() {
+        let config = DasConfig {
+            repeat_seconds: 0.0,
+            ..CONFIG
+        };
+        let mut das = DasState::default();
+        das.next_pulse(Some(MoveDirection::Right), true, 0.0, &config);
+        assert!(!das.charged(&config), "a tap is not an auto-shift");
+        assert_eq!(
+            das.next_pulse(Some(MoveDirection::Right), false, 0.3, &config),
+            Some(MoveDirection::Right)
+        );
+        for _ in 0..3 {
+            assert!(das.charged(&config));
+            assert_eq!(
+                das.next_pulse(Some(MoveDirection::Right), false, 0.016, &config),
+                Some(MoveDirection::Right)
+            );
+        }
+    }
 }
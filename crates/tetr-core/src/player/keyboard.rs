@@ -7,9 +7,15 @@
 //! `poll` resolves the held horizontal direction, advances the DAS machine, and
 //! emits an [`InputFrame`] whose `left`/`right` are per-frame one-cell pulses at
 //! the DAS cadence. The other action flags are edge-triggered (just-pressed) so
-//! the engine (which has no edge detection) sees one action per press.
+//! the engine (which has no edge detection) sees one action per press — all but
+//! soft drop, which pulses at the configured soft-drop factor while held.
+//!
+//! A rotation press or a spawn cuts a charged DAS ([`DasState::cut`]); `poll`
+//! spots a spawn as a deal from the bag between two snapshots.
 
-use crate::engine::{EngineSnapshot, InputFrame, MoveDirection};
+use crate::engine::{
+    EngineSnapshot, Gravity, InputFrame, MIN_LEVEL, MoveDirection, fall_speed_seconds,
+};
 use crate::player::das::{DasConfig, DasState};
 use crate::player::{PlayerController, resolve_horizontal};
 
@@ -39,6 +45,15 @@ pub struct KeyboardController {
     config: DasConfig,
     das: DasState,
     input: RawKeyboardFrame,
+    /// Time soft drop has been held since its last pulse; `None` while up.
+    soft_drop_seconds: Option<f32>,
+    /// Seconds per row of the gravity in force at the last polled snapshot
+    /// (the soft-drop factor divides it; 20G is zero). `None` before the
+    /// first poll, read as level 1's.
+    row_seconds: Option<f32>,
+    /// The bag's remaining count at the last poll: a change is a deal, and
+    /// every spawn deals.
+    last_bag: Option<usize>,
 }
 
 impl KeyboardController {
@@ -47,6 +62,9 @@ impl KeyboardController {
             config,
             das: DasState::default(),
             input: RawKeyboardFrame::default(),
+            soft_drop_seconds: None,
+            row_seconds: None,
+            last_bag: None,
         }
     }
 
@@ -57,8 +75,12 @@ impl KeyboardController {
 
     /// Build an [`InputFrame`] from a staged [`RawKeyboardFrame`] without mutating
     /// shared state — used internally by [`poll`] and directly by tests to drive
-    /// the DAS machine deterministically.
-    fn resolve_frame(&mut self, input: &RawKeyboardFrame) -> InputFrame {
+    /// the DAS machine deterministically, and by the AI's human-like input
+    /// renderer to run its held-key timelines through the same DAS.
+    pub(crate) fn resolve_frame(&mut self, input: &RawKeyboardFrame) -> InputFrame {
+        if input.rotate_cw_just_pressed || input.rotate_ccw_just_pressed {
+            self.das.cut(&self.config);
+        }
         let (held_direction, just_pressed) = resolve_horizontal(
             input.left_pressed,
             input.right_pressed,
@@ -70,23 +92,68 @@ impl KeyboardController {
         let pulse =
             self.das
                 .next_pulse(held_direction, just_pressed, input.dt_seconds, &self.config);
+        // ARR 0: an auto-shift goes all the way; a tap is still one cell.
+        let instant_shift =
+            pulse.is_some() && self.config.repeat_seconds <= 0.0 && self.das.charged(&self.config);
 
         InputFrame {
             dt_seconds: input.dt_seconds,
             left: pulse == Some(MoveDirection::Left),
             right: pulse == Some(MoveDirection::Right),
-            soft_drop: input.soft_drop,
+            instant_shift,
+            soft_drop: self.soft_drop_pulse(input),
             hard_drop: input.hard_drop_just_pressed,
             rotate_clockwise: input.rotate_cw_just_pressed,
-            rotate_counterclockwise: input.rotate_ccw_just_pressed,
+            // The engine turns once per frame, clockwise first; a frame that
+            // claims both is one a replay validator would have to second-guess.
+            rotate_counterclockwise: input.rotate_ccw_just_pressed && !input.rotate_cw_just_pressed,
             hold: input.hold_just_pressed,
             pause: input.pause_just_pressed,
         }
     }
+
+    /// Whether soft drop moves a cell this frame: at once on the press, then
+    /// every `gravity / factor` seconds while held (every frame without a
+    /// factor).
+    fn soft_drop_pulse(&mut self, input: &RawKeyboardFrame) -> bool {
+        if !input.soft_drop {
+            self.soft_drop_seconds = None;
+            return false;
+        }
+        let Some(factor) = self.config.soft_drop_factor else {
+            return true;
+        };
+        let Some(elapsed) = self.soft_drop_seconds.as_mut() else {
+            self.soft_drop_seconds = Some(0.0);
+            return true;
+        };
+        *elapsed += input.dt_seconds.max(0.0);
+        let row_seconds = self
+            .row_seconds
+            .unwrap_or_else(|| fall_speed_seconds(MIN_LEVEL));
+        let interval = row_seconds / factor.max(1.0);
+        if *elapsed >= interval {
+            *elapsed -= interval;
+            // A pulse a frame at most: a long frame does not bank extra cells.
+            *elapsed = elapsed.min(interval);
+            true
+        } else {
+            false
+        }
+    }
 }
 
 impl PlayerController for KeyboardController {
-    fn poll(&mut self, _snapshot: &EngineSnapshot) -> InputFrame {
+    fn poll(&mut self, snapshot: &EngineSnapshot) -> InputFrame {
+        self.row_seconds = Some(match snapshot.gravity() {
+            Gravity::SecondsPerRow(seconds) => seconds,
+            Gravity::TwentyG => 0.0,
+        });
+        let bag = snapshot.bag_remainder.len();
+        if self.last_bag.is_some_and(|last| last != bag) {
+            self.das.cut(&self.config);
+        }
+        self.last_bag = Some(bag);
         let input = self.input;
         self.resolve_frame(&input)
     }
@@ -100,6 +167,8 @@ mod tests {
     const CONFIG: DasConfig = DasConfig {
         delay_seconds: 0.3,
         repeat_seconds: 0.05,
+        cut_delay_seconds: 0.0,
+        soft_drop_factor: None,
     };
 
     /// A throwaway snapshot to satisfy `poll`'s signature; the keyboard
@@ -225,4 +294,130 @@ mod tests {
         assert!(frame.pause);
         assert_eq!(frame.dt_seconds, 0.016);
     }
+
+    #[test]
+    fn arr_zero_asks_for_a_slide_only_once_charged() {
+        let mut controller = KeyboardController::new(DasConfig {
+            repeat_seconds: 0.0,
+            ..CONFIG
+        });
+        let tap = poll(&mut controller, tap_left());
+        assert!(tap.left && !tap.instant_shift, "a tap is one cell");
+        assert!(!poll(&mut controller, hold_left(0.29)).left);
+        let charged = poll(&mut controller, hold_left(0.02));
+        assert!(charged.left && charged.instant_shift);
+    }
+
+    #[test]
+    fn arr_zero_slides_the_piece_to_the_wall() {
+        let mut engine = Engine::new(EngineConfig::default(), 7);
+        let mut controller = KeyboardController::new(DasConfig {
+            repeat_seconds: 0.0,
+            ..CONFIG
+        });
+        // Tap, charge, shift: well under a level-1 row of gravity in total.
+        for input in [tap_left(), hold_left(0.29), hold_left(0.02)] {
+            controller.set_input(input);
+            engine.step(controller.poll(&engine.snapshot()));
+        }
+        let active = engine.snapshot().active.unwrap();
+        let leftmost = active.cells.iter().map(|c| c.x).min().unwrap();
+        assert_eq!(leftmost, 0, "the charged frame took it to the wall");
+    }
+
+    #[test]
+    fn a_rotation_cuts_a_charged_das() {
+        let mut controller = KeyboardController::new(DasConfig {
+            cut_delay_seconds: 0.1,
+            ..CONFIG
+        });
+        poll(&mut controller, tap_left());
+        assert!(poll(&mut controller, hold_left(0.3)).left);
+        let rotate = RawKeyboardFrame {
+            rotate_cw_just_pressed: true,
+            ..hold_left(0.05)
+        };
+        let frame = poll(&mut controller, rotate);
+        assert!(frame.rotate_clockwise);
+        assert!(!frame.left, "the rotation frame is cut");
+        assert!(!poll(&mut controller, hold_left(0.04)).left);
+        assert!(poll(&mut controller, hold_left(0.02)).left);
+    }
+
+    #[test]
+    fn a_spawn_cuts_a_charged_das() {
+        let mut engine = Engine::new(EngineConfig::default(), 3);
+        let mut controller = KeyboardController::new(DasConfig {
+            cut_delay_seconds: 0.1,
+            ..CONFIG
+        });
+        controller.set_input(tap_left());
+        engine.step(controller.poll(&engine.snapshot())); // spawns, taps
+        controller.set_input(hold_left(0.3));
+        assert!(controller.poll(&engine.snapshot()).left, "charged");
+
+        // A hard drop deals the next piece; the next poll sees the deal.
+        engine.step(InputFrame {
+            hard_drop: true,
+            ..InputFrame::default()
+        });
+        engine.step(InputFrame::default());
+        controller.set_input(hold_left(0.05));
+        assert!(
+            !controller.poll(&engine.snapshot()).left,
+            "cut by the spawn"
+        );
+    }
+
+    #[test]
+    fn the_soft_drop_factor_paces_the_drop_off_gravity() {
+        let mut controller = KeyboardController::new(DasConfig {
+            soft_drop_factor: Some(20.0),
+            ..CONFIG
+        });
+        // Level 1 falls a row a second: 20x is a cell every 0.05 s.
+        let soft = |dt| RawKeyboardFrame {
+            dt_seconds: dt,
+            soft_drop: true,
+            ..RawKeyboardFrame::default()
+        };
+        assert!(
+            poll(&mut controller, soft(0.016)).soft_drop,
+            "the press drops"
+        );
+        assert!(!poll(&mut controller, soft(0.03)).soft_drop);
+        assert!(poll(&mut controller, soft(0.03)).soft_drop);
+        assert!(!poll(&mut controller, RawKeyboardFrame::default()).soft_drop);
+        assert!(
+            poll(&mut controller, soft(0.016)).soft_drop,
+            "a re-press drops"
+        );
+
+        // Master gravity, not the frozen guideline level, sets the pace: at
+        // a row every 0.2 s, 20x is a cell every 0.01 s — every frame.
+        let mut controller = KeyboardController::new(DasConfig {
+            soft_drop_factor: Some(20.0),
+            ..CONFIG
+        });
+        let mut master = Engine::new(
+            EngineConfig {
+                master: true,
+                ..EngineConfig::default()
+            },
+            7,
+        )
+        .snapshot();
+        assert_eq!(master.level, MIN_LEVEL);
+        master.master.as_mut().unwrap().gravity = Gravity::SecondsPerRow(0.2);
+        for _ in 0..4 {
+            controller.set_input(soft(0.016));
+            assert!(controller.poll(&master).soft_drop);
+        }
+
+        // Without a factor, every held frame is a cell.
+        let mut controller = KeyboardController::new(CONFIG);
+        for _ in 0..3 {
+            assert!(poll(&mut controller, soft(0.016)).soft_drop);
+        }
+    }
 }
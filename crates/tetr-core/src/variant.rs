@@ -0,0 +1,266 @@
+//! Solo variant rules: what ends a Marathon, Sprint, Ultra, Blitz, Dig or
+//! Master run and how its result ranks.
+//!
+//! The game wraps these in its own `Variant` (display names, reflection,
+//! high-score slots); they live here, below the host, because a run's
+//! legitimacy is decided by them too — the score validator (`tetr-verify`)
+//! replays a submitted run against the same end condition the game ended it
+//! on, without linking the game.
+
+use crate::engine::{
+    DigConfig, EngineSnapshot, GoalSystem, LockDelayCurve, MASTER_END_LEVEL, MAX_LEVEL,
+};
+
+/// Default Sprint line target (clear N lines as fast as possible).
+pub const DEFAULT_SPRINT_LINES: usize = 40;
+/// Default Ultra time limit in seconds (score as high as possible in 2 minutes).
+pub const DEFAULT_ULTRA_SECONDS: f32 = 120.0;
+/// Blitz time limit in seconds.
+pub const BLITZ_SECONDS: f32 = 120.0;
+/// Marathon ends when the player completes the final level (engine [`MAX_LEVEL`]).
+pub const MARATHON_END_LEVEL: u8 = MAX_LEVEL;
+/// The cheese lengths Dig offers, each in a clean and a messy flavour.
+pub const DIG_LINES: [u32; 3] = [10, 18, 100];
+
+/// What makes a variant end (besides the engine's own block-/lock-out, which is
+/// always fatal).
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EndCondition {
+    /// Marathon: the player completed level `N` (snapshot level reaches it).
+    ReachLevel(u8),
+    /// Sprint: `N` total lines cleared.
+    ClearLines(usize),
+    /// Ultra, Blitz: `secs` of play elapsed.
+    TimeLimit(f32),
+    /// Dig: every cheese row cleared.
+    ClearCheese,
+    /// Master: the level counter reached [`MASTER_END_LEVEL`].
+    CompleteMaster,
+}
+
+impl EndCondition {
+    /// Whether the condition holds for `snapshot` after `elapsed_seconds` of
+    /// play.
+    pub fn met(self, snapshot: &EngineSnapshot, elapsed_seconds: f32) -> bool {
+        match self {
+            EndCondition::ReachLevel(level) => snapshot.level >= level,
+            EndCondition::ClearLines(lines) => snapshot.lines >= lines,
+            EndCondition::TimeLimit(limit) => elapsed_seconds >= limit,
+            EndCondition::ClearCheese => snapshot.dig_remaining == Some(0),
+            EndCondition::CompleteMaster => snapshot
+                .master
+                .as_ref()
+                .is_some_and(|master| master.level >= MASTER_END_LEVEL),
+        }
+    }
+}
+
+/// Which figure is the "primary" result for ranking on the high-score board.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScoreKind {
+    /// Lower is better — rank by elapsed time ascending (Sprint, Dig).
+    Time,
+    /// Higher is better — rank by score descending (Marathon, Ultra, Blitz).
+    Score,
+    /// Higher grade is better, then higher score (Master). The grade files
+    /// as the result's level (see [`EngineSnapshot::result_level`]).
+    Grade,
+}
+
+/// The rule set of one solo variant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SoloRules {
+    Marathon,
+    Sprint,
+    Ultra,
+    /// Score as high as possible in two minutes, with levels that come fast,
+    /// a lock delay that tightens as they do, and an all-clear bonus.
+    Blitz,
+    /// Clear a seeded cheese stack as fast as possible; each length and
+    /// flavour is its own board.
+    Dig(DigConfig),
+    /// TGM-style: climb the 0–999 counter into 20G for a grade.
+    Master,
+}
+
+const fn dig(lines: u32, messy: bool) -> SoloRules {
+    SoloRules::Dig(DigConfig { lines, messy })
+}
+
+impl SoloRules {
+    pub const ALL: [SoloRules; 11] = [
+        SoloRules::Marathon,
+        SoloRules::Sprint,
+        SoloRules::Ultra,
+        SoloRules::Blitz,
+        dig(DIG_LINES[0], false),
+        dig(DIG_LINES[1], false),
+        dig(DIG_LINES[2], false),
+        dig(DIG_LINES[0], true),
+        dig(DIG_LINES[1], true),
+        dig(DIG_LINES[2], true),
+        SoloRules::Master,
+    ];
+
+    /// The engine's goal system under this variant.
+    pub fn goal_system(self) -> GoalSystem {
+        match self {
+            SoloRules::Marathon => GoalSystem::Variable,
+            SoloRules::Sprint | SoloRules::Ultra | SoloRules::Dig(_) => GoalSystem::Fixed,
+            SoloRules::Blitz => GoalSystem::Blitz,
+            // The Master counter replaces guideline leveling; scoring stays
+            // at level 1.
+            SoloRules::Master => GoalSystem::None,
+        }
+    }
+
+    /// How the lock delay scales with level: Blitz tightens it, everything
+    /// else keeps the player's setting flat.
+    pub fn lock_delay_curve(self) -> LockDelayCurve {
+        match self {
+            SoloRules::Blitz => LockDelayCurve::Tightening,
+            // Master's lock delay is its own, by section.
+            SoloRules::Marathon
+            | SoloRules::Sprint
+            | SoloRules::Ultra
+            | SoloRules::Dig(_)
+            | SoloRules::Master => LockDelayCurve::Flat,
+        }
+    }
+
+    /// Whether an all-clear scores the perfect-clear bonus (Blitz only).
+    pub fn perfect_clear_bonus(self) -> bool {
+        matches!(self, SoloRules::Blitz)
+    }
+
+    /// Whether the engine runs the Master level counter, gravity and grade.
+    pub fn master(self) -> bool {
+        matches!(self, SoloRules::Master)
+    }
+
+    /// The cheese the engine starts on (`None` outside Dig).
+    pub fn dig(self) -> Option<DigConfig> {
+        match self {
+            SoloRules::Dig(dig) => Some(dig),
+            SoloRules::Marathon
+            | SoloRules::Sprint
+            | SoloRules::Ultra
+            | SoloRules::Blitz
+            | SoloRules::Master => None,
+        }
+    }
+
+    pub fn end_condition(self) -> EndCondition {
+        match self {
+            SoloRules::Marathon => EndCondition::ReachLevel(MARATHON_END_LEVEL),
+            SoloRules::Sprint => EndCondition::ClearLines(DEFAULT_SPRINT_LINES),
+            SoloRules::Ultra => EndCondition::TimeLimit(DEFAULT_ULTRA_SECONDS),
+            SoloRules::Blitz => EndCondition::TimeLimit(BLITZ_SECONDS),
+            SoloRules::Dig(_) => EndCondition::ClearCheese,
+            SoloRules::Master => EndCondition::CompleteMaster,
+        }
+    }
+
+    pub fn score_kind(self) -> ScoreKind {
+        match self {
+            SoloRules::Sprint | SoloRules::Dig(_) => ScoreKind::Time,
+            SoloRules::Marathon | SoloRules::Ultra | SoloRules::Blitz => ScoreKind::Score,
+            SoloRules::Master => ScoreKind::Grade,
+        }
+    }
+
+    /// Lower-case identifier, as replay files and the leaderboard name it
+    /// (`dig-18-messy` for a Dig board).
+    pub fn key(self) -> String {
+        match self {
+            SoloRules::Marathon => "marathon".into(),
+            SoloRules::Sprint => "sprint".into(),
+            SoloRules::Ultra => "ultra".into(),
+            SoloRules::Blitz => "blitz".into(),
+            SoloRules::Dig(dig) => {
+                let flavour = if dig.messy { "messy" } else { "clean" };
+                format!("dig-{}-{flavour}", dig.lines)
+            }
+            SoloRules::Master => "master".into(),
+        }
+    }
+
+    /// Inverse of [`key`](Self::key).
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|rules| rules.key() == key)
+    }
+
+    /// Whether a finished run files on the board: a time-ranked run only
+    /// ranks once it reached the goal (a top-out has no meaningful time);
+    /// a score- or grade-ranked run always posts its (possibly lower) result.
+    pub fn run_qualifies(self, snapshot: &EngineSnapshot, elapsed_seconds: f32) -> bool {
+        match self.score_kind() {
+            ScoreKind::Time => self.end_condition().met(snapshot, elapsed_seconds),
+            ScoreKind::Score | ScoreKind::Grade => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_round_trip() {
+        for rules in SoloRules::ALL {
+            assert_eq!(SoloRules::from_key(&rules.key()), Some(rules));
+        }
+        assert_eq!(SoloRules::from_key("zen"), None);
+        assert_eq!(SoloRules::from_key("dig-12-clean"), None);
+    }
+
+    #[test]
+    fn blitz_is_a_two_minute_score_race_on_tightening_locks() {
+        let rules = SoloRules::from_key("blitz").unwrap();
+        assert_eq!(
+            rules.end_condition(),
+            EndCondition::TimeLimit(BLITZ_SECONDS)
+        );
+        assert_eq!(rules.score_kind(), ScoreKind::Score);
+        assert_eq!(rules.goal_system(), GoalSystem::Blitz);
+        assert_eq!(rules.lock_delay_curve(), LockDelayCurve::Tightening);
+        assert!(rules.perfect_clear_bonus());
+        assert!(!SoloRules::Ultra.perfect_clear_bonus());
+    }
+
+    #[test]
+    fn master_ends_at_999_and_files_its_grade_as_the_level() {
+        let rules = SoloRules::from_key("master").unwrap();
+        let config = crate::engine::EngineConfig {
+            goal_system: rules.goal_system(),
+            master: rules.master(),
+            ..Default::default()
+        };
+        let mut snapshot = crate::engine::Engine::new(config, 3).snapshot();
+        assert_eq!(snapshot.master.as_ref().unwrap().level, 0);
+        assert!(!rules.end_condition().met(&snapshot, 0.0));
+        assert!(rules.run_qualifies(&snapshot, 0.0));
+        let master = snapshot.master.as_mut().unwrap();
+        master.level = MASTER_END_LEVEL;
+        master.grade = crate::engine::MasterGrade::GRAND_MASTER;
+        assert!(rules.end_condition().met(&snapshot, 0.0));
+        assert_eq!(snapshot.result_level(), 18);
+        assert_eq!(rules.score_kind(), ScoreKind::Grade);
+    }
+
+    #[test]
+    fn dig_ends_when_the_cheese_is_gone() {
+        let rules = SoloRules::from_key("dig-10-messy").unwrap();
+        let config = crate::engine::EngineConfig {
+            goal_system: rules.goal_system(),
+            dig: rules.dig(),
+            ..Default::default()
+        };
+        let mut snapshot = crate::engine::Engine::new(config, 3).snapshot();
+        assert_eq!(snapshot.dig_remaining, Some(10));
+        assert!(!rules.end_condition().met(&snapshot, 600.0));
+        assert!(!rules.run_qualifies(&snapshot, 600.0));
+        snapshot.dig_remaining = Some(0);
+        assert!(rules.run_qualifies(&snapshot, 0.0));
+    }
+}
@@ -15,6 +15,10 @@
 //!   [`RawKeyboardFrame`] built from the DOM key state the JS reports through
 //!   [`Game::key_down`] / [`Game::key_up`]. Swapping modes is the whole "click to
 //!   take over / release to resume" mechanic — same engine, different controller.
+//!   The keyboard's handling (DAS, ARR, DCD, soft-drop factor) is the game's
+//!   defaults until the page sets its own with [`Game::set_handling`]. The bot
+//!   keys its placements in zero game time until the page asks for a person's
+//!   pace with [`Game::set_human_pace`].
 //! - Render reads ([`Game::board_cells`] etc.) return flat typed arrays of the
 //!   *cached* snapshot taken at the end of the last `tick`, so a frame is a handful
 //!   of cheap copies, no per-getter re-snapshot.
@@ -38,9 +42,9 @@
 
 use core::time::Duration;
 
-use tetr_core::ai::{AiController, Handicap};
+use tetr_core::ai::{AiController, Handicap, HumanInputConfig};
 use tetr_core::engine::{Engine, EngineConfig, EngineEvent, EngineSnapshot, SnapshotCell};
-use tetr_core::player::{KeyboardController, RawKeyboardFrame, drive_engine};
+use tetr_core::player::{DasConfig, KeyboardController, RawKeyboardFrame, drive_engine};
 use wasm_bindgen::prelude::*;
 
 /// The fixed simulation slice: 60 Hz, matching the engine driver and the AI
@@ -109,8 +113,14 @@ fn event_tag(e: &EngineEvent) -> Option<u8> {
 /// embed's "click to take over" / beatability contract: the dials degrade even
 /// this brain into a beatable opponent, and at a fixed handicap the run stays
 /// byte-identical per seed (the search is deterministic — no RNG, no clock).
-fn make_ai(handicap: Handicap, seed: u32) -> AiController {
-    AiController::attack(handicap, ai_seed(seed))
+/// At `human_pace` it keys each placement through DAS like a person instead.
+fn make_ai(handicap: Handicap, seed: u32, human_pace: bool) -> AiController {
+    let ai = AiController::attack(handicap, ai_seed(seed));
+    if human_pace {
+        ai.with_human_inputs(HumanInputConfig::default())
+    } else {
+        ai
+    }
 }
 
 /// Which controller is currently driving the engine.
@@ -129,7 +139,13 @@ pub struct Game {
     engine: Engine,
     ai: AiController,
     keyboard: KeyboardController,
+    /// The human's handling; every fresh keyboard controller is built with it.
+    handling: DasConfig,
     handicap: Handicap,
+    /// Whether the bot keys at a person's pace (see [`Game::set_human_pace`]).
+    human_pace: bool,
+    /// The seed the current game was dealt (the bot is rebuilt from it).
+    seed: u32,
     mode: Mode,
     /// Currently-held action bits.
     pressed: u16,
@@ -165,13 +181,17 @@ impl Game {
             // page). `f32::clamp` alone does NOT fix NaN (it propagates), so map any
             // non-finite value to 0 first.
             imperfection: sanitize_unit(imperfection),
+            ..Handicap::default()
         };
         let (engine, snap) = fresh_engine(seed);
         Game {
             engine,
-            ai: make_ai(handicap, seed),
+            ai: make_ai(handicap, seed, false),
             keyboard: KeyboardController::default(),
+            handling: DasConfig::default(),
             handicap,
+            human_pace: false,
+            seed,
             mode: Mode::Ai,
             pressed: 0,
             prev_pressed: 0,
@@ -185,8 +205,9 @@ impl Game {
         let (engine, snap) = fresh_engine(seed);
         self.engine = engine;
         self.snap = snap;
-        self.ai = make_ai(self.handicap, seed);
-        self.keyboard = KeyboardController::default();
+        self.seed = seed;
+        self.ai = make_ai(self.handicap, seed, self.human_pace);
+        self.keyboard = KeyboardController::new(self.handling);
         self.pressed = 0;
         self.prev_pressed = 0;
         self.acc = 0.0;
@@ -249,7 +270,36 @@ impl Game {
         self.mode = Mode::Human;
         self.pressed = 0;
         self.prev_pressed = 0;
-        self.keyboard = KeyboardController::default();
+        self.keyboard = KeyboardController::new(self.handling);
+    }
+
+    /// Set the human's handling, as the game's Options screen does:
+    ///
+    /// - `das_ms`: delayed auto-shift, the hold before a direction repeats.
+    /// - `arr_ms`: auto-repeat rate; `0` slides straight to the wall.
+    /// - `dcd_ms`: DAS cut delay after a rotation or a spawn; `0` is off.
+    /// - `sdf`: soft-drop factor over gravity (the guideline is 20). `0`, a
+    ///   negative or a non-finite value means max: a cell every step.
+    ///
+    /// Takes effect at once; a DAS charge in progress starts over.
+    pub fn set_handling(&mut self, das_ms: u32, arr_ms: u32, dcd_ms: u32, sdf: f32) {
+        let seconds = |ms: u32| ms as f32 / 1000.0;
+        self.handling = DasConfig {
+            delay_seconds: seconds(das_ms),
+            repeat_seconds: seconds(arr_ms),
+            cut_delay_seconds: seconds(dcd_ms),
+            soft_drop_factor: (sdf.is_finite() && sdf > 0.0).then_some(sdf),
+        };
+        self.keyboard = KeyboardController::new(self.handling);
+    }
+
+    /// Have the bot key its placements the way a person would — DAS, jittered
+    /// key intervals, gravity running through each maneuver — instead of in
+    /// zero game time, so an exhibition board moves at a human pace. Takes
+    /// effect at once: the bot is rebuilt and re-plans the piece in play.
+    pub fn set_human_pace(&mut self, on: bool) {
+        self.human_pace = on;
+        self.ai = make_ai(self.handicap, self.seed, on);
     }
 
     /// Whether a human is currently in control.
@@ -463,4 +513,37 @@ mod tests {
         assert_eq!(a.score(), b.score(), "reset(seed) must match new(seed)");
         assert_eq!(a.lines(), b.lines());
     }
+
+    /// A human-paced bot places markedly fewer pieces in the same time, and
+    /// still plays.
+    #[test]
+    fn human_pace_slows_the_bot() {
+        let locks = |human_pace: bool| {
+            let mut g = Game::new(7, 0, 0.0);
+            g.set_human_pace(human_pace);
+            let mut locks = 0;
+            for _ in 0..600 {
+                locks += g.tick(SIM_DT).iter().filter(|&&tag| tag <= 2).count();
+            }
+            locks
+        };
+        let (exact, human) = (locks(false), locks(true));
+        assert!(human > 0, "the human-paced bot still places pieces");
+        assert!(human * 2 < exact, "exact {exact}, human {human}");
+    }
+
+    /// The page's handling reaches the keyboard: ARR 0 takes a held piece to
+    /// the wall, and a non-finite soft-drop factor is just "max".
+    #[test]
+    fn handling_from_js_drives_the_keyboard() {
+        let mut g = Game::new(7, 0, 0.0);
+        g.set_handling(100, 0, 0, f32::NAN);
+        g.set_mode_human();
+        g.key_down(0);
+        for _ in 0..12 {
+            g.tick(SIM_DT);
+        }
+        let leftmost = g.active_cells().chunks(3).map(|c| c[0]).min().unwrap();
+        assert_eq!(leftmost, 0, "ARR 0 slid the piece to the wall");
+    }
 }
@@ -0,0 +1,674 @@
+//! The client side: a relay connection and the two controllers that put it
+//! behind the [`PlayerController`] seam.
+//!
+//! [`Lobby::join`] takes a seat in a room's lobby without waiting for the
+//! match: a host UI polls its [`roster`](Lobby::roster), readies up, and
+//! polls [`Lobby::started`] until the relay deals the seed.
+//! [`NetSession::connect`] is the blocking shorthand — join, ready up, wait.
+//! A started session hands out:
+//!
+//! - a [`LocalController`] wrapping this client's own driver (keyboard, bot):
+//!   every frame it produces is sent to the room and applied locally
+//!   [`NetConfig::input_delay`] ticks later;
+//! - a [`RemoteController`] per other seat, which replays that seat's frames
+//!   on the same schedule, blocking until the frame for the current tick has
+//!   arrived.
+//!
+//! Both apply the frame produced on tick `t` at tick `t + input_delay` (the
+//! first `input_delay` ticks are empty frames on every board), so every
+//! client steps every engine with the identical input sequence — lockstep.
+//! The delay is the latency budget: a remote frame only stalls the local
+//! simulation if it takes longer than `input_delay` ticks to arrive.
+//!
+//! `poll` cannot fail, so a remote seat that starves (timeout, disconnect)
+//! gets an empty frame and the failure is latched on the session; the driver
+//! checks [`NetSession::failure`] after polling and must stop, because the
+//! boards are no longer in step. [`LockstepMatch`](crate::LockstepMatch) does
+//! exactly that.
+//!
+//! A host that must never block (a game's frame loop) drives the same
+//! schedule through a [`LockstepClock`] instead: it submits the local frame
+//! (and any [`Command`]) when asked, and asks for the next tick's inputs,
+//! which are there or not yet.
+
+use std::collections::VecDeque;
+use std::io;
+use std::net::{Shutdown, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use tetr_core::engine::{EngineSnapshot, InputFrame};
+use tetr_core::player::PlayerController;
+
+use crate::protocol::{Command, Message, PROTOCOL_VERSION, read_message, write_message};
+use crate::relay::ROOM_SEATS;
+
+/// Lockstep knobs. Every client in a room must use the same
+/// `input_delay` — it is part of the simulation, not a local preference.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NetConfig {
+    /// Ticks between producing a frame and applying it (the latency budget).
+    pub input_delay: u32,
+    /// Report a state hash every this many ticks (`0` = never).
+    pub hash_interval: u32,
+    /// Longest a remote frame may be waited for before the seat counts as
+    /// lost; also bounds connecting to the relay.
+    pub timeout: Duration,
+    /// Longest [`NetSession::connect`] waits for the room to fill (`None` =
+    /// until the relay starts it or drops the connection).
+    pub start_timeout: Option<Duration>,
+}
+
+impl Default for NetConfig {
+    fn default() -> Self {
+        Self {
+            // 50 ms at the 60 Hz sim: hides a LAN round trip with room to spare.
+            input_delay: 3,
+            hash_interval: 60,
+            timeout: Duration::from_secs(5),
+            start_timeout: None,
+        }
+    }
+}
+
+/// Why a networked match cannot continue.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum NetError {
+    /// The socket failed (message kept; `io::Error` is not `Clone`).
+    Io(String),
+    /// The relay refused the join.
+    Refused(String),
+    /// The relay spoke out of turn.
+    Protocol(String),
+    /// No frame from `seat` for its `sequence` within [`NetConfig::timeout`].
+    Timeout { seat: u8, sequence: u32 },
+    /// `seat` left the room.
+    PeerLeft { seat: u8 },
+    /// The relay saw the clients' hashes disagree at `tick`.
+    Desync { tick: u32 },
+}
+
+impl std::fmt::Display for NetError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NetError::Io(err) => write!(f, "connection: {err}"),
+            NetError::Refused(reason) => write!(f, "the relay refused the join: {reason}"),
+            NetError::Protocol(err) => write!(f, "protocol: {err}"),
+            NetError::Timeout { seat, sequence } => {
+                write!(f, "seat {seat} sent no frame {sequence} in time")
+            }
+            NetError::PeerLeft { seat } => write!(f, "seat {seat} left the match"),
+            NetError::Desync { tick } => write!(f, "the boards desynced by tick {tick}"),
+        }
+    }
+}
+
+impl std::error::Error for NetError {}
+
+impl From<io::Error> for NetError {
+    fn from(err: io::Error) -> Self {
+        NetError::Io(err.to_string())
+    }
+}
+
+/// Who is in a room's lobby: per seat, present and readied up.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Roster {
+    pub present: Vec<bool>,
+    pub ready: Vec<bool>,
+}
+
+impl Roster {
+    fn from_masks(present: u8, ready: u8) -> Self {
+        let bits = |mask: u8| {
+            (0..ROOM_SEATS)
+                .map(|seat| mask & (1 << seat) != 0)
+                .collect()
+        };
+        Self {
+            present: bits(present),
+            ready: bits(ready),
+        }
+    }
+}
+
+/// A seat in a room that has not started yet.
+pub struct Lobby {
+    seat: u8,
+    config: NetConfig,
+    link: Arc<Link>,
+}
+
+/// A joined, started room.
+pub struct NetSession {
+    seat: u8,
+    players: u8,
+    seed: u64,
+    config: NetConfig,
+    link: Arc<Link>,
+}
+
+/// The connection shared by the session and its controllers. Dropping the
+/// last handle shuts the socket, which also ends the reader thread.
+struct Link {
+    writer: Mutex<TcpStream>,
+    inbox: Arc<Inbox>,
+}
+
+/// What the reader thread has received, behind one lock + condvar.
+struct Inbox {
+    state: Mutex<InboxState>,
+    arrived: Condvar,
+}
+
+struct InboxState {
+    roster: Roster,
+    /// `(seat, players, seed)` once the relay has started the match.
+    start: Option<(u8, u8, u64)>,
+    /// Per seat: frames (and their commands) received and not yet applied,
+    /// oldest first. Sized by the `Start`.
+    frames: Vec<VecDeque<(InputFrame, Option<Command>)>>,
+    /// Per seat: the next sequence number the reader expects.
+    expected: Vec<u32>,
+    desync: Option<u32>,
+    /// Why the reader stopped (peer left, socket closed, bad message).
+    closed: Option<NetError>,
+    /// The first failure that actually starved the simulation.
+    failure: Option<NetError>,
+}
+
+impl Lobby {
+    /// Connect to the relay at `addr` and take a seat in `room`'s lobby.
+    /// Blocks only for the handshake (bounded by [`NetConfig::timeout`]).
+    pub fn join(addr: impl ToSocketAddrs, room: &str, config: NetConfig) -> Result<Self, NetError> {
+        let mut stream = connect_any(addr, config.timeout)?;
+        stream.set_nodelay(true)?;
+        write_message(
+            &mut stream,
+            &Message::Join {
+                version: PROTOCOL_VERSION,
+                room: room.to_owned(),
+            },
+        )?;
+        stream.set_read_timeout(Some(config.timeout))?;
+        let seat = match read_message(&mut stream)? {
+            Message::Joined { seat } => seat,
+            Message::Refused { reason } => return Err(NetError::Refused(reason)),
+            other => {
+                return Err(NetError::Protocol(format!(
+                    "expected Joined, got {other:?}"
+                )));
+            }
+        };
+        stream.set_read_timeout(None)?;
+
+        let inbox = Arc::new(Inbox {
+            state: Mutex::new(InboxState {
+                roster: Roster::default(),
+                start: None,
+                frames: Vec::new(),
+                expected: Vec::new(),
+                desync: None,
+                closed: None,
+                failure: None,
+            }),
+            arrived: Condvar::new(),
+        });
+        let reader = stream.try_clone()?;
+        let reader_inbox = Arc::clone(&inbox);
+        thread::spawn(move || read_loop(reader, &reader_inbox));
+
+        Ok(Self {
+            seat,
+            config,
+            link: Arc::new(Link {
+                writer: Mutex::new(stream),
+                inbox,
+            }),
+        })
+    }
+
+    /// The seat the relay gave us.
+    pub fn seat(&self) -> u8 {
+        self.seat
+    }
+
+    /// The room as of the relay's latest roster.
+    pub fn roster(&self) -> Roster {
+        self.link.inbox.lock().roster.clone()
+    }
+
+    /// Ready up (or stand down). Readying contributes a fresh random seed
+    /// share, so the dealt seed is never one client's choice.
+    pub fn set_ready(&self, ready: bool) {
+        self.link.send(&Message::Ready {
+            ready,
+            share: rand::random(),
+        });
+    }
+
+    /// The started match, once every seat is ready; `Ok(None)` while the
+    /// lobby is still gathering. An error means the lobby is gone.
+    pub fn started(&self) -> Result<Option<NetSession>, NetError> {
+        let state = self.link.inbox.lock();
+        if let Some((seat, players, seed)) = state.start {
+            return Ok(Some(NetSession {
+                seat,
+                players,
+                seed,
+                config: self.config,
+                link: Arc::clone(&self.link),
+            }));
+        }
+        match &state.closed {
+            Some(closed) => Err(closed.clone()),
+            None => Ok(None),
+        }
+    }
+
+    /// Block until the match starts, or `timeout` (`None` = forever) passes.
+    pub fn wait_started(&self, timeout: Option<Duration>) -> Result<NetSession, NetError> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        loop {
+            if let Some(session) = self.started()? {
+                return Ok(session);
+            }
+            let state = self.link.inbox.lock();
+            if state.start.is_some() || state.closed.is_some() {
+                continue;
+            }
+            match deadline {
+                None => drop(self.link.inbox.arrived.wait(state).expect("net inbox lock")),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        return Err(NetError::Io("the match did not start in time".into()));
+                    }
+                    drop(
+                        self.link
+                            .inbox
+                            .arrived
+                            .wait_timeout(state, deadline - now)
+                            .expect("net inbox lock"),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Connect to the first of `addr`'s addresses that answers within `timeout`.
+fn connect_any(addr: impl ToSocketAddrs, timeout: Duration) -> Result<TcpStream, NetError> {
+    let mut last = NetError::Io("no address to connect to".into());
+    for addr in addr.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&addr, timeout) {
+            Ok(stream) => return Ok(stream),
+            Err(err) => last = err.into(),
+        }
+    }
+    Err(last)
+}
+
+impl NetSession {
+    /// Connect to the relay at `addr`, join `room`, ready up, and block until
+    /// the match starts (or [`NetConfig::start_timeout`] runs out).
+    pub fn connect(
+        addr: impl ToSocketAddrs,
+        room: &str,
+        config: NetConfig,
+    ) -> Result<Self, NetError> {
+        let lobby = Lobby::join(addr, room, config)?;
+        lobby.set_ready(true);
+        lobby.wait_started(config.start_timeout)
+    }
+
+    /// This client's own seat.
+    pub fn seat(&self) -> u8 {
+        self.seat
+    }
+
+    pub fn players(&self) -> u8 {
+        self.players
+    }
+
+    /// The engine seed the relay dealt the room.
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    pub fn config(&self) -> NetConfig {
+        self.config
+    }
+
+    /// Wrap this client's own driver so its frames reach the room.
+    pub fn local(&self, inner: Box<dyn PlayerController>) -> LocalController {
+        LocalController {
+            inner,
+            link: Arc::clone(&self.link),
+            seat: self.seat,
+            delay: self.config.input_delay,
+            pending: VecDeque::new(),
+            sequence: 0,
+        }
+    }
+
+    /// The controller that replays `seat`'s frames.
+    pub fn remote(&self, seat: u8) -> RemoteController {
+        assert!(
+            seat != self.seat && seat < self.players,
+            "seat {seat} is not a remote seat of this room"
+        );
+        RemoteController {
+            link: Arc::clone(&self.link),
+            seat,
+            delay: self.config.input_delay,
+            timeout: self.config.timeout,
+            polled: 0,
+        }
+    }
+
+    /// Report this client's state hash after `tick` ticks.
+    pub fn report_hash(&self, tick: u32, hash: u64) {
+        self.link.send(&Message::Hash { tick, hash });
+    }
+
+    /// The tick the relay flagged as desynced, once it has.
+    pub fn desync(&self) -> Option<u32> {
+        self.link.inbox.lock().desync
+    }
+
+    /// The failure that stopped a remote seat's frames, if any. Once set, the
+    /// boards are out of step and the match must end.
+    pub fn failure(&self) -> Option<NetError> {
+        self.link.inbox.lock().failure.clone()
+    }
+}
+
+impl Link {
+    fn send(&self, message: &Message) {
+        let mut writer = self.writer.lock().expect("net writer lock");
+        if let Err(err) = write_message(&mut *writer, message) {
+            // Our own frames not arriving starves the peer, not us; record it
+            // so a driver still sees the connection is gone.
+            self.inbox.lock().failure.get_or_insert(err.into());
+        }
+    }
+}
+
+impl Drop for Link {
+    fn drop(&mut self) {
+        if let Ok(writer) = self.writer.lock() {
+            let _ = writer.shutdown(Shutdown::Both);
+        }
+    }
+}
+
+impl Inbox {
+    fn lock(&self) -> std::sync::MutexGuard<'_, InboxState> {
+        self.state.lock().expect("net inbox lock")
+    }
+
+    /// Block until `seat`'s next frame is in, or the seat is lost.
+    fn next_frame(&self, seat: u8, sequence: u32, timeout: Duration) -> Option<InputFrame> {
+        let deadline = Instant::now() + timeout;
+        let mut state = self.lock();
+        loop {
+            if state.failure.is_some() {
+                return None;
+            }
+            if let Some((frame, _)) = state.frames[seat as usize].pop_front() {
+                return Some(frame);
+            }
+            if let Some(closed) = state.closed.clone() {
+                state.failure = Some(closed);
+                return None;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                state.failure = Some(NetError::Timeout { seat, sequence });
+                return None;
+            }
+            state = self
+                .arrived
+                .wait_timeout(state, deadline - now)
+                .expect("net inbox lock")
+                .0;
+        }
+    }
+}
+
+/// The reader thread: file every incoming message until the socket ends.
+fn read_loop(mut stream: TcpStream, inbox: &Inbox) {
+    let closed = loop {
+        let message = match read_message(&mut stream) {
+            Ok(message) => message,
+            Err(err) => break NetError::from(err),
+        };
+        let mut state = inbox.lock();
+        match message {
+            Message::Lobby { present, ready } => {
+                state.roster = Roster::from_masks(present, ready);
+            }
+            Message::Start {
+                seat,
+                players,
+                seed,
+            } if state.start.is_none() => {
+                state.start = Some((seat, players, seed));
+                state.frames = vec![VecDeque::new(); players as usize];
+                state.expected = vec![0; players as usize];
+            }
+            Message::Input {
+                seat,
+                sequence,
+                frame,
+                command,
+            } => {
+                let Some(expected) = state.expected.get_mut(seat as usize) else {
+                    break NetError::Protocol(format!("input for seat {seat}"));
+                };
+                if sequence != *expected {
+                    break NetError::Protocol(format!(
+                        "seat {seat} sent frame {sequence}, expected {expected}"
+                    ));
+                }
+                *expected += 1;
+                state.frames[seat as usize].push_back((frame, command));
+            }
+            Message::Desync { tick } => {
+                state.desync.get_or_insert(tick);
+            }
+            Message::PeerLeft { seat } => break NetError::PeerLeft { seat },
+            other => break NetError::Protocol(format!("unexpected {other:?}")),
+        }
+        drop(state);
+        inbox.arrived.notify_all();
+    };
+    inbox.lock().closed.get_or_insert(closed);
+    inbox.arrived.notify_all();
+}
+
+/// This client's own seat: sends each frame its driver produces, and applies
+/// it [`NetConfig::input_delay`] ticks later.
+pub struct LocalController {
+    inner: Box<dyn PlayerController>,
+    link: Arc<Link>,
+    seat: u8,
+    delay: u32,
+    pending: VecDeque<InputFrame>,
+    sequence: u32,
+}
+
+impl PlayerController for LocalController {
+    fn poll(&mut self, snapshot: &EngineSnapshot) -> InputFrame {
+        let frame = self.inner.poll(snapshot);
+        self.link.send(&Message::Input {
+            seat: self.seat,
+            sequence: self.sequence,
+            frame: frame.clone(),
+            command: None,
+        });
+        self.sequence += 1;
+        self.pending.push_back(frame);
+        if self.pending.len() as u32 > self.delay {
+            self.pending.pop_front().expect("pending is non-empty")
+        } else {
+            InputFrame::default()
+        }
+    }
+}
+
+/// A remote seat: replays the frames that seat sent, [`NetConfig::input_delay`]
+/// ticks after it produced them. Blocks in `poll` until the needed frame has
+/// arrived; a seat that never delivers yields empty frames and latches
+/// [`NetSession::failure`].
+pub struct RemoteController {
+    link: Arc<Link>,
+    seat: u8,
+    delay: u32,
+    timeout: Duration,
+    polled: u32,
+}
+
+impl PlayerController for RemoteController {
+    fn poll(&mut self, _snapshot: &EngineSnapshot) -> InputFrame {
+        let tick = self.polled;
+        self.polled += 1;
+        let Some(sequence) = tick.checked_sub(self.delay) else {
+            return InputFrame::default();
+        };
+        self.link
+            .inbox
+            .next_frame(self.seat, sequence, self.timeout)
+            .unwrap_or_default()
+    }
+}
+
+/// One lockstep tick's inputs, every seat's, as [`LockstepClock`] releases
+/// them.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tick {
+    pub tick: u32,
+    /// Per seat, in seat order: the frame to step that seat's board with.
+    pub frames: Vec<InputFrame>,
+    /// The commands issued with those frames, in seat order.
+    pub commands: Vec<(u8, Command)>,
+}
+
+/// The lockstep schedule for a host that must never block: the same
+/// `tick + input_delay` rule as the controllers, but a remote frame that has
+/// not arrived yet is a "not yet" instead of a wait.
+///
+/// Each tick the host asks [`needs_input`](Self::needs_input); when it does,
+/// it [`submit`](Self::submit)s exactly one local frame (its driver's poll,
+/// plus any command the player issued). Then [`advance`](Self::advance)
+/// releases the tick's inputs once every seat's are in. The host steps its
+/// boards only on a released tick, so every client steps in step.
+pub struct LockstepClock {
+    session: NetSession,
+    tick: u32,
+    sent: u32,
+    local: VecDeque<(InputFrame, Option<Command>)>,
+    /// When the current tick first came up short, for the timeout.
+    waiting_since: Option<Instant>,
+}
+
+impl LockstepClock {
+    pub fn new(session: NetSession) -> Self {
+        Self {
+            session,
+            tick: 0,
+            sent: 0,
+            local: VecDeque::new(),
+            waiting_since: None,
+        }
+    }
+
+    pub fn session(&self) -> &NetSession {
+        &self.session
+    }
+
+    /// Ticks released so far.
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// The local seat owes its frame for the current tick.
+    pub fn needs_input(&self) -> bool {
+        self.sent == self.tick
+    }
+
+    /// Send the local seat's frame for the current tick (applied
+    /// `input_delay` ticks from now, on every client).
+    pub fn submit(&mut self, frame: InputFrame, command: Option<Command>) {
+        self.session.link.send(&Message::Input {
+            seat: self.session.seat,
+            sequence: self.sent,
+            frame: frame.clone(),
+            command,
+        });
+        self.sent += 1;
+        self.local.push_back((frame, command));
+    }
+
+    /// Release the current tick if every seat's input for it is in:
+    /// `Ok(None)` means not yet (step nothing this time). Errors are final —
+    /// the peer is gone, starved past [`NetConfig::timeout`], or the relay
+    /// flagged a desync.
+    pub fn advance(&mut self) -> Result<Option<Tick>, NetError> {
+        let players = self.session.players as usize;
+        let local = self.session.seat as usize;
+        let Some(sequence) = self.tick.checked_sub(self.session.config.input_delay) else {
+            self.tick += 1;
+            return Ok(Some(Tick {
+                tick: self.tick - 1,
+                frames: vec![InputFrame::default(); players],
+                commands: Vec::new(),
+            }));
+        };
+        if self.sent <= sequence {
+            return Ok(None);
+        }
+        let mut state = self.session.link.inbox.lock();
+        if let Some(tick) = state.desync {
+            return Err(NetError::Desync { tick });
+        }
+        let missing = (0..players)
+            .find(|&seat| seat != local && state.frames[seat].is_empty())
+            .map(|seat| seat as u8);
+        if let Some(seat) = missing {
+            if let Some(closed) = state.closed.clone() {
+                state.failure.get_or_insert(closed.clone());
+                return Err(closed);
+            }
+            let since = *self.waiting_since.get_or_insert_with(Instant::now);
+            if since.elapsed() >= self.session.config.timeout {
+                let timeout = NetError::Timeout { seat, sequence };
+                state.failure.get_or_insert(timeout.clone());
+                return Err(timeout);
+            }
+            return Ok(None);
+        }
+        self.waiting_since = None;
+        let mut frames = Vec::with_capacity(players);
+        let mut commands = Vec::new();
+        for seat in 0..players {
+            let (frame, command) = if seat == local {
+                self.local.pop_front().expect("submitted above")
+            } else {
+                state.frames[seat].pop_front().expect("checked above")
+            };
+            frames.push(frame);
+            if let Some(command) = command {
+                commands.push((seat as u8, command));
+            }
+        }
+        self.tick += 1;
+        Ok(Some(Tick {
+            tick: self.tick - 1,
+            frames,
+            commands,
+        }))
+    }
+}
@@ -0,0 +1,159 @@
+//! State hashes: the desync detector.
+//!
+//! Lockstep never ships state, so nothing on the wire would notice two
+//! clients whose boards have drifted apart — a nondeterminism bug, a dropped
+//! garbage route, a tampered client. Each client therefore folds its whole
+//! match into one `u64` every [`NetConfig::hash_interval`](crate::NetConfig)
+//! ticks and reports it; the relay compares the seats' values for the same
+//! tick and flags the first mismatch.
+//!
+//! The hash is FNV-1a over everything a [`EngineSnapshot`] exposes that the
+//! rules depend on — the settled board, the active piece down to its lock
+//! timer bits, hold, queue, bag, score state and pending garbage. Ghost cells
+//! and the config are derived or fixed, so they are left out. It is a change
+//! detector, not a checksum against an adversary.
+
+use tetr_core::engine::{Engine, EngineSnapshot, SnapshotCell};
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+struct Fnv(u64);
+
+impl Fnv {
+    fn bytes(&mut self, bytes: &[u8]) {
+        for &b in bytes {
+            self.0 ^= u64::from(b);
+            self.0 = self.0.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    fn u64(&mut self, v: u64) {
+        self.bytes(&v.to_le_bytes());
+    }
+
+    fn cells(&mut self, cells: &[SnapshotCell]) {
+        self.u64(cells.len() as u64);
+        for cell in cells {
+            self.u64(cell.x as u64);
+            self.u64(cell.y as u64);
+            self.bytes(&[cell.piece_type as u8, u8::from(cell.garbage)]);
+        }
+    }
+}
+
+/// Hash one board's rule-relevant state.
+pub fn state_hash(snapshot: &EngineSnapshot) -> u64 {
+    let mut h = Fnv(FNV_OFFSET);
+    h.cells(&snapshot.board_cells);
+    match &snapshot.active {
+        Some(active) => {
+            h.bytes(&[1, active.piece_type as u8, active.rotation as u8]);
+            h.u64(active.origin.0 as u64);
+            h.u64(active.origin.1 as u64);
+            h.bytes(&[u8::from(active.hold_used), u8::from(active.landed)]);
+            h.bytes(&active.lock_timer_seconds.to_bits().to_le_bytes());
+        }
+        None => h.bytes(&[0]),
+    }
+    h.bytes(&[snapshot.hold.map_or(0xFF, |p| p as u8)]);
+    let pieces = |queue: &[_]| queue.iter().map(|&p| p as u8).collect::<Vec<u8>>();
+    h.u64(snapshot.next_queue.len() as u64);
+    h.bytes(&pieces(&snapshot.next_queue));
+    h.u64(snapshot.bag_remainder.len() as u64);
+    h.bytes(&pieces(&snapshot.bag_remainder));
+    h.u64(snapshot.score as u64);
+    h.u64(snapshot.lines as u64);
+    h.bytes(&[snapshot.level, u8::from(snapshot.back_to_back_active)]);
+    h.u64(u64::from(snapshot.combo));
+    h.u64(snapshot.pending_garbage.len() as u64);
+    for batch in &snapshot.pending_garbage {
+        h.u64(u64::from(batch.lines));
+        h.u64(batch.hole_col as u64);
+    }
+    h.bytes(&[match snapshot.game_over {
+        None => 0,
+        Some(status) => 1 + status as u8,
+    }]);
+    h.0
+}
+
+/// Hash a whole match: every seat's board, in seat order.
+pub fn match_hash(engines: &[Engine]) -> u64 {
+    snapshots_hash(
+        engines
+            .iter()
+            .map(Engine::snapshot)
+            .collect::<Vec<_>>()
+            .iter(),
+    )
+}
+
+/// [`match_hash`] for a host that keeps its boards' snapshots rather than a
+/// slice of engines (the game's seat entities), in seat order.
+pub fn snapshots_hash<'a>(snapshots: impl IntoIterator<Item = &'a EngineSnapshot>) -> u64 {
+    let mut h = Fnv(FNV_OFFSET);
+    for snapshot in snapshots {
+        h.u64(state_hash(snapshot));
+    }
+    h.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tetr_core::engine::{EngineConfig, InputFrame};
+
+    fn stepped(seed: u64, frames: &[InputFrame]) -> Engine {
+        let mut engine = Engine::new(EngineConfig::default(), seed);
+        for frame in frames {
+            engine.step(frame.clone());
+        }
+        engine
+    }
+
+    #[test]
+    fn equal_histories_hash_equal_and_any_divergence_shows() {
+        let tick = InputFrame {
+            dt_seconds: 1.0 / 60.0,
+            ..InputFrame::default()
+        };
+        let left = InputFrame {
+            left: true,
+            ..tick.clone()
+        };
+        let base = vec![tick.clone(); 30];
+        let a = stepped(5, &base);
+        assert_eq!(
+            state_hash(&a.snapshot()),
+            state_hash(&stepped(5, &base).snapshot())
+        );
+
+        // One extra input, another seed, one queued garbage line: all visible.
+        let mut moved = base.clone();
+        moved[10] = left;
+        assert_ne!(
+            state_hash(&a.snapshot()),
+            state_hash(&stepped(5, &moved).snapshot())
+        );
+        assert_ne!(
+            state_hash(&a.snapshot()),
+            state_hash(&stepped(6, &base).snapshot())
+        );
+        let mut attacked = stepped(5, &base);
+        attacked.queue_garbage(1);
+        assert_ne!(state_hash(&a.snapshot()), state_hash(&attacked.snapshot()));
+    }
+
+    #[test]
+    fn the_match_hash_depends_on_seat_order() {
+        let tick = InputFrame {
+            dt_seconds: 1.0 / 60.0,
+            ..InputFrame::default()
+        };
+        let frames = std::slice::from_ref(&tick);
+        let ab = match_hash(&[stepped(1, frames), stepped(2, frames)]);
+        let ba = match_hash(&[stepped(2, frames), stepped(1, frames)]);
+        assert_ne!(ab, ba);
+    }
+}
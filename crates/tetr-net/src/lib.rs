@@ -0,0 +1,47 @@
+//! `tetr-net` — deterministic netplay: lockstep over a relay server, or rollback.
+//!
+//! The engine is a pure function of `(seed, input frames)`, so a networked
+//! match never ships state: every client simulates every board, and the only
+//! traffic is each seat's per-tick [`InputFrame`](tetr_core::engine::InputFrame).
+//! The crate is three layers on that idea:
+//!
+//! | module | role |
+//! |---|---|
+//! | [`protocol`] | the wire format: sequenced input frames, state hashes, room control |
+//! | [`relay`] | the TCP relay server: rooms, seed dealing, forwarding, desync checks |
+//! | [`client`] | the lobby, the client connection, its [`PlayerController`](tetr_core::player::PlayerController)s and the non-blocking [`LockstepClock`] |
+//!
+//! plus [`LockstepMatch`], the headless two-board match a client runs on top
+//! (the game host drives the same pieces through its session), and
+//! [`state_hash`] / [`match_hash`], the desync detector.
+//!
+//! Lockstep stalls for every remote frame, so its input delay must cover the
+//! round trip. [`rollback`] is the alternative for laggier links: a
+//! transport-agnostic [`RollbackSession`] that predicts remote inputs, keeps
+//! saved engine states, and re-simulates when a prediction turns out wrong.
+//! [`netsim`] is the seeded latency/jitter/loss link its tests run over.
+//!
+//! Bevy-free and std-only, like `tetr-core`. The `tetr-relay` binary
+//! (src/main.rs) is the relay as a process:
+//!
+//! ```text
+//! cargo run --release -p tetr-net -- --addr 127.0.0.1:7878
+//! ```
+
+pub mod client;
+mod hash;
+mod lockstep;
+pub mod netsim;
+pub mod protocol;
+pub mod relay;
+pub mod rollback;
+
+pub use client::{
+    Lobby, LocalController, LockstepClock, NetConfig, NetError, NetSession, RemoteController,
+    Roster, Tick,
+};
+pub use hash::{match_hash, snapshots_hash, state_hash};
+pub use lockstep::{LockstepMatch, MatchOutcome};
+pub use protocol::Command;
+pub use relay::{ROOM_SEATS, Relay};
+pub use rollback::{Advance, InputPacket, RollbackConfig, RollbackSession, RollbackStats, Stalled};
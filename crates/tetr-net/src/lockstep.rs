@@ -0,0 +1,148 @@
+//! A headless networked versus match: every seat's engine, stepped in
+//! lockstep from the local driver and the relay.
+//!
+//! Each client owns a full copy of the match — one engine per seat, all on
+//! the relay's seed — and steps it exactly like the game's session step: poll
+//! and step every seat in seat order, **then** route each seat's
+//! `AttackSent` to the opponent's pending queue. Since every client feeds the
+//! same frames to the same engines in the same order, their copies stay
+//! bit-identical; the periodic [`match_hash`] report is what proves it.
+
+use tetr_core::engine::{Engine, EngineConfig, EngineEvent};
+use tetr_core::player::{PlayerController, drive_engine};
+
+use crate::client::{NetError, NetSession};
+use crate::hash::match_hash;
+
+/// One client's copy of a relayed match.
+pub struct LockstepMatch {
+    session: NetSession,
+    engines: Vec<Engine>,
+    controllers: Vec<Box<dyn PlayerController>>,
+    tick: u32,
+    attack: Vec<u32>,
+    topped: Vec<bool>,
+}
+
+/// How a [`LockstepMatch::run`] ended, as this client saw it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MatchOutcome {
+    pub ticks: u32,
+    /// Per seat: topped out.
+    pub topped: Vec<bool>,
+    /// Per seat: net attack sent.
+    pub attack: Vec<u32>,
+    /// [`match_hash`] of the final boards.
+    pub final_hash: u64,
+}
+
+impl LockstepMatch {
+    /// Seat every board: `local` drives this client's seat, a
+    /// [`RemoteController`](crate::RemoteController) each of the others.
+    pub fn new(
+        session: NetSession,
+        config: EngineConfig,
+        local: Box<dyn PlayerController>,
+    ) -> Self {
+        let players = session.players();
+        let mut local = Some(local);
+        let controllers = (0..players)
+            .map(|seat| -> Box<dyn PlayerController> {
+                if seat == session.seat() {
+                    Box::new(session.local(local.take().expect("one local seat")))
+                } else {
+                    Box::new(session.remote(seat))
+                }
+            })
+            .collect();
+        let engines = (0..players)
+            .map(|_| Engine::new(config.clone(), session.seed()))
+            .collect();
+        Self {
+            session,
+            engines,
+            controllers,
+            tick: 0,
+            attack: vec![0; players as usize],
+            topped: vec![false; players as usize],
+        }
+    }
+
+    pub fn session(&self) -> &NetSession {
+        &self.session
+    }
+
+    pub fn engines(&self) -> &[Engine] {
+        &self.engines
+    }
+
+    /// Ticks stepped so far.
+    pub fn tick(&self) -> u32 {
+        self.tick
+    }
+
+    /// Harness seam: reach into one board out of band (the desync tests
+    /// corrupt a client this way). A real driver never needs it.
+    #[doc(hidden)]
+    pub fn engine_mut(&mut self, seat: usize) -> &mut Engine {
+        &mut self.engines[seat]
+    }
+
+    /// Advance every board one tick. Returns each seat's events, or the
+    /// reason the match cannot go on (a lost seat, a flagged desync).
+    pub fn step(&mut self) -> Result<Vec<Vec<EngineEvent>>, NetError> {
+        let events: Vec<Vec<EngineEvent>> = self
+            .engines
+            .iter_mut()
+            .zip(&mut self.controllers)
+            .map(|(engine, controller)| drive_engine(engine, &mut **controller))
+            .collect();
+        // A flagged desync outranks the lost seat it usually causes: the peer
+        // that saw it first hangs up, and that is not the news.
+        if let Some(tick) = self.session.desync() {
+            return Err(NetError::Desync { tick });
+        }
+        if let Some(failure) = self.session.failure() {
+            return Err(failure);
+        }
+        for (seat, events) in events.iter().enumerate() {
+            let mut lines = 0;
+            for event in events {
+                match event {
+                    EngineEvent::AttackSent { lines: sent } => lines += sent,
+                    EngineEvent::GameOver { .. } => self.topped[seat] = true,
+                    _ => {}
+                }
+            }
+            if lines == 0 {
+                continue;
+            }
+            self.attack[seat] += lines;
+            for (target, engine) in self.engines.iter_mut().enumerate() {
+                if target != seat {
+                    engine.queue_garbage(lines);
+                }
+            }
+        }
+        self.tick += 1;
+        let interval = self.session.config().hash_interval;
+        if interval > 0 && self.tick.is_multiple_of(interval) {
+            self.session
+                .report_hash(self.tick, match_hash(&self.engines));
+        }
+        Ok(events)
+    }
+
+    /// Step until a seat tops out or `max_ticks` have run.
+    pub fn run(&mut self, max_ticks: u32) -> Result<MatchOutcome, NetError> {
+        while self.tick < max_ticks && !self.topped.contains(&true) {
+            self.step()?;
+        }
+        Ok(MatchOutcome {
+            ticks: self.tick,
+            topped: self.topped.clone(),
+            attack: self.attack.clone(),
+            final_hash: match_hash(&self.engines),
+        })
+    }
+}
@@ -0,0 +1,60 @@
+//! `tetr-relay` — run the lockstep relay server.
+//!
+//! ```text
+//! tetr-relay [--addr 127.0.0.1:7878] [--seed <u64>]
+//! ```
+//!
+//! `--seed` deals every room the same seed (reproducing a match offline);
+//! without it each room gets a fresh one.
+
+use std::process::ExitCode;
+
+use tetr_net::Relay;
+
+const DEFAULT_ADDR: &str = "127.0.0.1:7878";
+const USAGE: &str = "usage: tetr-relay [--addr <host:port>] [--seed <u64>]";
+
+fn main() -> ExitCode {
+    let mut addr = DEFAULT_ADDR.to_owned();
+    let mut seed = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match (arg.as_str(), args.next()) {
+            ("--addr", Some(value)) => addr = value,
+            ("--seed", Some(value)) => match value.parse::<u64>() {
+                Ok(value) => seed = Some(value),
+                Err(_) => {
+                    eprintln!("bad --seed {value:?}\n{USAGE}");
+                    return ExitCode::FAILURE;
+                }
+            },
+            _ => {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    let relay = match Relay::bind(&addr) {
+        Ok(relay) => relay,
+        Err(err) => {
+            eprintln!("tetr-relay: cannot bind {addr}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    let relay = match seed {
+        Some(seed) => relay.with_seed(seed),
+        None => relay,
+    };
+    match relay.local_addr() {
+        Ok(local) => eprintln!("tetr-relay: listening on {local}"),
+        Err(_) => eprintln!("tetr-relay: listening on {addr}"),
+    }
+    match relay.serve() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("tetr-relay: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
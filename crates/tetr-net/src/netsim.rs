@@ -0,0 +1,123 @@
+//! A deterministic network simulator for netcode tests.
+//!
+//! Real sockets make bad test fixtures: loopback has no latency worth the
+//! name, and anything slower is nondeterministic. A [`SimLink`] is one
+//! direction of a lossy, jittery link measured in **ticks**: each message is
+//! dropped with probability [`LinkConditions::loss`], otherwise delivered
+//! `latency + rand(0..=jitter)` ticks after it was sent — so messages can
+//! overtake each other. Everything comes from a seeded RNG, so a failing
+//! scenario replays exactly.
+
+use rand::rngs::StdRng;
+use rand::{RngExt, SeedableRng};
+
+/// One direction's quality. Ticks are the host's simulation ticks
+/// (1/60 s in the game), so `latency: 6` is a 100 ms one-way trip.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct LinkConditions {
+    pub latency: u32,
+    /// Extra delay per message, uniform in `0..=jitter`.
+    pub jitter: u32,
+    /// Chance in `0.0..=1.0` that a message never arrives.
+    pub loss: f64,
+}
+
+/// One direction of a simulated link carrying `T`s.
+pub struct SimLink<T> {
+    conditions: LinkConditions,
+    rng: StdRng,
+    /// `(arrival tick, send order, message)`, unsorted.
+    in_flight: Vec<(u32, u64, T)>,
+    sent: u64,
+}
+
+impl<T> SimLink<T> {
+    pub fn new(conditions: LinkConditions, seed: u64) -> Self {
+        Self {
+            conditions,
+            rng: StdRng::seed_from_u64(seed),
+            in_flight: Vec::new(),
+            sent: 0,
+        }
+    }
+
+    /// Put `message` on the wire at tick `now`.
+    pub fn send(&mut self, now: u32, message: T) {
+        self.sent += 1;
+        if self.conditions.loss > 0.0 && self.rng.random_bool(self.conditions.loss.min(1.0)) {
+            return;
+        }
+        let jitter = self.rng.random_range(0..=self.conditions.jitter);
+        let arrival = now + self.conditions.latency + jitter;
+        self.in_flight.push((arrival, self.sent, message));
+    }
+
+    /// Everything that has arrived by tick `now`, in arrival order (ties in
+    /// send order).
+    pub fn deliver(&mut self, now: u32) -> Vec<T> {
+        let mut arrived = Vec::new();
+        let mut i = 0;
+        while i < self.in_flight.len() {
+            if self.in_flight[i].0 <= now {
+                arrived.push(self.in_flight.swap_remove(i));
+            } else {
+                i += 1;
+            }
+        }
+        arrived.sort_by_key(|&(arrival, order, _)| (arrival, order));
+        arrived.into_iter().map(|(_, _, message)| message).collect()
+    }
+
+    /// Messages sent but neither delivered nor lost yet.
+    pub fn in_flight(&self) -> usize {
+        self.in_flight.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn a_clean_link_delivers_after_exactly_the_latency() {
+        let mut link = SimLink::new(
+            LinkConditions {
+                latency: 3,
+                ..LinkConditions::default()
+            },
+            1,
+        );
+        link.send(10, 'a');
+        link.send(11, 'b');
+        assert!(link.deliver(12).is_empty());
+        assert_eq!(link.deliver(13), vec!['a']);
+        assert_eq!(link.deliver(20), vec!['b']);
+        assert_eq!(link.in_flight(), 0);
+    }
+
+    #[test]
+    fn jitter_and_loss_are_seeded() {
+        let conditions = LinkConditions {
+            latency: 2,
+            jitter: 5,
+            loss: 0.25,
+        };
+        let trace = |seed| {
+            let mut link = SimLink::new(conditions, seed);
+            (0..200)
+                .flat_map(|t| {
+                    link.send(t, t);
+                    link.deliver(t)
+                })
+                .collect::<Vec<u32>>()
+        };
+        let a = trace(7);
+        assert_eq!(a, trace(7));
+        assert_ne!(a, trace(8));
+        assert!(a.len() > 120 && a.len() < 180, "~25% lost: {}", a.len());
+        assert!(
+            a.windows(2).any(|w| w[1] < w[0]),
+            "jitter reorders some messages"
+        );
+    }
+}
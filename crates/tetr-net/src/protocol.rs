@@ -0,0 +1,440 @@
+//! The wire protocol: a handful of length-prefixed binary messages.
+//!
+//! Every message travels as a little-endian `u32` body length followed by the
+//! body, whose first byte is the message tag. Lockstep only ever ships what
+//! the engine consumes — one [`InputFrame`] per seat per tick, stamped with a
+//! sequence number — plus a periodic [`state_hash`](crate::state_hash) so the
+//! relay can catch two clients whose simulations have drifted apart. No
+//! board, score, or snapshot data crosses the wire: every client simulates
+//! every board from `(seed, inputs)`.
+//!
+//! An [`InputFrame`] packs into six bytes: the nine buttons (the instant-shift
+//! flag counts as one) as one bit each in a `u16`, then `dt_seconds` as its raw
+//! `f32` bits. The bits are the point — an `f32`
+//! that survives a text round-trip "close enough" would desync gravity.
+//!
+//! Before the match, a room is a lobby: the relay admits a client with
+//! [`Message::Joined`], keeps everyone's [`Message::Lobby`] roster current,
+//! and starts the match once every seat has sent [`Message::Ready`]. Each
+//! `Ready` carries a random seed share and the dealt seed mixes all of them,
+//! so no single client picks the deal. Match control that must happen on the
+//! same tick everywhere (pause, resume, rematch) is a [`Command`] riding in
+//! the input stream: it takes effect on the tick its frame is applied.
+
+use std::io::{self, Read, Write};
+
+use tetr_core::engine::InputFrame;
+
+/// Bumped on any change to the message layout; the relay refuses a client
+/// that speaks a different version.
+pub const PROTOCOL_VERSION: u16 = 3;
+
+/// Largest body either side accepts. The biggest legal message is a `Join`
+/// with a room code well under this; anything larger is garbage on the wire.
+pub const MAX_BODY_BYTES: usize = 1024;
+
+/// Longest room code a `Join` may carry.
+pub const MAX_ROOM_LEN: usize = 64;
+
+/// Match control carried in the input stream, applied by every client on the
+/// tick the carrying frame is applied — so both boards pause, resume and
+/// restart on the same tick however the frames were delayed on the wire.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Command {
+    /// Freeze the match (either seat may pause).
+    Pause,
+    /// Unfreeze it (either seat may resume).
+    Resume,
+    /// This seat wants a rematch; it starts once every seat has asked.
+    Rematch,
+}
+
+impl Command {
+    fn to_byte(command: Option<Command>) -> u8 {
+        match command {
+            None => 0,
+            Some(Command::Pause) => 1,
+            Some(Command::Resume) => 2,
+            Some(Command::Rematch) => 3,
+        }
+    }
+
+    fn from_byte(byte: u8) -> Result<Option<Command>, ProtocolError> {
+        Ok(match byte {
+            0 => None,
+            1 => Some(Command::Pause),
+            2 => Some(Command::Resume),
+            3 => Some(Command::Rematch),
+            other => return Err(ProtocolError(format!("unknown command {other}"))),
+        })
+    }
+}
+
+/// One protocol message. Directions are noted per variant; the relay stamps
+/// the sender's seat on anything it forwards, so a client never claims one.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Message {
+    /// Client → relay: join `room`, opening it if nobody has yet.
+    Join { version: u16, room: String },
+    /// Relay → client: admitted to the room's lobby on `seat`.
+    Joined { seat: u8 },
+    /// Relay → clients: the lobby roster changed. Bit `i` of each mask is
+    /// seat `i`: `present` is occupied, `ready` has readied up.
+    Lobby { present: u8, ready: u8 },
+    /// Client → relay: ready up (or stand down) in the lobby. `share` is the
+    /// client's random contribution to the match seed.
+    Ready { ready: bool, share: u64 },
+    /// Relay → client: every seat is ready and the match starts. `seed` is
+    /// the engine seed every board uses; `seat` is the receiver's own.
+    Start { seat: u8, players: u8, seed: u64 },
+    /// Both ways: the input `seat` produced on its `sequence`-th tick, plus
+    /// any match command issued with it. A client sends with its own seat
+    /// (ignored); the relay forwards it to the rest of the room with the seat
+    /// it knows the sender by.
+    Input {
+        seat: u8,
+        sequence: u32,
+        frame: InputFrame,
+        command: Option<Command>,
+    },
+    /// Client → relay: the sender's state hash after `tick` ticks.
+    Hash { tick: u32, hash: u64 },
+    /// Relay → clients: the seats' hashes disagreed at `tick`.
+    Desync { tick: u32 },
+    /// Relay → client: the join was refused (room full, version mismatch).
+    Refused { reason: String },
+    /// Relay → clients: `seat`'s connection closed.
+    PeerLeft { seat: u8 },
+}
+
+const TAG_JOIN: u8 = 1;
+const TAG_START: u8 = 2;
+const TAG_INPUT: u8 = 3;
+const TAG_HASH: u8 = 4;
+const TAG_DESYNC: u8 = 5;
+const TAG_REFUSED: u8 = 6;
+const TAG_PEER_LEFT: u8 = 7;
+const TAG_JOINED: u8 = 8;
+const TAG_LOBBY: u8 = 9;
+const TAG_READY: u8 = 10;
+
+/// A body that does not decode: unknown tag, truncated field, bad UTF-8.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProtocolError(pub String);
+
+impl std::fmt::Display for ProtocolError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "tetr-net protocol: {}", self.0)
+    }
+}
+
+impl std::error::Error for ProtocolError {}
+
+impl From<ProtocolError> for io::Error {
+    fn from(err: ProtocolError) -> Self {
+        io::Error::new(io::ErrorKind::InvalidData, err)
+    }
+}
+
+impl Message {
+    /// The message body (tag first, no length prefix).
+    pub fn encode(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(16);
+        match self {
+            Message::Join { version, room } => {
+                out.push(TAG_JOIN);
+                out.extend_from_slice(&version.to_le_bytes());
+                put_str(&mut out, room);
+            }
+            Message::Joined { seat } => out.extend_from_slice(&[TAG_JOINED, *seat]),
+            Message::Lobby { present, ready } => {
+                out.extend_from_slice(&[TAG_LOBBY, *present, *ready]);
+            }
+            Message::Ready { ready, share } => {
+                out.extend_from_slice(&[TAG_READY, u8::from(*ready)]);
+                out.extend_from_slice(&share.to_le_bytes());
+            }
+            Message::Start {
+                seat,
+                players,
+                seed,
+            } => {
+                out.push(TAG_START);
+                out.extend_from_slice(&[*seat, *players]);
+                out.extend_from_slice(&seed.to_le_bytes());
+            }
+            Message::Input {
+                seat,
+                sequence,
+                frame,
+                command,
+            } => {
+                out.push(TAG_INPUT);
+                out.push(*seat);
+                out.extend_from_slice(&sequence.to_le_bytes());
+                out.extend_from_slice(&encode_frame(frame));
+                out.push(Command::to_byte(*command));
+            }
+            Message::Hash { tick, hash } => {
+                out.push(TAG_HASH);
+                out.extend_from_slice(&tick.to_le_bytes());
+                out.extend_from_slice(&hash.to_le_bytes());
+            }
+            Message::Desync { tick } => {
+                out.push(TAG_DESYNC);
+                out.extend_from_slice(&tick.to_le_bytes());
+            }
+            Message::Refused { reason } => {
+                out.push(TAG_REFUSED);
+                put_str(&mut out, reason);
+            }
+            Message::PeerLeft { seat } => {
+                out.extend_from_slice(&[TAG_PEER_LEFT, *seat]);
+            }
+        }
+        out
+    }
+
+    /// Decode one body. Trailing bytes are an error: a well-formed peer never
+    /// sends them, so they mean the stream is out of step.
+    pub fn decode(body: &[u8]) -> Result<Self, ProtocolError> {
+        let mut r = Reader(body);
+        let message = match r.u8()? {
+            TAG_JOIN => Message::Join {
+                version: u16::from_le_bytes(r.array()?),
+                room: r.string()?,
+            },
+            TAG_JOINED => Message::Joined { seat: r.u8()? },
+            TAG_LOBBY => Message::Lobby {
+                present: r.u8()?,
+                ready: r.u8()?,
+            },
+            TAG_READY => Message::Ready {
+                ready: r.u8()? != 0,
+                share: u64::from_le_bytes(r.array()?),
+            },
+            TAG_START => Message::Start {
+                seat: r.u8()?,
+                players: r.u8()?,
+                seed: u64::from_le_bytes(r.array()?),
+            },
+            TAG_INPUT => Message::Input {
+                seat: r.u8()?,
+                sequence: u32::from_le_bytes(r.array()?),
+                frame: decode_frame(r.array()?),
+                command: Command::from_byte(r.u8()?)?,
+            },
+            TAG_HASH => Message::Hash {
+                tick: u32::from_le_bytes(r.array()?),
+                hash: u64::from_le_bytes(r.array()?),
+            },
+            TAG_DESYNC => Message::Desync {
+                tick: u32::from_le_bytes(r.array()?),
+            },
+            TAG_REFUSED => Message::Refused {
+                reason: r.string()?,
+            },
+            TAG_PEER_LEFT => Message::PeerLeft { seat: r.u8()? },
+            tag => return Err(ProtocolError(format!("unknown message tag {tag}"))),
+        };
+        if !r.0.is_empty() {
+            return Err(ProtocolError(format!(
+                "{} trailing bytes after a {message:?}",
+                r.0.len()
+            )));
+        }
+        Ok(message)
+    }
+}
+
+/// Write one length-prefixed message.
+pub fn write_message(w: &mut impl Write, message: &Message) -> io::Result<()> {
+    let body = message.encode();
+    let mut framed = Vec::with_capacity(4 + body.len());
+    framed.extend_from_slice(&(body.len() as u32).to_le_bytes());
+    framed.extend_from_slice(&body);
+    // One write per message: with TCP_NODELAY set, a split header/body would
+    // go out as two segments.
+    w.write_all(&framed)?;
+    w.flush()
+}
+
+/// Read one length-prefixed message. A clean end of stream before the length
+/// prefix surfaces as [`io::ErrorKind::UnexpectedEof`], like a torn one.
+pub fn read_message(r: &mut impl Read) -> io::Result<Message> {
+    let mut len = [0u8; 4];
+    r.read_exact(&mut len)?;
+    let len = u32::from_le_bytes(len) as usize;
+    if len == 0 || len > MAX_BODY_BYTES {
+        return Err(ProtocolError(format!("message body of {len} bytes")).into());
+    }
+    let mut body = vec![0u8; len];
+    r.read_exact(&mut body)?;
+    Ok(Message::decode(&body)?)
+}
+
+/// Pack a frame: its [`InputFrame::buttons`] (two bytes, little-endian),
+/// then the raw `dt` bits.
+pub fn encode_frame(frame: &InputFrame) -> [u8; 6] {
+    let bits = frame.buttons().to_le_bytes();
+    let dt = frame.dt_seconds.to_bits().to_le_bytes();
+    [bits[0], bits[1], dt[0], dt[1], dt[2], dt[3]]
+}
+
+/// Inverse of [`encode_frame`], bit-exact.
+pub fn decode_frame(bytes: [u8; 6]) -> InputFrame {
+    InputFrame::from_buttons(
+        u16::from_le_bytes([bytes[0], bytes[1]]),
+        f32::from_bits(u32::from_le_bytes([bytes[2], bytes[3], bytes[4], bytes[5]])),
+    )
+}
+
+fn put_str(out: &mut Vec<u8>, s: &str) {
+    // Callers bound their strings (room codes, refusal reasons) far below this.
+    let len = s.len().min(u8::MAX as usize);
+    out.push(len as u8);
+    out.extend_from_slice(&s.as_bytes()[..len]);
+}
+
+/// A cursor over a message body.
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take(&mut self, n: usize) -> Result<&[u8], ProtocolError> {
+        if self.0.len() < n {
+            return Err(ProtocolError(format!(
+                "truncated body: wanted {n} more bytes, had {}",
+                self.0.len()
+            )));
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, ProtocolError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn array<const N: usize>(&mut self) -> Result<[u8; N], ProtocolError> {
+        Ok(self.take(N)?.try_into().expect("take returned N bytes"))
+    }
+
+    fn string(&mut self) -> Result<String, ProtocolError> {
+        let len = self.u8()? as usize;
+        String::from_utf8(self.take(len)?.to_vec())
+            .map_err(|_| ProtocolError("string field is not UTF-8".into()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn every_message() -> Vec<Message> {
+        vec![
+            Message::Join {
+                version: PROTOCOL_VERSION,
+                room: "ab12".into(),
+            },
+            Message::Joined { seat: 1 },
+            Message::Lobby {
+                present: 0b11,
+                ready: 0b10,
+            },
+            Message::Ready {
+                ready: true,
+                share: 0x0123_4567_89AB_CDEF,
+            },
+            Message::Start {
+                seat: 1,
+                players: 2,
+                seed: 0xDEAD_BEEF_0123_4567,
+            },
+            Message::Input {
+                seat: 0,
+                sequence: 70_000,
+                frame: InputFrame {
+                    dt_seconds: 1.0 / 60.0,
+                    left: true,
+                    hard_drop: true,
+                    hold: true,
+                    ..InputFrame::default()
+                },
+                command: None,
+            },
+            Message::Input {
+                seat: 1,
+                sequence: 3,
+                frame: InputFrame::default(),
+                command: Some(Command::Rematch),
+            },
+            Message::Hash {
+                tick: 600,
+                hash: u64::MAX - 3,
+            },
+            Message::Desync { tick: 120 },
+            Message::Refused {
+                reason: "room full".into(),
+            },
+            Message::PeerLeft { seat: 1 },
+        ]
+    }
+
+    #[test]
+    fn every_message_round_trips_through_the_stream_codec() {
+        let mut wire = Vec::new();
+        for message in every_message() {
+            write_message(&mut wire, &message).unwrap();
+        }
+        let mut cursor = io::Cursor::new(wire);
+        for message in every_message() {
+            assert_eq!(read_message(&mut cursor).unwrap(), message);
+        }
+        let end = read_message(&mut cursor).unwrap_err();
+        assert_eq!(end.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    /// Each button owns its bit, and `dt` survives bit-exactly — including a
+    /// value with no short decimal form.
+    #[test]
+    fn frames_pack_bit_exactly() {
+        for i in 0..9u16 {
+            let [lo, hi] = (1u16 << i).to_le_bytes();
+            let frame = decode_frame([lo, hi, 0, 0, 0, 0]);
+            assert_eq!(encode_frame(&frame)[..2], [lo, hi]);
+        }
+        let frame = InputFrame {
+            dt_seconds: f32::from_bits(0x3C88_8889),
+            rotate_counterclockwise: true,
+            pause: true,
+            instant_shift: true,
+            ..InputFrame::default()
+        };
+        let back = decode_frame(encode_frame(&frame));
+        assert_eq!(back, frame);
+        assert_eq!(back.dt_seconds.to_bits(), 0x3C88_8889);
+    }
+
+    #[test]
+    fn malformed_bodies_are_rejected() {
+        assert!(Message::decode(&[99]).is_err(), "unknown tag");
+        assert!(Message::decode(&[TAG_HASH, 1, 2]).is_err(), "truncated");
+        let mut long = Message::Desync { tick: 1 }.encode();
+        long.push(0);
+        assert!(Message::decode(&long).is_err(), "trailing bytes");
+        let mut bad_command = Message::Input {
+            seat: 0,
+            sequence: 0,
+            frame: InputFrame::default(),
+            command: None,
+        }
+        .encode();
+        *bad_command.last_mut().unwrap() = 9;
+        assert!(Message::decode(&bad_command).is_err(), "unknown command");
+
+        let oversized = ((MAX_BODY_BYTES + 1) as u32).to_le_bytes();
+        let err = read_message(&mut io::Cursor::new(oversized)).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+}
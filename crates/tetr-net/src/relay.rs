@@ -0,0 +1,376 @@
+//! The relay: a small TCP server that runs lockstep rooms.
+//!
+//! A room is opened by the first [`Message::Join`] that names it. Until the
+//! match starts it is a lobby: each client is admitted to a free seat with
+//! [`Message::Joined`], every change to who is present and who is ready goes
+//! out to the room as a [`Message::Lobby`] roster, and a seat that leaves
+//! frees its slot for the next joiner. Once all [`ROOM_SEATS`] seats have
+//! sent [`Message::Ready`], the relay deals the match seed — a mix of every
+//! seat's seed share — and sends each client a [`Message::Start`]. From then
+//! on it is a dumb pipe with one job of its own — every [`Message::Input`] is
+//! forwarded to the rest of the room stamped with the sender's seat, and
+//! every [`Message::Hash`] is held until all seats have reported that tick,
+//! then compared. The first mismatch is broadcast as [`Message::Desync`]
+//! (once per room: after a desync the later hashes say nothing new). A
+//! closed connection is broadcast as [`Message::PeerLeft`]; the room is
+//! dropped when its last client leaves.
+//!
+//! The relay never simulates and never reorders: TCP keeps each seat's inputs
+//! in sequence, so forwarding in arrival order is the whole protocol. Two
+//! threads per connection — its reader, and a writer draining the seat's
+//! outbox — with all rooms behind one lock that is never held across a
+//! socket write, so a stalled client backs up only its own outbox (and is cut
+//! off after [`WRITE_TIMEOUT`]). Sized for a localhost or LAN relay, not a
+//! matchmaking service.
+
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::protocol::{MAX_ROOM_LEN, Message, PROTOCOL_VERSION, read_message, write_message};
+
+/// Clients per room: lockstep versus is a two-board format.
+pub const ROOM_SEATS: usize = 2;
+
+/// How long one write to a client may block before the relay gives up on
+/// it and closes the connection.
+pub const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Joins so far, relay-wide: numbers each seat holder, so a departing
+/// client can only ever release its own seat.
+static JOINS: AtomicU64 = AtomicU64::new(0);
+
+/// A bound relay, ready to [`serve`](Self::serve).
+pub struct Relay {
+    listener: TcpListener,
+    seed: Option<u64>,
+    rooms: Arc<Mutex<HashMap<String, Room>>>,
+}
+
+struct Room {
+    /// One member per seat; `None` while the seat is free (or, once started,
+    /// since its client left). Only the seat's own reader frees it.
+    members: Vec<Option<Member>>,
+    /// Per seat: the seed share it readied up with.
+    ready: Vec<Option<u64>>,
+    started: bool,
+    /// Reported hashes per tick, until every seat has reported it.
+    hashes: BTreeMap<u32, Vec<Option<u64>>>,
+    desynced: bool,
+}
+
+/// A seated client, as the room sees it.
+struct Member {
+    /// Which join holds the seat (see [`JOINS`]).
+    join: u64,
+    /// Messages for the client, written by its writer thread in order.
+    outbox: Sender<Message>,
+}
+
+impl Relay {
+    /// Bind the listening socket (`127.0.0.1:0` picks a free port; read it
+    /// back with [`local_addr`](Self::local_addr)).
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            seed: None,
+            rooms: Arc::default(),
+        })
+    }
+
+    /// Deal every room this fixed seed instead of mixing the seats' shares,
+    /// so a relayed match can be reproduced offline.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accept connections forever, one handler thread each. Returns only if
+    /// the listener itself fails.
+    pub fn serve(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let rooms = Arc::clone(&self.rooms);
+            let seed = self.seed;
+            thread::spawn(move || {
+                let peer = stream.peer_addr().ok();
+                if let Err(err) = handle_client(stream, &rooms, seed) {
+                    eprintln!("relay: client {peer:?}: {err}");
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// [`serve`](Self::serve) on a background thread (tests, embedding a
+    /// relay in a host).
+    pub fn spawn(self) -> JoinHandle<io::Result<()>> {
+        thread::spawn(move || self.serve())
+    }
+}
+
+/// One client's lifetime: the join handshake, then its message loop, then the
+/// seat's release.
+fn handle_client(
+    mut stream: TcpStream,
+    rooms: &Mutex<HashMap<String, Room>>,
+    seed: Option<u64>,
+) -> io::Result<()> {
+    stream.set_nodelay(true)?;
+    let room = match read_message(&mut stream)? {
+        Message::Join { version, room } if version != PROTOCOL_VERSION => {
+            return refuse(
+                &mut stream,
+                format!("protocol {version}, relay speaks {PROTOCOL_VERSION}"),
+                &room,
+            );
+        }
+        Message::Join { room, .. } if room.is_empty() || room.len() > MAX_ROOM_LEN => {
+            return refuse(&mut stream, "bad room code".into(), &room);
+        }
+        Message::Join { room, .. } => room,
+        other => {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("expected Join, got {other:?}"),
+            ));
+        }
+    };
+
+    let join = JOINS.fetch_add(1, Ordering::Relaxed);
+    let seat = {
+        let mut rooms = rooms.lock().expect("relay rooms lock");
+        let entry = rooms.entry(room.clone()).or_insert_with(|| Room {
+            members: (0..ROOM_SEATS).map(|_| None).collect(),
+            ready: vec![None; ROOM_SEATS],
+            started: false,
+            hashes: BTreeMap::new(),
+            desynced: false,
+        });
+        let free = entry.members.iter().position(Option::is_none);
+        let Some(seat) = free.filter(|_| !entry.started) else {
+            drop(rooms);
+            return refuse(&mut stream, "room full".into(), &room);
+        };
+        let outbox = spawn_writer(&stream)?;
+        // The roster below queues behind it.
+        let _ = outbox.send(Message::Joined { seat: seat as u8 });
+        entry.members[seat] = Some(Member { join, outbox });
+        entry.broadcast_roster();
+        seat
+    };
+
+    let result = client_loop(&mut stream, rooms, &room, seat, seed);
+    leave(rooms, &room, seat, join);
+    match result {
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(()),
+        other => other,
+    }
+}
+
+/// Start the writer thread for a seated client and return its outbox. The
+/// thread ends when the outbox is dropped (the seat was released) or a write
+/// fails or times out (the client stopped taking messages); either way it
+/// closes the connection, which also ends the client's reader.
+fn spawn_writer(stream: &TcpStream) -> io::Result<Sender<Message>> {
+    let mut writer = stream.try_clone()?;
+    writer.set_write_timeout(Some(WRITE_TIMEOUT))?;
+    let (outbox, queue) = mpsc::channel();
+    thread::spawn(move || {
+        for message in queue {
+            if write_message(&mut writer, &message).is_err() {
+                break;
+            }
+        }
+        let _ = writer.shutdown(Shutdown::Both);
+    });
+    Ok(outbox)
+}
+
+fn client_loop(
+    stream: &mut TcpStream,
+    rooms: &Mutex<HashMap<String, Room>>,
+    room: &str,
+    seat: usize,
+    seed: Option<u64>,
+) -> io::Result<()> {
+    loop {
+        let message = read_message(stream)?;
+        let mut rooms = rooms.lock().expect("relay rooms lock");
+        let Some(entry) = rooms.get_mut(room) else {
+            return Ok(());
+        };
+        match message {
+            // Readiness only means something in the lobby; a late toggle
+            // after the start is harmless and ignored.
+            Message::Ready { ready, share } if !entry.started => {
+                entry.ready[seat] = ready.then_some(share);
+                entry.broadcast_roster();
+                entry.start_if_ready(seed, room);
+            }
+            Message::Ready { .. } => {}
+            Message::Input {
+                sequence,
+                frame,
+                command,
+                ..
+            } if entry.started => {
+                let forward = Message::Input {
+                    seat: seat as u8,
+                    sequence,
+                    frame,
+                    command,
+                };
+                entry.broadcast_except(seat, &forward);
+            }
+            Message::Hash { tick, hash } if entry.started => {
+                entry.record_hash(seat, tick, hash, room);
+            }
+            other => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("unexpected {other:?} from seat {seat}"),
+                ));
+            }
+        }
+    }
+}
+
+impl Room {
+    /// Tell every member who is present and who is ready.
+    fn broadcast_roster(&self) {
+        let mask = |bits: &mut dyn Iterator<Item = bool>| {
+            bits.enumerate()
+                .fold(0u8, |mask, (seat, set)| mask | (u8::from(set) << seat))
+        };
+        let roster = Message::Lobby {
+            present: mask(&mut self.members.iter().map(Option::is_some)),
+            ready: mask(&mut self.ready.iter().map(Option::is_some)),
+        };
+        self.broadcast_except(usize::MAX, &roster);
+    }
+
+    /// Start the match once every seat is taken and ready: deal the seed and
+    /// send each member its `Start`.
+    fn start_if_ready(&mut self, seed: Option<u64>, room: &str) {
+        let everyone = self.members.iter().all(Option::is_some);
+        let Some(shares) = self.ready.iter().copied().collect::<Option<Vec<u64>>>() else {
+            return;
+        };
+        if !everyone {
+            return;
+        }
+        self.started = true;
+        let seed = seed.unwrap_or_else(|| mix_shares(&shares));
+        eprintln!("relay: room {room:?} starts, seed {seed:#018x}");
+        for (seat, member) in self.members.iter().enumerate() {
+            if let Some(member) = member {
+                let _ = member.outbox.send(Message::Start {
+                    seat: seat as u8,
+                    players: ROOM_SEATS as u8,
+                    seed,
+                });
+            }
+        }
+    }
+
+    fn broadcast_except(&self, sender: usize, message: &Message) {
+        for (seat, member) in self.members.iter().enumerate() {
+            if seat == sender {
+                continue;
+            }
+            // A closed outbox is a writer that gave up on its client: the
+            // seat stays taken until that client's reader releases it.
+            if let Some(member) = member {
+                let _ = member.outbox.send(message.clone());
+            }
+        }
+    }
+
+    fn record_hash(&mut self, seat: usize, tick: u32, hash: u64, room: &str) {
+        if self.desynced {
+            return;
+        }
+        let seats = self.members.len();
+        let reported = self.hashes.entry(tick).or_insert_with(|| vec![None; seats]);
+        reported[seat] = Some(hash);
+        if reported.iter().any(Option::is_none) {
+            return;
+        }
+        let agreed = reported.windows(2).all(|w| w[0] == w[1]);
+        self.hashes.remove(&tick);
+        if !agreed {
+            self.desynced = true;
+            eprintln!("relay: room {room:?} desynced at tick {tick}");
+            self.broadcast_except(usize::MAX, &Message::Desync { tick });
+        }
+    }
+}
+
+/// Release `seat` if `join` still holds it: tell the others, and drop the
+/// room once it is empty.
+fn leave(rooms: &Mutex<HashMap<String, Room>>, room: &str, seat: usize, join: u64) {
+    let mut rooms = rooms.lock().expect("relay rooms lock");
+    let Some(entry) = rooms.get_mut(room) else {
+        return;
+    };
+    if entry.members[seat]
+        .as_ref()
+        .is_none_or(|member| member.join != join)
+    {
+        return;
+    }
+    // Dropping the outbox lets the writer flush what is queued and close.
+    entry.members[seat] = None;
+    entry.ready[seat] = None;
+    if entry.members.iter().all(Option::is_none) {
+        rooms.remove(room);
+    } else if entry.started {
+        entry.broadcast_except(seat, &Message::PeerLeft { seat: seat as u8 });
+    } else {
+        // Still in the lobby: the seat is free again for the next joiner.
+        entry.broadcast_roster();
+    }
+}
+
+fn refuse(stream: &mut TcpStream, reason: String, room: &str) -> io::Result<()> {
+    eprintln!("relay: refused a join to {room:?}: {reason}");
+    write_message(stream, &Message::Refused { reason })
+}
+
+/// The match seed from every seat's share, in seat order. Each share passes
+/// through a full-avalanche mix (splitmix64's finalizer), so the seed is as
+/// random as the most random share: one honest client is enough.
+fn mix_shares(shares: &[u64]) -> u64 {
+    shares.iter().fold(0x9E37_79B9_7F4A_7C15, |acc, &share| {
+        let mut z = (acc ^ share).wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Every share moves the seed, and seat order matters (a seat cannot
+    /// cancel its opponent's share by echoing it).
+    #[test]
+    fn the_seed_depends_on_every_share() {
+        let base = mix_shares(&[1, 2]);
+        assert_ne!(base, mix_shares(&[1, 3]));
+        assert_ne!(base, mix_shares(&[0, 2]));
+        assert_ne!(base, mix_shares(&[2, 1]));
+        assert_ne!(mix_shares(&[7, 7]), mix_shares(&[0, 0]));
+    }
+}
@@ -0,0 +1,583 @@
+//! Rollback netcode: GGPO-style speculation over the engine.
+//!
+//! Lockstep ([`LockstepMatch`](crate::LockstepMatch)) waits for every remote
+//! frame before stepping, so its input delay must cover the round trip —
+//! fine on a LAN, sluggish above ~50 ms. A [`RollbackSession`] never waits:
+//! it steps every board at once with the local frame and a **prediction** for
+//! each remote seat (the seat's last confirmed frame, repeated), and keeps a
+//! ring of saved states, one per frame of the prediction window. When a
+//! remote frame arrives that disagrees with what was predicted for its tick,
+//! the next [`advance`](RollbackSession::advance) restores the state saved at
+//! that tick and re-simulates up to the present with the corrected inputs —
+//! every seat, garbage routing included, because a mispredicted clear changes
+//! what the other board receives.
+//!
+//! The session is host-agnostic. It owns the engines and the input logs; the
+//! host owns time and transport: it polls its local controller, calls
+//! `advance`, ships [`outgoing`](RollbackSession::outgoing) packets however it
+//! likes (the relay, a socket, the [`netsim`](crate::netsim) in tests) and
+//! feeds what arrives to [`receive`](RollbackSession::receive). Packets carry
+//! every frame the recipient has not acknowledged, so a lost or reordered
+//! packet costs latency, never correctness.
+//!
+//! Speculation is bounded: once the local side is
+//! [`RollbackConfig::max_rollback`] frames ahead of a remote seat's last
+//! confirmed frame, `advance` refuses ([`Stalled`]) until that seat catches
+//! up — the ring only holds that many states. Frames below
+//! [`confirmed_frame`](RollbackSession::confirmed_frame) are final on every
+//! client; [`confirmed_hash`](RollbackSession::confirmed_hash) is the value
+//! to compare for desync detection.
+
+use std::collections::VecDeque;
+
+use tetr_core::engine::{Engine, EngineConfig, EngineEvent, InputFrame};
+
+use crate::hash::match_hash;
+
+/// Rollback knobs. Every client of a match must agree on `input_delay` (it is
+/// part of the simulation); `max_rollback` is local.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RollbackConfig {
+    /// Most frames the local side may run ahead of a remote seat's last
+    /// confirmed frame; also the depth of the saved-state ring.
+    pub max_rollback: u32,
+    /// Frames between producing a local frame and applying it. A frame or two
+    /// of delay trades a little latency for far fewer rollbacks.
+    pub input_delay: u32,
+}
+
+impl Default for RollbackConfig {
+    fn default() -> Self {
+        Self {
+            // 133 ms of speculation at 60 Hz: covers a cross-continent ping.
+            max_rollback: 8,
+            input_delay: 1,
+        }
+    }
+}
+
+/// One seat's frames for the recipient, from the first the recipient has not
+/// acknowledged. `ack` acknowledges the recipient's own frames: the sender
+/// has confirmed every one of them below it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputPacket {
+    pub seat: u8,
+    pub first_tick: u32,
+    pub frames: Vec<InputFrame>,
+    pub ack: u32,
+}
+
+/// [`RollbackSession::advance`] declined: a remote seat is
+/// [`RollbackConfig::max_rollback`] frames behind. Nothing was consumed; poll
+/// the local controller again only once [`RollbackSession::can_advance`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stalled {
+    pub seat: u8,
+}
+
+/// What one [`RollbackSession::advance`] did.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Advance {
+    /// The tick just simulated.
+    pub tick: u32,
+    /// Each seat's events for that tick — speculative until the tick is
+    /// confirmed (a later rollback may replay it differently).
+    pub events: Vec<Vec<EngineEvent>>,
+    /// Frames re-simulated before it to apply corrections (0 = none).
+    pub rolled_back: u32,
+}
+
+/// Running totals, for tuning and tests.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RollbackStats {
+    /// Corrections applied.
+    pub rollbacks: u32,
+    /// Frames re-simulated across all corrections.
+    pub resimulated: u64,
+    /// Deepest single correction.
+    pub deepest: u32,
+}
+
+/// Per-seat match totals, saved and restored with the engines.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Tally {
+    attack: Vec<u32>,
+    topped: Vec<bool>,
+}
+
+struct SavedFrame {
+    tick: u32,
+    engines: Vec<Engine>,
+    tally: Tally,
+}
+
+/// One seat's input log. `confirmed` holds ticks `base..` as known for
+/// certain; `used` holds ticks `base..frame` as actually simulated (a
+/// prediction wherever the confirmed frame was not in yet).
+struct SeatInputs {
+    base: u32,
+    confirmed: VecDeque<InputFrame>,
+    used: VecDeque<InputFrame>,
+    /// The latest confirmed frame — the prediction for every tick past it.
+    last: InputFrame,
+}
+
+impl SeatInputs {
+    fn new(input_delay: u32) -> Self {
+        // The first `input_delay` ticks are empty on every seat, by rule, so
+        // they start confirmed everywhere.
+        Self {
+            base: 0,
+            confirmed: VecDeque::from(vec![InputFrame::default(); input_delay as usize]),
+            used: VecDeque::new(),
+            last: InputFrame::default(),
+        }
+    }
+
+    /// One past the last confirmed tick.
+    fn confirmed_end(&self) -> u32 {
+        self.base + self.confirmed.len() as u32
+    }
+
+    /// The frame to simulate `tick` with: confirmed if known, else predicted.
+    fn input_for(&self, tick: u32) -> InputFrame {
+        match tick.checked_sub(self.base) {
+            Some(i) if (i as usize) < self.confirmed.len() => self.confirmed[i as usize].clone(),
+            _ => self.last.clone(),
+        }
+    }
+
+    fn confirm(&mut self, frame: InputFrame) {
+        self.last = frame.clone();
+        self.confirmed.push_back(frame);
+    }
+
+    fn record_used(&mut self, tick: u32, frame: InputFrame) {
+        let i = (tick - self.base) as usize;
+        if i < self.used.len() {
+            self.used[i] = frame;
+        } else {
+            self.used.push_back(frame);
+        }
+    }
+
+    /// Drop ticks below `lower` that are both simulated and confirmed.
+    fn prune(&mut self, lower: u32) {
+        while self.base < lower && !self.used.is_empty() && !self.confirmed.is_empty() {
+            self.used.pop_front();
+            self.confirmed.pop_front();
+            self.base += 1;
+        }
+    }
+}
+
+/// A rollback match: every seat's engine, the local seat driven directly and
+/// the others by prediction-plus-correction.
+pub struct RollbackSession {
+    config: RollbackConfig,
+    local: u8,
+    engines: Vec<Engine>,
+    tally: Tally,
+    /// Ticks simulated so far (the next tick to simulate).
+    frame: u32,
+    inputs: Vec<SeatInputs>,
+    /// Ring of saved states, indexed by `tick % len`.
+    saved: Vec<Option<SavedFrame>>,
+    /// Earliest tick whose simulated inputs turned out wrong.
+    rollback_from: Option<u32>,
+    /// Per seat: how many of our local frames that seat has confirmed.
+    acked: Vec<u32>,
+    stats: RollbackStats,
+}
+
+impl RollbackSession {
+    /// A match of `players` boards, all on `seed`; this client drives `local`.
+    pub fn new(
+        config: EngineConfig,
+        seed: u64,
+        players: u8,
+        local: u8,
+        rollback: RollbackConfig,
+    ) -> Self {
+        assert!(local < players, "local seat {local} of {players}");
+        let seats = players as usize;
+        Self {
+            config: rollback,
+            local,
+            engines: (0..seats)
+                .map(|_| Engine::new(config.clone(), seed))
+                .collect(),
+            tally: Tally {
+                attack: vec![0; seats],
+                topped: vec![false; seats],
+            },
+            frame: 0,
+            inputs: (0..seats)
+                .map(|_| SeatInputs::new(rollback.input_delay))
+                .collect(),
+            saved: (0..=rollback.max_rollback).map(|_| None).collect(),
+            rollback_from: None,
+            acked: vec![rollback.input_delay; seats],
+            stats: RollbackStats::default(),
+        }
+    }
+
+    pub fn local_seat(&self) -> u8 {
+        self.local
+    }
+
+    /// The current (speculative) boards — what the host renders.
+    pub fn engines(&self) -> &[Engine] {
+        &self.engines
+    }
+
+    /// Harness seam: reach into one board out of band. Only safe before the
+    /// first [`advance`](Self::advance), identically on every client (a test
+    /// preparing a board); a real driver never needs it.
+    #[doc(hidden)]
+    pub fn engine_mut(&mut self, seat: usize) -> &mut Engine {
+        &mut self.engines[seat]
+    }
+
+    /// Ticks simulated so far.
+    pub fn frame(&self) -> u32 {
+        self.frame
+    }
+
+    /// Per seat: net attack sent so far (speculative).
+    pub fn attack(&self) -> &[u32] {
+        &self.tally.attack
+    }
+
+    /// Per seat: topped out (speculative — check it again once
+    /// [`confirmed_frame`](Self::confirmed_frame) has caught up before
+    /// calling the match).
+    pub fn topped(&self) -> &[bool] {
+        &self.tally.topped
+    }
+
+    pub fn stats(&self) -> RollbackStats {
+        self.stats
+    }
+
+    /// Every tick below this is simulated with confirmed inputs on every seat
+    /// — final, identical on every client.
+    pub fn confirmed_frame(&self) -> u32 {
+        self.inputs
+            .iter()
+            .map(SeatInputs::confirmed_end)
+            .min()
+            .unwrap_or(0)
+            .min(self.frame)
+    }
+
+    /// `(tick, match hash)` of the state at [`confirmed_frame`](Self::confirmed_frame),
+    /// once any pending correction is applied. Two clients reporting
+    /// different hashes for the same tick have desynced.
+    pub fn confirmed_hash(&self) -> Option<(u32, u64)> {
+        if self.rollback_from.is_some() {
+            return None;
+        }
+        let tick = self.confirmed_frame();
+        if tick == self.frame {
+            return Some((tick, match_hash(&self.engines)));
+        }
+        self.saved_at(tick)
+            .map(|saved| (tick, match_hash(&saved.engines)))
+    }
+
+    /// Whether [`advance`](Self::advance) would run now.
+    pub fn can_advance(&self) -> bool {
+        self.stalled_seat().is_none()
+    }
+
+    fn stalled_seat(&self) -> Option<u8> {
+        (0..self.inputs.len() as u8).find(|&seat| {
+            seat != self.local
+                && self.frame
+                    >= self.inputs[seat as usize].confirmed_end() + self.config.max_rollback
+        })
+    }
+
+    /// Take this tick's local frame (applied [`RollbackConfig::input_delay`]
+    /// ticks from now), apply any pending correction, and simulate one tick.
+    pub fn advance(&mut self, local: InputFrame) -> Result<Advance, Stalled> {
+        if let Some(seat) = self.stalled_seat() {
+            return Err(Stalled { seat });
+        }
+        self.inputs[self.local as usize].confirm(local);
+        let rolled_back = self.settle();
+        let tick = self.frame;
+        self.save(tick);
+        let events = self.simulate(tick);
+        self.frame += 1;
+        self.prune();
+        Ok(Advance {
+            tick,
+            events,
+            rolled_back,
+        })
+    }
+
+    /// Apply a pending correction now, without advancing: restore the state
+    /// saved at the first mispredicted tick and re-simulate to the present.
+    /// Returns the frames re-simulated. `advance` does this itself; a host
+    /// calls it directly only to settle a finished match.
+    pub fn settle(&mut self) -> u32 {
+        let Some(from) = self.rollback_from.take() else {
+            return 0;
+        };
+        let saved = self
+            .saved_at(from)
+            .expect("a correction never reaches past the saved window");
+        let (engines, tally) = (saved.engines.clone(), saved.tally.clone());
+        self.engines = engines;
+        self.tally = tally;
+        for tick in from..self.frame {
+            if tick > from {
+                self.save(tick);
+            }
+            self.simulate(tick);
+        }
+        let depth = self.frame - from;
+        self.stats.rollbacks += 1;
+        self.stats.resimulated += u64::from(depth);
+        self.stats.deepest = self.stats.deepest.max(depth);
+        depth
+    }
+
+    /// The packet that brings `to` up to date on our local frames.
+    pub fn outgoing(&self, to: u8) -> InputPacket {
+        let log = &self.inputs[self.local as usize];
+        let first_tick = self.acked[to as usize].max(log.base);
+        let frames = log
+            .confirmed
+            .iter()
+            .skip((first_tick - log.base) as usize)
+            .cloned()
+            .collect();
+        InputPacket {
+            seat: self.local,
+            first_tick,
+            frames,
+            ack: self.inputs[to as usize].confirmed_end(),
+        }
+    }
+
+    /// Ingest a remote seat's packet: confirm its new frames, note its
+    /// acknowledgement, and schedule a correction if any already-simulated
+    /// tick used a different frame. Duplicate and stale frames are ignored.
+    pub fn receive(&mut self, packet: &InputPacket) {
+        let seat = packet.seat as usize;
+        if packet.seat == self.local || seat >= self.inputs.len() {
+            return;
+        }
+        self.acked[seat] = self.acked[seat].max(packet.ack);
+        let log = &mut self.inputs[seat];
+        let old_end = log.confirmed_end();
+        if packet.first_tick > old_end {
+            // A gap: unreachable with cumulative packets; drop it rather than
+            // confirm out of order.
+            return;
+        }
+        let skip = (old_end - packet.first_tick) as usize;
+        for frame in packet.frames.iter().skip(skip) {
+            log.confirm(frame.clone());
+        }
+        // Every already-simulated tick from the first new confirmation on may
+        // now read differently: the confirmed frame itself, and the fresh
+        // prediction past it.
+        let log = &self.inputs[seat];
+        let mismatch = (old_end..self.frame).find(|&tick| {
+            log.used
+                .get((tick - log.base) as usize)
+                .is_some_and(|used| *used != log.input_for(tick))
+        });
+        if let Some(tick) = mismatch {
+            self.rollback_from = Some(self.rollback_from.map_or(tick, |t| t.min(tick)));
+        }
+    }
+
+    /// Step every seat through `tick` with its current best input, then route
+    /// attack — the session step's order, so a re-simulation routes exactly
+    /// what a first simulation would.
+    fn simulate(&mut self, tick: u32) -> Vec<Vec<EngineEvent>> {
+        let mut all = Vec::with_capacity(self.engines.len());
+        for (seat, engine) in self.engines.iter_mut().enumerate() {
+            let frame = self.inputs[seat].input_for(tick);
+            self.inputs[seat].record_used(tick, frame.clone());
+            all.push(engine.step(frame));
+        }
+        for (seat, events) in all.iter().enumerate() {
+            let mut lines = 0;
+            for event in events {
+                match event {
+                    EngineEvent::AttackSent { lines: sent } => lines += sent,
+                    EngineEvent::GameOver { .. } => self.tally.topped[seat] = true,
+                    _ => {}
+                }
+            }
+            if lines == 0 {
+                continue;
+            }
+            self.tally.attack[seat] += lines;
+            for (target, engine) in self.engines.iter_mut().enumerate() {
+                if target != seat {
+                    engine.queue_garbage(lines);
+                }
+            }
+        }
+        all
+    }
+
+    fn save(&mut self, tick: u32) {
+        let slot = tick as usize % self.saved.len();
+        self.saved[slot] = Some(SavedFrame {
+            tick,
+            engines: self.engines.clone(),
+            tally: self.tally.clone(),
+        });
+    }
+
+    fn saved_at(&self, tick: u32) -> Option<&SavedFrame> {
+        self.saved[tick as usize % self.saved.len()]
+            .as_ref()
+            .filter(|saved| saved.tick == tick)
+    }
+
+    /// Forget input history nothing can reach any more: below the saved
+    /// window, and (for our own frames) below every peer's acknowledgement.
+    fn prune(&mut self) {
+        let window = self
+            .frame
+            .saturating_sub(self.saved.len() as u32)
+            .min(self.rollback_from.unwrap_or(u32::MAX));
+        let unacked = self
+            .acked
+            .iter()
+            .enumerate()
+            .filter(|&(seat, _)| seat != self.local as usize)
+            .map(|(_, &ack)| ack)
+            .min()
+            .unwrap_or(u32::MAX);
+        for (seat, log) in self.inputs.iter_mut().enumerate() {
+            let lower = if seat == self.local as usize {
+                window.min(unacked)
+            } else {
+                window
+            };
+            log.prune(lower);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 1.0 / 60.0;
+
+    fn idle() -> InputFrame {
+        InputFrame {
+            dt_seconds: DT,
+            ..InputFrame::default()
+        }
+    }
+
+    fn pair(config: RollbackConfig) -> (RollbackSession, RollbackSession) {
+        let make = |local| RollbackSession::new(EngineConfig::default(), 3, 2, local, config);
+        (make(0), make(1))
+    }
+
+    /// A scripted seat: drift left on some ticks, hard drop on others — enough
+    /// variety that repeating the last frame mispredicts often.
+    fn script(seat: u8, tick: u32) -> InputFrame {
+        let phase = (tick + 7 * u32::from(seat)) % 23;
+        InputFrame {
+            left: phase % 5 == 1,
+            right: seat == 1 && phase % 7 == 2,
+            hard_drop: phase == 22,
+            ..idle()
+        }
+    }
+
+    /// Packets that arrive `lag` ticks late, both ways, end in the same final
+    /// state as an instant exchange — after corrections the speculation never
+    /// leaves a trace. (With one frame of input delay an instant exchange is
+    /// always in time, so it never predicts at all.)
+    #[test]
+    fn late_inputs_roll_back_to_the_instant_result() {
+        let config = RollbackConfig::default();
+        let run = |lag: usize| {
+            let (mut a, mut b) = pair(config);
+            let mut to_b = VecDeque::new();
+            let mut to_a = VecDeque::new();
+            for tick in 0..400 {
+                a.advance(script(0, tick)).expect("within the window");
+                b.advance(script(1, tick)).expect("within the window");
+                to_b.push_back(a.outgoing(1));
+                to_a.push_back(b.outgoing(0));
+                if to_b.len() > lag {
+                    b.receive(&to_b.pop_front().unwrap());
+                    a.receive(&to_a.pop_front().unwrap());
+                }
+            }
+            for packet in to_b {
+                b.receive(&packet);
+            }
+            for packet in to_a {
+                a.receive(&packet);
+            }
+            a.receive(&b.outgoing(0));
+            b.receive(&a.outgoing(1));
+            a.settle();
+            b.settle();
+            assert_eq!(a.confirmed_frame(), 400);
+            assert_eq!(a.confirmed_hash(), b.confirmed_hash());
+            (a.confirmed_hash(), a.stats())
+        };
+        let (instant, calm) = run(0);
+        let (lagged, stats) = run(4);
+        assert_eq!(calm.rollbacks, 0, "instant delivery never mispredicts");
+        assert!(stats.rollbacks > 0, "4 ticks of lag must mispredict");
+        assert!(stats.deepest <= 5);
+        assert_eq!(lagged, instant);
+    }
+
+    #[test]
+    fn a_silent_peer_stalls_the_session_at_the_window() {
+        let (mut a, _) = pair(RollbackConfig {
+            max_rollback: 4,
+            input_delay: 2,
+        });
+        // Two delay ticks are confirmed by rule, plus four of speculation.
+        for tick in 0..6 {
+            assert!(a.advance(idle()).is_ok(), "tick {tick}");
+        }
+        assert!(!a.can_advance());
+        assert_eq!(a.advance(idle()), Err(Stalled { seat: 1 }));
+        assert_eq!(a.frame(), 6, "a refused advance consumes nothing");
+    }
+
+    /// Duplicate, stale, and reordered packets change nothing: the log only
+    /// ever extends from its confirmed end.
+    #[test]
+    fn stale_and_duplicate_packets_are_harmless() {
+        let config = RollbackConfig::default();
+        let (mut a, mut b) = pair(config);
+        let mut early = None;
+        for tick in 0..5 {
+            b.advance(script(1, tick)).unwrap();
+            if tick == 1 {
+                early = Some(b.outgoing(0));
+            }
+        }
+        let latest = b.outgoing(0);
+        a.receive(&latest);
+        let confirmed = a.inputs[1].confirmed_end();
+        a.receive(&latest);
+        a.receive(&early.unwrap());
+        assert_eq!(a.inputs[1].confirmed_end(), confirmed);
+        assert_eq!(confirmed, 5 + config.input_delay);
+    }
+}
@@ -0,0 +1,264 @@
+//! Loopback integration: a real relay on 127.0.0.1 and two headless clients,
+//! each running its own copy of the two-board match.
+
+use std::net::SocketAddr;
+use std::thread;
+use std::time::Duration;
+
+use tetr_core::ai::{AiController, Handicap};
+use tetr_core::engine::EngineConfig;
+use tetr_core::engine::InputFrame;
+use tetr_net::{
+    Command, Lobby, LockstepClock, LockstepMatch, MatchOutcome, NetConfig, NetError, NetSession,
+    Relay, Roster,
+};
+
+const SEED: u64 = 0x5EED_0031;
+
+fn relay() -> SocketAddr {
+    let relay = Relay::bind("127.0.0.1:0").unwrap().with_seed(SEED);
+    let addr = relay.local_addr().unwrap();
+    relay.spawn();
+    addr
+}
+
+fn config() -> NetConfig {
+    NetConfig {
+        input_delay: 3,
+        hash_interval: 60,
+        timeout: Duration::from_secs(10),
+        start_timeout: Some(Duration::from_secs(10)),
+    }
+}
+
+/// Join `room` and seat a perfect greedy bot (seeded by seat, so the two
+/// sides play differently) as the local driver.
+fn join(addr: SocketAddr, room: &str) -> LockstepMatch {
+    let session = NetSession::connect(addr, room, config()).expect("join the room");
+    let bot = AiController::new(Handicap::perfect(), 100 + u64::from(session.seat()));
+    LockstepMatch::new(session, EngineConfig::default(), Box::new(bot))
+}
+
+/// Run both clients of `room` on their own threads; `tamper` may corrupt a
+/// client's boards once, at the given tick.
+fn play_pair(
+    room: &'static str,
+    max_ticks: u32,
+    tamper: Option<(u8, u32)>,
+) -> Vec<(u8, Result<MatchOutcome, NetError>)> {
+    let addr = relay();
+    let clients: Vec<_> = (0..2)
+        .map(|_| {
+            thread::spawn(move || {
+                let mut game = join(addr, room);
+                let seat = game.session().seat();
+                let result = (|| {
+                    while game.tick() < max_ticks {
+                        if tamper == Some((seat, game.tick())) {
+                            game.engine_mut(0).queue_garbage(2);
+                        }
+                        game.step()?;
+                    }
+                    game.run(max_ticks)
+                })();
+                (seat, result)
+            })
+        })
+        .collect();
+    let mut results: Vec<_> = clients.into_iter().map(|c| c.join().unwrap()).collect();
+    results.sort_by_key(|(seat, _)| *seat);
+    results
+}
+
+#[test]
+fn both_clients_play_the_identical_match() {
+    let results = play_pair("same-match", 900, None);
+    assert_eq!(results[0].0, 0);
+    assert_eq!(results[1].0, 1);
+    let a = results[0].1.clone().expect("seat 0 finished cleanly");
+    let b = results[1].1.clone().expect("seat 1 finished cleanly");
+    assert_eq!(a, b, "the two clients' copies of the match diverged");
+    assert_eq!(a.ticks, 900);
+}
+
+/// Both clients play the relay's seed, and the relayed match is real play:
+/// pieces lock on both boards.
+#[test]
+fn the_relayed_match_is_played_on_the_dealt_seed() {
+    let addr = relay();
+    let other = thread::spawn(move || {
+        let mut game = join(addr, "dealt-seed");
+        game.run(600)
+            .map(|_| game.engines()[1].snapshot().board_cells.len())
+    });
+    let mut game = join(addr, "dealt-seed");
+    assert_eq!(game.session().seed(), SEED);
+    game.run(600).unwrap();
+    let here: Vec<usize> = game
+        .engines()
+        .iter()
+        .map(|e| e.snapshot().board_cells.len())
+        .collect();
+    assert!(
+        here.iter().all(|&cells| cells > 0),
+        "both seats placed pieces"
+    );
+    assert_eq!(other.join().unwrap().unwrap(), here[1]);
+}
+
+/// One client's boards are corrupted mid-match: the next hash report after the
+/// corruption is flagged on both clients, at the same tick, and the hash
+/// before it passed.
+#[test]
+fn a_corrupted_client_is_flagged_as_desynced() {
+    let results = play_pair("desync", 900, Some((1, 100)));
+    for (seat, result) in results {
+        assert_eq!(
+            result,
+            Err(NetError::Desync { tick: 120 }),
+            "seat {seat} must see the desync at the first hash after tick 100"
+        );
+    }
+}
+
+#[test]
+fn a_full_room_refuses_a_third_client() {
+    let addr = relay();
+    let first = thread::spawn(move || NetSession::connect(addr, "full", config()).unwrap());
+    let second = NetSession::connect(addr, "full", config()).unwrap();
+    let first = first.join().unwrap();
+    assert_ne!(first.seat(), second.seat());
+    match NetSession::connect(addr, "full", config()) {
+        Err(NetError::Refused(reason)) => assert!(reason.contains("full"), "{reason}"),
+        Err(other) => panic!("expected a refusal, got {other}"),
+        Ok(_) => panic!("expected a refusal, got a seat"),
+    }
+}
+
+/// A peer that vanishes mid-match ends it for the one left behind, instead of
+/// leaving it blocked on frames that will never come.
+#[test]
+fn a_departed_peer_ends_the_match() {
+    let addr = relay();
+    let leaver = thread::spawn(move || {
+        let mut game = join(addr, "leaver");
+        for _ in 0..30 {
+            game.step().unwrap();
+        }
+        // Dropping the match closes the connection.
+    });
+    let mut game = join(addr, "leaver");
+    let err = game.run(10_000).unwrap_err();
+    leaver.join().unwrap();
+    assert!(
+        matches!(err, NetError::PeerLeft { .. } | NetError::Io(_)),
+        "unexpected {err}"
+    );
+    assert!(game.tick() >= 30, "frames already sent are still played");
+}
+
+/// Poll `lobby` until its roster satisfies `done` (the relay's updates arrive
+/// on the reader thread).
+fn await_roster(lobby: &Lobby, done: impl Fn(&Roster) -> bool) -> Roster {
+    for _ in 0..500 {
+        let roster = lobby.roster();
+        if done(&roster) {
+            return roster;
+        }
+        thread::sleep(Duration::from_millis(2));
+    }
+    panic!("roster never settled: {:?}", lobby.roster());
+}
+
+/// The lobby gathers, shows who is ready, and starts only once everyone is —
+/// on a seed both clients agree on.
+#[test]
+fn the_lobby_starts_only_when_every_seat_is_ready() {
+    let addr = {
+        let relay = Relay::bind("127.0.0.1:0").unwrap();
+        let addr = relay.local_addr().unwrap();
+        relay.spawn();
+        addr
+    };
+    let host = Lobby::join(addr, "lobby", config()).unwrap();
+    let guest = Lobby::join(addr, "lobby", config()).unwrap();
+    assert_eq!((host.seat(), guest.seat()), (0, 1));
+    await_roster(&host, |r| r.present == [true, true]);
+
+    host.set_ready(true);
+    let roster = await_roster(&guest, |r| r.ready.first() == Some(&true));
+    assert_eq!(roster.ready, [true, false]);
+    assert!(host.started().unwrap().is_none(), "one seat is not ready");
+
+    guest.set_ready(true);
+    let a = host.wait_started(Some(Duration::from_secs(5))).unwrap();
+    let b = guest.wait_started(Some(Duration::from_secs(5))).unwrap();
+    assert_eq!((a.seat(), b.seat()), (0, 1));
+    assert_eq!(a.seed(), b.seed(), "both clients play the dealt seed");
+}
+
+#[test]
+fn a_seat_left_in_the_lobby_is_free_again() {
+    let addr = relay();
+    let host = Lobby::join(addr, "revolving", config()).unwrap();
+    let quitter = Lobby::join(addr, "revolving", config()).unwrap();
+    await_roster(&host, |r| r.present == [true, true]);
+    quitter.set_ready(true);
+    await_roster(&host, |r| r.ready == [false, true]);
+    drop(quitter);
+    await_roster(&host, |r| r.present == [true, false]);
+    let next = Lobby::join(addr, "revolving", config()).unwrap();
+    assert_eq!(next.seat(), 1, "the freed seat is reused");
+    let roster = await_roster(&next, |r| r.present == [true, true]);
+    assert_eq!(
+        roster.ready,
+        [false, false],
+        "the quitter's ready left with it"
+    );
+}
+
+/// A command rides with its frame, so every client sees it released on the
+/// same tick — `input_delay` after the sender issued it — whatever the
+/// network did to the timing.
+#[test]
+fn commands_are_released_on_the_same_tick_everywhere() {
+    let addr = relay();
+    let clients: Vec<_> = (0..2)
+        .map(|_| {
+            thread::spawn(move || {
+                let session = NetSession::connect(addr, "commands", config()).unwrap();
+                let seat = session.seat();
+                let mut clock = LockstepClock::new(session);
+                let mut released = Vec::new();
+                while clock.tick() < 120 {
+                    if clock.needs_input() {
+                        let command = match (seat, clock.tick()) {
+                            (0, 40) => Some(Command::Pause),
+                            (1, 70) => Some(Command::Resume),
+                            _ => None,
+                        };
+                        clock.submit(InputFrame::default(), command);
+                    }
+                    match clock.advance().unwrap() {
+                        Some(tick) if !tick.commands.is_empty() => {
+                            released.push((tick.tick, tick.commands));
+                        }
+                        Some(_) => {}
+                        None => thread::sleep(Duration::from_micros(200)),
+                    }
+                }
+                released
+            })
+        })
+        .collect();
+    let released: Vec<_> = clients.into_iter().map(|c| c.join().unwrap()).collect();
+    let delay = config().input_delay;
+    assert_eq!(
+        released[0],
+        vec![
+            (40 + delay, vec![(0, Command::Pause)]),
+            (70 + delay, vec![(1, Command::Resume)]),
+        ]
+    );
+    assert_eq!(released[0], released[1]);
+}
@@ -0,0 +1,174 @@
+//! Rollback over simulated links: two headless clients, each seating a bot on
+//! its own [`RollbackSession`], exchange input packets through seeded
+//! [`SimLink`]s. However bad the link, once every frame is confirmed both
+//! clients hold the same match — and it is the match an offline lockstep
+//! replay of the frames they actually played produces.
+
+use tetr_core::ai::{AiController, Handicap};
+use tetr_core::engine::{Engine, EngineConfig, EngineEvent, InputFrame};
+use tetr_core::player::PlayerController;
+use tetr_net::match_hash;
+use tetr_net::netsim::{LinkConditions, SimLink};
+use tetr_net::{InputPacket, RollbackConfig, RollbackSession, RollbackStats};
+
+const SEED: u64 = 0x5EED_0032;
+const TICKS: u32 = 1_500;
+
+struct Client {
+    session: RollbackSession,
+    bot: AiController,
+    /// The local frames actually played, in order.
+    played: Vec<InputFrame>,
+}
+
+struct Finished {
+    hashes: [u64; 2],
+    reference: u64,
+    stats: [RollbackStats; 2],
+    attack: Vec<u32>,
+}
+
+fn play(conditions: LinkConditions, config: RollbackConfig) -> Finished {
+    let mut clients: Vec<Client> = (0..2u8)
+        .map(|seat| Client {
+            session: RollbackSession::new(EngineConfig::default(), SEED, 2, seat, config),
+            bot: AiController::new(Handicap::perfect(), 200 + u64::from(seat)),
+            played: Vec::new(),
+        })
+        .collect();
+    // links[i] carries client i's packets to the other client.
+    let mut links: Vec<SimLink<InputPacket>> =
+        (0..2).map(|i| SimLink::new(conditions, 17 + i)).collect();
+
+    let mut now = 0;
+    while clients
+        .iter()
+        .any(|c| c.session.confirmed_frame() < TICKS || c.session.frame() < TICKS)
+    {
+        for (i, client) in clients.iter_mut().enumerate() {
+            if client.session.frame() < TICKS && client.session.can_advance() {
+                let local = client.session.local_seat() as usize;
+                let frame = client.bot.poll(&client.session.engines()[local].snapshot());
+                client.played.push(frame.clone());
+                client.session.advance(frame).expect("can_advance said so");
+            }
+            links[i].send(now, client.session.outgoing(1 - i as u8));
+        }
+        for (i, client) in clients.iter_mut().enumerate() {
+            for packet in links[1 - i].deliver(now) {
+                client.session.receive(&packet);
+            }
+        }
+        now += 1;
+        assert!(now < TICKS * 4, "the match never converged");
+    }
+
+    let hashes = [0, 1].map(|i| {
+        let session = &mut clients[i].session;
+        session.settle();
+        let (tick, hash) = session.confirmed_hash().expect("settled");
+        assert_eq!(tick, TICKS);
+        hash
+    });
+    let played: Vec<&[InputFrame]> = clients.iter().map(|c| &c.played[..]).collect();
+    Finished {
+        hashes,
+        reference: lockstep_reference(&played, config.input_delay),
+        stats: [0, 1].map(|i| clients[i].session.stats()),
+        attack: clients[0].session.attack().to_vec(),
+    }
+}
+
+/// The same frames, stepped the lockstep way: every seat's frame `n` lands
+/// on tick `n + input_delay`, every seat steps, then attack is routed.
+fn lockstep_reference(played: &[&[InputFrame]], input_delay: u32) -> u64 {
+    let mut engines: Vec<Engine> = (0..played.len())
+        .map(|_| Engine::new(EngineConfig::default(), SEED))
+        .collect();
+    for tick in 0..TICKS {
+        let events: Vec<Vec<EngineEvent>> = engines
+            .iter_mut()
+            .zip(played)
+            .map(|(engine, frames)| {
+                let frame = tick
+                    .checked_sub(input_delay)
+                    .map_or_else(InputFrame::default, |n| frames[n as usize].clone());
+                engine.step(frame)
+            })
+            .collect();
+        for (seat, events) in events.iter().enumerate() {
+            let lines: u32 = events
+                .iter()
+                .map(|e| match e {
+                    EngineEvent::AttackSent { lines } => *lines,
+                    _ => 0,
+                })
+                .sum();
+            if lines > 0 {
+                for (target, engine) in engines.iter_mut().enumerate() {
+                    if target != seat {
+                        engine.queue_garbage(lines);
+                    }
+                }
+            }
+        }
+    }
+    match_hash(&engines)
+}
+
+#[test]
+fn a_clean_link_with_enough_delay_never_rolls_back() {
+    let done = play(
+        LinkConditions::default(),
+        RollbackConfig {
+            max_rollback: 8,
+            input_delay: 1,
+        },
+    );
+    assert_eq!(done.hashes[0], done.hashes[1]);
+    assert_eq!(done.hashes[0], done.reference);
+    assert_eq!(done.stats, [RollbackStats::default(); 2]);
+}
+
+/// 100 ms each way: both clients speculate constantly, and the corrections
+/// (garbage re-routed included — both bots send attack) leave no trace.
+#[test]
+fn a_laggy_link_rolls_back_to_the_lockstep_result() {
+    let done = play(
+        LinkConditions {
+            latency: 6,
+            ..LinkConditions::default()
+        },
+        RollbackConfig {
+            max_rollback: 10,
+            input_delay: 1,
+        },
+    );
+    assert!(done.attack.iter().all(|&a| a > 0), "{:?}", done.attack);
+    for stats in done.stats {
+        assert!(stats.rollbacks > 0, "{stats:?}");
+        assert!(stats.deepest <= 10, "{stats:?}");
+    }
+    assert_eq!(done.hashes[0], done.hashes[1]);
+    assert_eq!(done.hashes[0], done.reference);
+}
+
+/// Jitter reorders packets and a fifth of them are lost; the redundant
+/// resends and a tight window (stalls included) still converge.
+#[test]
+fn jitter_and_loss_still_converge() {
+    let done = play(
+        LinkConditions {
+            latency: 3,
+            jitter: 6,
+            loss: 0.2,
+        },
+        RollbackConfig {
+            max_rollback: 6,
+            input_delay: 2,
+        },
+    );
+    assert!(done.stats.iter().any(|s| s.rollbacks > 0));
+    assert_eq!(done.hashes[0], done.hashes[1]);
+    assert_eq!(done.hashes[0], done.reference);
+}
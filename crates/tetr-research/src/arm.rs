@@ -22,6 +22,7 @@ use std::path::PathBuf;
 use std::str::FromStr;
 
 use tetr_core::ai::BeamPlanner;
+use tetr_core::ai::Handicap;
 use tetr_core::ai::SearchBudget;
 use tetr_core::ai::eval::Cc2Weights;
 use tetr_core::player::PlayerController;
@@ -54,6 +55,7 @@ impl Arm {
                 Box::new(BeamPlanner::new(*width)),
                 Box::new(NetEvaluator::load(dir).expect("arm model dir loads")),
                 SearchBudget::beam(*depth),
+                Handicap::perfect(),
                 seed,
             ),
         }
@@ -15,6 +15,11 @@
 //! let bot = aware.controller(7);
 //! ```
 //!
+//! The one sanctioned departure is [`BotSpec::handicapped`], which builds the
+//! same brain under an in-game [`Handicap`] — for evals that measure the
+//! handicap itself (the `handicap-ladder` calibration), never for policy
+//! comparisons.
+//!
 //! There is exactly one way to build a research bot: compose a spec. (The
 //! pre-spec factory functions are gone — this crate carries no compatibility
 //! surface; recorded run records cite settings, which a spec expresses
@@ -28,13 +33,12 @@
 //! benchmarkable everywhere with no per-command plumbing. Like experiment
 //! names, bot names with recorded runs are immutable: new weights, new name.
 
-use std::time::Duration;
-
 use tetr_core::ai::eval::{Cc2Evaluator, Cc2Weights, Evaluator, LinearEvaluator, Weights};
 use tetr_core::ai::{
-    AiController, BeamPlanner, BestFirstPlanner, Handicap, PcCoverageConfig, PcCoveragePlanner,
-    PcCoverageUnit, Policy, SearchBudget, SearchPolicy,
+    AiController, BeamPlanner, BestFirstPlanner, Handicap, Mind, OpenerBookPlanner,
+    PcCoverageConfig, PcCoveragePlanner, PcCoverageUnit, Policy, SearchBudget, SearchPolicy,
 };
+use tetr_core::opener::OpenerBook;
 use tetr_core::player::PlayerController;
 
 use crate::versus::BlindToGarbage;
@@ -98,6 +102,9 @@ pub struct BotSpec {
     /// speculative tail (which dominates deep search past the ~6-ply preview) buys
     /// strength or just adds discounted noise. No effect on non-beam searches.
     pub speculate: bool,
+    /// Play the standard opener book before the search takes over
+    /// ([`OpenerBookPlanner`]) — the subgoal-layer arm of the opener A/B.
+    pub book: bool,
 }
 
 impl BotSpec {
@@ -108,6 +115,7 @@ impl BotSpec {
             eval: EvalSpec::Linear(Weights::default()),
             blind: false,
             speculate: true,
+            book: false,
         }
     }
 
@@ -118,6 +126,7 @@ impl BotSpec {
             eval: EvalSpec::Linear(Weights::default()),
             blind: false,
             speculate: true,
+            book: false,
         }
     }
 
@@ -128,6 +137,7 @@ impl BotSpec {
             eval: EvalSpec::Linear(Weights::default()),
             blind: false,
             speculate: true,
+            book: false,
         }
     }
 
@@ -138,6 +148,7 @@ impl BotSpec {
             eval: EvalSpec::Linear(Weights::default()),
             blind: false,
             speculate: true,
+            book: false,
         }
     }
 
@@ -148,6 +159,7 @@ impl BotSpec {
             eval: EvalSpec::Linear(Weights::default()),
             blind: false,
             speculate: true,
+            book: false,
         }
     }
 
@@ -177,8 +189,31 @@ impl BotSpec {
         self
     }
 
+    /// Serve the opener book's placements while the board is on one of its
+    /// setups (no effect once the search has the game).
+    pub fn opener_book(mut self) -> Self {
+        self.book = true;
+        self
+    }
+
+    /// `planner`, behind the opener book when the spec asks for it.
+    fn mind(&self, planner: Box<dyn Mind>) -> Box<dyn Mind> {
+        if self.book {
+            Box::new(OpenerBookPlanner::new(OpenerBook::standard(), planner))
+        } else {
+            planner
+        }
+    }
+
     /// Build a fresh controller for this spec (the policy RNG seeded by `seed`).
     pub fn controller(&self, seed: u64) -> Box<dyn PlayerController> {
+        self.handicapped(seed, Handicap::perfect())
+    }
+
+    /// Build this spec's brain under an in-game `handicap` (imperfection in the
+    /// policy, reaction / pace cap / misdrop / blindness in the shell). With
+    /// [`Handicap::perfect`] this is exactly [`controller`](Self::controller).
+    pub fn handicapped(&self, seed: u64, handicap: Handicap) -> Box<dyn PlayerController> {
         let inner: Box<dyn PlayerController> = match self.search {
             SearchSpec::Greedy => {
                 // Greedy is the shipped baseline construction and cannot take a
@@ -189,30 +224,42 @@ impl BotSpec {
                     self.eval == EvalSpec::Linear(Weights::default()),
                     "SearchSpec::Greedy ignores custom evaluators — compose beam()/best_first() instead"
                 );
-                Box::new(AiController::new(Handicap::perfect(), seed))
+                assert!(
+                    !self.book,
+                    "SearchSpec::Greedy cannot take the opener book — compose beam()/best_first() instead"
+                );
+                Box::new(AiController::new(handicap, seed))
             }
             SearchSpec::Beam { width, depth } => full_strength(
-                Box::new(BeamPlanner::new(width).with_speculation(self.speculate)),
+                self.mind(Box::new(
+                    BeamPlanner::new(width).with_speculation(self.speculate),
+                )),
                 self.eval.build(),
                 SearchBudget::beam(depth),
+                handicap,
                 seed,
             ),
             SearchSpec::TpBeam { width, depth } => full_strength(
-                Box::new(BeamPlanner::transposing(width).with_speculation(self.speculate)),
+                self.mind(Box::new(
+                    BeamPlanner::transposing(width).with_speculation(self.speculate),
+                )),
                 self.eval.build(),
                 SearchBudget::beam(depth),
+                handicap,
                 seed,
             ),
             SearchSpec::BestFirst { budget, depth } => full_strength(
-                Box::new(BestFirstPlanner::new()),
+                self.mind(Box::new(BestFirstPlanner::new())),
                 self.eval.build(),
                 SearchBudget::best_first(budget, depth),
+                handicap,
                 seed,
             ),
             SearchSpec::PcCoverage { config, depth } => full_strength(
-                Box::new(PcCoveragePlanner::new(config)),
+                self.mind(Box::new(PcCoveragePlanner::new(config))),
                 self.eval.build(),
                 SearchBudget::beam(depth),
+                handicap,
                 seed,
             ),
         };
@@ -229,20 +276,22 @@ impl BotSpec {
     }
 }
 
-/// The one place the full-strength convention lives: imperfection 0 and no
-/// reaction delay (suites measure pure policy quality), blocking venue.
-/// Shared with [`crate::arm`], whose net arms follow the same convention.
+/// The one place the full-strength convention lives: with
+/// [`Handicap::perfect`], imperfection 0 and no reaction delay (suites measure
+/// pure policy quality), blocking venue. Shared with [`crate::arm`], whose net
+/// arms follow the same convention.
 pub(crate) fn full_strength(
     planner: Box<dyn tetr_core::ai::Mind>,
     eval: Box<dyn Evaluator>,
     budget: SearchBudget,
+    handicap: Handicap,
     seed: u64,
 ) -> Box<dyn PlayerController> {
-    let policy = SearchPolicy::new(planner, eval, budget, 0.0, seed);
-    Box::new(AiController::with_policy(
-        Box::new(policy) as Box<dyn Policy>,
-        Duration::ZERO,
-    ))
+    let policy = SearchPolicy::new(planner, eval, budget, handicap.imperfection, seed);
+    Box::new(
+        AiController::with_policy(Box::new(policy) as Box<dyn Policy>, handicap.reaction)
+            .with_handicap(handicap, seed),
+    )
 }
 
 /// Reward = exactly `λ ×` attack sent (the engine's guideline table, chain-exact
@@ -472,6 +521,16 @@ pub fn bots() -> Vec<(&'static str, BotSpec)> {
             "probe-tp16d12",
             BotSpec::tp_beam(16, 12).cc2(Cc2Weights::attack_tuned()),
         ),
+        // The opener book as a subgoal layer (research-directions §4.6): probe-tp16d9 that
+        // plays TKI / DT / MKO / PCO while the bag allows, then searches. A/B against its
+        // bookless twin: `run opener-app-v1 book-tp16d9` vs `run opener-app-v1 probe-tp16d9`
+        // for opening APP, `run race book-tp16d9 probe-tp16d9` for survival.
+        (
+            "book-tp16d9",
+            BotSpec::tp_beam(16, 9)
+                .cc2(Cc2Weights::attack_tuned())
+                .opener_book(),
+        ),
         (
             "probe-tp16d15",
             BotSpec::tp_beam(16, 15).cc2(Cc2Weights::attack_tuned()),
@@ -650,6 +709,26 @@ pub fn bots() -> Vec<(&'static str, BotSpec)> {
             BotSpec::beam(4, 1).cc2(Cc2Weights::attack_tuned()),
         ),
         ("dt20-tiny", BotSpec::beam(4, 1)),
+        // --- personality-* : the in-game playstyle presets (Cc2Weights'
+        // personality constructors) on the catalog's depth-3 beam; the
+        // `personality-style` eval checks each shows its style against
+        // attack-tuned-d3.
+        (
+            "personality-tspin",
+            BotSpec::beam(16, 3).cc2(Cc2Weights::t_spin_specialist()),
+        ),
+        (
+            "personality-4wide",
+            BotSpec::beam(16, 3).cc2(Cc2Weights::four_wide()),
+        ),
+        (
+            "personality-downstack",
+            BotSpec::beam(16, 3).cc2(Cc2Weights::downstacker()),
+        ),
+        (
+            "personality-tetris",
+            BotSpec::beam(16, 3).cc2(Cc2Weights::tetris_only()),
+        ),
     ]
 }
 
@@ -712,4 +791,22 @@ mod tests {
             (o2.score, o2.pieces, o2.lines)
         );
     }
+
+    /// `.opener_book()` changes the opening (the book, not the search, places
+    /// the first pieces) and stays deterministic.
+    #[test]
+    fn opener_book_spec_plays_its_own_deterministic_opening() {
+        let spec = BotSpec::beam(8, 2).cc2(Cc2Weights::attack_tuned());
+        let play = |spec: BotSpec| {
+            let o = crate::marathon::play_marathon_capped(&spec.factory(), 3, 50_000, 14);
+            (o.score, o.pieces, o.lines, o.total_attack)
+        };
+        let book = play(spec.opener_book());
+        assert_eq!(book, play(spec.opener_book()));
+        assert_ne!(
+            book,
+            play(spec),
+            "the book opens differently from the search"
+        );
+    }
 }
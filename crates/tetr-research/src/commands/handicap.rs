@@ -0,0 +1,188 @@
+//! Handicap-ladder eval: the calibration behind the in-game skill presets.
+//! For each [`SkillPreset`] (Novice → Master) the subject's brain plays capped
+//! marathons under that preset's [`Handicap`], and the measured pieces per
+//! second and attack per minute are reported against the preset's
+//! [`SkillAnchor`] — the target a player picking "Expert" expects to face.
+//! The clock is the sim's: `frames / SIM_HZ`, so the pace cap and reaction
+//! delay are what set PPS, not the host's search speed.
+//!
+//! A preset is **calibrated** when its measured PPS sits within
+//! [`Spec::tolerance`] of its anchor and the ladder is monotone (each rung at
+//! least as fast and as hard-hitting as the one below). APM is reported, not
+//! gated: it depends on the brain, and the anchors assume the shipped one.
+//!
+//! # RUN RECORD (2026-10-18, `run handicap-ladder attack-tuned`, 4 TRAIN seeds, cap 100)
+//!
+//! | preset  | PPS (anchor)  | APM (anchor) |
+//! |---------|---------------|--------------|
+//! | Novice  | 0.50 (0.5)    |  9.0 (8)     |
+//! | Casual  | 0.90 (0.9)    | 22.6 (18)    |
+//! | Skilled | 1.40 (1.4)    | 38.4 (32)    |
+//! | Expert  | 1.98 (2.0)    | 54.5 (50)    |
+//! | Master  | 2.87 (3.0)    | 73.3 (80)    |
+//!
+//! Monotone, calibrated. The first pass had Master at 80 ms reaction and read
+//! 2.51 PPS: above ~2.5 the reaction delay plus one-frame-per-input execution
+//! binds before the cap does, so Master now reacts in one frame (16 ms) and
+//! the cap alone sets its pace. The lower rungs are all cap-bound (their
+//! reaction fits inside the cap's budget), so PPS lands on the anchor almost
+//! exactly; APM runs a little hot on the attack-tuned brain — the shipped
+//! registry models are weaker, which is the direction the anchors assume.
+//! Novice topped out on 1 of 4 seeds (misdrops at 8%), as a novice would.
+
+use serde_json::json;
+use tetr_core::ai::SkillPreset;
+
+use crate::bots::Bot;
+use crate::commands::Runtime;
+use crate::events;
+use crate::marathon::{DEFAULT_MAX_FRAMES, SIM_HZ, evaluate_capped};
+use crate::seeds::seed_set_from;
+
+#[derive(Clone, Copy, Debug, serde::Serialize)]
+pub struct Spec {
+    pub seeds: usize,
+    /// Per-game piece cap; long enough that the opening's first-piece
+    /// latency is noise in the per-second rates.
+    pub max_pieces: u32,
+    pub seed_start: usize,
+    /// Relative PPS error (|measured − anchor| / anchor) a preset may carry
+    /// and still count as calibrated.
+    pub tolerance: f32,
+}
+
+impl Default for Spec {
+    fn default() -> Self {
+        Self {
+            seeds: 4,
+            max_pieces: 100,
+            seed_start: 0,
+            tolerance: 0.15,
+        }
+    }
+}
+
+/// One rung's measurement.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Rung {
+    pps: f32,
+    apm: f32,
+}
+
+pub fn run(spec: &Spec, bot: &Bot, _rt: &Runtime) -> std::io::Result<serde_json::Value> {
+    let seeds = seed_set_from(spec.seed_start, spec.seeds);
+    let mut rungs = Vec::new();
+    let mut report = Vec::new();
+    for preset in SkillPreset::ALL {
+        let handicap = preset.handicap();
+        let brain = bot.spec;
+        let stats = evaluate_capped(
+            &move |seed| brain.handicapped(seed, handicap),
+            &seeds,
+            DEFAULT_MAX_FRAMES,
+            spec.max_pieces,
+        );
+        let (mut pieces, mut attack, mut frames) = (0u64, 0u64, 0u64);
+        for o in &stats.outcomes {
+            pieces += u64::from(o.pieces);
+            attack += u64::from(o.total_attack);
+            frames += u64::from(o.frames);
+            events::game(json!({
+                "preset": preset.label(),
+                "seed": events::seed_hex(o.seed),
+                "pieces": o.pieces,
+                "frames": o.frames,
+                "attack": o.total_attack,
+                "topped": o.topped_out,
+            }));
+        }
+        let rung = measure(pieces, attack, frames);
+        let anchor = preset.anchor();
+        let error = (rung.pps - anchor.pps).abs() / anchor.pps;
+        eprintln!(
+            "{:<8} PPS {:.2} (anchor {:.1}, {:+.0}%) | APM {:.1} (anchor {:.0}) | topout {:.0}%",
+            preset.label(),
+            rung.pps,
+            anchor.pps,
+            (rung.pps / anchor.pps - 1.0) * 100.0,
+            rung.apm,
+            anchor.apm,
+            stats.topout_rate * 100.0,
+        );
+        report.push(json!({
+            "preset": preset.label(),
+            "pps": rung.pps,
+            "apm": rung.apm,
+            "anchor_pps": anchor.pps,
+            "anchor_apm": anchor.apm,
+            "pps_error": error,
+            "within_tolerance": error <= spec.tolerance,
+        }));
+        rungs.push(rung);
+    }
+    let monotone = is_monotone(&rungs);
+    let calibrated = monotone
+        && report
+            .iter()
+            .all(|r| r["within_tolerance"].as_bool() == Some(true));
+    eprintln!(
+        "{} | {} seeds cap={} | monotone={monotone} calibrated={calibrated}",
+        bot.name,
+        seeds.len(),
+        spec.max_pieces
+    );
+    Ok(json!({
+        "presets": report,
+        "monotone": monotone,
+        "calibrated": calibrated,
+    }))
+}
+
+/// Pooled rates over a rung's games (pooling weights long games properly; a
+/// per-game mean would let a quick top-out swing the rung).
+fn measure(pieces: u64, attack: u64, frames: u64) -> Rung {
+    let seconds = frames as f32 / SIM_HZ;
+    if seconds <= 0.0 {
+        return Rung { pps: 0.0, apm: 0.0 };
+    }
+    Rung {
+        pps: pieces as f32 / seconds,
+        apm: attack as f32 / (seconds / 60.0),
+    }
+}
+
+/// Each rung at least as fast and as hard-hitting as the one below it.
+fn is_monotone(rungs: &[Rung]) -> bool {
+    rungs
+        .windows(2)
+        .all(|w| w[1].pps >= w[0].pps && w[1].apm >= w[0].apm)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rates_use_the_sim_clock() {
+        // 120 pieces and 60 attack over one simulated minute.
+        let rung = measure(120, 60, 60 * SIM_HZ as u64);
+        assert!((rung.pps - 2.0).abs() < 1e-5);
+        assert!((rung.apm - 60.0).abs() < 1e-3);
+        assert_eq!(measure(5, 5, 0), Rung { pps: 0.0, apm: 0.0 });
+    }
+
+    #[test]
+    fn a_slower_rung_above_breaks_monotonicity() {
+        let low = Rung {
+            pps: 1.0,
+            apm: 10.0,
+        };
+        let high = Rung {
+            pps: 2.0,
+            apm: 30.0,
+        };
+        assert!(is_monotone(&[low, high]));
+        assert!(!is_monotone(&[high, low]));
+        assert!(!is_monotone(&[low, Rung { pps: 2.0, apm: 5.0 }]));
+    }
+}
@@ -21,9 +21,12 @@ use std::time::Duration;
 pub mod cc2_baseline;
 pub mod climb_app;
 pub mod downstack;
+pub mod handicap;
 pub mod marathon;
 pub mod pc;
+pub mod pc_odds;
 pub mod race;
+pub mod style;
 pub mod versus;
 
 /// Machine-local circumstances of one invocation — everything here may vary
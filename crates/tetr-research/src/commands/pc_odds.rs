@@ -0,0 +1,141 @@
+//! Perfect-clear odds for standard setups: no bot plays — every legal bag
+//! continuation of each setup's known queue is solved exhaustively by
+//! [`tetr_core::ai::pc_solver`], with and without hold, and the share of
+//! sequences that admit a perfect clear is the reading (the solution-finder
+//! `percent` number). Each setup also reports how many distinct layouts
+//! appeared and the share of the single most buildable one — how far a player
+//! gets learning one picture.
+//!
+//! | setup | board | known queue | draws from | lines |
+//! |---|---|---|---|---|
+//! | `pco` | the opener book's PCO | `T` (the first bag's leftover) | a fresh bag | 4 |
+//!
+//! # RUN RECORD (2026-10-19, `run pc-odds`, ~25 s release)
+//!
+//! `pco`: 66.9% with hold (562 of 840 sequences; 35 layouts, the most
+//! buildable one alone 11.4%), 18.1% without (38 of 210). A pure tiling bound
+//! (no gravity, no mid-build clears) gives 54.3% and 17.1%: the surplus is
+//! solutions whose pieces span a row cleared under them. An empty-board
+//! two-line setup was dropped after reading 0% — ten cells a row with five
+//! tetrominoes needs a repeated piece, which one bag never deals.
+
+use serde_json::json;
+
+use tetr_core::ai::BagState;
+use tetr_core::ai::pc_solver::{HoldPolicy, PcProblem, PcSurvey, survey};
+use tetr_core::engine::{BitBoard, Board, CellKind, PieceType};
+use tetr_core::opener::OpenerBook;
+
+use crate::commands::Runtime;
+use crate::events;
+
+#[derive(Clone, Copy, Debug, serde::Serialize)]
+pub struct Spec {
+    /// The setups to read, by name (see the module table).
+    pub setups: &'static [&'static str],
+}
+
+impl Default for Spec {
+    fn default() -> Self {
+        Self { setups: &SETUPS }
+    }
+}
+
+/// Every setup this command knows.
+pub const SETUPS: [&str; 1] = ["pco"];
+
+/// `name`'s position under `hold_policy`, with the bag its unknown draws come
+/// from.
+fn setup(name: &str, hold_policy: HoldPolicy) -> Option<(PcProblem, BagState)> {
+    let mut board = Board::with_top_margin(10, 20, 20);
+    let (queue, bag, lines) = match name {
+        "pco" => {
+            let book = OpenerBook::standard();
+            let pco = book.get("pco").expect("the standard book has PCO");
+            for target in pco.setups()[0].targets() {
+                for &(x, y) in &target.cells {
+                    board.set(x, y, CellKind::Some(target.piece_type));
+                }
+            }
+            // PCO spends six of the first bag; the seventh is the T.
+            (vec![PieceType::T], BagState::from_pieces([]), 4)
+        }
+        _ => return None,
+    };
+    let problem = PcProblem {
+        board: BitBoard::from_board(&board),
+        queue,
+        hold: None,
+        lines,
+        hold_policy,
+    };
+    Some((problem, bag))
+}
+
+/// One reading's JSON: the rate, its counts, and the layout spread.
+fn reading(survey: &PcSurvey) -> serde_json::Value {
+    json!({
+        "rate": survey.success_rate(),
+        "solved": survey.solved,
+        "sequences": survey.sequences,
+        "layouts": survey.layouts.len(),
+        "top_layout_rate": top_layout_rate(survey),
+    })
+}
+
+/// The share of sequences the most buildable layout covers.
+fn top_layout_rate(survey: &PcSurvey) -> f64 {
+    survey.layouts.first().map_or(0.0, |share| {
+        share.sequences as f64 / survey.sequences.max(1) as f64
+    })
+}
+
+pub fn run(spec: &Spec, _rt: &Runtime) -> std::io::Result<serde_json::Value> {
+    let mut result = serde_json::Map::new();
+    for &name in spec.setups {
+        let read = |hold_policy| {
+            let (problem, bag) = setup(name, hold_policy).expect("pc-odds names a known setup");
+            survey(&problem, bag)
+        };
+        let (held, bare) = (read(HoldPolicy::Allowed), read(HoldPolicy::Forbidden));
+        events::game(json!({
+            "setup": name,
+            "hold": reading(&held),
+            "no_hold": reading(&bare),
+        }));
+        eprintln!(
+            "{name:<10} hold {:5.1}% ({}/{}, {} layouts, best {:.1}%) | no hold {:5.1}% ({}/{})",
+            held.success_rate() * 100.0,
+            held.solved,
+            held.sequences,
+            held.layouts.len(),
+            top_layout_rate(&held) * 100.0,
+            bare.success_rate() * 100.0,
+            bare.solved,
+            bare.sequences,
+        );
+        result.insert(
+            name.to_string(),
+            json!({ "hold": held.success_rate(), "no_hold": bare.success_rate() }),
+        );
+    }
+    Ok(json!({ "pc_odds": result }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_setup_is_a_whole_number_of_pieces() {
+        for name in SETUPS {
+            let (problem, _) = setup(name, HoldPolicy::Allowed).expect("a known setup");
+            assert!(problem.pieces_needed().is_some(), "{name}");
+        }
+        assert_eq!(
+            setup("pco", HoldPolicy::Allowed).unwrap().0.pieces_needed(),
+            Some(4)
+        );
+        assert!(setup("tki", HoldPolicy::Allowed).is_none());
+    }
+}
@@ -0,0 +1,191 @@
+//! Personality-style eval: does each personality bot actually play its style?
+//! Every registered personality and the reference it was tuned away from
+//! (`attack-tuned-d3`: the same beam, the attack weights) play the same capped
+//! Marathon seeds through the [`crate::style`] suite; each personality is then
+//! checked on its **signature** — the one histogram ratio its weights exist
+//! to move — against the reference's value:
+//!
+//! | bot | signature | holds when |
+//! |---|---|---|
+//! | `personality-tspin` | T-spin share of clears | above the reference |
+//! | `personality-4wide` | mean longest combo | above the reference |
+//! | `personality-downstack` | mean stack height | below the reference |
+//! | `personality-tetris` | tetris share of lines | above the reference and ≥ 0.8 |
+//!
+//! The whole histogram is reported for every bot (a personality that shows
+//! its style by topping out is visible in `topouts`).
+//!
+//! # RUN RECORD (2026-10-18, `run personality-style`, 6 TRAIN seeds, cap 150)
+//!
+//! All four hold. Signatures (personality vs `attack-tuned-d3`): T-spin share
+//! 0.56 vs 0.45; mean longest combo 4.3 vs 2.2; mean stack height 2.30 vs
+//! 5.48; tetris line share 0.90 vs 0.47. No top-outs anywhere. The first
+//! pass had the T-spin bot at 0.475 (a tetris reward of 2.5 kept it
+//! tetris-happy) and the combo bot at 2.8 (cheap singles won over chains);
+//! the shipped weights are the second pass — tetris reward 0.5 with slots and
+//! spin rewards raised, and combo reward 10 with plain clears made slightly
+//! negative.
+//!
+//! Tuning context from the standing evals (same bots, TRAIN):
+//!
+//! | bot | marathon APP | downstack cleared-only mean pieces |
+//! |---|---|---|
+//! | attack-tuned-d3 | 0.572 | 18.7 |
+//! | personality-tspin | 0.547 | 23.2 |
+//! | personality-4wide | 0.321 | 18.3 |
+//! | personality-downstack | 0.067 | **11.8** |
+//! | personality-tetris | 0.449 | 26.8 |
+//!
+//! The personalities are flavours, not upgrades: only the T-spin bot keeps
+//! near-reference offense, and the downstacker trades nearly all of it for the
+//! fastest dig in the catalog.
+
+use serde_json::json;
+
+use crate::bots;
+use crate::commands::Runtime;
+use crate::events;
+use crate::seeds::seed_set_from;
+use crate::style::{StyleStats, evaluate_style};
+
+/// The bot every signature is read against.
+pub const REFERENCE: &str = "attack-tuned-d3";
+
+#[derive(Clone, Copy, Debug, serde::Serialize)]
+pub struct Spec {
+    pub seeds: usize,
+    pub max_pieces: u32,
+    pub seed_start: usize,
+}
+
+impl Default for Spec {
+    fn default() -> Self {
+        Self {
+            seeds: 6,
+            max_pieces: 150,
+            seed_start: 0,
+        }
+    }
+}
+
+/// A personality's style claim, as a check on its stats vs the reference's.
+#[derive(Clone, Copy, Debug)]
+enum Signature {
+    TSpins,
+    Combos,
+    LowStack,
+    TetrisOnly,
+}
+
+impl Signature {
+    fn metric(self) -> &'static str {
+        match self {
+            Signature::TSpins => "t_spin_share",
+            Signature::Combos => "mean_max_combo",
+            Signature::LowStack => "mean_height",
+            Signature::TetrisOnly => "tetris_line_share",
+        }
+    }
+
+    fn value(self, stats: &StyleStats) -> f32 {
+        match self {
+            Signature::TSpins => stats.t_spin_share,
+            Signature::Combos => stats.mean_max_combo,
+            Signature::LowStack => stats.mean_height,
+            Signature::TetrisOnly => stats.tetris_line_share,
+        }
+    }
+
+    fn holds(self, subject: &StyleStats, reference: &StyleStats) -> bool {
+        let (s, r) = (self.value(subject), self.value(reference));
+        match self {
+            Signature::LowStack => s < r,
+            Signature::TetrisOnly => s > r && s >= 0.8,
+            _ => s > r,
+        }
+    }
+}
+
+/// The personalities under test, by registered bot name.
+const PERSONALITIES: [(&str, Signature); 4] = [
+    ("personality-tspin", Signature::TSpins),
+    ("personality-4wide", Signature::Combos),
+    ("personality-downstack", Signature::LowStack),
+    ("personality-tetris", Signature::TetrisOnly),
+];
+
+pub fn run(spec: &Spec, _rt: &Runtime) -> std::io::Result<serde_json::Value> {
+    let seeds = seed_set_from(spec.seed_start, spec.seeds);
+    let measure = |name: &str| {
+        let bot = bots::find(name).expect("personality-style names registered bots");
+        let (stats, outcomes) = evaluate_style(&bot.spec.factory(), &seeds, spec.max_pieces);
+        for o in &outcomes {
+            events::game(json!({
+                "bot": name,
+                "seed": events::seed_hex(o.seed),
+                "pieces": o.pieces,
+                "normal": o.normal,
+                "t_spins": o.t_spins,
+                "t_spin_minis": o.t_spin_minis,
+                "perfect_clears": o.perfect_clears,
+                "max_combo": o.max_combo,
+                "deep_combo_clears": o.deep_combo_clears,
+                "topped": o.topped_out,
+            }));
+        }
+        eprintln!(
+            "{name:<22} clears S/D/T/Q {:?} tspin {:?}+{}m | tspin {:.2} tetris-lines {:.2} \
+             max-combo {:.1} height {:.2} | topouts {}",
+            &stats.normal[1..],
+            &stats.t_spins[1..],
+            stats.t_spin_minis,
+            stats.t_spin_share,
+            stats.tetris_line_share,
+            stats.mean_max_combo,
+            stats.mean_height,
+            stats.topouts,
+        );
+        stats
+    };
+    let reference = measure(REFERENCE);
+    let mut report = Vec::new();
+    let mut all_hold = true;
+    for (name, signature) in PERSONALITIES {
+        let stats = measure(name);
+        let holds = signature.holds(&stats, &reference);
+        all_hold &= holds;
+        eprintln!(
+            "  {name}: {} {:.3} vs reference {:.3} -> {}",
+            signature.metric(),
+            signature.value(&stats),
+            signature.value(&reference),
+            if holds { "shows its style" } else { "DOES NOT" }
+        );
+        report.push(json!({
+            "bot": name,
+            "signature": signature.metric(),
+            "value": signature.value(&stats),
+            "reference": signature.value(&reference),
+            "holds": holds,
+            "stats": stats,
+        }));
+    }
+    Ok(json!({
+        "reference": reference,
+        "personalities": report,
+        "all_hold": all_hold,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_personality_is_a_registered_bot() {
+        assert!(bots::find(REFERENCE).is_some());
+        for (name, _) in PERSONALITIES {
+            assert!(bots::find(name).is_some(), "{name} is not registered");
+        }
+    }
+}
@@ -58,6 +58,7 @@
 //! | [`versus`] | head-to-head under the **engine's** garbage rules |
 //! | [`versus_legacy`] | the pre-engine harness scheduler, quarantined for the TBP referee + scripted scenarios |
 //! | [`sprt`] | pair-level GSPRT over death-decisive seed pairs |
+//! | [`style`] | clear-type histogram suite (what a bot clears, not how much) |
 //! | [`bots`] | bot construction + the named bot registry (the other surface) |
 //! | [`seeds`] | deterministic seed sets + region discipline |
 //! | [`cc2`] | TBP client for baselining Cold Clear 2 as a subprocess |
@@ -83,6 +84,7 @@ pub mod registry;
 pub mod rng;
 pub mod seeds;
 pub mod sprt;
+pub mod style;
 pub mod versus;
 pub mod versus_legacy;
 
@@ -251,11 +251,14 @@ fn execute(
     let result = match &entry.experiment {
         Marathon(spec) => commands::marathon::run(spec, &bot(0), &rt),
         Pc(spec) => commands::pc::run(spec, &bot(0), &rt),
+        PcOdds(spec) => commands::pc_odds::run(spec, &rt),
         Downstack(spec) => commands::downstack::run(spec, &bot(0), &rt),
         Versus(spec) => commands::versus::run(spec, &bot(0), &bot(1), &rt),
         Race(spec) => commands::race::run(spec, &bot(0), &bot(1), &rt),
         Cc2Baseline(spec) => commands::cc2_baseline::run(spec, &rt),
         AppClimb(spec) => commands::climb_app::run(spec, &bot(0), &rt),
+        HandicapLadder(spec) => commands::handicap::run(spec, &bot(0), &rt),
+        PersonalityStyle(spec) => commands::style::run(spec, &rt),
     }?;
     // The entire stdout contract: ONE self-describing JSON line per run
     // (humans read stderr; pipelines read this).
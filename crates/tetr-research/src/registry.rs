@@ -17,7 +17,9 @@
 //! Optimizers are not evals: the search side was removed pending a
 //! first-principles redesign (history in git, `aa7bda9` and earlier).
 
-use crate::commands::{cc2_baseline, climb_app, downstack, marathon, pc, race, versus};
+use crate::commands::{
+    cc2_baseline, climb_app, downstack, handicap, marathon, pc, pc_odds, race, style, versus,
+};
 
 /// One runnable eval: a name, a one-line description, and its spec.
 #[derive(Clone, Debug)]
@@ -34,11 +36,14 @@ pub struct Entry {
 pub enum Experiment {
     Marathon(marathon::Spec),
     Pc(pc::Spec),
+    PcOdds(pc_odds::Spec),
     Downstack(downstack::Spec),
     Versus(versus::Spec),
     Race(race::Spec),
     Cc2Baseline(cc2_baseline::Spec),
     AppClimb(climb_app::Spec),
+    HandicapLadder(handicap::Spec),
+    PersonalityStyle(style::Spec),
 }
 
 impl Experiment {
@@ -46,7 +51,9 @@ impl Experiment {
     pub fn bot_slots(&self) -> usize {
         match self {
             Experiment::Versus(_) | Experiment::Race(_) => 2,
-            Experiment::Cc2Baseline(_) => 0,
+            Experiment::Cc2Baseline(_)
+            | Experiment::PersonalityStyle(_)
+            | Experiment::PcOdds(_) => 0,
             _ => 1,
         }
     }
@@ -56,7 +63,9 @@ impl Experiment {
         match self {
             Experiment::Versus(_) => "<bot-a> <bot-b>",
             Experiment::Race(_) => "<candidate> <incumbent>",
-            Experiment::Cc2Baseline(_) => "",
+            Experiment::Cc2Baseline(_)
+            | Experiment::PersonalityStyle(_)
+            | Experiment::PcOdds(_) => "",
             Experiment::AppClimb(_) => "<subject>",
             _ => "<bot>",
         }
@@ -106,6 +115,20 @@ pub fn entries() -> Vec<Entry> {
                 default_budget_secs: 180,
             }),
         ),
+        e(
+            "opener-app-v1",
+            "30-piece opening APP on TRAIN seeds — the opener-book A/B (`run opener-app-v1 book-tp16d9`)",
+            Marathon(marathon::Spec {
+                seeds: 12,
+                max_pieces: 30,
+                seed_start: crate::seeds::regions::TRAIN,
+            }),
+        ),
+        e(
+            "pc-odds",
+            "exhaustive PC success rates for standard setups over the bag, hold and no hold",
+            PcOdds(pc_odds::Spec::default()),
+        ),
         e(
             "versus",
             "head-to-head win/death/attack report (`run versus cc2-default dt20`)",
@@ -116,6 +139,16 @@ pub fn entries() -> Vec<Entry> {
             "pair-GSPRT survival verdict (`run race <candidate> attack-tuned`)",
             Race(race::Spec::default()),
         ),
+        e(
+            "handicap-ladder",
+            "skill-preset PPS/APM vs anchors (`run handicap-ladder attack-tuned`)",
+            HandicapLadder(handicap::Spec::default()),
+        ),
+        e(
+            "personality-style",
+            "clear-type histograms: each personality bot vs attack-tuned-d3 on its signature",
+            PersonalityStyle(style::Spec::default()),
+        ),
         // --- holdout verdicts (one read per candidate at promotion time; ---
         // --- looping on these makes them training data) ----------------------
         e(
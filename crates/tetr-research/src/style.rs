@@ -0,0 +1,264 @@
+//! Headless **style** suite: what kind of clears a bot makes, not how many
+//! points they earn. A capped Marathon game is folded into a clear-type
+//! histogram — normal clears by line count, T-spins by line count, minis,
+//! perfect clears — plus the combo shape (longest chain, clears made three or
+//! more deep in one) and the stack height at every lock. The personality
+//! bots are told apart by these, where score/APP would rank them on one axis.
+//!
+//! Determinism: like [`crate::marathon`], a game is a pure function of
+//! `(bot factory, seed)`; the parallel suite is order-stable.
+
+use rayon::prelude::*;
+use tetr_core::engine::{Engine, EngineEvent, EngineScoreAction, TSpinKind};
+use tetr_core::player::{PlayerController, drive_engine};
+
+use crate::accounting::{action_clear_lines, controller_seed};
+use crate::marathon::{DEFAULT_MAX_FRAMES, marathon_config};
+
+/// Combo index (0 = the chain's first clear) from which a clear counts as
+/// "deep in a combo" — the third consecutive clearing placement.
+pub const DEEP_COMBO: u32 = 2;
+
+/// One game's clear-type histogram.
+#[derive(Debug, Clone, Copy, Default, serde::Serialize)]
+pub struct StyleOutcome {
+    pub seed: u64,
+    pub pieces: u32,
+    /// Plain clears by line count (`[_, singles, doubles, triples, tetrises]`).
+    pub normal: [u32; 5],
+    /// Full T-spin clears by line count (index 0 = a zero-line spin).
+    pub t_spins: [u32; 4],
+    /// Mini T-spins that cleared a line.
+    pub t_spin_minis: u32,
+    pub perfect_clears: u32,
+    /// Longest run of consecutive clearing placements.
+    pub max_combo: u32,
+    /// Clears made at combo index [`DEEP_COMBO`] or later.
+    pub deep_combo_clears: u32,
+    /// Sum over locks of the post-lock stack height (rows).
+    pub height_sum: u64,
+    pub topped_out: bool,
+}
+
+impl StyleOutcome {
+    /// Line-clearing placements of any kind.
+    pub fn clears(&self) -> u32 {
+        self.normal[1..].iter().sum::<u32>()
+            + self.t_spins[1..].iter().sum::<u32>()
+            + self.t_spin_minis
+    }
+
+    /// Lines cleared, over every clear kind.
+    pub fn lines(&self) -> u32 {
+        let by_count = |table: &[u32]| {
+            table
+                .iter()
+                .enumerate()
+                .map(|(lines, n)| lines as u32 * n)
+                .sum::<u32>()
+        };
+        by_count(&self.normal) + by_count(&self.t_spins) + self.t_spin_minis
+    }
+}
+
+/// Play one capped Marathon game and fold it into a [`StyleOutcome`]. Minis
+/// are counted as one line (a mini that clears two is vanishingly rare and
+/// the histogram only needs "this bot spins small").
+pub fn play_style_game(
+    make_bot: &dyn Fn(u64) -> Box<dyn PlayerController>,
+    seed: u64,
+    max_pieces: u32,
+) -> StyleOutcome {
+    let mut engine = Engine::new(marathon_config(), seed);
+    let mut bot = make_bot(controller_seed(seed));
+    let mut out = StyleOutcome {
+        seed,
+        ..StyleOutcome::default()
+    };
+    let mut combo = 0u32;
+    let mut frames = 0u32;
+    while frames < DEFAULT_MAX_FRAMES && out.pieces < max_pieces && !out.topped_out {
+        frames += 1;
+        for event in drive_engine(&mut engine, &mut *bot) {
+            match event {
+                EngineEvent::Locked { lines_cleared, .. } => {
+                    out.pieces += 1;
+                    if lines_cleared == 0 {
+                        combo = 0;
+                    }
+                    out.height_sum += u64::from(stack_height(&engine));
+                }
+                EngineEvent::ScoreAwarded { action, .. } if action_clear_lines(action) > 0 => {
+                    let lines = action_clear_lines(action);
+                    match action {
+                        EngineScoreAction::TSpin {
+                            kind: TSpinKind::Mini,
+                            ..
+                        } => out.t_spin_minis += 1,
+                        EngineScoreAction::TSpin { .. } => out.t_spins[lines.min(3)] += 1,
+                        _ => out.normal[lines.min(4)] += 1,
+                    }
+                    if engine.board_is_empty() {
+                        out.perfect_clears += 1;
+                    }
+                    if combo >= DEEP_COMBO {
+                        out.deep_combo_clears += 1;
+                    }
+                    combo += 1;
+                    out.max_combo = out.max_combo.max(combo);
+                }
+                EngineEvent::GameOver { .. } => out.topped_out = true,
+                _ => {}
+            }
+        }
+    }
+    out
+}
+
+/// Rows from the floor to the highest settled cell.
+fn stack_height(engine: &Engine) -> u32 {
+    engine
+        .snapshot()
+        .board_cells
+        .iter()
+        .map(|c| c.y + 1)
+        .max()
+        .unwrap_or(0)
+        .max(0) as u32
+}
+
+/// A seed set's pooled histogram and the style ratios read off it.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct StyleStats {
+    pub games: usize,
+    pub pieces: u32,
+    pub clears: u32,
+    pub lines: u32,
+    pub topouts: u32,
+    pub normal: [u32; 5],
+    pub t_spins: [u32; 4],
+    pub t_spin_minis: u32,
+    pub perfect_clears: u32,
+    /// Share of clears that were T-spins (full or mini).
+    pub t_spin_share: f32,
+    /// Share of cleared lines that came from tetrises.
+    pub tetris_line_share: f32,
+    /// Share of clears made deep in a combo ([`DEEP_COMBO`]).
+    pub deep_combo_share: f32,
+    /// Mean over games of the longest combo.
+    pub mean_max_combo: f32,
+    /// Mean post-lock stack height.
+    pub mean_height: f32,
+}
+
+impl StyleStats {
+    pub fn from_outcomes(outcomes: &[StyleOutcome]) -> Self {
+        let mut normal = [0u32; 5];
+        let mut t_spins = [0u32; 4];
+        let (mut pieces, mut clears, mut lines, mut minis, mut pcs) = (0, 0, 0, 0, 0);
+        let (mut deep, mut topouts, mut combo_sum, mut height_sum) = (0u32, 0u32, 0u32, 0u64);
+        for o in outcomes {
+            for (sum, n) in normal.iter_mut().zip(o.normal) {
+                *sum += n;
+            }
+            for (sum, n) in t_spins.iter_mut().zip(o.t_spins) {
+                *sum += n;
+            }
+            pieces += o.pieces;
+            clears += o.clears();
+            lines += o.lines();
+            minis += o.t_spin_minis;
+            pcs += o.perfect_clears;
+            deep += o.deep_combo_clears;
+            topouts += u32::from(o.topped_out);
+            combo_sum += o.max_combo;
+            height_sum += o.height_sum;
+        }
+        let ratio = |num: u32, den: u32| {
+            if den > 0 {
+                num as f32 / den as f32
+            } else {
+                0.0
+            }
+        };
+        let spins = t_spins[1..].iter().sum::<u32>() + minis;
+        Self {
+            games: outcomes.len(),
+            pieces,
+            clears,
+            lines,
+            topouts,
+            normal,
+            t_spins,
+            t_spin_minis: minis,
+            perfect_clears: pcs,
+            t_spin_share: ratio(spins, clears),
+            tetris_line_share: ratio(4 * normal[4], lines),
+            deep_combo_share: ratio(deep, clears),
+            mean_max_combo: ratio(combo_sum, outcomes.len() as u32),
+            mean_height: if pieces > 0 {
+                height_sum as f32 / pieces as f32
+            } else {
+                0.0
+            },
+        }
+    }
+}
+
+/// Evaluate a bot's clear style over `seeds`, `max_pieces` per game.
+pub fn evaluate_style(
+    make_bot: &(dyn Fn(u64) -> Box<dyn PlayerController> + Sync),
+    seeds: &[u64],
+    max_pieces: u32,
+) -> (StyleStats, Vec<StyleOutcome>) {
+    let outcomes: Vec<StyleOutcome> = seeds
+        .par_iter()
+        .map(|&seed| play_style_game(make_bot, seed, max_pieces))
+        .collect();
+    (StyleStats::from_outcomes(&outcomes), outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game(normal: [u32; 5], t_spins: [u32; 4], max_combo: u32) -> StyleOutcome {
+        StyleOutcome {
+            pieces: 40,
+            normal,
+            t_spins,
+            max_combo,
+            height_sum: 40 * 5,
+            ..StyleOutcome::default()
+        }
+    }
+
+    #[test]
+    fn ratios_pool_over_games() {
+        let stats = StyleStats::from_outcomes(&[
+            game([0, 2, 0, 0, 2], [0, 0, 1, 0], 2),
+            game([0, 0, 0, 0, 1], [0, 0, 0, 0], 4),
+        ]);
+        assert_eq!(stats.clears, 6);
+        assert_eq!(stats.lines, 2 + 12 + 2);
+        assert!((stats.t_spin_share - 1.0 / 6.0).abs() < 1e-6);
+        assert!((stats.tetris_line_share - 12.0 / 16.0).abs() < 1e-6);
+        assert!((stats.mean_max_combo - 3.0).abs() < 1e-6);
+        assert!((stats.mean_height - 5.0).abs() < 1e-6);
+    }
+
+    /// The suite is bit-identical to sequential play of the same seeds.
+    #[test]
+    fn parallel_evaluation_matches_sequential() {
+        let make = crate::bots::BotSpec::greedy().factory();
+        let seeds = crate::seeds::seed_set(3);
+        let (_, parallel) = evaluate_style(&make, &seeds, 30);
+        for (p, &seed) in parallel.iter().zip(&seeds) {
+            let s = play_style_game(&make, seed, 30);
+            assert_eq!(
+                (p.pieces, p.normal, p.height_sum),
+                (s.pieces, s.normal, s.height_sum)
+            );
+        }
+    }
+}
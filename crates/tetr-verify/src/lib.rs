@@ -0,0 +1,33 @@
+//! `tetr-verify` — decide whether a submitted solo run really happened.
+//!
+//! A `HighScore` on the player's machine is just four numbers; once runs
+//! leave it, anyone can type in a better set. But the engine is a pure
+//! function of `(config, seed, input frames)`, so a run carries its own
+//! proof: submit the inputs with the claim and the server replays them. The
+//! crate is three layers on that idea:
+//!
+//! | module | role |
+//! |---|---|
+//! | [`replay`] | the submitted run: rules, seed, config knobs, claim and one [`InputFrame::buttons`](tetr_core::engine::InputFrame::buttons) set per tick, in a line-based text format |
+//! | [`verify`](mod@verify) | re-simulates a [`Replay`] through [`Engine`](tetr_core::engine::Engine) under its variant's end condition and checks the claim |
+//! | [`server`] | a minimal HTTP/1.1 leaderboard that files only verified runs |
+//!
+//! What is *not* checked: the seed is the submitter's choice, so a player can
+//! still shop for a friendly deal, and a bot can still play the inputs. Both
+//! need the server to hand out the seed (and a human to be watching), which
+//! is a matchmaking service's job, not this crate's.
+//!
+//! Bevy-free and std-only. The `tetr-verify` binary (src/main.rs):
+//!
+//! ```text
+//! cargo run --release -p tetr-verify -- check run.tetr-replay
+//! cargo run --release -p tetr-verify -- serve --addr 127.0.0.1:7879
+//! ```
+
+pub mod replay;
+pub mod server;
+pub mod verify;
+
+pub use replay::{Claim, Replay, ReplayError};
+pub use server::{Leaderboard, LeaderboardServer};
+pub use verify::{Finish, Outcome, Rejection, simulate, verify};
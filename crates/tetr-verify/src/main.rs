@@ -0,0 +1,87 @@
+//! `tetr-verify` — check replay files, or serve the verified leaderboard.
+//!
+//! ```text
+//! tetr-verify check <replay>...
+//! tetr-verify serve [--addr 127.0.0.1:7879]
+//! ```
+//!
+//! `check` prints one verdict per file and fails if any run is rejected.
+
+use std::process::ExitCode;
+
+use tetr_verify::{LeaderboardServer, Replay, verify};
+
+const DEFAULT_ADDR: &str = "127.0.0.1:7879";
+const USAGE: &str = "usage: tetr-verify check <replay>... | tetr-verify serve [--addr <host:port>]";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match args.split_first() {
+        Some((command, files)) if command == "check" && !files.is_empty() => check(files),
+        Some((command, rest)) if command == "serve" => match rest {
+            [] => serve(DEFAULT_ADDR),
+            [flag, addr] if flag == "--addr" => serve(addr),
+            _ => usage(),
+        },
+        _ => usage(),
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("{USAGE}");
+    ExitCode::FAILURE
+}
+
+fn check(files: &[String]) -> ExitCode {
+    let mut all_ok = true;
+    for path in files {
+        let verdict = std::fs::read_to_string(path)
+            .map_err(|err| err.to_string())
+            .and_then(|text| Replay::parse(&text).map_err(|err| err.to_string()))
+            .and_then(|replay| {
+                verify(&replay)
+                    .map(|outcome| (replay.rules, outcome))
+                    .map_err(|rejection| format!("rejected: {rejection}"))
+            });
+        match verdict {
+            Ok((rules, outcome)) => println!(
+                "{path}: ok — {} score {} lines {} level {} in {:.2}s",
+                rules.key(),
+                outcome.score,
+                outcome.lines,
+                outcome.level,
+                outcome.time_seconds
+            ),
+            Err(reason) => {
+                all_ok = false;
+                println!("{path}: {reason}");
+            }
+        }
+    }
+    if all_ok {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+fn serve(addr: &str) -> ExitCode {
+    let server = match LeaderboardServer::bind(addr) {
+        Ok(server) => server,
+        Err(err) => {
+            eprintln!("tetr-verify: cannot bind {addr}: {err}");
+            return ExitCode::FAILURE;
+        }
+    };
+    match server.local_addr() {
+        Ok(local) => eprintln!("tetr-verify: leaderboard on http://{local}"),
+        Err(_) => eprintln!("tetr-verify: leaderboard on http://{addr}"),
+    }
+    match server.serve() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("tetr-verify: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,414 @@
+//! The submitted run and its text format.
+//!
+//! A [`Replay`] is everything the engine needs to play a solo run again —
+//! the variant's [`SoloRules`], the seed, the two config knobs the game lets
+//! a player change (preview count and lock-down rule) — plus the [`Claim`]
+//! the submitter makes about how it ended, and the input stream itself. The
+//! rest of the [`EngineConfig`] is not the submitter's to choose: the game
+//! always plays solo on the default board at the starting level under the
+//! variant's goal system, so the format cannot even describe anything else.
+//!
+//! On disk (and on the wire) it is line-based text:
+//!
+//! ```text
+//! tetr-replay 2
+//! rules sprint
+//! seed 1234
+//! preview 5
+//! lock extended
+//! claim score=21480 lines=40 level=5 time=58.35
+//! frames 3501
+//! 000000000000008004...
+//! ```
+//!
+//! Each frame is one tick of [`TICK_SECONDS`] — the game's fixed simulation
+//! step — written as three hex digits of [`InputFrame::buttons`], the layout
+//! tetr-net and the game's own replays use, 64 frames to a line (an ARR-0
+//! slide to the left is `101`). Which of those button sets a run may hold is
+//! the verifier's call, not the format's.
+//! Times print with Rust's shortest round-trip float formatting, so a claim
+//! parses back to the exact `f32` it was written from.
+//!
+//! The game keeps every seat it plays as a [`RecordedGame`];
+//! [`Replay::from_recording`] turns a solo seat's into a submission.
+
+use std::fmt::{self, Write as _};
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use tetr_core::ai::RecordedGame;
+use tetr_core::engine::{EngineConfig, InputFrame, LockDownMode};
+use tetr_core::variant::SoloRules;
+
+/// The `tetr-replay` header's format version.
+pub const FORMAT_VERSION: u32 = 2;
+
+/// One simulation tick: the game steps its engines at a fixed 60 Hz and
+/// stamps every frame with this `dt`.
+pub const TICK_SECONDS: f32 = 1.0 / 60.0;
+
+/// The longest run accepted: two hours of ticks. Nothing legitimate comes
+/// close; it bounds what one submission can make the server simulate.
+pub const MAX_FRAMES: usize = 2 * 60 * 60 * 60;
+
+/// Frames per line of the hex body.
+const FRAMES_PER_LINE: usize = 64;
+
+/// Hex digits per frame: enough for [`InputFrame::BUTTON_MASK`].
+const DIGITS_PER_FRAME: usize = 3;
+
+/// How the submitter says the run ended — the figures a high-score entry
+/// files.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Claim {
+    pub score: usize,
+    pub lines: usize,
+    /// The level reached (a Master run's grade index).
+    pub level: u8,
+    pub time_seconds: f32,
+}
+
+/// One submitted solo run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Replay {
+    pub rules: SoloRules,
+    pub seed: u64,
+    pub preview_count: usize,
+    pub lock_down_mode: LockDownMode,
+    pub claim: Claim,
+    /// One frame per tick, from the first tick of play. Frames read from text
+    /// always carry [`TICK_SECONDS`]; one built in code is checked for it.
+    pub frames: Vec<InputFrame>,
+}
+
+/// A replay file that does not parse.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReplayError(pub String);
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "replay: {}", self.0)
+    }
+}
+
+impl std::error::Error for ReplayError {}
+
+impl Replay {
+    /// The engine config the game played this run under.
+    pub fn engine_config(&self) -> EngineConfig {
+        EngineConfig {
+            preview_count: self.preview_count,
+            lock_down_mode: self.lock_down_mode,
+            goal_system: self.rules.goal_system(),
+            dig: self.rules.dig(),
+            lock_delay_curve: self.rules.lock_delay_curve(),
+            perfect_clear_bonus: self.rules.perfect_clear_bonus(),
+            master: self.rules.master(),
+            ..EngineConfig::default()
+        }
+    }
+
+    /// The submission for a solo seat the game recorded under `rules`. The
+    /// recording must be one the format can describe: `rules`' own config at
+    /// the fixed tick, with no attack from another seat. Pause presses drop
+    /// out (the engine ignores them, and a run carries none).
+    pub fn from_recording(
+        rules: SoloRules,
+        recording: &RecordedGame,
+        claim: Claim,
+    ) -> Result<Self, ReplayError> {
+        let replay = Self {
+            rules,
+            seed: recording.seed,
+            preview_count: recording.config.preview_count,
+            lock_down_mode: recording.config.lock_down_mode,
+            claim,
+            frames: Vec::with_capacity(recording.inputs.len()),
+        };
+        if replay.engine_config() != recording.config {
+            return Err(ReplayError(format!(
+                "not recorded under {} rules",
+                rules.key()
+            )));
+        }
+        if !recording.garbage.is_empty() {
+            return Err(ReplayError("a solo run takes no garbage".to_owned()));
+        }
+        let frames = recording
+            .inputs
+            .iter()
+            .enumerate()
+            .map(|(i, frame)| {
+                if frame.dt_seconds.to_bits() != TICK_SECONDS.to_bits() {
+                    return Err(ReplayError(format!(
+                        "frame {i}: dt {} is not the tick",
+                        frame.dt_seconds
+                    )));
+                }
+                Ok(InputFrame {
+                    pause: false,
+                    ..frame.clone()
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(Self { frames, ..replay })
+    }
+
+    /// A hash of the run itself — rules, seed, config knobs and inputs, but
+    /// not the claim — so every submission of one run shares it. Stable
+    /// within a process, which is all an in-memory board needs.
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.rules.key().hash(&mut hasher);
+        self.seed.hash(&mut hasher);
+        self.preview_count.hash(&mut hasher);
+        lock_key(self.lock_down_mode).hash(&mut hasher);
+        for frame in &self.frames {
+            frame.buttons().hash(&mut hasher);
+            frame.dt_seconds.to_bits().hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    /// Write the text format. Only the buttons of each frame are written;
+    /// `dt` is implied by the format.
+    pub fn to_text(&self) -> String {
+        let claim = &self.claim;
+        let mut out = format!(
+            "tetr-replay {FORMAT_VERSION}\nrules {}\nseed {}\npreview {}\nlock {}\n\
+             claim score={} lines={} level={} time={}\nframes {}\n",
+            self.rules.key(),
+            self.seed,
+            self.preview_count,
+            lock_key(self.lock_down_mode),
+            claim.score,
+            claim.lines,
+            claim.level,
+            claim.time_seconds,
+            self.frames.len(),
+        );
+        for line in self.frames.chunks(FRAMES_PER_LINE) {
+            for frame in line {
+                let _ = write!(out, "{:03x}", frame.buttons());
+            }
+            out.push('\n');
+        }
+        out
+    }
+
+    /// Parse the text format.
+    pub fn parse(text: &str) -> Result<Self, ReplayError> {
+        let mut lines = text.lines();
+        let mut field = |key: &str| -> Result<&str, ReplayError> {
+            let line = lines
+                .next()
+                .ok_or_else(|| ReplayError(format!("missing `{key}` line")))?;
+            line.strip_prefix(key)
+                .and_then(|rest| rest.strip_prefix(' '))
+                .map(str::trim)
+                .ok_or_else(|| ReplayError(format!("expected `{key} …`, found {line:?}")))
+        };
+
+        let version = field("tetr-replay")?;
+        if version != FORMAT_VERSION.to_string() {
+            return Err(ReplayError(format!("unsupported version {version:?}")));
+        }
+        let rules = field("rules")?;
+        let rules = SoloRules::from_key(rules)
+            .ok_or_else(|| ReplayError(format!("unknown rules {rules:?}")))?;
+        let seed = number(field("seed")?, "seed")?;
+        let preview_count = number(field("preview")?, "preview")?;
+        let lock = field("lock")?;
+        let lock_down_mode =
+            lock_from_key(lock).ok_or_else(|| ReplayError(format!("unknown lock {lock:?}")))?;
+        let claim = parse_claim(field("claim")?)?;
+        let count: usize = number(field("frames")?, "frames")?;
+        if count > MAX_FRAMES {
+            return Err(ReplayError(format!(
+                "{count} frames is over the {MAX_FRAMES} limit"
+            )));
+        }
+
+        let digits: Vec<u8> = lines
+            .flat_map(str::bytes)
+            .filter(|b| !b.is_ascii_whitespace())
+            .collect();
+        if digits.len() != count * DIGITS_PER_FRAME {
+            return Err(ReplayError(format!(
+                "{count} frames declared, {} hex digits found",
+                digits.len()
+            )));
+        }
+        let frames = digits
+            .chunks(DIGITS_PER_FRAME)
+            .enumerate()
+            .map(|(i, hex)| {
+                std::str::from_utf8(hex)
+                    .ok()
+                    .and_then(|hex| u16::from_str_radix(hex, 16).ok())
+                    .filter(|bits| bits & !InputFrame::BUTTON_MASK == 0)
+                    .map(|bits| InputFrame::from_buttons(bits, TICK_SECONDS))
+                    .ok_or_else(|| ReplayError(format!("frame {i}: bad button bits")))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            rules,
+            seed,
+            preview_count,
+            lock_down_mode,
+            claim,
+            frames,
+        })
+    }
+}
+
+fn number<T: std::str::FromStr>(value: &str, what: &str) -> Result<T, ReplayError> {
+    value
+        .parse()
+        .map_err(|_| ReplayError(format!("bad {what} {value:?}")))
+}
+
+fn parse_claim(value: &str) -> Result<Claim, ReplayError> {
+    let (mut score, mut lines, mut level, mut time) = (None, None, None, None);
+    for pair in value.split_whitespace() {
+        let (key, value) = pair
+            .split_once('=')
+            .ok_or_else(|| ReplayError(format!("bad claim field {pair:?}")))?;
+        match key {
+            "score" => score = Some(number(value, "score")?),
+            "lines" => lines = Some(number(value, "lines")?),
+            "level" => level = Some(number(value, "level")?),
+            "time" => time = Some(number::<f32>(value, "time")?),
+            _ => return Err(ReplayError(format!("unknown claim field {key:?}"))),
+        }
+    }
+    match (score, lines, level, time) {
+        (Some(score), Some(lines), Some(level), Some(time_seconds)) if time_seconds.is_finite() => {
+            Ok(Claim {
+                score,
+                lines,
+                level,
+                time_seconds,
+            })
+        }
+        _ => Err(ReplayError(
+            "claim needs finite score, lines, level and time".to_owned(),
+        )),
+    }
+}
+
+fn lock_key(mode: LockDownMode) -> &'static str {
+    match mode {
+        LockDownMode::Extended => "extended",
+        LockDownMode::Infinite => "infinite",
+        LockDownMode::Classic => "classic",
+    }
+}
+
+fn lock_from_key(key: &str) -> Option<LockDownMode> {
+    [
+        LockDownMode::Extended,
+        LockDownMode::Infinite,
+        LockDownMode::Classic,
+    ]
+    .into_iter()
+    .find(|&mode| lock_key(mode) == key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> Replay {
+        let tick = InputFrame {
+            dt_seconds: TICK_SECONDS,
+            ..InputFrame::default()
+        };
+        let mut frames = vec![tick.clone(); 130];
+        frames[3].left = true;
+        frames[4].right = true;
+        frames[4].instant_shift = true;
+        frames[64].hard_drop = true;
+        frames[129].hold = true;
+        frames[129].rotate_counterclockwise = true;
+        Replay {
+            rules: SoloRules::Ultra,
+            seed: u64::MAX,
+            preview_count: 3,
+            lock_down_mode: LockDownMode::Classic,
+            claim: Claim {
+                score: 12_345,
+                lines: 17,
+                level: 2,
+                time_seconds: 120.01667,
+            },
+            frames,
+        }
+    }
+
+    #[test]
+    fn the_text_format_round_trips_exactly() {
+        let replay = sample();
+        let text = replay.to_text();
+        assert!(text.starts_with("tetr-replay 2\nrules ultra\n"), "{text}");
+        assert_eq!(Replay::parse(&text), Ok(replay));
+    }
+
+    #[test]
+    fn the_fingerprint_is_the_run_not_the_claim() {
+        let replay = sample();
+        let mut reclaimed = replay.clone();
+        reclaimed.claim.score += 1;
+        assert_eq!(replay.fingerprint(), reclaimed.fingerprint());
+        let mut replayed = replay.clone();
+        replayed.frames[10].hold = true;
+        assert_ne!(replay.fingerprint(), replayed.fingerprint());
+        let mut reseeded = replay.clone();
+        reseeded.seed = 1;
+        assert_ne!(replay.fingerprint(), reseeded.fingerprint());
+    }
+
+    #[test]
+    fn a_recording_converts_under_its_own_rules_only() {
+        let replay = sample();
+        let mut recording = RecordedGame::new(replay.engine_config(), replay.seed);
+        recording.inputs = replay.frames.clone();
+        recording.inputs[7].pause = true;
+        assert_eq!(
+            Replay::from_recording(SoloRules::Ultra, &recording, replay.claim),
+            Ok(replay.clone())
+        );
+
+        let mut blitz = replay.clone();
+        blitz.rules = SoloRules::Blitz;
+        assert!(Replay::from_recording(SoloRules::Blitz, &recording, replay.claim).is_err());
+        recording.config = blitz.engine_config();
+        assert!(Replay::from_recording(SoloRules::Blitz, &recording, replay.claim).is_ok());
+
+        let mut attacked = recording.clone();
+        attacked.push_garbage(4);
+        assert!(Replay::from_recording(SoloRules::Blitz, &attacked, replay.claim).is_err());
+
+        recording.inputs[9].dt_seconds = TICK_SECONDS / 2.0;
+        assert!(Replay::from_recording(SoloRules::Blitz, &recording, replay.claim).is_err());
+    }
+
+    #[test]
+    fn malformed_files_are_refused_with_a_reason() {
+        let text = sample().to_text();
+        let bad = [
+            text.replace("tetr-replay 2", "tetr-replay 9"),
+            text.replace("rules ultra", "rules zen"),
+            text.replace("lock classic", "lock sticky"),
+            text.replace("time=", "tempo="),
+            text.replace("frames 130", "frames 131"),
+            text.replace("seed ", "seeds "),
+            format!("{}200\n", text.replace("frames 130", "frames 131")),
+            format!("{}00\n", text.replace("frames 130", "frames 131")),
+            text.lines().take(4).collect::<Vec<_>>().join("\n"),
+        ];
+        for text in bad {
+            assert!(Replay::parse(&text).is_err(), "{text}");
+        }
+    }
+}
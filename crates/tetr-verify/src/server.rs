@@ -0,0 +1,354 @@
+//! The verified leaderboard: a minimal HTTP/1.1 service that files a run only
+//! after [`verify`] accepts it.
+//!
+//! | request | response |
+//! |---|---|
+//! | `POST /runs?player=<name>` with a replay as the body | `201` and the rank it took, `200` if verified but off the board, `400` if it does not parse, `409` if the run was filed before, `422` with the [`Rejection`](crate::Rejection) |
+//! | `GET /leaderboard/<rules>` | `200` and the top ten, one run per line |
+//!
+//! The board keeps the *verified* figures, never the claimed ones, and ranks
+//! them the way the game's own high-score table does: Sprint and Dig by time
+//! ascending, Marathon, Ultra and Blitz by score descending, Master by grade
+//! and then score, a tie going to the run filed first. A run files once: the
+//! board remembers each accepted replay's [`fingerprint`](Replay::fingerprint),
+//! so resubmitting it, under another name or a nudged claim, cannot fill a
+//! board. Everything is plain text, one connection per request, one thread per
+//! connection, the boards behind one lock and kept in memory — sized for a LAN
+//! party, not the internet.
+
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::{Arc, Mutex};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use tetr_core::variant::{ScoreKind, SoloRules};
+
+use crate::replay::Replay;
+use crate::verify::{Outcome, verify};
+
+/// Entries kept per variant, as in the game.
+pub const MAX_ENTRIES: usize = 10;
+
+/// The largest request body read: a two-hour replay is well under it.
+const MAX_BODY_BYTES: usize = 2 << 20;
+
+/// Longest player name kept; longer names are cut.
+const MAX_PLAYER_LEN: usize = 16;
+
+/// One filed run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub player: String,
+    pub outcome: Outcome,
+}
+
+/// The per-variant top tens.
+#[derive(Debug, Default)]
+pub struct Leaderboard {
+    tables: HashMap<SoloRules, Vec<Entry>>,
+    /// The fingerprint of every run accepted so far, on the board or not.
+    filed: HashSet<u64>,
+}
+
+impl Leaderboard {
+    /// `rules`' board, best first.
+    pub fn table(&self, rules: SoloRules) -> &[Entry] {
+        self.tables.get(&rules).map_or(&[], Vec::as_slice)
+    }
+
+    /// Remember `replay` as filed. `false` if the same run was filed before,
+    /// whatever its claim or player.
+    pub fn mark_filed(&mut self, replay: &Replay) -> bool {
+        self.filed.insert(replay.fingerprint())
+    }
+
+    /// File a verified run. Returns its 0-based rank, or `None` if it did not
+    /// make the board.
+    pub fn insert(&mut self, rules: SoloRules, entry: Entry) -> Option<usize> {
+        let kind = rules.score_kind();
+        let table = self.tables.entry(rules).or_default();
+        let rank = table
+            .iter()
+            .position(|existing| better(&entry.outcome, &existing.outcome, kind))
+            .unwrap_or(table.len());
+        if rank >= MAX_ENTRIES {
+            return None;
+        }
+        table.insert(rank, entry);
+        table.truncate(MAX_ENTRIES);
+        Some(rank)
+    }
+}
+
+/// Whether `a` ranks strictly ahead of `b`.
+fn better(a: &Outcome, b: &Outcome, kind: ScoreKind) -> bool {
+    match kind {
+        ScoreKind::Time => a.time_seconds < b.time_seconds,
+        ScoreKind::Score => a.score > b.score,
+        ScoreKind::Grade => (a.level, a.score) > (b.level, b.score),
+    }
+}
+
+/// A bound leaderboard service, ready to [`serve`](Self::serve).
+pub struct LeaderboardServer {
+    listener: TcpListener,
+    board: Arc<Mutex<Leaderboard>>,
+}
+
+impl LeaderboardServer {
+    /// Bind the listening socket (`127.0.0.1:0` picks a free port; read it
+    /// back with [`local_addr`](Self::local_addr)).
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        Ok(Self {
+            listener: TcpListener::bind(addr)?,
+            board: Arc::default(),
+        })
+    }
+
+    pub fn local_addr(&self) -> io::Result<SocketAddr> {
+        self.listener.local_addr()
+    }
+
+    /// Accept connections forever, one handler thread each. Returns only if
+    /// the listener itself fails.
+    pub fn serve(self) -> io::Result<()> {
+        for stream in self.listener.incoming() {
+            let stream = stream?;
+            let board = Arc::clone(&self.board);
+            thread::spawn(move || {
+                let peer = stream.peer_addr().ok();
+                if let Err(err) = handle(stream, &board) {
+                    eprintln!("tetr-verify: client {peer:?}: {err}");
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// [`serve`](Self::serve) on a background thread.
+    pub fn spawn(self) -> JoinHandle<io::Result<()>> {
+        thread::spawn(move || self.serve())
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    query: String,
+    body: Vec<u8>,
+}
+
+struct Response {
+    status: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn new(status: &'static str, body: impl Into<String>) -> Self {
+        Self {
+            status,
+            body: body.into(),
+        }
+    }
+}
+
+fn handle(stream: TcpStream, board: &Mutex<Leaderboard>) -> io::Result<()> {
+    stream.set_read_timeout(Some(Duration::from_secs(10)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let response = match read_request(&mut reader)? {
+        Ok(request) => route(&request, board),
+        Err(response) => response,
+    };
+    let mut stream = stream;
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        response.status,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
+
+/// Read one request. The outer error is the connection failing; the inner
+/// one a request this server will not take, answered rather than dropped.
+fn read_request(reader: &mut impl BufRead) -> io::Result<Result<Request, Response>> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(target)) = (parts.next(), parts.next()) else {
+        return Ok(Err(Response::new("400 Bad Request", "bad request line\n")));
+    };
+    let (method, target) = (method.to_owned(), target.to_owned());
+
+    let mut length = 0usize;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':')
+            && name.eq_ignore_ascii_case("content-length")
+        {
+            match value.trim().parse() {
+                Ok(value) => length = value,
+                Err(_) => return Ok(Err(Response::new("400 Bad Request", "bad length\n"))),
+            }
+        }
+    }
+    if length > MAX_BODY_BYTES {
+        return Ok(Err(Response::new(
+            "413 Content Too Large",
+            "replay too large\n",
+        )));
+    }
+    let mut body = vec![0u8; length];
+    reader.read_exact(&mut body)?;
+
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    Ok(Ok(Request {
+        method,
+        path: path.to_owned(),
+        query: query.to_owned(),
+        body,
+    }))
+}
+
+fn route(request: &Request, board: &Mutex<Leaderboard>) -> Response {
+    let path: Vec<&str> = request.path.split('/').filter(|s| !s.is_empty()).collect();
+    match (request.method.as_str(), path.as_slice()) {
+        ("POST", ["runs"]) => submit(request, board),
+        ("GET", ["leaderboard", rules]) => match SoloRules::from_key(rules) {
+            Some(rules) => Response::new("200 OK", render_table(&board.lock().unwrap(), rules)),
+            None => Response::new("404 Not Found", format!("no leaderboard {rules:?}\n")),
+        },
+        (_, ["runs"] | ["leaderboard", _]) => {
+            Response::new("405 Method Not Allowed", "method not allowed\n")
+        }
+        _ => Response::new("404 Not Found", "not found\n"),
+    }
+}
+
+fn submit(request: &Request, board: &Mutex<Leaderboard>) -> Response {
+    let Ok(text) = std::str::from_utf8(&request.body) else {
+        return Response::new("400 Bad Request", "replay is not UTF-8\n");
+    };
+    let replay = match Replay::parse(text) {
+        Ok(replay) => replay,
+        Err(err) => return Response::new("400 Bad Request", format!("{err}\n")),
+    };
+    let outcome = match verify(&replay) {
+        Ok(outcome) => outcome,
+        Err(rejection) => {
+            return Response::new(
+                "422 Unprocessable Content",
+                format!("rejected: {rejection}\n"),
+            );
+        }
+    };
+    let entry = Entry {
+        player: player_name(&request.query),
+        outcome,
+    };
+    let mut board = board.lock().unwrap();
+    if !board.mark_filed(&replay) {
+        return Response::new("409 Conflict", "this run is already filed\n");
+    }
+    match board.insert(replay.rules, entry) {
+        Some(rank) => Response::new("201 Created", format!("rank {}\n", rank + 1)),
+        None => Response::new("200 OK", "verified, not on the board\n"),
+    }
+}
+
+/// The `player` query parameter, kept to a short run of name characters.
+fn player_name(query: &str) -> String {
+    let name: String = query
+        .split('&')
+        .find_map(|pair| pair.strip_prefix("player="))
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_'))
+        .take(MAX_PLAYER_LEN)
+        .collect();
+    if name.is_empty() {
+        "anonymous".to_owned()
+    } else {
+        name
+    }
+}
+
+fn render_table(board: &Leaderboard, rules: SoloRules) -> String {
+    board
+        .table(rules)
+        .iter()
+        .enumerate()
+        .map(|(rank, entry)| {
+            let o = &entry.outcome;
+            format!(
+                "{} {} score={} lines={} level={} time={:.2}\n",
+                rank + 1,
+                entry.player,
+                o.score,
+                o.lines,
+                o.level,
+                o.time_seconds
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::verify::Finish;
+
+    fn run(score: usize, time_seconds: f32) -> Entry {
+        Entry {
+            player: "p".to_owned(),
+            outcome: Outcome {
+                finish: Finish::Goal,
+                frames: 0,
+                score,
+                lines: 40,
+                level: 1,
+                time_seconds,
+                ranked: true,
+            },
+        }
+    }
+
+    #[test]
+    fn boards_rank_by_the_variants_own_key_and_keep_ten() {
+        let mut board = Leaderboard::default();
+        assert_eq!(board.insert(SoloRules::Sprint, run(0, 60.0)), Some(0));
+        assert_eq!(board.insert(SoloRules::Sprint, run(0, 50.0)), Some(0));
+        // A tie files after the run that got there first.
+        assert_eq!(board.insert(SoloRules::Sprint, run(0, 60.0)), Some(2));
+        assert_eq!(board.insert(SoloRules::Marathon, run(10, 99.0)), Some(0));
+        assert_eq!(board.insert(SoloRules::Marathon, run(20, 99.0)), Some(0));
+
+        for i in 0..MAX_ENTRIES {
+            board.insert(SoloRules::Ultra, run(100 + i, 120.0));
+        }
+        assert_eq!(board.insert(SoloRules::Ultra, run(50, 120.0)), None);
+        assert_eq!(board.insert(SoloRules::Ultra, run(500, 120.0)), Some(0));
+        assert_eq!(board.table(SoloRules::Ultra).len(), MAX_ENTRIES);
+    }
+
+    #[test]
+    fn player_names_are_tamed() {
+        assert_eq!(player_name("player=ada&x=1"), "ada");
+        assert_eq!(player_name("player=<b>evil</b>"), "bevilb");
+        assert_eq!(player_name(""), "anonymous");
+        assert_eq!(
+            player_name("player=abcdefghijklmnopqrstuvwxyz").len(),
+            MAX_PLAYER_LEN
+        );
+    }
+}
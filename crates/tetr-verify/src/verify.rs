@@ -0,0 +1,227 @@
+//! Re-simulation: play a [`Replay`] through the engine and hold its claim to
+//! what actually happened.
+//!
+//! [`simulate`] steps a fresh [`Engine`] one frame per tick, exactly as the
+//! game's session does, and stops where the game would have ended the run —
+//! at a top-out, or on the first tick the variant's
+//! [`EndCondition`](tetr_core::variant::EndCondition) holds. Frames past that
+//! point are ignored: the game may have stepped a slice or two more before
+//! its end check ran, and they change nothing it filed. [`verify`] then
+//! compares the [`Claim`]: score, lines and level exactly, time within
+//! [`TIME_TOLERANCE_SECONDS`].
+//!
+//! Before any of that, each frame must be one the game could have sent. Its
+//! `dt` is the fixed tick, and it asks for at most what the engine acts on in
+//! a frame: a single direction (the DAS pulses one side at a time), a single
+//! rotation (the keyboard resolver never sends both), an instant shift only
+//! on a direction, and no pause (a paused game steps no frames).
+
+use std::fmt;
+use std::ops::RangeInclusive;
+
+use tetr_core::engine::{Engine, InputFrame};
+
+use crate::replay::{Claim, MAX_FRAMES, Replay, TICK_SECONDS};
+
+/// How far a claimed time may sit from the re-simulated one. The game's
+/// match clock sums render-frame deltas while the engine steps fixed ticks,
+/// so the two drift by a frame or so over a run; a few ticks of slack keeps
+/// honest runs in without letting a forged time gain anything real.
+pub const TIME_TOLERANCE_SECONDS: f32 = 0.1;
+
+/// The preview counts the settings screen offers.
+pub const PREVIEW_COUNTS: RangeInclusive<usize> = 1..=6;
+
+/// How a re-simulated run ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Finish {
+    /// The variant's goal or time limit was reached.
+    Goal,
+    /// Block-out or lock-out.
+    ToppedOut,
+}
+
+/// What the replay actually produced.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Outcome {
+    pub finish: Finish,
+    /// Ticks played up to and including the one that ended the run.
+    pub frames: usize,
+    pub score: usize,
+    pub lines: usize,
+    /// The level reached (a Master run's grade index).
+    pub level: u8,
+    pub time_seconds: f32,
+    /// Whether the run files on a leaderboard at all: a time-ranked variant
+    /// ranks only runs that reached the goal.
+    pub ranked: bool,
+}
+
+impl Outcome {
+    /// The claim an honest submitter makes for this run.
+    pub fn claim(&self) -> Claim {
+        Claim {
+            score: self.score,
+            lines: self.lines,
+            level: self.level,
+            time_seconds: self.time_seconds,
+        }
+    }
+}
+
+/// Why a submitted run was refused.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rejection {
+    /// A config the game never plays solo under.
+    UnsupportedConfig(String),
+    /// More frames than [`MAX_FRAMES`].
+    TooLong { frames: usize },
+    /// A frame whose `dt` is not the fixed tick.
+    WrongStep { frame: usize, dt_seconds: f32 },
+    /// A frame no game client sends.
+    ImpossibleInput { frame: usize, reason: &'static str },
+    /// The input ran out before the run ended.
+    Unfinished { frames: usize },
+    /// A time-ranked run that topped out before the goal.
+    NotRanked,
+    /// The claim disagrees with the re-simulation.
+    ClaimMismatch {
+        field: &'static str,
+        claimed: String,
+        actual: String,
+    },
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rejection::UnsupportedConfig(what) => write!(f, "unsupported config: {what}"),
+            Rejection::TooLong { frames } => {
+                write!(f, "{frames} frames is over the {MAX_FRAMES} limit")
+            }
+            Rejection::WrongStep { frame, dt_seconds } => {
+                write!(
+                    f,
+                    "frame {frame}: dt {dt_seconds} is not the {TICK_SECONDS} tick"
+                )
+            }
+            Rejection::ImpossibleInput { frame, reason } => write!(f, "frame {frame}: {reason}"),
+            Rejection::Unfinished { frames } => {
+                write!(
+                    f,
+                    "the input ran out after {frames} frames with the run still going"
+                )
+            }
+            Rejection::NotRanked => write!(f, "a time-ranked run must reach its goal"),
+            Rejection::ClaimMismatch {
+                field,
+                claimed,
+                actual,
+            } => write!(f, "claimed {field} {claimed}, the replay makes it {actual}"),
+        }
+    }
+}
+
+impl std::error::Error for Rejection {}
+
+/// Play `replay` to its end without looking at the claim.
+pub fn simulate(replay: &Replay) -> Result<Outcome, Rejection> {
+    if !PREVIEW_COUNTS.contains(&replay.preview_count) {
+        return Err(Rejection::UnsupportedConfig(format!(
+            "preview count {}",
+            replay.preview_count
+        )));
+    }
+    if replay.frames.len() > MAX_FRAMES {
+        return Err(Rejection::TooLong {
+            frames: replay.frames.len(),
+        });
+    }
+
+    let end = replay.rules.end_condition();
+    let mut engine = Engine::new(replay.engine_config(), replay.seed);
+    for (i, frame) in replay.frames.iter().enumerate() {
+        check_frame(i, frame)?;
+        engine.step(frame.clone());
+        // Whole ticks, not a running sum: the same tick count is the same
+        // time however long the run.
+        let elapsed = ((i + 1) as f64 * f64::from(TICK_SECONDS)) as f32;
+        let snapshot = engine.snapshot();
+        let finish = if snapshot.game_over.is_some() {
+            Finish::ToppedOut
+        } else if end.met(&snapshot, elapsed) {
+            Finish::Goal
+        } else {
+            continue;
+        };
+        return Ok(Outcome {
+            finish,
+            frames: i + 1,
+            score: snapshot.score,
+            lines: snapshot.lines,
+            level: snapshot.result_level(),
+            time_seconds: elapsed,
+            ranked: replay.rules.run_qualifies(&snapshot, elapsed),
+        });
+    }
+    Err(Rejection::Unfinished {
+        frames: replay.frames.len(),
+    })
+}
+
+/// Play `replay` and accept it only if it ended on the board and its claim
+/// matches.
+pub fn verify(replay: &Replay) -> Result<Outcome, Rejection> {
+    let outcome = simulate(replay)?;
+    if !outcome.ranked {
+        return Err(Rejection::NotRanked);
+    }
+    let claim = &replay.claim;
+    let exact = [
+        ("score", claim.score, outcome.score),
+        ("lines", claim.lines, outcome.lines),
+        (
+            "level",
+            usize::from(claim.level),
+            usize::from(outcome.level),
+        ),
+    ];
+    for (field, claimed, actual) in exact {
+        if claimed != actual {
+            return Err(Rejection::ClaimMismatch {
+                field,
+                claimed: claimed.to_string(),
+                actual: actual.to_string(),
+            });
+        }
+    }
+    if (claim.time_seconds - outcome.time_seconds).abs() > TIME_TOLERANCE_SECONDS {
+        return Err(Rejection::ClaimMismatch {
+            field: "time",
+            claimed: claim.time_seconds.to_string(),
+            actual: outcome.time_seconds.to_string(),
+        });
+    }
+    Ok(outcome)
+}
+
+fn check_frame(i: usize, frame: &InputFrame) -> Result<(), Rejection> {
+    if frame.dt_seconds.to_bits() != TICK_SECONDS.to_bits() {
+        return Err(Rejection::WrongStep {
+            frame: i,
+            dt_seconds: frame.dt_seconds,
+        });
+    }
+    let reason = if frame.left && frame.right {
+        "left and right in one frame"
+    } else if frame.rotate_clockwise && frame.rotate_counterclockwise {
+        "two rotations in one frame"
+    } else if frame.instant_shift && !frame.left && !frame.right {
+        "an instant shift needs a direction"
+    } else if frame.pause {
+        "pause is not part of a run"
+    } else {
+        return Ok(());
+    };
+    Err(Rejection::ImpossibleInput { frame: i, reason })
+}
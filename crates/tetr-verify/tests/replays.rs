@@ -0,0 +1,291 @@
+//! Recorded runs and their forgeries: a bot plays real Sprint, Marathon and
+//! Dig runs the way the game's session steps them, each is written out as a
+//! replay file, and every way of tampering with one is caught — through the
+//! library and through the leaderboard service.
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+
+use tetr_core::ai::{AiController, Handicap};
+use tetr_core::engine::{Engine, InputFrame, LockDownMode};
+use tetr_core::player::PlayerController;
+use tetr_core::variant::SoloRules;
+use tetr_verify::replay::TICK_SECONDS;
+use tetr_verify::{Claim, Finish, LeaderboardServer, Rejection, Replay, simulate, verify};
+
+const SEED: u64 = 0x5EED_0034;
+
+fn blank(rules: SoloRules, frames: Vec<InputFrame>) -> Replay {
+    Replay {
+        rules,
+        seed: SEED,
+        preview_count: 5,
+        lock_down_mode: LockDownMode::Extended,
+        claim: Claim {
+            score: 0,
+            lines: 0,
+            level: 0,
+            time_seconds: 0.0,
+        },
+        frames,
+    }
+}
+
+/// A bot plays `rules` until the run ends, every frame stamped with the
+/// fixed tick as the session does; the claim is what the run made.
+fn recorded(rules: SoloRules) -> Replay {
+    let mut replay = blank(rules, Vec::new());
+    let mut engine = Engine::new(replay.engine_config(), SEED);
+    let mut bot = AiController::new(Handicap::perfect(), 34);
+    let end = rules.end_condition();
+    loop {
+        let mut frame = bot.poll(&engine.snapshot());
+        frame.dt_seconds = TICK_SECONDS;
+        engine.step(frame.clone());
+        replay.frames.push(frame);
+        let snapshot = engine.snapshot();
+        let elapsed = replay.frames.len() as f32 * TICK_SECONDS;
+        if snapshot.game_over.is_some() || end.met(&snapshot, elapsed) {
+            break;
+        }
+        assert!(replay.frames.len() < 20_000, "the bot never finished");
+    }
+    replay.claim = simulate(&replay).expect("a recorded run simulates").claim();
+    replay
+}
+
+/// Nothing but hard drops: a quick, honest top-out.
+fn topped_out(rules: SoloRules) -> Replay {
+    let drop = InputFrame {
+        dt_seconds: TICK_SECONDS,
+        hard_drop: true,
+        ..InputFrame::default()
+    };
+    let mut replay = blank(rules, vec![drop; 200]);
+    replay.claim = simulate(&replay).expect("topped out").claim();
+    replay
+}
+
+fn mismatch(replay: &Replay) -> &'static str {
+    match verify(replay) {
+        Err(Rejection::ClaimMismatch { field, .. }) => field,
+        other => panic!("expected a claim mismatch, got {other:?}"),
+    }
+}
+
+#[test]
+fn a_recorded_sprint_verifies_from_its_file() {
+    let replay = recorded(SoloRules::Sprint);
+    let parsed = Replay::parse(&replay.to_text()).expect("parses");
+    assert_eq!(parsed, replay);
+    let outcome = verify(&parsed).expect("an honest run verifies");
+    assert_eq!(outcome.finish, Finish::Goal);
+    assert!(outcome.lines >= 40);
+    assert_eq!(outcome.frames, replay.frames.len());
+}
+
+#[test]
+fn a_dig_run_replays_its_cheese_from_the_seed() {
+    let replay = recorded(SoloRules::from_key("dig-10-messy").unwrap());
+    let parsed = Replay::parse(&replay.to_text()).expect("parses");
+    let outcome = verify(&parsed).expect("an honest dig verifies");
+    assert_eq!(outcome.finish, Finish::Goal);
+    assert!(outcome.lines >= 10);
+    // The same inputs on another seed's cheese do not dig it out.
+    let mut reseeded = parsed.clone();
+    reseeded.seed ^= 1;
+    assert!(verify(&reseeded).is_err());
+}
+
+#[test]
+fn tampered_claims_are_caught() {
+    let replay = recorded(SoloRules::Sprint);
+
+    let mut richer = replay.clone();
+    richer.claim.score += 100;
+    assert_eq!(mismatch(&richer), "score");
+
+    let mut higher = replay.clone();
+    higher.claim.level += 1;
+    assert_eq!(mismatch(&higher), "level");
+
+    let mut faster = replay.clone();
+    faster.claim.time_seconds -= 1.0;
+    assert_eq!(mismatch(&faster), "time");
+
+    // Within the clock-drift slack is still the same run.
+    let mut jittered = replay.clone();
+    jittered.claim.time_seconds += 0.05;
+    assert!(verify(&jittered).is_ok());
+
+    // The same inputs on another deal do not make the same run.
+    let mut reseeded = replay.clone();
+    reseeded.seed ^= 1;
+    assert!(verify(&reseeded).is_err());
+}
+
+#[test]
+fn tampered_inputs_are_caught() {
+    let replay = recorded(SoloRules::Sprint);
+
+    let mut truncated = replay.clone();
+    truncated.frames.truncate(replay.frames.len() / 2);
+    assert!(matches!(
+        verify(&truncated),
+        Err(Rejection::Unfinished { .. })
+    ));
+
+    let mut both_ways = replay.clone();
+    both_ways.frames[10].left = true;
+    both_ways.frames[10].right = true;
+    assert_eq!(
+        verify(&both_ways),
+        Err(Rejection::ImpossibleInput {
+            frame: 10,
+            reason: "left and right in one frame",
+        })
+    );
+
+    let mut double_turn = replay.clone();
+    double_turn.frames[20].rotate_clockwise = true;
+    double_turn.frames[20].rotate_counterclockwise = true;
+    assert!(matches!(
+        verify(&double_turn),
+        Err(Rejection::ImpossibleInput { frame: 20, .. })
+    ));
+
+    let mut bare_shift = replay.clone();
+    bare_shift.frames[25].left = false;
+    bare_shift.frames[25].right = false;
+    bare_shift.frames[25].instant_shift = true;
+    assert_eq!(
+        verify(&bare_shift),
+        Err(Rejection::ImpossibleInput {
+            frame: 25,
+            reason: "an instant shift needs a direction",
+        })
+    );
+
+    let mut paused = replay.clone();
+    paused.frames[28].pause = true;
+    assert_eq!(
+        verify(&paused),
+        Err(Rejection::ImpossibleInput {
+            frame: 28,
+            reason: "pause is not part of a run",
+        })
+    );
+
+    let mut slow_motion = replay.clone();
+    slow_motion.frames[30].dt_seconds = TICK_SECONDS / 4.0;
+    assert!(matches!(
+        verify(&slow_motion),
+        Err(Rejection::WrongStep { frame: 30, .. })
+    ));
+
+    let mut long_preview = replay.clone();
+    long_preview.preview_count = 12;
+    assert!(matches!(
+        verify(&long_preview),
+        Err(Rejection::UnsupportedConfig(_))
+    ));
+
+    // A changed frame mid-run changes the game, so the old claim fails.
+    let mut edited = replay.clone();
+    let hard_drop = edited
+        .frames
+        .iter()
+        .position(|f| f.hard_drop)
+        .expect("the bot hard-drops");
+    edited.frames[hard_drop].hard_drop = false;
+    edited.frames.insert(
+        hard_drop,
+        InputFrame {
+            dt_seconds: TICK_SECONDS,
+            hold: true,
+            ..InputFrame::default()
+        },
+    );
+    assert!(verify(&edited).is_err());
+}
+
+#[test]
+fn frames_past_the_finish_are_ignored() {
+    let mut replay = recorded(SoloRules::Sprint);
+    let tick = InputFrame {
+        dt_seconds: TICK_SECONDS,
+        ..InputFrame::default()
+    };
+    replay.frames.extend(std::iter::repeat_n(tick, 3));
+    assert!(verify(&replay).is_ok());
+}
+
+#[test]
+fn a_top_out_ranks_only_where_score_is_the_key() {
+    let marathon = topped_out(SoloRules::Marathon);
+    let outcome = verify(&marathon).expect("a partial Marathon still posts its score");
+    assert_eq!(outcome.finish, Finish::ToppedOut);
+
+    assert_eq!(
+        verify(&topped_out(SoloRules::Sprint)),
+        Err(Rejection::NotRanked)
+    );
+}
+
+fn http(addr: std::net::SocketAddr, request: &str) -> String {
+    let mut stream = TcpStream::connect(addr).expect("connect");
+    stream.write_all(request.as_bytes()).expect("send");
+    let mut response = String::new();
+    stream.read_to_string(&mut response).expect("receive");
+    response
+}
+
+fn post(addr: std::net::SocketAddr, player: &str, replay: &Replay) -> String {
+    let body = replay.to_text();
+    http(
+        addr,
+        &format!(
+            "POST /runs?player={player} HTTP/1.1\r\nHost: test\r\nContent-Length: {}\r\n\r\n{body}",
+            body.len()
+        ),
+    )
+}
+
+#[test]
+fn the_leaderboard_files_only_verified_runs() {
+    let server = LeaderboardServer::bind("127.0.0.1:0").expect("bind");
+    let addr = server.local_addr().expect("addr");
+    server.spawn();
+
+    let replay = recorded(SoloRules::Sprint);
+    let mut forged = replay.clone();
+    forged.claim.time_seconds = 1.0;
+
+    let accepted = post(addr, "ada", &replay);
+    assert!(accepted.starts_with("HTTP/1.1 201"), "{accepted}");
+    assert!(accepted.ends_with("rank 1\n"), "{accepted}");
+    let refused = post(addr, "mallory", &forged);
+    assert!(refused.starts_with("HTTP/1.1 422"), "{refused}");
+    assert!(refused.contains("claimed time"), "{refused}");
+    // One run files once, under any name and any claim that still verifies.
+    let again = post(addr, "ada", &replay);
+    assert!(again.starts_with("HTTP/1.1 409"), "{again}");
+    let mut nudged = replay.clone();
+    nudged.claim.time_seconds += 0.05;
+    let copied = post(addr, "eve", &nudged);
+    assert!(copied.starts_with("HTTP/1.1 409"), "{copied}");
+    let garbled = http(
+        addr,
+        "POST /runs HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello",
+    );
+    assert!(garbled.starts_with("HTTP/1.1 400"), "{garbled}");
+
+    let board = http(addr, "GET /leaderboard/sprint HTTP/1.1\r\n\r\n");
+    assert!(board.starts_with("HTTP/1.1 200"), "{board}");
+    let body = board.split("\r\n\r\n").nth(1).expect("a body");
+    assert_eq!(body.lines().count(), 1, "{body}");
+    assert!(body.starts_with("1 ada score="), "{body}");
+
+    let missing = http(addr, "GET /leaderboard/zen HTTP/1.1\r\n\r\n");
+    assert!(missing.starts_with("HTTP/1.1 404"), "{missing}");
+}
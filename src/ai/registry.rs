@@ -8,9 +8,12 @@
 //! The setup screens (`crate::screens`) render per-seat rows via
 //! [`len`](ModelRegistry::len) / [`label`](ModelRegistry::label); the session's
 //! seat spawner builds a controller via [`build`](ModelRegistry::build) per bot
-//! seat when a session starts. Difficulty is the shared `beatable()` handicap
-//! for every entry: only the *model* (the planner + board evaluator) differs, so
-//! picks compare like-for-like: greedy vs beam, linear eval vs ported CC2 eval.
+//! seat when a session starts, under the seat's [`SkillPreset`] handicap. The
+//! preset is the seat's, not the entry's: only the *model* (the planner + board
+//! evaluator) differs between entries, so picks at one skill compare
+//! like-for-like: greedy vs beam, linear eval vs ported CC2 eval.
+//!
+//! [`SkillPreset`]: crate::ai::SkillPreset
 
 use bevy::prelude::*;
 
@@ -63,14 +66,14 @@ struct ModelEntry {
     label: String,
     /// One-line description shown for the focused row; wraps in the detail pane.
     detail: String,
-    build: Box<dyn Fn() -> AiController + Send + Sync>,
+    build: Box<dyn Fn(Handicap) -> AiController + Send + Sync>,
 }
 
 impl ModelEntry {
     fn new(
         label: impl Into<String>,
         detail: impl Into<String>,
-        build: impl Fn() -> AiController + Send + Sync + 'static,
+        build: impl Fn(Handicap) -> AiController + Send + Sync + 'static,
     ) -> Self {
         Self {
             label: label.into(),
@@ -106,11 +109,12 @@ impl ModelRegistry {
         self.entries.get(index).map_or("", |e| e.label.as_str())
     }
 
-    /// Build a fresh [`AiController`] for model `index`, if it exists. Each bot
-    /// seat addresses the catalog by index (a versus match builds *two*, possibly
-    /// the same model twice), so the catalog has no single "selected" cursor.
-    pub fn build(&self, index: usize) -> Option<AiController> {
-        self.entries.get(index).map(|e| (e.build)())
+    /// Build a fresh [`AiController`] for model `index` under `handicap`, if the
+    /// model exists. Each bot seat addresses the catalog by index (a versus match
+    /// builds *two*, possibly the same model twice at different skills), so the
+    /// catalog has no single "selected" cursor.
+    pub fn build(&self, index: usize, handicap: Handicap) -> Option<AiController> {
+        self.entries.get(index).map(|e| (e.build)(handicap))
     }
 
     /// Number of models in the catalog (the picker's cycle length).
@@ -126,8 +130,8 @@ impl ModelRegistry {
 }
 
 /// Wire a mind + evaluator into a fresh controller — the core's
-/// [`AiController::interactive`] convention (default handicap + AI seed +
-/// time-budgeted cooperative venue), so an entry differs only by the (mind,
+/// [`AiController::interactive`] convention (the seat's handicap + default AI
+/// seed + time-budgeted cooperative venue), so an entry differs only by the (mind,
 /// evaluator, budget) triple it names and the game can never fork the operating
 /// conventions from the core's. The host supplies the venue's [`FrameClock`] — the
 /// core stays clock-free.
@@ -135,8 +139,9 @@ fn search_model(
     mind: Box<dyn Mind>,
     eval: Box<dyn Evaluator>,
     budget: SearchBudget,
+    handicap: Handicap,
 ) -> AiController {
-    AiController::interactive(mind, eval, budget, Box::new(FrameClock::new()))
+    AiController::interactive(mind, eval, budget, handicap, Box::new(FrameClock::new()))
 }
 
 /// The host clock for the time-budgeted venue. The engine-agnostic core defines the
@@ -164,11 +169,26 @@ impl MonotonicClock for FrameClock {
 /// A transposition-pruned beam over the attack-tuned CC2 evaluator at `(width, depth)` — the
 /// shared shape of every in-game "attack" bot (the champion family). One home for the
 /// construction so a tweak (eval weights, speculation) lands once instead of per entry.
-fn tp_attack_model(width: usize, depth: u8) -> AiController {
+fn tp_attack_model(width: usize, depth: u8, handicap: Handicap) -> AiController {
     search_model(
         Box::new(BeamPlanner::transposing(width)),
         Box::new(Cc2Evaluator::new(Cc2Weights::attack_tuned())),
         SearchBudget::beam(depth),
+        handicap,
+    )
+}
+
+/// A personality: the depth-3 beam with one of the playstyle weight presets
+/// (`Cc2Weights::t_spin_specialist` and friends). Same search as "Beam CC2
+/// Attack", so the personalities differ from it — and from each other — only in
+/// what they want to clear; the research `personality-style` eval checks each
+/// one's clear histogram shows it.
+fn personality_model(weights: Cc2Weights, handicap: Handicap) -> AiController {
+    search_model(
+        Box::new(BeamPlanner::new(BEAM_WIDTH)),
+        Box::new(Cc2Evaluator::new(weights)),
+        SearchBudget::beam(3),
+        handicap,
     )
 }
 
@@ -181,7 +201,7 @@ impl Default for ModelRegistry {
             "Greedy DT-20",
             "The baseline: one-piece greedy search over the linear DT-20 board \
              evaluator — the original shipped opponent.",
-            AiController::beatable,
+            |handicap| AiController::new(handicap, DEFAULT_AI_SEED),
         ));
 
         // The Tier-2 beam: deterministic multi-ply search over the SAME linear eval.
@@ -190,11 +210,12 @@ impl Default for ModelRegistry {
             "Beam DT-20",
             "Deterministic multi-ply beam search over the same linear evaluator — \
              the Tier-2 architecture jump over greedy.",
-            || {
+            |handicap| {
                 search_model(
                     Box::new(BeamPlanner::new(BEAM_WIDTH)),
                     Box::new(LinearEvaluator::default()),
                     SearchBudget::beam(BEAM_DEPTH),
+                    handicap,
                 )
             },
         ));
@@ -205,11 +226,12 @@ impl Default for ModelRegistry {
             "Beam CC2",
             "Cold Clear 2's board evaluator, ported verbatim, on our beam — the \
              benchmark rival's style on this engine.",
-            || {
+            |handicap| {
                 search_model(
                     Box::new(BeamPlanner::new(BEAM_WIDTH)),
                     Box::new(Cc2Evaluator::default()),
                     SearchBudget::beam(BEAM_DEPTH),
+                    handicap,
                 )
             },
         ));
@@ -221,11 +243,12 @@ impl Default for ModelRegistry {
             "Beam CC2 Attack",
             "CC2's evaluator with board weights climbed for attack-per-piece, on a \
              deeper beam — concentrated B2B Tetris and T-spin offense.",
-            || {
+            |handicap| {
                 search_model(
                     Box::new(BeamPlanner::new(BEAM_WIDTH)),
                     Box::new(Cc2Evaluator::new(Cc2Weights::attack_tuned())),
                     SearchBudget::beam(3), // deeper than the default 2 — attack tuning + combos need lookahead
+                    handicap,
                 )
             },
         ));
@@ -236,7 +259,7 @@ impl Default for ModelRegistry {
             "Best-First Attack",
             "The strongest model: best-first graph search with transposition over \
              the tuned attack evaluator. Also the brain of the web embed.",
-            || AiController::attack(Handicap::default(), DEFAULT_AI_SEED),
+            |handicap| AiController::attack(handicap, DEFAULT_AI_SEED),
         ));
 
         // The perfect-clear hunter: the research crate's coverage planner at
@@ -247,17 +270,17 @@ impl Default for ModelRegistry {
         // hitches), and committed PC lines serve follow-up pieces with zero
         // search, so the full scan runs about once per line, not per piece.
         // Two deliberate deviations from the catalog's shared conventions,
-        // both about the model's character: imperfection 0 (one misplaced
-        // piece kills a ten-piece PC line — the shared 0.12 would erase what
-        // this entry exists to show, and would desync the committed line)
-        // while the human-feel reaction stays; and the custom venue quantum
-        // below.
+        // both about the model's character: no imperfection and no misdrops
+        // at any skill (one misplaced piece kills a ten-piece PC line — the
+        // dials would erase what this entry exists to show, and would desync
+        // the committed line) while the seat's reaction and pace cap stay;
+        // and the custom venue quantum below.
         entries.push(ModelEntry::new(
             "PC Hunter",
             "Perfect-clear builder: covers the bag's possible continuations and \
              keeps boards PC-alive; plays precisely (no imperfection), with a \
              general attack beam as its fallback.",
-            || {
+            |handicap| {
                 AiController::interactive_with(
                     Box::new(PcCoveragePlanner::new(PcCoverageConfig {
                         scenario_cap: 14,
@@ -273,8 +296,9 @@ impl Default for ModelRegistry {
                     Box::new(Cc2Evaluator::new(Cc2Weights::attack_tuned())),
                     SearchBudget::beam(PC_FALLBACK_DEPTH),
                     Handicap {
-                        reaction: Duration::from_millis(200),
                         imperfection: 0.0,
+                        misdrop: 0.0,
+                        ..handicap
                     },
                     PC_QUANTUM,
                 )
@@ -290,7 +314,7 @@ impl Default for ModelRegistry {
             "Transposition-pruned beam over the attack-tuned CC2 evaluator — the \
              search mechanism behind our strongest headless APP bot, at a \
              browser-watchable width and depth.",
-            || tp_attack_model(32, 4),
+            |handicap| tp_attack_model(32, 4, handicap),
         ));
 
         // The literal session champion: TP-beam width 128 / depth 9 over the
@@ -304,7 +328,7 @@ impl Default for ModelRegistry {
             "The strongest headless bot, verbatim: transposition-pruned beam \
              (width 128, depth 9) over the attack-tuned CC2 evaluator, 0.8225 \
              APP held-out. Thinks a beat per move — built to watch, not race.",
-            || tp_attack_model(128, 9),
+            |handicap| tp_attack_model(128, 9, handicap),
         ));
 
         // The deeper champion from the depth-cap study (docs/research-directions.md):
@@ -317,7 +341,7 @@ impl Default for ModelRegistry {
             "A beat deeper than the APP champion: transposition-pruned beam at width 128, \
              depth 12. Slightly safer head-to-head, slightly less raw attack — the \
              heaviest model, built to watch think.",
-            || tp_attack_model(128, 12),
+            |handicap| tp_attack_model(128, 12, handicap),
         ));
 
         // The efficient narrow-deep config from the scaling study: a small survival-width
@@ -330,7 +354,35 @@ impl Default for ModelRegistry {
             "Narrow but deep (width 16, depth 12) — the scaling study's efficient corner. \
              Searches deep at a fraction of the champion's cost, so it plays snappily; \
              less raw attack than the wide bots.",
-            || tp_attack_model(16, 12),
+            |handicap| tp_attack_model(16, 12, handicap),
+        ));
+
+        // The playstyle personalities: distinct opponents rather than stronger
+        // ones. Each is the attack weights bent toward one style (numbers and
+        // the style check in the research `personality-style` run record).
+        entries.push(ModelEntry::new(
+            "T-Spin Specialist",
+            "Builds T-slots and cashes them: T-spin rewards and slot value doubled, \
+             small plain clears discouraged. Over half its clears are T-spins.",
+            |handicap| personality_model(Cc2Weights::t_spin_specialist(), handicap),
+        ));
+        entries.push(ModelEntry::new(
+            "4-Wide Combo",
+            "Chains clears: combos priced far above single big clears, with the \
+             stack let up high enough to feed a long chain.",
+            |handicap| personality_model(Cc2Weights::four_wide(), handicap),
+        ));
+        entries.push(ModelEntry::new(
+            "Downstacker",
+            "Stays low and clean: holes weigh more than anything it could send, so \
+             it digs out fast and attacks little — a patient, hard-to-kill opponent.",
+            |handicap| personality_model(Cc2Weights::downstacker(), handicap),
+        ));
+        entries.push(ModelEntry::new(
+            "Tetris Classic",
+            "The old-school style: one deep well, four lines at a time, no spins. \
+             Nine in ten of its cleared lines come from tetrises.",
+            |handicap| personality_model(Cc2Weights::tetris_only(), handicap),
         ));
 
         // The deployed learned evaluator (tetr-valuenet): a single-board value
@@ -345,11 +397,12 @@ impl Default for ModelRegistry {
                 "A learned board value net (16×32×32 conv, self-play \
                  replay-buffer trained) composed with the CC2 attack reward — \
                  the strongest stable learned evaluator. Pure-Rust, no GPU.",
-                move || {
+                move |handicap| {
                     search_model(
                         Box::new(BeamPlanner::new(BEAM_WIDTH)),
                         Box::new(tetr_valuenet::DeployNet::new(value_net.clone())),
                         SearchBudget::beam(BEAM_DEPTH),
+                        handicap,
                     )
                 },
             ));
@@ -392,16 +445,37 @@ mod tests {
         assert_eq!(reg.detail(usize::MAX), "", "out of range reads empty");
     }
 
+    /// A wrapped detail literal needs its `\` continuation, or the source
+    /// indent lands mid-sentence in the picker's detail pane.
+    #[test]
+    fn details_have_no_wrap_gaps() {
+        let reg = ModelRegistry::default();
+        for i in 0..reg.len() {
+            let detail = reg.detail(i);
+            assert!(
+                !detail.contains("  "),
+                "entry {i}'s detail has a gap: {detail:?}"
+            );
+        }
+    }
+
     #[test]
     fn every_entry_builds_a_controller() {
+        use crate::ai::SkillPreset;
+
         // Each factory must construct without panicking — the registry-level smoke
         // test that a catalog edit cannot ship an unbuildable model.
         let reg = ModelRegistry::default();
         for i in 0..reg.len() {
-            assert!(reg.build(i).is_some(), "entry {i} failed to build");
+            for skill in SkillPreset::ALL {
+                assert!(
+                    reg.build(i, skill.handicap()).is_some(),
+                    "entry {i} failed to build at {skill:?}"
+                );
+            }
         }
         assert!(
-            reg.build(usize::MAX).is_none(),
+            reg.build(usize::MAX, Handicap::default()).is_none(),
             "out of range builds nothing"
         );
     }
@@ -0,0 +1,545 @@
+//! The daily challenge: one deal and one ruleset per UTC day, the same for
+//! every player.
+//!
+//! The day ([`Day`], counted from the Unix epoch) fixes everything: the engine
+//! seed, and which rules it plays — Sprint, Ultra, Dig 10 (clean and messy in
+//! turn) or a puzzle from the catalog, rotating day by day. A day allows one
+//! official attempt, spent the moment it is dealt (quitting forfeits it), and
+//! any number of practice runs on the same deal. Every run files on the day's
+//! own board in the [`DailyBook`], never on the variant leaderboards, and the
+//! book keeps the last month of days as the player's history.
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+
+use crate::features::high_scores::format_time;
+use crate::puzzle::PuzzleCatalog;
+use crate::session::SessionMode;
+use crate::storage::{Storage, keys};
+use crate::variant::{DIG_LINES, ScoreKind, Variant};
+
+/// Runs kept on one day's board (the official one always stays).
+pub const MAX_ENTRIES_PER_DAY: usize = 10;
+
+/// Days the book keeps before the oldest is dropped.
+pub const MAX_DAYS: usize = 30;
+
+/// The first day there was a daily challenge: Daily #1, 2026-01-01.
+const FIRST_DAY: Day = Day(20_454);
+
+/// A UTC calendar day, as days since 1970-01-01.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Day(pub i64);
+
+impl Day {
+    /// Today, by the system clock (a clock set before 1970 reads as day 0).
+    pub fn today() -> Self {
+        let since_epoch = web_time::SystemTime::now()
+            .duration_since(web_time::UNIX_EPOCH)
+            .unwrap_or_default();
+        Self((since_epoch.as_secs() / 86_400) as i64)
+    }
+
+    /// The proleptic Gregorian `(year, month, day)` of this day.
+    pub fn date(self) -> (i64, u32, u32) {
+        // Howard Hinnant's `civil_from_days`: eras of 400 years, each
+        // starting on March 1st so the leap day ends the year.
+        let z = self.0 + 719_468;
+        let era = z.div_euclid(146_097);
+        let day_of_era = z.rem_euclid(146_097);
+        let year_of_era =
+            (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let month_index = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+        let month = if month_index < 10 {
+            month_index + 3
+        } else {
+            month_index - 9
+        } as u32;
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        (year, month, day)
+    }
+
+    /// The challenge's running number: Daily #1 was 2026-01-01.
+    pub fn number(self) -> i64 {
+        self.0 - FIRST_DAY.0 + 1
+    }
+}
+
+impl std::fmt::Display for Day {
+    /// `YYYY-MM-DD`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (year, month, day) = self.date();
+        write!(f, "{year:04}-{month:02}-{day:02}")
+    }
+}
+
+/// What a day plays.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DailyRules {
+    /// A solo variant on the day's deal.
+    Solo(Variant),
+    /// A puzzle from the [`PuzzleCatalog`], by index.
+    Puzzle(usize),
+}
+
+impl DailyRules {
+    /// The session mode that plays these rules.
+    pub fn mode(self) -> SessionMode {
+        match self {
+            DailyRules::Solo(variant) => SessionMode::Solo { variant },
+            DailyRules::Puzzle(index) => SessionMode::Puzzle { index },
+        }
+    }
+
+    /// How runs rank: a puzzle is a race to the solve.
+    pub fn score_kind(self) -> ScoreKind {
+        match self {
+            DailyRules::Solo(variant) => variant.def().score_kind,
+            DailyRules::Puzzle(_) => ScoreKind::Time,
+        }
+    }
+
+    /// The rules' name: the variant's, or `Puzzle: <name>`.
+    pub fn label(self, puzzles: &PuzzleCatalog) -> String {
+        match self {
+            DailyRules::Solo(variant) => variant.display_name().to_string(),
+            DailyRules::Puzzle(index) => puzzles.get(index).map_or_else(
+                || "Puzzle".to_string(),
+                |puzzle| format!("Puzzle: {}", puzzle.name),
+            ),
+        }
+    }
+
+    /// A run's result as these rules read it: a time, a score, or how far
+    /// an unfinished race got.
+    pub fn result(self, entry: &DailyEntry) -> String {
+        match (self, self.score_kind()) {
+            (_, ScoreKind::Score | ScoreKind::Grade) => format!("{} points", entry.score),
+            (_, ScoreKind::Time) if entry.completed => format_time(entry.time_seconds),
+            (DailyRules::Puzzle(_), ScoreKind::Time) => "unsolved".to_string(),
+            (DailyRules::Solo(_), ScoreKind::Time) => format!("DNF, {} lines", entry.lines),
+        }
+    }
+}
+
+/// One day's challenge: its deal and its rules.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DailyChallenge {
+    pub day: Day,
+    pub seed: u64,
+    pub rules: DailyRules,
+}
+
+impl DailyChallenge {
+    /// `day`'s challenge, picking its puzzle (on a puzzle day) from a catalog
+    /// of `puzzle_count`. Every player with the same catalog gets the same one.
+    pub fn for_day(day: Day, puzzle_count: usize) -> Self {
+        let round = day.0.div_euclid(4);
+        let rules = match day.0.rem_euclid(4) {
+            0 => DailyRules::Solo(Variant::Sprint),
+            1 => DailyRules::Solo(Variant::Ultra),
+            2 => DailyRules::Solo(Variant::Dig {
+                lines: DIG_LINES[0],
+                messy: round % 2 == 1,
+            }),
+            _ if puzzle_count == 0 => DailyRules::Solo(Variant::Sprint),
+            _ => DailyRules::Puzzle(round.rem_euclid(puzzle_count as i64) as usize),
+        };
+        Self {
+            day,
+            seed: day_seed(day),
+            rules,
+        }
+    }
+
+    /// The line a player pastes to compare with friends: the challenge, the
+    /// rules and the result, marked when it was only practice.
+    pub fn share_text(&self, puzzles: &PuzzleCatalog, entry: &DailyEntry) -> String {
+        format!(
+            "TETR ONLINE Daily #{} ({}) - {} - {}{}",
+            self.day.number(),
+            self.day,
+            self.rules.label(puzzles),
+            self.rules.result(entry),
+            if entry.official { "" } else { " (practice)" }
+        )
+    }
+}
+
+/// The day's engine seed: the day number through a SplitMix64 finalizer, so
+/// neighbouring days deal unrelated bags.
+fn day_seed(day: Day) -> u64 {
+    let mut z = (day.0 as u64) ^ 0x6461_696C_7974_6574; // "dailytet"
+    z = z.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+/// One finished daily run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DailyEntry {
+    pub score: usize,
+    pub time_seconds: f32,
+    pub lines: usize,
+    /// The run met its goal (the race finished, the puzzle solved, the time
+    /// limit reached).
+    pub completed: bool,
+    /// The day's official attempt rather than practice.
+    pub official: bool,
+}
+
+impl DailyEntry {
+    /// Whether this run places above `other` under `kind`: the higher score,
+    /// or for a race the finish first, then the faster time (unfinished
+    /// races by how far they got).
+    fn beats(&self, other: &DailyEntry, kind: ScoreKind) -> bool {
+        match kind {
+            ScoreKind::Score | ScoreKind::Grade => self.score > other.score,
+            ScoreKind::Time if self.completed != other.completed => self.completed,
+            ScoreKind::Time if self.completed => self.time_seconds < other.time_seconds,
+            ScoreKind::Time => self.lines > other.lines,
+        }
+    }
+}
+
+/// One day in the book.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DailyDay {
+    /// The official attempt has been dealt (with no official entry, it was
+    /// abandoned: forfeit).
+    pub official_begun: bool,
+    /// The day's runs, best first.
+    entries: Vec<DailyEntry>,
+}
+
+impl DailyDay {
+    /// The day's board, best first.
+    pub fn board(&self) -> &[DailyEntry] {
+        &self.entries
+    }
+
+    /// The official attempt's result, once it has finished.
+    pub fn official(&self) -> Option<&DailyEntry> {
+        self.entries.iter().find(|entry| entry.official)
+    }
+
+    /// The run worth sharing: the official one, else the best practice.
+    pub fn shareable(&self) -> Option<&DailyEntry> {
+        self.official().or(self.entries.first())
+    }
+}
+
+/// Every day the player has taken the challenge, per day: the official
+/// attempt and the day's board.
+#[derive(Resource, Clone, Debug, Default, PartialEq)]
+pub struct DailyBook {
+    days: BTreeMap<Day, DailyDay>,
+}
+
+impl DailyBook {
+    pub fn day(&self, day: Day) -> Option<&DailyDay> {
+        self.days.get(&day)
+    }
+
+    /// Whether `day`'s official attempt is still to be taken.
+    pub fn official_open(&self, day: Day) -> bool {
+        self.day(day).is_none_or(|record| !record.official_begun)
+    }
+
+    /// Spend `day`'s official attempt; `false` if it was already spent.
+    pub fn begin_official(&mut self, day: Day) -> bool {
+        if !self.official_open(day) {
+            return false;
+        }
+        self.day_mut(day).official_begun = true;
+        true
+    }
+
+    /// File a run on the challenge's board; its rank there, or `None` if it
+    /// did not place.
+    pub fn record(&mut self, challenge: &DailyChallenge, entry: DailyEntry) -> Option<usize> {
+        let kind = challenge.rules.score_kind();
+        let record = self.day_mut(challenge.day);
+        let rank = record
+            .entries
+            .iter()
+            .position(|other| entry.beats(other, kind))
+            .unwrap_or(record.entries.len());
+        record.entries.insert(rank, entry);
+        // Trim from the bottom, sparing the official run.
+        while record.entries.len() > MAX_ENTRIES_PER_DAY {
+            let Some(last) = record.entries.iter().rposition(|entry| !entry.official) else {
+                break;
+            };
+            record.entries.remove(last);
+        }
+        (record.entries.get(rank) == Some(&entry)).then_some(rank)
+    }
+
+    /// The days on record, newest first.
+    pub fn history(&self) -> impl Iterator<Item = (Day, &DailyDay)> {
+        self.days.iter().rev().map(|(&day, record)| (day, record))
+    }
+
+    /// `day`'s record, opened on first use (the oldest day makes room).
+    fn day_mut(&mut self, day: Day) -> &mut DailyDay {
+        if !self.days.contains_key(&day) && self.days.len() >= MAX_DAYS {
+            self.days.pop_first();
+        }
+        self.days.entry(day).or_default()
+    }
+
+    /// Restore from storage (missing or empty: no days).
+    pub fn load(storage: &dyn Storage) -> Self {
+        storage
+            .load(keys::DAILY)
+            .map(|blob| Self::decode(&blob))
+            .unwrap_or_default()
+    }
+
+    pub fn persist(&self, storage: &dyn Storage) {
+        storage.save(keys::DAILY, &self.encode());
+    }
+
+    /// One line per fact: `<day> begun` once the official attempt is dealt,
+    /// then `<day> <o|p> <score> <time_seconds> <lines> <0|1>` per run in
+    /// board order (`o` official, `p` practice; the last field is
+    /// `completed`).
+    fn encode(&self) -> String {
+        use std::fmt::Write as _;
+
+        let mut out = String::new();
+        for (day, record) in &self.days {
+            // Writing into a `String` is infallible.
+            if record.official_begun {
+                let _ = writeln!(out, "{} begun", day.0);
+            }
+            for entry in &record.entries {
+                let _ = writeln!(
+                    out,
+                    "{} {} {} {} {} {}",
+                    day.0,
+                    if entry.official { "o" } else { "p" },
+                    entry.score,
+                    entry.time_seconds,
+                    entry.lines,
+                    u8::from(entry.completed),
+                );
+            }
+        }
+        out
+    }
+
+    /// Parse [`encode`](Self::encode)'s blob, skipping lines that don't.
+    fn decode(blob: &str) -> Self {
+        let mut book = Self::default();
+        for line in blob.lines() {
+            let mut fields = line.split_whitespace();
+            let Some(day) = fields.next().and_then(|day| day.parse().ok()).map(Day) else {
+                continue;
+            };
+            let parsed = match fields.next() {
+                Some("begun") => Some(None),
+                Some(kind @ ("o" | "p")) => parse_entry(&mut fields, kind == "o").map(Some),
+                _ => None,
+            };
+            // Reject trailing garbage rather than half-read a newer format.
+            let (Some(parsed), None) = (parsed, fields.next()) else {
+                continue;
+            };
+            let record = book.day_mut(day);
+            match parsed {
+                None => record.official_begun = true,
+                Some(entry) if record.entries.len() < MAX_ENTRIES_PER_DAY => {
+                    record.entries.push(entry);
+                }
+                Some(_) => {}
+            }
+        }
+        book
+    }
+}
+
+fn parse_entry<'a>(
+    fields: &mut impl Iterator<Item = &'a str>,
+    official: bool,
+) -> Option<DailyEntry> {
+    Some(DailyEntry {
+        score: fields.next()?.parse().ok()?,
+        time_seconds: fields.next()?.parse().ok()?,
+        lines: fields.next()?.parse().ok()?,
+        completed: match fields.next()? {
+            "0" => false,
+            "1" => true,
+            _ => return None,
+        },
+        official,
+    })
+}
+
+/// The daily run being played, inserted by the daily screen and dropped when
+/// the session is left. The session deals its seed and files the result in
+/// the [`DailyBook`]; once the official attempt is spent, a retry is practice.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DailyAttempt {
+    pub challenge: DailyChallenge,
+    pub official: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(score: usize, time: f32, lines: usize, completed: bool) -> DailyEntry {
+        DailyEntry {
+            score,
+            time_seconds: time,
+            lines,
+            completed,
+            official: false,
+        }
+    }
+
+    #[test]
+    fn days_read_as_utc_dates() {
+        assert_eq!(Day(0).to_string(), "1970-01-01");
+        assert_eq!(Day(11_016).to_string(), "2000-02-29");
+        assert_eq!(Day(20_745).to_string(), "2026-10-19");
+        assert_eq!(Day(-1).to_string(), "1969-12-31");
+        assert_eq!(FIRST_DAY.to_string(), "2026-01-01");
+        assert_eq!(Day(20_745).number(), 292);
+    }
+
+    #[test]
+    fn the_rules_rotate_with_the_day_and_the_deal_is_fixed() {
+        let rules: Vec<DailyRules> = (20_744..20_752)
+            .map(|day| DailyChallenge::for_day(Day(day), 4).rules)
+            .collect();
+        assert_eq!(
+            rules,
+            [
+                DailyRules::Solo(Variant::Sprint),
+                DailyRules::Solo(Variant::Ultra),
+                DailyRules::Solo(Variant::Dig {
+                    lines: 10,
+                    messy: false
+                }),
+                DailyRules::Puzzle(2),
+                DailyRules::Solo(Variant::Sprint),
+                DailyRules::Solo(Variant::Ultra),
+                DailyRules::Solo(Variant::Dig {
+                    lines: 10,
+                    messy: true
+                }),
+                DailyRules::Puzzle(3),
+            ]
+        );
+        let today = DailyChallenge::for_day(Day(20_745), 4);
+        assert_eq!(today, DailyChallenge::for_day(Day(20_745), 4));
+        assert_ne!(today.seed, DailyChallenge::for_day(Day(20_746), 4).seed);
+        // No puzzles to pick from: a puzzle day races instead.
+        assert_eq!(
+            DailyChallenge::for_day(Day(20_747), 0).rules,
+            DailyRules::Solo(Variant::Sprint)
+        );
+    }
+
+    #[test]
+    fn the_official_attempt_is_spent_once() {
+        let mut book = DailyBook::default();
+        let day = Day(20_745);
+        assert!(book.official_open(day));
+        assert!(book.begin_official(day));
+        assert!(!book.official_open(day));
+        assert!(!book.begin_official(day));
+        // Begun but never finished: forfeit, with nothing to share.
+        assert!(book.day(day).unwrap().official().is_none());
+        assert!(book.official_open(Day(20_746)));
+    }
+
+    #[test]
+    fn a_race_board_ranks_finishes_by_time_and_keeps_the_official_run() {
+        let sprint = DailyChallenge::for_day(Day(20_744), 4);
+        let mut book = DailyBook::default();
+        let official = DailyEntry {
+            official: true,
+            ..entry(0, 90.0, 40, true)
+        };
+        assert_eq!(book.record(&sprint, official), Some(0));
+        assert_eq!(book.record(&sprint, entry(0, 60.0, 40, true)), Some(0));
+        assert_eq!(book.record(&sprint, entry(0, 30.0, 22, false)), Some(2));
+        for _ in 0..MAX_ENTRIES_PER_DAY {
+            book.record(&sprint, entry(0, 80.0, 40, true));
+        }
+        let record = book.day(sprint.day).unwrap();
+        assert_eq!(record.board().len(), MAX_ENTRIES_PER_DAY);
+        assert_eq!(record.board()[0].time_seconds, 60.0);
+        assert_eq!(record.official(), Some(&official));
+        assert_eq!(record.shareable(), Some(&official));
+        // The unfinished run fell off the bottom.
+        assert!(record.board().iter().all(|entry| entry.completed));
+        assert_eq!(book.record(&sprint, entry(0, 10.0, 3, false)), None);
+    }
+
+    #[test]
+    fn the_book_round_trips_and_skips_bad_lines() {
+        let mut book = DailyBook::default();
+        let ultra = DailyChallenge::for_day(Day(20_745), 4);
+        book.begin_official(ultra.day);
+        book.record(
+            &ultra,
+            DailyEntry {
+                official: true,
+                ..entry(41_000, 120.0, 70, true)
+            },
+        );
+        book.record(&ultra, entry(52_500, 120.0, 81, true));
+        book.begin_official(Day(20_744));
+        let blob = book.encode();
+        assert_eq!(DailyBook::decode(&blob), book);
+
+        let garbled = format!("{blob}x begun\n20745 q 1 2 3 1\n20745 p 1 2 3 1 extra\n\n");
+        assert_eq!(DailyBook::decode(&garbled), book);
+        let days: Vec<Day> = book.history().map(|(day, _)| day).collect();
+        assert_eq!(days, [Day(20_745), Day(20_744)]);
+    }
+
+    #[test]
+    fn the_book_keeps_a_month_of_days() {
+        let mut book = DailyBook::default();
+        for day in 0..(MAX_DAYS as i64 + 5) {
+            book.begin_official(Day(day));
+        }
+        assert_eq!(book.history().count(), MAX_DAYS);
+        assert!(book.day(Day(4)).is_none());
+        assert!(book.day(Day(5)).is_some());
+    }
+
+    #[test]
+    fn the_share_text_names_the_day_rules_and_result() {
+        let puzzles = PuzzleCatalog::default();
+        let sprint = DailyChallenge::for_day(Day(20_744), puzzles.0.len());
+        let official = DailyEntry {
+            official: true,
+            ..entry(0, 62.5, 40, true)
+        };
+        assert_eq!(
+            sprint.share_text(&puzzles, &official),
+            "TETR ONLINE Daily #291 (2026-10-18) - Sprint - 1:02.500"
+        );
+        let ultra = DailyChallenge::for_day(Day(20_745), puzzles.0.len());
+        assert_eq!(
+            ultra.share_text(&puzzles, &entry(38_200, 120.0, 60, true)),
+            "TETR ONLINE Daily #292 (2026-10-19) - Ultra - 38200 points (practice)"
+        );
+        assert!(
+            sprint
+                .share_text(&puzzles, &entry(0, 50.0, 12, false))
+                .ends_with("DNF, 12 lines (practice)")
+        );
+    }
+}
@@ -0,0 +1,409 @@
+//! The board editor's position: a hand-built board, hold slot and queue.
+//!
+//! [`EditorPosition`] is the whole 10×40 grid — the visible field and the
+//! buffer above it — with a piece colour or garbage per cell, plus the hold
+//! piece and the queue to deal before the seeded bags. [`validate`] rejects
+//! positions the engine could never have reached or could not start from (a
+//! full row, a row floating over an empty one, a blocked spawn), and
+//! [`scenario`] hands a valid one to the engine as a [`Scenario`], the same
+//! start-of-game seam puzzles use, so a practice run or the AI's game from an
+//! edited position re-simulates exactly from `(config, seed, inputs)`.
+//!
+//! A position travels as a one-line text code ([`encode`] / [`decode`]):
+//!
+//! ```text
+//! tetr1:T:IOSZ:9X./4X.5X/T9.
+//! ```
+//!
+//! `tetr1`, the hold letter (`-` for none), the queue letters, then the rows
+//! from the floor up to the highest filled one, `/`-separated. A row is its
+//! cells left to right (`.` empty, `X` garbage, a piece letter for a coloured
+//! mino), a run of two or more alike written once with its length in
+//! front. The code
+//! is also what the [`EDITOR_SLOTS`] save slots store through the
+//! [`Storage`] trait (`keys::POSITION_PREFIX` plus the slot number).
+//!
+//! [`validate`]: EditorPosition::validate
+//! [`scenario`]: EditorPosition::scenario
+//! [`encode`]: EditorPosition::encode
+//! [`decode`]: EditorPosition::decode
+
+use bevy::prelude::*;
+
+use crate::engine::{CellKind, Piece, PieceType, Scenario};
+use crate::puzzle::piece_for;
+use crate::storage::{Storage, keys};
+
+/// Columns in the editor's grid (the standard field).
+pub const EDITOR_WIDTH: usize = 10;
+
+/// Rows in the editor's grid: the visible field and the buffer above it.
+pub const EDITOR_HEIGHT: usize = 40;
+
+/// Rows of the visible field; the engine's pieces spawn just above them.
+pub const EDITOR_VISIBLE: usize = 20;
+
+/// Save slots the editor offers.
+pub const EDITOR_SLOTS: usize = 4;
+
+/// The code's leading tag (and format version).
+const CODE_TAG: &str = "tetr1";
+
+/// The position being edited. Kept across visits to the editor, and read by
+/// the session when Practice or the AI is launched from it.
+#[derive(Resource, Clone, Debug, PartialEq)]
+pub struct EditorPosition {
+    /// Row-major from the floor up, `EDITOR_WIDTH` cells per row.
+    cells: Vec<Option<CellKind>>,
+    pub hold: Option<PieceType>,
+    pub queue: Vec<PieceType>,
+}
+
+impl Default for EditorPosition {
+    fn default() -> Self {
+        Self {
+            cells: vec![None; EDITOR_WIDTH * EDITOR_HEIGHT],
+            hold: None,
+            queue: Vec::new(),
+        }
+    }
+}
+
+impl EditorPosition {
+    /// The cell at column `x`, row `y` (from the floor); `None` is empty, as
+    /// is anything off the grid.
+    pub fn cell(&self, x: usize, y: usize) -> Option<CellKind> {
+        if x < EDITOR_WIDTH && y < EDITOR_HEIGHT {
+            self.cells[y * EDITOR_WIDTH + x]
+        } else {
+            None
+        }
+    }
+
+    /// Paint (or with `None`, erase) a cell; off-grid is ignored.
+    pub fn set(&mut self, x: usize, y: usize, cell: Option<CellKind>) {
+        if x < EDITOR_WIDTH && y < EDITOR_HEIGHT {
+            self.cells[y * EDITOR_WIDTH + x] = cell;
+        }
+    }
+
+    /// Empty the grid, keeping the hold and queue.
+    pub fn clear_board(&mut self) {
+        self.cells.fill(None);
+    }
+
+    fn row_filled(&self, y: usize) -> usize {
+        (0..EDITOR_WIDTH)
+            .filter(|&x| self.cell(x, y).is_some())
+            .count()
+    }
+
+    /// Whether the engine could start from this position; the problem with
+    /// it otherwise.
+    pub fn validate(&self) -> Result<(), String> {
+        let mut empty_below = None;
+        for y in 0..EDITOR_HEIGHT {
+            match self.row_filled(y) {
+                // A full row would never clear: the engine only clears on a
+                // lock.
+                EDITOR_WIDTH => return Err(format!("row {} is full", y + 1)),
+                0 => {
+                    empty_below.get_or_insert(y);
+                }
+                // Pieces rest on what is under them: nothing stands on an
+                // empty row.
+                _ => {
+                    if let Some(empty) = empty_below {
+                        return Err(format!(
+                            "row {} floats above the empty row {}",
+                            y + 1,
+                            empty + 1
+                        ));
+                    }
+                }
+            }
+        }
+        if spawn_cells().any(|(x, y)| self.cell(x, y).is_some()) {
+            return Err("the spawn rows are blocked".to_string());
+        }
+        Ok(())
+    }
+
+    /// The engine start for this position: its cells, hold and queue.
+    pub fn scenario(&self) -> Scenario {
+        let cells = (0..EDITOR_HEIGHT)
+            .flat_map(|y| (0..EDITOR_WIDTH).map(move |x| (x, y)))
+            .filter_map(|(x, y)| self.cell(x, y).map(|cell| (x as isize, y as isize, cell)))
+            .collect();
+        Scenario {
+            cells,
+            hold: self.hold,
+            sequence: self.queue.clone(),
+            garbage: 0,
+        }
+    }
+
+    /// The position as a one-line text code (see the module doc).
+    pub fn encode(&self) -> String {
+        let hold = self.hold.map_or('-', piece_letter);
+        let queue: String = self.queue.iter().copied().map(piece_letter).collect();
+        let top = (0..EDITOR_HEIGHT)
+            .rev()
+            .find(|&y| self.row_filled(y) > 0)
+            .map_or(0, |y| y + 1);
+        let rows: Vec<String> = (0..top)
+            .map(|y| {
+                let mut row = String::new();
+                let mut x = 0;
+                while x < EDITOR_WIDTH {
+                    let c = cell_letter(self.cell(x, y));
+                    let run = (x..EDITOR_WIDTH)
+                        .take_while(|&i| cell_letter(self.cell(i, y)) == c)
+                        .count();
+                    if run > 1 {
+                        row.push_str(&run.to_string());
+                    }
+                    row.push(c);
+                    x += run;
+                }
+                row
+            })
+            .collect();
+        format!("{CODE_TAG}:{hold}:{queue}:{}", rows.join("/"))
+    }
+
+    /// Read a text code back. The position is not [validated](Self::validate):
+    /// a code for an unfinished position loads so it can be finished.
+    pub fn decode(code: &str) -> Result<Self, String> {
+        let mut parts = code.trim().split(':');
+        if parts.next() != Some(CODE_TAG) {
+            return Err(format!(
+                "not a position code (it should start with {CODE_TAG}:)"
+            ));
+        }
+        let (Some(hold), Some(queue), Some(rows), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err("a position code has four parts".to_string());
+        };
+        let hold = match hold {
+            "-" => None,
+            _ => Some(
+                single_letter(hold)
+                    .and_then(piece_for)
+                    .ok_or_else(|| format!("unknown hold piece '{hold}'"))?,
+            ),
+        };
+        let queue = queue
+            .chars()
+            .map(|c| piece_for(c).ok_or_else(|| format!("unknown piece '{c}' in the queue")))
+            .collect::<Result<_, _>>()?;
+        let mut position = Self {
+            hold,
+            queue,
+            ..Self::default()
+        };
+        let rows: Vec<&str> = if rows.is_empty() {
+            Vec::new()
+        } else {
+            rows.split('/').collect()
+        };
+        if rows.len() > EDITOR_HEIGHT {
+            return Err(format!("the board has more than {EDITOR_HEIGHT} rows"));
+        }
+        for (y, row) in rows.into_iter().enumerate() {
+            let mut x = 0;
+            let mut run = String::new();
+            for c in row.chars() {
+                if c.is_ascii_digit() {
+                    run.push(c);
+                    continue;
+                }
+                let cell = match c {
+                    '.' => None,
+                    'X' => Some(CellKind::Garbage),
+                    piece => {
+                        Some(CellKind::Some(piece_for(piece).ok_or_else(|| {
+                            format!("unknown cell '{c}' in row {}", y + 1)
+                        })?))
+                    }
+                };
+                let count = if run.is_empty() {
+                    1
+                } else {
+                    run.parse::<usize>().map_err(|error| error.to_string())?
+                };
+                run.clear();
+                if x + count > EDITOR_WIDTH {
+                    return Err(format!("row {} is wider than {EDITOR_WIDTH}", y + 1));
+                }
+                for _ in 0..count {
+                    position.set(x, y, cell);
+                    x += 1;
+                }
+            }
+            if x != EDITOR_WIDTH || !run.is_empty() {
+                return Err(format!("row {} is not {EDITOR_WIDTH} cells wide", y + 1));
+            }
+        }
+        Ok(position)
+    }
+
+    /// The position saved in `slot`, if there is one that reads back.
+    pub fn load(storage: &dyn Storage, slot: usize) -> Option<Self> {
+        let code = storage.load(&slot_key(slot))?;
+        match Self::decode(&code) {
+            Ok(position) => Some(position),
+            Err(error) => {
+                warn!("editor slot {}: {error}", slot + 1);
+                None
+            }
+        }
+    }
+
+    /// Save the position in `slot`.
+    pub fn persist(&self, storage: &dyn Storage, slot: usize) {
+        storage.save(&slot_key(slot), &self.encode());
+    }
+}
+
+/// Where a new piece appears: every cell any piece covers at its spawn, on
+/// the standard field. The editor shades them; a position must keep them
+/// clear.
+pub fn spawn_cells() -> impl Iterator<Item = (usize, usize)> {
+    PieceType::ALL.into_iter().flat_map(|piece_type| {
+        let piece = Piece::from(piece_type);
+        let (ox, oy) = piece.spawn_coords(EDITOR_WIDTH, EDITOR_VISIBLE);
+        piece
+            .cells()
+            .map(|(dx, dy)| ((ox + dx) as usize, (oy + dy) as usize))
+    })
+}
+
+fn slot_key(slot: usize) -> String {
+    format!("{}{slot}", keys::POSITION_PREFIX)
+}
+
+/// A piece's letter, as codes and puzzle files write it.
+pub fn piece_letter(piece_type: PieceType) -> char {
+    match piece_type {
+        PieceType::I => 'I',
+        PieceType::O => 'O',
+        PieceType::T => 'T',
+        PieceType::S => 'S',
+        PieceType::Z => 'Z',
+        PieceType::J => 'J',
+        PieceType::L => 'L',
+    }
+}
+
+fn cell_letter(cell: Option<CellKind>) -> char {
+    match cell {
+        Some(CellKind::Some(piece_type)) => piece_letter(piece_type),
+        Some(_) => 'X',
+        None => '.',
+    }
+}
+
+fn single_letter(text: &str) -> Option<char> {
+    let mut chars = text.chars();
+    let c = chars.next()?;
+    chars.next().is_none().then_some(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> EditorPosition {
+        let mut position = EditorPosition {
+            hold: Some(PieceType::T),
+            queue: vec![PieceType::I, PieceType::O, PieceType::S],
+            ..default()
+        };
+        for x in 0..9 {
+            position.set(x, 0, Some(CellKind::Garbage));
+        }
+        for x in [0, 1, 2, 3, 5, 6, 7, 8, 9] {
+            position.set(x, 1, Some(CellKind::Some(PieceType::Z)));
+        }
+        position.set(0, 2, Some(CellKind::Some(PieceType::L)));
+        position
+    }
+
+    #[test]
+    fn a_position_round_trips_through_its_code() {
+        let position = sample();
+        let code = position.encode();
+        assert_eq!(code, "tetr1:T:IOS:9X./4Z.5Z/L9.");
+        assert_eq!(EditorPosition::decode(&code), Ok(position));
+        let empty = EditorPosition::default();
+        assert_eq!(empty.encode(), "tetr1:-::");
+        assert_eq!(EditorPosition::decode(&empty.encode()), Ok(empty));
+    }
+
+    #[test]
+    fn decode_rejects_malformed_codes() {
+        for code in [
+            "tetr2:-::",
+            "tetr1:-:",
+            "tetr1:Q::",
+            "tetr1:-:IQ:",
+            "tetr1:-::9X",
+            "tetr1:-::11X",
+            "tetr1:-::9Y.",
+        ] {
+            assert!(EditorPosition::decode(code).is_err(), "{code}");
+        }
+    }
+
+    #[test]
+    fn validation_rejects_unreachable_positions() {
+        assert_eq!(sample().validate(), Ok(()));
+
+        let mut full = sample();
+        full.set(9, 0, Some(CellKind::Garbage));
+        assert_eq!(full.validate(), Err("row 1 is full".to_string()));
+
+        let mut floating = sample();
+        floating.set(4, 5, Some(CellKind::Garbage));
+        assert_eq!(
+            floating.validate(),
+            Err("row 6 floats above the empty row 4".to_string())
+        );
+
+        let mut blocked = EditorPosition::default();
+        for y in 0..=EDITOR_VISIBLE {
+            blocked.set(4, y, Some(CellKind::Garbage));
+        }
+        assert_eq!(
+            blocked.validate(),
+            Err("the spawn rows are blocked".to_string())
+        );
+    }
+
+    #[test]
+    fn the_scenario_starts_the_engine_on_the_position() {
+        let position = sample();
+        let config = crate::engine::EngineConfig {
+            scenario: Some(position.scenario()),
+            ..default()
+        };
+        let mut engine = crate::engine::Engine::new(config, 1);
+        engine.step(crate::engine::InputFrame::default());
+        let snapshot = engine.snapshot();
+        assert_eq!(snapshot.board_cells.len(), 19);
+        assert_eq!(snapshot.hold, Some(PieceType::T));
+        assert_eq!(
+            snapshot.active.map(|active| active.piece_type),
+            Some(PieceType::I)
+        );
+        assert_eq!(snapshot.next_queue[..2], [PieceType::O, PieceType::S]);
+    }
+
+    #[test]
+    fn the_spawn_cells_sit_just_above_the_field() {
+        let rows: std::collections::BTreeSet<usize> = spawn_cells().map(|(_, y)| y).collect();
+        assert_eq!(rows.into_iter().collect::<Vec<_>>(), [20, 21]);
+    }
+}
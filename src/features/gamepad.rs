@@ -0,0 +1,364 @@
+//! Gamepad feature: pads as a second input device, in play and in menus.
+//!
+//! Bevy's gamepad backend spawns one [`Gamepad`] entity per connected pad.
+//! This feature:
+//!
+//! * assigns pads to players ([`PadAssignments`]): the first pad to connect
+//!   is Player 1's and the second Player 2's, the Options gamepad page swaps
+//!   them, and a disconnect frees the slot for the next pad;
+//! * turns pads into the same [`RawKeyboardFrame`] the keyboard produces
+//!   ([`gamepad_input_from_binds`]), so a pad drives a seat through its
+//!   `KeyboardController` — one DAS path for both devices;
+//! * folds every pad into [`MenuPad`] each frame, the menu edges the
+//!   [`focus`](crate::ui::focus) helpers read next to the keyboard.
+//!
+//! The left stick mirrors the D-pad's directions once it leans past
+//! [`GameSettings::stick_threshold`]; a [`StickLatch`] remembers last frame's
+//! lean so a lean reports one press edge, like a button.
+
+use bevy::input::InputSystems;
+use bevy::prelude::*;
+
+use crate::player::RawKeyboardFrame;
+use crate::settings::{GameAction, GameSettings, GamepadBinds, Keymap};
+use crate::ui::focus::MenuPad;
+
+/// Gamepad assignment and menu input.
+pub struct GamepadPlugin;
+
+impl Plugin for GamepadPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PadAssignments>()
+            .init_resource::<MenuPad>()
+            .add_systems(
+                PreUpdate,
+                (assign_pads, read_menu_pad)
+                    .chain()
+                    .in_set(MenuPadSystems)
+                    .after(InputSystems),
+            );
+    }
+}
+
+/// Where [`MenuPad`] is refreshed; a system that must see (or swallow) this
+/// frame's menu edges orders itself after it.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct MenuPadSystems;
+
+/// Which pad belongs to which player, indexed by [`Keymap`].
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PadAssignments {
+    slots: [Option<Entity>; 2],
+}
+
+impl PadAssignments {
+    /// The pad `keymap`'s player holds, if one is connected.
+    pub fn pad(&self, keymap: Keymap) -> Option<Entity> {
+        self.slots[keymap as usize]
+    }
+
+    /// Trade the two players' pads (an empty slot trades too).
+    pub fn swap(&mut self) {
+        self.slots.swap(0, 1);
+    }
+
+    /// Keep the slots in step with the `connected` pads: a departed pad frees
+    /// its slot, and a new pad takes the first free one.
+    fn reconcile(&mut self, connected: &[Entity]) {
+        for slot in &mut self.slots {
+            if slot.is_some_and(|pad| !connected.contains(&pad)) {
+                *slot = None;
+            }
+        }
+        for &pad in connected {
+            if self.slots.contains(&Some(pad)) {
+                continue;
+            }
+            if let Some(free) = self.slots.iter_mut().find(|slot| slot.is_none()) {
+                *free = Some(pad);
+            }
+        }
+    }
+}
+
+/// Follow connects and disconnects. Pads are taken in entity order, which is
+/// connection order for pads that arrive one at a time.
+fn assign_pads(pads: Query<Entity, With<Gamepad>>, mut assignments: ResMut<PadAssignments>) {
+    let mut connected: Vec<Entity> = pads.iter().collect();
+    connected.sort();
+    let mut next = *assignments;
+    next.reconcile(&connected);
+    // Write only on a real change, so readers can trust `is_changed`.
+    assignments.set_if_neq(next);
+}
+
+/// The left stick's digital directions: each one is "pressed" while the stick
+/// leans past the threshold along its axis. Kept between frames, it turns a
+/// lean into a one-frame press edge.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StickLatch {
+    left: bool,
+    right: bool,
+    up: bool,
+    down: bool,
+}
+
+impl StickLatch {
+    fn read(stick: Vec2, threshold: f32) -> Self {
+        Self {
+            left: stick.x <= -threshold,
+            right: stick.x >= threshold,
+            up: stick.y >= threshold,
+            down: stick.y <= -threshold,
+        }
+    }
+
+    /// Every pad's stick, leaning together: any pad's lean counts.
+    fn read_all<'a>(pads: impl IntoIterator<Item = &'a Gamepad>, threshold: f32) -> Self {
+        pads.into_iter()
+            .map(|pad| Self::read(pad.left_stick(), threshold))
+            .fold(Self::default(), |a, b| Self {
+                left: a.left || b.left,
+                right: a.right || b.right,
+                up: a.up || b.up,
+                down: a.down || b.down,
+            })
+    }
+}
+
+/// Build a [`RawKeyboardFrame`] from `pads` under the player's
+/// [`GamepadBinds`] — the pad's side of
+/// [`keyboard_input_from_keybinds`](super::options::keyboard_input_from_keybinds),
+/// so the seat's `KeyboardController` runs DAS on it unchanged. Buttons from
+/// every pad given count; the left stick adds left/right/soft drop past
+/// `threshold` (never hard drop: a stick flick would misfire it), with
+/// `stick` carrying last frame's lean for the press edges. `hold_enabled`
+/// gates Hold exactly as it does the keyboard.
+pub fn gamepad_input_from_binds<'a>(
+    pads: impl IntoIterator<Item = &'a Gamepad> + Clone,
+    binds: &GamepadBinds,
+    threshold: f32,
+    stick: &mut StickLatch,
+    hold_enabled: bool,
+    dt_seconds: f32,
+) -> RawKeyboardFrame {
+    let pressed = |action: GameAction| {
+        let (primary, secondary) = binds.get(action);
+        pads.clone()
+            .into_iter()
+            .any(|pad| pad.pressed(primary) || secondary.is_some_and(|button| pad.pressed(button)))
+    };
+    let just = |action: GameAction| {
+        let (primary, secondary) = binds.get(action);
+        pads.clone().into_iter().any(|pad| {
+            pad.just_pressed(primary) || secondary.is_some_and(|button| pad.just_pressed(button))
+        })
+    };
+    let lean = StickLatch::read_all(pads.clone(), threshold);
+    let was = std::mem::replace(stick, lean);
+
+    RawKeyboardFrame {
+        dt_seconds,
+        left_pressed: pressed(GameAction::MoveLeft) || lean.left,
+        right_pressed: pressed(GameAction::MoveRight) || lean.right,
+        left_just_pressed: just(GameAction::MoveLeft) || (lean.left && !was.left),
+        right_just_pressed: just(GameAction::MoveRight) || (lean.right && !was.right),
+        soft_drop: pressed(GameAction::SoftDrop) || lean.down,
+        hard_drop_just_pressed: just(GameAction::HardDrop),
+        rotate_cw_just_pressed: just(GameAction::RotateCw),
+        rotate_ccw_just_pressed: just(GameAction::RotateCcw),
+        hold_just_pressed: hold_enabled && just(GameAction::Hold),
+        pause_just_pressed: just(GameAction::Pause),
+    }
+}
+
+/// Two devices driving one seat: a flag is set if either device sets it.
+/// `dt_seconds` comes from `a`.
+pub fn either_device(a: RawKeyboardFrame, b: RawKeyboardFrame) -> RawKeyboardFrame {
+    RawKeyboardFrame {
+        dt_seconds: a.dt_seconds,
+        left_pressed: a.left_pressed || b.left_pressed,
+        right_pressed: a.right_pressed || b.right_pressed,
+        left_just_pressed: a.left_just_pressed || b.left_just_pressed,
+        right_just_pressed: a.right_just_pressed || b.right_just_pressed,
+        soft_drop: a.soft_drop || b.soft_drop,
+        hard_drop_just_pressed: a.hard_drop_just_pressed || b.hard_drop_just_pressed,
+        rotate_cw_just_pressed: a.rotate_cw_just_pressed || b.rotate_cw_just_pressed,
+        rotate_ccw_just_pressed: a.rotate_ccw_just_pressed || b.rotate_ccw_just_pressed,
+        hold_just_pressed: a.hold_just_pressed || b.hold_just_pressed,
+        pause_just_pressed: a.pause_just_pressed || b.pause_just_pressed,
+    }
+}
+
+/// Fold every pad into this frame's [`MenuPad`]: the D-pad or the left stick
+/// steps the cursor, South or Start selects, East backs out. Menus are not
+/// remapped — they follow the console convention whatever the game binds.
+fn read_menu_pad(
+    pads: Query<&Gamepad>,
+    settings: Res<GameSettings>,
+    mut stick: Local<StickLatch>,
+    mut menu: ResMut<MenuPad>,
+) {
+    let any = |button: GamepadButton| pads.iter().any(|pad| pad.just_pressed(button));
+    let lean = StickLatch::read_all(pads, settings.stick_threshold);
+    let was = std::mem::replace(&mut *stick, lean);
+    menu.set_if_neq(MenuPad {
+        up: any(GamepadButton::DPadUp) || (lean.up && !was.up),
+        down: any(GamepadButton::DPadDown) || (lean.down && !was.down),
+        left: any(GamepadButton::DPadLeft) || (lean.left && !was.left),
+        right: any(GamepadButton::DPadRight) || (lean.right && !was.right),
+        select: any(GamepadButton::South) || any(GamepadButton::Start),
+        back: any(GamepadButton::East),
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::input::gamepad::{
+        GamepadConnection, GamepadConnectionEvent, RawGamepadAxisChangedEvent,
+        RawGamepadButtonChangedEvent, RawGamepadEvent,
+    };
+
+    fn pad_with(buttons: &[GamepadButton], stick: Vec2) -> Gamepad {
+        let mut pad = Gamepad::default();
+        for &button in buttons {
+            pad.digital_mut().press(button);
+        }
+        pad.analog_mut().set(GamepadAxis::LeftStickX, stick.x);
+        pad.analog_mut().set(GamepadAxis::LeftStickY, stick.y);
+        pad
+    }
+
+    #[test]
+    fn buttons_map_through_the_binds_and_a_lean_is_one_edge() {
+        let binds = GamepadBinds::default();
+        let mut stick = StickLatch::default();
+        let pad = pad_with(&[GamepadButton::East], Vec2::new(-0.8, -0.6));
+        let pads = [&pad];
+
+        let first = gamepad_input_from_binds(pads, &binds, 0.5, &mut stick, true, 0.016);
+        assert!(
+            first.rotate_cw_just_pressed,
+            "East is the default rotate CW"
+        );
+        assert!(first.left_pressed && first.left_just_pressed);
+        assert!(first.soft_drop, "down-left leans into soft drop too");
+        assert!(!first.hard_drop_just_pressed, "no stick hard drop");
+
+        // Still leaning next frame: held, but no second edge.
+        let second = gamepad_input_from_binds(pads, &binds, 0.5, &mut stick, true, 0.016);
+        assert!(second.left_pressed && !second.left_just_pressed);
+
+        // A shallow lean stays under the threshold.
+        let shallow = pad_with(&[], Vec2::new(0.3, 0.0));
+        let mut fresh = StickLatch::default();
+        let frame = gamepad_input_from_binds([&shallow], &binds, 0.5, &mut fresh, true, 0.016);
+        assert!(!frame.right_pressed);
+    }
+
+    #[test]
+    fn hold_disabled_suppresses_the_pad_hold() {
+        let binds = GamepadBinds::default();
+        let pad = pad_with(&[GamepadButton::RightTrigger], Vec2::ZERO);
+        let mut stick = StickLatch::default();
+        assert!(
+            gamepad_input_from_binds([&pad], &binds, 0.5, &mut stick, true, 0.0).hold_just_pressed
+        );
+        assert!(
+            !gamepad_input_from_binds([&pad], &binds, 0.5, &mut stick, false, 0.0)
+                .hold_just_pressed
+        );
+    }
+
+    #[test]
+    fn either_device_ors_the_flags() {
+        let keys = RawKeyboardFrame {
+            dt_seconds: 0.5,
+            left_pressed: true,
+            ..RawKeyboardFrame::default()
+        };
+        let pad = RawKeyboardFrame {
+            hard_drop_just_pressed: true,
+            ..RawKeyboardFrame::default()
+        };
+        let both = either_device(keys, pad);
+        assert!(both.left_pressed && both.hard_drop_just_pressed);
+        assert_eq!(both.dt_seconds, 0.5);
+    }
+
+    #[test]
+    fn slots_fill_in_order_and_free_on_disconnect() {
+        let mut world = World::new();
+        let (a, b, c) = (
+            world.spawn_empty().id(),
+            world.spawn_empty().id(),
+            world.spawn_empty().id(),
+        );
+        let mut assignments = PadAssignments::default();
+        assignments.reconcile(&[a, b]);
+        assert_eq!(assignments.pad(Keymap::P1), Some(a));
+        assert_eq!(assignments.pad(Keymap::P2), Some(b));
+
+        assignments.swap();
+        assert_eq!(assignments.pad(Keymap::P1), Some(b));
+
+        // P2's pad (a) leaves; the next pad takes its slot, P1 keeps theirs.
+        assignments.reconcile(&[b]);
+        assert_eq!(assignments.pad(Keymap::P2), None);
+        assignments.reconcile(&[b, c]);
+        assert_eq!(assignments.pad(Keymap::P1), Some(b));
+        assert_eq!(assignments.pad(Keymap::P2), Some(c));
+    }
+
+    /// End to end through Bevy's input pipeline: synthetic raw events connect
+    /// a pad, which takes Player 1's slot, and its D-pad press reaches the
+    /// menus as a cursor step.
+    #[test]
+    fn a_synthetic_pad_connects_and_steps_the_menu() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, bevy::input::InputPlugin))
+            .init_resource::<GameSettings>()
+            .add_plugins(GamepadPlugin);
+        let pad = app.world_mut().spawn_empty().id();
+        // Bevy inserts the `Gamepad` on a `GamepadConnectionEvent`, which the
+        // backend sends next to its raw button and axis stream.
+        app.world_mut().write_message(GamepadConnectionEvent::new(
+            pad,
+            GamepadConnection::Connected {
+                name: "Test Pad".into(),
+                vendor_id: None,
+                product_id: None,
+            },
+        ));
+        app.update(); // connect
+        app.update(); // assign
+        assert_eq!(
+            app.world().resource::<PadAssignments>().pad(Keymap::P1),
+            Some(pad)
+        );
+
+        app.world_mut()
+            .write_message(RawGamepadEvent::Button(RawGamepadButtonChangedEvent::new(
+                pad,
+                GamepadButton::DPadDown,
+                1.0,
+            )));
+        app.update();
+        assert!(app.world().resource::<MenuPad>().down);
+        app.update(); // still held: no new edge
+        assert!(!app.world().resource::<MenuPad>().down);
+
+        // The stick steps the cursor too, once per lean.
+        app.world_mut()
+            .write_message(RawGamepadEvent::Axis(RawGamepadAxisChangedEvent::new(
+                pad,
+                GamepadAxis::LeftStickY,
+                0.9,
+            )));
+        app.update();
+        assert!(app.world().resource::<MenuPad>().up);
+        app.update();
+        assert!(!app.world().resource::<MenuPad>().up);
+    }
+}
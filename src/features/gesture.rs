@@ -0,0 +1,458 @@
+//! Swipe-gesture recognition for the touch controls, as a pure state machine.
+//!
+//! [`GestureRecognizer`] eats raw [`TouchSample`]s (one per touch event, in
+//! arrival order) and emits the same [`RawKeyboardFrame`] a keyboard would,
+//! once per render frame:
+//!
+//! * **drag sideways** — every [`GestureConfig::cell_px`] of horizontal travel
+//!   shifts the piece one column, one column per frame (a fast drag queues);
+//! * **drag down** — holds soft drop while the finger sits a cell or more
+//!   below the highest point of its stroke;
+//! * **flick down** — a release moving down faster than
+//!   [`GestureConfig::flick_speed`] hard-drops; a flick **up** holds;
+//! * **tap** — a short press that barely moves rotates: clockwise on the
+//!   right half of the screen, counter-clockwise on the left.
+//!
+//! No ECS here: the touch feature (`features/touch.rs`) converts Bevy's touch
+//! messages into samples and feeds the frame into the seat's input, so the
+//! tests below drive the recognizer with synthetic touch sequences.
+
+use bevy::input::touch::TouchPhase;
+use bevy::math::Vec2;
+
+use crate::player::RawKeyboardFrame;
+
+/// One touch event, in logical pixels from the window's top-left corner.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TouchSample {
+    pub id: u64,
+    pub phase: TouchPhase,
+    pub position: Vec2,
+}
+
+/// Recognizer thresholds, in logical pixels and seconds.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GestureConfig {
+    /// Drag distance that moves the piece one column (and, downward, that
+    /// starts soft drop).
+    pub cell_px: f32,
+    /// Release speed (px/s) along the vertical that makes a flick.
+    pub flick_speed: f32,
+    /// How far back, in seconds, the release speed is measured over.
+    pub flick_window: f32,
+    /// The longest press that can still be a tap.
+    pub tap_seconds: f32,
+    /// How far a tap may wander before it becomes a drag.
+    pub tap_slop: f32,
+}
+
+impl GestureConfig {
+    /// The defaults with the drag cell sized to `screen`: a fourteenth of
+    /// its shorter side, a fingertip's worth on a phone.
+    pub fn for_screen(screen: Vec2) -> Self {
+        Self {
+            cell_px: screen.min_element() / 14.0,
+            ..Self::default()
+        }
+    }
+}
+
+impl Default for GestureConfig {
+    fn default() -> Self {
+        Self {
+            cell_px: 28.0,
+            flick_speed: 900.0,
+            flick_window: 0.08,
+            tap_seconds: 0.25,
+            tap_slop: 12.0,
+        }
+    }
+}
+
+/// One finger's stroke, from touch-down to release.
+#[derive(Debug, Clone)]
+struct Stroke {
+    id: u64,
+    start: Vec2,
+    started_at: f32,
+    /// Where the next column shift is measured from.
+    anchor_x: f32,
+    /// The highest point of the stroke (smallest y): soft drop measures from
+    /// here, so drawing the finger back up ends it.
+    top_y: f32,
+    /// Furthest the stroke has wandered from `start`.
+    travel: f32,
+    /// Recent `(time, position)` points, for the release speed.
+    trail: Vec<(f32, Vec2)>,
+}
+
+impl Stroke {
+    fn new(id: u64, position: Vec2, now: f32) -> Self {
+        Self {
+            id,
+            start: position,
+            started_at: now,
+            anchor_x: position.x,
+            top_y: position.y,
+            travel: 0.0,
+            trail: vec![(now, position)],
+        }
+    }
+
+    fn position(&self) -> Vec2 {
+        self.trail.last().map_or(self.start, |&(_, p)| p)
+    }
+
+    /// Follow the finger to `position`; returns the column shifts it earned
+    /// (negative is left).
+    fn track(&mut self, position: Vec2, now: f32, config: &GestureConfig) -> i32 {
+        self.travel = self.travel.max(position.distance(self.start));
+        self.top_y = self.top_y.min(position.y);
+        self.trail.push((now, position));
+        let horizon = now - config.flick_window;
+        // Keep one point at or before the window's edge so the speed always
+        // spans the whole window.
+        while self.trail.len() > 2 && self.trail[1].0 <= horizon {
+            self.trail.remove(0);
+        }
+        let mut shifts = 0;
+        while position.x - self.anchor_x >= config.cell_px {
+            self.anchor_x += config.cell_px;
+            shifts += 1;
+        }
+        while self.anchor_x - position.x >= config.cell_px {
+            self.anchor_x -= config.cell_px;
+            shifts -= 1;
+        }
+        shifts
+    }
+
+    /// Speed over the recent trail, in px/s.
+    fn velocity(&self) -> Vec2 {
+        let (Some(&(t0, p0)), Some(&(t1, p1))) = (self.trail.first(), self.trail.last()) else {
+            return Vec2::ZERO;
+        };
+        if t1 > t0 {
+            (p1 - p0) / (t1 - t0)
+        } else {
+            Vec2::ZERO
+        }
+    }
+
+    fn soft_dropping(&self, config: &GestureConfig) -> bool {
+        self.position().y - self.top_y >= config.cell_px
+    }
+}
+
+/// Turns touch strokes into keyboard-shaped input frames.
+#[derive(Debug, Clone, Default)]
+pub struct GestureRecognizer {
+    config: GestureConfig,
+    strokes: Vec<Stroke>,
+    /// Column shifts earned but not yet sent (negative is left); one goes out
+    /// per frame, like a tap of the arrow key.
+    pending_shift: i32,
+    /// Seconds since the recognizer was made; samples are stamped with it.
+    now: f32,
+}
+
+impl GestureRecognizer {
+    pub fn new(config: GestureConfig) -> Self {
+        Self {
+            config,
+            ..Self::default()
+        }
+    }
+
+    /// Whether a finger is down.
+    pub fn is_touching(&self) -> bool {
+        !self.strokes.is_empty()
+    }
+
+    /// Advance `dt` seconds, read this frame's `samples` (in arrival order),
+    /// and emit the frame's input. `screen` is the window size, which splits
+    /// taps into clockwise and counter-clockwise halves.
+    pub fn frame(&mut self, samples: &[TouchSample], dt: f32, screen: Vec2) -> RawKeyboardFrame {
+        self.now += dt.max(0.0);
+        let mut out = RawKeyboardFrame {
+            dt_seconds: dt,
+            ..RawKeyboardFrame::default()
+        };
+        for sample in samples {
+            match sample.phase {
+                TouchPhase::Started => {
+                    self.strokes.retain(|s| s.id != sample.id);
+                    self.strokes
+                        .push(Stroke::new(sample.id, sample.position, self.now));
+                }
+                TouchPhase::Moved => {
+                    if let Some(stroke) = self.strokes.iter_mut().find(|s| s.id == sample.id) {
+                        self.pending_shift += stroke.track(sample.position, self.now, &self.config);
+                    }
+                }
+                TouchPhase::Ended => {
+                    let Some(index) = self.strokes.iter().position(|s| s.id == sample.id) else {
+                        continue;
+                    };
+                    let mut stroke = self.strokes.swap_remove(index);
+                    self.pending_shift += stroke.track(sample.position, self.now, &self.config);
+                    self.release(&stroke, screen, &mut out);
+                }
+                TouchPhase::Canceled => self.strokes.retain(|s| s.id != sample.id),
+            }
+        }
+
+        // One column a frame: each is its own press edge, so the DAS never
+        // charges off a drag.
+        if self.pending_shift < 0 {
+            self.pending_shift += 1;
+            out.left_pressed = true;
+            out.left_just_pressed = true;
+        } else if self.pending_shift > 0 {
+            self.pending_shift -= 1;
+            out.right_pressed = true;
+            out.right_just_pressed = true;
+        }
+        out.soft_drop = self.strokes.iter().any(|s| s.soft_dropping(&self.config));
+        out
+    }
+
+    /// Classify a finished stroke: flick, tap, or neither.
+    fn release(&self, stroke: &Stroke, screen: Vec2, out: &mut RawKeyboardFrame) {
+        let velocity = stroke.velocity();
+        let vertical = velocity.y.abs() > velocity.x.abs();
+        if vertical && velocity.y >= self.config.flick_speed {
+            out.hard_drop_just_pressed = true;
+        } else if vertical && -velocity.y >= self.config.flick_speed {
+            out.hold_just_pressed = true;
+        } else if stroke.travel <= self.config.tap_slop
+            && self.now - stroke.started_at <= self.config.tap_seconds
+        {
+            if stroke.start.x >= screen.x / 2.0 {
+                out.rotate_cw_just_pressed = true;
+            } else {
+                out.rotate_ccw_just_pressed = true;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCREEN: Vec2 = Vec2::new(400.0, 800.0);
+    const DT: f32 = 1.0 / 60.0;
+
+    fn sample(id: u64, phase: TouchPhase, x: f32, y: f32) -> TouchSample {
+        TouchSample {
+            id,
+            phase,
+            position: Vec2::new(x, y),
+        }
+    }
+
+    /// Play `frames` (each a frame's samples) and collect the output frames.
+    fn play(
+        recognizer: &mut GestureRecognizer,
+        frames: &[Vec<TouchSample>],
+    ) -> Vec<RawKeyboardFrame> {
+        frames
+            .iter()
+            .map(|samples| recognizer.frame(samples, DT, SCREEN))
+            .collect()
+    }
+
+    fn idle(count: usize) -> Vec<Vec<TouchSample>> {
+        vec![Vec::new(); count]
+    }
+
+    #[test]
+    fn a_short_tap_rotates_by_screen_half() {
+        let mut recognizer = GestureRecognizer::default();
+        let right = play(
+            &mut recognizer,
+            &[
+                vec![sample(1, TouchPhase::Started, 300.0, 400.0)],
+                vec![sample(1, TouchPhase::Ended, 302.0, 401.0)],
+            ],
+        );
+        assert!(right[1].rotate_cw_just_pressed);
+        assert!(!right[1].rotate_ccw_just_pressed);
+
+        let left = play(
+            &mut recognizer,
+            &[
+                vec![sample(2, TouchPhase::Started, 80.0, 400.0)],
+                vec![sample(2, TouchPhase::Ended, 80.0, 400.0)],
+            ],
+        );
+        assert!(left[1].rotate_ccw_just_pressed);
+    }
+
+    #[test]
+    fn a_long_press_is_not_a_tap() {
+        let mut recognizer = GestureRecognizer::default();
+        let mut frames = vec![vec![sample(1, TouchPhase::Started, 300.0, 400.0)]];
+        frames.extend(idle(30));
+        frames.push(vec![sample(1, TouchPhase::Ended, 300.0, 400.0)]);
+        let out = play(&mut recognizer, &frames);
+        assert!(out.iter().all(|f| *f
+            == RawKeyboardFrame {
+                dt_seconds: DT,
+                ..RawKeyboardFrame::default()
+            }));
+    }
+
+    #[test]
+    fn a_drag_shifts_one_column_per_cell_one_per_frame() {
+        let config = GestureConfig::default();
+        let mut recognizer = GestureRecognizer::new(config);
+        // Three and a half cells right in a single move: three shifts, sent
+        // on three consecutive frames, each its own press edge.
+        let out = play(
+            &mut recognizer,
+            &[
+                vec![sample(1, TouchPhase::Started, 100.0, 400.0)],
+                vec![sample(
+                    1,
+                    TouchPhase::Moved,
+                    100.0 + 3.5 * config.cell_px,
+                    400.0,
+                )],
+                vec![],
+                vec![],
+                vec![],
+            ],
+        );
+        let shifts: Vec<bool> = out.iter().map(|f| f.right_just_pressed).collect();
+        assert_eq!(shifts, [false, true, true, true, false]);
+        assert!(
+            out.iter()
+                .all(|f| !f.left_pressed && !f.rotate_cw_just_pressed)
+        );
+
+        // Back two cells (from the last anchor): two shifts left; the release
+        // after a drag is no tap.
+        let out = play(
+            &mut recognizer,
+            &[
+                vec![sample(
+                    1,
+                    TouchPhase::Moved,
+                    100.0 + 1.0 * config.cell_px,
+                    400.0,
+                )],
+                vec![sample(
+                    1,
+                    TouchPhase::Ended,
+                    100.0 + 1.0 * config.cell_px,
+                    400.0,
+                )],
+                vec![],
+            ],
+        );
+        let shifts: Vec<bool> = out.iter().map(|f| f.left_just_pressed).collect();
+        assert_eq!(shifts, [true, true, false]);
+        assert!(
+            out.iter()
+                .all(|f| !f.rotate_cw_just_pressed && !f.rotate_ccw_just_pressed)
+        );
+    }
+
+    #[test]
+    fn a_slow_drag_down_soft_drops_until_the_finger_comes_back_up() {
+        let config = GestureConfig::default();
+        let mut recognizer = GestureRecognizer::new(config);
+        let mut frames = vec![vec![sample(1, TouchPhase::Started, 200.0, 300.0)]];
+        // Half a cell per frame: slow, and after two frames a whole cell down.
+        for step in 1..=6 {
+            let y = 300.0 + step as f32 * config.cell_px / 2.0;
+            frames.push(vec![sample(1, TouchPhase::Moved, 200.0, y)]);
+        }
+        // Back up to the top of the stroke: soft drop lets go.
+        frames.push(vec![sample(1, TouchPhase::Moved, 200.0, 300.0)]);
+        frames.extend(idle(10));
+        frames.push(vec![sample(1, TouchPhase::Ended, 200.0, 300.0)]);
+        let out = play(&mut recognizer, &frames);
+        let soft: Vec<bool> = out[..8].iter().map(|f| f.soft_drop).collect();
+        assert_eq!(soft, [false, false, true, true, true, true, true, false]);
+        assert!(out.iter().all(|f| !f.hard_drop_just_pressed));
+        assert!(!recognizer.is_touching());
+    }
+
+    #[test]
+    fn a_flick_down_hard_drops_and_a_flick_up_holds() {
+        let mut recognizer = GestureRecognizer::default();
+        // 40 px a frame is 2400 px/s: well past the flick speed.
+        let out = play(
+            &mut recognizer,
+            &[
+                vec![sample(1, TouchPhase::Started, 200.0, 300.0)],
+                vec![sample(1, TouchPhase::Moved, 202.0, 340.0)],
+                vec![sample(1, TouchPhase::Moved, 203.0, 380.0)],
+                vec![sample(1, TouchPhase::Ended, 204.0, 420.0)],
+            ],
+        );
+        assert!(out[3].hard_drop_just_pressed);
+        assert!(!out[3].hold_just_pressed);
+
+        let out = play(
+            &mut recognizer,
+            &[
+                vec![sample(2, TouchPhase::Started, 200.0, 500.0)],
+                vec![sample(2, TouchPhase::Moved, 200.0, 460.0)],
+                vec![sample(2, TouchPhase::Ended, 200.0, 420.0)],
+            ],
+        );
+        assert!(out[2].hold_just_pressed);
+        assert!(!out[2].hard_drop_just_pressed);
+    }
+
+    #[test]
+    fn a_drag_that_stops_before_release_is_no_flick() {
+        let mut recognizer = GestureRecognizer::default();
+        let mut frames = vec![
+            vec![sample(1, TouchPhase::Started, 200.0, 300.0)],
+            vec![sample(1, TouchPhase::Moved, 200.0, 360.0)],
+            vec![sample(1, TouchPhase::Moved, 200.0, 420.0)],
+        ];
+        // The finger rests long enough for the speed window to pass.
+        frames.extend(idle(10));
+        frames.push(vec![sample(1, TouchPhase::Ended, 200.0, 420.0)]);
+        let out = play(&mut recognizer, &frames);
+        assert!(out.iter().all(|f| !f.hard_drop_just_pressed));
+    }
+
+    #[test]
+    fn two_fingers_are_tracked_apart_and_a_cancel_is_dropped() {
+        let config = GestureConfig::default();
+        let mut recognizer = GestureRecognizer::new(config);
+        let out = play(
+            &mut recognizer,
+            &[
+                vec![
+                    sample(1, TouchPhase::Started, 100.0, 600.0),
+                    sample(2, TouchPhase::Started, 300.0, 300.0),
+                ],
+                vec![
+                    sample(1, TouchPhase::Moved, 100.0 - config.cell_px, 600.0),
+                    sample(2, TouchPhase::Ended, 300.0, 300.0),
+                ],
+                vec![sample(
+                    1,
+                    TouchPhase::Canceled,
+                    100.0 - config.cell_px,
+                    600.0,
+                )],
+            ],
+        );
+        assert!(out[1].left_just_pressed, "finger one drags a column left");
+        assert!(
+            out[1].rotate_cw_just_pressed,
+            "finger two taps on the right"
+        );
+        assert!(!recognizer.is_touching());
+        assert!(!out[2].rotate_ccw_just_pressed, "a cancel is never a tap");
+    }
+}
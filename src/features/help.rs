@@ -285,6 +285,22 @@ const HOW_TO_PLAY: &[(&str, &str)] = &[
         "Back-to-Back",
         "Chaining special clears (Tetris / T-Spin) without a normal clear adds a bonus.",
     ),
+    (
+        "Practice",
+        "Backspace undoes a placement, Enter redoes it, R retries the bag, F freezes gravity, B shows the next bag.",
+    ),
+    (
+        "Board Editor",
+        "Paint cells with Space or the mouse, queue pieces by letter, then P to practise the position or A to watch the AI.",
+    ),
+    (
+        "Openers",
+        "Place each piece on the outlined target to build the opener; HOLD means the book wants a swap first.",
+    ),
+    (
+        "Daily Challenge",
+        "Everyone gets the same deal and rules each UTC day: one official attempt, then practice as often as you like.",
+    ),
 ];
 
 /// A short, human-readable name for a [`KeyCode`].
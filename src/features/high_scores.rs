@@ -10,9 +10,11 @@
 //!    [`StorageResource`] under [`storage::keys::HIGH_SCORES`]. The board is
 //!    loaded back from storage once on startup so leaderboards survive restarts.
 //! 2. **Display.** On [`GameState::HighScores`] we populate the screen shell's
-//!    [`HighScoresRoot`] with one column per [`Variant`], formatting each row's
-//!    primary figure per the variant's [`ScoreKind`] (Sprint shows fastest time
-//!    first; Marathon/Ultra show highest score first).
+//!    [`HighScoresRoot`] with one column per [`Variant`] (a Dig board only once
+//!    it has a run, and a fading or invisible stack's board likewise),
+//!    formatting each row's primary figure per the variant's
+//!    [`ScoreKind`] (Sprint and Dig show fastest time first; Marathon, Ultra
+//!    and Blitz show highest score first; Master shows best grade first).
 //!
 //! The persisted blob is a tiny line-based text format (see [`codec`]) so we keep
 //! the shared [`HighScore`] contract untouched and add no serialization
@@ -37,6 +39,7 @@ use crate::assets::GameAssets;
 use crate::engine::EngineSnapshot;
 use crate::high_scores::{HighScore, HighScores};
 use crate::screens::HighScoresRoot;
+use crate::settings::StackChallenge;
 use crate::storage::{StorageResource, keys};
 use crate::ui::widgets::label_text;
 use crate::variant::{ScoreKind, Variant, VariantDef};
@@ -66,19 +69,19 @@ impl Plugin for HighScoresFeaturePlugin {
 // Record + persist
 // ---------------------------------------------------------------------------
 
-/// Build a [`HighScore`] from the final snapshot + run clock and try to file it
-/// for the active variant. Persists the whole board iff the run made the table.
-/// File a finished run for `variant`, returning its rank when it made the
+/// File a finished run for `variant`, on the board of the `stack` challenge
+/// it was played under, returning its rank when it made the
 /// table (and persisting the board). The qualify rule: a time-ranked variant
 /// (Sprint) ranks by *lowest* time, so a top-out before the line target would
 /// post a sub-target time that beats every legitimate completion — dropped.
-/// Score-ranked variants (Marathon, Ultra) record every run; a partial run's
+/// Score-ranked variants (Marathon, Ultra, Blitz) record every run; a partial run's
 /// lower score already sorts correctly. Persistence is skipped when no
 /// storage is wired (headless tests).
 pub(crate) fn record(
     snapshot: &crate::engine::EngineSnapshot,
     elapsed_seconds: f32,
     variant: crate::variant::Variant,
+    stack: StackChallenge,
     scores: &mut HighScores,
     storage: &Option<bevy::prelude::Res<StorageResource>>,
 ) -> Option<usize> {
@@ -90,7 +93,10 @@ pub(crate) fn record(
         score: snapshot.score,
         time_seconds: elapsed_seconds,
         lines: snapshot.lines,
-        level: snapshot.level,
+        level: snapshot.result_level(),
+        stack,
+        // Attached by the session's replay capture if this is the new best.
+        replay: None,
     };
 
     let rank = scores.insert(variant, candidate)?;
@@ -101,10 +107,15 @@ pub(crate) fn record(
         candidate.score,
         format_time(candidate.time_seconds),
     );
+    persist(scores, storage);
+    Some(rank)
+}
+
+/// Save every board (a no-op when no storage is wired).
+pub(crate) fn persist(scores: &HighScores, storage: &Option<Res<StorageResource>>) {
     if let Some(storage) = storage {
         storage.0.save(keys::HIGH_SCORES, &codec::serialize(scores));
     }
-    Some(rank)
 }
 
 /// Whether a finished run should be filed for `def`.
@@ -166,7 +177,10 @@ fn populate_tables(
             HighScoresTables,
             Node {
                 flex_direction: FlexDirection::Row,
+                flex_wrap: FlexWrap::Wrap,
+                justify_content: JustifyContent::Center,
                 column_gap: px(40),
+                row_gap: px(16),
                 align_items: AlignItems::FlexStart,
                 margin: UiRect::top(px(8)),
                 ..default()
@@ -174,26 +188,35 @@ fn populate_tables(
         ))
         .id();
 
-    for variant in Variant::ALL {
-        let column = spawn_variant_column(&mut commands, &assets, &scores, variant);
-        commands.entity(tables).add_child(column);
+    for stack in StackChallenge::ALL {
+        for variant in Variant::ALL {
+            // Six Dig boards would crowd the screen, and the challenge boards
+            // more so; each of those shows once it has a run.
+            let optional = matches!(variant, Variant::Dig { .. }) || !stack.is_standard();
+            if optional && scores.table(variant, stack).is_empty() {
+                continue;
+            }
+            let column = spawn_variant_column(&mut commands, &assets, &scores, variant, stack);
+            commands.entity(tables).add_child(column);
+        }
     }
     commands.entity(root).add_child(tables);
 }
 
-/// One vertical column: variant name, a header, then up to ten rows (or an
-/// "empty" hint when the board has no entries yet).
+/// One vertical column: variant name (and stack challenge), a header, then
+/// up to ten rows (or an "empty" hint when the board has no entries yet).
 fn spawn_variant_column(
     commands: &mut Commands,
     assets: &GameAssets,
     scores: &HighScores,
     variant: Variant,
+    stack: StackChallenge,
 ) -> Entity {
     // Heading in the display voice (Dogica); the table itself is the working
     // voice (Departure Mono) — fixed-width rows align for free.
     let font = assets.font_body.clone();
     let kind = variant.def().score_kind;
-    let table = scores.table(variant);
+    let table = scores.table(variant, stack);
 
     let column = commands
         .spawn(Node {
@@ -208,6 +231,10 @@ fn spawn_variant_column(
         .spawn(column_heading(variant.display_name(), assets.font.clone()))
         .id();
     commands.entity(column).add_child(heading);
+    if !stack.is_standard() {
+        let challenge = commands.spawn(label_text(stack.label(), font.clone())).id();
+        commands.entity(column).add_child(challenge);
+    }
 
     let header = commands
         .spawn(label_text(header_row(kind), font.clone()))
@@ -255,6 +282,7 @@ fn header_row(kind: ScoreKind) -> &'static str {
     match kind {
         ScoreKind::Time => "#   TIME       SCORE",
         ScoreKind::Score => "#   SCORE      LINES",
+        ScoreKind::Grade => "#   GRADE      SCORE",
     }
 }
 
@@ -270,11 +298,22 @@ fn format_row(index: usize, entry: &HighScore, kind: ScoreKind) -> String {
             entry.score
         ),
         ScoreKind::Score => format!("{:>2}  {:<9}  {}", rank, entry.score, entry.lines),
+        ScoreKind::Grade => format!(
+            "{:>2}  {:<9}  {}",
+            rank,
+            grade_label(entry.level),
+            entry.score
+        ),
     }
 }
 
+/// A Master entry's grade (filed as its level), as the game writes it.
+pub(crate) fn grade_label(level: u8) -> String {
+    crate::engine::MasterGrade::from_index(level).map_or_else(|| "?".into(), |grade| grade.label())
+}
+
 /// Render seconds as `M:SS.mmm` (e.g. `1:23.456`) so Sprint times read naturally.
-fn format_time(seconds: f32) -> String {
+pub(crate) fn format_time(seconds: f32) -> String {
     let seconds = seconds.max(0.0);
     let minutes = (seconds / 60.0).floor() as u64;
     let secs = seconds - (minutes as f32) * 60.0;
@@ -287,48 +326,76 @@ fn format_time(seconds: f32) -> String {
 
 /// Line-based, dependency-free encoding for the per-variant boards.
 ///
-/// One entry per line: `<tag> <score> <time_seconds> <lines> <level>`, where
-/// `tag` is `M`/`S`/`U` for Marathon/Sprint/Ultra. Lines that don't parse are
+/// One entry per line: `<tag> <score> <time_seconds> <lines> <level>
+/// [replay]`, where `tag` is `M`/`S`/`U`/`B`/`G` for
+/// Marathon/Sprint/Ultra/Blitz/Master (`D<lines>`
+/// for a clean Dig board, `D<lines>m` for a messy one), suffixed with
+/// `:<challenge>` (`S:invisible`, say) on a stack challenge's board. Older
+/// files have no suffixes: every entry in them is a standard run. The optional last
+/// field is the entry's replay id (older files never have
+/// one). Lines that don't parse are
 /// skipped, and every accepted entry is re-inserted via
 /// [`HighScores::insert`], so ordering/truncation are re-derived on load and a
 /// malformed file can never corrupt the in-memory board or panic.
 mod codec {
-    use super::{HighScore, HighScores, Variant};
+    use super::{HighScore, HighScores, StackChallenge, Variant};
 
-    fn tag(variant: Variant) -> char {
+    fn tag(variant: Variant) -> String {
         match variant {
-            Variant::Marathon => 'M',
-            Variant::Sprint => 'S',
-            Variant::Ultra => 'U',
+            Variant::Marathon => "M".into(),
+            Variant::Sprint => "S".into(),
+            Variant::Ultra => "U".into(),
+            Variant::Blitz => "B".into(),
+            Variant::Master => "G".into(),
+            Variant::Dig { lines, messy } => {
+                format!("D{lines}{}", if messy { "m" } else { "" })
+            }
         }
     }
 
-    fn variant_for_tag(tag: &str) -> Option<Variant> {
-        match tag {
-            "M" => Some(Variant::Marathon),
-            "S" => Some(Variant::Sprint),
-            "U" => Some(Variant::Ultra),
-            _ => None,
+    /// The variant tag, plus the stack challenge's key when it has one.
+    fn board_tag(variant: Variant, stack: StackChallenge) -> String {
+        match stack.key() {
+            Some(key) => format!("{}:{key}", tag(variant)),
+            None => tag(variant),
         }
     }
 
+    fn board_for_tag(tag: &str) -> Option<(Variant, StackChallenge)> {
+        let (variant, stack) = match tag.split_once(':') {
+            Some((variant, key)) => (variant, StackChallenge::from_key(key)?),
+            None => (tag, StackChallenge::STANDARD),
+        };
+        // Only a board `Variant::ALL` has is a board at all.
+        let variant = Variant::ALL
+            .into_iter()
+            .find(|&candidate| self::tag(candidate) == variant)?;
+        Some((variant, stack))
+    }
+
     /// Serialize every variant's table to the line format described above.
     pub fn serialize(scores: &HighScores) -> String {
         use std::fmt::Write as _;
 
         let mut out = String::new();
-        for variant in Variant::ALL {
-            for entry in scores.table(variant) {
-                // Writing into a `String` is infallible.
-                let _ = writeln!(
-                    out,
-                    "{} {} {} {} {}",
-                    tag(variant),
-                    entry.score,
-                    entry.time_seconds,
-                    entry.lines,
-                    entry.level,
-                );
+        for stack in StackChallenge::ALL {
+            for variant in Variant::ALL {
+                for entry in scores.table(variant, stack) {
+                    // Writing into a `String` is infallible.
+                    let _ = write!(
+                        out,
+                        "{} {} {} {} {}",
+                        board_tag(variant, stack),
+                        entry.score,
+                        entry.time_seconds,
+                        entry.lines,
+                        entry.level,
+                    );
+                    let _ = match entry.replay {
+                        Some(id) => writeln!(out, " {id}"),
+                        None => writeln!(out),
+                    };
+                }
             }
         }
         out
@@ -348,12 +415,17 @@ mod codec {
 
     fn parse_line(line: &str) -> Option<(Variant, HighScore)> {
         let mut fields = line.split_whitespace();
-        let variant = variant_for_tag(fields.next()?)?;
+        let (variant, stack) = board_for_tag(fields.next()?)?;
         let entry = HighScore {
             score: fields.next()?.parse().ok()?,
             time_seconds: fields.next()?.parse().ok()?,
             lines: fields.next()?.parse().ok()?,
             level: fields.next()?.parse().ok()?,
+            stack,
+            replay: match fields.next() {
+                Some(id) => Some(id.parse().ok()?),
+                None => None,
+            },
         };
         // Reject trailing garbage so an extended/forward-incompatible line is
         // skipped rather than silently truncated.
@@ -373,6 +445,8 @@ mod codec {
                 time_seconds: time,
                 lines,
                 level,
+                stack: StackChallenge::STANDARD,
+                replay: None,
             }
         }
 
@@ -383,11 +457,28 @@ mod codec {
             scores.insert(Variant::Marathon, run(8000, 120.0, 70, 15));
             scores.insert(Variant::Sprint, run(1200, 42.25, 40, 5));
             scores.insert(Variant::Ultra, run(9999, 120.0, 88, 12));
+            scores.insert(Variant::Blitz, run(45000, 120.0, 61, 9));
+            scores.insert(Variant::Master, run(88000, 512.0, 180, 11));
+            scores.set_best_replay(Variant::Sprint, 17);
+            for variant in Variant::ALL {
+                if let Variant::Dig { lines, .. } = variant {
+                    scores.insert(variant, run(300, lines as f32, lines as usize, 1));
+                }
+            }
+            for stack in StackChallenge::ALL {
+                let entry = HighScore {
+                    stack,
+                    ..run(700, 60.0, 40, 3)
+                };
+                scores.insert(Variant::Sprint, entry);
+            }
 
             let restored = deserialize(&serialize(&scores));
 
-            for variant in Variant::ALL {
-                assert_eq!(restored.table(variant), scores.table(variant));
+            for stack in StackChallenge::ALL {
+                for variant in Variant::ALL {
+                    assert_eq!(restored.table(variant, stack), scores.table(variant, stack));
+                }
             }
         }
 
@@ -396,7 +487,7 @@ mod codec {
             let scores = HighScores::default();
             let restored = deserialize(&serialize(&scores));
             for variant in Variant::ALL {
-                assert!(restored.table(variant).is_empty());
+                assert!(restored.table(variant, StackChallenge::STANDARD).is_empty());
             }
         }
 
@@ -407,15 +498,41 @@ M 100 12.5 10 3
 garbage line
 S notanumber 1 2 3
 U 200 7.0 5 4 extrafield
+U 300 7.0 5 4 9 extrafield
+D12 10 7.0 12 1
+M:blurry 100 12.5 10 3
 S 1500 30.0 40 6
+M:invisible 400 12.5 10 3
 ";
             let restored = deserialize(blob);
-            // Only the two well-formed lines survive.
-            assert_eq!(restored.table(Variant::Marathon).len(), 1);
-            assert_eq!(restored.table(Variant::Marathon)[0].score, 100);
-            assert_eq!(restored.table(Variant::Sprint).len(), 1);
-            assert_eq!(restored.table(Variant::Sprint)[0].score, 1500);
-            assert!(restored.table(Variant::Ultra).is_empty());
+            // Only the three well-formed lines survive.
+            assert_eq!(
+                restored
+                    .table(Variant::Marathon, StackChallenge::STANDARD)
+                    .len(),
+                1
+            );
+            assert_eq!(
+                restored.table(Variant::Marathon, StackChallenge::STANDARD)[0].score,
+                100
+            );
+            assert_eq!(
+                restored
+                    .table(Variant::Sprint, StackChallenge::STANDARD)
+                    .len(),
+                1
+            );
+            assert_eq!(
+                restored.table(Variant::Sprint, StackChallenge::STANDARD)[0].score,
+                1500
+            );
+            assert!(
+                restored
+                    .table(Variant::Ultra, StackChallenge::STANDARD)
+                    .is_empty()
+            );
+            let invisible = StackChallenge::from_key("invisible").unwrap();
+            assert_eq!(restored.table(Variant::Marathon, invisible)[0].score, 400);
         }
 
         #[test]
@@ -430,7 +547,7 @@ S 1500 30.0 40 6
             }
             let restored = deserialize(&blob);
             let times: Vec<f32> = restored
-                .table(Variant::Sprint)
+                .table(Variant::Sprint, StackChallenge::STANDARD)
                 .iter()
                 .map(|e| e.time_seconds)
                 .collect();
@@ -499,6 +616,8 @@ mod tests {
             time_seconds: 42.0,
             lines: 40,
             level: 7,
+            stack: StackChallenge::STANDARD,
+            replay: None,
         };
         let time_row = format_row(0, &entry, ScoreKind::Time);
         let score_row = format_row(0, &entry, ScoreKind::Score);
@@ -88,9 +88,9 @@ fn trigger_hit_stop(
     mut hit_stop: ResMut<HitStop>,
     mut virtual_time: ResMut<Time<Virtual>>,
 ) {
-    // Solo only: pausing Time<Virtual> freezes EVERY seat's fixed stepping,
+    // One seat only: pausing Time<Virtual> freezes EVERY seat's fixed stepping,
     // which in versus would let one player's Tetris stop the opponent's clock.
-    if !matches!(config.mode, crate::session::SessionMode::Solo { .. }) {
+    if config.mode.seat_count() != 1 {
         return;
     }
     let mut freeze = 0.0_f32;
@@ -220,7 +220,10 @@ mod tests {
             .insert_resource(crate::session::SessionConfig {
                 seats: [
                     crate::session::Participant::Human,
-                    crate::session::Participant::Bot { model: 0 },
+                    crate::session::Participant::Bot {
+                        model: 0,
+                        skill: crate::ai::SkillPreset::Skilled.into(),
+                    },
                 ],
                 mode: crate::session::SessionMode::Solo {
                     variant: crate::variant::Variant::Marathon,
@@ -8,6 +8,8 @@
 //! |----------------|-------------------------------|----------------------------|-------|
 //! | ambient_wave   | `features/ambient_wave.rs`    | `AmbientWavePlugin`        | the Kissaten pixel-grain background layer |
 //! | options        | `features/options.rs`         | `OptionsPlugin`            | Options-screen widgets that mutate `GameSettings` |
+//! | gamepad        | `features/gamepad.rs`         | `GamepadPlugin`            | pad-to-player assignment, pad input frames, menu edges |
+//! | touch          | `features/touch.rs`           | `TouchPlugin`              | on-screen buttons / swipes (pure recognizer in `gesture.rs`), portrait framing |
 //! | help           | `features/help.rs`            | `HelpPlugin`               | Help-screen controls/about content |
 //! | notifications  | `features/notifications.rs`   | `NotificationsPlugin`      | line-clear flash + hard-drop trail effects |
 //! | screen_shake   | `features/screen_shake.rs`    | `ScreenShakePlugin`        | trauma-based camera shake on impacts |
@@ -18,6 +20,8 @@
 use bevy::prelude::*;
 
 mod ambient_wave;
+pub(crate) mod gamepad;
+mod gesture;
 mod help;
 pub(crate) mod high_scores;
 pub(crate) mod hit_stop;
@@ -25,6 +29,7 @@ mod notifications;
 pub(crate) mod options;
 pub(crate) mod screen_shake;
 mod sfx;
+pub(crate) mod touch;
 
 /// Registers every feature plugin.
 pub struct FeaturesPlugin;
@@ -34,6 +39,8 @@ impl Plugin for FeaturesPlugin {
         app.add_plugins((
             ambient_wave::AmbientWavePlugin,
             options::OptionsPlugin,
+            gamepad::GamepadPlugin,
+            touch::TouchPlugin,
             help::HelpPlugin,
             notifications::NotificationsPlugin,
             screen_shake::ScreenShakePlugin,
@@ -3,15 +3,22 @@
 //! Builds keyboard-navigable widgets under the [`OptionsRoot`] the screen shell
 //! spawns on [`GameState::Options`], letting the player edit
 //! [`GameSettings`]: next-piece count (1..=6), hold/ghost toggles, lock-down
-//! mode, music/SFX volumes, and the per-action [`Keybinds`]. Every edit calls
-//! [`GameSettings::sanitize`] and persists the whole struct through
-//! [`StorageResource`] under [`storage::keys::SETTINGS`]; settings are also
-//! loaded from there at startup and persisted again on screen exit.
+//! mode, handling (DAS, ARR, DCD, soft-drop factor), music/SFX volumes, and
+//! the per-action [`Keybinds`] of both players, each on its own
+//! [`OptionsPage`]; the gamepad page (the pads'
+//! [`GamepadBinds`](crate::settings::GamepadBinds), the stick threshold, and
+//! which player owns which pad); and the touch page (scheme, hand, button
+//! size). A key page warns when a key is bound twice (within or across the
+//! two players' sets). Every edit calls [`GameSettings::sanitize`] and
+//! persists the whole struct through [`StorageResource`] under
+//! [`storage::keys::SETTINGS`]; settings are also loaded from there at startup
+//! and persisted again on screen exit.
 //!
 //! Changes take effect because the readers already consume the shared
 //! [`GameSettings`] resource: `level_setup` mirrors `next_count` into the
 //! previewer/engine, `reconcile_ghost_piece` honors `ghost_enabled`, the engine
-//! bridge feeds `lock_down_mode`, and the SFX feature reads the volumes. The
+//! bridge feeds `lock_down_mode` and the handling (a session seats its keyboard
+//! with them), and the SFX feature reads the volumes. The
 //! keyboard controller reads [`Keybinds`] via [`keyboard_input_from_keybinds`].
 //!
 //! Encoding: RON through the serde derives on [`GameSettings`] — the wire
@@ -21,10 +28,14 @@
 use bevy::prelude::*;
 
 use crate::assets::GameAssets;
-use crate::screens::OptionsRoot;
-use crate::settings::{GameAction, GameSettings, Keybinds, MAX_NEXT_COUNT, MIN_NEXT_COUNT};
+use crate::features::gamepad::{MenuPadSystems, PadAssignments};
+use crate::screens::{OptionsPage, OptionsRoot};
+use crate::settings::{
+    GameAction, GameSettings, KeyConflict, Keybinds, Keymap, MAX_NEXT_COUNT, MAX_SOFT_DROP_FACTOR,
+    MIN_NEXT_COUNT, MIN_SOFT_DROP_FACTOR, StackVisibility, TouchScheme,
+};
 use crate::storage::{StorageResource, keys};
-use crate::ui::focus::{FocusList, Focusable, focus_navigation};
+use crate::ui::focus::{FocusList, Focusable, MenuPad, focus_navigation};
 use crate::ui::theme;
 use crate::ui::widgets::label_text;
 use crate::{GameState, engine::LockDownMode};
@@ -46,6 +57,14 @@ impl Plugin for OptionsPlugin {
                 OnExit(GameState::Options),
                 (clear_rebind_state, save_settings),
             )
+            // A pad rebind captures before anything reads this frame's menu
+            // edges, so the binding press never also navigates.
+            .add_systems(
+                PreUpdate,
+                capture_pad_rebind
+                    .after(MenuPadSystems)
+                    .run_if(in_state(GameState::Options)),
+            )
             .add_systems(
                 Update,
                 (
@@ -69,38 +88,83 @@ impl Plugin for OptionsPlugin {
 // Row model
 // ---------------------------------------------------------------------------
 
-/// One editable settings row. Fixed rows come first, then one row per
-/// [`GameAction`] rebind, matching their [`Focusable`] indices on the screen.
+/// One editable settings row. The general page lists the fixed rows, then a
+/// link to each sub-page; a key page lists one row per [`GameAction`] rebind,
+/// and the gamepad page one per pad rebind plus the stick threshold and the
+/// pad owners. Rows match their [`Focusable`] indices on the screen.
 #[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
 enum OptionRow {
     NextCount,
     HoldEnabled,
     GhostEnabled,
     Background,
+    StackVisibility,
+    StackOutline,
     LockDownMode,
+    Das,
+    Arr,
+    Dcd,
+    SoftDropFactor,
     MusicVolume,
     SfxVolume,
-    Rebind(GameAction),
+    Open(OptionsPage),
+    Rebind(Keymap, GameAction),
+    PadRebind(GameAction),
+    StickThreshold,
+    PadOwners,
+    TouchScheme,
+    TouchHand,
+    TouchSize,
 }
 
 impl OptionRow {
     /// Fixed (non-rebind) rows, in display order.
-    const FIXED: [OptionRow; 7] = [
+    const FIXED: [OptionRow; 13] = [
         OptionRow::NextCount,
         OptionRow::HoldEnabled,
         OptionRow::GhostEnabled,
         OptionRow::Background,
+        OptionRow::StackVisibility,
+        OptionRow::StackOutline,
         OptionRow::LockDownMode,
+        OptionRow::Das,
+        OptionRow::Arr,
+        OptionRow::Dcd,
+        OptionRow::SoftDropFactor,
         OptionRow::MusicVolume,
         OptionRow::SfxVolume,
     ];
 
-    /// Every row in display order (fixed settings then per-action rebinds).
-    fn all() -> Vec<OptionRow> {
-        OptionRow::FIXED
-            .into_iter()
-            .chain(GameAction::ALL.into_iter().map(OptionRow::Rebind))
-            .collect()
+    /// The rows of `page`, in display order.
+    fn rows(page: OptionsPage) -> Vec<OptionRow> {
+        match page {
+            OptionsPage::General => OptionRow::FIXED
+                .into_iter()
+                .chain(
+                    [
+                        OptionsPage::Keys(Keymap::P1),
+                        OptionsPage::Keys(Keymap::P2),
+                        OptionsPage::Gamepad,
+                        OptionsPage::Touch,
+                    ]
+                    .map(OptionRow::Open),
+                )
+                .collect(),
+            OptionsPage::Keys(keymap) => GameAction::ALL
+                .into_iter()
+                .map(|action| OptionRow::Rebind(keymap, action))
+                .collect(),
+            OptionsPage::Gamepad => GameAction::ALL
+                .into_iter()
+                .map(OptionRow::PadRebind)
+                .chain([OptionRow::StickThreshold, OptionRow::PadOwners])
+                .collect(),
+            OptionsPage::Touch => vec![
+                OptionRow::TouchScheme,
+                OptionRow::TouchHand,
+                OptionRow::TouchSize,
+            ],
+        }
     }
 
     fn label(self) -> String {
@@ -109,29 +173,82 @@ impl OptionRow {
             OptionRow::HoldEnabled => "Hold".into(),
             OptionRow::GhostEnabled => "Ghost Piece".into(),
             OptionRow::Background => "Background".into(),
+            OptionRow::StackVisibility => "Stack".into(),
+            OptionRow::StackOutline => "Stack Outline".into(),
             OptionRow::LockDownMode => "Lock-Down".into(),
+            OptionRow::Das => "DAS".into(),
+            OptionRow::Arr => "ARR".into(),
+            OptionRow::Dcd => "DAS Cut".into(),
+            OptionRow::SoftDropFactor => "Soft Drop".into(),
             OptionRow::MusicVolume => "Music Volume".into(),
             OptionRow::SfxVolume => "SFX Volume".into(),
-            OptionRow::Rebind(action) => action.label().into(),
+            OptionRow::Open(page) => page.title().into(),
+            OptionRow::Rebind(_, action) | OptionRow::PadRebind(action) => action.label().into(),
+            OptionRow::StickThreshold => "Stick Threshold".into(),
+            OptionRow::PadOwners => "Pads".into(),
+            OptionRow::TouchScheme => "Scheme".into(),
+            OptionRow::TouchHand => "Hand".into(),
+            OptionRow::TouchSize => "Button Size".into(),
         }
     }
 
     /// The current value rendered on the right of the row.
-    fn value(self, settings: &GameSettings, rebind: &RebindState) -> String {
-        if let OptionRow::Rebind(action) = self
-            && rebind.capturing == Some(action)
-        {
-            return "press a key...".into();
+    fn value(self, settings: &GameSettings, rebind: &RebindState, pads: &PadAssignments) -> String {
+        match (self, rebind.capturing) {
+            (OptionRow::Rebind(keymap, action), Some(RebindTarget::Key(k, a)))
+                if (keymap, action) == (k, a) =>
+            {
+                return "press a key...".into();
+            }
+            (OptionRow::PadRebind(action), Some(RebindTarget::Pad(a))) if action == a => {
+                return "press a button...".into();
+            }
+            _ => {}
         }
         match self {
             OptionRow::NextCount => settings.next_count.to_string(),
             OptionRow::HoldEnabled => on_off(settings.hold_enabled),
             OptionRow::GhostEnabled => on_off(settings.ghost_enabled),
             OptionRow::Background => on_off(settings.background_enabled),
+            OptionRow::StackVisibility => settings.stack_visibility.label().into(),
+            OptionRow::StackOutline => on_off(settings.stack_outline),
             OptionRow::LockDownMode => lock_down_label(settings.lock_down_mode).into(),
+            OptionRow::Das => ms_label(settings.das_ms),
+            OptionRow::Arr if settings.arr_ms == 0 => "Instant".into(),
+            OptionRow::Arr => ms_label(settings.arr_ms),
+            OptionRow::Dcd if settings.dcd_ms == 0 => "Off".into(),
+            OptionRow::Dcd => ms_label(settings.dcd_ms),
+            OptionRow::SoftDropFactor => match settings.soft_drop_factor {
+                Some(sdf) => format!("{sdf}x"),
+                None => "Max".into(),
+            },
             OptionRow::MusicVolume => volume_label(settings.music_volume),
             OptionRow::SfxVolume => volume_label(settings.sfx_volume),
-            OptionRow::Rebind(action) => key_label(settings.keybinds.get(action).0),
+            OptionRow::Open(OptionsPage::Keys(keymap)) => {
+                let clashes = settings.keybind_conflicts(&Keymap::ALL);
+                if clashes
+                    .iter()
+                    .any(|c| c.first.0 == keymap || c.second.0 == keymap)
+                {
+                    "Conflict".into()
+                } else {
+                    "Edit".into()
+                }
+            }
+            OptionRow::Open(_) => "Edit".into(),
+            OptionRow::Rebind(keymap, action) => key_label(settings.keymap(keymap).get(action).0),
+            OptionRow::PadRebind(action) => button_label(settings.gamepad_binds.get(action).0),
+            OptionRow::StickThreshold => volume_label(settings.stick_threshold),
+            OptionRow::PadOwners => match (pads.pad(Keymap::P1), pads.pad(Keymap::P2)) {
+                (Some(_), Some(_)) => "P1 + P2".into(),
+                (Some(_), None) => "P1".into(),
+                (None, Some(_)) => "P2".into(),
+                (None, None) => "None".into(),
+            },
+            OptionRow::TouchScheme => settings.touch_scheme.label().into(),
+            OptionRow::TouchHand if settings.touch_layout.left_handed => "Left".into(),
+            OptionRow::TouchHand => "Right".into(),
+            OptionRow::TouchSize => volume_label(settings.touch_layout.scale),
         }
     }
 }
@@ -140,6 +257,10 @@ fn on_off(value: bool) -> String {
     if value { "On".into() } else { "Off".into() }
 }
 
+fn ms_label(ms: u32) -> String {
+    format!("{ms} ms")
+}
+
 fn volume_label(value: f32) -> String {
     format!("{}%", (value * 100.0).round() as i32)
 }
@@ -161,11 +282,24 @@ fn lock_down_label(mode: LockDownMode) -> &'static str {
 #[derive(Component)]
 struct OptionValueText(OptionRow);
 
-/// Tracks an in-progress keybind capture: while `capturing` is `Some`, the next
-/// key press rebinds that action and nav is suppressed.
+/// Marks a key page's conflict line, rewritten as bindings change.
+#[derive(Component)]
+struct ConflictWarningText;
+
+/// What a rebind capture is waiting for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RebindTarget {
+    /// A key, for one player's action.
+    Key(Keymap, GameAction),
+    /// A pad button, for an action on every pad.
+    Pad(GameAction),
+}
+
+/// Tracks an in-progress rebind capture: while `capturing` is `Some`, the next
+/// key (or pad button) press rebinds that action and nav is suppressed.
 #[derive(Resource, Default)]
 struct RebindState {
-    capturing: Option<GameAction>,
+    capturing: Option<RebindTarget>,
 }
 
 fn rebinding(state: Res<RebindState>) -> bool {
@@ -176,39 +310,68 @@ fn rebinding(state: Res<RebindState>) -> bool {
 // Setup: attach editor rows under the shell's OptionsRoot
 // ---------------------------------------------------------------------------
 
+#[allow(clippy::too_many_arguments)] // a Bevy system's params are its dependency list
 fn build_options_ui(
     mut commands: Commands,
     settings: Res<GameSettings>,
     rebind: Res<RebindState>,
+    pads: Res<PadAssignments>,
+    page: Res<OptionsPage>,
     assets: Res<GameAssets>,
     // `Single` skips the system on frames where the root was not just added.
-    root: Single<Entity, Added<OptionsRoot>>,
+    root: Single<(Entity, &mut Node), Added<OptionsRoot>>,
     existing: Query<(), With<OptionValueText>>,
 ) {
-    let root = *root;
+    let (root, mut root_node) = root.into_inner();
     // Defensive idempotency: never build the rows twice for one screen visit.
     if !existing.is_empty() {
         return;
     }
+    // Thirteen rows crowd a 720p window at the shell's menu spacing.
+    root_node.row_gap = px(2);
     // Rows + hint speak the working voice (Departure Mono, body size).
     let font = assets.font_body.clone();
-    let rows = OptionRow::all();
+    let rows = OptionRow::rows(*page);
 
     // The FocusList lives on the same entity carrying the screen marker the
     // focus helper is generic over (OptionsRoot), per the shared pattern.
     commands.entity(root).insert(FocusList::new(rows.len()));
 
     // A hint line so the controls are discoverable.
-    let hint = commands
-        .spawn(label_text(
-            "Up/Down select  -  Left/Right adjust  -  Enter toggle/rebind  -  Esc back",
-            font.clone(),
-        ))
-        .id();
+    let hint = match *page {
+        OptionsPage::General => {
+            "Up/Down select  -  Left/Right adjust  -  Enter toggle/open  -  Esc back"
+        }
+        OptionsPage::Keys(_) => "Up/Down select  -  Enter rebind  -  Esc back",
+        OptionsPage::Gamepad => {
+            "Up/Down select  -  Enter rebind/swap  -  Left/Right adjust  -  Esc back"
+        }
+        OptionsPage::Touch => "Up/Down select  -  Left/Right adjust  -  Esc back",
+    };
+    let hint = commands.spawn(label_text(hint, font.clone())).id();
     commands.entity(root).add_child(hint);
+    // Conflicts span both players' sets: in two-player versus a shared key
+    // would drive both boards.
+    if matches!(*page, OptionsPage::Keys(_)) {
+        let warning = commands
+            .spawn((
+                ConflictWarningText,
+                Text::new(
+                    conflict_warning(&settings.keybind_conflicts(&Keymap::ALL)).unwrap_or_default(),
+                ),
+                TextFont {
+                    font: font.clone(),
+                    font_size: theme::LABEL_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(theme::ACCENT),
+            ))
+            .id();
+        commands.entity(root).add_child(warning);
+    }
 
     for (index, row) in rows.into_iter().enumerate() {
-        let value = row.value(&settings, &rebind);
+        let value = row.value(&settings, &rebind, &pads);
         let entity = commands
             .spawn((
                 row,
@@ -263,19 +426,27 @@ fn build_options_ui(
 // ---------------------------------------------------------------------------
 
 /// Handle input against the focused row. Left/Right adjust numeric & enum
-/// settings, Enter toggles bools / cycles lock-down / starts a rebind (or, while
-/// capturing, the pressed key becomes the new binding). Persists after any
-/// change.
+/// settings, Enter toggles bools / cycles lock-down / opens a sub-page /
+/// starts a rebind / swaps the pads (or, while capturing a key, the pressed
+/// key becomes the new binding). A pad's D-pad and South do the same as the
+/// arrows and Enter. Persists after any change.
 ///
-/// Esc is owned by the screen shell (`screens/options.rs`), which exits to the
-/// main menu; this system never sets state. While capturing a rebind, Esc
-/// cancels the capture (no key is bound) — the shell still exits on that same
-/// Esc, which is the intuitive "get me out" behavior. Esc therefore can't be
-/// *bound* through the UI (it stays reserved for back/pause).
+/// Esc is owned by the screen shell (`screens/options.rs`), which steps back a
+/// page or exits to the main menu; this system only sets state to open a key
+/// page. While capturing a rebind, Esc cancels the capture (no key is bound) —
+/// the shell still steps back on that same Esc, which is the intuitive "get
+/// me out" behavior. Esc therefore can't be *bound* through the UI (it stays
+/// reserved for back/pause). A pad-button capture is finished by
+/// [`capture_pad_rebind`]; the keyboard can only cancel it.
+#[allow(clippy::too_many_arguments)] // a Bevy system's params are its dependency list
 fn edit_options(
     keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
+    mut pads: ResMut<PadAssignments>,
     mut settings: ResMut<GameSettings>,
     mut rebind: ResMut<RebindState>,
+    mut page: ResMut<OptionsPage>,
+    mut next: ResMut<NextState<GameState>>,
     // Stays a plain `Query` (not `Single`): the rebind-capture branch below must
     // run even on a frame with no/zero focus list, so the system can't be skipped.
     lists: Query<&FocusList, With<OptionsRoot>>,
@@ -283,13 +454,15 @@ fn edit_options(
     storage: Res<StorageResource>,
 ) {
     // --- Rebind capture takes priority over everything else. ---
-    if let Some(action) = rebind.capturing {
+    if let Some(target) = rebind.capturing {
         if keys.just_pressed(KeyCode::Escape) {
             rebind.capturing = None;
             return;
         }
-        if let Some(key) = first_just_pressed(&keys) {
-            settings.keybinds.set_primary(action, key);
+        if let RebindTarget::Key(keymap, action) = target
+            && let Some(key) = first_just_pressed(&keys)
+        {
+            settings.keymap_mut(keymap).set_primary(action, key);
             settings.sanitize();
             persist(&storage, &settings);
             rebind.capturing = None;
@@ -309,11 +482,14 @@ fn edit_options(
         return;
     };
 
-    let left = keys.just_pressed(KeyCode::ArrowLeft) || keys.just_pressed(KeyCode::KeyA);
-    let right = keys.just_pressed(KeyCode::ArrowRight) || keys.just_pressed(KeyCode::KeyD);
+    let left =
+        keys.just_pressed(KeyCode::ArrowLeft) || keys.just_pressed(KeyCode::KeyA) || pad.left;
+    let right =
+        keys.just_pressed(KeyCode::ArrowRight) || keys.just_pressed(KeyCode::KeyD) || pad.right;
     let activate = keys.just_pressed(KeyCode::Enter)
         || keys.just_pressed(KeyCode::NumpadEnter)
-        || keys.just_pressed(KeyCode::Space);
+        || keys.just_pressed(KeyCode::Space)
+        || pad.select;
 
     let mut changed = false;
     match row {
@@ -344,6 +520,21 @@ fn edit_options(
                 changed = true;
             }
         }
+        OptionRow::StackVisibility => {
+            if right || activate {
+                settings.stack_visibility = cycle_stack_visibility(settings.stack_visibility, 1);
+                changed = true;
+            } else if left {
+                settings.stack_visibility = cycle_stack_visibility(settings.stack_visibility, -1);
+                changed = true;
+            }
+        }
+        OptionRow::StackOutline => {
+            if left || right || activate {
+                settings.stack_outline = !settings.stack_outline;
+                changed = true;
+            }
+        }
         OptionRow::LockDownMode => {
             if right || activate {
                 settings.lock_down_mode = cycle_lock_down(settings.lock_down_mode, 1);
@@ -353,6 +544,42 @@ fn edit_options(
                 changed = true;
             }
         }
+        OptionRow::Das => {
+            if right {
+                settings.das_ms = settings.das_ms.saturating_add(HANDLING_STEP_MS);
+                changed = true;
+            } else if left {
+                settings.das_ms = settings.das_ms.saturating_sub(HANDLING_STEP_MS);
+                changed = true;
+            }
+        }
+        OptionRow::Arr => {
+            if right {
+                settings.arr_ms = settings.arr_ms.saturating_add(ARR_STEP_MS);
+                changed = true;
+            } else if left {
+                settings.arr_ms = settings.arr_ms.saturating_sub(ARR_STEP_MS);
+                changed = true;
+            }
+        }
+        OptionRow::Dcd => {
+            if right {
+                settings.dcd_ms = settings.dcd_ms.saturating_add(HANDLING_STEP_MS);
+                changed = true;
+            } else if left {
+                settings.dcd_ms = settings.dcd_ms.saturating_sub(HANDLING_STEP_MS);
+                changed = true;
+            }
+        }
+        OptionRow::SoftDropFactor => {
+            if right {
+                settings.soft_drop_factor = step_soft_drop_factor(settings.soft_drop_factor, 1);
+                changed = true;
+            } else if left {
+                settings.soft_drop_factor = step_soft_drop_factor(settings.soft_drop_factor, -1);
+                changed = true;
+            }
+        }
         OptionRow::MusicVolume => {
             if right {
                 settings.music_volume += VOLUME_STEP;
@@ -371,9 +598,58 @@ fn edit_options(
                 changed = true;
             }
         }
-        OptionRow::Rebind(action) => {
+        OptionRow::Open(target) => {
+            if activate {
+                *page = target;
+                next.set(GameState::Options);
+            }
+        }
+        OptionRow::Rebind(keymap, action) => {
             if activate {
-                rebind.capturing = Some(action);
+                rebind.capturing = Some(RebindTarget::Key(keymap, action));
+            }
+        }
+        OptionRow::PadRebind(action) => {
+            if activate {
+                rebind.capturing = Some(RebindTarget::Pad(action));
+            }
+        }
+        OptionRow::StickThreshold => {
+            if right {
+                settings.stick_threshold += STICK_THRESHOLD_STEP;
+                changed = true;
+            } else if left {
+                settings.stick_threshold -= STICK_THRESHOLD_STEP;
+                changed = true;
+            }
+        }
+        OptionRow::PadOwners => {
+            if left || right || activate {
+                pads.swap();
+            }
+        }
+        OptionRow::TouchScheme => {
+            if right || activate {
+                settings.touch_scheme = cycle_touch_scheme(settings.touch_scheme, 1);
+                changed = true;
+            } else if left {
+                settings.touch_scheme = cycle_touch_scheme(settings.touch_scheme, -1);
+                changed = true;
+            }
+        }
+        OptionRow::TouchHand => {
+            if left || right || activate {
+                settings.touch_layout.left_handed = !settings.touch_layout.left_handed;
+                changed = true;
+            }
+        }
+        OptionRow::TouchSize => {
+            if right {
+                settings.touch_layout.scale += TOUCH_SCALE_STEP;
+                changed = true;
+            } else if left {
+                settings.touch_layout.scale -= TOUCH_SCALE_STEP;
+                changed = true;
             }
         }
     }
@@ -394,6 +670,26 @@ fn edit_options(
 }
 
 const VOLUME_STEP: f32 = 0.1;
+const STICK_THRESHOLD_STEP: f32 = 0.05;
+const TOUCH_SCALE_STEP: f32 = 0.1;
+const HANDLING_STEP_MS: u32 = 10;
+const ARR_STEP_MS: u32 = 5;
+const SOFT_DROP_FACTOR_STEP: u32 = 5;
+
+/// One notch of the soft-drop factor: 5x..=40x in steps of 5, then "max"
+/// past the top (and back down from it). Stops at both ends.
+fn step_soft_drop_factor(sdf: Option<u32>, delta: i32) -> Option<u32> {
+    match (sdf, delta > 0) {
+        (None, true) => None,
+        (None, false) => Some(MAX_SOFT_DROP_FACTOR),
+        (Some(sdf), true) if sdf >= MAX_SOFT_DROP_FACTOR => None,
+        (Some(sdf), true) => Some(sdf + SOFT_DROP_FACTOR_STEP),
+        (Some(sdf), false) => Some(
+            sdf.saturating_sub(SOFT_DROP_FACTOR_STEP)
+                .max(MIN_SOFT_DROP_FACTOR),
+        ),
+    }
+}
 
 fn cycle_lock_down(mode: LockDownMode, delta: i32) -> LockDownMode {
     const ORDER: [LockDownMode; 3] = [
@@ -406,27 +702,87 @@ fn cycle_lock_down(mode: LockDownMode, delta: i32) -> LockDownMode {
     ORDER[next]
 }
 
+fn cycle_touch_scheme(scheme: TouchScheme, delta: i32) -> TouchScheme {
+    let all = TouchScheme::ALL;
+    let idx = all.iter().position(|&s| s == scheme).unwrap_or(0) as i32;
+    all[(idx + delta).rem_euclid(all.len() as i32) as usize]
+}
+
+fn cycle_stack_visibility(visibility: StackVisibility, delta: i32) -> StackVisibility {
+    let all = StackVisibility::ALL;
+    let idx = all.iter().position(|&v| v == visibility).unwrap_or(0) as i32;
+    all[(idx + delta).rem_euclid(all.len() as i32) as usize]
+}
+
 /// The first key that transitioned to pressed this frame, ignoring nothing —
 /// used to capture a rebind. Returns `None` if no key was just pressed.
 fn first_just_pressed(keys: &ButtonInput<KeyCode>) -> Option<KeyCode> {
     keys.get_just_pressed().next().copied()
 }
 
+/// Finish a pad-button capture: the first button any pad presses becomes the
+/// action's binding. Runs after [`MenuPadSystems`] and swallows the frame's
+/// menu edges while capturing, so the press (East included) never also
+/// navigates or backs out.
+fn capture_pad_rebind(
+    pads: Query<&Gamepad>,
+    mut menu: ResMut<MenuPad>,
+    mut settings: ResMut<GameSettings>,
+    mut rebind: ResMut<RebindState>,
+    storage: Res<StorageResource>,
+) {
+    let Some(RebindTarget::Pad(action)) = rebind.capturing else {
+        return;
+    };
+    *menu = MenuPad::default();
+    if let Some(&button) = pads.iter().find_map(|pad| pad.get_just_pressed().next()) {
+        settings.gamepad_binds.set_primary(action, button);
+        settings.sanitize();
+        persist(&storage, &settings);
+        rebind.capturing = None;
+    }
+}
+
 /// Rewrite each row's value text so the UI reflects the current settings (after
-/// an edit) and the "press a key..." prompt while capturing.
+/// an edit) and the "press a key..." prompt while capturing; the conflict line
+/// follows the bindings.
 fn refresh_option_rows(
     settings: Res<GameSettings>,
     rebind: Res<RebindState>,
-    mut texts: Query<(&OptionValueText, &mut Text)>,
+    pads: Res<PadAssignments>,
+    mut texts: Query<(&OptionValueText, &mut Text), Without<ConflictWarningText>>,
+    mut warnings: Query<&mut Text, With<ConflictWarningText>>,
 ) {
-    if !settings.is_changed() && !rebind.is_changed() {
+    if !settings.is_changed() && !rebind.is_changed() && !pads.is_changed() {
         return;
     }
     for (marker, mut text) in &mut texts {
-        text.0 = marker.0.value(&settings, &rebind);
+        text.0 = marker.0.value(&settings, &rebind, &pads);
+    }
+    let warning = conflict_warning(&settings.keybind_conflicts(&Keymap::ALL)).unwrap_or_default();
+    for mut text in &mut warnings {
+        text.0 = warning.clone();
     }
 }
 
+/// One line describing `conflicts` for the player, or `None` when there are
+/// none: the first clash spelled out, the rest counted.
+pub(crate) fn conflict_warning(conflicts: &[KeyConflict]) -> Option<String> {
+    let first = conflicts.first()?;
+    let slot =
+        |(keymap, action): (Keymap, GameAction)| format!("{} {}", keymap.label(), action.label());
+    let mut line = format!(
+        "Key conflict: {} is {} and {}",
+        key_label(first.key),
+        slot(first.first),
+        slot(first.second)
+    );
+    if conflicts.len() > 1 {
+        line.push_str(&format!(" (+{} more)", conflicts.len() - 1));
+    }
+    Some(line)
+}
+
 fn clear_rebind_state(mut rebind: ResMut<RebindState>) {
     rebind.capturing = None;
 }
@@ -497,6 +853,7 @@ const KEY_TABLE: &[(KeyCode, &str)] = &[
     (KeyCode::ArrowUp, "Up"), (KeyCode::ArrowDown, "Down"),
     (KeyCode::Space, "Space"), (KeyCode::Enter, "Enter"),
     (KeyCode::Escape, "Esc"), (KeyCode::Tab, "Tab"),
+    (KeyCode::Backspace, "Backspace"),
     (KeyCode::ShiftLeft, "LShift"), (KeyCode::ShiftRight, "RShift"),
     (KeyCode::ControlLeft, "LCtrl"), (KeyCode::ControlRight, "RCtrl"),
     (KeyCode::AltLeft, "LAlt"), (KeyCode::AltRight, "RAlt"),
@@ -518,6 +875,27 @@ const KEY_TABLE: &[(KeyCode, &str)] = &[
     (KeyCode::Digit9, "9"),
 ];
 
+/// Short label for a bound pad button, in the Xbox-style layout most pads
+/// report through. Falls back to the `Debug` name outside the table.
+fn button_label(button: GamepadButton) -> String {
+    BUTTON_TABLE
+        .iter()
+        .find(|(b, _)| *b == button)
+        .map_or_else(|| format!("{button:?}"), |(_, label)| (*label).to_string())
+}
+
+#[rustfmt::skip]
+const BUTTON_TABLE: &[(GamepadButton, &str)] = &[
+    (GamepadButton::South, "South"), (GamepadButton::East, "East"),
+    (GamepadButton::North, "North"), (GamepadButton::West, "West"),
+    (GamepadButton::DPadUp, "D-Up"), (GamepadButton::DPadDown, "D-Down"),
+    (GamepadButton::DPadLeft, "D-Left"), (GamepadButton::DPadRight, "D-Right"),
+    (GamepadButton::LeftTrigger, "LB"), (GamepadButton::RightTrigger, "RB"),
+    (GamepadButton::LeftTrigger2, "LT"), (GamepadButton::RightTrigger2, "RT"),
+    (GamepadButton::LeftThumb, "L3"), (GamepadButton::RightThumb, "R3"),
+    (GamepadButton::Select, "Select"), (GamepadButton::Start, "Start"),
+];
+
 // ---------------------------------------------------------------------------
 // Keybind read-path for the controller (migration helper)
 // ---------------------------------------------------------------------------
@@ -575,6 +953,38 @@ mod tests {
     // UI itself — row coverage, lock-down cycling, key labels, and the input
     // read-path the gameplay driver calls.
 
+    #[test]
+    fn the_touch_scheme_cycles_through_off_and_back() {
+        assert_eq!(
+            cycle_touch_scheme(TouchScheme::Buttons, 1),
+            TouchScheme::Swipe
+        );
+        assert_eq!(
+            cycle_touch_scheme(TouchScheme::Off, 1),
+            TouchScheme::Buttons
+        );
+        assert_eq!(
+            cycle_touch_scheme(TouchScheme::Buttons, -1),
+            TouchScheme::Off
+        );
+    }
+
+    #[test]
+    fn the_stack_visibility_cycles_through_the_challenges() {
+        assert_eq!(
+            cycle_stack_visibility(StackVisibility::Visible, 1),
+            StackVisibility::Fading
+        );
+        assert_eq!(
+            cycle_stack_visibility(StackVisibility::Invisible, 1),
+            StackVisibility::Visible
+        );
+        assert_eq!(
+            cycle_stack_visibility(StackVisibility::Visible, -1),
+            StackVisibility::Invisible
+        );
+    }
+
     #[test]
     fn lock_down_cycles_forward_and_back() {
         assert_eq!(
@@ -592,31 +1002,94 @@ mod tests {
     }
 
     #[test]
-    fn all_rows_cover_fixed_plus_every_action() {
-        let rows = OptionRow::all();
-        assert_eq!(rows.len(), OptionRow::FIXED.len() + GameAction::ALL.len());
+    fn the_soft_drop_factor_steps_up_to_max_and_back() {
+        assert_eq!(step_soft_drop_factor(Some(20), 1), Some(25));
+        assert_eq!(
+            step_soft_drop_factor(Some(MAX_SOFT_DROP_FACTOR), 1),
+            None,
+            "past the top is max"
+        );
+        assert_eq!(step_soft_drop_factor(None, 1), None, "max is the end");
+        assert_eq!(step_soft_drop_factor(None, -1), Some(MAX_SOFT_DROP_FACTOR));
+        assert_eq!(
+            step_soft_drop_factor(Some(MIN_SOFT_DROP_FACTOR), -1),
+            Some(MIN_SOFT_DROP_FACTOR)
+        );
+    }
+
+    #[test]
+    fn pages_cover_fixed_rows_and_every_action_per_keymap() {
+        let general = OptionRow::rows(OptionsPage::General);
+        assert_eq!(
+            general.len(),
+            OptionRow::FIXED.len() + Keymap::ALL.len() + 2
+        );
+        assert!(general.contains(&OptionRow::Open(OptionsPage::Gamepad)));
+        assert!(general.contains(&OptionRow::Open(OptionsPage::Touch)));
+        for keymap in Keymap::ALL {
+            assert!(general.contains(&OptionRow::Open(OptionsPage::Keys(keymap))));
+            let keys = OptionRow::rows(OptionsPage::Keys(keymap));
+            assert_eq!(keys.len(), GameAction::ALL.len());
+            for action in GameAction::ALL {
+                assert!(keys.contains(&OptionRow::Rebind(keymap, action)));
+            }
+        }
+    }
+
+    #[test]
+    fn the_gamepad_page_rebinds_every_action_and_labels_the_defaults() {
+        let rows = OptionRow::rows(OptionsPage::Gamepad);
+        assert_eq!(rows.len(), GameAction::ALL.len() + 2);
+        let binds = GameSettings::default().gamepad_binds;
         for action in GameAction::ALL {
-            assert!(rows.contains(&OptionRow::Rebind(action)));
+            assert!(rows.contains(&OptionRow::PadRebind(action)));
+            let (primary, secondary) = binds.get(action);
+            for button in std::iter::once(primary).chain(secondary) {
+                assert!(
+                    BUTTON_TABLE.iter().any(|(b, _)| *b == button),
+                    "{button:?} has no friendly label"
+                );
+            }
         }
+        assert_eq!(button_label(GamepadButton::DPadUp), "D-Up");
+        assert_eq!(button_label(GamepadButton::Mode), "Mode");
+    }
+
+    #[test]
+    fn a_conflict_names_the_key_and_both_slots() {
+        assert_eq!(conflict_warning(&[]), None);
+        let mut settings = GameSettings::default();
+        settings
+            .keybinds_p2
+            .set_primary(GameAction::HardDrop, KeyCode::Space);
+        settings
+            .keybinds_p2
+            .set_primary(GameAction::Hold, KeyCode::KeyZ);
+        assert_eq!(
+            conflict_warning(&settings.keybind_conflicts(&Keymap::ALL)).as_deref(),
+            Some("Key conflict: Space is P1 Hard Drop and P2 Hard Drop (+1 more)")
+        );
     }
 
     #[test]
     fn every_default_keybind_has_a_friendly_label() {
         // Persistence now serializes `KeyCode` directly, so the table is display
         // only — but every default binding should still render as a friendly label
-        // (a table token) in the rebind UI, not a raw `Debug` name.
-        let binds = Keybinds::default();
-        for action in GameAction::ALL {
-            let (primary, secondary) = binds.get(action);
-            assert!(
-                key_code_token(primary).is_some(),
-                "{action:?} primary has no friendly label"
-            );
-            if let Some(sec) = secondary {
+        // (a table token) in the rebind UI, not a raw `Debug` name — both
+        // players' sets.
+        for binds in [Keybinds::default(), Keybinds::player_two()] {
+            for action in GameAction::ALL {
+                let (primary, secondary) = binds.get(action);
                 assert!(
-                    key_code_token(sec).is_some(),
-                    "{action:?} secondary has no friendly label"
+                    key_code_token(primary).is_some(),
+                    "{action:?} primary has no friendly label"
                 );
+                if let Some(sec) = secondary {
+                    assert!(
+                        key_code_token(sec).is_some(),
+                        "{action:?} secondary has no friendly label"
+                    );
+                }
             }
         }
     }
@@ -0,0 +1,387 @@
+//! Touch feature: on-screen controls for phones and tablets.
+//!
+//! Nothing shows until the first touch, so desktop play never sees it. From
+//! then on, while a session runs, the player's [`TouchScheme`] decides how
+//! fingers play:
+//!
+//! * **Buttons** — a round button per action, placed by the
+//!   [`TouchLayout`] in settings. A button is held while any finger rests on
+//!   it, so a held arrow charges DAS like a key.
+//! * **Swipe** — the pure [`GestureRecognizer`](super::gesture) reads drags,
+//!   flicks and taps.
+//!
+//! Either way the result is one [`RawKeyboardFrame`] a frame in
+//! [`TouchFrame`], which Player 1's seat folds in next to the keyboard and
+//! pads, so touch feeds the same `KeyboardController` path. A pause button
+//! stays in the corner under both schemes. While the controls show on a
+//! portrait screen, the session is framed [`SceneOrientation::Portrait`] so
+//! the boards clear the bottom of the screen for the buttons.
+
+use bevy::input::InputSystems;
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+use super::gesture::{GestureConfig, GestureRecognizer, TouchSample};
+use crate::GameState;
+use crate::assets::GameAssets;
+use crate::player::RawKeyboardFrame;
+use crate::session::render::SceneOrientation;
+use crate::settings::{GameAction, GameSettings, TouchLayout, TouchScheme};
+use crate::ui::widgets::theme;
+
+/// Touch controls: reading, drawing, and framing.
+pub struct TouchPlugin;
+
+impl Plugin for TouchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TouchControls>()
+            .init_resource::<TouchFrame>()
+            .init_resource::<SceneOrientation>()
+            .add_systems(
+                PreUpdate,
+                (
+                    notice_touch,
+                    read_touch_controls.run_if(in_state(GameState::Session)),
+                )
+                    .chain()
+                    .in_set(TouchSystems)
+                    .after(InputSystems),
+            )
+            .add_systems(OnEnter(GameState::Session), spawn_touch_deck)
+            .add_systems(OnExit(GameState::Session), clear_touch_frame)
+            .add_systems(
+                Update,
+                (orient_scene, layout_touch_deck).run_if(in_state(GameState::Session)),
+            );
+    }
+}
+
+/// Where [`TouchFrame`] is written; the session latches input after it.
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub(crate) struct TouchSystems;
+
+/// Whether this device has been touched yet; the controls stay hidden until
+/// it has.
+#[derive(Resource, Debug, Default)]
+pub struct TouchControls {
+    seen: bool,
+}
+
+impl TouchControls {
+    /// Whether the controls are showing under `scheme`.
+    pub fn active(&self, scheme: TouchScheme) -> bool {
+        self.seen && scheme != TouchScheme::Off
+    }
+}
+
+/// This frame's touch input, shaped like the keyboard's.
+#[derive(Resource, Debug, Default)]
+pub struct TouchFrame {
+    pub input: RawKeyboardFrame,
+    /// Which buttons a finger rests on, indexed by [`GameAction`].
+    held: [bool; 8],
+    recognizer: GestureRecognizer,
+    /// The window size the recognizer's drag cell was sized for.
+    screen: Vec2,
+    /// Fingers that came down on the pause button: under the swipe scheme
+    /// they are no gesture.
+    on_pause: Vec<u64>,
+}
+
+/// Button radius over the window's shorter side, at layout scale 1.
+const BUTTON_RADIUS: f32 = 0.08;
+/// The pause button is smaller than the play buttons.
+const PAUSE_SCALE: f32 = 0.6;
+
+/// `action`'s button on a `screen`-sized window: center and radius, in
+/// logical pixels from the top-left corner.
+fn button_circle(layout: &TouchLayout, action: GameAction, screen: Vec2) -> (Vec2, f32) {
+    let mut radius = BUTTON_RADIUS * layout.scale * screen.min_element();
+    if action == GameAction::Pause {
+        radius *= PAUSE_SCALE;
+    }
+    (layout.center(action) * screen, radius)
+}
+
+/// The button under `point`, if any (the nearest, where two overlap).
+fn button_at(layout: &TouchLayout, point: Vec2, screen: Vec2) -> Option<GameAction> {
+    GameAction::ALL
+        .into_iter()
+        .map(|action| {
+            let (center, radius) = button_circle(layout, action, screen);
+            (action, center.distance(point), radius)
+        })
+        .filter(|&(_, distance, radius)| distance <= radius)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(action, ..)| action)
+}
+
+/// The frame the held buttons produce: `held` now, against `was` last frame.
+fn buttons_frame(held: [bool; 8], was: [bool; 8], dt: f32) -> RawKeyboardFrame {
+    let pressed = |action: GameAction| held[action as usize];
+    let just = |action: GameAction| held[action as usize] && !was[action as usize];
+    RawKeyboardFrame {
+        dt_seconds: dt,
+        left_pressed: pressed(GameAction::MoveLeft),
+        right_pressed: pressed(GameAction::MoveRight),
+        left_just_pressed: just(GameAction::MoveLeft),
+        right_just_pressed: just(GameAction::MoveRight),
+        soft_drop: pressed(GameAction::SoftDrop),
+        hard_drop_just_pressed: just(GameAction::HardDrop),
+        rotate_cw_just_pressed: just(GameAction::RotateCw),
+        rotate_ccw_just_pressed: just(GameAction::RotateCcw),
+        hold_just_pressed: just(GameAction::Hold),
+        pause_just_pressed: just(GameAction::Pause),
+    }
+}
+
+fn notice_touch(mut touches: MessageReader<TouchInput>, mut controls: ResMut<TouchControls>) {
+    if !controls.seen && touches.read().any(|t| t.phase == TouchPhase::Started) {
+        controls.seen = true;
+    }
+}
+
+fn read_touch_controls(
+    mut touches: MessageReader<TouchInput>,
+    resting: Res<Touches>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    time: Res<Time>,
+    settings: Res<GameSettings>,
+    controls: Res<TouchControls>,
+    mut frame: ResMut<TouchFrame>,
+) {
+    let frame = &mut *frame;
+    let samples: Vec<TouchSample> = touches
+        .read()
+        .map(|t| TouchSample {
+            id: t.id,
+            phase: t.phase,
+            position: t.position,
+        })
+        .collect();
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    if !controls.active(settings.touch_scheme) {
+        frame.input = RawKeyboardFrame::default();
+        return;
+    }
+    let screen = window.size();
+    let layout = &settings.touch_layout;
+    let dt = time.delta_secs();
+    if frame.screen != screen && !frame.recognizer.is_touching() {
+        frame.recognizer = GestureRecognizer::new(GestureConfig::for_screen(screen));
+        frame.screen = screen;
+    }
+
+    let was = frame.held;
+    frame.held = [false; 8];
+    for touch in resting.iter() {
+        if let Some(action) = button_at(layout, touch.position(), screen) {
+            frame.held[action as usize] = true;
+        }
+    }
+
+    frame.input = match settings.touch_scheme {
+        TouchScheme::Buttons | TouchScheme::Off => buttons_frame(frame.held, was, dt),
+        TouchScheme::Swipe => {
+            // Only the pause button is live; every other finger is a gesture.
+            let pause = GameAction::Pause as usize;
+            let paused = frame.held[pause];
+            frame.held = [false; 8];
+            frame.held[pause] = paused;
+            let mut gestures = Vec::with_capacity(samples.len());
+            for sample in samples {
+                let on_pause = frame.on_pause.contains(&sample.id);
+                match sample.phase {
+                    TouchPhase::Started
+                        if button_at(layout, sample.position, screen)
+                            == Some(GameAction::Pause) =>
+                    {
+                        frame.on_pause.push(sample.id);
+                    }
+                    TouchPhase::Ended | TouchPhase::Canceled if on_pause => {
+                        frame.on_pause.retain(|&id| id != sample.id);
+                    }
+                    _ if on_pause => {}
+                    _ => gestures.push(sample),
+                }
+            }
+            let mut input = frame.recognizer.frame(&gestures, dt, screen);
+            input.pause_just_pressed = frame.held[pause] && !was[pause];
+            input
+        }
+    };
+}
+
+/// Leaving the session drops any half-read gesture and held button.
+fn clear_touch_frame(mut frame: ResMut<TouchFrame>) {
+    *frame = TouchFrame::default();
+}
+
+/// Frame the scene portrait while the controls show on a tall screen.
+fn orient_scene(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    controls: Res<TouchControls>,
+    mut orientation: ResMut<SceneOrientation>,
+) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let portrait = controls.active(settings.touch_scheme) && window.height() > window.width();
+    orientation.set_if_neq(if portrait {
+        SceneOrientation::Portrait
+    } else {
+        SceneOrientation::Landscape
+    });
+}
+
+/// One on-screen button; [`layout_touch_deck`] places and shows it.
+#[derive(Component)]
+struct TouchButton(GameAction);
+
+fn button_label(action: GameAction) -> &'static str {
+    match action {
+        GameAction::MoveLeft => "<",
+        GameAction::MoveRight => ">",
+        GameAction::SoftDrop => "v",
+        GameAction::HardDrop => "DROP",
+        GameAction::RotateCw => "CW",
+        GameAction::RotateCcw => "CCW",
+        GameAction::Hold => "HOLD",
+        GameAction::Pause => "II",
+    }
+}
+
+fn spawn_touch_deck(mut commands: Commands, assets: Res<GameAssets>) {
+    for action in GameAction::ALL {
+        commands.spawn((
+            TouchButton(action),
+            Node {
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                border: UiRect::all(px(2)),
+                border_radius: BorderRadius::MAX,
+                ..default()
+            },
+            BackgroundColor(theme::FRAME.with_alpha(0.45)),
+            BorderColor::all(theme::TEXT_DIM.with_alpha(0.7)),
+            Visibility::Hidden,
+            DespawnOnExit(GameState::Session),
+            children![(
+                Text::new(button_label(action)),
+                TextFont {
+                    font: assets.font_body.clone(),
+                    font_size: theme::MICRO_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(theme::TEXT),
+            )],
+        ));
+    }
+}
+
+/// Place every button on its circle, light the held ones, and show only what
+/// the scheme uses.
+fn layout_touch_deck(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    settings: Res<GameSettings>,
+    controls: Res<TouchControls>,
+    frame: Res<TouchFrame>,
+    mut buttons: Query<(
+        &TouchButton,
+        &mut Node,
+        &mut Visibility,
+        &mut BackgroundColor,
+    )>,
+) {
+    let Ok(window) = windows.single() else {
+        return;
+    };
+    let screen = window.size();
+    let active = controls.active(settings.touch_scheme);
+    for (button, mut node, mut visibility, mut background) in &mut buttons {
+        let shown = active
+            && (button.0 == GameAction::Pause || settings.touch_scheme == TouchScheme::Buttons);
+        visibility.set_if_neq(if shown {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        });
+        if !shown {
+            continue;
+        }
+        let (center, radius) = button_circle(&settings.touch_layout, button.0, screen);
+        let (left, top, size) = (
+            px(center.x - radius),
+            px(center.y - radius),
+            px(2.0 * radius),
+        );
+        if node.left != left || node.top != top || node.width != size {
+            node.left = left;
+            node.top = top;
+            node.width = size;
+            node.height = size;
+        }
+        let tint = if frame.held[button.0 as usize] {
+            theme::ACCENT.with_alpha(0.6)
+        } else {
+            theme::FRAME.with_alpha(0.45)
+        };
+        background.set_if_neq(BackgroundColor(tint));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCREEN: Vec2 = Vec2::new(400.0, 800.0);
+
+    #[test]
+    fn every_button_is_hit_at_its_center_and_none_overlap() {
+        for layout in [
+            TouchLayout::default(),
+            TouchLayout {
+                left_handed: true,
+                ..TouchLayout::default()
+            },
+        ] {
+            for action in GameAction::ALL {
+                let (center, _) = button_circle(&layout, action, SCREEN);
+                assert_eq!(button_at(&layout, center, SCREEN), Some(action));
+            }
+            for (i, a) in GameAction::ALL.into_iter().enumerate() {
+                for b in GameAction::ALL.into_iter().skip(i + 1) {
+                    let (ca, ra) = button_circle(&layout, a, SCREEN);
+                    let (cb, rb) = button_circle(&layout, b, SCREEN);
+                    assert!(ca.distance(cb) > ra + rb, "{a:?} overlaps {b:?}");
+                }
+            }
+        }
+        // The board's half of a portrait screen is button-free.
+        assert_eq!(
+            button_at(
+                &TouchLayout::default(),
+                SCREEN * Vec2::new(0.5, 0.4),
+                SCREEN
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn a_held_button_presses_once_then_holds() {
+        let mut held = [false; 8];
+        held[0] = true; // MoveLeft
+        let first = buttons_frame(held, [false; 8], 0.016);
+        assert!(first.left_pressed && first.left_just_pressed);
+        let second = buttons_frame(held, held, 0.016);
+        assert!(second.left_pressed && !second.left_just_pressed);
+        let released = buttons_frame([false; 8], held, 0.016);
+        assert!(!released.left_pressed);
+    }
+}
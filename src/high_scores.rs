@@ -1,6 +1,8 @@
 //! High-score model: the shared tables every screen and the session read.
 //!
-//! [`HighScores`] holds a top-10 leaderboard per [`Variant`]. The *primary* sort
+//! [`HighScores`] holds a top-10 leaderboard per [`Variant`] and
+//! [`StackChallenge`] — a run with a fading or invisible stack never ranks
+//! against one that could see its stack. The *primary* sort
 //! key is variant-specific (Sprint ranks by elapsed time ascending — fastest
 //! first; Marathon/Ultra rank by score descending — highest first), driven by
 //! the variant's [`ScoreKind`]. The model is in-memory here; the high-scores
@@ -12,6 +14,7 @@
 
 use bevy::prelude::*;
 
+use crate::settings::StackChallenge;
 use crate::variant::{ScoreKind, Variant};
 
 /// Maximum entries retained per variant.
@@ -21,31 +24,40 @@ pub const MAX_ENTRIES_PER_VARIANT: usize = 10;
 ///
 /// Both `score` and `time_seconds` are always recorded; which one is the
 /// *primary* ranking key is decided by the variant's [`ScoreKind`]. `lines` and
-/// `level` are kept for display.
+/// `level` are kept for display; a Master run files its grade as the `level`
+/// (`EngineSnapshot::result_level`), which then ranks it.
 #[derive(Debug, Clone, Copy, PartialEq, Reflect)]
 pub struct HighScore {
     pub score: usize,
     pub time_seconds: f32,
     pub lines: usize,
     pub level: u8,
+    /// The stack challenge the run was played under; picks its board.
+    pub stack: StackChallenge,
+    /// The run's replay in the [`ReplayLibrary`](crate::replays::ReplayLibrary).
+    /// Only a board's best entry keeps one (see
+    /// [`HighScores::set_best_replay`]): it is the run the next Sprint races.
+    pub replay: Option<u64>,
 }
 
 impl HighScore {
     /// Compare `self` against `other` for `kind`, returning `true` when `self`
     /// ranks strictly *better* (should sort earlier). Sprint: lower time wins;
-    /// Score modes: higher score wins.
+    /// Score modes: higher score wins; Master: higher grade, then score.
     fn is_better_than(&self, other: &HighScore, kind: ScoreKind) -> bool {
         match kind {
             ScoreKind::Time => self.time_seconds < other.time_seconds,
             ScoreKind::Score => self.score > other.score,
+            ScoreKind::Grade => (self.level, self.score) > (other.level, other.score),
         }
     }
 }
 
-/// Per-variant top-10 boards: one slot per [`Variant`], indexed by its position in
-/// [`Variant::ALL`]. Keying by index — rather than a named field plus a `match`
-/// arm per variant — means adding a mode is a single edit to `Variant`, not a
-/// shotgun edit across this file.
+/// Per-variant top-10 boards: one slot per [`Variant`] and [`StackChallenge`],
+/// indexed by their positions in [`Variant::ALL`] and [`StackChallenge::ALL`].
+/// Keying by index — rather than a named field plus a `match` arm per
+/// variant — means adding a mode is a single edit to `Variant`, not a shotgun
+/// edit across this file.
 #[derive(Resource, Debug, Clone, Reflect)]
 #[reflect(Resource)]
 pub struct HighScores {
@@ -54,36 +66,43 @@ pub struct HighScores {
 
 impl Default for HighScores {
     fn default() -> Self {
-        // One empty board per variant; slots line up with `Variant::ALL`.
+        // One empty board per variant and challenge; slots line up with
+        // `Variant::ALL` × `StackChallenge::ALL`.
         Self {
-            tables: vec![Vec::new(); Variant::ALL.len()],
+            tables: vec![Vec::new(); Variant::ALL.len() * StackChallenge::ALL.len()],
         }
     }
 }
 
 impl HighScores {
-    /// `variant`'s slot index (its position in [`Variant::ALL`]).
-    fn slot(variant: Variant) -> usize {
-        Variant::ALL
+    /// The slot index of `variant`'s `stack` board.
+    fn slot(variant: Variant, stack: StackChallenge) -> usize {
+        let variant = Variant::ALL
             .iter()
             .position(|v| *v == variant)
-            .expect("Variant::ALL contains every variant")
+            .expect("Variant::ALL contains every variant");
+        let stack = StackChallenge::ALL
+            .iter()
+            .position(|c| *c == stack)
+            .expect("StackChallenge::ALL contains every (normalized) challenge");
+        variant * StackChallenge::ALL.len() + stack
     }
 
-    /// The (sorted, best-first) table for `variant`.
-    pub fn table(&self, variant: Variant) -> &[HighScore] {
-        &self.tables[Self::slot(variant)]
+    /// The (sorted, best-first) table for `variant` under `stack`.
+    pub fn table(&self, variant: Variant, stack: StackChallenge) -> &[HighScore] {
+        &self.tables[Self::slot(variant, stack)]
     }
 
-    fn table_mut(&mut self, variant: Variant) -> &mut Vec<HighScore> {
-        &mut self.tables[Self::slot(variant)]
+    fn table_mut(&mut self, variant: Variant, stack: StackChallenge) -> &mut Vec<HighScore> {
+        &mut self.tables[Self::slot(variant, stack)]
     }
 
-    /// Whether `candidate` would make `variant`'s top-10 (the board isn't full,
-    /// or `candidate` ranks better than the current worst entry).
+    /// Whether `candidate` would make the top-10 of `variant`'s board for its
+    /// stack challenge (the board isn't full, or `candidate` ranks better than
+    /// the current worst entry).
     pub fn qualifies(&self, variant: Variant, candidate: &HighScore) -> bool {
         let kind = variant.def().score_kind;
-        let table = self.table(variant);
+        let table = self.table(variant, candidate.stack);
         if table.len() < MAX_ENTRIES_PER_VARIANT {
             return true;
         }
@@ -94,7 +113,7 @@ impl HighScores {
             .is_some_and(|worst| candidate.is_better_than(worst, kind))
     }
 
-    /// Insert `candidate` into `variant`'s board, keeping it sorted best-first
+    /// Insert `candidate` into `variant`'s board for its stack challenge, keeping it sorted best-first
     /// and truncated to the top 10. Returns the candidate's 0-based rank if it
     /// landed on the board, or `None` if it did not qualify.
     pub fn insert(&mut self, variant: Variant, candidate: HighScore) -> Option<usize> {
@@ -102,7 +121,7 @@ impl HighScores {
             return None;
         }
         let kind = variant.def().score_kind;
-        let table = self.table_mut(variant);
+        let table = self.table_mut(variant, candidate.stack);
         // Find the first existing entry the candidate beats; insert before it.
         let position = table
             .iter()
@@ -112,11 +131,31 @@ impl HighScores {
         table.truncate(MAX_ENTRIES_PER_VARIANT);
         Some(position)
     }
+
+    /// Attach replay `id` to the best entry on `variant`'s standard board,
+    /// detaching any replay the entries below it still name (the library
+    /// keeps only the best). Challenge boards keep no replays: the library
+    /// holds one personal best per variant, and it is the standard one.
+    pub fn set_best_replay(&mut self, variant: Variant, id: u64) {
+        let table = self.table_mut(variant, StackChallenge::STANDARD);
+        for (rank, entry) in table.iter_mut().enumerate() {
+            entry.replay = (rank == 0).then_some(id);
+        }
+    }
+
+    /// The replay attached to the best entry on `variant`'s standard board,
+    /// if any.
+    pub fn best_replay(&self, variant: Variant) -> Option<u64> {
+        self.table(variant, StackChallenge::STANDARD)
+            .first()
+            .and_then(|best| best.replay)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::settings::StackVisibility;
 
     fn run(score: usize, time: f32) -> HighScore {
         HighScore {
@@ -124,6 +163,8 @@ mod tests {
             time_seconds: time,
             lines: 0,
             level: 1,
+            stack: StackChallenge::STANDARD,
+            replay: None,
         }
     }
 
@@ -141,7 +182,7 @@ mod tests {
         boards.insert(Variant::Marathon, run(200, 10.0));
 
         let scores: Vec<usize> = boards
-            .table(Variant::Marathon)
+            .table(Variant::Marathon, StackChallenge::STANDARD)
             .iter()
             .map(|s| s.score)
             .collect();
@@ -156,13 +197,32 @@ mod tests {
         boards.insert(Variant::Sprint, run(0, 45.0));
 
         let times: Vec<f32> = boards
-            .table(Variant::Sprint)
+            .table(Variant::Sprint, StackChallenge::STANDARD)
             .iter()
             .map(|s| s.time_seconds)
             .collect();
         assert_eq!(times, vec![30.0, 45.0, 60.0]);
     }
 
+    #[test]
+    fn master_ranks_by_grade_then_score() {
+        let mut boards = HighScores::default();
+        let graded = |grade, score| HighScore {
+            level: grade,
+            ..run(score, 0.0)
+        };
+        boards.insert(Variant::Master, graded(9, 50_000));
+        boards.insert(Variant::Master, graded(12, 20_000));
+        boards.insert(Variant::Master, graded(9, 70_000));
+
+        let ranked: Vec<(u8, usize)> = boards
+            .table(Variant::Master, StackChallenge::STANDARD)
+            .iter()
+            .map(|s| (s.level, s.score))
+            .collect();
+        assert_eq!(ranked, vec![(12, 20_000), (9, 70_000), (9, 50_000)]);
+    }
+
     #[test]
     fn board_keeps_only_top_ten_and_reports_rank() {
         let mut boards = HighScores::default();
@@ -170,12 +230,21 @@ mod tests {
             // Scores 10, 20, ... 100.
             boards.insert(Variant::Ultra, run((i + 1) * 10, 0.0));
         }
-        assert_eq!(boards.table(Variant::Ultra).len(), MAX_ENTRIES_PER_VARIANT);
+        assert_eq!(
+            boards.table(Variant::Ultra, StackChallenge::STANDARD).len(),
+            MAX_ENTRIES_PER_VARIANT
+        );
 
         // A new best lands at rank 0 and evicts the worst.
         assert_eq!(boards.insert(Variant::Ultra, run(999, 0.0)), Some(0));
-        assert_eq!(boards.table(Variant::Ultra).len(), MAX_ENTRIES_PER_VARIANT);
-        assert_eq!(boards.table(Variant::Ultra)[0].score, 999);
+        assert_eq!(
+            boards.table(Variant::Ultra, StackChallenge::STANDARD).len(),
+            MAX_ENTRIES_PER_VARIANT
+        );
+        assert_eq!(
+            boards.table(Variant::Ultra, StackChallenge::STANDARD)[0].score,
+            999
+        );
 
         // A score below the (new) worst does not qualify.
         assert!(!boards.qualifies(Variant::Ultra, &run(5, 0.0)));
@@ -188,11 +257,68 @@ mod tests {
         // empty board per variant) and inserting into one never aliases another.
         let mut boards = HighScores::default();
         for variant in Variant::ALL {
-            assert!(boards.table(variant).is_empty());
+            assert!(boards.table(variant, StackChallenge::STANDARD).is_empty());
         }
         boards.insert(Variant::Marathon, run(100, 0.0));
-        assert_eq!(boards.table(Variant::Marathon).len(), 1);
-        assert!(boards.table(Variant::Sprint).is_empty());
-        assert!(boards.table(Variant::Ultra).is_empty());
+        assert_eq!(
+            boards
+                .table(Variant::Marathon, StackChallenge::STANDARD)
+                .len(),
+            1
+        );
+        assert!(
+            boards
+                .table(Variant::Sprint, StackChallenge::STANDARD)
+                .is_empty()
+        );
+        assert!(
+            boards
+                .table(Variant::Ultra, StackChallenge::STANDARD)
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn only_the_best_entry_keeps_its_replay() {
+        let mut boards = HighScores::default();
+        boards.insert(Variant::Sprint, run(0, 60.0));
+        boards.set_best_replay(Variant::Sprint, 1);
+        assert_eq!(boards.best_replay(Variant::Sprint), Some(1));
+
+        // A faster run takes first place and the old PB's replay lets go.
+        boards.insert(Variant::Sprint, run(0, 50.0));
+        assert_eq!(boards.best_replay(Variant::Sprint), None);
+        boards.set_best_replay(Variant::Sprint, 2);
+        let replays: Vec<Option<u64>> = boards
+            .table(Variant::Sprint, StackChallenge::STANDARD)
+            .iter()
+            .map(|s| s.replay)
+            .collect();
+        assert_eq!(replays, vec![Some(2), None]);
+    }
+
+    #[test]
+    fn each_stack_challenge_ranks_on_its_own_board() {
+        let mut boards = HighScores::default();
+        let invisible = StackChallenge::new(StackVisibility::Invisible, false);
+        boards.insert(Variant::Marathon, run(100, 0.0));
+        let hidden = HighScore {
+            stack: invisible,
+            ..run(50, 0.0)
+        };
+        assert_eq!(boards.insert(Variant::Marathon, hidden), Some(0));
+        assert_eq!(boards.table(Variant::Marathon, invisible), [hidden]);
+        assert_eq!(
+            boards.table(Variant::Marathon, StackChallenge::STANDARD)[0].score,
+            100
+        );
+        assert!(
+            boards
+                .table(
+                    Variant::Marathon,
+                    StackChallenge::new(StackVisibility::Invisible, true)
+                )
+                .is_empty()
+        );
     }
 }
@@ -32,10 +32,6 @@ pub struct LevelConfig {
     pub(crate) preview_count: usize,
 
     pub(crate) board_height: usize,
-    // DAS timings stay player-side (consumed by KeyboardController via the
-    // engine-bridge's das_config_from_level; never read by the engine).
-    pub(crate) das_delay: Duration,
-    pub(crate) das_repeat_duration: Duration,
     pub(crate) locking_duration: Duration,
     // `LockDownMode` lives in the engine-agnostic `engine/` crate, which must not
     // depend on Bevy (no `Reflect`). Skip it for reflection rather than couple the
@@ -52,8 +48,6 @@ impl Default for LevelConfig {
             board_width: 10,
             board_height: 20,
             preview_count: 6,
-            das_delay: Duration::from_millis(300),
-            das_repeat_duration: Duration::from_millis(50),
             locking_duration: Duration::from_secs_f32(crate::engine::LOCK_DOWN_SECONDS),
             lock_down_mode: LockDownMode::default(),
         }
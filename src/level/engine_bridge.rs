@@ -3,7 +3,7 @@
 //! The session (`src/session/`) owns the engines and steps them; this module
 //! owns what a step is built from. [`engine_config_for_game`] folds
 //! `LevelConfig`/`GameSettings`/`Variant` into an [`EngineConfig`],
-//! [`das_config_from_level`] does the same for the keyboard's DAS timings,
+//! [`das_config_from_settings`] does the same for the keyboard's handling,
 //! and [`PendingEdges`] latches just-pressed input for the fixed slices. The
 //! renderer stays a one-way consumer of the engine: no render system mutates
 //! simulation state.
@@ -103,6 +103,14 @@ pub fn engine_config_from_level(config: &LevelConfig) -> EngineConfig {
         // Single-player: nothing feeds the garbage queue, so the cap is inert
         // until a versus mode arms it. The engine default is the standard 8.
         garbage_cap: EngineConfig::default().garbage_cap,
+        // Dig's cheese, Blitz's lock-delay curve and all-clear bonus and
+        // Master's rules are variant overrides (`VariantDef`), not level
+        // knobs; a puzzle's scripted start is the puzzle's own.
+        dig: None,
+        lock_delay_curve: crate::engine::LockDelayCurve::Flat,
+        perfect_clear_bonus: false,
+        master: false,
+        scenario: None,
     }
 }
 
@@ -126,12 +134,15 @@ pub fn engine_config_for_game(
     engine_config
 }
 
-/// Build the player-side [`DasConfig`] from the renderer's [`LevelConfig`] DAS
-/// durations (these stay on `LevelConfig`, consumed here, never by the engine).
-pub fn das_config_from_level(config: &LevelConfig) -> DasConfig {
+/// Build the player-side [`DasConfig`] from the player's handling settings
+/// (DAS, ARR, DCD and soft-drop factor — consumed here, never by the engine).
+pub fn das_config_from_settings(settings: &GameSettings) -> DasConfig {
+    let seconds = |ms: u32| ms as f32 / 1000.0;
     DasConfig {
-        delay_seconds: config.das_delay.as_secs_f32(),
-        repeat_seconds: config.das_repeat_duration.as_secs_f32(),
+        delay_seconds: seconds(settings.das_ms),
+        repeat_seconds: seconds(settings.arr_ms),
+        cut_delay_seconds: seconds(settings.dcd_ms),
+        soft_drop_factor: settings.soft_drop_factor.map(|sdf| sdf as f32),
     }
 }
 
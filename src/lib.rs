@@ -6,7 +6,7 @@
 //! entities stepped in `FixedUpdate`, snapshots reconciled into the ECS
 //! world); `screens` and `features` provide menus and presentation; [`player`]
 //! translates input into engine [`InputFrame`]s; [`storage`], [`settings`],
-//! [`high_scores`], and [`variant`] handle persistence and run configuration.
+//! [`high_scores`], [`replays`], and [`variant`] handle persistence and run configuration.
 //! The flat `tetr_online::` re-export surface below exposes the engine API the
 //! tests and host build against.
 
@@ -29,10 +29,15 @@ pub use tetr_core::{engine, player};
 /// Game-side AI: `tetr-core::ai` re-exported, plus the Watch-AI model registry.
 pub mod ai;
 mod assets;
+pub mod daily;
+pub mod editor;
 pub(crate) mod features;
 pub mod high_scores;
 pub(crate) mod level;
+pub mod openers;
 pub(crate) mod postfx;
+pub mod puzzle;
+pub mod replays;
 mod screens;
 /// Versus mode: two engines, attack routed between them, seats open to humans
 /// and bots (see `docs/adr-versus-mode-ui.md`).
@@ -56,7 +61,8 @@ pub use crate::engine::{
 
 /// Top-level screen the app is on. Drives which plugins' systems run and which
 /// UI is spawned. Flow: `Loading` (asset load) -> `Title` -> `MainMenu`, with
-/// `ModeSelect`/`Options`/`Help`/`HighScores` reachable from the menu and every
+/// `ModeSelect`/`Daily`/`Options`/`Help`/`HighScores`/`Replays`/`Puzzles`/`Editor` reachable from the menu (`Openers` from
+/// mode select) and every
 /// game running in `Session`. Pause, countdown, and the result banner are
 /// phases of the session ([`session::SessionPhase`]), never sibling states.
 #[derive(States, PartialEq, Eq, Debug, Clone, Hash, Default)]
@@ -76,9 +82,28 @@ pub enum GameState {
     Help,
     /// Leaderboards (filled by the high-scores feature).
     HighScores,
+    /// Recent games and personal bests ([`replays`]); choosing one plays it
+    /// back in [`GameState::Session`].
+    Replays,
+    /// The puzzle browser ([`puzzle`]); choosing one plays it in
+    /// [`GameState::Session`].
+    Puzzles,
+    /// The board editor ([`editor`]); launching plays the position in
+    /// [`GameState::Session`].
+    Editor,
+    /// The opener list ([`openers`]), reached from mode select; choosing one
+    /// trains it in [`GameState::Session`].
+    Openers,
+    /// Today's daily challenge ([`daily`]): its rules, the official attempt,
+    /// practice, the day's board and history; playing enters
+    /// [`GameState::Session`].
+    Daily,
     /// Configure a seated session (who sits at each board) before starting it
     /// — the versus and Watch-AI entry point.
     SessionSetup,
+    /// Host or join an online room and ready up; a started room enters
+    /// [`GameState::Session`] with a remote seat.
+    Lobby,
     /// A live seated session — one seat (solo / Watch-AI) or two (versus).
     /// Its lifecycle (countdown/running/paused/over) is the
     /// [`session::SessionPhase`] sub-state; the result screen is the `Over`
@@ -109,11 +134,16 @@ impl Plugin for GamePlugin {
             .register_type::<crate::vfx::VfxToggles>()
             // Reflection registration for the shared contracts (canonical
             // owner). Inner non-engine types embedded in these (Keybinds,
-            // GameAction, Variant, HighScore) are registered so the inspector can
-            // descend into them. Engine-typed fields (LockDownMode) are
-            // `#[reflect(ignore)]`d at the field to preserve the engine boundary.
+            // GamepadBinds, the touch layout and scheme, GameAction, Variant,
+            // HighScore) are registered so the inspector can descend into them.
+            // Engine-typed fields (LockDownMode) are `#[reflect(ignore)]`d at
+            // the field to preserve the engine boundary.
             .register_type::<crate::settings::GameSettings>()
             .register_type::<crate::settings::Keybinds>()
+            .register_type::<crate::settings::GamepadBinds>()
+            .register_type::<crate::settings::TouchLayout>()
+            .register_type::<crate::settings::TouchScheme>()
+            .register_type::<crate::settings::StackVisibility>()
             .register_type::<crate::settings::GameAction>()
             .register_type::<crate::variant::ActiveVariant>()
             .register_type::<crate::variant::Variant>()
@@ -0,0 +1,203 @@
+//! The opener trainer: build a named opener from a fresh bag, one graded
+//! placement at a time.
+//!
+//! The openers themselves — TKI, DT Cannon, MKO, PCO, each with its mirror —
+//! are the core's [`OpenerBook`], the same book the AI can open from. A
+//! trainer session is one seat on the versus rules with a fresh deal;
+//! [`OpenerTrainer`] watches it slice by slice, keeps the book's next target
+//! (which the board outlines, with a HOLD hint when the target needs a swap),
+//! and grades every lock: on book, an alternate that still builds the opener,
+//! or off book. The session ends when the opener is complete or the board
+//! leaves the book. Trainer runs never rank and are not kept as replays.
+
+use bevy::prelude::*;
+
+use crate::ai::SearchState;
+use crate::engine::{EngineEvent, EngineSnapshot};
+use tetr_core::opener::{Grade, Opener, OpenerBook, OpenerRun, RunEnd};
+
+/// The openers on offer, in browser order.
+#[derive(Resource, Clone, Debug)]
+pub struct OpenerCatalog(pub OpenerBook);
+
+impl Default for OpenerCatalog {
+    fn default() -> Self {
+        Self(OpenerBook::standard())
+    }
+}
+
+impl OpenerCatalog {
+    pub fn get(&self, index: usize) -> Option<&Opener> {
+        self.0.openers().get(index)
+    }
+}
+
+/// A live trainer attempt.
+#[derive(Resource, Clone)]
+pub struct OpenerTrainer {
+    run: OpenerRun,
+    /// Whether the first piece has been planned for.
+    started: bool,
+}
+
+impl OpenerTrainer {
+    pub fn new(opener: &Opener) -> Self {
+        Self {
+            run: OpenerRun::new(opener.clone()),
+            started: false,
+        }
+    }
+
+    pub fn opener(&self) -> &Opener {
+        self.run.opener()
+    }
+
+    pub fn grades(&self) -> &[Grade] {
+        self.run.grades()
+    }
+
+    /// Placements graded `grade` so far.
+    pub fn count(&self, grade: Grade) -> usize {
+        self.grades().iter().filter(|&&g| g == grade).count()
+    }
+
+    /// The board cells of the book's next target, empty once the run ends.
+    pub fn target_cells(&self) -> Vec<(isize, isize)> {
+        self.run.target().map_or_else(Vec::new, |placement| {
+            let (ox, oy) = placement.origin();
+            placement
+                .piece
+                .piece()
+                .cells()
+                .iter()
+                .map(|(x, y)| (x + ox, y + oy))
+                .collect()
+        })
+    }
+
+    /// Whether the next target is the held (or next) piece — the player
+    /// must hold first.
+    pub fn wants_hold(&self) -> bool {
+        self.run
+            .target()
+            .is_some_and(|placement| placement.used_hold)
+    }
+
+    /// Fold one simulation slice in: the first spawn, a lock or a hold is a
+    /// new decision, re-planned from the post-slice `snapshot`. Returns how
+    /// the run ended, if it just did.
+    pub fn observe(&mut self, events: &[EngineEvent], snapshot: &EngineSnapshot) -> Option<RunEnd> {
+        let locked = events
+            .iter()
+            .any(|event| matches!(event, EngineEvent::Locked { .. }));
+        let held = events
+            .iter()
+            .any(|event| matches!(event, EngineEvent::Held { .. }));
+        if self.started && !locked && !held {
+            return None;
+        }
+        let state = SearchState::from_snapshot(snapshot);
+        if state.is_none() && !locked {
+            return None; // nothing in play yet
+        }
+        self.started = true;
+        self.run.observe(locked, state.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::placement_to_inputs;
+    use crate::engine::{Engine, EngineConfig, InputFrame, PieceType};
+    use crate::level::engine_bridge::SIM_DT_SECONDS;
+
+    fn frame() -> InputFrame {
+        InputFrame {
+            dt_seconds: SIM_DT_SECONDS,
+            ..InputFrame::default()
+        }
+    }
+
+    /// A seed whose first piece is `first` and whose bag order can build
+    /// `opener`.
+    fn seed_opening_with(first: PieceType, opener: &Opener) -> u64 {
+        (0..)
+            .find(|&seed| {
+                let mut engine = Engine::new(EngineConfig::default(), seed);
+                let mut trainer = OpenerTrainer::new(opener);
+                engine.snapshot().next_queue[0] == first
+                    && step(&mut engine, &mut trainer, frame()).is_none()
+            })
+            .unwrap()
+    }
+
+    /// Step `engine` with `input`, folding the slice into `trainer`.
+    fn step(engine: &mut Engine, trainer: &mut OpenerTrainer, input: InputFrame) -> Option<RunEnd> {
+        let events = engine.step(input);
+        trainer.observe(&events, &engine.snapshot())
+    }
+
+    #[test]
+    fn the_catalog_is_the_standard_book() {
+        let catalog = OpenerCatalog::default();
+        assert_eq!(catalog.0.openers().len(), 4);
+        assert_eq!(catalog.get(0).unwrap().name, "TKI");
+        assert!(catalog.get(4).is_none());
+    }
+
+    /// Playing the target the trainer shows, piece after piece, completes
+    /// the opener with every placement on book.
+    #[test]
+    fn following_the_targets_completes_the_opener() {
+        let catalog = OpenerCatalog::default();
+        for seed in 0..6 {
+            let mut engine = Engine::new(EngineConfig::default(), seed);
+            let mut trainer = OpenerTrainer::new(catalog.get(0).unwrap());
+            let mut end = step(&mut engine, &mut trainer, frame());
+            // A bag order TKI cannot use ends the attempt at the first spawn.
+            if end == Some(RunEnd::OffBook) {
+                continue;
+            }
+            while end.is_none() {
+                let snapshot = engine.snapshot();
+                let state = SearchState::from_snapshot(&snapshot).unwrap();
+                let target = trainer.run.target().unwrap().clone();
+                let inputs = placement_to_inputs(&state.board.to_array2d(), &state.active, &target);
+                for input in inputs {
+                    let input = InputFrame {
+                        dt_seconds: SIM_DT_SECONDS,
+                        ..input
+                    };
+                    end = step(&mut engine, &mut trainer, input).or(end);
+                }
+            }
+            assert_eq!(end, Some(RunEnd::Complete), "seed {seed}");
+            assert!(trainer.grades().iter().all(|&grade| grade == Grade::Book));
+            assert_eq!(trainer.grades().len(), 7, "six pieces and the TSD");
+            return;
+        }
+        panic!("no seed in range lets TKI open");
+    }
+
+    /// A flat S dropped at spawn is on no setup: the run ends off book.
+    #[test]
+    fn a_stray_placement_ends_off_book() {
+        let catalog = OpenerCatalog::default();
+        let tki = catalog.get(0).unwrap();
+        let mut engine = Engine::new(
+            EngineConfig::default(),
+            seed_opening_with(PieceType::S, tki),
+        );
+        let mut trainer = OpenerTrainer::new(tki);
+        assert_eq!(step(&mut engine, &mut trainer, frame()), None);
+        assert!(!trainer.target_cells().is_empty());
+        let drop = InputFrame {
+            hard_drop: true,
+            ..frame()
+        };
+        assert_eq!(step(&mut engine, &mut trainer, drop), Some(RunEnd::OffBook));
+        assert_eq!(trainer.grades(), [Grade::OffBook]);
+        assert_eq!(trainer.count(Grade::OffBook), 1);
+    }
+}
@@ -0,0 +1,510 @@
+//! Puzzle mode: hand-made positions with a goal and a piece budget.
+//!
+//! A puzzle is a small RON file (the bundled set lives in `assets/puzzles/`
+//! and is compiled in, so every build — wasm included — has it): the starting
+//! board drawn as text rows, the hold slot, the scripted piece sequence, the
+//! goal, and how many pieces the player may lock to reach it. [`Puzzle::parse`]
+//! turns one into an engine [`Scenario`], which rides on the engine config like
+//! any other rule, so an attempt re-simulates exactly from `(config, seed,
+//! inputs)`.
+//!
+//! The session plays a puzzle on one seat and [`PuzzleRun`] judges it slice by
+//! slice; [`PuzzleProgress`] remembers which puzzles have been solved through
+//! the [`Storage`] trait under [`keys::PUZZLES`]. Puzzles never rank on the
+//! high-score boards and are not kept as replays (the replay format has no
+//! room for a scripted start).
+//!
+//! The file format, by example:
+//!
+//! ```text
+//! (
+//!     name: "Tetris Well",
+//!     goal: Lines(4),
+//!     pieces: 1,
+//!     hold: None,
+//!     sequence: "I",
+//!     board: [
+//!         "XXXXXXXXX.",
+//!         "XXXXXXXXX.",
+//!     ],
+//! )
+//! ```
+//!
+//! `board` rows run top to bottom and stand on the floor; each is exactly
+//! [`PUZZLE_WIDTH`] cells: `.` empty, `X` garbage, or a piece letter for a
+//! coloured mino. `hold` is a piece letter (`Some('T')`) or `None`.
+//!
+//! [`keys::PUZZLES`]: crate::storage::keys::PUZZLES
+
+use std::collections::BTreeSet;
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::engine::{
+    CellKind, EngineConfig, EngineEvent, EngineScoreAction, GoalSystem, PieceType, Scenario,
+    TSpinKind,
+};
+use crate::storage::{Storage, keys};
+
+/// Columns in a puzzle board (the standard field).
+pub const PUZZLE_WIDTH: usize = 10;
+
+/// Rows in a puzzle's visible field; a board may draw at most this many.
+pub const PUZZLE_HEIGHT: usize = 20;
+
+/// The engine seed every puzzle plays on: the pieces past the script and a
+/// survival goal's garbage holes are the same on every attempt, so a puzzle
+/// solved once stays solvable.
+pub const PUZZLE_SEED: u64 = 0x7075_7a7a_6c65; // "puzzle"
+
+/// The bundled puzzles, in browser order: `(id, file)`. The id is the file
+/// stem and the key a puzzle's completion is stored under.
+const BUNDLED: [(&str, &str); 4] = [
+    (
+        "tetris-well",
+        include_str!("../assets/puzzles/tetris-well.ron"),
+    ),
+    (
+        "left-and-right",
+        include_str!("../assets/puzzles/left-and-right.ron"),
+    ),
+    (
+        "triple-slot",
+        include_str!("../assets/puzzles/triple-slot.ron"),
+    ),
+    (
+        "rising-tide",
+        include_str!("../assets/puzzles/rising-tide.ron"),
+    ),
+];
+
+/// What solves a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+pub enum PuzzleGoal {
+    /// Empty the board with a clear.
+    PerfectClear,
+    /// Clear at least this many lines in total.
+    Lines(u32),
+    /// Clear three lines with a (full) T-spin.
+    TSpinTriple,
+    /// This many garbage lines are queued at the start; lock every piece of
+    /// the budget without topping out.
+    SurviveGarbage(u32),
+}
+
+impl PuzzleGoal {
+    /// Display line for the browser and the HUD.
+    pub fn label(self) -> String {
+        match self {
+            PuzzleGoal::PerfectClear => "Perfect clear".to_string(),
+            PuzzleGoal::Lines(1) => "Clear 1 line".to_string(),
+            PuzzleGoal::Lines(lines) => format!("Clear {lines} lines"),
+            PuzzleGoal::TSpinTriple => "T-spin triple".to_string(),
+            PuzzleGoal::SurviveGarbage(lines) => format!("Survive {lines} garbage"),
+        }
+    }
+}
+
+/// One puzzle file, as written.
+#[derive(Deserialize)]
+struct PuzzleFile {
+    name: String,
+    goal: PuzzleGoal,
+    pieces: u32,
+    #[serde(default)]
+    hold: Option<char>,
+    sequence: String,
+    board: Vec<String>,
+}
+
+/// A checked, ready-to-play puzzle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Puzzle {
+    pub id: String,
+    pub name: String,
+    pub goal: PuzzleGoal,
+    /// Pieces the player may lock.
+    pub piece_limit: u32,
+    /// The start the engine builds.
+    pub scenario: Scenario,
+}
+
+impl Puzzle {
+    /// Read and check a puzzle file. The error names the first problem, for
+    /// whoever is writing the puzzle.
+    pub fn parse(id: &str, text: &str) -> Result<Self, String> {
+        let file: PuzzleFile = ron::from_str(text).map_err(|error| error.to_string())?;
+        if file.pieces == 0 {
+            return Err("a puzzle must allow at least one piece".to_string());
+        }
+        if file.board.len() > PUZZLE_HEIGHT {
+            return Err(format!(
+                "the board has {} rows; at most {PUZZLE_HEIGHT} fit",
+                file.board.len()
+            ));
+        }
+        let sequence = file
+            .sequence
+            .chars()
+            .map(|c| piece_for(c).ok_or_else(|| format!("unknown piece '{c}' in the sequence")))
+            .collect::<Result<Vec<_>, _>>()?;
+        // Every piece the budget can lock must be scripted: the player never
+        // depends on what the bag deals after it.
+        if sequence.len() < file.pieces as usize {
+            return Err(format!(
+                "the sequence has {} pieces but the puzzle allows {}",
+                sequence.len(),
+                file.pieces
+            ));
+        }
+        let hold = file
+            .hold
+            .map(|c| piece_for(c).ok_or_else(|| format!("unknown hold piece '{c}'")))
+            .transpose()?;
+
+        let mut cells = Vec::new();
+        for (row, line) in file.board.iter().enumerate() {
+            let y = (file.board.len() - 1 - row) as isize;
+            if line.chars().count() != PUZZLE_WIDTH {
+                return Err(format!(
+                    "board row {} is {} cells wide, not {PUZZLE_WIDTH}",
+                    row + 1,
+                    line.chars().count()
+                ));
+            }
+            let mut filled = 0;
+            for (x, c) in line.chars().enumerate() {
+                let cell =
+                    match c {
+                        '.' => continue,
+                        'X' => CellKind::Garbage,
+                        piece => CellKind::Some(piece_for(piece).ok_or_else(|| {
+                            format!("unknown cell '{c}' in board row {}", row + 1)
+                        })?),
+                    };
+                cells.push((x as isize, y, cell));
+                filled += 1;
+            }
+            // A full row would never clear: the engine only clears on a lock.
+            if filled == PUZZLE_WIDTH {
+                return Err(format!("board row {} is already full", row + 1));
+            }
+        }
+
+        let garbage = match file.goal {
+            PuzzleGoal::SurviveGarbage(lines) => lines,
+            _ => 0,
+        };
+        Ok(Self {
+            id: id.to_string(),
+            name: file.name,
+            goal: file.goal,
+            piece_limit: file.pieces,
+            scenario: Scenario {
+                cells,
+                hold,
+                sequence,
+                garbage,
+            },
+        })
+    }
+
+    /// The engine rules a puzzle plays under: the standard field at level 1
+    /// with no goal system (the puzzle's goal ends it), the player's preview
+    /// and lock-down preferences, and the puzzle's start.
+    pub fn engine_config(&self, settings: &crate::settings::GameSettings) -> EngineConfig {
+        EngineConfig {
+            board_width: PUZZLE_WIDTH,
+            visible_height: PUZZLE_HEIGHT,
+            preview_count: settings.next_count,
+            lock_down_mode: settings.lock_down_mode,
+            goal_system: GoalSystem::None,
+            // A perfect-clear goal is judged off the bonus event.
+            perfect_clear_bonus: true,
+            scenario: Some(self.scenario.clone()),
+            ..EngineConfig::default()
+        }
+    }
+}
+
+/// A piece letter (`I`, `O`, `T`, `S`, `Z`, `J`, `L`).
+pub(crate) fn piece_for(c: char) -> Option<PieceType> {
+    Some(match c {
+        'I' => PieceType::I,
+        'O' => PieceType::O,
+        'T' => PieceType::T,
+        'S' => PieceType::S,
+        'Z' => PieceType::Z,
+        'J' => PieceType::J,
+        'L' => PieceType::L,
+        _ => return None,
+    })
+}
+
+/// The puzzles on offer, in browser order. Built from the bundled files; one
+/// that fails to parse is left out with a warning (the catalog test keeps
+/// that from shipping).
+#[derive(Resource, Clone, Debug)]
+pub struct PuzzleCatalog(pub Vec<Puzzle>);
+
+impl Default for PuzzleCatalog {
+    fn default() -> Self {
+        let puzzles = BUNDLED
+            .into_iter()
+            .filter_map(|(id, text)| match Puzzle::parse(id, text) {
+                Ok(puzzle) => Some(puzzle),
+                Err(error) => {
+                    warn!("puzzle {id}: {error}");
+                    None
+                }
+            })
+            .collect();
+        Self(puzzles)
+    }
+}
+
+impl PuzzleCatalog {
+    pub fn get(&self, index: usize) -> Option<&Puzzle> {
+        self.0.get(index)
+    }
+}
+
+/// A live attempt: the pieces locked and lines cleared so far, judged against
+/// the puzzle's goal and budget.
+#[derive(Resource, Clone, Debug)]
+pub struct PuzzleRun {
+    pub goal: PuzzleGoal,
+    pub piece_limit: u32,
+    pub pieces: u32,
+    lines: u32,
+    met: bool,
+}
+
+impl PuzzleRun {
+    pub fn new(puzzle: &Puzzle) -> Self {
+        Self {
+            goal: puzzle.goal,
+            piece_limit: puzzle.piece_limit,
+            pieces: 0,
+            lines: 0,
+            met: false,
+        }
+    }
+
+    /// Fold one simulation slice's events in: `Some(true)` once the goal is
+    /// met, `Some(false)` on a top-out or a spent budget, `None` while the
+    /// attempt goes on. A slice locks at most one piece, so a goal met by the
+    /// last piece of the budget counts.
+    pub fn observe(&mut self, events: &[EngineEvent]) -> Option<bool> {
+        for event in events {
+            match event {
+                EngineEvent::Locked { lines_cleared, .. } => {
+                    self.pieces += 1;
+                    self.lines += *lines_cleared as u32;
+                }
+                EngineEvent::PerfectClearBonus { .. } => {
+                    self.met |= self.goal == PuzzleGoal::PerfectClear;
+                }
+                EngineEvent::ScoreAwarded {
+                    action:
+                        EngineScoreAction::TSpin {
+                            kind: TSpinKind::Full,
+                            lines: 3,
+                        },
+                    ..
+                } => self.met |= self.goal == PuzzleGoal::TSpinTriple,
+                EngineEvent::GameOver { .. } => return Some(false),
+                _ => {}
+            }
+        }
+        if let PuzzleGoal::Lines(lines) = self.goal {
+            self.met |= self.lines >= lines;
+        }
+        if self.met {
+            Some(true)
+        } else if self.pieces >= self.piece_limit {
+            // Surviving is the whole goal: a spent budget is the win.
+            Some(matches!(self.goal, PuzzleGoal::SurviveGarbage(_)))
+        } else {
+            None
+        }
+    }
+}
+
+/// Which puzzles the player has solved, by id.
+#[derive(Resource, Default, Debug, PartialEq, Eq)]
+pub struct PuzzleProgress {
+    solved: BTreeSet<String>,
+}
+
+impl PuzzleProgress {
+    pub fn is_solved(&self, id: &str) -> bool {
+        self.solved.contains(id)
+    }
+
+    /// Record a solve; `true` if it is the puzzle's first.
+    pub fn mark_solved(&mut self, id: &str) -> bool {
+        self.solved.insert(id.to_string())
+    }
+
+    /// Restore from storage (missing or empty: nothing solved).
+    pub fn load(storage: &dyn Storage) -> Self {
+        storage
+            .load(keys::PUZZLES)
+            .map(|blob| Self::decode(&blob))
+            .unwrap_or_default()
+    }
+
+    pub fn persist(&self, storage: &dyn Storage) {
+        storage.save(keys::PUZZLES, &self.encode());
+    }
+
+    /// One solved id per line.
+    fn encode(&self) -> String {
+        self.solved
+            .iter()
+            .map(|id| format!("{id}\n"))
+            .collect::<String>()
+    }
+
+    fn decode(blob: &str) -> Self {
+        Self {
+            solved: blob
+                .lines()
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(str::to_string)
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::{Engine, InputFrame};
+    use tetr_core::ai::search::hold_placements;
+    use tetr_core::ai::{SearchState, placement_to_inputs};
+
+    #[test]
+    fn every_bundled_puzzle_parses() {
+        for (id, text) in BUNDLED {
+            if let Err(error) = Puzzle::parse(id, text) {
+                panic!("{id}: {error}");
+            }
+        }
+        assert_eq!(PuzzleCatalog::default().0.len(), BUNDLED.len());
+    }
+
+    #[test]
+    fn parse_reads_the_board_bottom_up_and_rejects_bad_files() {
+        let text = r#"(name: "t", goal: Lines(1), pieces: 1, hold: Some('O'), sequence: "IT",
+            board: ["T.........", "XXXXXXXXX."])"#;
+        let puzzle = Puzzle::parse("t", text).unwrap();
+        assert_eq!(puzzle.scenario.hold, Some(PieceType::O));
+        assert_eq!(puzzle.scenario.sequence, [PieceType::I, PieceType::T]);
+        assert!(
+            puzzle
+                .scenario
+                .cells
+                .contains(&(0, 1, CellKind::Some(PieceType::T)))
+        );
+        assert!(puzzle.scenario.cells.contains(&(8, 0, CellKind::Garbage)));
+        assert_eq!(puzzle.scenario.cells.len(), 10);
+
+        let bad = [
+            text.replace("\"IT\"", "\"\""),
+            text.replace("XXXXXXXXX.", "XXXXXXXXXX"),
+            text.replace("T.........", "T........"),
+            text.replace("\"IT\"", "\"IQ\""),
+            text.replace("pieces: 1", "pieces: 0"),
+        ];
+        for text in bad {
+            assert!(Puzzle::parse("t", &text).is_err(), "{text}");
+        }
+    }
+
+    #[test]
+    fn a_run_is_judged_by_its_goal_and_budget() {
+        let locked = |lines| EngineEvent::Locked {
+            piece_type: PieceType::I,
+            lines_cleared: lines,
+        };
+        let mut puzzle = Puzzle::parse("t", BUNDLED[0].1).unwrap();
+        puzzle.goal = PuzzleGoal::Lines(2);
+        puzzle.piece_limit = 2;
+        let mut run = PuzzleRun::new(&puzzle);
+        assert_eq!(run.observe(&[locked(1)]), None);
+        assert_eq!(run.observe(&[locked(1)]), Some(true));
+
+        let mut run = PuzzleRun::new(&puzzle);
+        assert_eq!(run.observe(&[locked(0)]), None);
+        assert_eq!(run.observe(&[locked(1)]), Some(false));
+
+        puzzle.goal = PuzzleGoal::SurviveGarbage(4);
+        let mut run = PuzzleRun::new(&puzzle);
+        assert_eq!(run.observe(&[locked(0)]), None);
+        assert_eq!(run.observe(&[locked(0)]), Some(true));
+        let mut run = PuzzleRun::new(&puzzle);
+        let over = EngineEvent::GameOver {
+            reason: crate::engine::GameOverStatus::BlockOut,
+        };
+        assert_eq!(run.observe(&[locked(0), over]), Some(false));
+    }
+
+    #[test]
+    fn progress_round_trips_through_its_codec() {
+        let mut progress = PuzzleProgress::default();
+        assert!(progress.mark_solved("tetris-well"));
+        assert!(!progress.mark_solved("tetris-well"));
+        progress.mark_solved("rising-tide");
+        let restored = PuzzleProgress::decode(&progress.encode());
+        assert!(restored.is_solved("tetris-well"));
+        assert!(!restored.is_solved("triple-slot"));
+        assert_eq!(restored, progress);
+        assert_eq!(PuzzleProgress::decode(""), PuzzleProgress::default());
+    }
+
+    /// Depth-first over the AI's move generator: every placement of every
+    /// piece (hold swaps included), driven through a real engine as the
+    /// inputs the bot would press, judged by the same [`PuzzleRun`] the
+    /// session uses. `true` once any line of play solves the puzzle.
+    fn solvable(engine: &Engine, run: &PuzzleRun) -> bool {
+        let snapshot = engine.snapshot();
+        let Some(state) = SearchState::from_snapshot(&snapshot) else {
+            return false;
+        };
+        let hold_used = snapshot.active.as_ref().is_some_and(|a| a.hold_used);
+        let board = state.board.to_array2d();
+        hold_placements(&state)
+            .iter()
+            .filter(|placement| !(placement.used_hold && hold_used))
+            .any(|placement| {
+                let mut engine = engine.clone();
+                let mut run = run.clone();
+                for frame in placement_to_inputs(&board, &state.active, placement) {
+                    let events = engine.step(frame);
+                    if let Some(solved) = run.observe(&events) {
+                        return solved;
+                    }
+                }
+                solvable(&engine, &run)
+            })
+    }
+
+    #[test]
+    fn the_ai_solves_every_bundled_puzzle() {
+        let settings = crate::settings::GameSettings::default();
+        for puzzle in PuzzleCatalog::default().0 {
+            let mut engine = Engine::new(puzzle.engine_config(&settings), PUZZLE_SEED);
+            // The first step deals the first piece.
+            engine.step(InputFrame::default());
+            assert!(
+                solvable(&engine, &PuzzleRun::new(&puzzle)),
+                "{} has no solution",
+                puzzle.id
+            );
+        }
+    }
+}
@@ -0,0 +1,713 @@
+//! Replay model: every finished session, kept as the inputs that made it.
+//!
+//! The engine is a pure function of its config, seed and input frames, so a
+//! [`GameReplay`] is just that per seat — a [`RecordedGame`] each, plus
+//! who sat there and the session's mode. The session records every seat as it
+//! steps (`session::replay` captures the match when it ends) and plays one
+//! back by re-stepping fresh engines; attack between seats replays from each
+//! seat's recorded garbage, so playback never has to re-route anything.
+//!
+//! [`ReplayLibrary`] holds the last [`MAX_RECENT`] games and, per solo
+//! variant, the replay of the run that set the personal best. It persists
+//! through the [`Storage`] trait: an index under [`keys::REPLAYS`] and one
+//! blob per replay (`replay-<id>`), each in a small line-based text format
+//! (see `codec`) — on native that is a file per replay in the config dir.
+//! A solo replay's one seat is also a leaderboard submission as it stands:
+//! `tetr_verify::Replay::from_recording` takes its [`RecordedGame`] as is.
+//!
+//! [`keys::REPLAYS`]: crate::storage::keys::REPLAYS
+
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use bevy::prelude::*;
+
+use crate::ai::RecordedGame;
+use crate::session::{Participant, SessionMode};
+use crate::storage::{Storage, keys};
+use crate::variant::Variant;
+
+/// Recent games kept (older ones are dropped as new ones finish).
+pub const MAX_RECENT: usize = 8;
+
+/// One seat of a recorded session.
+#[derive(Clone, Debug)]
+pub struct SeatReplay {
+    /// Who drove the seat (a bot's skill is not kept: playback never polls
+    /// it, and the label only needs the model).
+    pub participant: Participant,
+    pub game: RecordedGame,
+}
+
+/// A recorded session: everything needed to play it again.
+#[derive(Clone, Debug)]
+pub struct GameReplay {
+    pub mode: SessionMode,
+    /// One line naming the game in the replay list, written when it ended.
+    pub summary: String,
+    /// In seat order.
+    pub seats: Vec<SeatReplay>,
+}
+
+impl GameReplay {
+    /// Length in simulation slices (every seat steps every slice, so the
+    /// recordings are the same length).
+    pub fn frames(&self) -> usize {
+        self.seats
+            .iter()
+            .map(|seat| seat.game.inputs.len())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Where a listed replay sits in the library.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplaySlot {
+    /// The run behind `variant`'s current best high score.
+    PersonalBest(Variant),
+    /// One of the recent games, newest first.
+    Recent,
+}
+
+/// The stored replays: the recent games and the personal bests.
+#[derive(Resource, Default)]
+pub struct ReplayLibrary {
+    next_id: u64,
+    replays: BTreeMap<u64, GameReplay>,
+    /// Newest first.
+    recent: VecDeque<u64>,
+    bests: HashMap<Variant, u64>,
+}
+
+/// What [`ReplayLibrary::add`] changed, for [`ReplayLibrary::persist`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct LibraryChange {
+    pub added: u64,
+    /// Replays no longer referenced by any slot.
+    pub dropped: Vec<u64>,
+}
+
+impl ReplayLibrary {
+    /// File a finished game as the newest recent one and, if it set a new
+    /// personal best, as `best`'s PB replay.
+    pub fn add(&mut self, replay: GameReplay, best: Option<Variant>) -> LibraryChange {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.replays.insert(id, replay);
+        self.recent.push_front(id);
+
+        let mut released = Vec::new();
+        if let Some(old) = self.recent.get(MAX_RECENT).copied() {
+            self.recent.truncate(MAX_RECENT);
+            released.push(old);
+        }
+        if let Some(variant) = best
+            && let Some(old) = self.bests.insert(variant, id)
+        {
+            released.push(old);
+        }
+        let dropped: Vec<u64> = released
+            .into_iter()
+            .filter(|&old| !self.referenced(old))
+            .collect();
+        for old in &dropped {
+            self.replays.remove(old);
+        }
+        LibraryChange { added: id, dropped }
+    }
+
+    fn referenced(&self, id: u64) -> bool {
+        self.recent.contains(&id) || self.bests.values().any(|&best| best == id)
+    }
+
+    pub fn get(&self, id: u64) -> Option<&GameReplay> {
+        self.replays.get(&id)
+    }
+
+    /// The replay of `variant`'s personal best, if one was recorded.
+    pub fn personal_best(&self, variant: Variant) -> Option<&GameReplay> {
+        self.bests.get(&variant).and_then(|id| self.get(*id))
+    }
+
+    /// Every listed replay, personal bests (in variant order) first, then the
+    /// recent games newest first. A PB that is also recent is listed twice.
+    pub fn listing(&self) -> Vec<(u64, ReplaySlot)> {
+        let bests = Variant::ALL.into_iter().filter_map(|variant| {
+            self.bests
+                .get(&variant)
+                .map(|&id| (id, ReplaySlot::PersonalBest(variant)))
+        });
+        bests
+            .chain(self.recent.iter().map(|&id| (id, ReplaySlot::Recent)))
+            .collect()
+    }
+
+    /// Write an [`add`](Self::add)'s change through: the new blob, the index,
+    /// and the removal of every dropped blob.
+    pub fn persist(&self, storage: &dyn Storage, change: &LibraryChange) {
+        if let Some(replay) = self.get(change.added) {
+            storage.save(&blob_key(change.added), &codec::encode(replay));
+        }
+        for id in &change.dropped {
+            storage.remove(&blob_key(*id));
+        }
+        storage.save(keys::REPLAYS, &self.index());
+    }
+
+    /// Rebuild the library from storage. Entries whose blob is missing or
+    /// does not parse are left out.
+    pub fn load(storage: &dyn Storage) -> Self {
+        let mut library = Self::default();
+        let Some(index) = storage.load(keys::REPLAYS) else {
+            return library;
+        };
+        let mut fetch = |id: u64| -> bool {
+            if library.replays.contains_key(&id) {
+                return true;
+            }
+            match storage.load(&blob_key(id)).and_then(|b| codec::decode(&b)) {
+                Some(replay) => {
+                    library.replays.insert(id, replay);
+                    true
+                }
+                None => false,
+            }
+        };
+        let mut recent = VecDeque::new();
+        let mut bests = HashMap::new();
+        let mut next_id = 0;
+        for line in index.lines() {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            match fields.as_slice() {
+                ["next", id] => next_id = id.parse().unwrap_or(0),
+                ["recent", id] => {
+                    if let Ok(id) = id.parse()
+                        && recent.len() < MAX_RECENT
+                        && fetch(id)
+                    {
+                        recent.push_back(id);
+                    }
+                }
+                ["best", variant, id] => {
+                    if let (Some(variant), Ok(id)) = (codec::variant_for_key(variant), id.parse())
+                        && fetch(id)
+                    {
+                        bests.insert(variant, id);
+                    }
+                }
+                _ => {}
+            }
+        }
+        library.recent = recent;
+        library.bests = bests;
+        // Never reuse a stored id, whatever the index claimed.
+        let highest = library.replays.keys().next_back().map_or(0, |id| id + 1);
+        library.next_id = next_id.max(highest);
+        library
+    }
+
+    /// The index blob: the id counter, the recent ids, the PB ids.
+    fn index(&self) -> String {
+        let mut out = format!("next {}\n", self.next_id);
+        for id in &self.recent {
+            out.push_str(&format!("recent {id}\n"));
+        }
+        for variant in Variant::ALL {
+            if let Some(id) = self.bests.get(&variant) {
+                out.push_str(&format!("best {} {id}\n", variant.rules().key()));
+            }
+        }
+        out
+    }
+}
+
+fn blob_key(id: u64) -> String {
+    format!("{}{id}", keys::REPLAY_PREFIX)
+}
+
+/// Line-based, dependency-free encoding of one [`GameReplay`].
+///
+/// ```text
+/// tetr-game-replay 1
+/// mode solo sprint            (or: mode versus)
+/// summary Sprint · YOU · …
+/// seat human                  (or: seat remote / seat bot <model>)
+/// engine 10 20 5 extended 0.5 1 fixed 8   (+ optional flags, below)
+/// seed 1234
+/// garbage 41:2 97:1           (after-frame:lines, possibly none)
+/// frames 3600
+/// 00 00 08 01 01@0 …          (32 per line)
+/// seat …                      (the next seat, same shape)
+/// ```
+///
+/// A frame is its [`InputFrame::buttons`] in hex (the layout tetr-net and
+/// tetr-verify share, so an ARR-0 slide to the left is `101`), with `@<dt>`
+/// appended when its `dt` is not the fixed slice — bots maneuver on
+/// zero-`dt` frames. Floats print with Rust's shortest round-trip
+/// formatting, so decoding is bit-exact. [`decode`] is all-or-nothing: a
+/// replay that does not parse in full is not a replay.
+pub(crate) mod codec {
+    use std::fmt::Write as _;
+
+    use crate::ai::RecordedGame;
+    use crate::ai::analysis::IncomingGarbage;
+    use crate::engine::{EngineConfig, GoalSystem, InputFrame, LockDelayCurve, LockDownMode};
+    use crate::level::SIM_DT_SECONDS;
+    use crate::session::{Participant, SessionMode};
+    use crate::variant::{SoloRules, Variant};
+
+    use super::{GameReplay, SeatReplay};
+
+    const HEADER: &str = "tetr-game-replay 1";
+    const FRAMES_PER_LINE: usize = 32;
+    const TIGHTENING_FLAG: &str = "tightening";
+    const PC_BONUS_FLAG: &str = "pc-bonus";
+    const MASTER_FLAG: &str = "master";
+
+    pub(crate) fn variant_for_key(key: &str) -> Option<Variant> {
+        Variant::ALL.into_iter().find(|v| v.rules().key() == key)
+    }
+
+    fn lock_key(mode: LockDownMode) -> &'static str {
+        match mode {
+            LockDownMode::Extended => "extended",
+            LockDownMode::Infinite => "infinite",
+            LockDownMode::Classic => "classic",
+        }
+    }
+
+    fn lock_for_key(key: &str) -> Option<LockDownMode> {
+        [
+            LockDownMode::Extended,
+            LockDownMode::Infinite,
+            LockDownMode::Classic,
+        ]
+        .into_iter()
+        .find(|&mode| lock_key(mode) == key)
+    }
+
+    fn goal_key(goal: GoalSystem) -> &'static str {
+        match goal {
+            GoalSystem::Fixed => "fixed",
+            GoalSystem::Variable => "variable",
+            GoalSystem::None => "none",
+            GoalSystem::Blitz => "blitz",
+        }
+    }
+
+    fn goal_for_key(key: &str) -> Option<GoalSystem> {
+        [
+            GoalSystem::Fixed,
+            GoalSystem::Variable,
+            GoalSystem::None,
+            GoalSystem::Blitz,
+        ]
+        .into_iter()
+        .find(|&goal| goal_key(goal) == key)
+    }
+
+    fn frame_token(token: &str) -> Option<InputFrame> {
+        let (bits, dt) = match token.split_once('@') {
+            Some((bits, dt)) => (bits, dt.parse().ok()?),
+            None => (token, SIM_DT_SECONDS),
+        };
+        let bits = u16::from_str_radix(bits, 16).ok()?;
+        if bits & !InputFrame::BUTTON_MASK != 0 {
+            return None;
+        }
+        Some(InputFrame::from_buttons(bits, dt))
+    }
+
+    pub fn encode(replay: &GameReplay) -> String {
+        // Writing into a `String` is infallible.
+        let mut out = format!("{HEADER}\n");
+        let _ = match replay.mode {
+            SessionMode::Solo { variant } => writeln!(out, "mode solo {}", variant.rules().key()),
+            SessionMode::Versus => writeln!(out, "mode versus"),
+            // Never captured (the scripted start is not encoded, a practice
+            // line is an edited one, and an opener drill is too short to
+            // keep); a stray one fails to decode rather than playing back on
+            // the wrong board.
+            SessionMode::Puzzle { index } => writeln!(out, "mode puzzle {index}"),
+            SessionMode::Practice { .. } => writeln!(out, "mode practice"),
+            SessionMode::Opener { index } => writeln!(out, "mode opener {index}"),
+        };
+        let _ = writeln!(out, "summary {}", replay.summary.replace('\n', " "));
+        for seat in &replay.seats {
+            let _ = match seat.participant {
+                Participant::Human => writeln!(out, "seat human"),
+                Participant::Remote => writeln!(out, "seat remote"),
+                Participant::Bot { model, .. } => writeln!(out, "seat bot {model}"),
+            };
+            let game = &seat.game;
+            let c = &game.config;
+            let _ = writeln!(
+                out,
+                "engine {} {} {} {} {} {} {} {}",
+                c.board_width,
+                c.visible_height,
+                c.preview_count,
+                lock_key(c.lock_down_mode),
+                c.lock_down_seconds,
+                c.starting_level,
+                goal_key(c.goal_system),
+                c.garbage_cap,
+            );
+            // Variant rules off the plain engine ride as trailing flags
+            // (older files never have them): Blitz's `tightening` lock delay
+            // and `pc-bonus`, Dig's cheese, named like its rules, and
+            // `master`.
+            let mut flags = Vec::new();
+            if c.lock_delay_curve == LockDelayCurve::Tightening {
+                flags.push(TIGHTENING_FLAG.to_owned());
+            }
+            if c.perfect_clear_bonus {
+                flags.push(PC_BONUS_FLAG.to_owned());
+            }
+            if let Some(dig) = c.dig {
+                flags.push(SoloRules::Dig(dig).key());
+            }
+            if c.master {
+                flags.push(MASTER_FLAG.to_owned());
+            }
+            if !flags.is_empty() {
+                out.pop();
+                let _ = writeln!(out, " {}", flags.join(" "));
+            }
+            let _ = writeln!(out, "seed {}", game.seed);
+            out.push_str("garbage");
+            for batch in &game.garbage {
+                let _ = write!(out, " {}:{}", batch.after_frame, batch.lines);
+            }
+            let _ = writeln!(out, "\nframes {}", game.inputs.len());
+            for line in game.inputs.chunks(FRAMES_PER_LINE) {
+                let tokens: Vec<String> = line
+                    .iter()
+                    .map(|frame| {
+                        let bits = frame.buttons();
+                        if frame.dt_seconds.to_bits() == SIM_DT_SECONDS.to_bits() {
+                            format!("{bits:02x}")
+                        } else {
+                            format!("{bits:02x}@{}", frame.dt_seconds)
+                        }
+                    })
+                    .collect();
+                let _ = writeln!(out, "{}", tokens.join(" "));
+            }
+        }
+        out
+    }
+
+    pub fn decode(blob: &str) -> Option<GameReplay> {
+        let mut lines = blob.lines();
+        if lines.next()? != HEADER {
+            return None;
+        }
+        let mode = match lines.next()?.strip_prefix("mode ")? {
+            "versus" => SessionMode::Versus,
+            solo => SessionMode::Solo {
+                variant: variant_for_key(solo.strip_prefix("solo ")?)?,
+            },
+        };
+        let summary = lines.next()?.strip_prefix("summary ")?.to_string();
+
+        let mut seats = Vec::new();
+        while let Some(line) = lines.next() {
+            let participant = match line.strip_prefix("seat ")? {
+                "human" => Participant::Human,
+                "remote" => Participant::Remote,
+                bot => Participant::Bot {
+                    model: bot.strip_prefix("bot ")?.parse().ok()?,
+                    skill: Default::default(),
+                },
+            };
+            let engine: Vec<&str> = lines
+                .next()?
+                .strip_prefix("engine ")?
+                .split_whitespace()
+                .collect();
+            let (fields, flags) = engine.split_at_checked(8)?;
+            let [w, h, preview, lock, lock_seconds, level, goal, cap] = fields else {
+                return None;
+            };
+            let (mut lock_delay_curve, mut perfect_clear_bonus, mut dig, mut master) =
+                (LockDelayCurve::Flat, false, None, false);
+            for &flag in flags {
+                match flag {
+                    TIGHTENING_FLAG if lock_delay_curve == LockDelayCurve::Flat => {
+                        lock_delay_curve = LockDelayCurve::Tightening;
+                    }
+                    PC_BONUS_FLAG if !perfect_clear_bonus => perfect_clear_bonus = true,
+                    MASTER_FLAG if !master => master = true,
+                    _ if dig.is_none() => dig = Some(SoloRules::from_key(flag)?.dig()?),
+                    _ => return None,
+                }
+            }
+            let config = EngineConfig {
+                board_width: w.parse().ok()?,
+                visible_height: h.parse().ok()?,
+                preview_count: preview.parse().ok()?,
+                lock_down_mode: lock_for_key(lock)?,
+                lock_down_seconds: lock_seconds.parse().ok()?,
+                starting_level: level.parse().ok()?,
+                goal_system: goal_for_key(goal)?,
+                garbage_cap: cap.parse().ok()?,
+                dig,
+                lock_delay_curve,
+                perfect_clear_bonus,
+                master,
+                // Puzzle attempts are never recorded, so no replay carries a
+                // scripted start.
+                scenario: None,
+            };
+            let seed = lines.next()?.strip_prefix("seed ")?.parse().ok()?;
+            let garbage = lines
+                .next()?
+                .strip_prefix("garbage")?
+                .split_whitespace()
+                .map(|batch| {
+                    let (after, lines) = batch.split_once(':')?;
+                    Some(IncomingGarbage {
+                        after_frame: after.parse().ok()?,
+                        lines: lines.parse().ok()?,
+                    })
+                })
+                .collect::<Option<Vec<_>>>()?;
+            let count: usize = lines.next()?.strip_prefix("frames ")?.parse().ok()?;
+            let mut inputs = Vec::with_capacity(count.min(1 << 20));
+            while inputs.len() < count {
+                for token in lines.next()?.split_whitespace() {
+                    inputs.push(frame_token(token)?);
+                }
+            }
+            if inputs.len() != count {
+                return None;
+            }
+            seats.push(SeatReplay {
+                participant,
+                game: RecordedGame {
+                    config,
+                    seed,
+                    inputs,
+                    garbage,
+                },
+            });
+        }
+        if seats.is_empty() {
+            return None;
+        }
+        Some(GameReplay {
+            mode,
+            summary,
+            seats,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use super::*;
+    use crate::ai::analysis::IncomingGarbage;
+    use crate::engine::{EngineConfig, GoalSystem, InputFrame};
+    use crate::level::SIM_DT_SECONDS;
+
+    #[derive(Default)]
+    struct MemoryStorage(Mutex<HashMap<String, String>>);
+
+    impl Storage for MemoryStorage {
+        fn load(&self, key: &str) -> Option<String> {
+            self.0.lock().unwrap().get(key).cloned()
+        }
+        fn save(&self, key: &str, value: &str) {
+            self.0
+                .lock()
+                .unwrap()
+                .insert(key.to_string(), value.to_string());
+        }
+        fn remove(&self, key: &str) {
+            self.0.lock().unwrap().remove(key);
+        }
+    }
+
+    fn versus(tag: &str) -> GameReplay {
+        let tick = InputFrame {
+            dt_seconds: SIM_DT_SECONDS,
+            ..default()
+        };
+        let maneuver = InputFrame {
+            dt_seconds: 0.0,
+            left: true,
+            ..default()
+        };
+        let mut inputs = vec![tick.clone(); 70];
+        inputs[5] = maneuver;
+        inputs[6].hard_drop = true;
+        inputs[7].right = true;
+        inputs[7].instant_shift = true;
+        inputs[69].pause = true;
+        let game = RecordedGame {
+            config: EngineConfig {
+                goal_system: GoalSystem::None,
+                preview_count: 3,
+                ..EngineConfig::default()
+            },
+            seed: 42,
+            inputs,
+            garbage: vec![IncomingGarbage {
+                after_frame: 12,
+                lines: 4,
+            }],
+        };
+        GameReplay {
+            mode: SessionMode::Versus,
+            summary: format!("Versus · {tag}"),
+            seats: vec![
+                SeatReplay {
+                    participant: Participant::Human,
+                    game: game.clone(),
+                },
+                SeatReplay {
+                    participant: Participant::Bot {
+                        model: 3,
+                        skill: default(),
+                    },
+                    game: RecordedGame {
+                        garbage: Vec::new(),
+                        ..game
+                    },
+                },
+            ],
+        }
+    }
+
+    fn same(a: &GameReplay, b: &GameReplay) -> bool {
+        a.mode == b.mode
+            && a.summary == b.summary
+            && a.seats.len() == b.seats.len()
+            && a.seats.iter().zip(&b.seats).all(|(a, b)| {
+                a.participant == b.participant
+                    && a.game.config == b.game.config
+                    && a.game.seed == b.game.seed
+                    && a.game.inputs == b.game.inputs
+                    && a.game.garbage == b.game.garbage
+            })
+    }
+
+    #[test]
+    fn replays_round_trip_bit_exact() {
+        let replay = versus("a");
+        let decoded = codec::decode(&codec::encode(&replay)).expect("decodes");
+        assert!(same(&replay, &decoded));
+
+        let solo = GameReplay {
+            mode: SessionMode::Solo {
+                variant: Variant::Sprint,
+            },
+            ..versus("b")
+        };
+        let decoded = codec::decode(&codec::encode(&solo)).expect("decodes");
+        assert_eq!(decoded.mode, solo.mode);
+
+        // A Dig seat carries its cheese, so playback rebuilds the same board.
+        let variant = Variant::Dig {
+            lines: 100,
+            messy: true,
+        };
+        let mut dig = GameReplay {
+            mode: SessionMode::Solo { variant },
+            ..versus("c")
+        };
+        dig.seats.truncate(1);
+        dig.seats[0].game.config.dig = variant.rules().dig();
+        let blob = codec::encode(&dig);
+        assert!(blob.contains(" 8 dig-100-messy\n"), "{blob}");
+        let decoded = codec::decode(&blob).expect("decodes");
+        assert!(same(&dig, &decoded));
+        assert!(codec::decode(&blob.replace("dig-100-messy", "dig-7-messy")).is_none());
+
+        // So does a Blitz seat's lock-delay curve and all-clear bonus.
+        let mut blitz = GameReplay {
+            mode: SessionMode::Solo {
+                variant: Variant::Blitz,
+            },
+            ..versus("d")
+        };
+        blitz.seats.truncate(1);
+        Variant::Blitz
+            .def()
+            .apply_engine_overrides(&mut blitz.seats[0].game.config);
+        let blob = codec::encode(&blitz);
+        assert!(blob.contains(" blitz 8 tightening pc-bonus\n"), "{blob}");
+        assert!(same(&blitz, &codec::decode(&blob).expect("decodes")));
+        let doubled = blob.replace("pc-bonus", "pc-bonus pc-bonus");
+        assert!(codec::decode(&doubled).is_none());
+    }
+
+    #[test]
+    fn a_damaged_blob_is_rejected_whole() {
+        let blob = codec::encode(&versus("a"));
+        for damaged in [
+            blob.replace("frames 70", "frames 71"),
+            blob.replace("seat bot 3", "seat dog"),
+            blob.replace("engine 10 20 3", "engine 10 20"),
+            blob.replace(" 12:4", " 12:four"),
+            blob.replacen("00", "zz", 1),
+            blob.replace("tetr-game-replay 1", "tetr-game-replay 2"),
+        ] {
+            assert!(codec::decode(&damaged).is_none(), "{damaged}");
+        }
+    }
+
+    #[test]
+    fn the_library_keeps_recent_games_and_personal_bests() {
+        let mut library = ReplayLibrary::default();
+        let pb = library.add(versus("pb"), Some(Variant::Sprint)).added;
+        for i in 0..MAX_RECENT {
+            let change = library.add(versus(&i.to_string()), None);
+            // The PB fell off the recent list but stays as the PB.
+            assert!(change.dropped.is_empty());
+        }
+        assert!(library.personal_best(Variant::Sprint).is_some());
+        assert_eq!(library.listing().len(), 1 + MAX_RECENT);
+        assert_eq!(
+            library.listing()[0],
+            (pb, ReplaySlot::PersonalBest(Variant::Sprint))
+        );
+
+        // A new PB releases the old one; the oldest recent game drops too.
+        let change = library.add(versus("pb2"), Some(Variant::Sprint));
+        assert_eq!(change.dropped.len(), 2);
+        assert!(change.dropped.contains(&pb));
+        assert_eq!(
+            library.personal_best(Variant::Sprint).unwrap().summary,
+            "Versus · pb2"
+        );
+    }
+
+    #[test]
+    fn the_library_survives_a_restart() {
+        let storage = MemoryStorage::default();
+        let mut library = ReplayLibrary::default();
+        for (i, best) in [None, Some(Variant::Ultra), None].into_iter().enumerate() {
+            let change = library.add(versus(&i.to_string()), best);
+            library.persist(&storage, &change);
+        }
+
+        let restored = ReplayLibrary::load(&storage);
+        assert_eq!(restored.listing(), library.listing());
+        assert_eq!(
+            restored.personal_best(Variant::Ultra).unwrap().summary,
+            "Versus · 1"
+        );
+        // New ids continue past the stored ones.
+        let mut restored = restored;
+        assert_eq!(restored.add(versus("next"), None).added, 3);
+
+        // A lost blob drops only its own entry.
+        storage.remove(&blob_key(0));
+        assert_eq!(ReplayLibrary::load(&storage).listing().len(), 3);
+    }
+}
@@ -0,0 +1,192 @@
+//! Daily challenge screen: today's rules, the official attempt and practice,
+//! the day's board, the share text and the recent days.
+//!
+//! The challenge is fixed when the screen opens (see [`DailyChallenge`]), so
+//! crossing midnight on this screen still plays the day it shows. Choosing
+//! the official attempt (offered until it is spent) or practice seats the
+//! player in the challenge's session with a [`DailyAttempt`]. Esc returns to
+//! the main menu.
+
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::assets::GameAssets;
+use crate::daily::{DailyAttempt, DailyBook, DailyChallenge, DailyRules, Day};
+use crate::puzzle::PuzzleCatalog;
+use crate::session::Participant;
+use crate::ui::focus::{
+    FocusList, Focusable, MenuPad, NavAction, clicked_focusable, focus_navigation, read_nav_action,
+};
+use crate::ui::widgets::{label_text, menu_button, screen_root, title_text};
+use crate::variant::ActiveVariant;
+
+/// Board rows shown for today.
+const BOARD_ROWS: usize = 5;
+
+/// Earlier days shown under today.
+const HISTORY_ROWS: usize = 5;
+
+pub struct DailyScreenPlugin;
+
+impl Plugin for DailyScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<DailyRoot>()
+            .add_systems(OnEnter(GameState::Daily), setup)
+            .add_systems(
+                Update,
+                (focus_navigation::<DailyRoot>, activate)
+                    .chain()
+                    .run_if(in_state(GameState::Daily)),
+            );
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct DailyRoot;
+
+/// The challenge the screen shows (and plays).
+#[derive(Component)]
+struct ShownChallenge(DailyChallenge);
+
+fn setup(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    book: Res<DailyBook>,
+    puzzles: Res<PuzzleCatalog>,
+) {
+    commands.spawn((
+        crate::ui::widgets::menu_camera(),
+        DespawnOnExit(GameState::Daily),
+    ));
+    let challenge = DailyChallenge::for_day(Day::today(), puzzles.0.len());
+    let today = book.day(challenge.day);
+    let official_open = book.official_open(challenge.day);
+    let status = match today.and_then(|record| record.official()) {
+        Some(official) => format!("Official: {}", challenge.rules.result(official)),
+        None if official_open => "One official attempt  -  practice is unlimited".to_string(),
+        None => "Official attempt forfeited".to_string(),
+    };
+    let root = commands
+        .spawn((
+            DailyRoot,
+            ShownChallenge(challenge),
+            FocusList::new(1 + usize::from(official_open)),
+            screen_root(),
+            DespawnOnExit(GameState::Daily),
+            children![
+                title_text("Daily Challenge", assets.font.clone()),
+                label_text(
+                    format!(
+                        "#{}  -  {}  -  {}",
+                        challenge.day.number(),
+                        challenge.day,
+                        challenge.rules.label(&puzzles)
+                    ),
+                    assets.font_body.clone()
+                ),
+                label_text(status, assets.font_body.clone()),
+            ],
+        ))
+        .id();
+
+    let rows = official_open
+        .then_some("Official Attempt")
+        .into_iter()
+        .chain(["Practice"]);
+    for (index, label) in rows.enumerate() {
+        let button = commands
+            .spawn(menu_button(index, label, assets.font.clone()))
+            .id();
+        commands.entity(root).add_child(button);
+    }
+
+    let mut lines = Vec::new();
+    if let Some(record) = today.filter(|record| !record.board().is_empty()) {
+        lines.push("TODAY".to_string());
+        for (rank, entry) in record.board().iter().take(BOARD_ROWS).enumerate() {
+            lines.push(format!(
+                "{:>2}  {}{}",
+                rank + 1,
+                challenge.rules.result(entry),
+                if entry.official { "  (official)" } else { "" }
+            ));
+        }
+        if let Some(entry) = record.shareable() {
+            lines.push(String::new());
+            lines.push(challenge.share_text(&puzzles, entry));
+        }
+    }
+    let earlier: Vec<String> = book
+        .history()
+        .filter(|&(day, _)| day < challenge.day)
+        .take(HISTORY_ROWS)
+        .map(|(day, record)| {
+            let rules = DailyChallenge::for_day(day, puzzles.0.len()).rules;
+            format!(
+                "#{}  {}  -  {}",
+                day.number(),
+                short_label(rules, &puzzles),
+                match record.official() {
+                    Some(official) => rules.result(official),
+                    None if record.official_begun => "forfeited".to_string(),
+                    None => "practice only".to_string(),
+                }
+            )
+        })
+        .collect();
+    if !earlier.is_empty() {
+        lines.push(String::new());
+        lines.push("EARLIER".to_string());
+        lines.extend(earlier);
+    }
+    lines.push(String::new());
+    lines.push("Enter to play  -  Esc to go back".to_string());
+    let details = commands
+        .spawn(label_text(lines.join("\n"), assets.font_body.clone()))
+        .id();
+    commands.entity(root).add_child(details);
+}
+
+/// A history row's rules: the variant, or just "Puzzle".
+fn short_label(rules: DailyRules, puzzles: &PuzzleCatalog) -> String {
+    match rules {
+        DailyRules::Solo(_) => rules.label(puzzles),
+        DailyRules::Puzzle(_) => "Puzzle".to_string(),
+    }
+}
+
+#[allow(clippy::too_many_arguments)] // a Bevy system's params are its dependency list
+fn activate(
+    keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
+    screen: Single<(&FocusList, &ShownChallenge), With<DailyRoot>>,
+    clicks: Query<(&Focusable, &Interaction), Changed<Interaction>>,
+    book: Res<DailyBook>,
+    mut active: ResMut<ActiveVariant>,
+    mut session: ResMut<crate::session::SessionConfig>,
+    mut commands: Commands,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    let (list, ShownChallenge(challenge)) = *screen;
+    let nav = read_nav_action(&keys, &pad, list)
+        .or_else(|| clicked_focusable(&clicks).map(NavAction::Select));
+    match nav {
+        Some(NavAction::Select(index)) => {
+            // The official row comes first while it is offered.
+            let official = book.official_open(challenge.day) && index == 0;
+            if let DailyRules::Solo(variant) = challenge.rules {
+                *active = ActiveVariant(variant);
+            }
+            session.seats[0] = Participant::Human;
+            session.mode = challenge.rules.mode();
+            commands.insert_resource(DailyAttempt {
+                challenge: *challenge,
+                official,
+            });
+            next.set(GameState::Session);
+        }
+        Some(NavAction::Back) => next.set(GameState::MainMenu),
+        None => {}
+    }
+}
@@ -0,0 +1,462 @@
+//! Board editor: paint a position by hand, then play it or watch the AI.
+//!
+//! The grid is the whole 10×40 field ([`EditorPosition`]), visible rows at
+//! the bottom and the buffer above them, with the cells the engine spawns
+//! pieces into shaded. Paint with the mouse (left paints the brush, right
+//! erases) or the keyboard cursor; the side panel holds the brush, the hold
+//! piece, the queue, the save slot and whether the position is valid.
+//! Launching starts a [`SessionMode::Practice`] session from the position —
+//! the player's, or the AI's with a bot seated. Positions save to and load
+//! from the editor's slots, and export as a text code (shown, and logged so
+//! it can be copied).
+//!
+//! Keys: arrows move the cursor, Space paints and X erases, Tab or 1–8
+//! picks the brush; a piece letter queues that piece and Backspace drops the
+//! last one; H cycles the hold piece and C clears the board; N picks the
+//! slot, F5 saves and F9 loads; E exports; P plays, A watches the AI, Esc
+//! goes back.
+
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::ai::SkillPreset;
+use crate::assets::GameAssets;
+use crate::editor::{
+    EDITOR_HEIGHT, EDITOR_SLOTS, EDITOR_VISIBLE, EDITOR_WIDTH, EditorPosition, piece_letter,
+    spawn_cells,
+};
+use crate::engine::{CellKind, PieceType};
+use crate::session::{BotSkill, Participant, SessionMode};
+use crate::ui::focus::MenuPad;
+use crate::ui::widgets::{label_text, screen_root, theme, title_text};
+
+/// One grid cell's side, in pixels: forty rows have to fit the window.
+const CELL: f32 = 12.0;
+
+/// The side panel's width; the exported code wraps inside it.
+const PANEL_WIDTH: f32 = 440.0;
+
+/// Longest queue the editor keeps: two bags' worth.
+const QUEUE_MAX: usize = 14;
+
+/// The AI that plays an edited position: the Tier-2 beam (the versus
+/// default, registry index 1) at full strength.
+const EDITOR_BOT: Participant = Participant::Bot {
+    model: 1,
+    skill: BotSkill::Preset(SkillPreset::Master),
+};
+
+/// The brushes, in Tab order (the 1–8 keys pick them directly).
+const BRUSHES: [CellKind; 8] = [
+    CellKind::Some(PieceType::I),
+    CellKind::Some(PieceType::O),
+    CellKind::Some(PieceType::T),
+    CellKind::Some(PieceType::S),
+    CellKind::Some(PieceType::Z),
+    CellKind::Some(PieceType::J),
+    CellKind::Some(PieceType::L),
+    CellKind::Garbage,
+];
+
+/// The keys that queue a piece.
+const QUEUE_KEYS: [(KeyCode, PieceType); 7] = [
+    (KeyCode::KeyI, PieceType::I),
+    (KeyCode::KeyO, PieceType::O),
+    (KeyCode::KeyT, PieceType::T),
+    (KeyCode::KeyS, PieceType::S),
+    (KeyCode::KeyZ, PieceType::Z),
+    (KeyCode::KeyJ, PieceType::J),
+    (KeyCode::KeyL, PieceType::L),
+];
+
+const BRUSH_KEYS: [KeyCode; 8] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+];
+
+pub struct EditorScreenPlugin;
+
+impl Plugin for EditorScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<EditorRoot>()
+            .add_systems(OnEnter(GameState::Editor), setup)
+            .add_systems(
+                Update,
+                (edit_keys, paint_clicks, refresh)
+                    .chain()
+                    .run_if(in_state(GameState::Editor)),
+            );
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct EditorRoot;
+
+/// A grid cell, by column and row from the floor.
+#[derive(Component, Clone, Copy)]
+struct EditorCell {
+    x: usize,
+    y: usize,
+}
+
+/// The side panel's live lines.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum EditorText {
+    Brush,
+    Hold,
+    Queue,
+    Slot,
+    Status,
+    Code,
+}
+
+/// The screen's own state: the cursor, brush and slot, and the last
+/// message.
+#[derive(Resource, Default)]
+struct EditorUi {
+    cursor: (usize, usize),
+    brush: usize,
+    slot: usize,
+    message: Option<String>,
+    code: Option<String>,
+}
+
+fn setup(mut commands: Commands, assets: Res<GameAssets>) {
+    commands.spawn((
+        crate::ui::widgets::menu_camera(),
+        DespawnOnExit(GameState::Editor),
+    ));
+    commands.insert_resource(EditorUi::default());
+
+    let grid = commands
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            row_gap: px(1),
+            ..default()
+        })
+        .id();
+    for y in (0..EDITOR_HEIGHT).rev() {
+        let row = commands
+            .spawn(Node {
+                flex_direction: FlexDirection::Row,
+                column_gap: px(1),
+                ..default()
+            })
+            .id();
+        for x in 0..EDITOR_WIDTH {
+            let cell = commands
+                .spawn((
+                    Button,
+                    EditorCell { x, y },
+                    Node {
+                        width: px(CELL),
+                        height: px(CELL),
+                        border: UiRect::all(px(1)),
+                        ..default()
+                    },
+                    BackgroundColor(theme::GRID),
+                    BorderColor::all(Color::NONE),
+                ))
+                .id();
+            commands.entity(row).add_child(cell);
+        }
+        commands.entity(grid).add_child(row);
+    }
+
+    let panel = commands
+        .spawn(Node {
+            width: px(PANEL_WIDTH),
+            flex_direction: FlexDirection::Column,
+            row_gap: px(8),
+            ..default()
+        })
+        .id();
+    for line in [
+        EditorText::Brush,
+        EditorText::Hold,
+        EditorText::Queue,
+        EditorText::Slot,
+        EditorText::Status,
+        EditorText::Code,
+    ] {
+        let text = commands
+            .spawn((line, label_text("", assets.font_body.clone())))
+            .id();
+        commands.entity(panel).add_child(text);
+    }
+    for hint in [
+        "Arrows move  -  Space paints  -  X erases  -  Tab / 1-8 brush",
+        "I O T S Z J L queue a piece  -  Backspace drops it",
+        "H cycles hold  -  C clears the board",
+        "N slot  -  F5 save  -  F9 load  -  E export code",
+        "P practice  -  A watch the AI  -  Esc back",
+    ] {
+        let text = commands
+            .spawn(label_text(hint, assets.font_body.clone()))
+            .insert(TextFont {
+                font: assets.font_body.clone(),
+                font_size: theme::MICRO_FONT_SIZE,
+                ..default()
+            })
+            .id();
+        commands.entity(panel).add_child(text);
+    }
+
+    let body = commands
+        .spawn(Node {
+            flex_direction: FlexDirection::Row,
+            align_items: AlignItems::Center,
+            column_gap: px(32),
+            ..default()
+        })
+        .add_children(&[grid, panel])
+        .id();
+    commands
+        .spawn((EditorRoot, screen_root(), DespawnOnExit(GameState::Editor)))
+        .with_child(title_text("Board Editor", assets.font.clone()))
+        .add_child(body);
+}
+
+/// Everything on the keyboard (see the module doc).
+fn edit_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
+    storage: Option<Res<crate::storage::StorageResource>>,
+    mut position: ResMut<EditorPosition>,
+    mut ui: ResMut<EditorUi>,
+    mut session: ResMut<crate::session::SessionConfig>,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    if keys.just_pressed(KeyCode::Escape) || pad.back {
+        next.set(GameState::MainMenu);
+        return;
+    }
+
+    let (x, y) = ui.cursor;
+    if keys.just_pressed(KeyCode::ArrowLeft) {
+        ui.cursor.0 = x.saturating_sub(1);
+    }
+    if keys.just_pressed(KeyCode::ArrowRight) {
+        ui.cursor.0 = (x + 1).min(EDITOR_WIDTH - 1);
+    }
+    if keys.just_pressed(KeyCode::ArrowDown) {
+        ui.cursor.1 = y.saturating_sub(1);
+    }
+    if keys.just_pressed(KeyCode::ArrowUp) {
+        ui.cursor.1 = (y + 1).min(EDITOR_HEIGHT - 1);
+    }
+    let (x, y) = ui.cursor;
+    if keys.just_pressed(KeyCode::Space) {
+        position.set(x, y, Some(BRUSHES[ui.brush]));
+    }
+    if keys.just_pressed(KeyCode::KeyX) {
+        position.set(x, y, None);
+    }
+
+    if keys.just_pressed(KeyCode::Tab) {
+        let step = if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+            BRUSHES.len() - 1
+        } else {
+            1
+        };
+        ui.brush = (ui.brush + step) % BRUSHES.len();
+    }
+    if let Some(brush) = BRUSH_KEYS.iter().position(|key| keys.just_pressed(*key)) {
+        ui.brush = brush;
+    }
+
+    for (key, piece_type) in QUEUE_KEYS {
+        if keys.just_pressed(key) && position.queue.len() < QUEUE_MAX {
+            position.queue.push(piece_type);
+        }
+    }
+    if keys.just_pressed(KeyCode::Backspace) {
+        position.queue.pop();
+    }
+    if keys.just_pressed(KeyCode::KeyH) {
+        // None, then each piece in turn.
+        position.hold = match position.hold {
+            None => Some(PieceType::ALL[0]),
+            Some(held) => PieceType::ALL
+                .iter()
+                .position(|p| *p == held)
+                .and_then(|i| PieceType::ALL.get(i + 1))
+                .copied(),
+        };
+    }
+    if keys.just_pressed(KeyCode::KeyC) {
+        position.clear_board();
+    }
+
+    if keys.just_pressed(KeyCode::KeyN) {
+        ui.slot = (ui.slot + 1) % EDITOR_SLOTS;
+        ui.message = None;
+    }
+    if keys.just_pressed(KeyCode::F5) {
+        ui.message = Some(match &storage {
+            Some(storage) => {
+                position.persist(storage.0.as_ref(), ui.slot);
+                format!("Saved to slot {}", ui.slot + 1)
+            }
+            None => "No storage to save to".to_string(),
+        });
+    }
+    if keys.just_pressed(KeyCode::F9) {
+        let loaded = storage
+            .as_ref()
+            .and_then(|storage| EditorPosition::load(storage.0.as_ref(), ui.slot));
+        ui.message = Some(match loaded {
+            Some(loaded) => {
+                *position = loaded;
+                format!("Loaded slot {}", ui.slot + 1)
+            }
+            None => format!("Slot {} is empty", ui.slot + 1),
+        });
+    }
+    if keys.just_pressed(KeyCode::KeyE) {
+        let code = position.encode();
+        info!("position code: {code}");
+        ui.code = Some(code);
+    }
+
+    let launch = if keys.just_pressed(KeyCode::KeyP) {
+        Some(Participant::Human)
+    } else if keys.just_pressed(KeyCode::KeyA) {
+        Some(EDITOR_BOT)
+    } else {
+        None
+    };
+    if let Some(participant) = launch {
+        match position.validate() {
+            Ok(()) => {
+                session.seats[0] = participant;
+                session.mode = SessionMode::Practice { edited: true };
+                next.set(GameState::Session);
+            }
+            Err(problem) => ui.message = Some(format!("Can't start: {problem}")),
+        }
+    }
+}
+
+/// The mouse: a left press paints the brush and moves the cursor there; the
+/// right button erases whatever it is held over.
+fn paint_clicks(
+    mouse: Res<ButtonInput<MouseButton>>,
+    cells: Query<(&EditorCell, &Interaction)>,
+    mut position: ResMut<EditorPosition>,
+    mut ui: ResMut<EditorUi>,
+) {
+    for (cell, interaction) in &cells {
+        match interaction {
+            Interaction::Pressed => {
+                ui.cursor = (cell.x, cell.y);
+                if position.cell(cell.x, cell.y) != Some(BRUSHES[ui.brush]) {
+                    position.set(cell.x, cell.y, Some(BRUSHES[ui.brush]));
+                }
+            }
+            Interaction::Hovered
+                if mouse.pressed(MouseButton::Right) && position.cell(cell.x, cell.y).is_some() =>
+            {
+                position.set(cell.x, cell.y, None);
+            }
+            _ => {}
+        }
+    }
+}
+
+fn cell_color(cell: Option<CellKind>) -> Option<Color> {
+    match cell {
+        Some(CellKind::Some(piece_type)) => Some(crate::level::common::piece_color(piece_type)),
+        Some(_) => Some(theme::GARBAGE),
+        None => None,
+    }
+}
+
+fn brush_name(cell: CellKind) -> String {
+    match cell {
+        CellKind::Some(piece_type) => piece_letter(piece_type).to_string(),
+        _ => "Garbage".to_string(),
+    }
+}
+
+/// Repaint the grid and the panel after any edit.
+fn refresh(
+    position: Res<EditorPosition>,
+    ui: Res<EditorUi>,
+    mut cells: Query<(&EditorCell, &mut BackgroundColor, &mut BorderColor)>,
+    mut texts: Query<(&EditorText, &mut Text, &mut TextColor)>,
+) {
+    if !position.is_changed() && !ui.is_changed() {
+        return;
+    }
+    let spawn: Vec<(usize, usize)> = spawn_cells().collect();
+    for (cell, mut background, mut border) in &mut cells {
+        let empty = if spawn.contains(&(cell.x, cell.y)) {
+            theme::ACCENT.with_alpha(0.3)
+        } else if cell.y >= EDITOR_VISIBLE {
+            theme::BG
+        } else {
+            theme::GRID
+        };
+        background.0 = cell_color(position.cell(cell.x, cell.y)).unwrap_or(empty);
+        *border = BorderColor::all(if ui.cursor == (cell.x, cell.y) {
+            theme::ACCENT
+        } else {
+            Color::NONE
+        });
+    }
+
+    let valid = position.validate();
+    for (line, mut text, mut color) in &mut texts {
+        let (content, tint) = match line {
+            EditorText::Brush => (
+                format!("BRUSH  {}", brush_name(BRUSHES[ui.brush])),
+                theme::TEXT,
+            ),
+            EditorText::Hold => (
+                format!(
+                    "HOLD  {}",
+                    position
+                        .hold
+                        .map_or("-".to_string(), |p| piece_letter(p).to_string())
+                ),
+                theme::TEXT,
+            ),
+            EditorText::Queue => {
+                let queue: String = position.queue.iter().copied().map(piece_letter).collect();
+                (
+                    format!(
+                        "QUEUE  {}",
+                        if queue.is_empty() {
+                            "(the bag deals)".to_string()
+                        } else {
+                            queue
+                        }
+                    ),
+                    theme::TEXT,
+                )
+            }
+            EditorText::Slot => (
+                format!("SLOT  {} / {EDITOR_SLOTS}", ui.slot + 1),
+                theme::TEXT,
+            ),
+            EditorText::Status => match (&ui.message, &valid) {
+                (Some(message), _) => (message.clone(), theme::ACCENT),
+                (None, Ok(())) => ("Ready to play".to_string(), theme::TEXT_DIM),
+                (None, Err(problem)) => (format!("Invalid: {problem}"), theme::ATTACK),
+            },
+            EditorText::Code => (ui.code.clone().unwrap_or_default(), theme::TEXT_DIM),
+        };
+        if text.0 != content {
+            text.0 = content;
+        }
+        color.0 = tint;
+    }
+}
@@ -5,6 +5,7 @@ use bevy::prelude::*;
 
 use crate::GameState;
 use crate::assets::GameAssets;
+use crate::ui::focus::MenuPad;
 use crate::ui::widgets::{label_text, screen_root, title_text};
 
 pub struct HelpScreenPlugin;
@@ -38,8 +39,12 @@ fn setup(mut commands: Commands, assets: Res<GameAssets>) {
     ));
 }
 
-fn back(keys: Res<ButtonInput<KeyCode>>, mut next: ResMut<NextState<GameState>>) {
-    if keys.just_pressed(KeyCode::Escape) {
+fn back(
+    keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    if keys.just_pressed(KeyCode::Escape) || pad.back {
         next.set(GameState::MainMenu);
     }
 }
@@ -0,0 +1,379 @@
+//! Online lobby: host or join a room, ready up, and start an online match.
+//!
+//! Four rows in the shared `FocusList` idiom: **Mode** (Host / Join, cycled
+//! with Left/Right like the setup screen's seats), **Address** and **Room**
+//! (typed while focused — digits, `.` and `:`, Backspace deletes; the menu
+//! keys keep their meaning), and **Connect**, which becomes **Ready** /
+//! **Not Ready** once seated. A status line mirrors the room's roster.
+//!
+//! Hosting runs a tetr-net [`Relay`] inside this process on the address's
+//! port (kept for the rest of the run, so a second host reuses it) and joins
+//! it over loopback; the rival joins the same address and room. When every
+//! seat is ready the relay mixes the seats' seed shares and starts the room:
+//! the lobby seats the local keyboard and a
+//! [`Participant::Remote`](crate::session::Participant::Remote) rival and
+//! hands the session an [`OnlineMatch`]. Joining blocks only for the
+//! handshake; on a target without sockets (wasm) it fails with an error line.
+
+use std::net::SocketAddr;
+
+use bevy::prelude::*;
+use tetr_net::{Lobby, NetConfig, Relay};
+
+use crate::GameState;
+use crate::assets::GameAssets;
+use crate::session::online::OnlineMatch;
+use crate::session::{Participant, SessionConfig, SessionMode};
+use crate::ui::focus::{
+    FocusList, Focusable, MenuPad, NavAction, clicked_focusable, focus_navigation, read_nav_action,
+};
+use crate::ui::widgets::{label_text, menu_button_sized, screen_root, title_text};
+
+/// The address field's starting value: this machine, on the port the
+/// standalone `tetr-relay` binary listens on.
+const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+/// Room codes are this many digits.
+const ROOM_DIGITS: usize = 4;
+/// Longest address the field accepts (`255.255.255.255:65535`).
+const MAX_ADDRESS_LEN: usize = 21;
+
+pub struct LobbyPlugin;
+
+impl Plugin for LobbyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LobbyForm>()
+            .add_systems(OnEnter(GameState::Lobby), setup)
+            .add_systems(OnExit(GameState::Lobby), leave_lobby)
+            .add_systems(
+                Update,
+                (
+                    focus_navigation::<LobbyRoot>,
+                    edit_fields,
+                    activate,
+                    poll_lobby,
+                    refresh_labels,
+                )
+                    .chain()
+                    .run_if(in_state(GameState::Lobby)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct LobbyRoot;
+
+/// The row a button is, in focus order.
+#[derive(Component, Clone, Copy, PartialEq, Eq)]
+enum LobbyRow {
+    Mode,
+    Address,
+    Room,
+    Connect,
+}
+
+const ROWS: [LobbyRow; 4] = [
+    LobbyRow::Mode,
+    LobbyRow::Address,
+    LobbyRow::Room,
+    LobbyRow::Connect,
+];
+
+/// The status line under the rows.
+#[derive(Component)]
+struct LobbyStatus;
+
+/// What the lobby screen holds: the form (kept between visits, like the
+/// setup screen's seats) and, once connected, the seat in the room.
+#[derive(Resource)]
+struct LobbyForm {
+    host: bool,
+    address: String,
+    room: String,
+    lobby: Option<Lobby>,
+    ready: bool,
+    /// The last failure, shown until the next attempt.
+    error: Option<String>,
+}
+
+impl Default for LobbyForm {
+    fn default() -> Self {
+        Self {
+            host: true,
+            address: DEFAULT_ADDRESS.to_string(),
+            room: "0000".to_string(),
+            lobby: None,
+            ready: false,
+            error: None,
+        }
+    }
+}
+
+/// The relay this process hosts, once it has hosted one. Relays run until the
+/// app exits, so hosting again (another room, a second match) reuses it.
+#[derive(Resource)]
+struct HostedRelay(SocketAddr);
+
+fn setup(mut commands: Commands, assets: Res<GameAssets>, mut form: ResMut<LobbyForm>) {
+    form.lobby = None;
+    form.ready = false;
+    form.error = None;
+    commands.spawn((
+        crate::ui::widgets::menu_camera(),
+        DespawnOnExit(GameState::Lobby),
+    ));
+    let root = commands
+        .spawn((
+            LobbyRoot,
+            FocusList::new(ROWS.len()),
+            screen_root(),
+            DespawnOnExit(GameState::Lobby),
+            children![title_text("ONLINE", assets.font.clone())],
+        ))
+        .id();
+    for (index, row) in ROWS.into_iter().enumerate() {
+        let button = commands
+            .spawn((
+                menu_button_sized(index, "", assets.font.clone(), 460.0),
+                row,
+            ))
+            .id();
+        commands.entity(root).add_child(button);
+    }
+    let status = commands
+        .spawn((LobbyStatus, label_text("", assets.font_body.clone())))
+        .id();
+    let hint = commands
+        .spawn(label_text(
+            "Type on Address / Room  -  Left/Right mode  -  Enter select  -  Esc back",
+            assets.font_body.clone(),
+        ))
+        .id();
+    commands.entity(root).add_children(&[status, hint]);
+}
+
+/// Leaving the screen gives up the seat (unless the match took it).
+fn leave_lobby(mut form: ResMut<LobbyForm>) {
+    form.lobby = None;
+    form.ready = false;
+}
+
+/// The character a key types into the address/room fields, if any.
+fn typed_char(key: KeyCode) -> Option<char> {
+    let digit = match key {
+        KeyCode::Digit0 | KeyCode::Numpad0 => 0,
+        KeyCode::Digit1 | KeyCode::Numpad1 => 1,
+        KeyCode::Digit2 | KeyCode::Numpad2 => 2,
+        KeyCode::Digit3 | KeyCode::Numpad3 => 3,
+        KeyCode::Digit4 | KeyCode::Numpad4 => 4,
+        KeyCode::Digit5 | KeyCode::Numpad5 => 5,
+        KeyCode::Digit6 | KeyCode::Numpad6 => 6,
+        KeyCode::Digit7 | KeyCode::Numpad7 => 7,
+        KeyCode::Digit8 | KeyCode::Numpad8 => 8,
+        KeyCode::Digit9 | KeyCode::Numpad9 => 9,
+        KeyCode::Period | KeyCode::NumpadDecimal => return Some('.'),
+        KeyCode::Semicolon => return Some(':'),
+        _ => return None,
+    };
+    char::from_digit(digit, 10)
+}
+
+/// Typing on the focused Address / Room row edits it; Left/Right on the Mode
+/// row flips Host / Join. Locked while seated in a room.
+fn edit_fields(
+    keys: Res<ButtonInput<KeyCode>>,
+    list: Single<&FocusList, With<LobbyRoot>>,
+    mut form: ResMut<LobbyForm>,
+) {
+    if form.lobby.is_some() {
+        return;
+    }
+    let row = ROWS[list.index.min(ROWS.len() - 1)];
+    if row == LobbyRow::Mode
+        && (keys.just_pressed(KeyCode::ArrowLeft) || keys.just_pressed(KeyCode::ArrowRight))
+    {
+        form.host = !form.host;
+        return;
+    }
+    let form = &mut *form;
+    let (field, max, digits_only) = match row {
+        LobbyRow::Address => (&mut form.address, MAX_ADDRESS_LEN, false),
+        LobbyRow::Room => (&mut form.room, ROOM_DIGITS, true),
+        _ => return,
+    };
+    if keys.just_pressed(KeyCode::Backspace) {
+        field.pop();
+    }
+    for &key in keys.get_just_pressed() {
+        if let Some(c) = typed_char(key)
+            && (!digits_only || c.is_ascii_digit())
+            && field.len() < max
+        {
+            field.push(c);
+        }
+    }
+}
+
+/// Enter / click: Mode flips, Connect joins (hosting first when hosting),
+/// Ready toggles the seat's ready flag. Esc returns to the main menu.
+#[allow(clippy::too_many_arguments)] // a Bevy system's params are its dependency list
+fn activate(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
+    list: Single<&FocusList, With<LobbyRoot>>,
+    clicks: Query<(&Focusable, &Interaction), Changed<Interaction>>,
+    hosted: Option<Res<HostedRelay>>,
+    mut form: ResMut<LobbyForm>,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    let nav = read_nav_action(&keys, &pad, *list)
+        .or_else(|| clicked_focusable(&clicks).map(NavAction::Select));
+    let index = match nav {
+        Some(NavAction::Back) => {
+            next.set(GameState::MainMenu);
+            return;
+        }
+        Some(NavAction::Select(index)) => index,
+        None => return,
+    };
+    match ROWS.get(index) {
+        Some(LobbyRow::Mode) if form.lobby.is_none() => form.host = !form.host,
+        Some(LobbyRow::Connect) => match &form.lobby {
+            Some(lobby) => {
+                let ready = !form.ready;
+                lobby.set_ready(ready);
+                form.ready = ready;
+            }
+            None => {
+                form.error = None;
+                match connect(&form, hosted.as_deref(), &mut commands) {
+                    Ok(lobby) => form.lobby = Some(lobby),
+                    Err(err) => form.error = Some(err),
+                }
+            }
+        },
+        _ => {}
+    }
+}
+
+/// Join the room, binding this process's relay first when hosting.
+fn connect(
+    form: &LobbyForm,
+    hosted: Option<&HostedRelay>,
+    commands: &mut Commands,
+) -> Result<Lobby, String> {
+    if form.room.len() != ROOM_DIGITS {
+        return Err(format!("the room code is {ROOM_DIGITS} digits"));
+    }
+    let address: SocketAddr = form
+        .address
+        .parse()
+        .map_err(|_| format!("\"{}\" is not an address:port", form.address))?;
+    let target = if form.host {
+        let port = match hosted {
+            Some(relay) => relay.0.port(),
+            None => {
+                let relay = Relay::bind(("0.0.0.0", address.port()))
+                    .map_err(|err| format!("cannot host on port {}: {err}", address.port()))?;
+                let bound = relay.local_addr().map_err(|err| err.to_string())?;
+                info!("online: hosting a relay on {bound}");
+                relay.spawn();
+                commands.insert_resource(HostedRelay(bound));
+                bound.port()
+            }
+        };
+        SocketAddr::from(([127, 0, 0, 1], port))
+    } else {
+        address
+    };
+    Lobby::join(target, &form.room, NetConfig::default()).map_err(|err| err.to_string())
+}
+
+/// Watch the room: a dropped connection returns to the form; a started room
+/// seats the match and enters the session.
+fn poll_lobby(
+    mut commands: Commands,
+    mut form: ResMut<LobbyForm>,
+    mut config: ResMut<SessionConfig>,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    let Some(lobby) = &form.lobby else {
+        return;
+    };
+    match lobby.started() {
+        Ok(None) => {}
+        Ok(Some(session)) => {
+            let local = session.seat() as usize;
+            info!(
+                "online: room {} started, seat {local}, seed {}",
+                form.room,
+                session.seed()
+            );
+            config.seats = [Participant::Remote; 2];
+            config.seats[local.min(1)] = Participant::Human;
+            config.mode = SessionMode::Versus;
+            config.seed = None;
+            commands.insert_resource(OnlineMatch::new(session));
+            form.lobby = None;
+            next.set(GameState::Session);
+        }
+        Err(err) => {
+            form.error = Some(err.to_string());
+            form.lobby = None;
+            form.ready = false;
+        }
+    }
+}
+
+/// Keep the row labels and the status line mirroring the form and roster.
+fn refresh_labels(
+    form: Res<LobbyForm>,
+    rows: Query<(&LobbyRow, &Children)>,
+    mut status: Query<&mut Text, With<LobbyStatus>>,
+    mut texts: Query<&mut Text, Without<LobbyStatus>>,
+) {
+    for (row, children) in &rows {
+        let label = match row {
+            LobbyRow::Mode => format!("MODE  < {} >", if form.host { "Host" } else { "Join" }),
+            LobbyRow::Address => format!("ADDRESS  {}", form.address),
+            LobbyRow::Room => format!("ROOM  {}", form.room),
+            LobbyRow::Connect => match (&form.lobby, form.ready) {
+                (None, _) if form.host => "Host and Connect".to_string(),
+                (None, _) => "Connect".to_string(),
+                (Some(_), false) => "Ready".to_string(),
+                (Some(_), true) => "Not Ready".to_string(),
+            },
+        };
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(child)
+                && text.0 != label
+            {
+                text.0 = label.clone();
+            }
+        }
+    }
+    let line = match (&form.error, &form.lobby) {
+        (Some(err), _) => format!("Error: {err}"),
+        (None, None) => "Not connected".to_string(),
+        (None, Some(lobby)) => {
+            let roster = lobby.roster();
+            let seat = lobby.seat() as usize;
+            let rival = (0..roster.present.len()).find(|&s| s != seat);
+            let rival = match rival {
+                Some(s) if roster.ready[s] => "ready",
+                Some(s) if roster.present[s] => "not ready",
+                _ => "waiting to join",
+            };
+            format!(
+                "Room {}  -  seat P{}  -  rival {rival}",
+                form.room,
+                seat + 1
+            )
+        }
+    };
+    for mut text in &mut status {
+        if text.0 != line {
+            text.0 = line.clone();
+        }
+    }
+}
@@ -1,4 +1,5 @@
-//! Main menu: Play / Options / Help / High Scores, keyboard-navigable.
+//! Main menu: Play / Daily Challenge / Puzzles / Editor / Options / Help / High Scores / Replays,
+//! keyboard-navigable.
 //!
 //! Reference implementation of the shared focus-navigation pattern: a
 //! [`FocusList`] on the root, [`menu_button`] rows each tagged with a
@@ -10,7 +11,7 @@ use bevy::prelude::*;
 use crate::GameState;
 use crate::assets::GameAssets;
 use crate::ui::focus::{
-    FocusList, Focusable, NavAction, clicked_focusable, focus_navigation, read_nav_action,
+    FocusList, Focusable, MenuPad, NavAction, clicked_focusable, focus_navigation, read_nav_action,
 };
 use crate::ui::widgets::{menu_button, screen_root, title_text};
 
@@ -38,20 +39,30 @@ struct MainMenuRoot;
 #[reflect(Component)]
 enum MainMenuAction {
     Play,
+    Daily,
     Versus,
+    Online,
     WatchAi,
+    Puzzles,
+    Editor,
     Options,
     Help,
     HighScores,
+    Replays,
 }
 
-const ITEMS: [(MainMenuAction, &str); 6] = [
+const ITEMS: [(MainMenuAction, &str); 11] = [
     (MainMenuAction::Play, "Play"),
+    (MainMenuAction::Daily, "Daily Challenge"),
     (MainMenuAction::Versus, "Versus"),
+    (MainMenuAction::Online, "Online"),
     (MainMenuAction::WatchAi, "Watch AI"),
+    (MainMenuAction::Puzzles, "Puzzles"),
+    (MainMenuAction::Editor, "Editor"),
     (MainMenuAction::Options, "Options"),
     (MainMenuAction::Help, "Help"),
     (MainMenuAction::HighScores, "High Scores"),
+    (MainMenuAction::Replays, "Replays"),
 ];
 
 fn setup(mut commands: Commands, assets: Res<GameAssets>) {
@@ -84,11 +95,13 @@ fn setup(mut commands: Commands, assets: Res<GameAssets>) {
 /// **Play** seats you and goes to mode select; **Watch AI** picks its bot in
 /// the seat picker first, then the mode — both land in a one-seat Solo
 /// session, differing only in who occupies the seat. **Versus** configures
-/// two seats. The seats are written HERE (and by the pickers), so a previous
+/// two seats; **Online** has the lobby seat them when its room starts. The
+/// seats are written HERE (and by the pickers and the lobby), so a previous
 /// run's configuration can never leak into the next.
 #[allow(clippy::too_many_arguments)] // a Bevy system's params are its dependency list
 fn activate(
     keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
     list: Single<&FocusList, With<MainMenuRoot>>,
     actions: Query<(&Focusable, &MainMenuAction)>,
     clicks: Query<(&Focusable, &Interaction), Changed<Interaction>>,
@@ -98,7 +111,7 @@ fn activate(
 ) {
     // Select via keyboard (Enter/Space on the focused row) or a mouse click on a
     // row. Esc is a no-op here (the main menu is the root).
-    let Some(index) = read_nav_action(&keys, *list)
+    let Some(index) = read_nav_action(&keys, &pad, *list)
         .and_then(|nav| match nav {
             NavAction::Select(index) => Some(index),
             NavAction::Back => None,
@@ -117,18 +130,26 @@ fn activate(
                 session.seats[0] = crate::session::Participant::Human;
                 next.set(GameState::ModeSelect);
             }
+            // The daily screen seats you when an attempt is chosen.
+            MainMenuAction::Daily => next.set(GameState::Daily),
             MainMenuAction::Versus => {
                 *setup_kind = crate::screens::session_setup::SetupKind::Versus;
                 next.set(GameState::SessionSetup);
             }
+            // The lobby seats both boards when its room starts.
+            MainMenuAction::Online => next.set(GameState::Lobby),
             MainMenuAction::WatchAi => {
                 *setup_kind = crate::screens::session_setup::SetupKind::WatchAi;
                 // Watch-AI picks its bot in the seat picker, then the mode.
                 next.set(GameState::SessionSetup);
             }
+            // The browser seats you when a puzzle is chosen.
+            MainMenuAction::Puzzles => next.set(GameState::Puzzles),
+            MainMenuAction::Editor => next.set(GameState::Editor),
             MainMenuAction::Options => next.set(GameState::Options),
             MainMenuAction::Help => next.set(GameState::Help),
             MainMenuAction::HighScores => next.set(GameState::HighScores),
+            MainMenuAction::Replays => next.set(GameState::Replays),
         }
     }
 }
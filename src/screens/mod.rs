@@ -1,7 +1,7 @@
 //! Menu screen shells (A1.1).
 //!
 //! One plugin per non-gameplay screen — Title, MainMenu, ModeSelect, Options,
-//! Help, HighScores. Each spawns a keyboard-navigable screen root on enter and
+//! Help, HighScores, Replays, Puzzles, Editor, Openers, Daily, SessionSetup, Lobby. Each spawns a keyboard-navigable screen root on enter and
 //! tears it down on exit (`DespawnOnExit`). Navigation works (Up/Down move
 //! focus, Enter selects, Esc backs out); the *content* of Options/Help/
 //! HighScores is supplied by the corresponding feature plugin in
@@ -11,11 +11,17 @@
 
 use bevy::prelude::*;
 
+mod daily;
+mod editor;
 mod help;
 mod high_scores;
+mod lobby;
 mod main_menu;
 mod mode_select;
+mod openers;
 mod options;
+mod puzzles;
+mod replays;
 mod session_setup;
 mod title;
 
@@ -23,7 +29,7 @@ mod title;
 // `crate::features` can attach their content under the right entity.
 pub(crate) use help::HelpRoot;
 pub(crate) use high_scores::HighScoresRoot;
-pub(crate) use options::OptionsRoot;
+pub(crate) use options::{OptionsPage, OptionsRoot};
 
 /// Registers every menu screen plugin.
 pub struct ScreensPlugin;
@@ -41,9 +47,15 @@ impl Plugin for ScreensPlugin {
                 main_menu::MainMenuPlugin,
                 mode_select::ModeSelectPlugin,
                 session_setup::VersusSetupPlugin,
+                lobby::LobbyPlugin,
                 options::OptionsScreenPlugin,
                 help::HelpScreenPlugin,
                 high_scores::HighScoresScreenPlugin,
+                replays::ReplaysScreenPlugin,
+                puzzles::PuzzlesScreenPlugin,
+                editor::EditorScreenPlugin,
+                openers::OpenersScreenPlugin,
+                daily::DailyScreenPlugin,
             ));
     }
 }
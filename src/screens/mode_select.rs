@@ -1,15 +1,18 @@
 //! Mode select: choose a [`Variant`] then start the game.
 //!
-//! Each row corresponds to a [`Variant`]; selecting it writes [`ActiveVariant`]
+//! Each row corresponds to a [`Variant`] (every Dig length and flavour is its
+//! own row); selecting it writes [`ActiveVariant`]
 //! (which the engine bridge reads when building the engine) and transitions to
-//! a one-seat Solo session. Esc returns to the main menu.
+//! a one-seat Solo session. A human also gets two last rows: Practice, free
+//! play with undo (see `session::practice`), and Openers, the opener trainer's
+//! list. Esc returns to the main menu.
 
 use bevy::prelude::*;
 
 use crate::GameState;
 use crate::assets::GameAssets;
 use crate::ui::focus::{
-    FocusList, Focusable, NavAction, clicked_focusable, focus_navigation, read_nav_action,
+    FocusList, Focusable, MenuPad, NavAction, clicked_focusable, focus_navigation, read_nav_action,
 };
 use crate::ui::widgets::{label_text, menu_button, screen_root, title_text};
 use crate::variant::{ActiveVariant, Variant};
@@ -33,7 +36,18 @@ impl Plugin for ModeSelectPlugin {
 #[reflect(Component)]
 struct ModeSelectRoot;
 
-fn setup(mut commands: Commands, assets: Res<GameAssets>) {
+/// Practice and the opener trainer are for the player: a bot seat (Watch AI)
+/// gets no rows for them.
+fn practice_rows(session: &crate::session::SessionConfig) -> bool {
+    session.seats[0] == crate::session::Participant::Human
+}
+
+fn setup(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    session: Res<crate::session::SessionConfig>,
+) {
+    let rows = Variant::ALL.len() + 2 * usize::from(practice_rows(&session));
     commands.spawn((
         crate::ui::widgets::menu_camera(),
         DespawnOnExit(GameState::ModeSelect),
@@ -41,7 +55,7 @@ fn setup(mut commands: Commands, assets: Res<GameAssets>) {
     let root = commands
         .spawn((
             ModeSelectRoot,
-            FocusList::new(Variant::ALL.len()),
+            FocusList::new(rows),
             screen_root(),
             DespawnOnExit(GameState::ModeSelect),
             children![
@@ -51,13 +65,15 @@ fn setup(mut commands: Commands, assets: Res<GameAssets>) {
         ))
         .id();
 
-    for (index, variant) in Variant::ALL.into_iter().enumerate() {
+    let names = Variant::ALL.map(Variant::display_name);
+    for (index, name) in names
+        .into_iter()
+        .chain(["Practice", "Openers"])
+        .take(rows)
+        .enumerate()
+    {
         let button = commands
-            .spawn(menu_button(
-                index,
-                variant.display_name(),
-                assets.font.clone(),
-            ))
+            .spawn(menu_button(index, name, assets.font.clone()))
             .id();
         commands.entity(root).add_child(button);
     }
@@ -65,6 +81,7 @@ fn setup(mut commands: Commands, assets: Res<GameAssets>) {
 
 fn activate(
     keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
     list: Single<&FocusList, With<ModeSelectRoot>>,
     clicks: Query<(&Focusable, &Interaction), Changed<Interaction>>,
     mut active: ResMut<ActiveVariant>,
@@ -72,8 +89,8 @@ fn activate(
     mut next: ResMut<NextState<GameState>>,
 ) {
     // Keyboard (Enter/Space) or a mouse click both select the focused variant.
-    let nav =
-        read_nav_action(&keys, *list).or_else(|| clicked_focusable(&clicks).map(NavAction::Select));
+    let nav = read_nav_action(&keys, &pad, *list)
+        .or_else(|| clicked_focusable(&clicks).map(NavAction::Select));
     match nav {
         Some(NavAction::Select(index)) => {
             if let Some(&variant) = Variant::ALL.get(index) {
@@ -82,6 +99,11 @@ fn activate(
                 // Watch AI = the picked bot); this screen sets the rules.
                 session.mode = crate::session::SessionMode::Solo { variant };
                 next.set(GameState::Session);
+            } else if index == Variant::ALL.len() && practice_rows(&session) {
+                session.mode = crate::session::SessionMode::Practice { edited: false };
+                next.set(GameState::Session);
+            } else if index == Variant::ALL.len() + 1 && practice_rows(&session) {
+                next.set(GameState::Openers);
             }
         }
         Some(NavAction::Back) => next.set(GameState::MainMenu),
@@ -0,0 +1,106 @@
+//! Openers screen: the opener trainer's list.
+//!
+//! Each row is an opener from the [`OpenerCatalog`]; selecting one seats the
+//! player in a [`SessionMode::Opener`] session on a fresh deal. Esc returns
+//! to mode select.
+
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::assets::GameAssets;
+use crate::openers::OpenerCatalog;
+use crate::session::{Participant, SessionMode};
+use crate::ui::focus::{
+    FocusList, Focusable, MenuPad, NavAction, clicked_focusable, focus_navigation, read_nav_action,
+};
+use crate::ui::widgets::{label_text, menu_button_sized, screen_root, title_text};
+
+/// Row width: a name, a piece count and a finish.
+const ROW_WIDTH: f32 = 700.0;
+
+pub struct OpenersScreenPlugin;
+
+impl Plugin for OpenersScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<OpenersRoot>()
+            .add_systems(OnEnter(GameState::Openers), setup)
+            .add_systems(
+                Update,
+                (focus_navigation::<OpenersRoot>, activate)
+                    .chain()
+                    .run_if(in_state(GameState::Openers)),
+            );
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct OpenersRoot;
+
+fn setup(mut commands: Commands, assets: Res<GameAssets>, catalog: Res<OpenerCatalog>) {
+    commands.spawn((
+        crate::ui::widgets::menu_camera(),
+        DespawnOnExit(GameState::Openers),
+    ));
+    let openers = catalog.0.openers();
+    let root = commands
+        .spawn((
+            OpenersRoot,
+            FocusList::new(openers.len()),
+            screen_root(),
+            DespawnOnExit(GameState::Openers),
+            children![
+                title_text("Openers", assets.font.clone()),
+                label_text(
+                    "Build the outlined target  -  Enter to play  -  Esc to go back",
+                    assets.font_body.clone()
+                ),
+            ],
+        ))
+        .id();
+
+    for (index, opener) in openers.iter().enumerate() {
+        let setup = &opener.setups()[0];
+        let finish = if setup.finisher().is_some() {
+            "T-spin finish"
+        } else {
+            "setup only"
+        };
+        let label = format!(
+            "{}  -  {} pieces  -  {finish}",
+            opener.name,
+            setup.targets().len()
+        );
+        let button = commands
+            .spawn(menu_button_sized(
+                index,
+                label,
+                assets.font.clone(),
+                ROW_WIDTH,
+            ))
+            .id();
+        commands.entity(root).add_child(button);
+    }
+}
+
+fn activate(
+    keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
+    list: Single<&FocusList, With<OpenersRoot>>,
+    clicks: Query<(&Focusable, &Interaction), Changed<Interaction>>,
+    catalog: Res<OpenerCatalog>,
+    mut session: ResMut<crate::session::SessionConfig>,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    let nav = read_nav_action(&keys, &pad, *list)
+        .or_else(|| clicked_focusable(&clicks).map(NavAction::Select));
+    match nav {
+        Some(NavAction::Select(index)) if catalog.get(index).is_some() => {
+            session.seats[0] = Participant::Human;
+            session.mode = SessionMode::Opener { index };
+            next.set(GameState::Session);
+        }
+        Some(NavAction::Back) => next.set(GameState::ModeSelect),
+        _ => {}
+    }
+}
@@ -2,12 +2,20 @@
 //! (next-count, toggles, volumes, rebinds reading [`GameSettings`]) are added by
 //! the options feature plugin (`src/features/options.rs`) onto this same state.
 //!
+//! The screen has pages ([`OptionsPage`]): the general settings, one rebind
+//! page per player's keys, the gamepad page, and the touch page. Switching page re-enters
+//! [`GameState::Options`], so every page is a fresh build of the same screen;
+//! Esc (or a pad's East) on a sub-page returns to the general page, and from
+//! there to the menu.
+//!
 //! [`GameSettings`]: crate::settings::GameSettings
 
 use bevy::prelude::*;
 
 use crate::GameState;
 use crate::assets::GameAssets;
+use crate::settings::Keymap;
+use crate::ui::focus::MenuPad;
 use crate::ui::widgets::{label_text, screen_root, title_text};
 
 pub struct OptionsScreenPlugin;
@@ -15,6 +23,7 @@ pub struct OptionsScreenPlugin;
 impl Plugin for OptionsScreenPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<OptionsRoot>()
+            .init_resource::<OptionsPage>()
             .add_systems(OnEnter(GameState::Options), setup)
             .add_systems(Update, back.run_if(in_state(GameState::Options)));
     }
@@ -25,7 +34,34 @@ impl Plugin for OptionsScreenPlugin {
 #[reflect(Component)]
 pub struct OptionsRoot;
 
-fn setup(mut commands: Commands, assets: Res<GameAssets>) {
+/// Which page of the Options screen is showing.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum OptionsPage {
+    /// Gameplay, handling and audio, plus links to the key pages.
+    #[default]
+    General,
+    /// One player's rebind list.
+    Keys(Keymap),
+    /// The pad bindings, stick threshold and pad owners.
+    Gamepad,
+    /// The touch scheme and the on-screen buttons' hand and size.
+    Touch,
+}
+
+impl OptionsPage {
+    /// The page's heading, also the label of the general page's link to it.
+    pub(crate) fn title(self) -> &'static str {
+        match self {
+            OptionsPage::General => "Options",
+            OptionsPage::Keys(Keymap::P1) => "Player 1 Keys",
+            OptionsPage::Keys(Keymap::P2) => "Player 2 Keys",
+            OptionsPage::Gamepad => "Gamepad",
+            OptionsPage::Touch => "Touch Controls",
+        }
+    }
+}
+
+fn setup(mut commands: Commands, assets: Res<GameAssets>, page: Res<OptionsPage>) {
     commands.spawn((
         crate::ui::widgets::menu_camera(),
         DespawnOnExit(GameState::Options),
@@ -35,14 +71,25 @@ fn setup(mut commands: Commands, assets: Res<GameAssets>) {
         screen_root(),
         DespawnOnExit(GameState::Options),
         children![
-            title_text("Options", assets.font.clone()),
+            title_text(page.title(), assets.font.clone()),
             label_text("Esc to go back", assets.font_body.clone()),
         ],
     ));
 }
 
-fn back(keys: Res<ButtonInput<KeyCode>>, mut next: ResMut<NextState<GameState>>) {
-    if keys.just_pressed(KeyCode::Escape) {
-        next.set(GameState::MainMenu);
+fn back(
+    keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
+    mut page: ResMut<OptionsPage>,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    if keys.just_pressed(KeyCode::Escape) || pad.back {
+        match *page {
+            OptionsPage::General => next.set(GameState::MainMenu),
+            OptionsPage::Keys(_) | OptionsPage::Gamepad | OptionsPage::Touch => {
+                *page = OptionsPage::General;
+                next.set(GameState::Options);
+            }
+        }
     }
 }
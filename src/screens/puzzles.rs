@@ -0,0 +1,122 @@
+//! Puzzles screen: the bundled puzzles, each with its goal, piece budget and
+//! whether it has been solved.
+//!
+//! Each row is a puzzle from the [`PuzzleCatalog`]; selecting one seats the
+//! player in a [`SessionMode::Puzzle`] session. Solved puzzles are ticked from
+//! the [`PuzzleProgress`] the session keeps. Esc returns to the main menu.
+
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::assets::GameAssets;
+use crate::puzzle::{PuzzleCatalog, PuzzleProgress};
+use crate::session::{Participant, SessionMode};
+use crate::ui::focus::{
+    FocusList, Focusable, MenuPad, NavAction, clicked_focusable, focus_navigation, read_nav_action,
+};
+use crate::ui::widgets::{label_text, menu_button_sized, screen_root, title_text};
+
+/// Row width: a name, a goal and a budget.
+const ROW_WIDTH: f32 = 800.0;
+
+pub struct PuzzlesScreenPlugin;
+
+impl Plugin for PuzzlesScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<PuzzlesRoot>()
+            .add_systems(OnEnter(GameState::Puzzles), setup)
+            .add_systems(
+                Update,
+                (focus_navigation::<PuzzlesRoot>, activate)
+                    .chain()
+                    .run_if(in_state(GameState::Puzzles)),
+            );
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct PuzzlesRoot;
+
+fn setup(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    catalog: Res<PuzzleCatalog>,
+    progress: Res<PuzzleProgress>,
+) {
+    commands.spawn((
+        crate::ui::widgets::menu_camera(),
+        DespawnOnExit(GameState::Puzzles),
+    ));
+    let solved = catalog
+        .0
+        .iter()
+        .filter(|puzzle| progress.is_solved(&puzzle.id))
+        .count();
+    let hint = format!(
+        "{solved} of {} solved  -  Enter to play  -  Esc to go back",
+        catalog.0.len()
+    );
+    let root = commands
+        .spawn((
+            PuzzlesRoot,
+            FocusList::new(catalog.0.len()),
+            screen_root(),
+            DespawnOnExit(GameState::Puzzles),
+            children![
+                title_text("Puzzles", assets.font.clone()),
+                label_text(hint, assets.font_body.clone()),
+            ],
+        ))
+        .id();
+
+    for (index, puzzle) in catalog.0.iter().enumerate() {
+        let mark = if progress.is_solved(&puzzle.id) {
+            "[x]"
+        } else {
+            "[ ]"
+        };
+        let label = format!(
+            "{mark}  {}  -  {}  -  {} {}",
+            puzzle.name,
+            puzzle.goal.label(),
+            puzzle.piece_limit,
+            if puzzle.piece_limit == 1 {
+                "piece"
+            } else {
+                "pieces"
+            }
+        );
+        let button = commands
+            .spawn(menu_button_sized(
+                index,
+                label,
+                assets.font.clone(),
+                ROW_WIDTH,
+            ))
+            .id();
+        commands.entity(root).add_child(button);
+    }
+}
+
+fn activate(
+    keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
+    list: Single<&FocusList, With<PuzzlesRoot>>,
+    clicks: Query<(&Focusable, &Interaction), Changed<Interaction>>,
+    catalog: Res<PuzzleCatalog>,
+    mut session: ResMut<crate::session::SessionConfig>,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    let nav = read_nav_action(&keys, &pad, *list)
+        .or_else(|| clicked_focusable(&clicks).map(NavAction::Select));
+    match nav {
+        Some(NavAction::Select(index)) if catalog.get(index).is_some() => {
+            session.seats[0] = Participant::Human;
+            session.mode = SessionMode::Puzzle { index };
+            next.set(GameState::Session);
+        }
+        Some(NavAction::Back) => next.set(GameState::MainMenu),
+        _ => {}
+    }
+}
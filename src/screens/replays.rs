@@ -0,0 +1,115 @@
+//! Replays screen: the personal bests, then the recent games, newest first.
+//!
+//! Each row is a stored [`GameReplay`](crate::replays::GameReplay) from the
+//! [`ReplayLibrary`]; selecting one seats it through
+//! [`SessionPlayback::begin`] and enters the session, where the replay
+//! viewer takes over (`session::replay`). Esc returns to the main menu — and
+//! the viewer's Esc comes back here.
+
+use bevy::prelude::*;
+
+use crate::GameState;
+use crate::assets::GameAssets;
+use crate::replays::{ReplayLibrary, ReplaySlot};
+use crate::session::replay::SessionPlayback;
+use crate::ui::focus::{
+    FocusList, Focusable, MenuPad, NavAction, clicked_focusable, focus_navigation, read_nav_action,
+};
+use crate::ui::widgets::{label_text, menu_button_sized, screen_root, title_text};
+
+/// Row width: a versus summary names both seats.
+const ROW_WIDTH: f32 = 800.0;
+
+pub struct ReplaysScreenPlugin;
+
+impl Plugin for ReplaysScreenPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<ReplaysRoot>()
+            .add_systems(OnEnter(GameState::Replays), setup)
+            .add_systems(
+                Update,
+                (focus_navigation::<ReplaysRoot>, activate)
+                    .chain()
+                    .run_if(in_state(GameState::Replays)),
+            );
+    }
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct ReplaysRoot;
+
+/// The library id a row plays.
+#[derive(Component, Clone, Copy)]
+struct ReplayRow(u64);
+
+fn setup(mut commands: Commands, assets: Res<GameAssets>, library: Res<ReplayLibrary>) {
+    commands.spawn((
+        crate::ui::widgets::menu_camera(),
+        DespawnOnExit(GameState::Replays),
+    ));
+    let listing = library.listing();
+    let hint = if listing.is_empty() {
+        "No games yet  -  finished games show up here  -  Esc to go back"
+    } else {
+        "Enter to watch  -  Esc to go back"
+    };
+    let root = commands
+        .spawn((
+            ReplaysRoot,
+            FocusList::new(listing.len()),
+            screen_root(),
+            DespawnOnExit(GameState::Replays),
+            children![
+                title_text("Replays", assets.font.clone()),
+                label_text(hint, assets.font_body.clone()),
+            ],
+        ))
+        .id();
+
+    for (index, (id, slot)) in listing.into_iter().enumerate() {
+        let Some(replay) = library.get(id) else {
+            continue;
+        };
+        let label = match slot {
+            ReplaySlot::PersonalBest(_) => format!("PB  {}", replay.summary),
+            ReplaySlot::Recent => replay.summary.clone(),
+        };
+        let button = commands
+            .spawn((
+                menu_button_sized(index, label, assets.font.clone(), ROW_WIDTH),
+                ReplayRow(id),
+            ))
+            .id();
+        commands.entity(root).add_child(button);
+    }
+}
+
+#[allow(clippy::too_many_arguments)] // a Bevy system's params are its dependency list
+fn activate(
+    keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
+    list: Single<&FocusList, With<ReplaysRoot>>,
+    rows: Query<(&Focusable, &ReplayRow)>,
+    clicks: Query<(&Focusable, &Interaction), Changed<Interaction>>,
+    library: Res<ReplayLibrary>,
+    mut session: ResMut<crate::session::SessionConfig>,
+    mut commands: Commands,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    let nav = read_nav_action(&keys, &pad, *list)
+        .or_else(|| clicked_focusable(&clicks).map(NavAction::Select));
+    match nav {
+        Some(NavAction::Select(index)) => {
+            let Some((_, row)) = rows.iter().find(|(focusable, _)| focusable.index == index) else {
+                return;
+            };
+            if let Some(replay) = library.get(row.0) {
+                commands.insert_resource(SessionPlayback::begin(replay.clone(), &mut session));
+                next.set(GameState::Session);
+            }
+        }
+        Some(NavAction::Back) => next.set(GameState::MainMenu),
+        None => {}
+    }
+}
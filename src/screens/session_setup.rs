@@ -1,22 +1,27 @@
 //! Session setup: who sits at each board.
 //!
 //! Seat rows plus Start, in the shared `FocusList` idiom; [`SetupKind`] picks
-//! the face. **Versus**: two rows (P1 offers "You" plus every registry model,
-//! P2 the models — one keyboard, so exactly one human seat; a bot P1 gives
-//! bot-vs-bot) and Start launches the match. **Watch AI**: one bot row, and
-//! Start continues to mode select (the bot then plays the chosen variant on
-//! one seat). A row cycles with Left/Right (Enter and click also cycle). The
-//! selection writes [`SessionConfig`], which the session reads once on spawn;
-//! the resource persists, so the screen remembers the last choice.
+//! the face. **Versus**: two seats, each offering "You" plus every registry
+//! model (a bot P1 gives bot-vs-bot; "You" on both seats is two players on
+//! one keyboard, P1 on Player 1's keys and P2 on Player 2's, with a warning
+//! line while the two sets share a key) and Start launches the match.
+//! **Watch AI**: one bot seat, and Start continues to mode select (the bot
+//! then plays the chosen variant on one seat). Under each seat row sits its skill row, cycling the bot's
+//! [`BotSkill`] — a preset (Novice through Master) or Adaptive, which steers
+//! itself toward the player between games (inert for a human seat). A row
+//! cycles with Left/Right (Enter and click also cycle). The selection writes
+//! [`SessionConfig`], which the session reads once on spawn; the resource
+//! persists, so the screen remembers the last choice.
 
 use bevy::prelude::*;
 
 use crate::GameState;
 use crate::ai::ModelRegistry;
 use crate::assets::GameAssets;
-use crate::session::{Participant, SessionConfig};
+use crate::session::{BotSkill, Participant, SessionConfig};
+use crate::settings::{GameSettings, Keymap};
 use crate::ui::focus::{
-    FocusList, Focusable, NavAction, clicked_focusable, focus_navigation, read_nav_action,
+    FocusList, Focusable, MenuPad, NavAction, clicked_focusable, focus_navigation, read_nav_action,
 };
 use crate::ui::widgets::{label_text, menu_button_sized, screen_root, title_text};
 
@@ -40,6 +45,7 @@ impl Plugin for VersusSetupPlugin {
                     focus_navigation::<VersusSetupRoot>,
                     cycle_participants,
                     refresh_row_labels,
+                    refresh_conflict_warning,
                     activate,
                 )
                     .chain()
@@ -51,33 +57,50 @@ impl Plugin for VersusSetupPlugin {
 #[derive(Component)]
 struct VersusSetupRoot;
 
-/// Row 0 or 1: configures `SessionConfig.seats[seat]`.
+/// Configures who sits at `SessionConfig.seats[seat]` (focus row `2 * seat`).
 #[derive(Component, Clone, Copy)]
 struct SeatRow {
     seat: usize,
 }
 
-/// Row 2: starts the match.
+/// Configures the skill of the bot at `seats[seat]` (focus row `2 * seat + 1`).
+#[derive(Component, Clone, Copy)]
+struct SkillRow {
+    seat: usize,
+}
+
+/// The last row: starts the match.
 #[derive(Component)]
 struct StartRow;
 
-/// The participants a seat row cycles through, in display order. Watch-AI
-/// rows are bot-only (the whole point is watching one).
-fn options_for(kind: SetupKind, seat: usize, registry: &ModelRegistry) -> Vec<Participant> {
+/// The key-conflict line, shown while two humans' keys overlap.
+#[derive(Component)]
+struct ConflictWarning;
+
+/// The drivers a seat row cycles through, in display order: `None` is the
+/// keyboard, `Some(model)` a registry bot. Watch-AI rows are bot-only (the
+/// whole point is watching one).
+fn options_for(kind: SetupKind, registry: &ModelRegistry) -> Vec<Option<usize>> {
     let mut options = Vec::new();
-    if kind == SetupKind::Versus && seat == 0 {
-        options.push(Participant::Human);
-    }
-    for model in 0..registry.len() {
-        options.push(Participant::Bot { model });
+    if kind == SetupKind::Versus {
+        options.push(None);
     }
+    options.extend((0..registry.len()).map(Some));
     options
 }
 
 fn participant_label(participant: Participant, registry: &ModelRegistry) -> String {
     match participant {
         Participant::Human => "You".to_string(),
-        Participant::Bot { model } => registry.label(model).to_string(),
+        Participant::Remote => "Rival".to_string(),
+        Participant::Bot { model, .. } => registry.label(model).to_string(),
+    }
+}
+
+fn skill_label(participant: Participant) -> String {
+    match participant {
+        Participant::Human | Participant::Remote => "-".to_string(),
+        Participant::Bot { skill, .. } => format!("< {} >", skill.label()),
     }
 }
 
@@ -99,14 +122,22 @@ fn setup(
     // A Watch-AI visit must find a bot on seat 0 even if Play seated a human
     // there earlier; snap to the registry's first entry.
     if *kind == SetupKind::WatchAi && config.seats[0] == Participant::Human {
-        config.seats[0] = Participant::Bot { model: 0 };
+        config.seats[0] = Participant::Bot {
+            model: 0,
+            skill: BotSkill::default(),
+        };
+    }
+    // An online match leaves its remote seat in the config; local setup has
+    // no wire to drive it, so start over from the default pairing.
+    if config.seats.contains(&Participant::Remote) {
+        config.seats = SessionConfig::default().seats;
     }
     let _ = &registry; // (options are derived per-row below)
 
     let root = commands
         .spawn((
             VersusSetupRoot,
-            FocusList::new(seat_rows + 1),
+            FocusList::new(seat_rows * 2 + 1),
             screen_root(),
             DespawnOnExit(GameState::SessionSetup),
             children![title_text(title, assets.font.clone())],
@@ -117,51 +148,71 @@ fn setup(
     for seat in 0..seat_rows {
         let row = commands
             .spawn((
-                menu_button_sized(seat, "", assets.font.clone(), 460.0),
+                menu_button_sized(seat * 2, "", assets.font.clone(), 460.0),
                 SeatRow { seat },
             ))
             .id();
-        commands.entity(root).add_child(row);
+        let skill = commands
+            .spawn((
+                menu_button_sized(seat * 2 + 1, "", assets.font.clone(), 460.0),
+                SkillRow { seat },
+            ))
+            .id();
+        commands.entity(root).add_children(&[row, skill]);
     }
     let start = commands
         .spawn((
-            menu_button_sized(seat_rows, "Start", assets.font.clone(), 220.0),
+            menu_button_sized(seat_rows * 2, "Start", assets.font.clone(), 220.0),
             StartRow,
         ))
         .id();
     let hint = commands
         .spawn(label_text(
-            "Left/Right change seat  -  Enter start  -  Esc back",
+            "Left/Right change seat or skill  -  Enter start  -  Esc back",
             assets.font_body.clone(),
         ))
         .id();
-    commands.entity(root).add_children(&[start, hint]);
+    let warning = commands
+        .spawn(label_text("", assets.font_body.clone()))
+        .insert((ConflictWarning, TextColor(crate::ui::theme::ACCENT)))
+        .id();
+    commands.entity(root).add_children(&[start, hint, warning]);
 }
 
-/// Left/Right on a focused seat row cycles its participant.
+/// Left/Right on a focused seat row cycles its participant; on a skill row,
+/// the bot's skill.
 fn cycle_participants(
     keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
     list: Single<&FocusList, With<VersusSetupRoot>>,
-    rows: Query<(&Focusable, &SeatRow)>,
+    rows: Query<(&Focusable, Option<&SeatRow>, Option<&SkillRow>)>,
     registry: Res<ModelRegistry>,
     kind: Res<SetupKind>,
     mut config: ResMut<SessionConfig>,
 ) {
-    let step: isize = if keys.just_pressed(KeyCode::ArrowRight) || keys.just_pressed(KeyCode::KeyD)
+    let step: isize = if keys.just_pressed(KeyCode::ArrowRight)
+        || keys.just_pressed(KeyCode::KeyD)
+        || pad.right
     {
         1
-    } else if keys.just_pressed(KeyCode::ArrowLeft) || keys.just_pressed(KeyCode::KeyA) {
+    } else if keys.just_pressed(KeyCode::ArrowLeft) || keys.just_pressed(KeyCode::KeyA) || pad.left
+    {
         -1
     } else {
         return;
     };
-    let Some((_, row)) = rows.iter().find(|(f, _)| f.index == list.index) else {
+    let Some((_, seat_row, skill_row)) = rows.iter().find(|(f, ..)| f.index == list.index) else {
         return;
     };
-    cycle_seat(&mut config, &registry, *kind, row.seat, step);
+    if let Some(row) = seat_row {
+        cycle_seat(&mut config, &registry, *kind, row.seat, step);
+    } else if let Some(row) = skill_row {
+        cycle_skill(&mut config, row.seat, step);
+    }
 }
 
-/// Advance `seats[seat]` by `step` through its option ring.
+/// Advance `seats[seat]` by `step` through its option ring, keeping the
+/// seat's skill when it moves between bots.
 fn cycle_seat(
     config: &mut SessionConfig,
     registry: &ModelRegistry,
@@ -169,13 +220,27 @@ fn cycle_seat(
     seat: usize,
     step: isize,
 ) {
-    let options = options_for(kind, seat, registry);
-    let current = options
-        .iter()
-        .position(|p| *p == config.seats[seat])
-        .unwrap_or(0);
+    let options = options_for(kind, registry);
+    let (driver, skill) = match config.seats[seat] {
+        Participant::Human | Participant::Remote => (None, BotSkill::default()),
+        Participant::Bot { model, skill } => (Some(model), skill),
+    };
+    let current = options.iter().position(|o| *o == driver).unwrap_or(0);
     let next = (current as isize + step).rem_euclid(options.len() as isize) as usize;
-    config.seats[seat] = options[next];
+    config.seats[seat] = match options[next] {
+        None => Participant::Human,
+        Some(model) => Participant::Bot { model, skill },
+    };
+}
+
+/// Advance the skill of the bot at `seats[seat]` by `step` (a human seat has
+/// no skill to change).
+fn cycle_skill(config: &mut SessionConfig, seat: usize, step: isize) {
+    if let Participant::Bot { skill, .. } = &mut config.seats[seat] {
+        let all = BotSkill::ALL;
+        let current = all.iter().position(|s| s == skill).unwrap_or(0);
+        *skill = all[(current as isize + step).rem_euclid(all.len() as isize) as usize];
+    }
 }
 
 /// Keep the seat-row labels mirroring the config (initial fill included —
@@ -184,19 +249,28 @@ fn refresh_row_labels(
     config: Res<SessionConfig>,
     registry: Res<ModelRegistry>,
     kind: Res<SetupKind>,
-    rows: Query<(&SeatRow, &Children)>,
+    rows: Query<(Option<&SeatRow>, Option<&SkillRow>, &Children)>,
     mut texts: Query<&mut Text>,
 ) {
-    for (row, children) in &rows {
+    for (seat_row, skill_row, children) in &rows {
+        let seat = match (seat_row, skill_row) {
+            (Some(row), _) => row.seat,
+            (_, Some(row)) => row.seat,
+            _ => continue,
+        };
         let prefix = match *kind {
-            SetupKind::Versus => format!("P{}", row.seat + 1),
+            SetupKind::Versus => format!("P{}", seat + 1),
             SetupKind::WatchAi => "BOT".to_string(),
         };
-        let label = format!(
-            "{}  < {} >",
-            prefix,
-            participant_label(config.seats[row.seat], &registry)
-        );
+        let label = if seat_row.is_some() {
+            format!(
+                "{}  < {} >",
+                prefix,
+                participant_label(config.seats[seat], &registry)
+            )
+        } else {
+            format!("{prefix} SKILL  {}", skill_label(config.seats[seat]))
+        };
         for child in children.iter() {
             if let Ok(mut text) = texts.get_mut(child)
                 && text.0 != label
@@ -207,25 +281,61 @@ fn refresh_row_labels(
     }
 }
 
-/// Enter on Start begins the match; Enter/click on a seat row cycles it (so a
-/// mouse-only player can configure everything); Esc backs out.
+/// Warn while both seats are human and the two players' keys collide (a
+/// shared key would drive both boards at once).
+fn refresh_conflict_warning(
+    config: Res<SessionConfig>,
+    settings: Res<GameSettings>,
+    kind: Res<SetupKind>,
+    mut warnings: Query<&mut Text, With<ConflictWarning>>,
+) {
+    let both_human = config.seats.iter().all(|p| *p == Participant::Human);
+    let warning = if *kind == SetupKind::Versus && both_human {
+        crate::features::options::conflict_warning(&settings.keybind_conflicts(&Keymap::ALL))
+            .map(|line| format!("{line}  -  rebind in Options"))
+            .unwrap_or_default()
+    } else {
+        String::new()
+    };
+    for mut text in &mut warnings {
+        if text.0 != warning {
+            text.0 = warning.clone();
+        }
+    }
+}
+
+/// Every focusable row with whichever role it plays.
+type SetupRowQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Focusable,
+        Option<&'static SeatRow>,
+        Option<&'static SkillRow>,
+        Has<StartRow>,
+    ),
+>;
+
+/// Enter on Start begins the match; Enter/click on a seat or skill row cycles
+/// it (so a mouse-only player can configure everything); Esc backs out.
 #[allow(clippy::too_many_arguments)] // a Bevy system's params are its dependency list
 fn activate(
     keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
     list: Single<&FocusList, With<VersusSetupRoot>>,
-    rows: Query<(&Focusable, Option<&SeatRow>, Has<StartRow>)>,
+    rows: SetupRowQuery,
     clicks: Query<(&Focusable, &Interaction), Changed<Interaction>>,
     registry: Res<ModelRegistry>,
     kind: Res<SetupKind>,
     mut config: ResMut<SessionConfig>,
     mut next: ResMut<NextState<GameState>>,
 ) {
-    let nav =
-        read_nav_action(&keys, *list).or_else(|| clicked_focusable(&clicks).map(NavAction::Select));
+    let nav = read_nav_action(&keys, &pad, *list)
+        .or_else(|| clicked_focusable(&clicks).map(NavAction::Select));
     match nav {
         Some(NavAction::Back) => next.set(GameState::MainMenu),
         Some(NavAction::Select(index)) => {
-            for (focusable, seat_row, is_start) in &rows {
+            for (focusable, seat_row, skill_row, is_start) in &rows {
                 if focusable.index != index {
                     continue;
                 }
@@ -245,6 +355,8 @@ fn activate(
                     }
                 } else if let Some(row) = seat_row {
                     cycle_seat(&mut config, &registry, *kind, row.seat, 1);
+                } else if let Some(row) = skill_row {
+                    cycle_skill(&mut config, row.seat, 1);
                 }
             }
         }
@@ -0,0 +1,404 @@
+//! Adaptive difficulty: a bot seat that tunes its handicap to the player.
+//!
+//! A seat configured [`BotSkill::Adaptive`](super::BotSkill::Adaptive) plays on
+//! the continuous skill scale ([`SkillPreset::handicap_at`]: `0.0` Novice …
+//! `4.0` Master, fractional levels blending neighbouring presets). After every
+//! versus game against the local player, [`AdaptiveDifficulty::record`] reads
+//! the outcome and both seats' [`SeatStats`] and moves the level toward the
+//! configured target win rate for the player: a player winning more than the
+//! target faces a stronger bot next game, one losing more a weaker. The step
+//! scales with how decisive the game was (the attack differential) and with a
+//! gain that decays per game, so the level settles instead of oscillating.
+//!
+//! The adjustment is **between games**, never within one: imperfection lives
+//! in the policy, which is built once per match, and a mid-game shift would be
+//! visible as the bot suddenly changing character. Every step is bounded
+//! ([`AdaptiveConfig::max_step`], the scale's ends), appended to
+//! [`AdaptiveDifficulty::log`] and logged, and is a pure function of the
+//! reports fed in — a scripted opponent drives it deterministically in tests.
+//!
+//! The settled level persists per player in [`Storage`] under
+//! [`keys::ADAPTIVE`], so the next session starts where this one converged.
+//! There is one local player today ([`LOCAL_PLAYER`]); the map is keyed so a
+//! second profile is another row, not a format change.
+//!
+//! [`Storage`]: crate::storage::Storage
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+
+use crate::ai::{Handicap, SkillPreset};
+use crate::storage::{StorageResource, keys};
+
+use super::{BotSkill, HumanSeat, Participant, Seat, SeatStats, SessionConfig, SessionMode};
+use super::{SessionOutcome, SessionPhase};
+
+/// The player an adaptive seat calibrates against: the local keyboard.
+pub const LOCAL_PLAYER: &str = "local";
+
+pub struct SessionAdaptivePlugin;
+
+impl Plugin for SessionAdaptivePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AdaptiveDifficulty>()
+            .add_systems(Startup, load_levels)
+            .add_systems(OnEnter(SessionPhase::Over), adapt_after_match);
+    }
+}
+
+/// The tuning of the adaptation loop.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct AdaptiveConfig {
+    /// The share of games the player should win, in `0.0..=1.0`.
+    pub target_win_rate: f32,
+    /// Levels moved per unit of result error on a player's first game.
+    pub gain: f32,
+    /// Per-game decay of the gain, so an early blowout moves far and a settled
+    /// player's occasional upset barely registers.
+    pub gain_decay: f32,
+    /// The floor the decayed gain never drops under (the level keeps tracking
+    /// a player who improves).
+    pub min_gain: f32,
+    /// The largest single-game move, in levels.
+    pub max_step: f32,
+    /// Where a player with no stored level starts (Skilled).
+    pub start_level: f32,
+}
+
+impl Default for AdaptiveConfig {
+    fn default() -> Self {
+        Self {
+            target_win_rate: 0.5,
+            gain: 1.2,
+            gain_decay: 0.85,
+            min_gain: 0.2,
+            max_step: 0.75,
+            start_level: 2.0,
+        }
+    }
+}
+
+/// A player's place on the skill scale.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PlayerLevel {
+    pub level: f32,
+    /// Games recorded so far (drives the gain decay).
+    pub games: u32,
+}
+
+/// How a game went, from the player's side.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchResult {
+    PlayerWon,
+    BotWon,
+    Draw,
+}
+
+impl MatchResult {
+    /// The player's score for the game: 1 for a win, ½ for a draw.
+    fn score(self) -> f32 {
+        match self {
+            MatchResult::PlayerWon => 1.0,
+            MatchResult::BotWon => 0.0,
+            MatchResult::Draw => 0.5,
+        }
+    }
+}
+
+/// One finished game against an adaptive seat.
+#[derive(Clone, Copy, Debug)]
+pub struct MatchReport {
+    pub result: MatchResult,
+    pub player: SeatStats,
+    pub bot: SeatStats,
+}
+
+impl MatchReport {
+    /// How one-sided the game was, in `0.0..=1.0`: the attack differential
+    /// over the attack both seats sent. A trade of blows reads near 0, a
+    /// shutout 1.
+    fn margin(&self) -> f32 {
+        let (a, b) = (self.player.attack_sent, self.bot.attack_sent);
+        a.abs_diff(b) as f32 / (a + b).max(1) as f32
+    }
+}
+
+/// One logged adaptation step.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Adjustment {
+    pub player: String,
+    /// The player's game count after this one.
+    pub game: u32,
+    pub result: MatchResult,
+    pub margin: f32,
+    pub before: f32,
+    pub after: f32,
+}
+
+/// Every player's adaptive level, the loop's tuning, and the step log.
+#[derive(Resource, Debug, Default)]
+pub struct AdaptiveDifficulty {
+    pub config: AdaptiveConfig,
+    players: BTreeMap<String, PlayerLevel>,
+    /// Every adjustment made this run, oldest first.
+    pub log: Vec<Adjustment>,
+}
+
+impl AdaptiveDifficulty {
+    /// `player`'s current level (the start level if they have none yet).
+    pub fn level(&self, player: &str) -> f32 {
+        self.players
+            .get(player)
+            .map_or(self.config.start_level, |p| p.level)
+    }
+
+    /// The handicap an adaptive bot plays `player` with.
+    pub fn handicap_for(&self, player: &str) -> Handicap {
+        SkillPreset::handicap_at(self.level(player))
+    }
+
+    /// Fold a finished game into `player`'s level and return (and log) the
+    /// step taken.
+    pub fn record(&mut self, player: &str, report: &MatchReport) -> Adjustment {
+        let config = self.config;
+        let entry = self
+            .players
+            .entry(player.to_string())
+            .or_insert(PlayerLevel {
+                level: config.start_level,
+                games: 0,
+            });
+        let gain = (config.gain * config.gain_decay.powi(entry.games as i32)).max(config.min_gain);
+        let error = report.result.score() - config.target_win_rate;
+        let margin = report.margin();
+        // A decisive game moves the level up to 1.5× as far as an even one;
+        // a coin-flip finish still moves it half as far.
+        let step = (gain * error * (0.5 + margin)).clamp(-config.max_step, config.max_step);
+        let before = entry.level;
+        entry.level = (before + step).clamp(0.0, SkillPreset::MAX_LEVEL);
+        entry.games += 1;
+        let adjustment = Adjustment {
+            player: player.to_string(),
+            game: entry.games,
+            result: report.result,
+            margin,
+            before,
+            after: entry.level,
+        };
+        info!(
+            "adaptive difficulty: {player} game {} {:?} (margin {:.2}) level {:.2} -> {:.2}",
+            adjustment.game, adjustment.result, margin, before, adjustment.after
+        );
+        self.log.push(adjustment.clone());
+        adjustment
+    }
+}
+
+/// Restore every player's level on startup, if a blob is stored.
+fn load_levels(storage: Option<Res<StorageResource>>, mut difficulty: ResMut<AdaptiveDifficulty>) {
+    let Some(blob) = storage.and_then(|s| s.0.load(keys::ADAPTIVE)) else {
+        return;
+    };
+    difficulty.players = codec::deserialize(&blob);
+}
+
+/// A versus game between the local player and an adaptive bot just ended:
+/// adjust the level and persist it. Any other pairing (bot vs bot, a fixed
+/// preset, solo) leaves the scale alone.
+fn adapt_after_match(
+    config: Res<SessionConfig>,
+    outcome: Option<Res<SessionOutcome>>,
+    seats: Query<(&Seat, &SeatStats, Has<HumanSeat>)>,
+    storage: Option<Res<StorageResource>>,
+    mut difficulty: ResMut<AdaptiveDifficulty>,
+) {
+    if config.mode != SessionMode::Versus {
+        return;
+    }
+    let Some(SessionOutcome::Versus { winner }) = outcome.as_deref().copied() else {
+        return;
+    };
+    let mut player = None;
+    let mut bot = None;
+    for (seat, stats, human) in &seats {
+        let adaptive = matches!(
+            config.seats.get(seat.index),
+            Some(Participant::Bot {
+                skill: BotSkill::Adaptive,
+                ..
+            })
+        );
+        if human {
+            player = Some((seat.index, *stats));
+        } else if adaptive {
+            bot = Some(*stats);
+        }
+    }
+    let (Some((player_seat, player)), Some(bot)) = (player, bot) else {
+        return;
+    };
+    let result = match winner {
+        None => MatchResult::Draw,
+        Some(seat) if seat == player_seat => MatchResult::PlayerWon,
+        Some(_) => MatchResult::BotWon,
+    };
+    difficulty.record(
+        LOCAL_PLAYER,
+        &MatchReport {
+            result,
+            player,
+            bot,
+        },
+    );
+    if let Some(storage) = storage {
+        storage
+            .0
+            .save(keys::ADAPTIVE, &codec::serialize(&difficulty.players));
+    }
+}
+
+/// The stored form: one `player level games` line per player. Unparseable
+/// lines are dropped, so a corrupt blob degrades to "start over".
+mod codec {
+    use std::collections::BTreeMap;
+
+    use super::PlayerLevel;
+
+    pub fn serialize(players: &BTreeMap<String, PlayerLevel>) -> String {
+        use std::fmt::Write as _;
+
+        let mut out = String::new();
+        for (name, player) in players {
+            // Writing into a `String` is infallible.
+            let _ = writeln!(out, "{name} {} {}", player.level, player.games);
+        }
+        out
+    }
+
+    pub fn deserialize(blob: &str) -> BTreeMap<String, PlayerLevel> {
+        blob.lines().filter_map(parse_line).collect()
+    }
+
+    fn parse_line(line: &str) -> Option<(String, PlayerLevel)> {
+        let mut fields = line.split_whitespace();
+        let name = fields.next()?.to_string();
+        let level: f32 = fields.next()?.parse().ok()?;
+        let games = fields.next()?.parse().ok()?;
+        if !level.is_finite() || fields.next().is_some() {
+            return None;
+        }
+        Some((name, PlayerLevel { level, games }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A scripted opponent of fixed strength `skill`: beats any bot below it
+    /// by a clear attack margin, loses to any at or above it.
+    fn scripted_game(skill: f32, level: f32) -> MatchReport {
+        let (strong, weak) = (
+            SeatStats {
+                attack_sent: 24,
+                garbage_taken: 6,
+            },
+            SeatStats {
+                attack_sent: 6,
+                garbage_taken: 24,
+            },
+        );
+        if level < skill {
+            MatchReport {
+                result: MatchResult::PlayerWon,
+                player: strong,
+                bot: weak,
+            }
+        } else {
+            MatchReport {
+                result: MatchResult::BotWon,
+                player: weak,
+                bot: strong,
+            }
+        }
+    }
+
+    fn play(difficulty: &mut AdaptiveDifficulty, skill: f32, games: usize) -> Vec<MatchResult> {
+        (0..games)
+            .map(|_| {
+                let report = scripted_game(skill, difficulty.level(LOCAL_PLAYER));
+                difficulty.record(LOCAL_PLAYER, &report);
+                report.result
+            })
+            .collect()
+    }
+
+    #[test]
+    fn the_level_settles_on_a_scripted_players_strength() {
+        for skill in [0.7, 2.3, 3.6] {
+            let mut difficulty = AdaptiveDifficulty::default();
+            play(&mut difficulty, skill, 40);
+            let level = difficulty.level(LOCAL_PLAYER);
+            assert!(
+                (level - skill).abs() < 0.3,
+                "player at {skill}: settled at {level}"
+            );
+        }
+    }
+
+    #[test]
+    fn a_higher_target_lets_the_player_win_more() {
+        let wins = |target: f32| {
+            let mut difficulty = AdaptiveDifficulty {
+                config: AdaptiveConfig {
+                    target_win_rate: target,
+                    ..AdaptiveConfig::default()
+                },
+                ..AdaptiveDifficulty::default()
+            };
+            let results = play(&mut difficulty, 2.0, 80);
+            results[40..]
+                .iter()
+                .filter(|r| **r == MatchResult::PlayerWon)
+                .count()
+        };
+        let (even, generous) = (wins(0.5), wins(0.8));
+        assert!((16..=24).contains(&even), "even target won {even}/40");
+        assert!(generous > even + 6, "0.8 target won {generous}/40");
+    }
+
+    #[test]
+    fn every_step_is_bounded_and_logged() {
+        let mut difficulty = AdaptiveDifficulty::default();
+        // An unbeatable player: the bot climbs, never by more than a step,
+        // and stops at Master.
+        play(&mut difficulty, f32::INFINITY, 12);
+        assert_eq!(difficulty.log.len(), 12);
+        for (i, step) in difficulty.log.iter().enumerate() {
+            assert_eq!(step.game, i as u32 + 1);
+            assert!(step.after >= step.before);
+            assert!(step.after - step.before <= difficulty.config.max_step + 1e-6);
+            assert!(step.after <= SkillPreset::MAX_LEVEL);
+        }
+        assert_eq!(difficulty.level(LOCAL_PLAYER), SkillPreset::MAX_LEVEL);
+        // A draw at an even target moves nothing.
+        let draw = MatchReport {
+            result: MatchResult::Draw,
+            player: SeatStats::default(),
+            bot: SeatStats::default(),
+        };
+        let step = difficulty.record(LOCAL_PLAYER, &draw);
+        assert_eq!(step.before, step.after);
+    }
+
+    #[test]
+    fn levels_round_trip_through_the_codec() {
+        let mut difficulty = AdaptiveDifficulty::default();
+        play(&mut difficulty, 1.4, 5);
+        let blob = codec::serialize(&difficulty.players);
+        let restored = codec::deserialize(&format!("{blob}garbage line\nbad nan 3\n"));
+        assert_eq!(restored, difficulty.players);
+        assert_eq!(restored[LOCAL_PLAYER].games, 5);
+    }
+}
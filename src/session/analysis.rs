@@ -0,0 +1,219 @@
+//! Post-game blunder analysis for the human seats.
+//!
+//! Every seat carries a [`SeatRecording`] — the seed, engine rules, every
+//! stepped frame, and the attack routed into it — written by the session
+//! step (the whole match is also kept as a replay, see `session::replay`).
+//! When the match ends a human seat's recording becomes a [`SeatAnalysis`]:
+//! the core's [`Analyst`] replays it and prices each lock against a
+//! full-strength beam, a few locks per render frame so the banner never
+//! hitches. The result banner hosts one [`BlunderGraph`] per human seat; it
+//! reads "analyzing" until the pass finishes, then draws one bar per lock
+//! (height = the lock's cost, colour = its verdict) with a one-line summary.
+//!
+//! Bots are never analysed: the review is for the player, and a bot's
+//! placements are the search's own answers anyway.
+
+use bevy::prelude::*;
+
+use crate::ai::{AnalysisConfig, Analyst, Cc2Evaluator, GameAnalysis, RecordedGame, Verdict};
+use crate::assets::GameAssets;
+use crate::ui::widgets::{label_text, theme};
+
+use super::{HumanSeat, Seat, SessionPhase};
+
+/// Locks reviewed per render frame while the banner is up. One full-strength
+/// search is a few milliseconds in release, so this keeps the frame smooth
+/// and finishes a typical game within a couple of seconds.
+const LOCKS_PER_FRAME: usize = 4;
+
+/// Bars are scaled so twice the blunder threshold reaches the top; anything
+/// worse is clipped (the colour already says "blunder").
+const GRAPH_CEILING_BLUNDERS: f32 = 2.0;
+
+/// Smallest drawn bar, as a percentage of the graph height, so a best move
+/// still marks its slot on the timeline.
+const MIN_BAR_PERCENT: f32 = 4.0;
+
+const GRAPH_WIDTH: f32 = 440.0;
+const GRAPH_HEIGHT: f32 = 56.0;
+
+pub struct SessionAnalysisPlugin;
+
+impl Plugin for SessionAnalysisPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(SessionPhase::Over), begin_analysis)
+            .add_systems(
+                Update,
+                (advance_analysis, draw_blunder_graphs)
+                    .chain()
+                    .run_if(in_state(SessionPhase::Over)),
+            );
+    }
+}
+
+/// A seat's game as it is played, in the form the analysis replays.
+#[derive(Component)]
+pub struct SeatRecording(pub RecordedGame);
+
+/// The post-game review in progress (or finished) for a human seat.
+#[derive(Component)]
+pub struct SeatAnalysis(pub Analyst);
+
+/// The banner's graph panel for `seat`; filled in once its analysis is done.
+#[derive(Component)]
+pub(crate) struct BlunderGraph {
+    seat: usize,
+    drawn: bool,
+}
+
+/// The caption under a [`BlunderGraph`] (progress, then the summary).
+#[derive(Component)]
+struct BlunderCaption {
+    seat: usize,
+}
+
+/// Hand every human seat's recording to a fresh [`Analyst`].
+fn begin_analysis(mut commands: Commands, seats: Query<(Entity, &SeatRecording), With<HumanSeat>>) {
+    for (entity, recording) in &seats {
+        let analyst = Analyst::new(
+            recording.0.clone(),
+            Box::new(Cc2Evaluator::default()),
+            AnalysisConfig::default(),
+        );
+        commands.entity(entity).insert(SeatAnalysis(analyst));
+    }
+}
+
+fn advance_analysis(mut seats: Query<&mut SeatAnalysis>) {
+    for mut analysis in &mut seats {
+        if !analysis.0.is_finished() {
+            analysis.0.advance(LOCKS_PER_FRAME);
+        }
+    }
+}
+
+/// Spawn the (empty) graph panel for `seat` under the banner; returns the
+/// panel so the banner can order it among its children.
+pub(crate) fn spawn_blunder_graph(
+    commands: &mut Commands,
+    assets: &GameAssets,
+    seat: usize,
+    heading: String,
+) -> Entity {
+    let heading = commands
+        .spawn(label_text(heading, assets.font_body.clone()))
+        .id();
+    let bars = commands
+        .spawn((
+            BlunderGraph { seat, drawn: false },
+            Node {
+                width: px(GRAPH_WIDTH),
+                height: px(GRAPH_HEIGHT),
+                flex_direction: FlexDirection::Row,
+                align_items: AlignItems::FlexEnd,
+                column_gap: px(1),
+                padding: UiRect::all(px(2)),
+                ..default()
+            },
+            BackgroundColor(theme::GRID),
+            Outline::new(px(1), Val::ZERO, theme::FRAME),
+        ))
+        .id();
+    let caption = commands
+        .spawn((
+            BlunderCaption { seat },
+            label_text("ANALYZING…", assets.font_body.clone()),
+        ))
+        .id();
+    commands
+        .spawn(Node {
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: px(4),
+            margin: UiRect::vertical(px(6)),
+            ..default()
+        })
+        .add_children(&[heading, bars, caption])
+        .id()
+}
+
+/// Progress captions while the pass runs; the bars and the summary once it
+/// finishes (drawn exactly once per graph).
+fn draw_blunder_graphs(
+    mut commands: Commands,
+    seats: Query<(&Seat, &SeatAnalysis)>,
+    mut graphs: Query<(Entity, &mut BlunderGraph)>,
+    mut captions: Query<(&BlunderCaption, &mut Text)>,
+) {
+    for (seat, analysis) in &seats {
+        let analyst = &analysis.0;
+        for (caption, mut text) in &mut captions {
+            if caption.seat != seat.index {
+                continue;
+            }
+            let line = if analyst.is_finished() {
+                summary_line(analyst.analysis())
+            } else {
+                format!("ANALYZING… {:.0}%", analyst.progress() * 100.0)
+            };
+            if text.0 != line {
+                text.0 = line;
+            }
+        }
+        if !analyst.is_finished() {
+            continue;
+        }
+        for (entity, mut graph) in &mut graphs {
+            if graph.seat != seat.index || graph.drawn {
+                continue;
+            }
+            graph.drawn = true;
+            let config = AnalysisConfig::default();
+            for review in &analyst.analysis().reviews {
+                let ceiling = config.blunder_gap as f32 * GRAPH_CEILING_BLUNDERS;
+                let height = review.gap().map_or(MIN_BAR_PERCENT, |gap| {
+                    (gap as f32 / ceiling * 100.0).clamp(MIN_BAR_PERCENT, 100.0)
+                });
+                let bar = commands
+                    .spawn((
+                        Node {
+                            flex_grow: 1.0,
+                            height: percent(height),
+                            ..default()
+                        },
+                        BackgroundColor(verdict_color(review.verdict)),
+                    ))
+                    .id();
+                commands.entity(entity).add_child(bar);
+            }
+        }
+    }
+}
+
+/// The graph's one-line verdict: counts plus the costliest lock.
+fn summary_line(analysis: &GameAnalysis) -> String {
+    let blunders = analysis.blunders().count();
+    let inaccuracies = analysis.inaccuracies().count();
+    if analysis.reviews.is_empty() {
+        return "NO PLACEMENTS TO REVIEW".to_string();
+    }
+    let worst = match analysis.worst() {
+        Some(review) if review.verdict == Verdict::Blunder => format!(
+            "   ·   WORST: PIECE #{} ({:?}) AT {:.0}s",
+            review.piece_index + 1,
+            review.piece_type,
+            review.elapsed_seconds
+        ),
+        _ => String::new(),
+    };
+    format!("BLUNDERS {blunders}   ·   INACCURACIES {inaccuracies}{worst}")
+}
+
+fn verdict_color(verdict: Verdict) -> Color {
+    match verdict {
+        Verdict::Best | Verdict::Good => theme::GARBAGE,
+        Verdict::Inaccuracy => theme::ACCENT,
+        Verdict::Blunder => theme::ATTACK,
+        Verdict::Unrated => theme::FRAME,
+    }
+}
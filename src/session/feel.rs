@@ -20,7 +20,7 @@ use crate::engine::EngineEvent;
 use crate::features::screen_shake::{ScreenShake, trauma_for_clear};
 use crate::level::common::{AudioCue, GameplayCamera};
 
-use super::render::SessionLayout;
+use super::render::{SceneOrientation, SessionLayout};
 use super::{HumanSeat, Seat, SeatEvents, SessionPhase};
 
 /// How long a "+n" attack pop lives, and how far it drifts up.
@@ -167,7 +167,8 @@ fn callout_label(action: &crate::engine::EngineScoreAction) -> Option<String> {
 /// LINE down the gutter ("T-SPIN" over "DOUBLE") so the column never grows
 /// wider than one display-size word — that worst-case width is what
 /// `SessionLayout::scene_min` budgets per side. A back-to-back clear gets a
-/// smaller "BACK-TO-BACK" prefix line above the stack, all amber.
+/// smaller "BACK-TO-BACK" prefix line above the stack, all amber, and a clear
+/// that paid the all-clear bonus (Blitz) an "ALL CLEAR" line above that.
 fn spawn_seat_callouts(
     mut commands: Commands,
     assets: Res<crate::assets::GameAssets>,
@@ -176,6 +177,10 @@ fn spawn_seat_callouts(
     use crate::engine::EngineEvent;
     use crate::ui::widgets::theme;
     for (seat, events) in &seats {
+        let all_clear = events
+            .0
+            .iter()
+            .any(|e| matches!(e, EngineEvent::PerfectClearBonus { .. }));
         for event in &events.0 {
             let EngineEvent::ScoreAwarded {
                 action,
@@ -220,6 +225,13 @@ fn spawn_seat_callouts(
                     lane_y + PREFIX_RISE * CALLOUT_LINE_STEP,
                 );
             }
+            if all_clear {
+                spawn_line(
+                    "ALL CLEAR",
+                    theme::BUTTON_FONT_SIZE,
+                    lane_y + 2.0 * PREFIX_RISE * CALLOUT_LINE_STEP,
+                );
+            }
         }
     }
 }
@@ -334,12 +346,13 @@ fn apply_session_shake(
     time: Res<Time>,
     mut shake: ResMut<ScreenShake>,
     config: Res<super::SessionConfig>,
+    orientation: Res<SceneOrientation>,
     mut cameras: Query<&mut Transform, With<GameplayCamera>>,
 ) {
     let (translation, rotation) = shake.pose_and_decay(
         time.elapsed_secs(),
         time.delta_secs(),
-        SessionLayout::scene_center(config.mode.seat_count()),
+        SessionLayout::scene_center(config.mode.seat_count(), *orientation),
     );
     for mut transform in &mut cameras {
         transform.translation = translation;
@@ -380,7 +393,7 @@ mod gutter_tests {
     /// How much horizontal room `scene_min` guarantees outboard of a board's
     /// outer edge, past the callout anchor.
     fn gutter_room(seat_count: usize) -> f32 {
-        let (min_width, _) = SessionLayout::scene_min(seat_count);
+        let (min_width, _) = SessionLayout::scene_min(seat_count, SceneOrientation::Landscape);
         let board_span = SessionLayout::board_origin(seat_count.saturating_sub(1)).x
             + SessionLayout::BOARD_W as f32 * SessionLayout::BLOCK;
         (min_width - board_span) / 2.0 - 0.75 * SessionLayout::BLOCK
@@ -0,0 +1,425 @@
+//! Ghost racing: a Sprint run races the replay of its personal best.
+//!
+//! When a human solo run of a line-target variant starts (and again on every
+//! retry), the best high-score entry's replay — attached when that run took
+//! first place — is loaded into a [`SprintGhost`]. The ghost re-steps it one
+//! frame per live slice on an engine of its own that nobody sees, so "the PB
+//! at this moment" is always the same number of slices in as the live run.
+//!
+//! The race reads beside the live board, under the next queue: two progress
+//! bars (the PB's lines and yours, toward the target), the line delta, and a
+//! split every [`SPLIT_LINES`] lines — the PB's time to that many lines, then
+//! yours against it once you get there. Splits count slices, not the render
+//! clock, so a split against yourself on the same inputs is exactly zero.
+
+use bevy::prelude::*;
+use bevy::sprite::Anchor;
+
+use crate::assets::GameAssets;
+use crate::engine::EngineEvent;
+use crate::level::engine_bridge::SIM_DT_SECONDS;
+use crate::replays::{GameReplay, ReplayLibrary};
+use crate::ui::widgets::theme;
+
+use super::online::OnlineMatch;
+use super::render::SessionLayout;
+use super::replay::{Playhead, SessionPlayback};
+use super::{
+    Participant, Seat, SeatSnapshot, SessionConfig, SessionMode, SessionOutcome, SessionPhase,
+};
+
+/// Lines between splits.
+pub const SPLIT_LINES: usize = 10;
+
+/// Length of a progress bar, in cells.
+const BAR_CELLS: f32 = 4.0;
+
+pub struct SessionGhostPlugin;
+
+impl Plugin for SessionGhostPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(SessionPhase::Countdown), arm_ghost)
+            .add_systems(
+                FixedUpdate,
+                step_ghost
+                    .after(super::session_step)
+                    .run_if(resource_exists::<SprintGhost>.and(in_state(SessionPhase::Running))),
+            )
+            .add_systems(
+                Update,
+                update_ghost_hud.run_if(
+                    resource_exists::<SprintGhost>.and(in_state(crate::GameState::Session)),
+                ),
+            )
+            .add_systems(OnExit(crate::GameState::Session), disarm_ghost);
+    }
+}
+
+/// One split: how many slices the PB and the live run took to clear
+/// `lines` lines.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Split {
+    pub lines: usize,
+    /// `None` if the PB never got there (it cannot have topped out short
+    /// of the target and still ranked, but a replay is only data).
+    pub pb_slices: Option<usize>,
+    pub live_slices: Option<usize>,
+}
+
+/// The personal best, replaying in lockstep with the live run.
+#[derive(Resource)]
+pub struct SprintGhost {
+    playhead: Playhead,
+    target: usize,
+    /// Slices the live run has played.
+    slices: usize,
+    ghost_lines: usize,
+    splits: Vec<Split>,
+}
+
+impl SprintGhost {
+    /// Race seat 0 of `replay` to `target` lines. The PB's splits are read
+    /// off one fast pass over the whole replay up front.
+    pub fn new(replay: GameReplay, target: usize) -> Self {
+        let mut splits: Vec<Split> = (1..=target / SPLIT_LINES)
+            .map(|k| Split {
+                lines: k * SPLIT_LINES,
+                pb_slices: None,
+                live_slices: None,
+            })
+            .collect();
+        let mut scout = Playhead::new(replay.clone());
+        let mut lines = 0;
+        while let Some(events) = scout.step() {
+            lines += cleared(&events);
+            for split in &mut splits {
+                if split.pb_slices.is_none() && lines >= split.lines {
+                    split.pb_slices = Some(scout.cursor());
+                }
+            }
+        }
+        Self {
+            playhead: Playhead::new(replay),
+            target,
+            slices: 0,
+            ghost_lines: 0,
+            splits,
+        }
+    }
+
+    /// One lockstep slice: the ghost plays its next frame, and the live run
+    /// stands at `live_lines` after its own.
+    pub fn advance(&mut self, live_lines: usize) {
+        self.slices += 1;
+        if let Some(events) = self.playhead.step() {
+            self.ghost_lines += cleared(&events);
+        }
+        for split in &mut self.splits {
+            if split.live_slices.is_none() && live_lines >= split.lines {
+                split.live_slices = Some(self.slices);
+            }
+        }
+    }
+
+    pub fn target(&self) -> usize {
+        self.target
+    }
+
+    /// Slices the live run has played since the race began.
+    #[cfg(test)]
+    pub fn slices(&self) -> usize {
+        self.slices
+    }
+
+    /// The PB's lines at this point of its run.
+    pub fn ghost_lines(&self) -> usize {
+        self.ghost_lines
+    }
+
+    /// Live lines minus the PB's, both capped at the target.
+    pub fn line_delta(&self, live_lines: usize) -> isize {
+        live_lines.min(self.target) as isize - self.ghost_lines.min(self.target) as isize
+    }
+
+    pub fn splits(&self) -> &[Split] {
+        &self.splits
+    }
+}
+
+/// Lines seat 0 cleared in one slice's events.
+fn cleared(events: &[Vec<EngineEvent>]) -> usize {
+    events.first().map_or(0, |events| {
+        events
+            .iter()
+            .map(|event| match event {
+                EngineEvent::Locked { lines_cleared, .. } => *lines_cleared,
+                _ => 0,
+            })
+            .sum()
+    })
+}
+
+/// Everything the race HUD spawned (despawned on a retry's re-arm).
+#[derive(Component)]
+struct GhostHud;
+
+#[derive(Component, Clone, Copy)]
+enum GhostBar {
+    Pb,
+    Live,
+}
+
+#[derive(Component)]
+struct GhostText;
+
+/// At the start of every run (the countdown, first game and retries alike):
+/// load the PB's replay if this run races one, and lay out its HUD.
+#[allow(clippy::too_many_arguments)] // a Bevy system's params are its dependency list
+fn arm_ghost(
+    mut commands: Commands,
+    config: Res<SessionConfig>,
+    scores: Res<crate::high_scores::HighScores>,
+    library: Res<ReplayLibrary>,
+    playback: Option<Res<SessionPlayback>>,
+    online: Option<Res<OnlineMatch>>,
+    huds: Query<Entity, With<GhostHud>>,
+    assets: Res<GameAssets>,
+) {
+    for hud in &huds {
+        commands.entity(hud).despawn();
+    }
+    commands.remove_resource::<SprintGhost>();
+
+    let SessionMode::Solo { variant } = config.mode else {
+        return;
+    };
+    let Some(target) = variant.def().line_target else {
+        return;
+    };
+    if config.seats[0] != Participant::Human || playback.is_some() || online.is_some() {
+        return;
+    }
+    let Some(replay) = scores
+        .best_replay(variant)
+        .and_then(|id| library.get(id))
+        .filter(|replay| !replay.seats.is_empty())
+    else {
+        return;
+    };
+    info!("racing the {} PB", variant.display_name());
+    commands.insert_resource(SprintGhost::new(replay.clone(), target));
+
+    let block = SessionLayout::BLOCK;
+    let origin = SessionLayout::board_origin(0);
+    let x = (SessionLayout::BOARD_W as f32 + 0.5) * block;
+    let bar_height = 0.2 * block;
+    let micro = |text: &str| {
+        (
+            Text2d::new(text),
+            TextFont {
+                font: assets.font_body.clone(),
+                font_size: theme::MICRO_FONT_SIZE,
+                ..default()
+            },
+            TextColor(theme::TEXT_DIM),
+        )
+    };
+    commands.spawn((
+        GhostHud,
+        micro("PB RACE"),
+        Anchor::BOTTOM_LEFT,
+        Transform::from_translation(origin + Vec3::new(x, 4.3 * block, 1.0)),
+        DespawnOnExit(crate::GameState::Session),
+    ));
+    for (bar, row, color) in [
+        (GhostBar::Pb, 3.8, theme::TEXT_DIM),
+        (GhostBar::Live, 3.3, theme::ACCENT),
+    ] {
+        let y = row * block;
+        commands.spawn((
+            GhostHud,
+            Sprite::from_color(theme::GRID, Vec2::new(BAR_CELLS * block, bar_height)),
+            Anchor::BOTTOM_LEFT,
+            Transform::from_translation(origin + Vec3::new(x, y, 0.9)),
+            DespawnOnExit(crate::GameState::Session),
+        ));
+        commands.spawn((
+            GhostHud,
+            bar,
+            Sprite::from_color(color, Vec2::new(0.0, bar_height)),
+            Anchor::BOTTOM_LEFT,
+            Transform::from_translation(origin + Vec3::new(x, y, 1.0)),
+            DespawnOnExit(crate::GameState::Session),
+        ));
+    }
+    commands.spawn((
+        GhostHud,
+        GhostText,
+        micro(""),
+        Anchor::TOP_LEFT,
+        Transform::from_translation(origin + Vec3::new(x, 3.0 * block, 1.0)),
+        DespawnOnExit(crate::GameState::Session),
+    ));
+}
+
+/// Step the ghost with the slice the live seat just played. Once the run is
+/// decided the race is too.
+fn step_ghost(
+    mut ghost: ResMut<SprintGhost>,
+    outcome: Option<Res<SessionOutcome>>,
+    seats: Query<(&Seat, &SeatSnapshot)>,
+) {
+    if outcome.is_some() {
+        return;
+    }
+    let Some((_, live)) = seats.iter().find(|(seat, _)| seat.index == 0) else {
+        return;
+    };
+    ghost.advance(live.0.lines);
+}
+
+fn update_ghost_hud(
+    ghost: Res<SprintGhost>,
+    seats: Query<(&Seat, &SeatSnapshot)>,
+    mut bars: Query<(&GhostBar, &mut Sprite)>,
+    mut texts: Query<(&mut Text2d, &mut TextColor), With<GhostText>>,
+) {
+    let Some((_, live)) = seats.iter().find(|(seat, _)| seat.index == 0) else {
+        return;
+    };
+    let live_lines = live.0.lines;
+    let target = ghost.target().max(1);
+    for (bar, mut sprite) in &mut bars {
+        let lines = match bar {
+            GhostBar::Pb => ghost.ghost_lines(),
+            GhostBar::Live => live_lines,
+        };
+        let fraction = lines.min(target) as f32 / target as f32;
+        if let Some(size) = sprite.custom_size.as_mut() {
+            size.x = fraction * BAR_CELLS * SessionLayout::BLOCK;
+        }
+    }
+
+    let delta = ghost.line_delta(live_lines);
+    let mut readout = match delta {
+        0 => "EVEN".to_string(),
+        d if d > 0 => format!("+{d} AHEAD"),
+        d => format!("{} BEHIND", -d),
+    };
+    let seconds = |slices: usize| slices as f32 * SIM_DT_SECONDS;
+    for split in ghost.splits() {
+        let line = match (split.pb_slices, split.live_slices) {
+            (Some(pb), Some(live)) => {
+                let diff = seconds(live) - seconds(pb);
+                let sign = if diff > 0.0 { '+' } else { '-' };
+                format!(
+                    "{:>2} {} {sign}{:.2}",
+                    split.lines,
+                    crate::features::high_scores::format_time(seconds(live)),
+                    diff.abs()
+                )
+            }
+            (None, Some(live)) => format!(
+                "{:>2} {}",
+                split.lines,
+                crate::features::high_scores::format_time(seconds(live))
+            ),
+            (Some(pb), None) => format!(
+                "{:>2} PB {}",
+                split.lines,
+                crate::features::high_scores::format_time(seconds(pb))
+            ),
+            (None, None) => format!("{:>2} --", split.lines),
+        };
+        readout.push('\n');
+        readout.push_str(&line);
+    }
+    let color = match delta {
+        0 => theme::TEXT_DIM,
+        d if d > 0 => theme::ACCENT,
+        _ => theme::ATTACK,
+    };
+    for (mut text, mut text_color) in &mut texts {
+        if text.0 != readout {
+            text.0.clone_from(&readout);
+        }
+        text_color.0 = color;
+    }
+}
+
+fn disarm_ghost(mut commands: Commands) {
+    commands.remove_resource::<SprintGhost>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{AiController, Handicap, RecordedGame};
+    use crate::engine::{Engine, EngineConfig};
+    use crate::player::PlayerController;
+    use crate::replays::SeatReplay;
+
+    /// A perfect bot's Sprint to `target` lines, recorded as the session
+    /// records it.
+    fn sprint(target: usize, seed: u64) -> GameReplay {
+        let config = EngineConfig::default();
+        let mut engine = Engine::new(config.clone(), seed);
+        let mut bot = AiController::new(Handicap::perfect(), seed);
+        let mut game = RecordedGame::new(config, seed);
+        while engine.snapshot().lines < target {
+            let mut frame = bot.poll(&engine.snapshot());
+            frame.dt_seconds = SIM_DT_SECONDS;
+            game.push_frame(frame.clone());
+            engine.step(frame);
+            assert!(game.inputs.len() < 20_000, "the bot never finished");
+        }
+        GameReplay {
+            mode: SessionMode::Solo {
+                variant: crate::variant::Variant::Sprint,
+            },
+            summary: String::new(),
+            seats: vec![SeatReplay {
+                participant: Participant::Human,
+                game,
+            }],
+        }
+    }
+
+    #[test]
+    fn racing_your_own_inputs_is_dead_even() {
+        let pb = sprint(20, 36);
+        let mut ghost = SprintGhost::new(pb.clone(), 20);
+        let splits: Vec<usize> = ghost.splits().iter().filter_map(|s| s.pb_slices).collect();
+        assert_eq!(splits.len(), 2, "the PB reached both splits");
+        assert!(splits[0] < splits[1]);
+        assert_eq!(splits[1], pb.frames(), "the last split is the finish");
+
+        // The "live" run replays the same inputs: never a line apart, and
+        // every split lands on the PB's own.
+        let mut live = Playhead::new(pb);
+        while live.step().is_some() {
+            let lines = live.snapshot(0).lines;
+            ghost.advance(lines);
+            assert_eq!(ghost.line_delta(lines), 0);
+        }
+        for split in ghost.splits() {
+            assert_eq!(split.live_slices, split.pb_slices);
+        }
+    }
+
+    #[test]
+    fn a_slower_run_trails_the_ghost() {
+        let pb = sprint(10, 36);
+        let mut ghost = SprintGhost::new(pb.clone(), 10);
+        // Stand still for the whole PB: it pulls ahead by everything it
+        // cleared, and the finish line is capped at the target.
+        for _ in 0..pb.frames() + 30 {
+            ghost.advance(0);
+        }
+        assert_eq!(ghost.line_delta(0), -10);
+        assert_eq!(ghost.splits()[0].live_slices, None);
+        ghost.advance(12);
+        assert_eq!(ghost.line_delta(12), 0);
+        assert_eq!(ghost.splits()[0].live_slices, Some(pb.frames() + 31));
+    }
+}
@@ -3,31 +3,47 @@
 //! The design record is `docs/adr-versus-mode-ui.md`. The shape in one
 //! paragraph: a session is `SessionMode::seat_count` **seat entities**
 //! (engine + snapshot + events + stats each), a `Participant` per seat
-//! saying who drives it (the local keyboard or a
-//! [`ModelRegistry`](crate::ai::ModelRegistry) bot; a future remote human is
-//! one more arm), and one fixed-update step that advances every engine,
+//! saying who drives it (the local keyboard through a player's keymap, a
+//! [`ModelRegistry`](crate::ai::ModelRegistry) bot, or a remote human whose
+//! frames arrive through [`online`]), and one fixed-update step that advances
+//! every engine,
 //! routes [`EngineEvent::AttackSent`] into the opposite seat's pending queue,
 //! and ends the session when a seat dies or a solo goal is met. The engine
 //! owns every garbage *rule* (`docs/adr-versus-rules.md`); this module only
 //! routes. Single-player is the one-seat case: same step, same render, with
 //! the variant's rules folded in through the engine-config seam.
 //!
+//! Practice is a one-seat session too, with an undo history on top
+//! ([`practice`]).
+//!
 //! The session lives in [`GameState::Session`] with its own
 //! `SessionPhase` lifecycle (countdown → running ⇄ paused → over).
 
 use bevy::prelude::*;
 
 use crate::GameState;
+use crate::ai::{RecordedGame, SkillPreset};
 use crate::engine::{
     Engine, EngineConfig, EngineEvent, EngineSnapshot, GoalSystem, LOCK_DOWN_SECONDS, MIN_LEVEL,
 };
+use crate::features::gamepad::{
+    PadAssignments, StickLatch, either_device, gamepad_input_from_binds,
+};
+use crate::features::touch::TouchFrame;
 use crate::level::common::LevelConfig;
-use crate::level::engine_bridge::{PendingEdges, SIM_DT_SECONDS, das_config_from_level};
+use crate::level::engine_bridge::{PendingEdges, SIM_DT_SECONDS, das_config_from_settings};
 use crate::player::{KeyboardController, PlayerController, RawKeyboardFrame};
+use crate::settings::Keymap;
 
+pub(crate) mod adaptive;
+pub(crate) mod analysis;
 mod feel;
+mod ghost;
+pub(crate) mod online;
 mod overlay;
+mod practice;
 pub(crate) mod render;
+pub(crate) mod replay;
 pub(crate) mod skin;
 
 /// Lifecycle of a live session, as a sub-state of [`GameState::Session`] —
@@ -49,19 +65,68 @@ pub enum SessionPhase {
     Over,
 }
 
-/// Who drives a seat. Deliberately an open set: a remote human is one more
-/// arm producing an `InputFrame` per slice, not a redesign.
+/// Who drives a seat. Deliberately an open set: each arm is one more way of
+/// producing an `InputFrame` per slice, not a redesign.
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Participant {
     /// The local keyboard (at most one seat in v1 — there is one keyboard).
     Human,
+    /// The other human of an online match: its frames come off the wire
+    /// ([`online::OnlineMatch`]), released in lockstep with the local seat's.
+    /// Only the lobby seats one; a stale config holding one outside an
+    /// online match is reset by the setup screen.
+    Remote,
     /// A bot from the [`ModelRegistry`](crate::ai::ModelRegistry) catalog.
     Bot {
         /// Index into the registry.
         model: usize,
+        /// How strongly it plays: the handicap is applied on build.
+        skill: BotSkill,
     },
 }
 
+/// How strongly a bot seat plays: a fixed preset, or the adaptive opponent
+/// that steers its handicap toward the player between games
+/// ([`adaptive`]).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BotSkill {
+    Preset(SkillPreset),
+    Adaptive,
+}
+
+impl BotSkill {
+    /// Every choice, in the setup screen's cycle order (presets weakest
+    /// first, then adaptive).
+    pub const ALL: [BotSkill; 6] = [
+        BotSkill::Preset(SkillPreset::Novice),
+        BotSkill::Preset(SkillPreset::Casual),
+        BotSkill::Preset(SkillPreset::Skilled),
+        BotSkill::Preset(SkillPreset::Expert),
+        BotSkill::Preset(SkillPreset::Master),
+        BotSkill::Adaptive,
+    ];
+
+    /// Display name.
+    pub fn label(self) -> &'static str {
+        match self {
+            BotSkill::Preset(preset) => preset.label(),
+            BotSkill::Adaptive => "Adaptive",
+        }
+    }
+}
+
+impl Default for BotSkill {
+    fn default() -> Self {
+        BotSkill::Preset(SkillPreset::default())
+    }
+}
+
+impl From<SkillPreset> for BotSkill {
+    fn from(preset: SkillPreset) -> Self {
+        BotSkill::Preset(preset)
+    }
+}
+
 /// What a session is FOR — the per-mode rules the seat machinery reads.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum SessionMode {
@@ -71,13 +136,31 @@ pub enum SessionMode {
     Solo { variant: crate::variant::Variant },
     /// The versus match on two seats: flat gravity, attack exchange, meters.
     Versus,
+    /// A puzzle from the [`PuzzleCatalog`](crate::puzzle::PuzzleCatalog), by
+    /// index, on one seat: its scripted start, judged against its goal and
+    /// piece budget. Never ranked.
+    Puzzle { index: usize },
+    /// Free play on one seat with undo, redo, a gravity freeze and a bag
+    /// reveal ([`practice`]): flat level-1 gravity, no goal. Never ranked.
+    /// `edited` starts it from the board editor's
+    /// [`EditorPosition`](crate::editor::EditorPosition) (with a bot seated,
+    /// that is the AI playing the position).
+    Practice { edited: bool },
+    /// The opener trainer on one seat: an opener from the
+    /// [`OpenerCatalog`](crate::openers::OpenerCatalog), by index, built from
+    /// a fresh deal on the versus rules and graded placement by placement
+    /// ([`OpenerTrainer`](crate::openers::OpenerTrainer)). Never ranked.
+    Opener { index: usize },
 }
 
 impl SessionMode {
     /// Seats this mode plays with (the prefix of [`SessionConfig::seats`]).
     pub fn seat_count(self) -> usize {
         match self {
-            SessionMode::Solo { .. } => 1,
+            SessionMode::Solo { .. }
+            | SessionMode::Puzzle { .. }
+            | SessionMode::Practice { .. }
+            | SessionMode::Opener { .. } => 1,
             SessionMode::Versus => 2,
         }
     }
@@ -86,7 +169,8 @@ impl SessionMode {
 /// Session configuration, written by the menus and read once when the session
 /// spawns. `seats[..mode.seat_count()]` are the live seats. `seed` is a
 /// test/replay override; a live session draws fresh entropy per game (a
-/// rematch/retry is a new deal, not a replay).
+/// rematch/retry is a new deal, not a replay) unless it is a daily run, which
+/// plays the day's deal (see [`DailyAttempt`](crate::daily::DailyAttempt)).
 #[derive(Resource, Clone, Copy, Debug)]
 pub struct SessionConfig {
     pub seats: [Participant; 2],
@@ -94,11 +178,36 @@ pub struct SessionConfig {
     pub seed: Option<u64>,
 }
 
+impl SessionConfig {
+    /// Whether every live seat is a local human: two-player versus on one
+    /// keyboard.
+    pub fn local_versus(&self) -> bool {
+        self.mode.seat_count() == 2 && self.seats.iter().all(|p| *p == Participant::Human)
+    }
+
+    /// The keys the human at `seat` plays with: Player 2's for the right
+    /// seat of a two-human match, Player 1's otherwise (a lone human is
+    /// Player 1 whichever seat they sit in).
+    pub fn keymap_for(&self, seat: usize) -> Keymap {
+        if self.local_versus() && seat == 1 {
+            Keymap::P2
+        } else {
+            Keymap::P1
+        }
+    }
+}
+
 impl Default for SessionConfig {
     fn default() -> Self {
         Self {
             // You vs the Tier-2 beam — a mid-strength opener (registry index 1).
-            seats: [Participant::Human, Participant::Bot { model: 1 }],
+            seats: [
+                Participant::Human,
+                Participant::Bot {
+                    model: 1,
+                    skill: SkillPreset::Skilled.into(),
+                },
+            ],
             mode: SessionMode::Versus,
             seed: None,
         }
@@ -127,7 +236,7 @@ pub struct SeatSnapshot(pub EngineSnapshot);
 pub struct SeatEvents(pub Vec<EngineEvent>);
 
 /// Running match totals for the HUD and the result banner.
-#[derive(Component, Default, Clone, Copy)]
+#[derive(Component, Default, Clone, Copy, Debug)]
 pub struct SeatStats {
     /// Net attack lines actually sent (post-cancellation).
     pub attack_sent: u32,
@@ -137,15 +246,34 @@ pub struct SeatStats {
 
 /// The local keyboard, seated. Owns the same latch discipline as the
 /// single-player driver (`PendingEdges` + a staged held frame, drained once
-/// per slice) and the player-side DAS state machine — per seat, so a second
-/// local keymap later is a second component, not a refactor.
+/// per slice) and the player-side DAS state machine — per seat, each reading
+/// its own [`Keymap`], so two humans share one keyboard.
 #[derive(Component)]
 pub struct HumanSeat {
+    keymap: Keymap,
+    /// A lone human plays on any pad; two humans each on their assigned one.
+    any_pad: bool,
+    stick: StickLatch,
     controller: KeyboardController,
     held: RawKeyboardFrame,
     edges: PendingEdges,
 }
 
+impl HumanSeat {
+    /// Drain this slice's latched input into the next frame. Offline the step
+    /// applies it at once; online it is sent and applies `input_delay` ticks
+    /// later, on every peer.
+    fn next_frame(&mut self, snapshot: &EngineSnapshot) -> crate::engine::InputFrame {
+        let mut input = self.held;
+        input.dt_seconds = SIM_DT_SECONDS;
+        self.edges.drain_onto(&mut input);
+        self.controller.set_input(input);
+        let frame = self.controller.poll(snapshot);
+        self.edges.reset();
+        frame
+    }
+}
+
 /// The bots seated this match, keyed by seat index. A non-send resource
 /// because `AiController` is `Send`-but-not-`Sync`, and the fixed-update
 /// driver runs on the main thread anyway.
@@ -160,6 +288,17 @@ pub enum SessionOutcome {
     /// Solo: the run ended — `completed` iff the variant's goal was met
     /// (vs a top-out). The banner reads the final snapshot for the numbers.
     Solo { completed: bool },
+    /// Puzzle: the goal was met, or the attempt topped out or ran out of
+    /// pieces.
+    Puzzle { solved: bool },
+    /// Practice: the stack topped out (a practice run has no goal to meet).
+    Practice,
+    /// Opener trainer: the opener was built and fired (`complete`), or a
+    /// placement left the book.
+    Opener { complete: bool },
+    /// Online: the connection failed (the rival left, timed out, or the
+    /// boards desynced) before the match was decided. No winner is scored.
+    Disconnected,
 }
 
 /// Wall-clock session length (advances only while `Running`); the result
@@ -167,36 +306,110 @@ pub enum SessionOutcome {
 #[derive(Resource, Default)]
 pub struct MatchClock(pub f32);
 
+/// A solo run's score total at each whole minute of [`MatchClock`], for the
+/// result banner's per-minute breakdown (variants with
+/// `VariantDef::score_by_minute`).
+#[derive(Resource, Default)]
+pub struct MinuteScores(pub Vec<usize>);
+
+impl MinuteScores {
+    /// The points scored in each minute of a run that ended on
+    /// `final_score` after `elapsed_seconds`. A trailing part-minute counts
+    /// as a minute of its own, unless it is under a second (the frame the
+    /// run ended on overshooting a whole minute), which folds into the last.
+    pub fn breakdown(&self, final_score: usize, elapsed_seconds: f32) -> Vec<usize> {
+        let mut totals = self.0.clone();
+        let marked = totals.len() as f32 * 60.0;
+        match totals.last_mut() {
+            Some(last) if elapsed_seconds - marked < 1.0 => *last = final_score,
+            _ => totals.push(final_score),
+        }
+        let mut before = 0;
+        totals
+            .into_iter()
+            .map(|total| {
+                let minute = total.saturating_sub(before);
+                before = total;
+                minute
+            })
+            .collect()
+    }
+}
+
+/// The stack challenge the human plays this session under: the player's
+/// setting for a solo run of their own, the standard stack anywhere else
+/// (versus, Watch-AI, replays). Fixed when the session spawns, so a setting
+/// changed mid-run neither shows nor ranks until the next one.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SessionChallenge(pub crate::settings::StackChallenge);
+
 /// The rank a finished solo run earned on the leaderboard (None = did not
 /// place, or a bot/unqualified run). Written when the session ends; the
 /// result banner reads it.
 #[derive(Resource, Clone, Copy)]
 pub struct SoloRecorded(pub Option<usize>);
 
+/// A finished daily run as the day's board filed it, and its rank there
+/// (None = did not place). The result banner shows its share text.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct DailyRecorded {
+    pub entry: crate::daily::DailyEntry,
+    pub rank: Option<usize>,
+}
+
 /// The engine rules for this session's seats, by mode. Versus: flat level-1
 /// gravity (no goal system — pressure comes from the opponent, not the clock)
 /// with the player's preview/lock-down preferences applied symmetrically.
 /// Solo: the variant's own rules through the same config seam single-player
-/// always used (leveling gravity, goal systems, variant overrides).
+/// always used (leveling gravity, goal systems, variant overrides). Puzzle:
+/// the puzzle's scripted start (see [`Puzzle::engine_config`](crate::puzzle::Puzzle::engine_config)).
+/// Practice: the versus rules, on one seat, from the edited position when
+/// launched from the editor. The opener trainer: the versus rules on one seat.
 fn session_engine_config(
     mode: SessionMode,
     level: &LevelConfig,
     settings: &crate::settings::GameSettings,
+    puzzles: &crate::puzzle::PuzzleCatalog,
+    editor: &crate::editor::EditorPosition,
 ) -> EngineConfig {
     match mode {
+        SessionMode::Practice { edited: true } => EngineConfig {
+            scenario: Some(editor.scenario()),
+            ..session_engine_config(
+                SessionMode::Practice { edited: false },
+                level,
+                settings,
+                puzzles,
+                editor,
+            )
+        },
+        // A stale index (the catalog changed under a kept config) plays an
+        // empty field rather than panicking mid-spawn.
+        SessionMode::Puzzle { index } => puzzles
+            .get(index)
+            .map_or_else(EngineConfig::default, |puzzle| {
+                puzzle.engine_config(settings)
+            }),
         SessionMode::Solo { variant } => {
             crate::level::engine_bridge::engine_config_for_game(level, settings, variant)
         }
-        SessionMode::Versus => EngineConfig {
-            board_width: 10,
-            visible_height: 20,
-            preview_count: settings.next_count,
-            lock_down_mode: settings.lock_down_mode,
-            lock_down_seconds: LOCK_DOWN_SECONDS,
-            starting_level: MIN_LEVEL,
-            goal_system: GoalSystem::None,
-            garbage_cap: EngineConfig::default().garbage_cap,
-        },
+        SessionMode::Versus | SessionMode::Practice { .. } | SessionMode::Opener { .. } => {
+            EngineConfig {
+                board_width: 10,
+                visible_height: 20,
+                preview_count: settings.next_count,
+                lock_down_mode: settings.lock_down_mode,
+                lock_down_seconds: LOCK_DOWN_SECONDS,
+                starting_level: MIN_LEVEL,
+                goal_system: GoalSystem::None,
+                garbage_cap: EngineConfig::default().garbage_cap,
+                dig: None,
+                lock_delay_curve: crate::engine::LockDelayCurve::Flat,
+                perfect_clear_bonus: false,
+                master: false,
+                scenario: None,
+            }
+        }
     }
 }
 
@@ -214,39 +427,70 @@ impl Plugin for SessionPlugin {
         .add_sub_state::<SessionPhase>()
         .init_resource::<SessionConfig>()
         .init_resource::<MatchClock>()
+        .init_resource::<SessionChallenge>()
         // Self-sufficiency for headless tests (idempotent: `GamePlugin`
         // stays the canonical owner of the shared contracts).
         .init_resource::<crate::settings::GameSettings>()
         .init_resource::<crate::ai::ModelRegistry>()
         .init_resource::<LevelConfig>()
         .init_resource::<crate::high_scores::HighScores>()
+        .init_resource::<crate::puzzle::PuzzleCatalog>()
+        .init_resource::<crate::puzzle::PuzzleProgress>()
+        .init_resource::<crate::editor::EditorPosition>()
+        .init_resource::<crate::openers::OpenerCatalog>()
+        .init_resource::<crate::daily::DailyBook>()
+        .add_systems(Startup, (load_puzzle_progress, load_daily_book))
         .add_systems(OnEnter(GameState::Session), session_setup)
-        .add_systems(OnExit(GameState::Session), session_teardown)
+        .add_systems(
+            OnExit(GameState::Session),
+            (session_teardown, end_daily_attempt),
+        )
         .add_systems(
             PreUpdate,
             (clear_seat_events, latch_human_input)
                 .chain()
                 .after(bevy::input::InputSystems)
+                .after(crate::features::touch::TouchSystems)
                 .run_if(in_state(SessionPhase::Running)),
         )
         .add_systems(
             FixedUpdate,
-            session_step.run_if(in_state(SessionPhase::Running)),
+            // Online, the lockstep clock (not the local phase) decides which
+            // slices step: it releases frames for a tick only once every
+            // peer's are in, and stages nothing while the match is held.
+            // A replay's seats are stepped by its playhead instead.
+            session_step.run_if(
+                in_state(SessionPhase::Running)
+                    .or(resource_exists::<online::OnlineMatch>)
+                    .and(not(resource_exists::<replay::SessionPlayback>)),
+            ),
         )
         .add_systems(
             Update,
-            (advance_match_clock, check_solo_end)
+            (advance_match_clock, mark_minute_scores, check_solo_end)
                 .chain()
-                .run_if(in_state(SessionPhase::Running)),
+                .run_if(
+                    in_state(SessionPhase::Running)
+                        .and(not(resource_exists::<replay::SessionPlayback>)),
+                ),
+        )
+        .add_systems(
+            OnEnter(SessionPhase::Over),
+            (record_solo_run, record_puzzle_run, record_daily_run),
         )
-        .add_systems(OnEnter(SessionPhase::Over), record_solo_run)
         // A press latched in the same render frame as a pause (a frame
         // that ran zero slices) must not fire on the first slice after a
         // resume, minutes later.
         .add_systems(OnEnter(SessionPhase::Running), reset_human_latch)
         .add_plugins(render::SessionRenderPlugin)
         .add_plugins(overlay::SessionOverlayPlugin)
-        .add_plugins(feel::SessionFeelPlugin);
+        .add_plugins(analysis::SessionAnalysisPlugin)
+        .add_plugins(replay::SessionReplayPlugin)
+        .add_plugins(ghost::SessionGhostPlugin)
+        .add_plugins(adaptive::SessionAdaptivePlugin)
+        .add_plugins(feel::SessionFeelPlugin)
+        .add_plugins(practice::SessionPracticePlugin)
+        .add_plugins(online::SessionOnlinePlugin);
     }
 }
 
@@ -255,27 +499,85 @@ impl Plugin for SessionPlugin {
 /// the engine's own salt) and one controller per bot seat. Exclusive because
 /// bot controllers go into a non-send resource.
 fn session_setup(world: &mut World) {
+    world.insert_resource(SessionChallenge::default());
+    if world.contains_resource::<replay::SessionPlayback>() {
+        replay::seat_playback(world);
+        return;
+    }
     let config = *world.resource::<SessionConfig>();
     let settings = world.resource::<crate::settings::GameSettings>().clone();
     let engine_config = {
         let level = world.resource::<LevelConfig>();
-        session_engine_config(config.mode, level, &settings)
+        let puzzles = world.resource::<crate::puzzle::PuzzleCatalog>();
+        let editor = world.resource::<crate::editor::EditorPosition>();
+        session_engine_config(config.mode, level, &settings, puzzles, editor)
     };
 
     // Fresh deal per match: app-clock entropy unless a test/replay pinned it.
-    // (Headless tests freeze the clock, so they pin the seed explicitly.)
-    let seed = config.seed.unwrap_or_else(|| {
-        world
-            .resource::<Time<Real>>()
-            .elapsed()
-            .subsec_nanos()
-            .wrapping_mul(0x9E37_79B9) as u64
-            ^ world.resource::<Time<Real>>().elapsed().as_nanos() as u64
-    });
+    // (Headless tests freeze the clock, so they pin the seed explicitly.) An
+    // online match plays the seed the peers agreed on in the lobby.
+    let online = world
+        .get_resource::<online::OnlineMatch>()
+        .map(|m| m.seed());
+    // A daily run plays the day's deal, and its official attempt is spent
+    // the moment it is dealt: a retry (or a second visit) is practice.
+    let daily = begin_daily_attempt(world);
+    // A puzzle always plays its own seed: every attempt is the same puzzle.
+    let seed = match config.mode {
+        SessionMode::Puzzle { .. } => crate::puzzle::PUZZLE_SEED,
+        _ => online
+            .or(daily.map(|attempt| attempt.challenge.seed))
+            .or(config.seed)
+            .unwrap_or_else(|| {
+                world
+                    .resource::<Time<Real>>()
+                    .elapsed()
+                    .subsec_nanos()
+                    .wrapping_mul(0x9E37_79B9) as u64
+                    ^ world.resource::<Time<Real>>().elapsed().as_nanos() as u64
+            }),
+    };
     info!("versus match: seed {seed}, seats {:?}", config.seats);
+    // Everyone plays the daily on the standard stack.
+    if matches!(config.mode, SessionMode::Solo { .. })
+        && config.seats[0] == Participant::Human
+        && online.is_none()
+        && daily.is_none()
+    {
+        world.insert_resource(SessionChallenge(settings.stack_challenge()));
+    }
+    let puzzle = match config.mode {
+        SessionMode::Puzzle { index } => world
+            .resource::<crate::puzzle::PuzzleCatalog>()
+            .get(index)
+            .map(crate::puzzle::PuzzleRun::new),
+        _ => None,
+    };
+    match puzzle {
+        Some(run) => world.insert_resource(run),
+        None => {
+            world.remove_resource::<crate::puzzle::PuzzleRun>();
+        }
+    }
+
+    let trainer = match config.mode {
+        SessionMode::Opener { index } => world
+            .resource::<crate::openers::OpenerCatalog>()
+            .get(index)
+            .map(crate::openers::OpenerTrainer::new),
+        _ => None,
+    };
+    match trainer {
+        Some(trainer) => world.insert_resource(trainer),
+        None => {
+            world.remove_resource::<crate::openers::OpenerTrainer>();
+        }
+    }
+
+    world.remove_resource::<practice::PracticeHistory>();
 
     let mut bots = SessionBots::default();
-    let das = das_config_from_level(world.resource::<LevelConfig>());
+    let das = das_config_from_settings(&settings);
 
     for (index, participant) in config
         .seats
@@ -289,25 +591,43 @@ fn session_setup(world: &mut World) {
         match participant {
             Participant::Human => {
                 human = Some(HumanSeat {
+                    keymap: config.keymap_for(index),
+                    any_pad: !config.local_versus(),
+                    stick: StickLatch::default(),
                     controller: KeyboardController::new(das),
                     held: RawKeyboardFrame::default(),
                     edges: PendingEdges::default(),
                 });
             }
-            Participant::Bot { model } => {
+            Participant::Bot { model, skill } => {
                 let registry = world.resource::<crate::ai::ModelRegistry>();
+                let handicap = match skill {
+                    BotSkill::Preset(preset) => preset.handicap(),
+                    BotSkill::Adaptive => world
+                        .resource::<adaptive::AdaptiveDifficulty>()
+                        .handicap_for(adaptive::LOCAL_PLAYER),
+                };
                 // An out-of-range model (a stale config) falls back to the
                 // first catalog entry rather than panicking mid-spawn.
-                let controller = registry.build(model).unwrap_or_else(|| {
+                let controller = registry.build(model, handicap).unwrap_or_else(|| {
                     warn!("versus: model {model} not in the registry; using entry 0");
-                    registry.build(0).expect("the registry is never empty")
+                    registry
+                        .build(0, handicap)
+                        .expect("the registry is never empty")
                 });
                 bots.0.push((index, controller));
             }
+            // Driven from the wire: nothing to build locally.
+            Participant::Remote => {}
         }
 
         let engine = Engine::new(engine_config.clone(), seed);
         let snapshot = engine.snapshot();
+        // A practising human can undo back to the opening position.
+        if matches!(config.mode, SessionMode::Practice { .. }) && human.is_some() {
+            let recording = RecordedGame::new(engine_config.clone(), seed);
+            world.insert_resource(practice::PracticeHistory::new(&engine, &recording));
+        }
         let mut seat = world.spawn((
             Seat { index },
             SeatEngine(engine),
@@ -316,15 +636,43 @@ fn session_setup(world: &mut World) {
             SeatStats::default(),
             DespawnOnExit(GameState::Session),
         ));
+        // Every seat is recorded: the match is kept as a replay, and human
+        // play also feeds the post-game blunder analysis.
+        let recording = RecordedGame::new(engine_config.clone(), seed);
+        seat.insert(analysis::SeatRecording(recording));
         if let Some(human) = human {
             seat.insert(human);
         }
+        if online.is_some() {
+            seat.insert(online::NetSeat::default());
+        }
     }
 
     world.insert_non_send_resource(bots);
     world.insert_resource(MatchClock::default());
+    world.insert_resource(MinuteScores::default());
     world.remove_resource::<SessionOutcome>();
     world.remove_resource::<SoloRecorded>();
+    world.remove_resource::<DailyRecorded>();
+}
+
+/// The daily attempt this deal plays, if any, its official attempt spent
+/// here (or turned to practice when the day's was spent already).
+fn begin_daily_attempt(world: &mut World) -> Option<crate::daily::DailyAttempt> {
+    let mut attempt = *world.get_resource::<crate::daily::DailyAttempt>()?;
+    if attempt.official {
+        let mut book = world.resource_mut::<crate::daily::DailyBook>();
+        attempt.official = book.begin_official(attempt.challenge.day);
+        if attempt.official
+            && let Some(storage) = world.get_resource::<crate::storage::StorageResource>()
+        {
+            world
+                .resource::<crate::daily::DailyBook>()
+                .persist(storage.0.as_ref());
+        }
+        world.insert_resource(attempt);
+    }
+    Some(attempt)
 }
 
 /// Drop the bots and the outcome when the session ends. Seat entities are
@@ -342,22 +690,45 @@ fn clear_seat_events(mut seats: Query<&mut SeatEvents>) {
     }
 }
 
-/// Sample the keyboard once per render frame for the human seat: latch edges,
+/// Sample the keyboard and pads once per render frame for each human seat —
+/// its own keymap, plus its pad (any pad for a lone human), plus the touch
+/// controls for Player 1: latch edges,
 /// stage held flags — the same drop/dup-safe discipline as the single-player
-/// `latch_input`, but stored on the seat so a second keymap later is data, not
-/// architecture.
+/// `latch_input`, stored per seat.
 fn latch_human_input(
     keyboard: Res<ButtonInput<KeyCode>>,
     settings: Res<crate::settings::GameSettings>,
+    assignments: Res<PadAssignments>,
+    pads: Query<(Entity, &Gamepad)>,
+    touch: Res<TouchFrame>,
     mut humans: Query<&mut HumanSeat>,
 ) {
     for mut human in &mut humans {
-        let raw = crate::features::options::keyboard_input_from_keybinds(
+        let human = &mut *human;
+        let keys = crate::features::options::keyboard_input_from_keybinds(
             &keyboard,
-            &settings.keybinds,
+            settings.keymap(human.keymap),
             settings.hold_enabled,
             SIM_DT_SECONDS,
         );
+        let own = assignments.pad(human.keymap);
+        let seat_pads = pads
+            .iter()
+            .filter(|(entity, _)| human.any_pad || own == Some(*entity))
+            .map(|(_, pad)| pad);
+        let from_pads = gamepad_input_from_binds(
+            seat_pads,
+            &settings.gamepad_binds,
+            settings.stick_threshold,
+            &mut human.stick,
+            settings.hold_enabled,
+            SIM_DT_SECONDS,
+        );
+        let mut raw = either_device(keys, from_pads);
+        // The touch screen is Player 1's.
+        if human.keymap == Keymap::P1 {
+            raw = either_device(raw, touch.input);
+        }
         human.edges.latch(&raw);
         human.held = raw;
     }
@@ -383,14 +754,20 @@ type SeatStepQuery<'w, 's> = Query<
         &'static mut SeatEvents,
         &'static mut SeatStats,
         Option<&'static mut HumanSeat>,
+        Option<&'static mut analysis::SeatRecording>,
+        Option<&'static mut online::NetSeat>,
     ),
 >;
 
+#[allow(clippy::too_many_arguments)] // a Bevy system's params are its dependency list
 fn session_step(
     mut seats: SeatStepQuery,
     bots: Option<NonSendMut<SessionBots>>,
     outcome: Option<Res<SessionOutcome>>,
     config: Res<SessionConfig>,
+    puzzle: Option<ResMut<crate::puzzle::PuzzleRun>>,
+    practice: Option<ResMut<practice::PracticeHistory>>,
+    trainer: Option<ResMut<crate::openers::OpenerTrainer>>,
     mut commands: Commands,
     mut next: ResMut<NextState<SessionPhase>>,
 ) {
@@ -406,32 +783,51 @@ fn session_step(
     if outcome.is_some() {
         return;
     }
+    // Online: a slice steps only on a tick the lockstep clock released for
+    // every seat. Anything less (a peer's frame still in flight, a held
+    // match) idles the whole slice — never one board alone.
+    if seats
+        .iter()
+        .any(|(.., net)| net.is_some_and(|net| net.staged.is_none()))
+    {
+        return;
+    }
     // Phase 1: step every seat with its participant's frame.
     let mut slice_events: [Vec<EngineEvent>; 2] = [Vec::new(), Vec::new()];
-    for (seat, mut engine, snapshot, _, _, human) in &mut seats {
-        let events = match human {
-            Some(mut human) => {
-                let mut input = human.held;
-                input.dt_seconds = SIM_DT_SECONDS;
-                human.edges.drain_onto(&mut input);
-                human.controller.set_input(input);
-                let frame = human.controller.poll(&snapshot.0);
-                human.edges.reset();
+    for (seat, mut engine, snapshot, _, _, human, recording, net) in &mut seats {
+        let events = match (net, human) {
+            // The released frame, the local seat's included (it was sampled
+            // `input_delay` ticks ago and sent).
+            (Some(mut net), _) => {
+                let frame = net.staged.take().expect("checked above");
+                if let Some(mut recording) = recording {
+                    recording.0.push_frame(frame.clone());
+                }
+                engine.0.step(frame)
+            }
+            (None, Some(mut human)) => {
+                let mut frame = human.next_frame(&snapshot.0);
+                // Practice's freeze: the frame still moves the piece, but no
+                // time passes, so nothing falls or locks by itself.
+                if practice.as_ref().is_some_and(|practice| practice.frozen) {
+                    frame.dt_seconds = 0.0;
+                }
+                if let Some(mut recording) = recording {
+                    recording.0.push_frame(frame.clone());
+                }
                 engine.0.step(frame)
             }
-            None => {
+            (None, None) => {
                 let Some((_, bot)) = bots.0.iter_mut().find(|(i, _)| *i == seat.index) else {
                     continue; // a seat with no driver idles (should not happen)
                 };
-                // The bot plays BLIND to the pending queue — deliberately. The
-                // experimental record (versus_climb header) shows the aware
-                // search is decisively worse under pressure with today's
-                // weights (the mispricing finding), and blindness also denies
-                // a bot the perfect hole information a human can't see. The
-                // engine still cancels and rises by rule regardless.
-                let mut snap = engine.0.snapshot();
-                snap.pending_garbage.clear();
-                let frame = bot.poll(&snap);
+                // A blind handicap (the weaker skill presets) has the
+                // controller strip the pending queue from what the bot reads;
+                // the engine still cancels and rises by rule regardless.
+                let frame = bot.poll(&engine.0.snapshot());
+                if let Some(mut recording) = recording {
+                    recording.0.push_frame(frame.clone());
+                }
                 engine.0.step(frame)
             }
         };
@@ -442,10 +838,13 @@ fn session_step(
 
     // Phase 2: route attack across seats (0 → 1, 1 → 0), symmetrically.
     let attack: [u32; 2] = [sent_lines(&slice_events[0]), sent_lines(&slice_events[1])];
-    for (seat, mut engine, _, _, mut stats, _) in &mut seats {
+    for (seat, mut engine, _, _, mut stats, _, recording, _) in &mut seats {
         let incoming = attack[1 - seat.index.min(1)];
         if incoming > 0 {
             engine.0.queue_garbage(incoming);
+            if let Some(mut recording) = recording {
+                recording.0.push_garbage(incoming);
+            }
         }
         stats.attack_sent += attack[seat.index.min(1)];
         stats.garbage_taken += slice_events[seat.index]
@@ -458,12 +857,50 @@ fn session_step(
     }
 
     // Phase 3: publish post-routing snapshots and the frame's events.
-    for (seat, engine, mut snapshot, mut events, _, _) in &mut seats {
+    for (seat, engine, mut snapshot, mut events, ..) in &mut seats {
         snapshot.0 = engine.0.snapshot();
         events.0.extend(slice_events[seat.index].iter().cloned());
     }
 
-    // Phase 4: death check, **per slice** — several slices can run in one
+    // Phase 4 (practice): every lock is a position to undo back to.
+    if let Some(mut practice) = practice {
+        for (seat, engine, .., recording, _) in &seats {
+            if let Some(recording) = recording
+                && slice_events[seat.index]
+                    .iter()
+                    .any(|event| matches!(event, EngineEvent::Locked { .. }))
+            {
+                practice.placed(&engine.0, &recording.0);
+            }
+        }
+    }
+
+    // Phase 5 (puzzle): judge the slice against the goal and the piece
+    // budget. Per slice for the same reason as the death check below — the
+    // lock that meets the goal must not be followed by one that spends it.
+    if let Some(mut puzzle) = puzzle
+        && let Some(solved) = puzzle.observe(&slice_events[0])
+    {
+        info!("puzzle over: solved {solved}");
+        commands.insert_resource(SessionOutcome::Puzzle { solved });
+        next.set(SessionPhase::Over);
+        return;
+    }
+
+    // Phase 6 (opener trainer): grade the slice's lock against the book and
+    // re-plan the target; completing the opener or leaving the book ends it.
+    if let Some(mut trainer) = trainer
+        && let Some((_, _, snapshot, ..)) = seats.iter().find(|(seat, ..)| seat.index == 0)
+        && let Some(end) = trainer.observe(&slice_events[0], &snapshot.0)
+    {
+        let complete = end == tetr_core::opener::RunEnd::Complete;
+        info!("opener over: complete {complete}");
+        commands.insert_resource(SessionOutcome::Opener { complete });
+        next.set(SessionPhase::Over);
+        return;
+    }
+
+    // Phase 7: death check, **per slice** — several slices can run in one
     // render frame (catch-up after a hitch), and the first death ends the
     // match in *its* slice. A frame-granular check would keep both engines
     // playing to the end of the frame and could score "both died this frame"
@@ -471,7 +908,7 @@ fn session_step(
     // death in the *same slice*. The commands apply between slices, so the
     // guard above freezes everything after this one.
     let mut dead = [false; 2];
-    for (seat, _, snapshot, _, _, _) in &seats {
+    for (seat, _, snapshot, ..) in &seats {
         if seat.index < 2 {
             dead[seat.index] = snapshot.0.game_over.is_some();
         }
@@ -490,6 +927,9 @@ fn session_step(
             // Solo: a death is an incomplete run (the variant goal ends runs
             // via `check_solo_end`, not here).
             SessionMode::Solo { .. } => SessionOutcome::Solo { completed: false },
+            SessionMode::Puzzle { .. } => SessionOutcome::Puzzle { solved: false },
+            SessionMode::Practice { .. } => SessionOutcome::Practice,
+            SessionMode::Opener { .. } => SessionOutcome::Opener { complete: false },
         };
         commands.insert_resource(outcome);
         next.set(SessionPhase::Over);
@@ -525,11 +965,14 @@ fn check_solo_end(
 }
 
 /// Solo only: when the run ends, file a HUMAN run for the leaderboard (bot
-/// seats — Watch-AI — never rank against the player) and stash the rank for
-/// the result banner.
+/// seats — Watch-AI — never rank against the player; a daily run ranks on the
+/// day's board instead) and stash the rank for the result banner.
+#[allow(clippy::too_many_arguments)] // a Bevy system's params are its dependency list
 fn record_solo_run(
     config: Res<SessionConfig>,
     clock: Res<MatchClock>,
+    challenge: Res<SessionChallenge>,
+    daily: Option<Res<crate::daily::DailyAttempt>>,
     seats: Query<(&SeatSnapshot, Option<&HumanSeat>), With<Seat>>,
     storage: Option<Res<crate::storage::StorageResource>>,
     mut scores: ResMut<crate::high_scores::HighScores>,
@@ -541,14 +984,108 @@ fn record_solo_run(
     let Some((snapshot, human)) = seats.iter().next() else {
         return;
     };
-    let rank = if human.is_some() {
-        crate::features::high_scores::record(&snapshot.0, clock.0, variant, &mut scores, &storage)
+    let rank = if human.is_some() && daily.is_none() {
+        crate::features::high_scores::record(
+            &snapshot.0,
+            clock.0,
+            variant,
+            challenge.0,
+            &mut scores,
+            &storage,
+        )
     } else {
         None
     };
     commands.insert_resource(SoloRecorded(rank));
 }
 
+/// Restore the solved-puzzle record on startup.
+fn load_puzzle_progress(
+    storage: Option<Res<crate::storage::StorageResource>>,
+    mut progress: ResMut<crate::puzzle::PuzzleProgress>,
+) {
+    if let Some(storage) = storage {
+        *progress = crate::puzzle::PuzzleProgress::load(storage.0.as_ref());
+    }
+}
+
+/// Restore the daily challenge's days on startup.
+fn load_daily_book(
+    storage: Option<Res<crate::storage::StorageResource>>,
+    mut book: ResMut<crate::daily::DailyBook>,
+) {
+    if let Some(storage) = storage {
+        *book = crate::daily::DailyBook::load(storage.0.as_ref());
+    }
+}
+
+/// Daily only: file the human's run on the day's board, stash it for the
+/// result banner, and make any retry practice.
+fn record_daily_run(
+    clock: Res<MatchClock>,
+    outcome: Option<Res<SessionOutcome>>,
+    seats: Query<&SeatSnapshot, (With<Seat>, With<HumanSeat>)>,
+    storage: Option<Res<crate::storage::StorageResource>>,
+    attempt: Option<ResMut<crate::daily::DailyAttempt>>,
+    mut book: ResMut<crate::daily::DailyBook>,
+    mut commands: Commands,
+) {
+    let (Some(mut attempt), Some(snapshot)) = (attempt, seats.iter().next()) else {
+        return;
+    };
+    let completed = match outcome.as_deref() {
+        Some(SessionOutcome::Solo { completed }) => *completed,
+        Some(SessionOutcome::Puzzle { solved }) => *solved,
+        _ => false,
+    };
+    let entry = crate::daily::DailyEntry {
+        score: snapshot.0.score,
+        time_seconds: clock.0,
+        lines: snapshot.0.lines,
+        completed,
+        official: attempt.official,
+    };
+    let rank = book.record(&attempt.challenge, entry);
+    if let Some(storage) = &storage {
+        book.persist(storage.0.as_ref());
+    }
+    attempt.official = false;
+    commands.insert_resource(DailyRecorded { entry, rank });
+}
+
+/// Leaving the session ends the daily attempt (an unfinished official one
+/// stays spent).
+fn end_daily_attempt(mut commands: Commands) {
+    commands.remove_resource::<crate::daily::DailyAttempt>();
+}
+
+/// Puzzle only: a solve by the human marks the puzzle done (the browser
+/// ticks it off).
+fn record_puzzle_run(
+    config: Res<SessionConfig>,
+    outcome: Option<Res<SessionOutcome>>,
+    puzzles: Res<crate::puzzle::PuzzleCatalog>,
+    storage: Option<Res<crate::storage::StorageResource>>,
+    mut progress: ResMut<crate::puzzle::PuzzleProgress>,
+) {
+    let SessionMode::Puzzle { index } = config.mode else {
+        return;
+    };
+    if config.seats[0] != Participant::Human
+        || outcome.as_deref() != Some(&SessionOutcome::Puzzle { solved: true })
+    {
+        return;
+    }
+    let Some(puzzle) = puzzles.get(index) else {
+        return;
+    };
+    if progress.mark_solved(&puzzle.id)
+        && let Some(storage) = &storage
+    {
+        progress.persist(storage.0.as_ref());
+    }
+}
+
 /// Total net attack in a slice's events.
 fn sent_lines(events: &[EngineEvent]) -> u32 {
     events
@@ -565,6 +1102,24 @@ fn advance_match_clock(time: Res<Time>, mut clock: ResMut<MatchClock>) {
     clock.0 += time.delta_secs();
 }
 
+/// Solo only: note the score total each time the clock passes a whole minute.
+fn mark_minute_scores(
+    config: Res<SessionConfig>,
+    clock: Res<MatchClock>,
+    seats: Query<&SeatSnapshot, With<Seat>>,
+    mut marks: ResMut<MinuteScores>,
+) {
+    if !matches!(config.mode, SessionMode::Solo { .. }) {
+        return;
+    }
+    let Some(snapshot) = seats.iter().next() else {
+        return;
+    };
+    while clock.0 >= (marks.0.len() + 1) as f32 * 60.0 {
+        marks.0.push(snapshot.0.score);
+    }
+}
+
 /// Drop any stale latched edges/held flags when the match (re)starts running —
 /// a hard drop pressed in the instant of pausing stays latched through the
 /// whole pause otherwise (the single-player latch has the same hazard; here it
@@ -599,6 +1154,8 @@ pub(crate) fn restart_match(world: &mut World) {
 mod tests {
     use super::*;
     use crate::assets::GameAssets;
+    use crate::editor::EditorPosition;
+    use crate::engine::{CellKind, PieceType};
     use bevy::state::app::StatesPlugin;
     use bevy::time::TimeUpdateStrategy;
     use core::time::Duration;
@@ -634,23 +1191,39 @@ mod tests {
     /// The harness without the phase override: enters `Versus` and leaves the
     /// match on its natural opening phase (the countdown).
     fn bare_session_app(config: SessionConfig) -> App {
+        let mut app = unstarted_session_app(config);
+        enter_session(&mut app);
+        app
+    }
+
+    /// The harness before the session starts (for resources a menu would
+    /// insert on the way in, like an online match).
+    fn unstarted_session_app(config: SessionConfig) -> App {
         let mut app = App::new();
         app.add_plugins((MinimalPlugins, StatesPlugin))
             .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO))
             .init_state::<GameState>()
             .insert_resource(ButtonInput::<KeyCode>::default())
             // `focus_navigation` (the pause/result menus) reads the mouse
-            // accumulator that `bevy_input` provides in a real app.
+            // accumulator that `bevy_input` provides in a real app, and the
+            // seats and menus read the pad and touch state the gamepad and
+            // touch features keep.
             .insert_resource(bevy::input::mouse::AccumulatedMouseMotion::default())
+            .init_resource::<crate::ui::focus::MenuPad>()
+            .init_resource::<PadAssignments>()
+            .init_resource::<TouchFrame>()
             .insert_resource(test_assets())
             .insert_resource(config)
             .add_plugins(SessionPlugin);
+        app
+    }
+
+    fn enter_session(app: &mut App) {
         app.world_mut()
             .resource_mut::<NextState<GameState>>()
             .set(GameState::Session);
         app.update(); // queue the transition
         app.update(); // apply Versus + run setup
-        app
     }
 
     /// The simulation contract has two halves: every engine step stamps
@@ -751,7 +1324,13 @@ mod tests {
     #[test]
     fn a_solo_goal_completion_records_complete() {
         let mut app = headless_session_app(SessionConfig {
-            seats: [Participant::Human, Participant::Bot { model: 0 }],
+            seats: [
+                Participant::Human,
+                Participant::Bot {
+                    model: 0,
+                    skill: SkillPreset::Skilled.into(),
+                },
+            ],
             mode: SessionMode::Solo {
                 variant: crate::variant::Variant::Ultra,
             },
@@ -777,6 +1356,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn minute_scores_break_a_run_down_by_minute() {
+        let marks = MinuteScores(vec![12_000, 30_000]);
+        // Blitz ends a frame past the two-minute mark: no third minute.
+        assert_eq!(marks.breakdown(30_400, 120.02), [12_000, 18_400]);
+        // A top-out mid-way through the third minute shows it.
+        assert_eq!(marks.breakdown(31_000, 150.0), [12_000, 18_000, 1_000]);
+        assert_eq!(MinuteScores::default().breakdown(900, 41.0), [900]);
+    }
+
     /// Solo pause conceals the field (the anti-pause-think rule); resume
     /// restores it. Versus pause is covered by the overlay's own tests.
     #[test]
@@ -814,7 +1403,13 @@ mod tests {
 
     fn solo_human(seed: u64) -> SessionConfig {
         SessionConfig {
-            seats: [Participant::Human, Participant::Bot { model: 0 }],
+            seats: [
+                Participant::Human,
+                Participant::Bot {
+                    model: 0,
+                    skill: SkillPreset::Skilled.into(),
+                },
+            ],
             mode: SessionMode::Solo {
                 variant: crate::variant::Variant::Marathon,
             },
@@ -840,6 +1435,8 @@ mod tests {
             },
             &level,
             &settings,
+            &crate::puzzle::PuzzleCatalog::default(),
+            &crate::editor::EditorPosition::default(),
         );
         let mut reference = Engine::new(config, 7);
         for _ in 0..slices {
@@ -858,6 +1455,66 @@ mod tests {
         );
     }
 
+    /// The human seat's recording is a faithful replay (a fresh engine fed
+    /// it lands on the live seat's exact state), and the match ending turns
+    /// it into a finished review with one rated verdict per lock.
+    #[test]
+    fn a_human_recording_replays_and_is_reviewed_after_the_match() {
+        let mut app = headless_session_app(solo_human(7));
+        for i in 0..24 {
+            {
+                let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+                if i % 2 == 0 {
+                    keys.press(KeyCode::Space);
+                } else {
+                    keys.release(KeyCode::Space);
+                    keys.clear();
+                }
+            }
+            tick_fixed(&mut app, 1);
+        }
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+
+        let (live, recording) = {
+            let mut seats = app
+                .world_mut()
+                .query::<(&SeatSnapshot, &analysis::SeatRecording)>();
+            let (snapshot, recording) = seats.iter(app.world()).next().expect("a human seat");
+            (snapshot.0.clone(), recording.0.clone())
+        };
+        let mut replay = Engine::new(recording.config.clone(), recording.seed);
+        let mut locks = 0;
+        for frame in &recording.inputs {
+            locks += replay
+                .step(frame.clone())
+                .iter()
+                .filter(|e| matches!(e, EngineEvent::Locked { .. }))
+                .count();
+        }
+        assert_eq!(replay.snapshot(), live, "the recording replays the seat");
+        assert!(locks > 0, "the hard drops locked pieces");
+
+        app.world_mut()
+            .resource_mut::<NextState<SessionPhase>>()
+            .set(SessionPhase::Over);
+        for _ in 0..20 {
+            app.update();
+        }
+        let mut seats = app.world_mut().query::<&analysis::SeatAnalysis>();
+        let analyst = &seats.iter(app.world()).next().expect("analysis began").0;
+        assert!(analyst.is_finished());
+        let reviews = &analyst.analysis().reviews;
+        assert_eq!(reviews.len(), locks, "one review per lock");
+        assert!(
+            reviews
+                .iter()
+                .all(|r| r.verdict != crate::ai::Verdict::Unrated),
+            "spawn-column hard drops are movegen placements"
+        );
+    }
+
     /// One press = one action even when several fixed slices run in a single
     /// render frame — the edge-latch property.
     #[test]
@@ -919,7 +1576,16 @@ mod tests {
     #[test]
     fn a_solo_bot_seat_drives_the_engine() {
         let mut app = headless_session_app(SessionConfig {
-            seats: [Participant::Bot { model: 0 }, Participant::Bot { model: 0 }],
+            seats: [
+                Participant::Bot {
+                    model: 0,
+                    skill: SkillPreset::Skilled.into(),
+                },
+                Participant::Bot {
+                    model: 0,
+                    skill: SkillPreset::Skilled.into(),
+                },
+            ],
             mode: SessionMode::Solo {
                 variant: crate::variant::Variant::Marathon,
             },
@@ -952,7 +1618,16 @@ mod tests {
             (solo_human(7), true),
             (
                 SessionConfig {
-                    seats: [Participant::Bot { model: 0 }, Participant::Bot { model: 0 }],
+                    seats: [
+                        Participant::Bot {
+                            model: 0,
+                            skill: SkillPreset::Skilled.into(),
+                        },
+                        Participant::Bot {
+                            model: 0,
+                            skill: SkillPreset::Skilled.into(),
+                        },
+                    ],
                     mode: SessionMode::Solo {
                         variant: crate::variant::Variant::Marathon,
                     },
@@ -969,7 +1644,10 @@ mod tests {
             app.update();
 
             let scores = app.world().resource::<crate::high_scores::HighScores>();
-            let table = scores.table(crate::variant::Variant::Marathon);
+            let table = scores.table(
+                crate::variant::Variant::Marathon,
+                crate::settings::StackChallenge::STANDARD,
+            );
             assert_eq!(
                 !table.is_empty(),
                 expect_recorded,
@@ -978,10 +1656,485 @@ mod tests {
         }
     }
 
+    /// A solo run's kept replay is a leaderboard submission: its seat,
+    /// with the high score it filed as the claim, survives the replay text
+    /// format and verifies.
+    #[test]
+    fn a_recorded_solo_run_verifies_as_its_high_score() {
+        let mut app = headless_session_app(solo_human(7));
+        tick_fixed(&mut app, 1); // spawn
+        // Hard drops on every other slice top the Marathon out in a few
+        // dozen pieces.
+        for i in 0..2000 {
+            {
+                let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+                if i % 2 == 0 {
+                    keys.press(KeyCode::Space);
+                } else {
+                    keys.release(KeyCode::Space);
+                    keys.clear();
+                }
+            }
+            tick_fixed(&mut app, 1);
+            if app.world().get_resource::<SessionOutcome>().is_some() {
+                break;
+            }
+        }
+        {
+            let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            keys.release(KeyCode::Space);
+            keys.clear();
+        }
+        app.update();
+
+        let marathon = crate::variant::Variant::Marathon;
+        let scores = app.world().resource::<crate::high_scores::HighScores>();
+        let filed = scores.table(marathon, crate::settings::StackChallenge::STANDARD)[0];
+        let library = app.world().resource::<crate::replays::ReplayLibrary>();
+        let kept = library.personal_best(marathon).expect("the run is the PB");
+        let claim = tetr_verify::Claim {
+            score: filed.score,
+            lines: filed.lines,
+            level: filed.level,
+            time_seconds: filed.time_seconds,
+        };
+        let submission =
+            tetr_verify::Replay::from_recording(marathon.rules(), &kept.seats[0].game, claim)
+                .expect("a solo seat converts");
+        let submission = tetr_verify::Replay::parse(&submission.to_text()).expect("it parses");
+        let outcome = tetr_verify::verify(&submission).expect("the run verifies");
+        assert_eq!(outcome.finish, tetr_verify::Finish::ToppedOut);
+        assert!(outcome.score > 0, "the drops scored");
+    }
+
+    /// A run under a stack challenge files on that challenge's board, not
+    /// the standard one, and leaves the variant's PB replay alone.
+    #[test]
+    fn an_invisible_run_ranks_on_its_own_board() {
+        use crate::settings::{StackChallenge, StackVisibility};
+        let mut app = unstarted_session_app(solo_human(7));
+        app.world_mut()
+            .resource_mut::<crate::settings::GameSettings>()
+            .stack_visibility = StackVisibility::Invisible;
+        enter_session(&mut app);
+        app.world_mut()
+            .resource_mut::<NextState<SessionPhase>>()
+            .set(SessionPhase::Running);
+        app.update();
+        let invisible = StackChallenge::new(StackVisibility::Invisible, false);
+        assert_eq!(app.world().resource::<SessionChallenge>().0, invisible);
+
+        tick_fixed(&mut app, 5);
+        app.world_mut()
+            .resource_mut::<NextState<SessionPhase>>()
+            .set(SessionPhase::Over);
+        app.update();
+
+        let marathon = crate::variant::Variant::Marathon;
+        let scores = app.world().resource::<crate::high_scores::HighScores>();
+        assert!(scores.table(marathon, StackChallenge::STANDARD).is_empty());
+        assert_eq!(scores.table(marathon, invisible).len(), 1);
+        let library = app.world().resource::<crate::replays::ReplayLibrary>();
+        assert!(library.personal_best(marathon).is_none());
+    }
+
+    /// A daily run plays the day's deal on the standard stack, spends the
+    /// official attempt as it is dealt, files on the day's board rather than
+    /// the variant's, and its retry is practice on the same deal.
+    #[test]
+    fn a_daily_run_spends_its_official_attempt_on_the_days_deal() {
+        use crate::daily::{DailyAttempt, DailyBook, DailyChallenge, Day};
+        use crate::settings::{StackChallenge, StackVisibility};
+        let challenge = DailyChallenge::for_day(Day(20_744), 4);
+        let mut app = unstarted_session_app(SessionConfig {
+            mode: challenge.rules.mode(),
+            ..solo_human(7)
+        });
+        app.world_mut()
+            .resource_mut::<crate::settings::GameSettings>()
+            .stack_visibility = StackVisibility::Invisible;
+        app.world_mut().insert_resource(DailyAttempt {
+            challenge,
+            official: true,
+        });
+        enter_session(&mut app);
+        let finish = |app: &mut App| {
+            app.world_mut()
+                .resource_mut::<NextState<SessionPhase>>()
+                .set(SessionPhase::Running);
+            app.update();
+            let seed = app
+                .world_mut()
+                .query::<&analysis::SeatRecording>()
+                .single(app.world())
+                .unwrap()
+                .0
+                .seed;
+            assert_eq!(seed, challenge.seed, "the day's deal");
+            tick_fixed(app, 5);
+            app.world_mut()
+                .resource_mut::<NextState<SessionPhase>>()
+                .set(SessionPhase::Over);
+            app.update();
+            app.world().resource::<DailyRecorded>().entry
+        };
+        assert_eq!(
+            app.world().resource::<SessionChallenge>().0,
+            StackChallenge::STANDARD
+        );
+        assert!(
+            !app.world()
+                .resource::<DailyBook>()
+                .official_open(challenge.day)
+        );
+        assert!(finish(&mut app).official);
+        assert!(!app.world().resource::<DailyAttempt>().official);
+
+        restart_match(app.world_mut());
+        assert!(!finish(&mut app).official, "the retry is practice");
+        let book = app.world().resource::<DailyBook>();
+        let day = book.day(challenge.day).unwrap();
+        assert_eq!(day.board().len(), 2);
+        assert!(day.official().is_some());
+        let scores = app.world().resource::<crate::high_scores::HighScores>();
+        assert!(
+            scores
+                .table(crate::variant::Variant::Sprint, StackChallenge::STANDARD)
+                .is_empty()
+        );
+    }
+
+    fn puzzle_human(index: usize) -> SessionConfig {
+        SessionConfig {
+            mode: SessionMode::Puzzle { index },
+            ..solo_human(0)
+        }
+    }
+
+    /// Tick until the session's outcome is settled (or the budget runs out).
+    fn play_out(app: &mut App, max_slices: u32) -> Option<SessionOutcome> {
+        for _ in 0..max_slices / 10 {
+            tick_fixed(app, 10);
+            if let Some(outcome) = app.world().get_resource::<SessionOutcome>() {
+                return Some(*outcome);
+            }
+        }
+        None
+    }
+
+    /// A puzzle plays its scripted start, ends when its one piece is spent
+    /// without meeting the goal, and is never kept as a replay.
+    #[test]
+    fn a_puzzle_fails_when_its_budget_is_spent() {
+        let mut app = headless_session_app(puzzle_human(0));
+        let snapshot = snapshots(&mut app)[0].1.clone();
+        assert_eq!(snapshot.board_cells.len(), 43, "the well board");
+
+        // Neutral frames: the I falls flat and locks without clearing.
+        assert_eq!(
+            play_out(&mut app, 3_000),
+            Some(SessionOutcome::Puzzle { solved: false })
+        );
+        app.update();
+        let progress = app.world().resource::<crate::puzzle::PuzzleProgress>();
+        assert!(!progress.is_solved("tetris-well"));
+        let library = app.world().resource::<crate::replays::ReplayLibrary>();
+        assert!(library.listing().is_empty());
+    }
+
+    /// Meeting the goal solves the puzzle and ticks it off.
+    #[test]
+    fn a_solved_puzzle_is_ticked_off() {
+        let mut app = headless_session_app(puzzle_human(0));
+        tick_fixed(&mut app, 1); // spawn
+        {
+            // Plug the well: whatever the I does, its lock clears four rows.
+            let mut seats = app.world_mut().query::<&mut SeatEngine>();
+            let mut engine = seats.iter_mut(app.world_mut()).next().unwrap();
+            for y in 0..4 {
+                engine.0.set_cell(9, y, crate::engine::CellKind::Garbage);
+            }
+        }
+        assert_eq!(
+            play_out(&mut app, 3_000),
+            Some(SessionOutcome::Puzzle { solved: true })
+        );
+        app.update();
+        let progress = app.world().resource::<crate::puzzle::PuzzleProgress>();
+        assert!(progress.is_solved("tetris-well"));
+    }
+
+    /// Practice: Backspace takes a placement back (the queue position with
+    /// it), and a run that tops out files no score and keeps no replay.
+    #[test]
+    fn practice_undoes_placements_and_never_ranks() {
+        let mut app = headless_session_app(SessionConfig {
+            mode: SessionMode::Practice { edited: false },
+            ..solo_human(3)
+        });
+        let opening = snapshots(&mut app)[0].1.clone();
+        let press = |app: &mut App, key: KeyCode| {
+            app.world_mut()
+                .resource_mut::<ButtonInput<KeyCode>>()
+                .press(key);
+            tick_fixed(app, 1);
+            let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+            keys.release(key);
+            keys.clear();
+            tick_fixed(app, 1);
+        };
+        press(&mut app, KeyCode::Space);
+        assert_ne!(snapshots(&mut app)[0].1.board_cells, opening.board_cells);
+        press(&mut app, KeyCode::Backspace);
+        // The neutral slice after the key respawned the same first piece,
+        // with the same preview behind it.
+        let undone = snapshots(&mut app)[0].1.clone();
+        assert!(undone.board_cells.is_empty());
+        assert_eq!(
+            undone.active.map(|active| active.piece_type),
+            Some(opening.next_queue[0])
+        );
+        let kept = opening.next_queue.len() - 1;
+        assert_eq!(undone.next_queue[..kept], opening.next_queue[1..]);
+
+        {
+            let mut seats = app.world_mut().query::<&mut SeatEngine>();
+            let mut engine = seats.iter_mut(app.world_mut()).next().unwrap();
+            engine.0.queue_garbage(48);
+        }
+        for _ in 0..120 {
+            press(&mut app, KeyCode::Space);
+            if app.world().get_resource::<SessionOutcome>().is_some() {
+                break;
+            }
+        }
+        assert_eq!(
+            app.world().get_resource::<SessionOutcome>(),
+            Some(&SessionOutcome::Practice)
+        );
+        app.update();
+        let scores = app.world().resource::<crate::high_scores::HighScores>();
+        for variant in crate::variant::Variant::ALL {
+            for stack in crate::settings::StackChallenge::ALL {
+                assert!(scores.table(variant, stack).is_empty());
+            }
+        }
+        let library = app.world().resource::<crate::replays::ReplayLibrary>();
+        assert!(library.listing().is_empty());
+    }
+
+    /// Practice's freeze holds the piece where it spawned: no gravity, no
+    /// lock delay, however long the player thinks.
+    #[test]
+    fn practice_freeze_stops_gravity() {
+        let mut app = headless_session_app(SessionConfig {
+            mode: SessionMode::Practice { edited: false },
+            ..solo_human(3)
+        });
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyF);
+        tick_fixed(&mut app, 1);
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .clear();
+        let spawned = snapshots(&mut app)[0].1.active.clone().unwrap();
+        tick_fixed(&mut app, 600);
+        let held = snapshots(&mut app)[0].1.clone();
+        assert_eq!(held.active.unwrap().origin, spawned.origin);
+        assert!(held.board_cells.is_empty());
+    }
+
+    /// An edited practice session starts from the editor's position: its
+    /// board, its hold piece and its queue first.
+    #[test]
+    fn edited_practice_starts_from_the_editor_position() {
+        let mut app = unstarted_session_app(SessionConfig {
+            mode: SessionMode::Practice { edited: true },
+            ..solo_human(3)
+        });
+        {
+            let mut position = app.world_mut().resource_mut::<EditorPosition>();
+            position.set(0, 0, Some(CellKind::Garbage));
+            position.set(1, 0, Some(CellKind::Some(PieceType::T)));
+            position.hold = Some(PieceType::O);
+            position.queue = vec![PieceType::Z, PieceType::S];
+        }
+        enter_session(&mut app);
+        let opening = snapshots(&mut app)[0].1.clone();
+        assert_eq!(opening.board_cells.len(), 2);
+        assert!(
+            opening
+                .board_cells
+                .iter()
+                .any(|cell| (cell.x, cell.y) == (0, 0) && cell.garbage)
+        );
+        assert_eq!(opening.hold, Some(PieceType::O));
+        // Nothing has spawned before the first step: the queue leads.
+        assert_eq!(opening.next_queue[..2], [PieceType::Z, PieceType::S]);
+    }
+
+    /// A finished versus match is kept as a replay of both seats, and playing
+    /// it back on the session's boards lands on the final position the live
+    /// match ended on. A first-place human solo run becomes the variant's PB.
+    #[test]
+    fn every_finished_match_is_kept_and_plays_back() {
+        let config = bot_match(11);
+        let mut app = headless_session_app(config);
+        tick_fixed(&mut app, 900);
+        let live = snapshots(&mut app);
+        app.world_mut()
+            .resource_mut::<NextState<SessionPhase>>()
+            .set(SessionPhase::Over);
+        app.update();
+
+        let library = app.world().resource::<crate::replays::ReplayLibrary>();
+        let [(id, crate::replays::ReplaySlot::Recent)] = library.listing()[..] else {
+            panic!("one recent game, no PB: {:?}", library.listing());
+        };
+        let replay = library.get(id).expect("stored").clone();
+        assert_eq!(replay.seats.len(), 2);
+        assert!(
+            replay
+                .seats
+                .iter()
+                .all(|seat| seat.game.inputs.len() == 900)
+        );
+
+        // Back out, then watch it the way the Replays screen starts it.
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Replays);
+        app.update();
+        let mut seating = *app.world().resource::<SessionConfig>();
+        let playback = replay::SessionPlayback::begin(replay, &mut seating);
+        app.insert_resource(seating).insert_resource(playback);
+        enter_session(&mut app);
+        app.update(); // the skipped countdown hands off to Running
+        assert_eq!(
+            app.world().resource::<State<SessionPhase>>().get(),
+            &SessionPhase::Running
+        );
+        tick_fixed(&mut app, 300);
+        {
+            let playback = app.world().resource::<replay::SessionPlayback>();
+            assert_eq!(playback.playhead.cursor(), 300, "1× plays a slice a slice");
+        }
+        app.world_mut()
+            .resource_mut::<replay::SessionPlayback>()
+            .playhead
+            .seek(900);
+        tick_fixed(&mut app, 1); // publish (the end stops the clock)
+        assert!(
+            snapshots(&mut app) == live,
+            "the replay ends where the match did"
+        );
+        assert!(!app.world().resource::<replay::SessionPlayback>().playing);
+
+        // Leaving playback puts the menus' seating back.
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::Replays);
+        app.update();
+        assert!(!app.world().contains_resource::<replay::SessionPlayback>());
+        assert_eq!(app.world().resource::<SessionConfig>().seed, Some(11));
+
+        let mut app = headless_session_app(solo_human(7));
+        tick_fixed(&mut app, 5);
+        app.world_mut()
+            .resource_mut::<NextState<SessionPhase>>()
+            .set(SessionPhase::Over);
+        app.update();
+        let library = app.world().resource::<crate::replays::ReplayLibrary>();
+        let best = library
+            .personal_best(crate::variant::Variant::Marathon)
+            .expect("the run is the PB");
+        let scores = app.world().resource::<crate::high_scores::HighScores>();
+        let linked = scores
+            .best_replay(crate::variant::Variant::Marathon)
+            .and_then(|id| library.get(id))
+            .expect("the new best high score carries its replay");
+        assert!(std::ptr::eq(linked, best));
+    }
+
+    #[test]
+    fn a_sprint_races_the_replay_on_its_best_score() {
+        let sprint = crate::variant::Variant::Sprint;
+        let mut app = unstarted_session_app(SessionConfig {
+            mode: SessionMode::Solo { variant: sprint },
+            ..solo_human(5)
+        });
+        // A PB that only ever idles: enough to pace the ghost.
+        let rules = crate::engine::EngineConfig::default();
+        let mut game = RecordedGame::new(rules, 9);
+        for _ in 0..600 {
+            game.push_frame(crate::engine::InputFrame {
+                dt_seconds: SIM_DT_SECONDS,
+                ..default()
+            });
+        }
+        let replay = crate::replays::GameReplay {
+            mode: SessionMode::Solo { variant: sprint },
+            summary: "Sprint".into(),
+            seats: vec![crate::replays::SeatReplay {
+                participant: Participant::Human,
+                game,
+            }],
+        };
+        let id = app
+            .world_mut()
+            .resource_mut::<crate::replays::ReplayLibrary>()
+            .add(replay, Some(sprint))
+            .added;
+        let mut scores = app
+            .world_mut()
+            .resource_mut::<crate::high_scores::HighScores>();
+        scores.insert(
+            sprint,
+            crate::high_scores::HighScore {
+                score: 0,
+                time_seconds: 10.0,
+                lines: 40,
+                level: 1,
+                stack: crate::settings::StackChallenge::STANDARD,
+                replay: None,
+            },
+        );
+        scores.set_best_replay(sprint, id);
+
+        enter_session(&mut app);
+        assert!(app.world().contains_resource::<ghost::SprintGhost>());
+        app.world_mut()
+            .resource_mut::<NextState<SessionPhase>>()
+            .set(SessionPhase::Running);
+        app.update();
+        tick_fixed(&mut app, 30);
+        assert_eq!(
+            app.world().resource::<ghost::SprintGhost>().slices(),
+            30,
+            "the ghost plays one frame per live slice"
+        );
+
+        // A retry races the PB from its first frame again.
+        app.world_mut().insert_resource(overlay::RematchRequested);
+        app.update();
+        app.update();
+        assert_eq!(app.world().resource::<ghost::SprintGhost>().slices(), 0);
+    }
+
     fn bot_match(seed: u64) -> SessionConfig {
         SessionConfig {
             // Greedy DT-20 on both seats: fast and deterministic.
-            seats: [Participant::Bot { model: 0 }, Participant::Bot { model: 0 }],
+            seats: [
+                Participant::Bot {
+                    model: 0,
+                    skill: SkillPreset::Skilled.into(),
+                },
+                Participant::Bot {
+                    model: 0,
+                    skill: SkillPreset::Skilled.into(),
+                },
+            ],
             mode: SessionMode::Versus,
             seed: Some(seed),
         }
@@ -1084,6 +2237,42 @@ mod tests {
         );
     }
 
+    /// Two humans on one keyboard: each seat latches only its own keymap, so
+    /// Player 2's hold key holds on the right board and Player 1's on the left.
+    #[test]
+    fn two_human_seats_latch_their_own_keys() {
+        let config = SessionConfig {
+            seats: [Participant::Human, Participant::Human],
+            mode: SessionMode::Versus,
+            seed: Some(7),
+        };
+        assert_eq!(
+            [config.keymap_for(0), config.keymap_for(1)],
+            [Keymap::P1, Keymap::P2]
+        );
+        assert_eq!(solo_human(7).keymap_for(0), Keymap::P1);
+        let mut app = headless_session_app(config);
+        tick_fixed(&mut app, 1); // spawn the first pieces
+
+        let held = |app: &mut App| -> Vec<bool> {
+            snapshots(app)
+                .into_iter()
+                .map(|(_, snap)| snap.hold.is_some())
+                .collect()
+        };
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyP); // Player 2's default Hold bind
+        tick_fixed(&mut app, 2);
+        assert_eq!(held(&mut app), [false, true], "P2's key drives seat 1 only");
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::ShiftLeft); // Player 1's default Hold bind
+        tick_fixed(&mut app, 2);
+        assert_eq!(held(&mut app), [true, true], "P1's key drives seat 0");
+    }
+
     #[test]
     fn setup_seats_two_engines_with_the_same_deal() {
         let mut app = headless_session_app(bot_match(7));
@@ -1218,8 +2407,8 @@ mod tests {
 
     #[test]
     fn the_engine_queue_survives_the_bots_blinded_poll() {
-        // The blinding is a strip on the snapshot handed to the bot; the
-        // engine-side queue must remain intact (it still rises by rule, and
+        // The blinding (a weaker preset's handicap) is a strip on the snapshot
+        // the bot reads; the engine-side queue must remain intact (it still rises by rule, and
         // the UI's meter renders from the published snapshot).
         let mut app = headless_session_app(bot_match(7));
         {
@@ -1395,4 +2584,238 @@ mod tests {
         let seats = app.world_mut().query::<&Seat>().iter(app.world()).count();
         assert_eq!(seats, 0, "seat entities are state-scoped");
     }
+
+    /// Both peers of one room on a real loopback relay, each a full headless
+    /// host: start the room, and hand each app its session the way the lobby
+    /// does. Returns the apps in seat order.
+    fn online_pair(room: &str) -> Vec<App> {
+        let relay = tetr_net::Relay::bind("127.0.0.1:0")
+            .unwrap()
+            .with_seed(0x5EED_0033);
+        let addr = relay.local_addr().unwrap();
+        relay.spawn();
+        let net = tetr_net::NetConfig {
+            input_delay: 2,
+            hash_interval: 30,
+            timeout: Duration::from_secs(10),
+            start_timeout: Some(Duration::from_secs(10)),
+        };
+        let lobbies: Vec<tetr_net::Lobby> = (0..2)
+            .map(|_| tetr_net::Lobby::join(addr, room, net).expect("join"))
+            .collect();
+        for lobby in &lobbies {
+            lobby.set_ready(true);
+        }
+        lobbies
+            .iter()
+            .map(|lobby| {
+                let session = lobby.wait_started(net.start_timeout).expect("start");
+                let mut seats = [Participant::Remote; 2];
+                seats[session.seat() as usize] = Participant::Human;
+                let mut app = unstarted_session_app(SessionConfig {
+                    seats,
+                    mode: SessionMode::Versus,
+                    seed: None,
+                });
+                app.insert_resource(online::OnlineMatch::new(session));
+                enter_session(&mut app);
+                app
+            })
+            .collect()
+    }
+
+    fn online_tick(app: &App) -> u32 {
+        app.world().resource::<online::OnlineMatch>().tick()
+    }
+
+    /// Step both peers one slice at a time until each has released `target`
+    /// ticks (a peer whose rival's frame is still in flight just idles).
+    fn run_online_to(apps: &mut [App], target: u32) {
+        let deadline = std::time::Instant::now() + Duration::from_secs(20);
+        while apps.iter().any(|app| online_tick(app) < target) {
+            for app in apps.iter_mut() {
+                if online_tick(app) < target {
+                    tick_fixed(app, 1);
+                }
+            }
+            assert!(std::time::Instant::now() < deadline, "the peers stalled");
+            std::thread::yield_now();
+        }
+    }
+
+    /// [`run_online_to`] `n` ticks past the peer that is furthest along.
+    fn run_online_by(apps: &mut [App], n: u32) {
+        let target = apps.iter().map(online_tick).max().unwrap_or(0) + n;
+        run_online_to(apps, target);
+    }
+
+    fn phase(app: &App) -> SessionPhase {
+        app.world().resource::<State<SessionPhase>>().get().clone()
+    }
+
+    /// The countdown starts both peers on the same tick, the local human's
+    /// presses reach the rival's copy, and both copies stay identical.
+    #[test]
+    fn online_peers_start_together_and_play_the_same_match() {
+        let mut apps = online_pair("3301");
+        for app in &apps {
+            assert_eq!(phase(app), SessionPhase::Countdown);
+            assert_eq!(
+                app.world().resource::<online::OnlineMatch>().seed(),
+                0x5EED_0033,
+                "the relay's seed deals the first match"
+            );
+        }
+        // Find the tick each peer's shared phase turned Running.
+        let mut started = [None; 2];
+        while started.contains(&None) {
+            run_online_by(&mut apps, 1);
+            for (i, app) in apps.iter().enumerate() {
+                let online = app.world().resource::<online::OnlineMatch>();
+                if started[i].is_none() && online.control().phase() == online::OnlinePhase::Running
+                {
+                    started[i] = Some(online.tick());
+                }
+            }
+        }
+        assert_eq!(
+            started[0], started[1],
+            "the countdown ends on the same tick"
+        );
+        for app in &mut apps {
+            app.update();
+            assert_eq!(phase(app), SessionPhase::Running);
+        }
+
+        // Seat 0 hard-drops every few ticks; seat 1 sits still.
+        let base = online_tick(&apps[0]);
+        for i in 0..40 {
+            {
+                let mut keys = apps[0].world_mut().resource_mut::<ButtonInput<KeyCode>>();
+                if i % 2 == 0 {
+                    keys.press(KeyCode::Space);
+                } else {
+                    keys.release(KeyCode::Space);
+                    keys.clear();
+                }
+            }
+            run_online_to(&mut apps, base + (i + 1) * 4);
+        }
+        run_online_to(&mut apps, base + 200);
+        let a = snapshots(&mut apps[0]);
+        let b = snapshots(&mut apps[1]);
+        assert!(
+            a[0].1.board_cells.len() > 20,
+            "seat 0's drops must land on its board"
+        );
+        assert_eq!(a, b, "the peers' copies of the match diverged");
+    }
+
+    /// Either peer pauses both on the same tick; nothing steps while held,
+    /// and the resume is shared too.
+    #[test]
+    fn an_online_pause_holds_both_peers() {
+        let mut apps = online_pair("3302");
+        run_online_to(&mut apps, 200); // past the countdown
+        apps[1]
+            .world_mut()
+            .resource_mut::<online::OnlineMatch>()
+            .request(tetr_net::Command::Pause);
+        let mut paused = [None; 2];
+        while paused.contains(&None) {
+            run_online_by(&mut apps, 1);
+            for (i, app) in apps.iter().enumerate() {
+                let online = app.world().resource::<online::OnlineMatch>();
+                if paused[i].is_none() && online.control().phase() == online::OnlinePhase::Paused {
+                    paused[i] = Some(online.tick());
+                }
+            }
+        }
+        assert_eq!(paused[0], paused[1], "the pause lands on the same tick");
+        for app in &mut apps {
+            app.update();
+            assert_eq!(phase(app), SessionPhase::Paused);
+        }
+        let held = snapshots(&mut apps[0]);
+        run_online_by(&mut apps, 60);
+        assert_eq!(snapshots(&mut apps[0]), held, "a paused match holds");
+
+        apps[0]
+            .world_mut()
+            .resource_mut::<online::OnlineMatch>()
+            .request(tetr_net::Command::Resume);
+        run_online_by(&mut apps, 10);
+        for app in &mut apps {
+            app.update();
+            assert_eq!(phase(app), SessionPhase::Running);
+        }
+        assert_eq!(snapshots(&mut apps[0]), snapshots(&mut apps[1]));
+    }
+
+    /// A rematch needs both votes; then both peers deal the same fresh match
+    /// and count down together.
+    #[test]
+    fn an_online_rematch_is_dealt_alike_on_both_peers() {
+        let mut apps = online_pair("3304");
+        run_online_to(&mut apps, 240);
+        // End the match on both peers at the same tick (a real top-out is
+        // deterministic, so it always does).
+        for app in &mut apps {
+            app.world_mut()
+                .insert_resource(SessionOutcome::Versus { winner: Some(0) });
+        }
+        let before = snapshots(&mut apps[0]);
+        run_online_by(&mut apps, 5);
+        apps[0]
+            .world_mut()
+            .resource_mut::<online::OnlineMatch>()
+            .request(tetr_net::Command::Rematch);
+        run_online_by(&mut apps, 30);
+        for app in &apps {
+            let online = app.world().resource::<online::OnlineMatch>();
+            assert_eq!(online.control().phase(), online::OnlinePhase::Over);
+            assert!(online.control().voted(0) && !online.control().voted(1));
+        }
+        apps[1]
+            .world_mut()
+            .resource_mut::<online::OnlineMatch>()
+            .request(tetr_net::Command::Rematch);
+        run_online_by(&mut apps, 30);
+        for app in &mut apps {
+            app.update();
+            let online = app.world().resource::<online::OnlineMatch>();
+            assert_eq!(online.control().rematches(), 1);
+            assert_ne!(online.seed(), 0x5EED_0033, "a rematch is a new deal");
+            assert_eq!(phase(app), SessionPhase::Countdown);
+            assert!(app.world().get_resource::<SessionOutcome>().is_none());
+        }
+        let a = snapshots(&mut apps[0]);
+        assert_ne!(a, before);
+        assert_eq!(a, snapshots(&mut apps[1]));
+        // And the new match runs in step.
+        run_online_by(&mut apps, 300);
+        assert_eq!(snapshots(&mut apps[0]), snapshots(&mut apps[1]));
+    }
+
+    /// A peer that leaves ends the other's match as disconnected, with no
+    /// winner scored.
+    #[test]
+    fn a_departed_peer_ends_the_match_as_disconnected() {
+        let mut apps = online_pair("3303");
+        run_online_to(&mut apps, 200);
+        drop(apps.pop());
+        let mut app = apps.pop().unwrap();
+        let deadline = std::time::Instant::now() + Duration::from_secs(20);
+        while app.world().get_resource::<SessionOutcome>().is_none() {
+            tick_fixed(&mut app, 1);
+            assert!(std::time::Instant::now() < deadline, "no disconnect seen");
+            std::thread::sleep(Duration::from_millis(1));
+        }
+        assert_eq!(
+            *app.world().resource::<SessionOutcome>(),
+            SessionOutcome::Disconnected
+        );
+        app.update();
+        assert_eq!(phase(&app), SessionPhase::Over);
+    }
 }
@@ -0,0 +1,379 @@
+//! Online versus: a [`Participant::Remote`](super::Participant::Remote) seat
+//! driven through a tetr-net [`LockstepClock`].
+//!
+//! The lobby (`screens::lobby`) hands the session a started
+//! [`NetSession`](tetr_net::NetSession) wrapped in an [`OnlineMatch`]. From
+//! then on the clock, not the local [`SessionPhase`], is the authority on
+//! time: every fixed slice [`online_exchange`] sends the local seat's frame
+//! (with any pending [`Command`]) and, once every peer's frame for the next
+//! tick is in, stages one frame per seat for `session_step` to consume. A
+//! tick that is not released yet stages nothing, and the step idles.
+//!
+//! Everything that has to happen on both peers at once rides the same
+//! released ticks: pause, resume and rematch are commands, and
+//! [`MatchControl`] folds them into the match phase tick by tick — so the
+//! countdown, a pause and a rematch's fresh deal begin on the same tick
+//! everywhere. The local `SessionPhase` only mirrors that phase for the
+//! overlays. A lost connection ends the match as
+//! [`SessionOutcome::Disconnected`].
+
+use bevy::prelude::*;
+use tetr_net::{Command, LockstepClock, NetError, NetSession};
+
+use super::{HumanSeat, Seat, SeatSnapshot, SessionOutcome, SessionPhase};
+use crate::GameState;
+use crate::engine::InputFrame;
+use crate::level::engine_bridge::{SIM_DT_SECONDS, SIM_HZ};
+
+/// Countdown length in ticks: the overlay's 3-2-1-GO, counted on the shared
+/// clock instead of each peer's wall clock.
+const COUNTDOWN_TICKS: u32 = (2.6 * SIM_HZ) as u32;
+
+pub struct SessionOnlinePlugin;
+
+impl Plugin for SessionOnlinePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (
+                online_exchange.before(super::session_step),
+                report_hash.after(super::session_step),
+            )
+                .run_if(resource_exists::<OnlineMatch>),
+        )
+        .add_systems(OnExit(GameState::Session), end_online_match);
+    }
+}
+
+/// Where the shared match is, as every peer sees it on the same tick.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OnlinePhase {
+    Countdown,
+    Running,
+    Paused,
+    Over,
+}
+
+/// What one released tick means for the host.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TickPlan {
+    /// Step the boards this tick.
+    pub run: bool,
+    /// The phase changed on this tick.
+    pub entered: Option<OnlinePhase>,
+    /// Every seat voted for a rematch: deal a fresh match now.
+    pub rematch: bool,
+}
+
+/// The match phase as a pure function of the released ticks. Both peers feed
+/// it the same ticks, commands and (deterministic) match end, so both always
+/// agree on it — no extra handshake.
+#[derive(Clone, Debug)]
+pub struct MatchControl {
+    phase: OnlinePhase,
+    /// The tick the current countdown began on.
+    countdown_from: u32,
+    /// Per seat: voted for a rematch since the match ended.
+    votes: Vec<bool>,
+    rematches: u32,
+}
+
+impl MatchControl {
+    pub fn new(players: usize) -> Self {
+        Self {
+            phase: OnlinePhase::Countdown,
+            countdown_from: 0,
+            votes: vec![false; players],
+            rematches: 0,
+        }
+    }
+
+    pub fn phase(&self) -> OnlinePhase {
+        self.phase
+    }
+
+    /// Matches dealt after the first.
+    pub fn rematches(&self) -> u32 {
+        self.rematches
+    }
+
+    pub fn voted(&self, seat: usize) -> bool {
+        self.votes.get(seat).copied().unwrap_or(false)
+    }
+
+    /// Ticks into the current countdown at `tick`.
+    pub fn countdown_elapsed(&self, tick: u32) -> u32 {
+        tick.saturating_sub(self.countdown_from)
+    }
+
+    /// Apply one released tick: its commands, and whether the match has
+    /// been decided (`over`) by the steps before it.
+    pub fn on_tick(&mut self, tick: u32, commands: &[(u8, Command)], over: bool) -> TickPlan {
+        let mut plan = TickPlan::default();
+        let sent = |command| commands.iter().any(|&(_, c)| c == command);
+        let before = self.phase;
+        match self.phase {
+            OnlinePhase::Countdown => {
+                if self.countdown_elapsed(tick) >= COUNTDOWN_TICKS {
+                    self.phase = OnlinePhase::Running;
+                }
+            }
+            OnlinePhase::Running => {
+                if over {
+                    self.phase = OnlinePhase::Over;
+                } else if sent(Command::Pause) {
+                    self.phase = OnlinePhase::Paused;
+                }
+            }
+            OnlinePhase::Paused => {
+                if sent(Command::Resume) {
+                    self.phase = OnlinePhase::Running;
+                }
+            }
+            OnlinePhase::Over => {
+                for &(seat, command) in commands {
+                    if command == Command::Rematch
+                        && let Some(vote) = self.votes.get_mut(seat as usize)
+                    {
+                        *vote = true;
+                    }
+                }
+                if self.votes.iter().all(|&v| v) {
+                    self.votes.fill(false);
+                    self.rematches += 1;
+                    self.countdown_from = tick;
+                    self.phase = OnlinePhase::Countdown;
+                    plan.rematch = true;
+                }
+            }
+        }
+        if self.phase != before {
+            plan.entered = Some(self.phase);
+        }
+        plan.run = self.phase == OnlinePhase::Running;
+        plan
+    }
+}
+
+/// A live online match: the lockstep clock plus the phase it drives.
+/// Inserted by the lobby before entering [`GameState::Session`]; removed
+/// (which hangs up) when the session ends.
+#[derive(Resource)]
+pub struct OnlineMatch {
+    clock: LockstepClock,
+    control: MatchControl,
+    /// Sent with the local seat's next frame.
+    pending: Option<Command>,
+    /// The tick the last `online_exchange` released, if it stepped boards.
+    stepped: Option<u32>,
+    failure: Option<NetError>,
+}
+
+impl OnlineMatch {
+    pub fn new(session: NetSession) -> Self {
+        let players = session.players() as usize;
+        Self {
+            clock: LockstepClock::new(session),
+            control: MatchControl::new(players),
+            pending: None,
+            stepped: None,
+            failure: None,
+        }
+    }
+
+    pub fn local_seat(&self) -> usize {
+        self.clock.session().seat() as usize
+    }
+
+    /// This deal's seed: the relay's for the first match, then a fresh one
+    /// per rematch that every peer derives alike.
+    pub fn seed(&self) -> u64 {
+        let base = self.clock.session().seed();
+        base ^ u64::from(self.control.rematches()).wrapping_mul(0x9E37_79B9_7F4A_7C15)
+    }
+
+    /// Ticks the shared clock has released.
+    pub fn tick(&self) -> u32 {
+        self.clock.tick()
+    }
+
+    pub fn control(&self) -> &MatchControl {
+        &self.control
+    }
+
+    /// Queue a pause, resume or rematch vote. It takes effect when its tick is
+    /// released — on every peer at once, this one included.
+    pub fn request(&mut self, command: Command) {
+        self.pending = Some(command);
+    }
+
+    /// Seconds into the current countdown, on the shared clock.
+    pub fn countdown_seconds(&self) -> f32 {
+        self.control.countdown_elapsed(self.tick()) as f32 * SIM_DT_SECONDS
+    }
+
+    /// Why the connection ended, once it has.
+    pub fn failure(&self) -> Option<&NetError> {
+        self.failure.as_ref()
+    }
+}
+
+/// A seat of an online match: the frame the lockstep clock released for it
+/// this slice, taken by `session_step`.
+#[derive(Component, Default)]
+pub struct NetSeat {
+    pub(super) staged: Option<InputFrame>,
+}
+
+/// Trade frames for one slice (see the module docs). The local human's frame
+/// is sampled only while the shared match runs; otherwise a neutral frame
+/// keeps the clock ticking through countdowns, pauses and the result banner.
+fn online_exchange(
+    mut online: ResMut<OnlineMatch>,
+    mut seats: Query<(&Seat, &SeatSnapshot, &mut NetSeat, Option<&mut HumanSeat>)>,
+    outcome: Option<Res<SessionOutcome>>,
+    mut commands: Commands,
+    mut next: ResMut<NextState<SessionPhase>>,
+) {
+    let online = &mut *online;
+    online.stepped = None;
+    for (.., mut net, _) in &mut seats {
+        net.staged = None;
+    }
+    if online.failure.is_some() {
+        return;
+    }
+    if online.clock.needs_input() {
+        let local = online.local_seat();
+        let running = online.control.phase() == OnlinePhase::Running;
+        let frame = seats
+            .iter_mut()
+            .find(|(seat, ..)| seat.index == local)
+            .and_then(|(_, snapshot, _, human)| {
+                human
+                    .filter(|_| running)
+                    .map(|mut h| h.next_frame(&snapshot.0))
+            })
+            .unwrap_or(InputFrame {
+                dt_seconds: SIM_DT_SECONDS,
+                ..InputFrame::default()
+            });
+        online.clock.submit(frame, online.pending.take());
+    }
+    let tick = match online.clock.advance() {
+        Ok(Some(tick)) => tick,
+        Ok(None) => return,
+        Err(err) => {
+            warn!("online match: {err}");
+            online.failure = Some(err);
+            if outcome.is_none() {
+                commands.insert_resource(SessionOutcome::Disconnected);
+                next.set(SessionPhase::Over);
+            }
+            return;
+        }
+    };
+    let plan = online
+        .control
+        .on_tick(tick.tick, &tick.commands, outcome.is_some());
+    if plan.rematch {
+        commands.insert_resource(super::overlay::RematchRequested);
+    }
+    // (`NextState`'s own `set_if_neq`, not the change-detection one.)
+    match plan.entered {
+        Some(OnlinePhase::Running) => NextState::set_if_neq(&mut next, SessionPhase::Running),
+        Some(OnlinePhase::Paused) => NextState::set_if_neq(&mut next, SessionPhase::Paused),
+        // The rematch resets the phase itself; `Over` is the step's call.
+        _ => {}
+    }
+    if plan.run {
+        online.stepped = Some(tick.tick);
+        for (seat, _, mut net, _) in &mut seats {
+            net.staged = tick.frames.get(seat.index).cloned();
+        }
+    }
+}
+
+/// After a stepped tick, report the boards' hash on the session's interval
+/// so the relay can catch a desync. Only stepped ticks count: a rematch is
+/// dealt between render frames, and a countdown tick may see either deal.
+fn report_hash(online: Res<OnlineMatch>, seats: Query<(&Seat, &SeatSnapshot)>) {
+    let Some(tick) = online.stepped else {
+        return;
+    };
+    let session = online.clock.session();
+    let interval = session.config().hash_interval;
+    if interval == 0 || !(tick + 1).is_multiple_of(interval) {
+        return;
+    }
+    let mut snapshots: Vec<_> = seats.iter().collect();
+    snapshots.sort_by_key(|(seat, _)| seat.index);
+    let hash = tetr_net::snapshots_hash(snapshots.iter().map(|(_, snapshot)| &snapshot.0));
+    session.report_hash(tick + 1, hash);
+}
+
+/// Leaving the session hangs up: dropping the session closes the socket, and
+/// the peer sees this seat leave.
+fn end_online_match(mut commands: Commands) {
+    commands.remove_resource::<OnlineMatch>();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run_to_running(control: &mut MatchControl) -> u32 {
+        let mut tick = 0;
+        while !control.on_tick(tick, &[], false).run {
+            tick += 1;
+        }
+        tick
+    }
+
+    #[test]
+    fn the_countdown_runs_on_ticks() {
+        let mut control = MatchControl::new(2);
+        let start = run_to_running(&mut control);
+        assert_eq!(start, COUNTDOWN_TICKS);
+        assert_eq!(control.phase(), OnlinePhase::Running);
+    }
+
+    #[test]
+    fn either_seat_pauses_and_resumes_the_match() {
+        let mut control = MatchControl::new(2);
+        let tick = run_to_running(&mut control) + 1;
+        let plan = control.on_tick(tick, &[(1, Command::Pause)], false);
+        assert_eq!(plan.entered, Some(OnlinePhase::Paused));
+        assert!(!plan.run);
+        assert!(!control.on_tick(tick + 1, &[], false).run);
+        let plan = control.on_tick(tick + 2, &[(0, Command::Resume)], false);
+        assert_eq!(plan.entered, Some(OnlinePhase::Running));
+        assert!(plan.run);
+    }
+
+    #[test]
+    fn a_rematch_needs_every_vote_and_restarts_the_countdown() {
+        let mut control = MatchControl::new(2);
+        let tick = run_to_running(&mut control) + 1;
+        let plan = control.on_tick(tick, &[], true);
+        assert_eq!(plan.entered, Some(OnlinePhase::Over));
+        // One vote is not enough, and a repeat vote counts once.
+        let plan = control.on_tick(tick + 1, &[(0, Command::Rematch)], true);
+        assert!(!plan.rematch);
+        assert!(control.voted(0) && !control.voted(1));
+        let plan = control.on_tick(tick + 2, &[(0, Command::Rematch)], true);
+        assert!(!plan.rematch);
+        let plan = control.on_tick(tick + 3, &[(1, Command::Rematch)], true);
+        assert!(plan.rematch);
+        assert_eq!(plan.entered, Some(OnlinePhase::Countdown));
+        assert_eq!(control.rematches(), 1);
+        assert!(!control.voted(0));
+        assert_eq!(control.countdown_elapsed(tick + 3), 0);
+        // The new deal runs after a full countdown from the vote's tick.
+        let mut t = tick + 4;
+        while !control.on_tick(t, &[], false).run {
+            t += 1;
+        }
+        assert_eq!(t, tick + 3 + COUNTDOWN_TICKS);
+    }
+}
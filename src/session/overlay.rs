@@ -5,19 +5,29 @@
 //! boards visible under a dim scrim — reading the losing stack is part of the
 //! result). Navigation reuses the shared `FocusList` idiom so these screens
 //! handle exactly like every other menu in the game.
+//!
+//! Online, the overlays only *ask*: pause, resume and rematch become commands
+//! on the [`OnlineMatch`], and the phase changes when the shared clock
+//! releases them — on both peers together. The countdown counts the shared
+//! clock's ticks for the same reason.
 
 use bevy::prelude::*;
 
 use crate::GameState;
 use crate::assets::GameAssets;
+use crate::features::touch::TouchFrame;
+use crate::settings::Keymap;
 use crate::ui::focus::{
-    FocusList, Focusable, NavAction, clicked_focusable, focus_navigation, read_nav_action,
+    FocusList, Focusable, MenuPad, NavAction, clicked_focusable, focus_navigation, read_nav_action,
 };
 use crate::ui::widgets::{label_text, menu_button, theme, title_text};
 
+use super::online::OnlineMatch;
+use super::replay::SessionPlayback;
 use super::{
     MatchClock, Participant, Seat, SeatStats, SessionConfig, SessionOutcome, SessionPhase,
 };
+use tetr_net::Command;
 
 /// Countdown pacing: three number beats, then a shorter "GO!".
 const NUMBER_BEAT_SECONDS: f32 = 0.7;
@@ -29,20 +39,31 @@ impl Plugin for SessionOverlayPlugin {
     fn build(&self, app: &mut App) {
         app
             // Countdown
-            .add_systems(OnEnter(SessionPhase::Countdown), spawn_countdown)
+            // A replay has no countdown and no pause menu: its viewer owns
+            // the keys (`session::replay`).
+            .add_systems(
+                OnEnter(SessionPhase::Countdown),
+                spawn_countdown.run_if(not(resource_exists::<SessionPlayback>)),
+            )
             .add_systems(
                 Update,
-                tick_countdown.run_if(in_state(SessionPhase::Countdown)),
+                tick_countdown.run_if(
+                    in_state(SessionPhase::Countdown).and(not(resource_exists::<SessionPlayback>)),
+                ),
             )
             // Pause (and the countdown's escape hatch: Esc before GO returns
             // to the setup screen — you can always leave a match).
             .add_systems(
                 Update,
-                pause_on_keybind.run_if(in_state(SessionPhase::Running)),
+                pause_on_keybind.run_if(
+                    in_state(SessionPhase::Running).and(not(resource_exists::<SessionPlayback>)),
+                ),
             )
             .add_systems(
                 Update,
-                countdown_escape.run_if(in_state(SessionPhase::Countdown)),
+                countdown_escape.run_if(
+                    in_state(SessionPhase::Countdown).and(not(resource_exists::<SessionPlayback>)),
+                ),
             )
             .add_systems(
                 OnEnter(SessionPhase::Paused),
@@ -56,10 +77,22 @@ impl Plugin for SessionOverlayPlugin {
                     .run_if(in_state(SessionPhase::Paused)),
             )
             // Result
-            .add_systems(OnEnter(SessionPhase::Over), spawn_result_banner)
+            .add_systems(
+                OnEnter(SessionPhase::Over),
+                (
+                    spawn_result_banner,
+                    spawn_daily_result
+                        .after(spawn_result_banner)
+                        .after(super::record_daily_run),
+                ),
+            )
             .add_systems(
                 Update,
-                (focus_navigation::<ResultRoot>, result_menu_activate)
+                (
+                    focus_navigation::<ResultRoot>,
+                    result_menu_activate,
+                    refresh_online_status.run_if(resource_exists::<OnlineMatch>),
+                )
                     .chain()
                     .run_if(in_state(SessionPhase::Over)),
             )
@@ -122,17 +155,24 @@ fn spawn_countdown(mut commands: Commands, assets: Res<GameAssets>) {
 
 /// Advance the 3-2-1-GO beats; hand the match to `Running` when they finish.
 /// Engines hold during the countdown (the step is `Running`-gated), so both
-/// first pieces spawn on the same slice after "GO!".
+/// first pieces spawn on the same slice after "GO!". Online, the beats follow
+/// the shared clock and the clock itself starts the match.
 fn tick_countdown(
     time: Res<Time>,
+    online: Option<Res<OnlineMatch>>,
     text: Single<(&mut CountdownText, &mut Text)>,
     mut next: ResMut<NextState<SessionPhase>>,
 ) {
     let (mut state, mut text) = text.into_inner();
-    state.elapsed += time.delta_secs();
+    state.elapsed = match &online {
+        Some(online) => online.countdown_seconds(),
+        None => state.elapsed + time.delta_secs(),
+    };
     let total = 3.0 * NUMBER_BEAT_SECONDS + GO_BEAT_SECONDS;
     if state.elapsed >= total {
-        next.set(SessionPhase::Running);
+        if online.is_none() {
+            next.set(SessionPhase::Running);
+        }
         return;
     }
     let label = match (state.elapsed / NUMBER_BEAT_SECONDS) as u32 {
@@ -149,10 +189,18 @@ fn tick_countdown(
 /// Esc during the countdown backs out to the setup screen. Pausing here would
 /// be wrong (entering `Paused` exits `Countdown`, despawning the count
 /// mid-beat), and being trapped for 2.6 s would be worse — the setup screen is
-/// where you were two seconds ago.
-fn countdown_escape(keys: Res<ButtonInput<KeyCode>>, mut next: ResMut<NextState<GameState>>) {
+/// where you were two seconds ago. Online, that is the lobby (leaving hangs
+/// up, so the rival's match ends too).
+fn countdown_escape(
+    keys: Res<ButtonInput<KeyCode>>,
+    online: Option<Res<OnlineMatch>>,
+    mut next: ResMut<NextState<GameState>>,
+) {
     if keys.just_pressed(KeyCode::Escape) {
-        next.set(GameState::SessionSetup);
+        next.set(match online {
+            Some(_) => GameState::Lobby,
+            None => GameState::SessionSetup,
+        });
     }
 }
 
@@ -170,16 +218,47 @@ enum PauseAction {
 }
 
 /// The player's pause keybind (Escape by default) freezes the whole match —
-/// pausing a local match is inherently mutual. Works in bot-vs-bot too.
+/// pausing a local match is inherently mutual, so with two humans either
+/// player's pause key works — as does any pad's pause button and the touch
+/// pause button. Works in
+/// bot-vs-bot too. Online it pauses both peers, once the request's tick is
+/// released.
 fn pause_on_keybind(
     keys: Res<ButtonInput<KeyCode>>,
+    pads: Query<&Gamepad>,
+    touch: Res<TouchFrame>,
     settings: Res<crate::settings::GameSettings>,
+    config: Res<SessionConfig>,
+    online: Option<ResMut<OnlineMatch>>,
     mut next: ResMut<NextState<SessionPhase>>,
 ) {
-    let (primary, secondary) = settings.keybinds.pause;
-    let pressed = keys.just_pressed(primary) || secondary.is_some_and(|key| keys.just_pressed(key));
+    let keymaps: &[Keymap] = if config.local_versus() {
+        &Keymap::ALL
+    } else {
+        &[Keymap::P1]
+    };
+    let pressed = keymaps.iter().any(|&keymap| {
+        let (primary, secondary) = settings.keymap(keymap).pause;
+        keys.just_pressed(primary) || secondary.is_some_and(|key| keys.just_pressed(key))
+    }) || {
+        let (primary, secondary) = settings.gamepad_binds.pause;
+        pads.iter().any(|pad| {
+            pad.just_pressed(primary) || secondary.is_some_and(|button| pad.just_pressed(button))
+        })
+    } || touch.input.pause_just_pressed;
     if pressed {
-        next.set(SessionPhase::Paused);
+        match online {
+            Some(mut online) => online.request(Command::Pause),
+            None => next.set(SessionPhase::Paused),
+        }
+    }
+}
+
+/// Resume the match: directly, or by asking the rival's peer too.
+fn resume(online: Option<&mut OnlineMatch>, next: &mut NextState<SessionPhase>) {
+    match online {
+        Some(online) => online.request(Command::Resume),
+        None => next.set(SessionPhase::Running),
     }
 }
 
@@ -231,26 +310,29 @@ fn spawn_pause_overlay(mut commands: Commands, assets: Res<GameAssets>) {
     commands.entity(root).add_children(&[resume, quit, hint]);
 }
 
+#[allow(clippy::too_many_arguments)] // a Bevy system's params are its dependency list
 fn pause_menu_activate(
     keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
     list: Single<&FocusList, With<PauseRoot>>,
     actions: Query<(&Focusable, &PauseAction)>,
     clicks: Query<(&Focusable, &Interaction), Changed<Interaction>>,
+    mut online: Option<ResMut<OnlineMatch>>,
     mut next_phase: ResMut<NextState<SessionPhase>>,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    let nav =
-        read_nav_action(&keys, *list).or_else(|| clicked_focusable(&clicks).map(NavAction::Select));
+    let nav = read_nav_action(&keys, &pad, *list)
+        .or_else(|| clicked_focusable(&clicks).map(NavAction::Select));
     match nav {
         // Esc toggles straight back into the match.
-        Some(NavAction::Back) => next_phase.set(SessionPhase::Running),
+        Some(NavAction::Back) => resume(online.as_deref_mut(), &mut next_phase),
         Some(NavAction::Select(index)) => {
             for (focusable, action) in &actions {
                 if focusable.index != index {
                     continue;
                 }
                 match action {
-                    PauseAction::Resume => next_phase.set(SessionPhase::Running),
+                    PauseAction::Resume => resume(online.as_deref_mut(), &mut next_phase),
                     PauseAction::Quit => next_state.set(GameState::MainMenu),
                 }
             }
@@ -272,17 +354,28 @@ enum ResultAction {
     Menu,
 }
 
+/// Online only: the rematch vote and the connection, under the summary.
+#[derive(Component)]
+struct OnlineStatus;
+
 /// Set by the result menu; consumed by [`apply_rematch`] (an exclusive system
 /// — `restart_match` rebuilds the seats in place). `pub(crate)` so the match
 /// tests can drive the exact path the Rematch button takes.
 #[derive(Resource)]
 pub(crate) struct RematchRequested;
 
-/// The seat's display name, as the HUD labels it.
-fn seat_label(config: &SessionConfig, registry: &crate::ai::ModelRegistry, seat: usize) -> String {
+/// The seat's display name, as the HUD labels it: "YOU", or the player's
+/// keymap ("P1"/"P2") when two humans share the keyboard.
+pub(super) fn seat_label(
+    config: &SessionConfig,
+    registry: &crate::ai::ModelRegistry,
+    seat: usize,
+) -> String {
     match config.seats[seat] {
+        Participant::Human if config.local_versus() => config.keymap_for(seat).label().to_string(),
         Participant::Human => "YOU".to_string(),
-        Participant::Bot { model } => registry.label(model).to_uppercase(),
+        Participant::Remote => "RIVAL".to_string(),
+        Participant::Bot { model, .. } => registry.label(model).to_uppercase(),
     }
 }
 
@@ -297,6 +390,12 @@ fn spawn_result_banner(
     seats: Query<(&Seat, &SeatStats)>,
     seats_snapshot: Query<&super::SeatSnapshot>,
     recorded: Option<Res<super::SoloRecorded>>,
+    minute_scores: Option<Res<super::MinuteScores>>,
+    challenge: Option<Res<super::SessionChallenge>>,
+    puzzles: Res<crate::puzzle::PuzzleCatalog>,
+    puzzle_run: Option<Res<crate::puzzle::PuzzleRun>>,
+    trainer: Option<Res<crate::openers::OpenerTrainer>>,
+    online: Option<Res<OnlineMatch>>,
 ) {
     // The banner reads the world it was raised over; a missing outcome (manual
     // state poke in a test) reads as a versus draw rather than a panic.
@@ -313,7 +412,13 @@ fn spawn_result_banner(
             let (title, color) = match winner {
                 None => ("DRAW".to_string(), theme::TEXT),
                 Some(seat) => match config.seats[seat] {
+                    // Two humans: name the winner rather than say "you".
+                    Participant::Human if config.local_versus() => (
+                        format!("{} WINS", seat_label(&config, &registry, seat)),
+                        theme::ACCENT,
+                    ),
                     Participant::Human => ("YOU WIN!".to_string(), theme::ACCENT),
+                    Participant::Remote => ("YOU LOSE".to_string(), theme::TEXT),
                     Participant::Bot { .. } => {
                         // A bot won. Against a human that reads as a loss; in
                         // bot-vs-bot, name the victor.
@@ -361,21 +466,125 @@ fn spawn_result_banner(
                         .and_then(|r| r.0)
                         .map(|rank| format!("   ·   HIGH SCORE #{}", rank + 1))
                         .unwrap_or_default();
-                    format!(
+                    // Master counts its own 0–999 level.
+                    let level = snap
+                        .master
+                        .as_ref()
+                        .map_or(u16::from(snap.level), |master| master.level);
+                    let mut summary = format!(
                         "SCORE {}   ·   LINES {}   ·   LEVEL {}   ·   TIME {}:{:04.1}{}",
-                        snap.score, snap.lines, snap.level, minutes, seconds, rank
-                    )
+                        snap.score, snap.lines, level, minutes, seconds, rank
+                    );
+                    // A stack challenge ranks on its own board: say which.
+                    if let Some(challenge) = challenge.filter(|c| !c.0.is_standard()) {
+                        summary.push_str(&format!("\nSTACK {}", challenge.0.label()));
+                    }
+                    // Master: the grade and how long each section took.
+                    if let Some(master) = &snap.master {
+                        let sections: Vec<String> = master
+                            .section_times
+                            .iter()
+                            .enumerate()
+                            .map(|(i, seconds)| {
+                                format!(
+                                    "{:03} {}:{:04.1}",
+                                    i * 100,
+                                    (seconds / 60.0) as u32,
+                                    seconds % 60.0
+                                )
+                            })
+                            .collect();
+                        summary.push_str(&format!("\nGRADE {}", master.grade.label()));
+                        if !sections.is_empty() {
+                            summary.push('\n');
+                            summary.push_str(&sections.join("   ·   "));
+                        }
+                    }
+                    // Blitz: where the points came from, minute by minute.
+                    let by_minute = match config.mode {
+                        super::SessionMode::Solo { variant } => variant.def().score_by_minute,
+                        super::SessionMode::Versus
+                        | super::SessionMode::Puzzle { .. }
+                        | super::SessionMode::Practice { .. }
+                        | super::SessionMode::Opener { .. } => false,
+                    };
+                    if let Some(marks) = minute_scores.filter(|_| by_minute) {
+                        let minutes: Vec<String> = marks
+                            .breakdown(snap.score, clock.0)
+                            .iter()
+                            .enumerate()
+                            .map(|(i, points)| format!("MIN {} {points}", i + 1))
+                            .collect();
+                        summary.push('\n');
+                        summary.push_str(&minutes.join("   ·   "));
+                    }
+                    summary
                 }
                 None => format!("TIME {minutes}:{seconds:04.1}"),
             };
             (title, summary, color)
         }
+        super::SessionOutcome::Puzzle { solved } => {
+            let (title, color) = if solved {
+                ("SOLVED!".to_string(), theme::ACCENT)
+            } else {
+                ("FAILED".to_string(), theme::TEXT)
+            };
+            let name = match config.mode {
+                super::SessionMode::Puzzle { index } => {
+                    puzzles.get(index).map(|puzzle| puzzle.name.to_uppercase())
+                }
+                _ => None,
+            };
+            let mut parts: Vec<String> = name.into_iter().collect();
+            if let Some(run) = &puzzle_run {
+                parts.push(run.goal.label().to_uppercase());
+                parts.push(format!("PIECES {}/{}", run.pieces, run.piece_limit));
+            }
+            parts.push(format!("TIME {minutes}:{seconds:04.1}"));
+            (title, parts.join("   ·   "), color)
+        }
+        super::SessionOutcome::Practice => {
+            let summary = match seats_snapshot.iter().next() {
+                Some(snap) => format!(
+                    "LINES {}   ·   SCORE {}   ·   TIME {minutes}:{seconds:04.1}",
+                    snap.0.lines, snap.0.score
+                ),
+                None => format!("TIME {minutes}:{seconds:04.1}"),
+            };
+            ("TOPPED OUT".to_string(), summary, theme::TEXT)
+        }
+        super::SessionOutcome::Opener { complete } => {
+            let (title, color) = if complete {
+                ("OPENER COMPLETE".to_string(), theme::ACCENT)
+            } else {
+                ("OFF BOOK".to_string(), theme::TEXT)
+            };
+            let mut parts = Vec::new();
+            if let Some(trainer) = &trainer {
+                use tetr_core::opener::Grade;
+                parts.push(trainer.opener().name.to_uppercase());
+                parts.push(format!("BOOK {}", trainer.count(Grade::Book)));
+                parts.push(format!("ALTERNATE {}", trainer.count(Grade::Alternate)));
+                parts.push(format!("OFF BOOK {}", trainer.count(Grade::OffBook)));
+            }
+            parts.push(format!("TIME {minutes}:{seconds:04.1}"));
+            (title, parts.join("   ·   "), color)
+        }
+        super::SessionOutcome::Disconnected => (
+            "DISCONNECTED".to_string(),
+            "The match ended before it was decided".to_string(),
+            theme::TEXT,
+        ),
     };
+    // A disconnected match has nothing to rematch: the banner is just the
+    // way out.
+    let disconnected = outcome == super::SessionOutcome::Disconnected;
 
     let root = commands
         .spawn((
             ResultRoot,
-            FocusList::new(2),
+            FocusList::new(if disconnected { 1 } else { 2 }),
             overlay_root(0.8),
             DespawnOnExit(SessionPhase::Over),
         ))
@@ -391,36 +600,121 @@ fn spawn_result_banner(
         .spawn(label_text(summary, assets.font_body.clone()))
         .id();
     let rematch_label = match config.mode {
-        super::SessionMode::Solo { .. } => "Retry",
+        super::SessionMode::Solo { .. }
+        | super::SessionMode::Puzzle { .. }
+        | super::SessionMode::Practice { .. }
+        | super::SessionMode::Opener { .. } => "Retry",
         super::SessionMode::Versus => "Rematch",
     };
-    let rematch = commands
-        .spawn((
-            menu_button(0, rematch_label, assets.font.clone()),
-            ResultAction::Rematch,
-        ))
-        .id();
     let menu = commands
         .spawn((
-            menu_button(1, "Main Menu", assets.font.clone()),
+            menu_button(usize::from(!disconnected), "Main Menu", assets.font.clone()),
             ResultAction::Menu,
         ))
         .id();
-    commands
-        .entity(root)
-        .add_children(&[summary_id, rematch, menu]);
+    commands.entity(root).add_child(summary_id);
+    if online.is_some() {
+        let status = commands
+            .spawn((OnlineStatus, label_text("", assets.font_body.clone())))
+            .id();
+        commands.entity(root).add_child(status);
+    }
+    // One blunder timeline per human seat (bots are never reviewed).
+    for seat in 0..config.mode.seat_count() {
+        if config.seats[seat] == Participant::Human {
+            let heading = match config.mode {
+                super::SessionMode::Solo { .. }
+                | super::SessionMode::Puzzle { .. }
+                | super::SessionMode::Practice { .. }
+                | super::SessionMode::Opener { .. } => "PLACEMENT REVIEW".to_string(),
+                super::SessionMode::Versus => format!("PLACEMENT REVIEW · SEAT {}", seat + 1),
+            };
+            let graph = super::analysis::spawn_blunder_graph(&mut commands, &assets, seat, heading);
+            commands.entity(root).add_child(graph);
+        }
+    }
+    if !disconnected {
+        let rematch = commands
+            .spawn((
+                menu_button(0, rematch_label, assets.font.clone()),
+                ResultAction::Rematch,
+            ))
+            .id();
+        commands.entity(root).add_child(rematch);
+    }
+    commands.entity(root).add_child(menu);
 }
 
+/// A daily run: the day's rank and the share text, under the summary.
+fn spawn_daily_result(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    recorded: Option<Res<super::DailyRecorded>>,
+    attempt: Option<Res<crate::daily::DailyAttempt>>,
+    puzzles: Res<crate::puzzle::PuzzleCatalog>,
+    roots: Query<Entity, With<ResultRoot>>,
+) {
+    let (Some(recorded), Some(attempt), Ok(root)) = (recorded, attempt, roots.single()) else {
+        return;
+    };
+    let kind = if recorded.entry.official {
+        "OFFICIAL"
+    } else {
+        "PRACTICE"
+    };
+    let rank = recorded
+        .rank
+        .map(|rank| format!("   ·   TODAY #{}", rank + 1))
+        .unwrap_or_default();
+    let text = format!(
+        "DAILY #{}   ·   {kind}{rank}\n{}",
+        attempt.challenge.day.number(),
+        attempt.challenge.share_text(&puzzles, &recorded.entry)
+    );
+    let line = commands
+        .spawn(label_text(text, assets.font_body.clone()))
+        .id();
+    // Title, summary, then this.
+    commands.entity(root).insert_children(2, &[line]);
+}
+
+/// Online: say where the rematch vote stands, or that the rival is gone.
+fn refresh_online_status(
+    online: Res<OnlineMatch>,
+    mut texts: Query<&mut Text, With<OnlineStatus>>,
+) {
+    let control = online.control();
+    let local = online.local_seat();
+    let line = if let Some(failure) = online.failure() {
+        format!("Connection closed: {failure}")
+    } else {
+        let rival = (0..2).any(|seat| seat != local && control.voted(seat));
+        match (control.voted(local), rival) {
+            (true, _) => "Waiting for your rival to accept the rematch".to_string(),
+            (false, true) => "Your rival wants a rematch".to_string(),
+            (false, false) => String::new(),
+        }
+    };
+    for mut text in &mut texts {
+        if text.0 != line {
+            text.0 = line.clone();
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)] // a Bevy system's params are its dependency list
 fn result_menu_activate(
     keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
     list: Single<&FocusList, With<ResultRoot>>,
     actions: Query<(&Focusable, &ResultAction)>,
     clicks: Query<(&Focusable, &Interaction), Changed<Interaction>>,
+    mut online: Option<ResMut<OnlineMatch>>,
     mut commands: Commands,
     mut next_state: ResMut<NextState<GameState>>,
 ) {
-    let nav =
-        read_nav_action(&keys, *list).or_else(|| clicked_focusable(&clicks).map(NavAction::Select));
+    let nav = read_nav_action(&keys, &pad, *list)
+        .or_else(|| clicked_focusable(&clicks).map(NavAction::Select));
     match nav {
         Some(NavAction::Back) => next_state.set(GameState::MainMenu),
         Some(NavAction::Select(index)) => {
@@ -429,7 +723,12 @@ fn result_menu_activate(
                     continue;
                 }
                 match action {
-                    ResultAction::Rematch => commands.insert_resource(RematchRequested),
+                    // Online, a rematch is a vote: it is dealt once every
+                    // seat has asked (see `MatchControl`).
+                    ResultAction::Rematch => match online.as_deref_mut() {
+                        Some(online) => online.request(Command::Rematch),
+                        None => commands.insert_resource(RematchRequested),
+                    },
                     ResultAction::Menu => next_state.set(GameState::MainMenu),
                 }
             }
@@ -0,0 +1,322 @@
+//! Practice mode: free play where a mistake costs nothing.
+//!
+//! A [`SessionMode::Practice`](super::SessionMode::Practice) session seats
+//! one human on flat level-1 gravity with no goal, and keeps a
+//! [`PracticeHistory`]: a bounded stack of whole engine states, one per
+//! placement. Undo restores the engine as it stood before the last lock —
+//! board, hold, score and the queue position, so the undone piece comes
+//! back as the same piece with the same preview behind it. The seat's
+//! recording is cut back with it, so the post-game review prices the line
+//! that was kept rather than the one that was taken back.
+//!
+//! The keys are fixed, like the replay viewer's: Backspace undoes the last
+//! placement and Enter redoes it, R retries from the start of the current
+//! bag, F freezes gravity and the lock delay, and B reveals the next bag.
+//! A frozen frame is an ordinary frame with no time in it (`dt_seconds` 0):
+//! the piece still moves and drops on command, but nothing falls or locks
+//! by itself. Practice runs never rank and are never kept as replays.
+
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::ai::RecordedGame;
+use crate::engine::{Engine, InputFrame, PieceType};
+
+use super::analysis::SeatRecording;
+use super::{HumanSeat, SeatEngine, SeatSnapshot, SessionPhase};
+
+/// Placements the undo stack remembers; the oldest fall off beyond it.
+pub const PRACTICE_UNDO_DEPTH: usize = 100;
+
+pub struct SessionPracticePlugin;
+
+impl Plugin for SessionPracticePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            practice_keys
+                .run_if(in_state(SessionPhase::Running).and(resource_exists::<PracticeHistory>)),
+        );
+    }
+}
+
+/// A position between pieces: the engine right after a lock, its next piece
+/// fresh at the spawn (or before the first spawn), and how much of the
+/// recording led up to it.
+#[derive(Clone)]
+struct Checkpoint {
+    engine: Engine,
+    frames: usize,
+    /// The piece at the spawn (or about to spawn) is the first of its bag.
+    opens_bag: bool,
+}
+
+impl Checkpoint {
+    fn new(engine: &Engine, recording: &RecordedGame) -> Self {
+        let snapshot = engine.snapshot();
+        // The piece to play is as far into its bag as the deal is, less the
+        // queue and itself (once spawned) dealt since. An empty remainder is
+        // a bag boundary.
+        let dealt = PieceType::LEN - snapshot.bag_remainder.len();
+        let since = snapshot.next_queue.len() + usize::from(snapshot.active.is_some());
+        let position = (dealt as isize - since as isize).rem_euclid(PieceType::LEN as isize);
+        Self {
+            engine: engine.clone(),
+            frames: recording.inputs.len(),
+            opens_bag: position == 0,
+        }
+    }
+}
+
+/// The practice seat's undo/redo history and toggles. Present only in a
+/// practice session with a human seat.
+#[derive(Resource)]
+pub struct PracticeHistory {
+    undo: VecDeque<Checkpoint>,
+    /// Undone positions, most recent last, each with the frames that led to
+    /// it from the one before.
+    redo: Vec<(Checkpoint, Vec<InputFrame>)>,
+    current: Checkpoint,
+    /// Gravity and the lock delay are held.
+    pub frozen: bool,
+    /// The HUD shows the bag after the preview.
+    pub reveal: bool,
+}
+
+impl PracticeHistory {
+    /// A history starting at the seat's opening position.
+    pub fn new(engine: &Engine, recording: &RecordedGame) -> Self {
+        Self {
+            undo: VecDeque::new(),
+            redo: Vec::new(),
+            current: Checkpoint::new(engine, recording),
+            frozen: false,
+            reveal: false,
+        }
+    }
+
+    /// A piece locked: the position after it becomes the one to return to,
+    /// and whatever had been undone is gone.
+    pub fn placed(&mut self, engine: &Engine, recording: &RecordedGame) {
+        let previous = std::mem::replace(&mut self.current, Checkpoint::new(engine, recording));
+        self.undo.push_back(previous);
+        if self.undo.len() > PRACTICE_UNDO_DEPTH {
+            self.undo.pop_front();
+        }
+        self.redo.clear();
+    }
+
+    /// Take back the last placement (and any moves since): the engine to
+    /// resume from, or `None` with nothing left to undo.
+    pub fn undo(&mut self, recording: &mut RecordedGame) -> Option<Engine> {
+        let previous = self.undo.pop_back()?;
+        let undone = std::mem::replace(&mut self.current, previous);
+        let tail = recording
+            .inputs
+            .get(self.current.frames..undone.frames)
+            .map(<[InputFrame]>::to_vec)
+            .unwrap_or_default();
+        self.redo.push((undone, tail));
+        Some(self.restore(recording))
+    }
+
+    /// Put back the last undone placement, or `None` with nothing to redo.
+    pub fn redo(&mut self, recording: &mut RecordedGame) -> Option<Engine> {
+        let (next, tail) = self.redo.pop()?;
+        let redone = std::mem::replace(&mut self.current, next);
+        recording.inputs.truncate(redone.frames);
+        recording.inputs.extend(tail);
+        self.undo.push_back(redone);
+        Some(self.restore(recording))
+    }
+
+    /// Undo back to the position the current bag's first piece spawned
+    /// from (or as far as the history reaches). Each step is an ordinary
+    /// undo, so redo walks forward again.
+    pub fn retry_bag(&mut self, recording: &mut RecordedGame) -> Engine {
+        while !self.current.opens_bag && self.undo(recording).is_some() {}
+        self.restore(recording)
+    }
+
+    /// Placements that can be undone, and redone.
+    pub fn depths(&self) -> (usize, usize) {
+        (self.undo.len(), self.redo.len())
+    }
+
+    /// Cut the recording back to the current position and hand out its
+    /// engine.
+    fn restore(&self, recording: &mut RecordedGame) -> Engine {
+        let frames = self.current.frames;
+        recording.inputs.truncate(frames);
+        recording
+            .garbage
+            .retain(|garbage| garbage.after_frame < frames);
+        self.current.engine.clone()
+    }
+}
+
+/// What the bag reveal shows: the rest of the current bag behind the
+/// preview, then the whole bag after it.
+pub fn next_bag(engine: &Engine) -> (Vec<PieceType>, Vec<PieceType>) {
+    let rest = engine.snapshot().bag_remainder.len();
+    let mut upcoming = engine.upcoming_pieces(rest + PieceType::LEN);
+    let next = upcoming.split_off(rest);
+    (upcoming, next)
+}
+
+/// The practice keys (see the module doc).
+fn practice_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut history: ResMut<PracticeHistory>,
+    mut seats: Query<(&mut SeatEngine, &mut SeatSnapshot, &mut SeatRecording), With<HumanSeat>>,
+) {
+    if keys.just_pressed(KeyCode::KeyF) {
+        history.frozen = !history.frozen;
+    }
+    if keys.just_pressed(KeyCode::KeyB) {
+        history.reveal = !history.reveal;
+    }
+    let Ok((mut engine, mut snapshot, mut recording)) = seats.single_mut() else {
+        return;
+    };
+    let restored = if keys.just_pressed(KeyCode::Backspace) {
+        history.undo(&mut recording.0)
+    } else if keys.just_pressed(KeyCode::Enter) {
+        history.redo(&mut recording.0)
+    } else if keys.just_pressed(KeyCode::KeyR) {
+        Some(history.retry_bag(&mut recording.0))
+    } else {
+        None
+    };
+    if let Some(restored) = restored {
+        engine.0 = restored;
+        snapshot.0 = engine.0.snapshot();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::engine::EngineConfig;
+
+    /// A seat that plays piece after piece into three columns, recording
+    /// every frame and checkpointing every lock, like the session step does.
+    struct Practice {
+        engine: Engine,
+        recording: RecordedGame,
+        history: PracticeHistory,
+    }
+
+    impl Practice {
+        fn new() -> Self {
+            let engine = Engine::new(EngineConfig::default(), 3);
+            let recording = RecordedGame::new(EngineConfig::default(), 3);
+            let history = PracticeHistory::new(&engine, &recording);
+            Self {
+                engine,
+                recording,
+                history,
+            }
+        }
+
+        fn step(&mut self, frame: InputFrame) {
+            self.recording.push_frame(frame.clone());
+            let events = self.engine.step(frame);
+            if events
+                .iter()
+                .any(|event| matches!(event, crate::engine::EngineEvent::Locked { .. }))
+            {
+                self.history.placed(&self.engine, &self.recording);
+            }
+        }
+
+        /// Spawn and slide (left, right, or not at all), then hard-drop.
+        fn place(&mut self, n: usize) {
+            for i in 0..n {
+                self.step(InputFrame {
+                    left: i % 3 == 0,
+                    right: i % 3 == 1,
+                    instant_shift: true,
+                    ..default()
+                });
+                self.step(InputFrame {
+                    hard_drop: true,
+                    ..default()
+                });
+            }
+        }
+    }
+
+    #[test]
+    fn undo_restores_the_whole_engine_and_redo_puts_it_back() {
+        let mut practice = Practice::new();
+        practice.place(2);
+        let before = practice.engine.snapshot();
+        let frames = practice.recording.inputs.len();
+        practice.place(1);
+        let after = practice.engine.snapshot();
+
+        let undone = practice.history.undo(&mut practice.recording).unwrap();
+        assert_eq!(undone.snapshot(), before, "board, queue and score");
+        assert_eq!(practice.recording.inputs.len(), frames);
+        assert_eq!(practice.history.depths(), (2, 1));
+
+        let redone = practice.history.redo(&mut practice.recording).unwrap();
+        assert_eq!(redone.snapshot(), after);
+        assert_eq!(practice.recording.inputs.len(), frames + 2);
+        assert!(practice.history.redo(&mut practice.recording).is_none());
+
+        // The recording still replays to the live position.
+        let mut replay = Engine::new(EngineConfig::default(), 3);
+        for frame in &practice.recording.inputs {
+            replay.step(frame.clone());
+        }
+        assert_eq!(replay.snapshot(), after);
+    }
+
+    #[test]
+    fn a_new_placement_drops_the_redo_and_the_stack_is_bounded() {
+        let mut practice = Practice::new();
+        practice.place(3);
+        practice.history.undo(&mut practice.recording);
+        practice.engine = practice.history.undo(&mut practice.recording).unwrap();
+        practice.place(1);
+        assert_eq!(practice.history.depths(), (2, 0));
+
+        let mut history = PracticeHistory::new(&practice.engine, &practice.recording);
+        for _ in 0..PRACTICE_UNDO_DEPTH + 5 {
+            history.placed(&practice.engine, &practice.recording);
+        }
+        assert_eq!(history.depths(), (PRACTICE_UNDO_DEPTH, 0));
+    }
+
+    #[test]
+    fn retry_goes_back_to_the_start_of_the_bag() {
+        let mut practice = Practice::new();
+        practice.place(9);
+        let engine = practice.history.retry_bag(&mut practice.recording);
+        // Pieces 0..7 were the first bag: the retry returns to piece 7.
+        assert_eq!(practice.history.depths(), (7, 2));
+        let mut fresh = Practice::new();
+        fresh.place(7);
+        assert_eq!(engine.snapshot(), fresh.engine.snapshot());
+
+        // At a bag's first piece, retry stays put.
+        practice.engine = engine;
+        practice.history.retry_bag(&mut practice.recording);
+        assert_eq!(practice.history.depths(), (7, 2));
+    }
+
+    #[test]
+    fn the_reveal_is_the_rest_of_the_bag_then_the_next_one() {
+        let mut practice = Practice::new();
+        practice.place(1);
+        let (rest, next) = next_bag(&practice.engine);
+        assert_eq!(rest.len(), practice.engine.snapshot().bag_remainder.len());
+        assert_eq!(next.len(), PieceType::LEN);
+        let mut sorted = next.clone();
+        sorted.sort_by_key(|piece| *piece as u8);
+        assert_eq!(sorted, PieceType::ALL);
+    }
+}
@@ -7,7 +7,9 @@
 //! (or anchored to) that root — position is one transform, despawn is one
 //! subtree, and a future mirrored layout is a per-root parameter. One camera
 //! frames both boards with `ScalingMode::AutoMin`, so native window resizes
-//! and the web canvas keep the whole match visible. The camera carries the
+//! and the web canvas keep the whole match visible; on a portrait touch screen
+//! ([`SceneOrientation::Portrait`]) it lifts the boards to leave the bottom of
+//! the screen to the touch controls. The camera carries the
 //! [`GameplayCamera`] tag, so the optional bloom skin applies to it; the
 //! screen-shake mover (`session::feel`) offsets it around the scene's rest
 //! center.
@@ -16,6 +18,12 @@
 //! what changed, querying per seat. Garbage cells (`SnapshotCell::garbage`) paint a
 //! warm zero-chroma gray: telling your own stack from their attack at a glance is the
 //! point of having a versus renderer at all.
+//!
+//! A stack challenge ([`SessionChallenge`]) is drawn over the same sprites:
+//! each locked mino knows its slot in the snapshot, and a per-frame pass
+//! fades it by the engine's lock age (`EngineSnapshot::board_cell_ages`) or
+//! hides it outright, until the game ends and the whole stack is revealed.
+//! The optional outline traces the stack's open edges in the ghost's hairline.
 
 use bevy::camera::ScalingMode;
 use bevy::prelude::*;
@@ -27,11 +35,12 @@ use crate::GameState;
 use crate::assets::GameAssets;
 use crate::engine::{Piece, PieceType, SnapshotCell};
 use crate::level::common::{GameplayCamera, to_translation};
+use crate::settings::{STACK_FADE_SECONDS, StackVisibility};
 use crate::ui::widgets::theme;
 
 use super::skin::{self, MinoKind, MinoSkin};
 
-use super::{Participant, Seat, SeatSnapshot, SeatStats, SessionConfig};
+use super::{Seat, SeatSnapshot, SeatStats, SessionChallenge, SessionConfig, SessionPhase};
 
 /// World-space layout of the two-board scene, in cells and pixels. One home
 /// for every magic number the renderer and overlays share.
@@ -56,6 +65,9 @@ impl SessionLayout {
     /// purpose: the readout is its own quiet row, not a caption glued to the
     /// frame.
     pub const READOUT_GAP_CELLS: f32 = 1.2;
+    /// Extra scene height a portrait frame keeps below the boards for the
+    /// touch-control deck, in cells.
+    pub const DECK_CELLS: f32 = 14.0;
 
     /// Offset from a board edge to its hold/preview column anchor, in cells.
     /// On a versus board's INNER edge (`beyond_meter`) the column steps out
@@ -75,11 +87,20 @@ impl SessionLayout {
     }
 
     /// Center of the whole scene (the camera's rest position), for however
-    /// many seats this session plays with.
-    pub fn scene_center(seat_count: usize) -> Vec3 {
+    /// many seats this session plays with. A portrait frame sits half a deck
+    /// lower, so the boards ride above the touch controls.
+    pub fn scene_center(seat_count: usize, orientation: SceneOrientation) -> Vec3 {
         let right =
             Self::board_origin(seat_count.saturating_sub(1)).x + Self::BOARD_W as f32 * Self::BLOCK;
-        Vec3::new(right / 2.0, Self::BOARD_H as f32 * Self::BLOCK / 2.0, 1.0)
+        let deck = match orientation {
+            SceneOrientation::Landscape => 0.0,
+            SceneOrientation::Portrait => Self::DECK_CELLS * Self::BLOCK,
+        };
+        Vec3::new(
+            right / 2.0,
+            (Self::BOARD_H as f32 * Self::BLOCK - deck) / 2.0,
+            1.0,
+        )
     }
 
     /// Minimum world-space rectangle the camera must keep visible: every
@@ -88,7 +109,8 @@ impl SessionLayout {
     /// in `session::feel` is one word per line; Dogica is monospaced at
     /// 1 em/glyph, so the longest word — "BACK-TO-BACK" at 16 px, "TETRIS" at
     /// 32 px — needs ~6 cells outboard of each board edge).
-    pub fn scene_min(seat_count: usize) -> (f32, f32) {
+    /// A portrait frame adds the touch deck beneath.
+    pub fn scene_min(seat_count: usize, orientation: SceneOrientation) -> (f32, f32) {
         let width_cells = match seat_count {
             // hold column + board + preview column + a callout gutter.
             0 | 1 => 24.0,
@@ -99,10 +121,37 @@ impl SessionLayout {
         // Height: the 20-cell board plus headroom for the seat label and,
         // below, the readout at its READOUT_GAP_CELLS offset with clear air
         // to the screen edge.
-        (width_cells * Self::BLOCK, 26.0 * Self::BLOCK)
+        let height_cells = match orientation {
+            SceneOrientation::Landscape => 26.0,
+            SceneOrientation::Portrait => 26.0 + Self::DECK_CELLS,
+        };
+        (width_cells * Self::BLOCK, height_cells * Self::BLOCK)
+    }
+
+    /// The camera projection that keeps [`scene_min`](Self::scene_min) in view.
+    fn projection(seat_count: usize, orientation: SceneOrientation) -> Projection {
+        let (min_width, min_height) = Self::scene_min(seat_count, orientation);
+        Projection::Orthographic(OrthographicProjection {
+            scaling_mode: ScalingMode::AutoMin {
+                min_width,
+                min_height,
+            },
+            ..OrthographicProjection::default_2d()
+        })
     }
 }
 
+/// How the session scene is framed. The touch feature sets it: portrait when
+/// its controls are showing on a screen taller than wide, landscape otherwise.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SceneOrientation {
+    /// The boards centered, nothing reserved.
+    #[default]
+    Landscape,
+    /// The boards up top, the touch deck below.
+    Portrait,
+}
+
 /// A seat's render anchor; all of the seat's visuals hang off this entity.
 /// `seat` is the identity an overlay or a future mirrored-layout pass selects
 /// roots by (today's readers use the layer/meter markers, hence the allow).
@@ -118,12 +167,22 @@ pub enum VsLayer {
     Static,
     Falling,
     Ghost,
+    /// The opener trainer's next target placement.
+    Target,
 }
 
 /// Marks a layer entity with its seat (paired with [`VsLayer`]).
 #[derive(Component, Clone, Copy)]
 pub struct LayerSeat(pub usize);
 
+/// A locked mino's seat and its index into the snapshot's `board_cells`
+/// (and so `board_cell_ages`): what the stack-challenge pass fades it by.
+#[derive(Component, Clone, Copy)]
+struct LockedMino {
+    seat: usize,
+    slot: usize,
+}
+
 /// World-space width of the garbage meter (track and fill), in pixels.
 const METER_WIDTH: f32 = 8.0;
 
@@ -167,20 +226,28 @@ pub struct SessionRenderPlugin;
 
 impl Plugin for SessionRenderPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, skin::build_mino_skin)
+        app.init_resource::<SceneOrientation>()
+            .add_systems(Startup, skin::build_mino_skin)
             .add_systems(OnEnter(GameState::Session), setup_scene)
             .add_systems(
                 Update,
                 (
-                    reconcile_locked_boards,
+                    (
+                        reconcile_locked_boards,
+                        hide_locked_minos
+                            .run_if(|challenge: Res<SessionChallenge>| !challenge.0.is_standard()),
+                    )
+                        .chain(),
                     reconcile_active_pieces,
                     reconcile_ghost_pieces,
+                    reconcile_opener_target,
                     reconcile_garbage_meters,
                     reconcile_hold_views,
                     reconcile_preview_views,
                     update_atk_texts,
                     update_seat_timer_bars,
                     tint_danger_frames,
+                    reframe_camera.run_if(resource_changed::<SceneOrientation>),
                 )
                     .run_if(in_state(GameState::Session)),
             );
@@ -229,6 +296,7 @@ fn setup_scene(
     assets: Res<GameAssets>,
     config: Res<SessionConfig>,
     registry: Res<crate::ai::ModelRegistry>,
+    orientation: Res<SceneOrientation>,
 ) {
     let block = SessionLayout::BLOCK;
 
@@ -284,7 +352,12 @@ fn setup_scene(
         commands.entity(root).add_children(&chrome);
 
         // Mino layers: children rebuilt by the reconcilers.
-        for layer in [VsLayer::Static, VsLayer::Falling, VsLayer::Ghost] {
+        for layer in [
+            VsLayer::Static,
+            VsLayer::Falling,
+            VsLayer::Ghost,
+            VsLayer::Target,
+        ] {
             let id = commands
                 .spawn((
                     layer,
@@ -304,9 +377,13 @@ fn setup_scene(
         // `METER_AIR_CELLS` of air on BOTH sides — frame · air · meter ·
         // air · column — never glued to either. Versus only — solo has no
         // garbage channel, and an always-empty groove would read as a stuck
-        // UI element (the meter reconciler tolerates the missing root).
-        let versus = matches!(config.mode, super::SessionMode::Versus);
-        if versus {
+        // UI element (the meter reconciler tolerates the missing root). A
+        // puzzle keeps it: a survival goal's garbage waits in it.
+        let meter = matches!(
+            config.mode,
+            super::SessionMode::Versus | super::SessionMode::Puzzle { .. }
+        );
+        if meter {
             let air = SessionLayout::METER_AIR_CELLS * block;
             let meter_x = if seat == 0 {
                 SessionLayout::BOARD_W as f32 * block + air
@@ -348,11 +425,11 @@ fn setup_scene(
         commands.entity(root).add_child(bar);
 
         // Hold column (top-left of the board) and preview column (top-right) —
-        // the single-player arrangement, duplicated per seat. On a versus
-        // board's inner edge the column steps outboard past the garbage-meter
-        // channel so the bar keeps clear air on both sides.
-        let hold_gap = SessionLayout::column_gap_cells(versus && seat == 1);
-        let preview_gap = SessionLayout::column_gap_cells(versus && seat == 0);
+        // the single-player arrangement, duplicated per seat. On the edge
+        // with the garbage meter the column steps outboard past its channel
+        // so the bar keeps clear air on both sides.
+        let hold_gap = SessionLayout::column_gap_cells(meter && seat == 1);
+        let preview_gap = SessionLayout::column_gap_cells(meter && seat == 0);
         let hold = commands
             .spawn((
                 SeatHoldView { seat },
@@ -415,11 +492,9 @@ fn setup_scene(
             .entity(root)
             .add_children(&[hold, preview, hold_label, next_label]);
 
-        // Seat label above the board: "YOU", or the model's catalog name.
-        let label = match config.seats[seat] {
-            Participant::Human => "YOU".to_string(),
-            Participant::Bot { model } => registry.label(model).to_uppercase(),
-        };
+        // Seat label above the board: "YOU" (or "P1"/"P2" for two humans),
+        // "RIVAL" (online), or the model's catalog name.
+        let label = super::overlay::seat_label(&config, &registry, seat);
         let label_id = commands
             .spawn((
                 Text2d::new(label),
@@ -443,7 +518,10 @@ fn setup_scene(
         // whole run line into one row, so it stays at the label size.
         let readout_size = match config.mode {
             super::SessionMode::Versus => theme::NUMERAL_FONT_SIZE,
-            super::SessionMode::Solo { .. } => theme::BUTTON_FONT_SIZE,
+            super::SessionMode::Solo { .. }
+            | super::SessionMode::Puzzle { .. }
+            | super::SessionMode::Practice { .. }
+            | super::SessionMode::Opener { .. } => theme::BUTTON_FONT_SIZE,
         };
         let atk_id = commands
             .spawn((
@@ -476,21 +554,27 @@ fn setup_scene(
             ..default()
         },
         GameplayCamera,
-        Projection::Orthographic(OrthographicProjection {
-            scaling_mode: {
-                let (min_width, min_height) = SessionLayout::scene_min(config.mode.seat_count());
-                ScalingMode::AutoMin {
-                    min_width,
-                    min_height,
-                }
-            },
-            ..OrthographicProjection::default_2d()
-        }),
-        Transform::from_translation(SessionLayout::scene_center(config.mode.seat_count())),
+        SessionLayout::projection(config.mode.seat_count(), *orientation),
+        Transform::from_translation(SessionLayout::scene_center(
+            config.mode.seat_count(),
+            *orientation,
+        )),
         DespawnOnExit(GameState::Session),
     ));
 }
 
+/// Refit the camera when the frame turns between landscape and portrait (the
+/// shake mover re-centers it on the new rest position).
+fn reframe_camera(
+    orientation: Res<SceneOrientation>,
+    config: Res<SessionConfig>,
+    mut cameras: Query<&mut Projection, With<GameplayCamera>>,
+) {
+    for mut projection in &mut cameras {
+        *projection = SessionLayout::projection(config.mode.seat_count(), *orientation);
+    }
+}
+
 /// Find the layer entity for `(seat, kind)`.
 fn layer_for(
     layers: &Query<(Entity, &VsLayer, &LayerSeat)>,
@@ -504,10 +588,12 @@ fn layer_for(
 }
 
 /// Rebuild a seat's locked minos when its board changes (diffed against the
-/// cached cells, exactly like the single-player reconciler).
+/// cached cells, exactly like the single-player reconciler), with the stack
+/// edge outlined when the session's challenge asks for it.
 fn reconcile_locked_boards(
     mut commands: Commands,
     minos: Res<MinoSkin>,
+    challenge: Res<SessionChallenge>,
     seats: Query<(&Seat, &SeatSnapshot)>,
     layers: Query<(Entity, &VsLayer, &LayerSeat)>,
     mut cache: Local<[Option<Vec<SnapshotCell>>; 2]>,
@@ -531,9 +617,10 @@ fn reconcile_locked_boards(
             .iter()
             .map(|cell| ((cell.x, cell.y), cell_kind(cell)))
             .collect();
-        let ids: Vec<Entity> = cells
+        let mut ids: Vec<Entity> = cells
             .iter()
-            .map(|cell| {
+            .enumerate()
+            .map(|(slot, cell)| {
                 let kind = cell_kind(cell);
                 let kind_mask = skin::neighbor_mask_where(cell.x, cell.y, |x, y| {
                     kinds.get(&(x, y)) == Some(&kind)
@@ -543,21 +630,81 @@ fn reconcile_locked_boards(
                         kinds.contains_key(&(x, y))
                     });
                 commands
-                    .spawn(block_sprite(
-                        minos.handle(kind, kind_mask, empty_mask),
-                        SessionLayout::BLOCK,
-                        cell.x,
-                        cell.y,
-                        0.0,
+                    .spawn((
+                        block_sprite(
+                            minos.handle(kind, kind_mask, empty_mask),
+                            SessionLayout::BLOCK,
+                            cell.x,
+                            cell.y,
+                            0.0,
+                        ),
+                        LockedMino {
+                            seat: seat.index,
+                            slot,
+                        },
                     ))
                     .id()
             })
             .collect();
+        if challenge.0.outline {
+            // The stack's open edges only: the walls and floor are the
+            // field's own frame, so they count as closed.
+            let closed = |x: isize, y: isize| {
+                x < 0
+                    || x >= SessionLayout::BOARD_W as isize
+                    || y < 0
+                    || kinds.contains_key(&(x, y))
+            };
+            for cell in cells {
+                let mask = skin::neighbor_mask_where(cell.x, cell.y, closed);
+                ids.extend(cell_outline(
+                    &mut commands,
+                    SessionLayout::BLOCK,
+                    cell.x,
+                    cell.y,
+                    mask,
+                    theme::TEXT.with_alpha(0.6),
+                    0.1,
+                ));
+            }
+        }
         commands.entity(layer).add_children(&ids);
         cache[index] = Some(cells.clone());
     }
 }
 
+/// The stack challenge: fade each locked mino by its lock age, or hide it,
+/// while the game runs; once it is over the whole stack shows again. Runs
+/// right after the rebuild so a fresh mino never flashes at full strength.
+fn hide_locked_minos(
+    challenge: Res<SessionChallenge>,
+    phase: Option<Res<State<SessionPhase>>>,
+    seats: Query<(&Seat, &SeatSnapshot)>,
+    mut minos: Query<(&LockedMino, &mut Sprite)>,
+) {
+    let over = phase.is_some_and(|phase| *phase.get() == SessionPhase::Over);
+    for (mino, mut sprite) in &mut minos {
+        let Some((_, snapshot)) = seats.iter().find(|(seat, _)| seat.index == mino.seat) else {
+            continue;
+        };
+        let age = snapshot.0.board_cell_ages.get(mino.slot).copied();
+        let alpha = stack_alpha(challenge.0.visibility, age.unwrap_or(0.0), over);
+        if sprite.color.alpha() != alpha {
+            sprite.color.set_alpha(alpha);
+        }
+    }
+}
+
+/// How strongly a locked mino `age` seconds old shows under `visibility`.
+fn stack_alpha(visibility: StackVisibility, age: f32, over: bool) -> f32 {
+    match visibility {
+        _ if over => 1.0,
+        StackVisibility::Visible => 1.0,
+        StackVisibility::Fading => (1.0 - age / STACK_FADE_SECONDS).clamp(0.0, 1.0),
+        StackVisibility::Invisible => 0.0,
+    }
+}
+
 /// Rebuild each seat's falling piece when its rendered cells change (4 sprites).
 fn reconcile_active_pieces(
     mut commands: Commands,
@@ -616,19 +763,21 @@ fn reconcile_active_pieces(
 /// The ghost is an outline, never a fill (`theme::TEXT` at 35%): four hairline
 /// edges per cell, stroke as thick as the articulation gap. Readable at a
 /// glance and never mistakable for a placed mino.
-/// Outline edges for one ghost cell, EXPOSED sides only (`mask` as in the
-/// mino skin): the ghost traces the piece's connected silhouette, matching
-/// the skin's shared-perimeter language, never a per-cell lattice.
-fn ghost_cell_outline(
+/// Outline edges for one cell, EXPOSED sides only (`mask` as in the mino
+/// skin): the ghost traces the piece's connected silhouette, matching the
+/// skin's shared-perimeter language, never a per-cell lattice — and a stack
+/// outline traces the stack's the same way.
+fn cell_outline(
     commands: &mut Commands,
     block: f32,
     x: isize,
     y: isize,
     mask: u8,
+    color: Color,
+    z: f32,
 ) -> Vec<Entity> {
     let stroke = block * GHOST_STROKE_FRACTION;
-    let base = to_translation(x, y, block) + Vec3::new(0.0, 0.0, -0.1);
-    let color = theme::TEXT.with_alpha(0.35);
+    let base = to_translation(x, y, block) + Vec3::new(0.0, 0.0, z);
     // (mask bit, offset, size): the full side, so silhouette corners meet.
     let edges = [
         (skin::MASK_S, Vec2::new(0.0, 0.0), Vec2::new(block, stroke)),
@@ -694,7 +843,15 @@ fn reconcile_ghost_pieces(
                 let mask = skin::neighbor_mask_where(cell.x, cell.y, |x, y| {
                     cells.iter().any(|c| c.x == x && c.y == y)
                 });
-                ghost_cell_outline(&mut commands, SessionLayout::BLOCK, cell.x, cell.y, mask)
+                cell_outline(
+                    &mut commands,
+                    SessionLayout::BLOCK,
+                    cell.x,
+                    cell.y,
+                    mask,
+                    theme::TEXT.with_alpha(0.35),
+                    -0.1,
+                )
             })
             .collect();
         commands.entity(layer).add_children(&ids);
@@ -702,6 +859,48 @@ fn reconcile_ghost_pieces(
     }
 }
 
+/// The opener target as last drawn: its layer and cells.
+type DrawnTarget = (Entity, Vec<(isize, isize)>);
+
+/// Outline the opener trainer's next target on seat 0, in the accent colour
+/// so it never reads as the ghost; cleared when the run ends.
+fn reconcile_opener_target(
+    mut commands: Commands,
+    trainer: Option<Res<crate::openers::OpenerTrainer>>,
+    layers: Query<(Entity, &VsLayer, &LayerSeat)>,
+    mut cache: Local<Option<DrawnTarget>>,
+) {
+    let Some(layer) = layer_for(&layers, 0, VsLayer::Target) else {
+        return;
+    };
+    let cells = trainer.map_or_else(Vec::new, |trainer| trainer.target_cells());
+    // Keyed on the layer too: a new session's fresh layer starts empty.
+    if cache
+        .as_ref()
+        .is_some_and(|(drawn, drawn_cells)| *drawn == layer && *drawn_cells == cells)
+    {
+        return;
+    }
+    commands.entity(layer).despawn_related::<Children>();
+    let ids: Vec<Entity> = cells
+        .iter()
+        .flat_map(|&(x, y)| {
+            let mask = skin::neighbor_mask_where(x, y, |nx, ny| cells.contains(&(nx, ny)));
+            cell_outline(
+                &mut commands,
+                SessionLayout::BLOCK,
+                x,
+                y,
+                mask,
+                theme::ACCENT.with_alpha(0.8),
+                -0.05,
+            )
+        })
+        .collect();
+    commands.entity(layer).add_children(&ids);
+    *cache = Some((layer, cells));
+}
+
 /// A pending batch as the meter draws it: `(lines, hole_col)`.
 type MeterBatches = Vec<(u32, usize)>;
 
@@ -943,10 +1142,13 @@ fn lock_bar_progress(snapshot: &crate::engine::EngineSnapshot) -> f32 {
 fn update_atk_texts(
     config: Res<super::SessionConfig>,
     clock: Res<super::MatchClock>,
-    seats: Query<(&Seat, &SeatStats, &SeatSnapshot)>,
+    puzzle: Option<Res<crate::puzzle::PuzzleRun>>,
+    practice: Option<Res<super::practice::PracticeHistory>>,
+    trainer: Option<Res<crate::openers::OpenerTrainer>>,
+    seats: Query<(&Seat, &SeatStats, &SeatSnapshot, Option<&super::SeatEngine>)>,
     mut texts: Query<(&SeatAtkText, &mut Text2d)>,
 ) {
-    for (seat, stats, snapshot) in &seats {
+    for (seat, stats, snapshot, engine) in &seats {
         for (atk, mut text) in &mut texts {
             if atk.seat != seat.index {
                 continue;
@@ -954,10 +1156,27 @@ fn update_atk_texts(
             let line = match config.mode {
                 // Versus: the pressure scoreboard.
                 super::SessionMode::Versus => format!("ATK {}", stats.attack_sent),
-                // Solo: the run line — score, lines, level, and the variant
-                // clock (Sprint counts up; Ultra counts down to its limit).
+                // Solo: the run line — score, lines, level (Dig: the cheese
+                // still to clear; Master: the counter against its section
+                // stop, and the grade), and the variant clock (Sprint counts
+                // up; Ultra counts down to its limit).
                 super::SessionMode::Solo { variant } => {
                     let snap = &snapshot.0;
+                    let progress = match (&snap.master, snap.dig_remaining) {
+                        (Some(master), _) => format!(
+                            "LVL {}/{}   GRADE {}{}",
+                            master.level,
+                            master.level_stop + 1,
+                            master.grade.label(),
+                            if master.gravity == crate::engine::Gravity::TwentyG {
+                                "   20G"
+                            } else {
+                                ""
+                            }
+                        ),
+                        (None, Some(left)) => format!("CHEESE {left}"),
+                        (None, None) => format!("LVL {}", snap.level),
+                    };
                     let shown = match variant.def().end_condition {
                         crate::variant::EndCondition::TimeLimit(limit) => {
                             (limit - clock.0).max(0.0)
@@ -965,14 +1184,59 @@ fn update_atk_texts(
                         _ => clock.0,
                     };
                     format!(
-                        "SCORE {}   LINES {}   LVL {}   {}:{:04.1}",
+                        "SCORE {}   LINES {}   {}   {}:{:04.1}",
                         snap.score,
                         snap.lines,
-                        snap.level,
+                        progress,
                         (shown / 60.0) as u32,
                         shown % 60.0
                     )
                 }
+                // Puzzle: the goal and the piece budget spent.
+                super::SessionMode::Puzzle { .. } => match &puzzle {
+                    Some(run) => format!(
+                        "{}   PIECES {}/{}",
+                        run.goal.label().to_uppercase(),
+                        run.pieces,
+                        run.piece_limit
+                    ),
+                    None => String::new(),
+                },
+                // Practice: the history's depth and toggles, and the bag
+                // reveal on a line of its own.
+                super::SessionMode::Practice { .. } => {
+                    let mut line = format!("LINES {}", snapshot.0.lines);
+                    if let Some(practice) = &practice {
+                        let (undo, redo) = practice.depths();
+                        line.push_str(&format!("   UNDO {undo}   REDO {redo}"));
+                        if practice.frozen {
+                            line.push_str("   FROZEN");
+                        }
+                        if let Some(engine) = engine.filter(|_| practice.reveal) {
+                            let (rest, next) = super::practice::next_bag(&engine.0);
+                            let letters = |pieces: Vec<crate::engine::PieceType>| {
+                                pieces.iter().map(|p| format!("{p:?}")).collect::<String>()
+                            };
+                            line.push_str(&format!("\nBAG {} | {}", letters(rest), letters(next)));
+                        }
+                    }
+                    line
+                }
+                // Opener trainer: the opener, the grades so far, and a hint
+                // when the target needs the hold.
+                super::SessionMode::Opener { .. } => match &trainer {
+                    Some(trainer) => {
+                        use tetr_core::opener::Grade;
+                        format!(
+                            "{}   BOOK {}   ALT {}{}",
+                            trainer.opener().name.to_uppercase(),
+                            trainer.count(Grade::Book),
+                            trainer.count(Grade::Alternate),
+                            if trainer.wants_hold() { "   HOLD" } else { "" }
+                        )
+                    }
+                    None => String::new(),
+                },
             };
             if text.0 != line {
                 text.0 = line;
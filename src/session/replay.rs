@@ -0,0 +1,717 @@
+//! Replays in the session: capture every finished match into the
+//! [`ReplayLibrary`], and play one back on the session's own boards.
+//!
+//! **Capture.** Every seat carries a [`SeatRecording`] (bots and remote
+//! rivals too), so when the session reaches `Over` the match is already a
+//! [`GameReplay`]; [`capture_replay`] files it as the newest recent game and,
+//! when a human solo run just took first place on its board, as that
+//! variant's personal best — attached to the high-score entry it set. A
+//! session left from the pause menu never ends, so it is not kept.
+//!
+//! **Playback.** The Replays screen inserts a [`SessionPlayback`] and enters
+//! [`GameState::Session`] as any game does. Setup sees the resource and seats
+//! the replay's participants with no drivers and no engines of their own: a
+//! [`Playhead`] owns the engines, re-steps them from the recorded frames and
+//! garbage, and publishes each seat's snapshot, stats and events exactly
+//! where the live step would — so the renderer, the sounds and the callouts
+//! cannot tell the difference. The countdown, the pause menu, the match clock
+//! and the end checks stand down; the clock shows the playhead instead.
+//!
+//! Seeking restores the nearest earlier keyframe (a clone of every seat taken
+//! each [`KEYFRAME_FRAMES`] slices on the way forward) and re-steps from
+//! there, so a jump anywhere costs at most ten seconds of simulation.
+
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+
+use crate::GameState;
+use crate::assets::GameAssets;
+use crate::engine::{Engine, EngineEvent, EngineSnapshot};
+use crate::level::engine_bridge::SIM_DT_SECONDS;
+use crate::replays::{GameReplay, ReplayLibrary};
+use crate::storage::StorageResource;
+use crate::ui::widgets::{label_text, theme};
+use crate::variant::ScoreKind;
+
+use super::analysis::SeatRecording;
+use super::{
+    MatchClock, Seat, SeatEvents, SeatSnapshot, SeatStats, SessionChallenge, SessionConfig,
+    SessionMode, SessionOutcome, SessionPhase, SoloRecorded, sent_lines,
+};
+
+/// Playback speeds, slowest first.
+pub const SPEEDS: [f32; 5] = [0.25, 0.5, 1.0, 2.0, 4.0];
+
+/// The speed a replay opens at (1×).
+const DEFAULT_SPEED: usize = 2;
+
+/// Slices between seek keyframes (ten seconds of play).
+pub const KEYFRAME_FRAMES: usize = 600;
+
+/// How far Shift+Left / Shift+Right jumps.
+const SEEK_SECONDS: f32 = 5.0;
+
+const TRACK_WIDTH: f32 = 520.0;
+const TRACK_HEIGHT: f32 = 10.0;
+
+pub struct SessionReplayPlugin;
+
+impl Plugin for SessionReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ReplayLibrary>()
+            .add_systems(Startup, load_library)
+            .add_systems(
+                OnEnter(SessionPhase::Over),
+                capture_replay.after(super::record_solo_run),
+            )
+            .add_systems(
+                OnEnter(GameState::Session),
+                spawn_playback_bar
+                    .after(super::session_setup)
+                    .run_if(resource_exists::<SessionPlayback>),
+            )
+            .add_systems(
+                OnEnter(SessionPhase::Countdown),
+                skip_countdown.run_if(resource_exists::<SessionPlayback>),
+            )
+            .add_systems(
+                FixedUpdate,
+                drive_playback.run_if(
+                    resource_exists::<SessionPlayback>.and(in_state(SessionPhase::Running)),
+                ),
+            )
+            .add_systems(
+                Update,
+                (playback_keys, scrub_playback, update_playback_bar)
+                    .chain()
+                    .run_if(resource_exists::<SessionPlayback>.and(in_state(GameState::Session))),
+            )
+            .add_systems(OnExit(GameState::Session), end_playback);
+    }
+}
+
+/// One seat's playback state: everything a keyframe has to restore.
+#[derive(Clone)]
+struct PlaybackSeat {
+    engine: Engine,
+    stats: SeatStats,
+    /// Index of the next recorded garbage batch to queue.
+    garbage: usize,
+}
+
+/// A replay being re-simulated, with random access.
+pub struct Playhead {
+    replay: GameReplay,
+    seats: Vec<PlaybackSeat>,
+    /// Slices played so far.
+    cursor: usize,
+    /// `keyframes[k]` is every seat at slice `k * KEYFRAME_FRAMES`.
+    keyframes: Vec<Vec<PlaybackSeat>>,
+}
+
+impl Playhead {
+    pub fn new(replay: GameReplay) -> Self {
+        let seats: Vec<PlaybackSeat> = replay
+            .seats
+            .iter()
+            .map(|seat| PlaybackSeat {
+                engine: Engine::new(seat.game.config.clone(), seat.game.seed),
+                stats: SeatStats::default(),
+                garbage: 0,
+            })
+            .collect();
+        Self {
+            keyframes: vec![seats.clone()],
+            replay,
+            seats,
+            cursor: 0,
+        }
+    }
+
+    pub fn replay(&self) -> &GameReplay {
+        &self.replay
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    /// The replay's length in slices.
+    pub fn frames(&self) -> usize {
+        self.replay.frames()
+    }
+
+    pub fn at_end(&self) -> bool {
+        self.cursor >= self.frames()
+    }
+
+    pub fn snapshot(&self, seat: usize) -> EngineSnapshot {
+        self.seats[seat].engine.snapshot()
+    }
+
+    pub fn stats(&self, seat: usize) -> SeatStats {
+        self.seats[seat].stats
+    }
+
+    /// Play one slice on every seat: step its recorded frame, then queue the
+    /// garbage that arrived after it. Returns each seat's events, or `None`
+    /// at the end of the replay.
+    pub fn step(&mut self) -> Option<Vec<Vec<EngineEvent>>> {
+        if self.at_end() {
+            return None;
+        }
+        let frame = self.cursor;
+        let mut all = Vec::with_capacity(self.seats.len());
+        for (seat, recorded) in self.seats.iter_mut().zip(&self.replay.seats) {
+            let game = &recorded.game;
+            let events = match game.inputs.get(frame) {
+                Some(input) => seat.engine.step(input.clone()),
+                None => Vec::new(),
+            };
+            while let Some(batch) = game.garbage.get(seat.garbage)
+                && batch.after_frame <= frame
+            {
+                seat.engine.queue_garbage(batch.lines);
+                seat.garbage += 1;
+            }
+            seat.stats.attack_sent += sent_lines(&events);
+            seat.stats.garbage_taken += events
+                .iter()
+                .map(|e| match e {
+                    EngineEvent::GarbageInserted { lines } => *lines,
+                    _ => 0,
+                })
+                .sum::<u32>();
+            all.push(events);
+        }
+        self.cursor += 1;
+        if self.cursor.is_multiple_of(KEYFRAME_FRAMES)
+            && self.keyframes.len() == self.cursor / KEYFRAME_FRAMES
+        {
+            self.keyframes.push(self.seats.clone());
+        }
+        Some(all)
+    }
+
+    /// Jump to slice `frame` (clamped to the replay). Backwards, or forwards
+    /// past a known keyframe, restarts from the nearest keyframe at or before
+    /// it.
+    pub fn seek(&mut self, frame: usize) {
+        let frame = frame.min(self.frames());
+        let key = (frame / KEYFRAME_FRAMES).min(self.keyframes.len() - 1);
+        if frame < self.cursor || key * KEYFRAME_FRAMES > self.cursor {
+            self.seats = self.keyframes[key].clone();
+            self.cursor = key * KEYFRAME_FRAMES;
+        }
+        while self.cursor < frame {
+            self.step();
+        }
+    }
+}
+
+/// The session is playing a replay rather than a live match. Inserted by the
+/// Replays screen (via [`begin`](Self::begin)) before it enters
+/// [`GameState::Session`]; removed when the session exits.
+#[derive(Resource)]
+pub struct SessionPlayback {
+    pub playhead: Playhead,
+    pub playing: bool,
+    /// Index into [`SPEEDS`].
+    pub speed: usize,
+    /// Fractional slices owed at speeds below 1×.
+    carry: f32,
+    /// The seating the menus had before the replay borrowed it.
+    restore: SessionConfig,
+}
+
+impl SessionPlayback {
+    /// Seat `replay` in `config` (its mode and participants), keeping the
+    /// old seating to put back when playback ends.
+    pub fn begin(replay: GameReplay, config: &mut SessionConfig) -> Self {
+        let restore = *config;
+        config.mode = replay.mode;
+        config.seed = None;
+        for (slot, seat) in config.seats.iter_mut().zip(&replay.seats) {
+            *slot = seat.participant;
+        }
+        Self {
+            playhead: Playhead::new(replay),
+            playing: true,
+            speed: DEFAULT_SPEED,
+            carry: 0.0,
+            restore,
+        }
+    }
+
+    pub fn speed(&self) -> f32 {
+        SPEEDS[self.speed]
+    }
+}
+
+/// Restore the stored replays on startup.
+fn load_library(storage: Option<Res<StorageResource>>, mut library: ResMut<ReplayLibrary>) {
+    if let Some(storage) = storage {
+        *library = ReplayLibrary::load(storage.0.as_ref());
+    }
+}
+
+/// The match just ended: file every seat's recording as one replay.
+#[allow(clippy::too_many_arguments)] // a Bevy system's params are its dependency list
+fn capture_replay(
+    config: Res<SessionConfig>,
+    outcome: Option<Res<SessionOutcome>>,
+    recorded: Option<Res<SoloRecorded>>,
+    challenge: Res<SessionChallenge>,
+    clock: Res<MatchClock>,
+    registry: Res<crate::ai::ModelRegistry>,
+    seats: Query<(&Seat, &SeatRecording, &SeatSnapshot)>,
+    storage: Option<Res<StorageResource>>,
+    mut library: ResMut<ReplayLibrary>,
+    mut scores: ResMut<crate::high_scores::HighScores>,
+) {
+    // A puzzle's scripted start has no place in the replay format, a
+    // practice run is the line left after its undos, not a game, and an
+    // opener drill is a few pieces long: none is kept.
+    if matches!(
+        config.mode,
+        SessionMode::Puzzle { .. } | SessionMode::Practice { .. } | SessionMode::Opener { .. }
+    ) {
+        return;
+    }
+    let mut seats: Vec<_> = seats.iter().collect();
+    seats.sort_by_key(|(seat, ..)| seat.index);
+    let Some((_, _, first)) = seats.first() else {
+        return;
+    };
+    let summary = summary(
+        &config,
+        outcome.as_deref().copied(),
+        &first.0,
+        clock.0,
+        &registry,
+    );
+    let replay = GameReplay {
+        mode: config.mode,
+        summary,
+        seats: seats
+            .iter()
+            .map(|(seat, recording, _)| crate::replays::SeatReplay {
+                participant: config.seats[seat.index.min(1)],
+                game: recording.0.clone(),
+            })
+            .collect(),
+    };
+    // A new first place on a solo board (only human runs rank) is the PB —
+    // on the standard board; a stack challenge's runs keep no PB replay.
+    let best = match config.mode {
+        SessionMode::Solo { variant }
+            if recorded.is_some_and(|r| r.0 == Some(0)) && challenge.0.is_standard() =>
+        {
+            Some(variant)
+        }
+        _ => None,
+    };
+    let change = library.add(replay, best);
+    if let Some(storage) = &storage {
+        library.persist(storage.0.as_ref(), &change);
+    }
+    // The high-score entry names its replay, so the next run can race it.
+    if let Some(variant) = best {
+        scores.set_best_replay(variant, change.added);
+        crate::features::high_scores::persist(&scores, &storage);
+    }
+}
+
+/// The replay list's one-line name for a finished match.
+fn summary(
+    config: &SessionConfig,
+    outcome: Option<SessionOutcome>,
+    first: &EngineSnapshot,
+    seconds: f32,
+    registry: &crate::ai::ModelRegistry,
+) -> String {
+    let time = format!("{}:{:04.1}", (seconds / 60.0) as u32, seconds % 60.0);
+    let label = |seat| super::overlay::seat_label(config, registry, seat);
+    match config.mode {
+        SessionMode::Solo { variant } => {
+            let completed = matches!(outcome, Some(SessionOutcome::Solo { completed: true }));
+            let result = match variant.rules().score_kind() {
+                ScoreKind::Time if completed => time,
+                ScoreKind::Time => format!("{} LINES", first.lines),
+                ScoreKind::Score => format!("SCORE {}", first.score),
+                ScoreKind::Grade => format!(
+                    "GRADE {}",
+                    crate::features::high_scores::grade_label(first.result_level())
+                ),
+            };
+            format!(
+                "{} · {} · {result}",
+                variant.display_name().to_uppercase(),
+                label(0)
+            )
+        }
+        SessionMode::Versus => {
+            let result = match outcome {
+                Some(SessionOutcome::Versus {
+                    winner: Some(seat), ..
+                }) => format!("P{} WINS", seat + 1),
+                Some(SessionOutcome::Disconnected) => "DISCONNECTED".to_string(),
+                _ => "DRAW".to_string(),
+            };
+            format!("{} v {} · {result} · {time}", label(0), label(1))
+        }
+        SessionMode::Puzzle { .. } => format!("PUZZLE · {} · {time}", label(0)),
+        SessionMode::Practice { .. } => format!("PRACTICE · {} · {time}", label(0)),
+        SessionMode::Opener { .. } => format!("OPENER · {} · {time}", label(0)),
+    }
+}
+
+/// Seat a replay instead of a live match: one seat entity per recorded seat,
+/// showing the opening position, with no driver and no engine (the
+/// [`Playhead`] owns those). Called by `session_setup`.
+pub(super) fn seat_playback(world: &mut World) {
+    let playback = world.resource::<SessionPlayback>();
+    let seats: Vec<(EngineSnapshot, SeatStats)> = (0..playback.playhead.replay().seats.len())
+        .map(|seat| {
+            (
+                playback.playhead.snapshot(seat),
+                playback.playhead.stats(seat),
+            )
+        })
+        .collect();
+    info!("replay: {}", playback.playhead.replay().summary);
+    for (index, (snapshot, stats)) in seats.into_iter().enumerate() {
+        world.spawn((
+            Seat { index },
+            SeatSnapshot(snapshot),
+            SeatEvents::default(),
+            stats,
+            DespawnOnExit(GameState::Session),
+        ));
+    }
+    world.insert_resource(MatchClock::default());
+    world.remove_resource::<SessionOutcome>();
+    world.remove_resource::<SoloRecorded>();
+}
+
+/// A replay opens already playing: there is nothing to count down to.
+fn skip_countdown(mut next: ResMut<NextState<SessionPhase>>) {
+    next.set(SessionPhase::Running);
+}
+
+/// Advance the playhead by this slice's share of the playback speed and
+/// publish what it played.
+fn drive_playback(
+    mut playback: ResMut<SessionPlayback>,
+    mut seats: Query<(&Seat, &mut SeatSnapshot, &mut SeatStats, &mut SeatEvents)>,
+    mut clock: ResMut<MatchClock>,
+) {
+    if !playback.playing {
+        return;
+    }
+    playback.carry += playback.speed();
+    while playback.carry >= 1.0 {
+        playback.carry -= 1.0;
+        let Some(events) = playback.playhead.step() else {
+            playback.playing = false;
+            playback.carry = 0.0;
+            break;
+        };
+        for (seat, .., mut seat_events) in &mut seats {
+            if let Some(events) = events.get(seat.index) {
+                seat_events.0.extend(events.iter().cloned());
+            }
+        }
+    }
+    if playback.playhead.at_end() {
+        playback.playing = false;
+    }
+    publish(&playback.playhead, &mut seats, &mut clock);
+}
+
+/// Write the playhead's position onto the seats and the match clock.
+fn publish(
+    playhead: &Playhead,
+    seats: &mut Query<(&Seat, &mut SeatSnapshot, &mut SeatStats, &mut SeatEvents)>,
+    clock: &mut MatchClock,
+) {
+    for (seat, mut snapshot, mut stats, _) in seats {
+        if seat.index < playhead.seats.len() {
+            snapshot.0 = playhead.snapshot(seat.index);
+            *stats = playhead.stats(seat.index);
+        }
+    }
+    clock.0 = playhead.cursor() as f32 * SIM_DT_SECONDS;
+}
+
+/// The viewer's keys: Space plays/pauses, Left/Right step a slice (with
+/// Shift, seek five seconds), Up/Down change speed, Home/End jump to either
+/// end, Esc returns to the replay list.
+fn playback_keys(
+    keys: Res<ButtonInput<KeyCode>>,
+    mut playback: ResMut<SessionPlayback>,
+    mut seats: Query<(&Seat, &mut SeatSnapshot, &mut SeatStats, &mut SeatEvents)>,
+    mut clock: ResMut<MatchClock>,
+    mut next: ResMut<NextState<GameState>>,
+) {
+    if keys.just_pressed(KeyCode::Escape) {
+        next.set(GameState::Replays);
+        return;
+    }
+    if keys.just_pressed(KeyCode::Space) {
+        if playback.playhead.at_end() {
+            playback.playhead.seek(0);
+        }
+        playback.playing = !playback.playing;
+    }
+    if keys.just_pressed(KeyCode::ArrowUp) {
+        playback.speed = (playback.speed + 1).min(SPEEDS.len() - 1);
+    }
+    if keys.just_pressed(KeyCode::ArrowDown) {
+        playback.speed = playback.speed.saturating_sub(1);
+    }
+
+    let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+    let jump = if shift {
+        (SEEK_SECONDS / SIM_DT_SECONDS).round() as usize
+    } else {
+        1
+    };
+    let cursor = playback.playhead.cursor();
+    let target = if keys.just_pressed(KeyCode::ArrowLeft) {
+        Some(cursor.saturating_sub(jump))
+    } else if keys.just_pressed(KeyCode::ArrowRight) {
+        Some(cursor + jump)
+    } else if keys.just_pressed(KeyCode::Home) {
+        Some(0)
+    } else if keys.just_pressed(KeyCode::End) {
+        Some(playback.playhead.frames())
+    } else {
+        None
+    };
+    if let Some(target) = target {
+        // Stepping is for looking: a single-slice step pauses.
+        if !shift {
+            playback.playing = false;
+        }
+        playback.playhead.seek(target);
+        publish(&playback.playhead, &mut seats, &mut clock);
+    }
+}
+
+/// The progress track: the playhead position, clickable and draggable.
+#[derive(Component)]
+struct PlaybackTrack;
+
+/// The filled part of the [`PlaybackTrack`].
+#[derive(Component)]
+struct PlaybackFill;
+
+/// State, speed and time readout.
+#[derive(Component)]
+struct PlaybackText;
+
+fn spawn_playback_bar(mut commands: Commands, assets: Res<GameAssets>) {
+    commands.spawn((
+        Node {
+            position_type: PositionType::Absolute,
+            bottom: px(12),
+            width: percent(100),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: px(6),
+            ..default()
+        },
+        DespawnOnExit(GameState::Session),
+        children![
+            (PlaybackText, label_text("", assets.font_body.clone())),
+            (
+                PlaybackTrack,
+                Button,
+                RelativeCursorPosition::default(),
+                Node {
+                    width: px(TRACK_WIDTH),
+                    height: px(TRACK_HEIGHT),
+                    border: UiRect::all(px(1)),
+                    ..default()
+                },
+                BackgroundColor(theme::GRID),
+                BorderColor::all(theme::FRAME),
+                children![(
+                    PlaybackFill,
+                    Node {
+                        width: percent(0),
+                        height: percent(100),
+                        ..default()
+                    },
+                    BackgroundColor(theme::ACCENT),
+                )],
+            ),
+            label_text(
+                "SPACE PLAY/PAUSE · LEFT/RIGHT STEP · SHIFT SEEK 5S · UP/DOWN SPEED · ESC BACK",
+                assets.font_body.clone(),
+            ),
+        ],
+    ));
+}
+
+/// Press or drag on the track to seek there.
+fn scrub_playback(
+    track: Query<(&Interaction, &RelativeCursorPosition), With<PlaybackTrack>>,
+    mut playback: ResMut<SessionPlayback>,
+    mut seats: Query<(&Seat, &mut SeatSnapshot, &mut SeatStats, &mut SeatEvents)>,
+    mut clock: ResMut<MatchClock>,
+) {
+    for (interaction, cursor) in &track {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(position) = cursor.normalized else {
+            continue;
+        };
+        // `normalized` is centered on the node.
+        let fraction = (position.x + 0.5).clamp(0.0, 1.0);
+        let target = (fraction * playback.playhead.frames() as f32).round() as usize;
+        if target != playback.playhead.cursor() {
+            playback.playhead.seek(target);
+            publish(&playback.playhead, &mut seats, &mut clock);
+        }
+    }
+}
+
+fn update_playback_bar(
+    playback: Res<SessionPlayback>,
+    mut text: Query<&mut Text, With<PlaybackText>>,
+    mut fill: Query<&mut Node, With<PlaybackFill>>,
+) {
+    let playhead = &playback.playhead;
+    let time = |frames: usize| {
+        let seconds = frames as f32 * SIM_DT_SECONDS;
+        format!("{}:{:05.2}", (seconds / 60.0) as u32, seconds % 60.0)
+    };
+    let state = if playback.playing {
+        "PLAYING"
+    } else if playhead.at_end() {
+        "END"
+    } else {
+        "PAUSED"
+    };
+    let readout = format!(
+        "{state} · {}× · {} / {}",
+        playback.speed(),
+        time(playhead.cursor()),
+        time(playhead.frames())
+    );
+    for mut text in &mut text {
+        if text.0 != readout {
+            text.0.clone_from(&readout);
+        }
+    }
+    let fraction = if playhead.frames() == 0 {
+        0.0
+    } else {
+        playhead.cursor() as f32 / playhead.frames() as f32
+    };
+    for mut node in &mut fill {
+        node.width = percent(fraction * 100.0);
+    }
+}
+
+/// Leaving the session ends playback and hands the menus their seating back.
+fn end_playback(
+    mut commands: Commands,
+    playback: Option<Res<SessionPlayback>>,
+    mut config: ResMut<SessionConfig>,
+) {
+    if let Some(playback) = playback {
+        *config = playback.restore;
+        commands.remove_resource::<SessionPlayback>();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::RecordedGame;
+    use crate::ai::analysis::IncomingGarbage;
+    use crate::engine::{EngineConfig, GoalSystem, InputFrame};
+    use crate::replays::SeatReplay;
+    use crate::session::Participant;
+
+    /// A scripted two-seat game long enough to cross several keyframes:
+    /// shifts, turns and drops on a rhythm, with garbage landing on seat 1.
+    fn scripted() -> GameReplay {
+        let inputs: Vec<InputFrame> = (0..2 * KEYFRAME_FRAMES + 137)
+            .map(|i| InputFrame {
+                dt_seconds: SIM_DT_SECONDS,
+                left: i % 11 == 3,
+                right: i % 13 == 5,
+                rotate_clockwise: i % 17 == 0,
+                hard_drop: i % 40 == 39,
+                ..default()
+            })
+            .collect();
+        let game = |seed, garbage| RecordedGame {
+            config: EngineConfig {
+                goal_system: GoalSystem::None,
+                ..EngineConfig::default()
+            },
+            seed,
+            inputs: inputs.clone(),
+            garbage,
+        };
+        GameReplay {
+            mode: SessionMode::Versus,
+            summary: String::new(),
+            seats: vec![
+                SeatReplay {
+                    participant: Participant::Human,
+                    game: game(5, Vec::new()),
+                },
+                SeatReplay {
+                    participant: Participant::Remote,
+                    game: game(
+                        6,
+                        vec![
+                            IncomingGarbage {
+                                after_frame: 80,
+                                lines: 2,
+                            },
+                            IncomingGarbage {
+                                after_frame: 700,
+                                lines: 1,
+                            },
+                        ],
+                    ),
+                },
+            ],
+        }
+    }
+
+    fn positions(playhead: &Playhead) -> Vec<(EngineSnapshot, u32)> {
+        (0..2)
+            .map(|seat| (playhead.snapshot(seat), playhead.stats(seat).garbage_taken))
+            .collect()
+    }
+
+    #[test]
+    fn seeking_lands_where_straight_playback_does() {
+        let replay = scripted();
+        let mut straight = Playhead::new(replay.clone());
+        let mut at = Vec::new();
+        at.push(positions(&straight));
+        while straight.step().is_some() {
+            at.push(positions(&straight));
+        }
+        assert_eq!(straight.cursor(), replay.frames());
+        assert!(straight.step().is_none(), "the end is the end");
+
+        let mut seeker = Playhead::new(replay);
+        for target in [900, 10, 1337, 599, 600, 0, 1200, 5000] {
+            seeker.seek(target);
+            let expected = target.min(at.len() - 1);
+            assert_eq!(seeker.cursor(), expected);
+            assert!(positions(&seeker) == at[expected], "seek to {target}");
+        }
+    }
+}
@@ -9,8 +9,20 @@
 //! * `hold_enabled` / `ghost_enabled` / `background_enabled` — feature toggles
 //!   read by gameplay/render (the last one by the ambient background layer).
 //! * `lock_down_mode` — the engine [`LockDownMode`] used when building the engine.
+//! * `das_ms` / `arr_ms` / `dcd_ms` / `soft_drop_factor` — the handling the
+//!   keyboard controller's [`DasConfig`](crate::player::DasConfig) is built from.
 //! * `music_volume` / `sfx_volume` — 0.0..=1.0, read by the SFX/music features.
-//! * `keybinds` — the action→key map the keyboard controller reads.
+//! * `keybinds` / `keybinds_p2` — the action→key maps the keyboard controller
+//!   reads: Player 1's, and Player 2's for two humans on one keyboard. A
+//!   [`Keymap`] names either; [`GameSettings::keybind_conflicts`] finds keys
+//!   bound twice.
+//! * `gamepad_binds` / `stick_threshold` — the action→button map every pad
+//!   reads, and how far the left stick must lean to count as a direction.
+//! * `touch_scheme` / `touch_layout` — how a touch screen plays (on-screen
+//!   buttons, swipes, or neither) and where the buttons sit.
+//! * `stack_visibility` / `stack_outline` — the memory challenges: a settled
+//!   stack that fades or stays hidden, optionally with its edge outlined.
+//!   [`GameSettings::stack_challenge`] is what a run records and ranks under.
 //!
 //! This type is defined ONCE here so the options feature mutates it and every
 //! reader (engine bridge, previewer, ghost system, SFX) shares one definition.
@@ -29,6 +41,29 @@ use crate::engine::LockDownMode;
 pub const MIN_NEXT_COUNT: usize = 1;
 pub const MAX_NEXT_COUNT: usize = 6;
 
+/// Handling ranges, in milliseconds. ARR and DCD reach zero (ARR 0 slides to
+/// the wall, DCD 0 is off); DAS always waits a little over a frame.
+pub const MIN_DAS_MS: u32 = 20;
+pub const MAX_DAS_MS: u32 = 500;
+pub const MAX_ARR_MS: u32 = 100;
+pub const MAX_DCD_MS: u32 = 200;
+
+/// How far the left stick must lean (0..=1) before it reads as a direction.
+pub const MIN_STICK_THRESHOLD: f32 = 0.2;
+pub const MAX_STICK_THRESHOLD: f32 = 0.9;
+
+/// The on-screen touch buttons' size range, as a scale over the default.
+pub const MIN_TOUCH_SCALE: f32 = 0.6;
+pub const MAX_TOUCH_SCALE: f32 = 1.6;
+
+/// The soft-drop factor range; past the top is "max", a cell every frame.
+pub const MIN_SOFT_DROP_FACTOR: u32 = 5;
+pub const MAX_SOFT_DROP_FACTOR: u32 = 40;
+
+/// How long a locked mino stays on screen under [`StackVisibility::Fading`]
+/// before it is gone. Fixed, so every fading run ranks on equal terms.
+pub const STACK_FADE_SECONDS: f32 = 5.0;
+
 /// A logical, rebindable player action. The keyboard controller maps the bound
 /// [`KeyCode`]s to its raw input each frame; the Options screen rebinds them.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
@@ -71,6 +106,29 @@ impl GameAction {
     }
 }
 
+/// Which of the two local players' [`Keybinds`] a set of keys belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Reflect)]
+pub enum Keymap {
+    /// The lone player's keys, and the left seat's in two-human versus.
+    #[default]
+    P1,
+    /// The right seat's keys in two-human versus.
+    P2,
+}
+
+impl Keymap {
+    /// Both keymaps, in display order.
+    pub const ALL: [Keymap; 2] = [Keymap::P1, Keymap::P2];
+
+    /// Short label ("P1"/"P2") for warnings and HUD text.
+    pub fn label(self) -> &'static str {
+        match self {
+            Keymap::P1 => "P1",
+            Keymap::P2 => "P2",
+        }
+    }
+}
+
 /// Action→keys binding map.
 ///
 /// Each action may bind to a primary and an optional secondary [`KeyCode`]
@@ -114,6 +172,23 @@ impl Default for Keybinds {
 }
 
 impl Keybinds {
+    /// Player 2's defaults: the right hand's IJKL block, clear of every
+    /// Player 1 default so two players share one keyboard out of the box.
+    /// J/L move, K soft drops, I hard drops, U/O rotate CCW/CW, P holds and
+    /// Backspace pauses.
+    pub fn player_two() -> Self {
+        Self {
+            move_left: (KeyCode::KeyJ, None),
+            move_right: (KeyCode::KeyL, None),
+            soft_drop: (KeyCode::KeyK, None),
+            hard_drop: (KeyCode::KeyI, None),
+            rotate_cw: (KeyCode::KeyO, None),
+            rotate_ccw: (KeyCode::KeyU, None),
+            hold: (KeyCode::KeyP, None),
+            pause: (KeyCode::Backspace, None),
+        }
+    }
+
     /// The (primary, secondary) keys bound to `action`.
     pub fn get(&self, action: GameAction) -> (KeyCode, Option<KeyCode>) {
         match action {
@@ -142,6 +217,285 @@ impl Keybinds {
         };
         *slot = (key, None);
     }
+
+    /// Every bound key with its action: primaries and secondaries alike.
+    fn keys(&self) -> impl Iterator<Item = (GameAction, KeyCode)> + '_ {
+        GameAction::ALL.into_iter().flat_map(|action| {
+            let (primary, secondary) = self.get(action);
+            std::iter::once((action, primary)).chain(secondary.map(|key| (action, key)))
+        })
+    }
+}
+
+/// Action→buttons binding map, shared by every pad.
+///
+/// The gamepad mirror of [`Keybinds`]: each action binds a primary and an
+/// optional secondary [`GamepadButton`]. The left stick always mirrors the
+/// D-pad's left/right/down on top of these (see `stick_threshold`).
+#[derive(Debug, Clone, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GamepadBinds {
+    pub move_left: (GamepadButton, Option<GamepadButton>),
+    pub move_right: (GamepadButton, Option<GamepadButton>),
+    pub soft_drop: (GamepadButton, Option<GamepadButton>),
+    pub hard_drop: (GamepadButton, Option<GamepadButton>),
+    pub rotate_cw: (GamepadButton, Option<GamepadButton>),
+    pub rotate_ccw: (GamepadButton, Option<GamepadButton>),
+    pub hold: (GamepadButton, Option<GamepadButton>),
+    pub pause: (GamepadButton, Option<GamepadButton>),
+}
+
+impl Default for GamepadBinds {
+    /// The console layout: D-pad to move, Down soft drops and Up hard drops,
+    /// East/South rotate CW/CCW (B/A on an Xbox pad), either shoulder holds,
+    /// Start pauses.
+    fn default() -> Self {
+        Self {
+            move_left: (GamepadButton::DPadLeft, None),
+            move_right: (GamepadButton::DPadRight, None),
+            soft_drop: (GamepadButton::DPadDown, None),
+            hard_drop: (GamepadButton::DPadUp, None),
+            rotate_cw: (GamepadButton::East, None),
+            rotate_ccw: (GamepadButton::South, None),
+            hold: (
+                GamepadButton::LeftTrigger,
+                Some(GamepadButton::RightTrigger),
+            ),
+            pause: (GamepadButton::Start, None),
+        }
+    }
+}
+
+impl GamepadBinds {
+    /// The (primary, secondary) buttons bound to `action`.
+    pub fn get(&self, action: GameAction) -> (GamepadButton, Option<GamepadButton>) {
+        match action {
+            GameAction::MoveLeft => self.move_left,
+            GameAction::MoveRight => self.move_right,
+            GameAction::SoftDrop => self.soft_drop,
+            GameAction::HardDrop => self.hard_drop,
+            GameAction::RotateCw => self.rotate_cw,
+            GameAction::RotateCcw => self.rotate_ccw,
+            GameAction::Hold => self.hold,
+            GameAction::Pause => self.pause,
+        }
+    }
+
+    /// Rebind `action`'s primary button, clearing the secondary. Used by Options.
+    pub fn set_primary(&mut self, action: GameAction, button: GamepadButton) {
+        let slot = match action {
+            GameAction::MoveLeft => &mut self.move_left,
+            GameAction::MoveRight => &mut self.move_right,
+            GameAction::SoftDrop => &mut self.soft_drop,
+            GameAction::HardDrop => &mut self.hard_drop,
+            GameAction::RotateCw => &mut self.rotate_cw,
+            GameAction::RotateCcw => &mut self.rotate_ccw,
+            GameAction::Hold => &mut self.hold,
+            GameAction::Pause => &mut self.pause,
+        };
+        *slot = (button, None);
+    }
+}
+
+/// How a touch screen drives the game. Either way the pause button stays on
+/// screen; neither scheme shows until the first touch.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Reflect, Serialize, Deserialize)]
+pub enum TouchScheme {
+    /// A button per action, laid out by [`TouchLayout`].
+    #[default]
+    Buttons,
+    /// Drag to shift, flick down to hard drop, tap to rotate.
+    Swipe,
+    /// No touch controls.
+    Off,
+}
+
+impl TouchScheme {
+    pub const ALL: [TouchScheme; 3] = [TouchScheme::Buttons, TouchScheme::Swipe, TouchScheme::Off];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TouchScheme::Buttons => "Buttons",
+            TouchScheme::Swipe => "Swipe",
+            TouchScheme::Off => "Off",
+        }
+    }
+}
+
+/// Where the on-screen buttons sit: each action's center as a fraction of the
+/// window (`(0, 0)` top-left, `(1, 1)` bottom-right), a size scale, and a
+/// left-handed mirror. The defaults fill the bottom third, which a portrait
+/// screen keeps clear of the board.
+#[derive(Debug, Clone, PartialEq, Reflect, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TouchLayout {
+    pub move_left: Vec2,
+    pub move_right: Vec2,
+    pub soft_drop: Vec2,
+    pub hard_drop: Vec2,
+    pub rotate_cw: Vec2,
+    pub rotate_ccw: Vec2,
+    pub hold: Vec2,
+    pub pause: Vec2,
+    /// Button size over the default (clamped to
+    /// `MIN_TOUCH_SCALE..=MAX_TOUCH_SCALE`).
+    pub scale: f32,
+    /// Mirror the layout left to right: movement under the right thumb.
+    pub left_handed: bool,
+}
+
+impl Default for TouchLayout {
+    /// Movement as a cross under the left thumb, the rotations and hold under
+    /// the right, pause in the top corner.
+    fn default() -> Self {
+        Self {
+            move_left: Vec2::new(0.1, 0.8),
+            move_right: Vec2::new(0.34, 0.8),
+            soft_drop: Vec2::new(0.22, 0.9),
+            hard_drop: Vec2::new(0.22, 0.7),
+            rotate_cw: Vec2::new(0.88, 0.76),
+            rotate_ccw: Vec2::new(0.68, 0.86),
+            hold: Vec2::new(0.72, 0.68),
+            pause: Vec2::new(0.93, 0.05),
+            scale: 1.0,
+            left_handed: false,
+        }
+    }
+}
+
+impl TouchLayout {
+    /// Where `action`'s button is centered, with the mirror applied.
+    pub fn center(&self, action: GameAction) -> Vec2 {
+        let center = match action {
+            GameAction::MoveLeft => self.move_left,
+            GameAction::MoveRight => self.move_right,
+            GameAction::SoftDrop => self.soft_drop,
+            GameAction::HardDrop => self.hard_drop,
+            GameAction::RotateCw => self.rotate_cw,
+            GameAction::RotateCcw => self.rotate_ccw,
+            GameAction::Hold => self.hold,
+            GameAction::Pause => self.pause,
+        };
+        if self.left_handed {
+            Vec2::new(1.0 - center.x, center.y)
+        } else {
+            center
+        }
+    }
+
+    fn clamp(&mut self) {
+        self.scale = self.scale.clamp(MIN_TOUCH_SCALE, MAX_TOUCH_SCALE);
+        for center in [
+            &mut self.move_left,
+            &mut self.move_right,
+            &mut self.soft_drop,
+            &mut self.hard_drop,
+            &mut self.rotate_cw,
+            &mut self.rotate_ccw,
+            &mut self.hold,
+            &mut self.pause,
+        ] {
+            *center = center.clamp(Vec2::ZERO, Vec2::ONE);
+        }
+    }
+}
+
+/// How the settled stack is drawn. Anything but `Visible` is a memory
+/// challenge: runs played under one rank on their own boards.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect, Serialize, Deserialize)]
+pub enum StackVisibility {
+    #[default]
+    Visible,
+    /// Each locked mino fades out over [`STACK_FADE_SECONDS`].
+    Fading,
+    /// Locked minos vanish at once and reappear when the game ends.
+    Invisible,
+}
+
+impl StackVisibility {
+    pub const ALL: [StackVisibility; 3] = [
+        StackVisibility::Visible,
+        StackVisibility::Fading,
+        StackVisibility::Invisible,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StackVisibility::Visible => "Visible",
+            StackVisibility::Fading => "Fading",
+            StackVisibility::Invisible => "Invisible",
+        }
+    }
+}
+
+/// The stack challenge a run was played under: what it records and ranks
+/// under. Normalized — the outline only exists on a stack that hides, so
+/// there are exactly [`ALL`](Self::ALL) of them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Reflect)]
+pub struct StackChallenge {
+    pub visibility: StackVisibility,
+    /// The stack's edge stays outlined while its minos are hidden.
+    pub outline: bool,
+}
+
+impl StackChallenge {
+    pub const ALL: [StackChallenge; 5] = [
+        StackChallenge::new(StackVisibility::Visible, false),
+        StackChallenge::new(StackVisibility::Fading, false),
+        StackChallenge::new(StackVisibility::Fading, true),
+        StackChallenge::new(StackVisibility::Invisible, false),
+        StackChallenge::new(StackVisibility::Invisible, true),
+    ];
+
+    /// The ordinary, fully visible stack.
+    pub const STANDARD: StackChallenge = StackChallenge::new(StackVisibility::Visible, false);
+
+    pub const fn new(visibility: StackVisibility, outline: bool) -> Self {
+        Self {
+            visibility,
+            outline: outline && !matches!(visibility, StackVisibility::Visible),
+        }
+    }
+
+    /// Whether this is the ordinary, fully visible stack.
+    pub fn is_standard(self) -> bool {
+        self.visibility == StackVisibility::Visible
+    }
+
+    /// A short stable name (`fading`, `invisible-outline`, …); `None` for
+    /// the standard stack. Persisted, so never renamed.
+    pub fn key(self) -> Option<&'static str> {
+        match (self.visibility, self.outline) {
+            (StackVisibility::Visible, _) => None,
+            (StackVisibility::Fading, false) => Some("fading"),
+            (StackVisibility::Fading, true) => Some("fading-outline"),
+            (StackVisibility::Invisible, false) => Some("invisible"),
+            (StackVisibility::Invisible, true) => Some("invisible-outline"),
+        }
+    }
+
+    /// Inverse of [`key`](Self::key) for the challenges that have one.
+    pub fn from_key(key: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|c| c.key() == Some(key))
+    }
+
+    /// How a board or result names it: `INVISIBLE + OUTLINE`, say.
+    pub fn label(self) -> String {
+        let name = self.visibility.label().to_uppercase();
+        if self.outline {
+            format!("{name} + OUTLINE")
+        } else {
+            name
+        }
+    }
+}
+
+/// One key bound to two action slots, so a press fires both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KeyConflict {
+    pub key: KeyCode,
+    pub first: (Keymap, GameAction),
+    pub second: (Keymap, GameAction),
 }
 
 /// Player-facing settings. The single shared, mutable contract for tunables.
@@ -168,12 +522,36 @@ pub struct GameSettings {
     #[reflect(ignore)]
     #[serde(with = "lock_down_serde")]
     pub lock_down_mode: LockDownMode,
+    /// Delayed auto-shift: how long a held direction waits before it repeats.
+    pub das_ms: u32,
+    /// Auto-repeat rate: the interval between repeats; `0` slides to the wall.
+    pub arr_ms: u32,
+    /// DAS cut delay: how long a charged repeat pauses after a rotation or a
+    /// spawn; `0` is off.
+    pub dcd_ms: u32,
+    /// Soft-drop factor over gravity; `None` is max (a cell every frame).
+    pub soft_drop_factor: Option<u32>,
     /// Music volume, 0.0..=1.0.
     pub music_volume: f32,
     /// Sound-effects volume, 0.0..=1.0.
     pub sfx_volume: f32,
     /// Action→key bindings.
     pub keybinds: Keybinds,
+    /// Player 2's bindings, read when two humans share the keyboard.
+    pub keybinds_p2: Keybinds,
+    /// Action→button bindings for every pad.
+    pub gamepad_binds: GamepadBinds,
+    /// How far the left stick leans before it counts as a press, 0..=1
+    /// (clamped to `MIN_STICK_THRESHOLD..=MAX_STICK_THRESHOLD`).
+    pub stick_threshold: f32,
+    /// How a touch screen plays.
+    pub touch_scheme: TouchScheme,
+    /// The on-screen buttons' placement and size.
+    pub touch_layout: TouchLayout,
+    /// How the settled stack is drawn in a solo run.
+    pub stack_visibility: StackVisibility,
+    /// Outline the stack's edge while its minos are hidden.
+    pub stack_outline: bool,
 }
 
 impl Default for GameSettings {
@@ -184,20 +562,91 @@ impl Default for GameSettings {
             ghost_enabled: true,
             background_enabled: true,
             lock_down_mode: LockDownMode::default(),
+            das_ms: 300,
+            arr_ms: 50,
+            dcd_ms: 0,
+            soft_drop_factor: None,
             music_volume: 0.5,
             sfx_volume: 0.5,
             keybinds: Keybinds::default(),
+            keybinds_p2: Keybinds::player_two(),
+            gamepad_binds: GamepadBinds::default(),
+            stick_threshold: 0.5,
+            touch_scheme: TouchScheme::default(),
+            touch_layout: TouchLayout::default(),
+            stack_visibility: StackVisibility::default(),
+            stack_outline: false,
         }
     }
 }
 
 impl GameSettings {
+    /// The stack challenge a solo run started now plays under.
+    pub fn stack_challenge(&self) -> StackChallenge {
+        StackChallenge::new(self.stack_visibility, self.stack_outline)
+    }
+
+    /// The bindings `keymap` names.
+    pub fn keymap(&self, keymap: Keymap) -> &Keybinds {
+        match keymap {
+            Keymap::P1 => &self.keybinds,
+            Keymap::P2 => &self.keybinds_p2,
+        }
+    }
+
+    /// The bindings `keymap` names, for the rebind flow.
+    pub fn keymap_mut(&mut self, keymap: Keymap) -> &mut Keybinds {
+        match keymap {
+            Keymap::P1 => &mut self.keybinds,
+            Keymap::P2 => &mut self.keybinds_p2,
+        }
+    }
+
+    /// Every key bound to more than one slot across `keymaps`, in binding
+    /// order. The two pause keys may share a key: pausing a local match is
+    /// mutual, so either player's pause does the same thing.
+    pub fn keybind_conflicts(&self, keymaps: &[Keymap]) -> Vec<KeyConflict> {
+        let slots: Vec<(Keymap, GameAction, KeyCode)> = keymaps
+            .iter()
+            .flat_map(|&keymap| {
+                self.keymap(keymap)
+                    .keys()
+                    .map(move |(action, key)| (keymap, action, key))
+            })
+            .collect();
+        let mut conflicts = Vec::new();
+        for (i, &(map_a, action_a, key)) in slots.iter().enumerate() {
+            for &(map_b, action_b, other) in &slots[i + 1..] {
+                let same_slot = map_a == map_b && action_a == action_b;
+                let shared_pause = action_a == GameAction::Pause && action_b == GameAction::Pause;
+                if key == other && !same_slot && !shared_pause {
+                    conflicts.push(KeyConflict {
+                        key,
+                        first: (map_a, action_a),
+                        second: (map_b, action_b),
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+
     /// Clamp every numeric field into its valid range. The options feature calls
     /// this after mutating so out-of-range values never reach gameplay.
     pub fn sanitize(&mut self) {
         self.next_count = self.next_count.clamp(MIN_NEXT_COUNT, MAX_NEXT_COUNT);
         self.music_volume = self.music_volume.clamp(0.0, 1.0);
         self.sfx_volume = self.sfx_volume.clamp(0.0, 1.0);
+        self.das_ms = self.das_ms.clamp(MIN_DAS_MS, MAX_DAS_MS);
+        self.arr_ms = self.arr_ms.min(MAX_ARR_MS);
+        self.dcd_ms = self.dcd_ms.min(MAX_DCD_MS);
+        self.stick_threshold = self
+            .stick_threshold
+            .clamp(MIN_STICK_THRESHOLD, MAX_STICK_THRESHOLD);
+        self.touch_layout.clamp();
+        self.soft_drop_factor = self
+            .soft_drop_factor
+            .map(|sdf| sdf.clamp(MIN_SOFT_DROP_FACTOR, MAX_SOFT_DROP_FACTOR));
         // Retired-default migration: rotate-CW shipped as Up+W before X took
         // the guideline slot. The Options rebind flow only ever writes
         // `(key, None)`, so Up+W in a stored blob is provably our old default
@@ -275,12 +724,58 @@ mod tests {
             next_count: 99,
             music_volume: 2.0,
             sfx_volume: -1.0,
+            das_ms: 0,
+            arr_ms: 900,
+            dcd_ms: 900,
+            soft_drop_factor: Some(1000),
+            stick_threshold: 0.0,
+            touch_layout: TouchLayout {
+                hold: Vec2::new(1.5, -0.2),
+                scale: 9.0,
+                ..TouchLayout::default()
+            },
             ..GameSettings::default()
         };
         settings.sanitize();
         assert_eq!(settings.next_count, MAX_NEXT_COUNT);
         assert_eq!(settings.music_volume, 1.0);
         assert_eq!(settings.sfx_volume, 0.0);
+        assert_eq!(settings.das_ms, MIN_DAS_MS);
+        assert_eq!(settings.arr_ms, MAX_ARR_MS);
+        assert_eq!(settings.dcd_ms, MAX_DCD_MS);
+        assert_eq!(settings.soft_drop_factor, Some(MAX_SOFT_DROP_FACTOR));
+        assert_eq!(settings.stick_threshold, MIN_STICK_THRESHOLD);
+        assert_eq!(settings.touch_layout.hold, Vec2::new(1.0, 0.0));
+        assert_eq!(settings.touch_layout.scale, MAX_TOUCH_SCALE);
+    }
+
+    #[test]
+    fn stack_challenges_normalize_and_round_trip_their_keys() {
+        let visible = GameSettings {
+            stack_outline: true,
+            ..GameSettings::default()
+        };
+        assert_eq!(visible.stack_challenge(), StackChallenge::default());
+        for challenge in StackChallenge::ALL {
+            match challenge.key() {
+                Some(key) => assert_eq!(StackChallenge::from_key(key), Some(challenge)),
+                None => assert!(challenge.is_standard()),
+            }
+        }
+        assert_eq!(StackChallenge::from_key("visible"), None);
+    }
+
+    #[test]
+    fn a_left_handed_layout_mirrors_every_button() {
+        let right = TouchLayout::default();
+        let left = TouchLayout {
+            left_handed: true,
+            ..TouchLayout::default()
+        };
+        for action in GameAction::ALL {
+            let (r, l) = (right.center(action), left.center(action));
+            assert_eq!(l, Vec2::new(1.0 - r.x, r.y), "{action:?}");
+        }
     }
 
     #[test]
@@ -310,6 +805,54 @@ mod tests {
         );
     }
 
+    #[test]
+    fn the_default_keymaps_never_collide() {
+        let settings = GameSettings::default();
+        assert_eq!(settings.keybinds_p2, Keybinds::player_two());
+        assert!(settings.keybind_conflicts(&Keymap::ALL).is_empty());
+    }
+
+    #[test]
+    fn conflicts_are_found_within_and_across_keymaps() {
+        let mut settings = GameSettings::default();
+        // Within P1: hard drop onto the rotate-CW alias.
+        settings
+            .keybinds
+            .set_primary(GameAction::HardDrop, KeyCode::KeyX);
+        assert_eq!(
+            settings.keybind_conflicts(&[Keymap::P1]),
+            vec![KeyConflict {
+                key: KeyCode::KeyX,
+                first: (Keymap::P1, GameAction::HardDrop),
+                second: (Keymap::P1, GameAction::RotateCw),
+            }]
+        );
+        // Across sets: P2's hold on P1's hold key only clashes when both count.
+        settings
+            .keybinds_p2
+            .set_primary(GameAction::Hold, KeyCode::ShiftLeft);
+        assert_eq!(settings.keybind_conflicts(&[Keymap::P2]), vec![]);
+        let both = settings.keybind_conflicts(&Keymap::ALL);
+        assert_eq!(both.len(), 2);
+        assert_eq!(
+            both[1],
+            KeyConflict {
+                key: KeyCode::ShiftLeft,
+                first: (Keymap::P1, GameAction::Hold),
+                second: (Keymap::P2, GameAction::Hold),
+            }
+        );
+    }
+
+    #[test]
+    fn a_shared_pause_key_is_not_a_conflict() {
+        let mut settings = GameSettings::default();
+        settings
+            .keybinds_p2
+            .set_primary(GameAction::Pause, KeyCode::Escape);
+        assert!(settings.keybind_conflicts(&Keymap::ALL).is_empty());
+    }
+
     #[test]
     fn set_primary_replaces_key_and_clears_secondary() {
         let mut binds = Keybinds::default();
@@ -331,6 +874,10 @@ mod tests {
             hold_enabled: false,
             ghost_enabled: false,
             lock_down_mode: LockDownMode::Classic,
+            das_ms: 117,
+            arr_ms: 0,
+            dcd_ms: 17,
+            soft_drop_factor: Some(25),
             music_volume: 0.2,
             sfx_volume: 0.9,
             ..GameSettings::default()
@@ -341,6 +888,16 @@ mod tests {
         settings
             .keybinds
             .set_primary(GameAction::MoveLeft, KeyCode::KeyA);
+        settings
+            .keybinds_p2
+            .set_primary(GameAction::Hold, KeyCode::Semicolon);
+        settings
+            .gamepad_binds
+            .set_primary(GameAction::HardDrop, GamepadButton::North);
+        settings.stick_threshold = 0.7;
+        settings.touch_scheme = TouchScheme::Swipe;
+        settings.touch_layout.left_handed = true;
+        settings.touch_layout.hold = Vec2::new(0.5, 0.5);
 
         let decoded = decode_settings(&encode_settings(&settings)).expect("blob decodes");
         assert_eq!(decoded, settings);
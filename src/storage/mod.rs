@@ -53,6 +53,21 @@ pub mod keys {
     pub const SETTINGS: &str = "settings";
     /// Serialized high-score tables (all variants).
     pub const HIGH_SCORES: &str = "highscores";
+    /// Each player's adaptive-opponent level
+    /// (see `session::adaptive`).
+    pub const ADAPTIVE: &str = "adaptive";
+    /// The replay library's index (see [`replays`](crate::replays)).
+    pub const REPLAYS: &str = "replays";
+    /// Prefix of each stored replay's own key (`replay-<id>`).
+    pub const REPLAY_PREFIX: &str = "replay-";
+    /// The puzzles solved so far (see [`puzzle`](crate::puzzle)).
+    pub const PUZZLES: &str = "puzzles";
+    /// The daily challenge's days: official attempts and boards (see
+    /// [`daily`](crate::daily)).
+    pub const DAILY: &str = "daily";
+    /// Prefix of each board-editor save slot's key (`position-<slot>`, see
+    /// [`editor`](crate::editor)).
+    pub const POSITION_PREFIX: &str = "position-";
 }
 
 /// Construct the platform-appropriate [`Storage`] backend.
@@ -7,6 +7,9 @@
 //! [`focus_activation`] reports Enter (select) and Esc (back) as events the
 //! screen reacts to.
 //!
+//! Gamepads navigate too: the gamepad feature folds every pad into a
+//! [`MenuPad`] each frame, and the helpers read it next to the keyboard.
+//!
 //! Screen plugins (and the options/help/high-scores feature agents) reuse this
 //! so every menu navigates identically.
 
@@ -73,6 +76,19 @@ type FocusRowItem = (
     Option<&'static Children>,
 );
 
+/// This frame's menu edges from the gamepads (any pad): D-pad or left stick
+/// to step, South/Start to select, East to back out. Refreshed in `PreUpdate`
+/// by the gamepad feature; all-false without a pad.
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MenuPad {
+    pub up: bool,
+    pub down: bool,
+    pub left: bool,
+    pub right: bool,
+    pub select: bool,
+    pub back: bool,
+}
+
 /// What the player did on a focused menu this frame.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum NavAction {
@@ -88,7 +104,8 @@ pub enum NavAction {
 /// - **Mouse:** moving the pointer onto a row (or pressing one) moves the cursor
 ///   to it, so the keyboard focus and the pointer agree and a click lands on the
 ///   highlighted item. A pressed row shows the pressed color.
-/// - **Keyboard:** Up/Down (and W/S) move the cursor.
+/// - **Keyboard:** Up/Down (and W/S) move the cursor; so do a pad's D-pad and
+///   left stick ([`MenuPad`]).
 ///
 /// "Most recent input device wins": a hover only claims the cursor on a frame the
 /// pointer actually *moved*. A pointer merely resting over a row must not re-grab
@@ -102,6 +119,7 @@ pub enum NavAction {
 /// marker `M` exists — the only state in which the rows are meaningful.
 pub fn focus_navigation<M: Component>(
     keys: Res<ButtonInput<KeyCode>>,
+    pad: Res<MenuPad>,
     pointer_motion: Res<AccumulatedMouseMotion>,
     mut list: Single<&mut FocusList, With<M>>,
     mut buttons: Query<FocusRowItem>,
@@ -127,10 +145,10 @@ pub fn focus_navigation<M: Component>(
         list.index = index;
     }
 
-    if keys.just_pressed(KeyCode::ArrowDown) || keys.just_pressed(KeyCode::KeyS) {
+    if keys.just_pressed(KeyCode::ArrowDown) || keys.just_pressed(KeyCode::KeyS) || pad.down {
         list.move_by(1);
     }
-    if keys.just_pressed(KeyCode::ArrowUp) || keys.just_pressed(KeyCode::KeyW) {
+    if keys.just_pressed(KeyCode::ArrowUp) || keys.just_pressed(KeyCode::KeyW) || pad.up {
         list.move_by(-1);
     }
 
@@ -158,19 +176,25 @@ pub fn focus_navigation<M: Component>(
     }
 }
 
-/// Detect Enter/Space (select focused) and Esc (back), returning a [`NavAction`]
-/// for the caller to handle against the current [`FocusList`]. Keyboard only;
-/// pair with [`clicked_focusable`] for mouse selection.
+/// Detect Enter/Space (select focused) and Esc (back) — or a pad's
+/// South/Start and East — returning a [`NavAction`] for the caller to handle
+/// against the current [`FocusList`]. Pair with [`clicked_focusable`] for
+/// mouse selection.
 ///
 /// Screens typically wrap this in their own system that matches on the result
 /// and sets `NextState<GameState>` accordingly.
-pub fn read_nav_action(keys: &ButtonInput<KeyCode>, list: &FocusList) -> Option<NavAction> {
-    if keys.just_pressed(KeyCode::Escape) {
+pub fn read_nav_action(
+    keys: &ButtonInput<KeyCode>,
+    pad: &MenuPad,
+    list: &FocusList,
+) -> Option<NavAction> {
+    if keys.just_pressed(KeyCode::Escape) || pad.back {
         return Some(NavAction::Back);
     }
     if keys.just_pressed(KeyCode::Enter)
         || keys.just_pressed(KeyCode::NumpadEnter)
         || keys.just_pressed(KeyCode::Space)
+        || pad.select
     {
         return Some(NavAction::Select(list.index));
     }
@@ -218,6 +242,26 @@ mod tests {
         assert_eq!(list.index, 0);
     }
 
+    #[test]
+    fn a_pad_selects_and_backs_out_like_the_keyboard() {
+        let keys = ButtonInput::<KeyCode>::default();
+        let list = FocusList { index: 2, count: 3 };
+        assert_eq!(read_nav_action(&keys, &MenuPad::default(), &list), None);
+        let select = MenuPad {
+            select: true,
+            ..MenuPad::default()
+        };
+        assert_eq!(
+            read_nav_action(&keys, &select, &list),
+            Some(NavAction::Select(2))
+        );
+        let back = MenuPad {
+            back: true,
+            ..MenuPad::default()
+        };
+        assert_eq!(read_nav_action(&keys, &back, &list), Some(NavAction::Back));
+    }
+
     #[test]
     fn move_by_is_noop_with_no_items() {
         let mut list = FocusList::new(0);
@@ -1,9 +1,9 @@
-//! Game variants: Marathon, Sprint, Ultra.
+//! Game variants: Marathon, Sprint, Ultra, Blitz, Dig and Master.
 //!
 //! A [`Variant`] selects the *rules around* a single game: which
 //! [`EngineConfig`] overrides apply, the goal system, the end condition, the
 //! display name, and which high-score category the result is filed under. The
-//! three variants are implemented inline here (rather than as separate plugins)
+//! variants are implemented inline here (rather than as separate plugins)
 //! because they cross-cut the session HUD (which reads goal/time/score) and
 //! high-scores (which file per category).
 //!
@@ -16,19 +16,24 @@
 //!   calls it every frame while running and completes the run when the
 //!   variant's goal/limit is met (an engine block-/lock-out ends a run
 //!   separately, as a death).
+//! * Dig is one variant per cheese length and flavour, so each gets its own
+//!   board; the cheese itself is the engine's (`EngineConfig::dig`), which
+//!   keeps a dig run replayable from its seed. Blitz's tightening lock delay
+//!   and all-clear bonus are engine config the same way, as is Master's
+//!   level counter, 20G gravity and grade (`EngineConfig::master`).
 
 use bevy::prelude::*;
 
-use crate::engine::{EngineConfig, EngineSnapshot, GoalSystem, MAX_LEVEL};
+use crate::engine::{EngineConfig, EngineSnapshot, GoalSystem, LockDelayCurve};
 
-/// Default Sprint line target (clear N lines as fast as possible).
-pub const DEFAULT_SPRINT_LINES: usize = 40;
-/// Default Ultra time limit in seconds (score as high as possible in 2 minutes).
-pub const DEFAULT_ULTRA_SECONDS: f32 = 120.0;
-/// Marathon ends when the player completes the final level (engine [`MAX_LEVEL`]).
-pub const MARATHON_END_LEVEL: u8 = MAX_LEVEL;
+// The rules themselves are engine-side (`tetr_core::variant`) so the score
+// validator can replay a run against them without linking the game.
+pub use tetr_core::variant::{
+    BLITZ_SECONDS, DEFAULT_SPRINT_LINES, DEFAULT_ULTRA_SECONDS, DIG_LINES, EndCondition,
+    MARATHON_END_LEVEL, ScoreKind, SoloRules,
+};
 
-/// The three single-player modes.
+/// The single-player modes.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
 pub enum Variant {
     /// Endless-style climb to the final level; score-primary.
@@ -37,42 +42,101 @@ pub enum Variant {
     Sprint,
     /// Score as high as possible within a fixed time limit; score-primary.
     Ultra,
+    /// Two minutes of fast levels and a tightening lock delay; score-primary.
+    Blitz,
+    /// Clear `lines` rows of seeded cheese as fast as possible; time-primary.
+    Dig { lines: u32, messy: bool },
+    /// Climb TGM's 0–999 counter into 20G; ranked by grade.
+    Master,
 }
 
 impl Variant {
-    /// All variants in mode-select display order.
-    pub const ALL: [Variant; 3] = [Variant::Marathon, Variant::Sprint, Variant::Ultra];
+    /// All variants in mode-select display order: one per [`SoloRules`].
+    pub const ALL: [Variant; 11] = [
+        Variant::Marathon,
+        Variant::Sprint,
+        Variant::Ultra,
+        Variant::Blitz,
+        Variant::Dig {
+            lines: DIG_LINES[0],
+            messy: false,
+        },
+        Variant::Dig {
+            lines: DIG_LINES[1],
+            messy: false,
+        },
+        Variant::Dig {
+            lines: DIG_LINES[2],
+            messy: false,
+        },
+        Variant::Dig {
+            lines: DIG_LINES[0],
+            messy: true,
+        },
+        Variant::Dig {
+            lines: DIG_LINES[1],
+            messy: true,
+        },
+        Variant::Dig {
+            lines: DIG_LINES[2],
+            messy: true,
+        },
+        Variant::Master,
+    ];
+
+    /// The engine-side rule set this variant plays by.
+    pub fn rules(self) -> SoloRules {
+        match self {
+            Variant::Marathon => SoloRules::Marathon,
+            Variant::Sprint => SoloRules::Sprint,
+            Variant::Ultra => SoloRules::Ultra,
+            Variant::Blitz => SoloRules::Blitz,
+            Variant::Dig { lines, messy } => {
+                SoloRules::Dig(crate::engine::DigConfig { lines, messy })
+            }
+            Variant::Master => SoloRules::Master,
+        }
+    }
 
     /// The full rules contract for this variant.
     pub fn def(self) -> VariantDef {
-        match self {
-            Variant::Marathon => VariantDef {
-                variant: self,
-                display_name: "Marathon",
-                goal_system: GoalSystem::Variable,
-                end_condition: EndCondition::ReachLevel(MARATHON_END_LEVEL),
-                score_kind: ScoreKind::Score,
-                line_target: None,
-                time_limit_seconds: None,
+        let rules = self.rules();
+        let end_condition = rules.end_condition();
+        VariantDef {
+            variant: self,
+            display_name: match self {
+                Variant::Marathon => "Marathon",
+                Variant::Sprint => "Sprint",
+                Variant::Ultra => "Ultra",
+                Variant::Blitz => "Blitz",
+                Variant::Dig { lines, messy } => match (lines, messy) {
+                    (10, false) => "Dig 10",
+                    (18, false) => "Dig 18",
+                    (100, false) => "Dig 100",
+                    (10, true) => "Messy Dig 10",
+                    (18, true) => "Messy Dig 18",
+                    (100, true) => "Messy Dig 100",
+                    (_, false) => "Dig",
+                    (_, true) => "Messy Dig",
+                },
+                Variant::Master => "Master",
             },
-            Variant::Sprint => VariantDef {
-                variant: self,
-                display_name: "Sprint",
-                goal_system: GoalSystem::Fixed,
-                end_condition: EndCondition::ClearLines(DEFAULT_SPRINT_LINES),
-                score_kind: ScoreKind::Time,
-                line_target: Some(DEFAULT_SPRINT_LINES),
-                time_limit_seconds: None,
+            goal_system: rules.goal_system(),
+            end_condition,
+            score_kind: rules.score_kind(),
+            line_target: match end_condition {
+                EndCondition::ClearLines(lines) => Some(lines),
+                _ => None,
             },
-            Variant::Ultra => VariantDef {
-                variant: self,
-                display_name: "Ultra",
-                goal_system: GoalSystem::Fixed,
-                end_condition: EndCondition::TimeLimit(DEFAULT_ULTRA_SECONDS),
-                score_kind: ScoreKind::Score,
-                line_target: None,
-                time_limit_seconds: Some(DEFAULT_ULTRA_SECONDS),
+            time_limit_seconds: match end_condition {
+                EndCondition::TimeLimit(secs) => Some(secs),
+                _ => None,
             },
+            dig: rules.dig(),
+            lock_delay_curve: rules.lock_delay_curve(),
+            perfect_clear_bonus: rules.perfect_clear_bonus(),
+            score_by_minute: self == Variant::Blitz,
+            master: rules.master(),
         }
     }
 
@@ -81,27 +145,6 @@ impl Variant {
     }
 }
 
-/// What makes a variant end (besides the engine's own block-/lock-out, which is
-/// always fatal).
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum EndCondition {
-    /// Marathon: the player completed level `N` (snapshot level reaches it).
-    ReachLevel(u8),
-    /// Sprint: `N` total lines cleared.
-    ClearLines(usize),
-    /// Ultra: `secs` of play elapsed.
-    TimeLimit(f32),
-}
-
-/// Which figure is the "primary" result for ranking on the high-score board.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum ScoreKind {
-    /// Lower is better — rank by elapsed time ascending (Sprint).
-    Time,
-    /// Higher is better — rank by score descending (Marathon, Ultra).
-    Score,
-}
-
 /// The resolved rules for a variant. Returned by [`Variant::def`]; consumed by
 /// the engine bridge, the session HUD, and high-scores.
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -115,16 +158,31 @@ pub struct VariantDef {
     pub line_target: Option<usize>,
     /// Ultra time limit in seconds (None for untimed variants).
     pub time_limit_seconds: Option<f32>,
+    /// Dig's cheese (None outside Dig).
+    pub dig: Option<crate::engine::DigConfig>,
+    /// Blitz tightens the lock delay as the level rises.
+    pub lock_delay_curve: LockDelayCurve,
+    /// Blitz pays a bonus for an all-clear.
+    pub perfect_clear_bonus: bool,
+    /// The result banner breaks the score down per minute of play (Blitz).
+    pub score_by_minute: bool,
+    /// Master's level counter, gravity and grade.
+    pub master: bool,
 }
 
 impl VariantDef {
     /// Apply this variant's overrides onto a base [`EngineConfig`].
     ///
-    /// Currently only the goal system differs per variant; board size,
+    /// The goal system, Dig's cheese, Blitz's lock-delay curve and
+    /// all-clear bonus, and Master's rules differ per variant; board size,
     /// preview count, and lock-down come from `GameSettings`/`LevelConfig`. Kept
     /// as a single seam so future per-variant engine tweaks land here.
     pub fn apply_engine_overrides(&self, config: &mut EngineConfig) {
         config.goal_system = self.goal_system;
+        config.dig = self.dig;
+        config.lock_delay_curve = self.lock_delay_curve;
+        config.perfect_clear_bonus = self.perfect_clear_bonus;
+        config.master = self.master;
     }
 }
 
@@ -146,11 +204,7 @@ pub fn end_condition_met(
     snapshot: &EngineSnapshot,
     elapsed_seconds: f32,
 ) -> bool {
-    match def.end_condition {
-        EndCondition::ReachLevel(level) => snapshot.level >= level,
-        EndCondition::ClearLines(lines) => snapshot.lines >= lines,
-        EndCondition::TimeLimit(limit) => elapsed_seconds >= limit,
-    }
+    def.end_condition.met(snapshot, elapsed_seconds)
 }
 
 #[cfg(test)]
@@ -209,5 +263,38 @@ mod tests {
         assert_eq!(config.goal_system, GoalSystem::Variable);
         Variant::Sprint.def().apply_engine_overrides(&mut config);
         assert_eq!(config.goal_system, GoalSystem::Fixed);
+        assert_eq!(config.dig, None);
+        let dig = Variant::Dig {
+            lines: 18,
+            messy: true,
+        };
+        dig.def().apply_engine_overrides(&mut config);
+        assert_eq!(config.dig, dig.rules().dig());
+    }
+
+    #[test]
+    fn every_variant_has_its_own_rules_and_name() {
+        for (i, a) in Variant::ALL.into_iter().enumerate() {
+            for b in &Variant::ALL[i + 1..] {
+                assert_ne!(a.rules(), b.rules());
+                assert_ne!(a.display_name(), b.display_name());
+            }
+        }
+        assert_eq!(Variant::ALL.len(), SoloRules::ALL.len());
+    }
+
+    #[test]
+    fn dig_is_timed_and_ends_on_clearing_the_cheese() {
+        let def = Variant::Dig {
+            lines: 10,
+            messy: false,
+        }
+        .def();
+        assert_eq!(def.score_kind, ScoreKind::Time);
+        assert_eq!(def.end_condition, EndCondition::ClearCheese);
+        let mut snap = snapshot_with(1, 40);
+        assert!(!end_condition_met(&def, &snap, 0.0));
+        snap.dig_remaining = Some(0);
+        assert!(end_condition_met(&def, &snap, 0.0));
     }
 }
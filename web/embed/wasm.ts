@@ -17,6 +17,10 @@ export interface WasmGame extends GameView {
   is_human(): boolean;
   key_down(action: number): void;
   key_up(action: number): void;
+  /** DAS / ARR / DCD in ms and the soft-drop factor (`0` = max); see the Rust docs. */
+  set_handling(dasMs: number, arrMs: number, dcdMs: number, sdf: number): void;
+  /** Key the bot's placements at a person's pace (DAS, jittered intervals). */
+  set_human_pace(on: boolean): void;
   reset(seed: number): void;
   active_piece(): number;
   back_to_back(): boolean;